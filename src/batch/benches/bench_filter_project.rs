@@ -0,0 +1,222 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares `FilterExecutor` and `ProjectionExecutor` driven by the row-by-row
+//! `BinaryExpression` with the same executors driven by the kernel-based expressions.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use risingwave_batch::executor::{BoxedExecutor, Executor, FilterExecutor, ProjectionExecutor};
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{BoolArray, DataChunk, I64Array};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_expr::expr::template::BinaryExpression;
+use risingwave_expr::expr::{build_from_prost, BoxedExpression, InputRefExpression};
+use risingwave_expr::vector_op::arithmetic_op::general_add;
+use risingwave_expr::vector_op::cmp::general_lt;
+use risingwave_pb::data::data_type::TypeName;
+use risingwave_pb::data::DataType as ProstDataType;
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::{ExprNode, FunctionCall, InputRefExpr};
+use tokio::runtime::Runtime;
+
+const CHUNK_SIZE: usize = 1024;
+const CHUNK_NUM: usize = 64;
+
+/// Yields the same chunks that are prepared in advance.
+struct ChunkSource {
+    chunks: VecDeque<DataChunk>,
+    schema: Schema,
+}
+
+#[async_trait::async_trait]
+impl Executor for ChunkSource {
+    async fn open(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        Ok(self.chunks.pop_front())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        "ChunkSource"
+    }
+}
+
+fn make_chunk(with_nulls: bool, half_visible: bool) -> DataChunk {
+    let lhs = (0..CHUNK_SIZE as i64)
+        .map(|i| {
+            if with_nulls && i % 7 == 0 {
+                None
+            } else {
+                Some(i)
+            }
+        })
+        .collect::<Vec<_>>();
+    let rhs = (0..CHUNK_SIZE as i64)
+        .map(|i| Some(CHUNK_SIZE as i64 - i))
+        .collect::<Vec<_>>();
+    let chunk = DataChunk::builder()
+        .columns(vec![
+            Column::new(Arc::new(I64Array::from_slice(&lhs).unwrap().into())),
+            Column::new(Arc::new(I64Array::from_slice(&rhs).unwrap().into())),
+        ])
+        .build();
+    if half_visible {
+        // Half of the rows are filtered out by an upstream filter.
+        chunk.with_visibility(Bitmap::from_bool_slice(
+            &(0..CHUNK_SIZE).map(|i| i % 2 == 0).collect::<Vec<_>>(),
+        ))
+    } else {
+        chunk
+    }
+}
+
+fn make_source(chunk: &DataChunk) -> BoxedExecutor {
+    Box::new(ChunkSource {
+        chunks: (0..CHUNK_NUM).map(|_| chunk.clone()).collect(),
+        schema: Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]),
+    })
+}
+
+fn make_input_ref(idx: i32) -> ExprNode {
+    ExprNode {
+        expr_type: Type::InputRef as i32,
+        return_type: Some(ProstDataType {
+            type_name: TypeName::Int64 as i32,
+            ..Default::default()
+        }),
+        rex_node: Some(RexNode::InputRef(InputRefExpr { column_idx: idx })),
+    }
+}
+
+fn make_vectorized_expr(kind: Type, ret: TypeName) -> BoxedExpression {
+    build_from_prost(&ExprNode {
+        expr_type: kind as i32,
+        return_type: Some(ProstDataType {
+            type_name: ret as i32,
+            ..Default::default()
+        }),
+        rex_node: Some(RexNode::FuncCall(FunctionCall {
+            children: vec![make_input_ref(0), make_input_ref(1)],
+        })),
+    })
+    .unwrap()
+}
+
+fn make_row_wise_lt() -> BoxedExpression {
+    Box::new(BinaryExpression::<I64Array, I64Array, BoolArray, _>::new(
+        Box::new(InputRefExpression::new(DataType::Int64, 0)),
+        Box::new(InputRefExpression::new(DataType::Int64, 1)),
+        DataType::Boolean,
+        general_lt::<i64, i64, i64>,
+    ))
+}
+
+fn make_row_wise_add() -> BoxedExpression {
+    Box::new(BinaryExpression::<I64Array, I64Array, I64Array, _>::new(
+        Box::new(InputRefExpression::new(DataType::Int64, 0)),
+        Box::new(InputRefExpression::new(DataType::Int64, 1)),
+        DataType::Int64,
+        general_add::<i64, i64, i64>,
+    ))
+}
+
+async fn execute(mut executor: BoxedExecutor) {
+    executor.open().await.unwrap();
+    while let Some(chunk) = executor.next().await.unwrap() {
+        criterion::black_box(chunk);
+    }
+    executor.close().await.unwrap();
+}
+
+fn bench_filter(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("filter");
+    let make_exprs: [(&str, fn() -> BoxedExpression); 2] = [
+        ("row_wise", make_row_wise_lt),
+        ("vectorized", || {
+            make_vectorized_expr(Type::LessThan, TypeName::Boolean)
+        }),
+    ];
+    for with_nulls in [false, true] {
+        let chunk = make_chunk(with_nulls, false);
+        for (name, make_expr) in make_exprs {
+            group.bench_with_input(BenchmarkId::new(name, with_nulls), &chunk, |b, chunk| {
+                b.iter_batched(
+                    || {
+                        Box::new(FilterExecutor::new(
+                            make_expr(),
+                            make_source(chunk),
+                            "FilterExecutor".to_string(),
+                        )) as BoxedExecutor
+                    },
+                    |executor| rt.block_on(execute(executor)),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_project(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("project");
+    let make_exprs: [(&str, fn() -> BoxedExpression); 2] = [
+        ("row_wise", make_row_wise_add),
+        ("vectorized", || {
+            make_vectorized_expr(Type::Add, TypeName::Int64)
+        }),
+    ];
+    for (visibility, half_visible) in [("all_visible", false), ("half_visible", true)] {
+        let chunk = make_chunk(true, half_visible);
+        for (name, make_expr) in make_exprs {
+            group.bench_with_input(BenchmarkId::new(name, visibility), &chunk, |b, chunk| {
+                b.iter_batched(
+                    || {
+                        Box::new(ProjectionExecutor::new(
+                            vec![make_expr()],
+                            make_source(chunk),
+                            "ProjectionExecutor".to_string(),
+                        )) as BoxedExecutor
+                    },
+                    |executor| rt.block_on(execute(executor)),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_filter, bench_project);
+criterion_main!(benches);
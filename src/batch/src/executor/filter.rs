@@ -26,7 +26,7 @@ use risingwave_pb::plan::plan_node::NodeBody;
 use super::{BoxedExecutor, BoxedExecutorBuilder};
 use crate::executor::{Executor, ExecutorBuilder};
 
-pub(super) struct FilterExecutor {
+pub struct FilterExecutor {
     expr: BoxedExpression,
     child: BoxedExecutor,
     chunk_builder: DataChunkBuilder,
@@ -85,6 +85,19 @@ impl Executor for FilterExecutor {
 }
 
 impl FilterExecutor {
+    pub fn new(expr: BoxedExpression, child: BoxedExecutor, identity: String) -> Self {
+        let chunk_builder =
+            DataChunkBuilder::new(child.schema().data_types(), DEFAULT_CHUNK_BUFFER_SIZE);
+        Self {
+            expr,
+            child,
+            chunk_builder,
+            last_input: None,
+            identity,
+            child_can_be_nexted: true,
+        }
+    }
+
     /// Fetch one chunk from child.
     async fn fetch_one_chunk(&mut self) -> Result<Option<DataChunk>> {
         if self.child_can_be_nexted {
@@ -122,19 +135,9 @@ impl BoxedExecutorBuilder for FilterExecutor {
         if let Some(child_plan) = source.plan_node.get_children().get(0) {
             let child = source.clone_for_plan(child_plan).build()?;
             debug!("Child schema: {:?}", child.schema());
-            let chunk_builder =
-                DataChunkBuilder::new(child.schema().data_types(), DEFAULT_CHUNK_BUFFER_SIZE);
 
             return Ok(Box::new(
-                Self {
-                    expr,
-                    child,
-                    chunk_builder,
-                    last_input: None,
-                    identity: source.plan_node().get_identity().clone(),
-                    child_can_be_nexted: true,
-                }
-                .fuse(),
+                Self::new(expr, child, source.plan_node().get_identity().clone()).fuse(),
             ));
         }
         Err(InternalError("Filter must have one children".to_string()).into())
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::{mem, vec};
@@ -19,6 +20,7 @@ use std::{mem, vec};
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::Result;
 use risingwave_common::hash::{
@@ -30,12 +32,16 @@ use risingwave_expr::vector_op::agg::{AggStateFactory, BoxedAggState};
 use risingwave_pb::plan::plan_node::NodeBody;
 use risingwave_pb::plan::HashAggNode;
 
+use super::spill::{PartitionedSpill, SpillContext, SpilledPartition, MAX_SPILL_DEPTH};
 use super::{BoxedExecutorBuilder, Executor, ExecutorBuilder};
 use crate::executor::BoxedExecutor;
-use crate::task::TaskId;
+use crate::task::{MemoryReservation, TaskId};
 
 type AggHashMap<K> = HashMap<K, Vec<BoxedAggState>, PrecomputedBuildHasher>;
 
+/// Estimated memory size of an aggregation state.
+const ESTIMATED_AGG_STATE_SIZE: usize = 64;
+
 struct HashAggExecutorBuilderDispatcher;
 
 /// A dispatcher to help create specialized hash agg executor.
@@ -55,6 +61,7 @@ pub(super) struct HashAggExecutorBuilder {
     group_key_types: Vec<DataType>,
     schema: Schema,
     task_id: TaskId,
+    spill_context: SpillContext,
     identity: String,
 }
 
@@ -63,6 +70,7 @@ impl HashAggExecutorBuilder {
         hash_agg_node: &HashAggNode,
         child: BoxedExecutor,
         task_id: TaskId,
+        spill_context: SpillContext,
         identity: String,
     ) -> Result<BoxedExecutor> {
         let group_key_columns = hash_agg_node
@@ -100,6 +108,7 @@ impl HashAggExecutorBuilder {
             group_key_types,
             schema: Schema { fields },
             task_id,
+            spill_context,
             identity,
         };
 
@@ -123,11 +132,22 @@ impl BoxedExecutorBuilder for HashAggExecutorBuilder {
         )?;
 
         let identity = source.plan_node().get_identity().clone();
-        Self::deserialize(hash_agg_node, child, source.task_id.clone(), identity)
+        Self::deserialize(
+            hash_agg_node,
+            child,
+            source.task_id.clone(),
+            SpillContext::from_builder(source),
+            identity,
+        )
     }
 }
 
 /// `HashAggExecutor` implements the hash aggregate algorithm.
+///
+/// Once the groups exceed the memory budget of the task, rows of new groups are spilled to disk,
+/// partitioned by their group keys, while the groups in memory keep being aggregated. After the
+/// groups in memory are output, each spilled partition is aggregated in memory in turn, and its
+/// rows of groups that don't fit are partitioned again.
 pub(super) struct HashAggExecutor<K> {
     /// factories to construct aggregator for each groups
     agg_factories: Vec<AggStateFactory>,
@@ -141,6 +161,13 @@ pub(super) struct HashAggExecutor<K> {
     result: Option<<AggHashMap<K> as IntoIterator>::IntoIter>,
     /// the data types of key columns
     group_key_types: Vec<DataType>,
+    spill_context: SpillContext,
+    /// memory reserved for `groups`
+    reservation: MemoryReservation,
+    /// whether rows of new groups are spilled
+    spilling: bool,
+    /// spilled partitions to be aggregated
+    spilled_partitions: Vec<SpilledPartition>,
     schema: Schema,
     identity: String,
 }
@@ -154,49 +181,133 @@ impl<K> HashAggExecutor<K> {
             groups: AggHashMap::<K>::default(),
             group_key_types: builder.group_key_types,
             result: None,
+            reservation: builder.spill_context.reservation(),
+            spill_context: builder.spill_context,
+            spilling: false,
+            spilled_partitions: vec![],
             schema: builder.schema,
             identity: builder.identity,
         }
     }
 }
 
-#[async_trait::async_trait]
-impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
-    async fn open(&mut self) -> Result<()> {
-        self.child.open().await?;
+impl<K: HashKey> HashAggExecutor<K> {
+    fn estimated_group_size(&self) -> usize {
+        mem::size_of::<K>()
+            + mem::size_of::<Vec<BoxedAggState>>()
+            + self.agg_factories.len() * ESTIMATED_AGG_STATE_SIZE
+    }
 
-        while let Some(chunk) = self.child.next().await? {
-            let chunk = chunk.compact()?;
-            let keys = K::build(self.group_key_columns.as_slice(), &chunk)?;
-            for (row_id, key) in keys.into_iter().enumerate() {
-                let mut err_flag = Ok(());
-                let states: &mut Vec<BoxedAggState> = self.groups.entry(key).or_insert_with(|| {
-                    self.agg_factories
+    /// Aggregates the rows of `chunk` into `groups`. If `can_spill`, rows of new groups are left
+    /// out once the memory budget is exceeded, and the visibility of these rows is returned.
+    fn aggregate_chunk(&mut self, chunk: &DataChunk, can_spill: bool) -> Result<Option<Bitmap>> {
+        let group_size = self.estimated_group_size();
+        let keys = K::build(self.group_key_columns.as_slice(), chunk)?;
+        let mut spilled_rows = vec![false; keys.len()];
+        let mut has_spilled = false;
+        for (row_id, key) in keys.into_iter().enumerate() {
+            // A single group always fits in memory.
+            let must_fit = !can_spill || self.groups.is_empty();
+            let states = match self.groups.entry(key) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    if must_fit {
+                        self.reservation.force_grow(group_size);
+                    } else if self.spilling || !self.reservation.try_grow(group_size) {
+                        self.spilling = true;
+                        spilled_rows[row_id] = true;
+                        has_spilled = true;
+                        continue;
+                    }
+                    let states = self
+                        .agg_factories
                         .iter()
                         .map(AggStateFactory::create_agg_state)
-                        .collect::<Result<Vec<_>>>()
-                        .unwrap_or_else(|x| {
-                            err_flag = Err(x);
-                            vec![]
-                        })
-                });
-                err_flag?;
-
-                // TODO: currently not a vectorized implementation
-                states
-                    .iter_mut()
-                    .for_each(|state| state.update_with_row(&chunk, row_id).unwrap());
+                        .collect::<Result<Vec<_>>>()?;
+                    entry.insert(states)
+                }
+            };
+
+            // TODO: currently not a vectorized implementation
+            states
+                .iter_mut()
+                .try_for_each(|state| state.update_with_row(chunk, row_id))?;
+        }
+
+        if has_spilled {
+            Ok(Some(Bitmap::try_from(spilled_rows)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Aggregates the rows of `chunk` at `depth` into `groups`, and spills the rows of groups that
+    /// don't fit into `spill`.
+    async fn aggregate_or_spill(
+        &mut self,
+        chunk: DataChunk,
+        depth: usize,
+        spill: &mut Option<PartitionedSpill>,
+    ) -> Result<()> {
+        let can_spill = depth < MAX_SPILL_DEPTH;
+        if let Some(spilled_rows) = self.aggregate_chunk(&chunk, can_spill)? {
+            if spill.is_none() {
+                *spill = Some(
+                    PartitionedSpill::new(
+                        &self.spill_context,
+                        self.group_key_columns.clone(),
+                        depth,
+                    )
+                    .await?,
+                );
             }
+            spill
+                .as_mut()
+                .unwrap()
+                .write_chunk(chunk.with_visibility(spilled_rows))
+                .await?;
         }
+        Ok(())
+    }
 
+    /// Outputs the groups in memory, and queues the partitions of `spill` to be aggregated after.
+    async fn finish_aggregate(&mut self, spill: Option<PartitionedSpill>) -> Result<()> {
+        if let Some(spill) = spill {
+            self.spilled_partitions
+                .extend(spill.into_partitions().await?);
+        }
+        self.spilling = false;
         assert!(self.result.is_none());
         self.result = Some(mem::take(&mut self.groups).into_iter());
-
         Ok(())
     }
 
+    /// Aggregates a spilled partition in memory and outputs its groups.
+    async fn aggregate_partition(&mut self, mut partition: SpilledPartition) -> Result<()> {
+        let mut spill = None;
+        while let Some(chunk) = partition.reader.read_chunk().await? {
+            self.aggregate_or_spill(chunk, partition.depth, &mut spill)
+                .await?;
+        }
+        self.finish_aggregate(spill).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
+    async fn open(&mut self) -> Result<()> {
+        self.child.open().await?;
+
+        let mut spill = None;
+        while let Some(chunk) = self.child.next().await? {
+            self.aggregate_or_spill(chunk.compact()?, 0, &mut spill)
+                .await?;
+        }
+        self.finish_aggregate(spill).await
+    }
+
     async fn next(&mut self) -> Result<Option<DataChunk>> {
-        if let Some(res) = self.result.as_mut() {
+        while let Some(res) = self.result.as_mut() {
             let cardinality = DEFAULT_CHUNK_BUFFER_SIZE;
             let mut group_builders = self
                 .group_key_types
@@ -225,7 +336,11 @@ impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
             }
             if !has_next {
                 self.result = None;
-                return Ok(None);
+                self.reservation.clear();
+                if let Some(partition) = self.spilled_partitions.pop() {
+                    self.aggregate_partition(partition).await?;
+                }
+                continue;
             }
 
             let columns = group_builders
@@ -257,7 +372,7 @@ impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
 
 #[cfg(test)]
 mod tests {
-    use risingwave_common::array::{I32Array, I64Array};
+    use risingwave_common::array::{Array, I32Array, I64Array};
     use risingwave_common::array_nonnull;
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_pb::data::data_type::TypeName;
@@ -308,6 +423,7 @@ mod tests {
                 ..Default::default()
             }),
             distinct: false,
+            ..Default::default()
         };
 
         let agg_prost = HashAggNode {
@@ -319,6 +435,7 @@ mod tests {
             &agg_prost,
             Box::new(src_exec),
             TaskId::default(),
+            SpillContext::for_test(usize::MAX),
             "HashAggExecutor".to_string(),
         )
         .unwrap();
@@ -368,6 +485,7 @@ mod tests {
                 ..Default::default()
             }),
             distinct: false,
+            ..Default::default()
         };
 
         let agg_prost = HashAggNode {
@@ -379,6 +497,7 @@ mod tests {
             &agg_prost,
             Box::new(src_exec),
             TaskId::default(),
+            SpillContext::for_test(usize::MAX),
             "HashAggExecutor".to_string(),
         )
         .unwrap();
@@ -393,4 +512,68 @@ mod tests {
         );
         diff_executor_output(actual_exec, Box::new(expect_exec)).await;
     }
+
+    #[tokio::test]
+    async fn execute_with_spilling() {
+        // Only a few groups fit in memory.
+        execute_count_with_budget(1024).await;
+        // Only the first group of each partition fits in memory, so partitions are spilled again
+        // until the depth limit.
+        execute_count_with_budget(0).await;
+    }
+
+    async fn execute_count_with_budget(budget: usize) {
+        let t32 = DataType::Int32;
+        let keys = (0..100).map(Some).collect_vec();
+        let mut src_exec = MockExecutor::new(Schema {
+            fields: vec![Field::unnamed(t32.clone())],
+        });
+        for _ in 0..3 {
+            let col = Column::new(Arc::new(I32Array::from_slice(&keys).unwrap().into()));
+            src_exec.add(DataChunk::builder().columns(vec![col]).build());
+        }
+
+        let agg_call = AggCall {
+            r#type: Type::Count as i32,
+            args: vec![],
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Int64 as i32,
+                ..Default::default()
+            }),
+            distinct: false,
+            ..Default::default()
+        };
+        let agg_prost = HashAggNode {
+            group_keys: vec![0],
+            agg_calls: vec![agg_call],
+        };
+        let spill_context = SpillContext::for_test(budget);
+        let mut actual_exec = HashAggExecutorBuilder::deserialize(
+            &agg_prost,
+            Box::new(src_exec),
+            TaskId::default(),
+            spill_context,
+            "HashAggExecutor".to_string(),
+        )
+        .unwrap();
+
+        actual_exec.open().await.unwrap();
+        let mut groups = vec![];
+        while let Some(chunk) = actual_exec.next().await.unwrap() {
+            let chunk = chunk.compact().unwrap();
+            let group_col = chunk.column_at(0).array();
+            let count_col = chunk.column_at(1).array();
+            groups.extend(
+                group_col
+                    .as_int32()
+                    .iter()
+                    .zip_eq(count_col.as_int64().iter())
+                    .map(|(group, count)| (group.unwrap(), count.unwrap())),
+            );
+        }
+        actual_exec.close().await.unwrap();
+
+        groups.sort_unstable();
+        assert_eq!(groups, (0..100).map(|key| (key, 3)).collect_vec());
+    }
 }
@@ -15,6 +15,7 @@
 use std::convert::TryInto;
 use std::mem::take;
 
+use itertools::Itertools;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::Result;
@@ -26,8 +27,12 @@ use risingwave_pb::plan::plan_node::NodeBody;
 
 use crate::executor::join::hash_join_state::{BuildTable, ProbeTable};
 use crate::executor::join::JoinType;
+use crate::executor::spill::{
+    estimate_chunk_size, PartitionedSpill, SpillContext, SpillReaderExecutor, SpilledPartition,
+    MAX_SPILL_DEPTH,
+};
 use crate::executor::{BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder};
-use crate::task::TaskId;
+use crate::task::{MemoryReservation, TaskId};
 
 /// Parameters of equi-join.
 ///
@@ -94,12 +99,22 @@ impl<K> Default for HashJoinState<K> {
     }
 }
 
+/// Hash join executor.
+///
+/// Once the build side exceeds the memory budget of the task, it falls back to grace hash join:
+/// both sides are spilled to disk, partitioned by their join keys, and each pair of partitions is
+/// joined in memory in turn. A pair whose build side still exceeds the budget is partitioned again.
 pub(super) struct HashJoinExecutor<K> {
-    /// Probe side
+    /// Probe side, replaced by the probe side of the current partition after spilling.
     left_child: BoxedExecutor,
     /// Build side
     right_child: BoxedExecutor,
     state: HashJoinState<K>,
+    spill_context: SpillContext,
+    /// Memory reserved for the build side.
+    reservation: MemoryReservation,
+    /// Spilled partitions of build side and probe side to be joined.
+    partitions: Vec<(SpilledPartition, SpilledPartition)>,
     schema: Schema,
     identity: String,
 }
@@ -201,16 +216,124 @@ impl<K: HashKey + Send + Sync> Executor for HashJoinExecutor<K> {
 impl<K: HashKey> HashJoinExecutor<K> {
     async fn build(&mut self, mut build_table: BuildTable) -> Result<()> {
         self.right_child.open().await?;
+        let mut build_spill = None;
         while let Some(chunk) = self.right_child.next().await? {
-            build_table.append_build_chunk(chunk)?;
+            self.build_or_spill(&mut build_table, chunk, 0, &mut build_spill)
+                .await?;
         }
 
-        let probe_table = build_table.try_into()?;
+        self.state = match build_spill {
+            Some(build_spill) => {
+                self.spill_probe_side(build_table.params(), 0, build_spill)
+                    .await?;
+                self.next_partition(build_table).await?
+            }
+            None => HashJoinState::FirstProbe(build_table.try_into()?),
+        };
+        Ok(())
+    }
 
-        self.state = HashJoinState::FirstProbe(probe_table);
+    /// Appends `chunk` of build side at `depth` to `build_table`. Once the budget is exceeded, the
+    /// rows in `build_table` and the rows after are spilled into `build_spill` instead.
+    async fn build_or_spill(
+        &mut self,
+        build_table: &mut BuildTable,
+        chunk: DataChunk,
+        depth: usize,
+        build_spill: &mut Option<PartitionedSpill>,
+    ) -> Result<()> {
+        if let Some(build_spill) = build_spill.as_mut() {
+            build_spill.write_chunk(chunk).await?;
+        } else if depth >= MAX_SPILL_DEPTH {
+            self.reservation.force_grow(estimate_chunk_size(&chunk));
+            build_table.append_build_chunk(chunk)?;
+        } else if self.reservation.try_grow(estimate_chunk_size(&chunk)) {
+            build_table.append_build_chunk(chunk)?;
+        } else {
+            let mut spill = PartitionedSpill::new(
+                &self.spill_context,
+                build_table.params().build_key_columns().to_vec(),
+                depth,
+            )
+            .await?;
+            for build_chunk in build_table.take_build_data() {
+                spill.write_chunk(build_chunk).await?;
+            }
+            spill.write_chunk(chunk).await?;
+            self.reservation.clear();
+            *build_spill = Some(spill);
+        }
         Ok(())
     }
 
+    /// Spills the probe side in the left child at `depth` like the build side in `build_spill`, and
+    /// queues the pairs of partitions to be joined.
+    async fn spill_probe_side(
+        &mut self,
+        params: &EquiJoinParams,
+        depth: usize,
+        build_spill: PartitionedSpill,
+    ) -> Result<()> {
+        let mut probe_spill = PartitionedSpill::new(
+            &self.spill_context,
+            params.probe_key_columns().to_vec(),
+            depth,
+        )
+        .await?;
+        self.left_child.open().await?;
+        while let Some(chunk) = self.left_child.next().await? {
+            probe_spill.write_chunk(chunk).await?;
+        }
+        self.left_child.close().await?;
+        self.partitions.extend(
+            build_spill
+                .into_partitions()
+                .await?
+                .into_iter()
+                .zip_eq(probe_spill.into_partitions().await?),
+        );
+        Ok(())
+    }
+
+    /// Builds the table of the next spilled partition, and makes its probe side the left child.
+    /// Partitions whose build side exceeds the budget are partitioned again. `build_table` must be
+    /// empty.
+    async fn next_partition(&mut self, mut build_table: BuildTable) -> Result<HashJoinState<K>> {
+        self.reservation.clear();
+        while let Some((mut build_partition, probe_partition)) = self.partitions.pop() {
+            if build_partition.reader.cardinality() == 0
+                && probe_partition.reader.cardinality() == 0
+            {
+                continue;
+            }
+            let depth = build_partition.depth;
+            let mut build_spill = None;
+            while let Some(chunk) = build_partition.reader.read_chunk().await? {
+                self.build_or_spill(&mut build_table, chunk, depth, &mut build_spill)
+                    .await?;
+            }
+            self.left_child = Box::new(SpillReaderExecutor::new(
+                probe_partition.reader,
+                self.left_child.schema().clone(),
+                format!("{}ProbeSpill", self.identity),
+            ));
+            match build_spill {
+                Some(build_spill) => {
+                    self.spill_probe_side(build_table.params(), depth, build_spill)
+                        .await?;
+                }
+                None => return Ok(HashJoinState::FirstProbe(build_table.try_into()?)),
+            }
+        }
+        Ok(HashJoinState::Done)
+    }
+
+    /// Moves on to the next spilled partition if any, after `probe_table` is done.
+    async fn finish_probe(&mut self, probe_table: ProbeTable<K>) -> Result<HashJoinState<K>> {
+        self.next_partition(BuildTable::with_params(probe_table.into_params()))
+            .await
+    }
+
     async fn probe(
         &mut self,
         first_probe: bool,
@@ -224,7 +347,13 @@ impl<K: HashKey> HashJoinExecutor<K> {
                     probe_table.set_probe_data(data_chunk)?;
                 }
                 None => {
-                    self.state = HashJoinState::Done;
+                    // Build side rows may still be output, e.g. for a spilled partition with an
+                    // empty probe side.
+                    if probe_table.join_type().need_join_remaining() {
+                        self.state = HashJoinState::ProbeRemaining(probe_table);
+                    } else {
+                        self.state = self.finish_probe(probe_table).await?;
+                    }
                     return Ok(None);
                 }
             }
@@ -270,7 +399,7 @@ impl<K: HashKey> HashJoinExecutor<K> {
                         if probe_table.join_type().need_join_remaining() {
                             self.state = HashJoinState::ProbeRemaining(probe_table);
                         } else {
-                            self.state = HashJoinState::Done;
+                            self.state = self.finish_probe(probe_table).await?;
                         }
                         return Ok(output_data_chunk);
                     }
@@ -293,7 +422,7 @@ impl<K: HashKey> HashJoinExecutor<K> {
             let ret_data_chunk = probe_table.consume_left()?;
             let output_data_chunk = probe_table.remove_null_columns_for_semi_anti(ret_data_chunk);
 
-            self.state = HashJoinState::Done;
+            self.state = self.finish_probe(probe_table).await?;
             output_data_chunk
         };
         Ok(Some(output_data_chunk))
@@ -305,6 +434,7 @@ impl<K> HashJoinExecutor<K> {
         left_child: BoxedExecutor,
         right_child: BoxedExecutor,
         params: EquiJoinParams,
+        spill_context: SpillContext,
         schema: Schema,
         identity: String,
     ) -> Self {
@@ -312,6 +442,9 @@ impl<K> HashJoinExecutor<K> {
             left_child,
             right_child,
             state: HashJoinState::Build(BuildTable::with_params(params)),
+            reservation: spill_context.reservation(),
+            spill_context,
+            partitions: vec![],
             schema,
             identity,
         }
@@ -322,6 +455,7 @@ pub struct HashJoinExecutorBuilder {
     params: EquiJoinParams,
     left_child: BoxedExecutor,
     right_child: BoxedExecutor,
+    spill_context: SpillContext,
     schema: Schema,
     task_id: TaskId,
 }
@@ -339,6 +473,7 @@ impl HashKeyDispatcher for HashJoinExecutorBuilderDispatcher {
                 input.left_child,
                 input.right_child,
                 input.params,
+                input.spill_context,
                 input.schema,
                 format!("HashJoinExecutor{:?}", input.task_id),
             )
@@ -426,6 +561,7 @@ impl BoxedExecutorBuilder for HashJoinExecutorBuilder {
             params,
             left_child,
             right_child,
+            spill_context: SpillContext::from_builder(context),
             schema: Schema {
                 fields: schema_fields,
             },
@@ -450,13 +586,14 @@ mod tests {
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::error::Result;
     use risingwave_common::hash::Key32;
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, Datum, ScalarImpl, ScalarRefImpl};
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
     use risingwave_expr::expr::{BoxedExpression, InputRefExpression};
     use risingwave_pb::expr::expr_node::Type;
 
     use crate::executor::join::hash_join::{EquiJoinParams, HashJoinExecutor};
     use crate::executor::join::JoinType;
+    use crate::executor::spill::SpillContext;
     use crate::executor::test_utils::MockExecutor;
     use crate::executor::BoxedExecutor;
 
@@ -511,6 +648,19 @@ mod tests {
             .all(|(row1, row2)| row1 == row2)
     }
 
+    fn sorted_rows(data_chunk: &DataChunk) -> Vec<Vec<Datum>> {
+        data_chunk
+            .rows()
+            .map(|row| {
+                row.0
+                    .into_iter()
+                    .map(|datum| datum.map(ScalarRefImpl::into_scalar_impl))
+                    .collect_vec()
+            })
+            .sorted()
+            .collect_vec()
+    }
+
     struct TestFixture {
         left_types: Vec<DataType>,
         right_types: Vec<DataType>,
@@ -666,7 +816,11 @@ mod tests {
             )
         }
 
-        fn create_join_executor(&self, has_non_equi_cond: bool) -> BoxedExecutor {
+        fn create_join_executor(
+            &self,
+            has_non_equi_cond: bool,
+            spill_context: SpillContext,
+        ) -> BoxedExecutor {
             let join_type = self.join_type;
 
             let left_child = self.create_left_executor();
@@ -718,6 +872,7 @@ mod tests {
                 left_child,
                 right_child,
                 params,
+                spill_context,
                 schema,
                 "HashJoinExecutor".to_string(),
             )) as BoxedExecutor
@@ -739,7 +894,22 @@ mod tests {
         }
 
         async fn do_test(&self, expected: DataChunk, has_non_equi_cond: bool) {
-            let mut join_executor = self.create_join_executor(has_non_equi_cond);
+            let output_chunk = self
+                .execute(has_non_equi_cond, SpillContext::for_test(usize::MAX))
+                .await;
+            // TODO: Replace this with unsorted comparison
+            // assert_eq!(expected, result_chunk);
+            assert!(is_data_chunk_eq(&expected, &output_chunk));
+
+            // Spill everything to disk, which changes the order of output rows.
+            let output_chunk = self
+                .execute(has_non_equi_cond, SpillContext::for_test(0))
+                .await;
+            assert_eq!(sorted_rows(&expected), sorted_rows(&output_chunk));
+        }
+
+        async fn execute(&self, has_non_equi_cond: bool, spill_context: SpillContext) -> DataChunk {
+            let mut join_executor = self.create_join_executor(has_non_equi_cond, spill_context);
             join_executor
                 .open()
                 .await
@@ -769,11 +939,7 @@ mod tests {
 
             // Take (t1.v2, t2.v2) in inner and left/right/full outer
             // or v2 decided by side of anti/semi.
-            let output_chunk = self.select_from_chunk(result_chunk);
-
-            // TODO: Replace this with unsorted comparison
-            // assert_eq!(expected, result_chunk);
-            assert!(is_data_chunk_eq(&expected, &output_chunk));
+            self.select_from_chunk(result_chunk)
         }
     }
 
@@ -1104,4 +1270,69 @@ mod tests {
 
         test_fixture.do_test(expected_chunk, true).await;
     }
+
+    /// The build side exceeds the budget with rows of a single key, which stay in one partition
+    /// however many times they are partitioned.
+    #[tokio::test]
+    async fn test_spill_skewed_key() {
+        fn create_executor() -> BoxedExecutor {
+            let keys = std::iter::repeat(1)
+                .take(30)
+                .chain(2..32)
+                .map(Some)
+                .collect_vec();
+            let mut executor = MockExecutor::new(Schema {
+                fields: vec![Field::unnamed(DataType::Int32)],
+            });
+            for keys in keys.chunks(20) {
+                let column = Column::new(Arc::new(I32Array::from_slice(keys).unwrap().into()));
+                executor.add(DataChunk::try_from(vec![column]).unwrap());
+            }
+            Box::new(executor)
+        }
+
+        let left_child = create_executor();
+        let right_child = create_executor();
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Int32),
+            ],
+        };
+        let params = EquiJoinParams {
+            join_type: JoinType::Inner,
+            left_key_columns: vec![0],
+            left_key_types: vec![DataType::Int32],
+            left_col_len: 1,
+            right_key_columns: vec![0],
+            right_key_types: vec![DataType::Int32],
+            right_col_len: 1,
+            full_data_types: vec![DataType::Int32, DataType::Int32],
+            batch_size: 1024,
+            cond: None,
+        };
+        let mut join_executor: BoxedExecutor = Box::new(HashJoinExecutor::<Key32>::new(
+            left_child,
+            right_child,
+            params,
+            SpillContext::for_test(64),
+            schema,
+            "HashJoinExecutor".to_string(),
+        ));
+
+        join_executor.open().await.unwrap();
+        let mut rows = vec![];
+        while let Some(chunk) = join_executor.next().await.unwrap() {
+            rows.extend(sorted_rows(&chunk.compact().unwrap()));
+        }
+        join_executor.close().await.unwrap();
+
+        rows.sort();
+        let expected = std::iter::repeat(1)
+            .take(30 * 30)
+            .chain(2..32)
+            .map(|key| vec![Some(ScalarImpl::Int32(key)), Some(ScalarImpl::Int32(key))])
+            .collect_vec();
+        assert_eq!(rows, expected);
+    }
 }
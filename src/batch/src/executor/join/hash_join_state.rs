@@ -61,6 +61,16 @@ impl BuildTable {
         Ok(())
     }
 
+    pub(super) fn params(&self) -> &EquiJoinParams {
+        &self.params
+    }
+
+    /// Takes the appended chunks out of the table, e.g. to spill them to disk.
+    pub(super) fn take_build_data(&mut self) -> Vec<DataChunk> {
+        self.row_count = 0;
+        mem::take(&mut self.build_data)
+    }
+
     fn build_hash_map<K: HashKey>(&self) -> Result<(ChunkedData<Option<RowId>>, JoinHashMap<K>)> {
         let mut hash_map =
             JoinHashMap::with_capacity_and_hasher(self.row_count, PrecomputedBuildHasher);
@@ -229,6 +239,11 @@ impl<K: HashKey> ProbeTable<K> {
         self.params.join_type()
     }
 
+    /// Drops the table and returns its parameters, e.g. to build the table of another partition.
+    pub(super) fn into_params(self) -> EquiJoinParams {
+        self.params
+    }
+
     pub(super) fn set_probe_data(&mut self, probe_data_chunk: DataChunk) -> Result<()> {
         self.build_data_chunk()?;
         let probe_data_chunk = probe_data_chunk.compact()?;
@@ -14,13 +14,13 @@
 
 use drop_stream::*;
 use drop_table::*;
-use filter::*;
+pub use filter::*;
 use generic_exchange::*;
 use hash_agg::*;
 use limit::*;
 use merge_sort_exchange::*;
 use order_by::*;
-use projection::*;
+pub use projection::*;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::ErrorCode::InternalError;
@@ -32,6 +32,7 @@ use sort_agg::*;
 use top_n::*;
 
 use self::fuse::FusedExecutor;
+use self::monitor::OperatorStatsRef;
 use crate::executor::create_source::CreateSourceExecutor;
 pub use crate::executor::create_table::CreateTableExecutor;
 pub use crate::executor::delete::DeleteExecutor;
@@ -40,8 +41,10 @@ pub use crate::executor::insert::InsertExecutor;
 use crate::executor::join::nested_loop_join::NestedLoopJoinExecutor;
 use crate::executor::join::sort_merge_join::SortMergeJoinExecutor;
 use crate::executor::join::HashJoinExecutorBuilder;
+use crate::executor::stats::StatsExecutor;
 pub use crate::executor::stream_scan::StreamScanExecutor;
 use crate::executor::trace::TraceExecutor;
+use crate::executor::unnest::UnnestExecutor;
 use crate::executor::values::ValuesExecutor;
 use crate::task::{BatchEnvironment, TaskId};
 
@@ -64,11 +67,14 @@ mod order_by;
 mod projection;
 mod row_seq_scan;
 mod sort_agg;
+mod spill;
+mod stats;
 mod stream_scan;
 #[cfg(test)]
 mod test_utils;
 mod top_n;
 mod trace;
+mod unnest;
 mod values;
 
 /// `Executor` is an operator in the query execution.
@@ -113,6 +119,7 @@ pub struct ExecutorBuilder<'a> {
     task_id: &'a TaskId,
     env: BatchEnvironment,
     epoch: u64,
+    operator_stats: OperatorStatsRef,
 }
 
 macro_rules! build_executor {
@@ -134,11 +141,14 @@ impl<'a> ExecutorBuilder<'a> {
         env: BatchEnvironment,
         epoch: u64,
     ) -> Self {
+        // Builders are created top-down, so operators are registered in pre-order.
+        let operator_stats = env.task_stats().register_operator();
         Self {
             plan_node,
             task_id,
             env,
             epoch,
+            operator_stats,
         }
     }
 
@@ -181,10 +191,15 @@ impl<'a> ExecutorBuilder<'a> {
             NodeBody::DropSource => DropStreamExecutor,
             NodeBody::HashAgg => HashAggExecutorBuilder,
             NodeBody::MergeSortExchange => MergeSortExchangeExecutor,
-            NodeBody::GenerateInt32Series => GenerateSeriesI32Executor
+            NodeBody::GenerateInt32Series => GenerateSeriesI32Executor,
+            NodeBody::Unnest => UnnestExecutor
         }?;
         let input_desc = real_executor.identity().to_string();
-        Ok(Box::new(TraceExecutor::new(real_executor, input_desc)))
+        let stats_executor = StatsExecutor::new(real_executor, self.operator_stats());
+        Ok(Box::new(TraceExecutor::new(
+            Box::new(stats_executor),
+            input_desc,
+        )))
     }
 
     pub fn plan_node(&self) -> &PlanNode {
@@ -194,6 +209,11 @@ impl<'a> ExecutorBuilder<'a> {
     pub fn global_batch_env(&self) -> &BatchEnvironment {
         &self.env
     }
+
+    /// Statistics of the executor built by this builder.
+    pub fn operator_stats(&self) -> OperatorStatsRef {
+        self.operator_stats.clone()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,147 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use risingwave_pb::task_service::OperatorStats as ProstOperatorStats;
+
+pub type OperatorStatsRef = Arc<OperatorStats>;
+
+/// Runtime statistics of a single operator of a task, reported by `EXPLAIN ANALYZE`.
+///
+/// Elapsed time includes the time spent in the inputs of the operator.
+#[derive(Debug, Default)]
+pub struct OperatorStats {
+    identity: Mutex<String>,
+    rows: AtomicU64,
+    chunks: AtomicU64,
+    elapsed_nanos: AtomicU64,
+    memory_bytes: AtomicU64,
+    peak_memory_bytes: AtomicU64,
+}
+
+impl OperatorStats {
+    pub fn set_identity(&self, identity: &str) {
+        *self.identity.lock() = identity.to_string();
+    }
+
+    /// Records a call of the operator taking `elapsed`, which output a chunk of `rows` if any.
+    pub fn record(&self, rows: Option<usize>, elapsed: Duration) {
+        if let Some(rows) = rows {
+            self.rows.fetch_add(rows as u64, Ordering::Relaxed);
+            self.chunks.fetch_add(1, Ordering::Relaxed);
+        }
+        self.elapsed_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn grow_memory(&self, bytes: usize) {
+        let used = self.memory_bytes.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+        self.peak_memory_bytes.fetch_max(used, Ordering::Relaxed);
+    }
+
+    pub fn shrink_memory(&self, bytes: usize) {
+        self.memory_bytes.fetch_sub(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn rows(&self) -> u64 {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    pub fn peak_memory_bytes(&self) -> u64 {
+        self.peak_memory_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn to_prost(&self) -> ProstOperatorStats {
+        ProstOperatorStats {
+            identity: self.identity.lock().clone(),
+            rows: self.rows(),
+            chunks: self.chunks.load(Ordering::Relaxed),
+            elapsed_nanos: self.elapsed_nanos.load(Ordering::Relaxed),
+            peak_memory_bytes: self.peak_memory_bytes(),
+        }
+    }
+}
+
+pub type TaskStatsRef = Arc<TaskStats>;
+
+/// Statistics of all operators of a task, in pre-order of the plan tree, so that the operators of
+/// the tasks of a stage can be matched with each other and with the plan.
+#[derive(Debug, Default)]
+pub struct TaskStats {
+    operators: Mutex<Vec<OperatorStatsRef>>,
+}
+
+impl TaskStats {
+    /// Registers an operator, which must be done before registering any of its inputs.
+    pub fn register_operator(&self) -> OperatorStatsRef {
+        let stats = OperatorStatsRef::default();
+        self.operators.lock().push(stats.clone());
+        stats
+    }
+
+    pub fn to_prost(&self) -> Vec<ProstOperatorStats> {
+        self.operators
+            .lock()
+            .iter()
+            .map(|stats| stats.to_prost())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operator_stats() {
+        let task_stats = TaskStats::default();
+        let root = task_stats.register_operator();
+        let input = task_stats.register_operator();
+        root.set_identity("root");
+        input.set_identity("input");
+
+        input.record(Some(3), Duration::from_nanos(10));
+        input.record(Some(2), Duration::from_nanos(10));
+        input.record(None, Duration::from_nanos(5));
+        root.record(Some(5), Duration::from_nanos(40));
+
+        root.grow_memory(100);
+        root.shrink_memory(60);
+        root.grow_memory(30);
+
+        let stats = task_stats.to_prost();
+        assert_eq!(
+            stats,
+            vec![
+                ProstOperatorStats {
+                    identity: "root".to_string(),
+                    rows: 5,
+                    chunks: 1,
+                    elapsed_nanos: 40,
+                    peak_memory_bytes: 100,
+                },
+                ProstOperatorStats {
+                    identity: "input".to_string(),
+                    rows: 5,
+                    chunks: 2,
+                    elapsed_nanos: 25,
+                    peak_memory_bytes: 0,
+                },
+            ]
+        );
+    }
+}
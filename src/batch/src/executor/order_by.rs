@@ -32,9 +32,16 @@ use risingwave_common::util::sort_util::{
 };
 use risingwave_pb::plan::plan_node::NodeBody;
 
+use super::spill::{estimate_chunk_size, SpillContext, SpillFile, SpillFileReader};
 use super::{BoxedExecutor, BoxedExecutorBuilder};
 use crate::executor::{Executor, ExecutorBuilder};
+use crate::task::MemoryReservation;
 
+/// Sorts the chunks in memory with a k-way merge.
+///
+/// Once the chunks exceed the memory budget of the task, they are merged into a sorted run spilled
+/// to disk. After all input is consumed, the spilled runs are merged with the same k-way merge,
+/// holding one chunk of each run in memory.
 pub(super) struct OrderByExecutor {
     child: BoxedExecutor,
     sorted_indices: Vec<Vec<usize>>,
@@ -45,6 +52,13 @@ pub(super) struct OrderByExecutor {
     encoded_keys: Vec<Arc<Vec<Vec<u8>>>>,
     encodable: bool,
     disable_encoding: bool,
+    spill_context: SpillContext,
+    /// memory reserved for `chunks`
+    reservation: MemoryReservation,
+    /// sorted runs spilled to disk
+    spilled_runs: Vec<SpillFile>,
+    /// sorted runs being merged, whose current chunks are in `chunks` at the same index
+    runs: Vec<SpillFileReader>,
     identity: String,
 }
 
@@ -65,18 +79,12 @@ impl BoxedExecutorBuilder for OrderByExecutor {
         if let Some(child_plan) = source.plan_node.get_children().get(0) {
             let child = source.clone_for_plan(child_plan).build()?;
             return Ok(Box::new(
-                Self {
-                    order_pairs: Arc::new(order_pairs),
+                Self::new(
                     child,
-                    vis_indices: vec![],
-                    chunks: vec![],
-                    sorted_indices: vec![],
-                    min_heap: BinaryHeap::new(),
-                    encoded_keys: vec![],
-                    encodable: false,
-                    disable_encoding: false,
-                    identity: source.plan_node().get_identity().clone(),
-                }
+                    order_pairs,
+                    SpillContext::from_builder(source),
+                    source.plan_node().get_identity().clone(),
+                )
                 .fuse(),
             ));
         }
@@ -85,7 +93,33 @@ impl BoxedExecutorBuilder for OrderByExecutor {
 }
 
 impl OrderByExecutor {
-    fn push_heap_for_chunk(&mut self, idx: usize) {
+    fn new(
+        child: BoxedExecutor,
+        order_pairs: Vec<OrderPair>,
+        spill_context: SpillContext,
+        identity: String,
+    ) -> Self {
+        Self {
+            order_pairs: Arc::new(order_pairs),
+            child,
+            vis_indices: vec![],
+            chunks: vec![],
+            sorted_indices: vec![],
+            min_heap: BinaryHeap::new(),
+            encoded_keys: vec![],
+            encodable: false,
+            disable_encoding: false,
+            reservation: spill_context.reservation(),
+            spill_context,
+            spilled_runs: vec![],
+            runs: vec![],
+            identity,
+        }
+    }
+
+    /// Pushes the next visible row of chunk `idx` into the heap. Returns false if the chunk is
+    /// exhausted.
+    fn push_heap_for_chunk(&mut self, idx: usize) -> bool {
         while self.vis_indices[idx] < self.chunks[idx].cardinality() {
             let skip: bool = match self.chunks[idx].visibility() {
                 Some(visibility) => visibility
@@ -109,10 +143,27 @@ impl OrderByExecutor {
                 };
                 self.min_heap.push(elem);
                 self.vis_indices[idx] += 1;
-                break;
+                return true;
             }
             self.vis_indices[idx] += 1;
         }
+        false
+    }
+
+    /// Pushes the next visible row of chunk `idx` into the heap, reading the following chunks of
+    /// the run `idx` if the chunk is exhausted.
+    async fn push_heap_for_run(&mut self, idx: usize) -> Result<()> {
+        while !self.push_heap_for_chunk(idx) {
+            let chunk = match self.runs.get_mut(idx) {
+                Some(run) => run.read_chunk().await?,
+                None => None,
+            };
+            match chunk {
+                Some(chunk) => self.replace_chunk(idx, chunk),
+                None => break,
+            }
+        }
+        Ok(())
     }
 
     fn get_order_index_from(&self, idx: usize) -> Vec<usize> {
@@ -136,45 +187,82 @@ impl OrderByExecutor {
         index
     }
 
-    async fn collect_child_data(&mut self) -> Result<()> {
-        while let Some(chunk) = self.child.next().await? {
-            if !self.disable_encoding && self.encodable {
-                self.encoded_keys
-                    .push(encode_chunk(&chunk, self.order_pairs.clone()));
-            }
-            self.chunks.push(Arc::new(chunk));
-            self.sorted_indices
-                .push(self.get_order_index_from(self.chunks.len() - 1));
+    fn add_chunk(&mut self, chunk: DataChunk) {
+        if !self.disable_encoding && self.encodable {
+            self.encoded_keys
+                .push(encode_chunk(&chunk, self.order_pairs.clone()));
         }
+        self.chunks.push(Arc::new(chunk));
+        self.sorted_indices
+            .push(self.get_order_index_from(self.chunks.len() - 1));
+    }
+
+    fn replace_chunk(&mut self, idx: usize, chunk: DataChunk) {
+        if !self.disable_encoding && self.encodable {
+            self.encoded_keys[idx] = Arc::new(encode_chunk(&chunk, self.order_pairs.clone()));
+        }
+        self.chunks[idx] = Arc::new(chunk);
+        self.sorted_indices[idx] = self.get_order_index_from(idx);
+        self.vis_indices[idx] = 0;
+    }
+
+    fn init_heap(&mut self) {
         self.vis_indices = vec![0usize; self.chunks.len()];
         for idx in 0..self.chunks.len() {
             self.push_heap_for_chunk(idx);
         }
-        Ok(())
     }
-}
-
-#[async_trait::async_trait]
-impl Executor for OrderByExecutor {
-    async fn open(&mut self) -> Result<()> {
-        self.child.open().await?;
 
-        if !self.disable_encoding {
-            let schema = self.schema();
-            self.encodable = self
-                .order_pairs
-                .iter()
-                .map(|pair| schema.fields[pair.column_idx].data_type.clone())
-                .all(is_type_encodable)
+    /// Merges the chunks in memory into a sorted run on disk.
+    async fn spill_run(&mut self) -> Result<()> {
+        self.init_heap();
+        let mut run = self.spill_context.create_file().await?;
+        while let Some(chunk) = self.next_sorted_chunk().await? {
+            run.write_chunk(chunk).await?;
         }
+        self.spilled_runs.push(run);
+        self.chunks.clear();
+        self.sorted_indices.clear();
+        self.encoded_keys.clear();
+        self.vis_indices.clear();
+        self.reservation.clear();
+        Ok(())
+    }
 
-        self.collect_child_data().await?;
+    /// Prepares the spilled runs to be merged, with their first chunks in memory.
+    async fn load_runs(&mut self) -> Result<()> {
+        for run in std::mem::take(&mut self.spilled_runs) {
+            let mut run = run.into_reader().await?;
+            if let Some(chunk) = run.read_chunk().await? {
+                self.add_chunk(chunk);
+                self.runs.push(run);
+            }
+        }
+        Ok(())
+    }
 
-        self.child.close().await?;
+    async fn collect_child_data(&mut self) -> Result<()> {
+        while let Some(chunk) = self.child.next().await? {
+            let chunk_size = estimate_chunk_size(&chunk);
+            if !self.reservation.try_grow(chunk_size) {
+                if !self.chunks.is_empty() {
+                    self.spill_run().await?;
+                }
+                self.reservation.force_grow(chunk_size);
+            }
+            self.add_chunk(chunk);
+        }
+        if !self.spilled_runs.is_empty() {
+            if !self.chunks.is_empty() {
+                self.spill_run().await?;
+            }
+            self.load_runs().await?;
+        }
+        self.init_heap();
         Ok(())
     }
 
-    async fn next(&mut self) -> Result<Option<DataChunk>> {
+    async fn next_sorted_chunk(&mut self) -> Result<Option<DataChunk>> {
         let data_types = self
             .schema()
             .fields()
@@ -219,7 +307,7 @@ impl Executor for OrderByExecutor {
                 );
             }
             chunk_size += 1;
-            self.push_heap_for_chunk(top.chunk_idx);
+            self.push_heap_for_run(top.chunk_idx).await?;
         }
         if chunk_size == 0 {
             return Ok(None);
@@ -231,6 +319,31 @@ impl Executor for OrderByExecutor {
         let chunk = DataChunk::builder().columns(columns).build();
         Ok(Some(chunk))
     }
+}
+
+#[async_trait::async_trait]
+impl Executor for OrderByExecutor {
+    async fn open(&mut self) -> Result<()> {
+        self.child.open().await?;
+
+        if !self.disable_encoding {
+            let schema = self.schema();
+            self.encodable = self
+                .order_pairs
+                .iter()
+                .map(|pair| schema.fields[pair.column_idx].data_type.clone())
+                .all(is_type_encodable)
+        }
+
+        self.collect_child_data().await?;
+
+        self.child.close().await?;
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        self.next_sorted_chunk().await
+    }
 
     async fn close(&mut self) -> Result<()> {
         Ok(())
@@ -318,18 +431,12 @@ mod tests {
                 order_type: OrderType::Ascending,
             },
         ];
-        let mut order_by_executor = OrderByExecutor {
-            order_pairs: Arc::new(order_pairs),
-            child: Box::new(mock_executor),
-            vis_indices: vec![],
-            chunks: vec![],
-            sorted_indices: vec![],
-            min_heap: BinaryHeap::new(),
-            encoded_keys: vec![],
-            encodable: false,
-            disable_encoding: false,
-            identity: "OrderByExecutor".to_string(),
-        };
+        let mut order_by_executor = OrderByExecutor::new(
+            Box::new(mock_executor),
+            order_pairs,
+            SpillContext::for_test(usize::MAX),
+            "OrderByExecutor".to_string(),
+        );
         let fields = &order_by_executor.schema().fields;
         assert_eq!(fields[0].data_type, DataType::Int32);
         assert_eq!(fields[1].data_type, DataType::Int32);
@@ -370,18 +477,12 @@ mod tests {
                 order_type: OrderType::Ascending,
             },
         ];
-        let mut order_by_executor = OrderByExecutor {
-            order_pairs: Arc::new(order_pairs),
-            child: Box::new(mock_executor),
-            vis_indices: vec![],
-            chunks: vec![],
-            sorted_indices: vec![],
-            min_heap: BinaryHeap::new(),
-            encoded_keys: vec![],
-            encodable: false,
-            disable_encoding: false,
-            identity: "OrderByExecutor".to_string(),
-        };
+        let mut order_by_executor = OrderByExecutor::new(
+            Box::new(mock_executor),
+            order_pairs,
+            SpillContext::for_test(usize::MAX),
+            "OrderByExecutor".to_string(),
+        );
         let fields = &order_by_executor.schema().fields;
         assert_eq!(fields[0].data_type, DataType::Float32);
         assert_eq!(fields[1].data_type, DataType::Float64);
@@ -431,18 +532,12 @@ mod tests {
                 order_type: OrderType::Ascending,
             },
         ];
-        let mut order_by_executor = OrderByExecutor {
-            order_pairs: Arc::new(order_pairs),
-            child: Box::new(mock_executor),
-            vis_indices: vec![],
-            chunks: vec![],
-            sorted_indices: vec![],
-            min_heap: BinaryHeap::new(),
-            encoded_keys: vec![],
-            encodable: false,
-            disable_encoding: false,
-            identity: "OrderByExecutor".to_string(),
-        };
+        let mut order_by_executor = OrderByExecutor::new(
+            Box::new(mock_executor),
+            order_pairs,
+            SpillContext::for_test(usize::MAX),
+            "OrderByExecutor".to_string(),
+        );
         let fields = &order_by_executor.schema().fields;
         assert_eq!(fields[0].data_type, DataType::Varchar);
         assert_eq!(fields[1].data_type, DataType::Varchar);
@@ -457,6 +552,49 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_order_by_with_spilling() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Varchar),
+            ],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        for chunk_idx in 0..10 {
+            let keys = (0..100)
+                .map(|i| Some((chunk_idx * 100 + i) * 37 % 1000))
+                .collect_vec();
+            let values = keys.iter().map(|k| k.map(|k| k.to_string())).collect_vec();
+            let col0 = create_column_i32(&keys).unwrap();
+            let col1 = create_column_string(&values).unwrap();
+            mock_executor.add(DataChunk::builder().columns(vec![col0, col1]).build());
+        }
+        let order_pairs = vec![OrderPair {
+            column_idx: 0,
+            order_type: OrderType::Descending,
+        }];
+        // Each chunk exceeds the budget, so every chunk is spilled as a sorted run.
+        let mut order_by_executor = OrderByExecutor::new(
+            Box::new(mock_executor),
+            order_pairs,
+            SpillContext::for_test(1000),
+            "OrderByExecutor".to_string(),
+        );
+        order_by_executor.open().await.unwrap();
+        let mut keys = vec![];
+        while let Some(res) = order_by_executor.next().await.unwrap() {
+            let col0 = res.column_at(0).array();
+            let col1 = res.column_at(1).array();
+            for (key, value) in col0.as_int32().iter().zip_eq(col1.as_utf8().iter()) {
+                assert_eq!(key.unwrap().to_string(), value.unwrap());
+                keys.push(key.unwrap());
+            }
+        }
+        order_by_executor.close().await.unwrap();
+        assert_eq!(keys, (0..1000).rev().collect_vec());
+    }
+
     // TODO: enable benches
 
     // fn benchmark_1e4(b: &mut Bencher, enable_encoding: bool) {
@@ -22,7 +22,7 @@ use risingwave_pb::plan::plan_node::NodeBody;
 use super::{BoxedExecutor, BoxedExecutorBuilder};
 use crate::executor::{Executor, ExecutorBuilder};
 
-pub(super) struct ProjectionExecutor {
+pub struct ProjectionExecutor {
     expr: Vec<BoxedExpression>,
     child: BoxedExecutor,
     schema: Schema,
@@ -67,6 +67,21 @@ impl Executor for ProjectionExecutor {
     }
 }
 
+impl ProjectionExecutor {
+    pub fn new(expr: Vec<BoxedExpression>, child: BoxedExecutor, identity: String) -> Self {
+        let fields = expr
+            .iter()
+            .map(|expr| Field::unnamed(expr.return_type()))
+            .collect::<Vec<Field>>();
+        Self {
+            expr,
+            child,
+            schema: Schema { fields },
+            identity,
+        }
+    }
+}
+
 impl BoxedExecutorBuilder for ProjectionExecutor {
     fn new_boxed_executor(source: &ExecutorBuilder) -> Result<BoxedExecutor> {
         ensure!(source.plan_node().get_children().len() == 1);
@@ -89,18 +104,12 @@ impl BoxedExecutorBuilder for ProjectionExecutor {
             .map(build_from_prost)
             .collect::<Result<Vec<BoxedExpression>>>()?;
 
-        let fields = project_exprs
-            .iter()
-            .map(|expr| Field::unnamed(expr.return_type()))
-            .collect::<Vec<Field>>();
-
         Ok(Box::new(
-            Self {
-                expr: project_exprs,
-                child: child_node,
-                schema: Schema { fields },
-                identity: source.plan_node().get_identity().clone(),
-            }
+            Self::new(
+                project_exprs,
+                child_node,
+                source.plan_node().get_identity().clone(),
+            )
             .fuse(),
         ))
     }
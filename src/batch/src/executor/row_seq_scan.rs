@@ -1,3 +1,4 @@
+use std::ops::Bound;
 use std::sync::Arc;
 
 // Copyright 2022 Singularity Data
@@ -18,6 +19,8 @@ use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{ColumnDesc, Schema, TableId};
 use risingwave_common::error::Result;
 use risingwave_pb::plan::plan_node::NodeBody;
+use risingwave_pb::plan::scan_range::Bound as ProstBound;
+use risingwave_pb::plan::ScanRange;
 use risingwave_storage::table::cell_based_table::{CellBasedTable, CellBasedTableRowIter};
 use risingwave_storage::{dispatch_state_store, Keyspace, StateStore, StateStoreImpl};
 
@@ -30,6 +33,8 @@ pub struct RowSeqScanExecutor<S: StateStore> {
     table: CellBasedTable<S>,
     /// An iterator to scan StateStore.
     iter: Option<CellBasedTableRowIter<S>>,
+    /// The memcomparable-encoded pk bounds of the rows to scan.
+    pk_bounds: (Bound<Vec<u8>>, Bound<Vec<u8>>),
     primary: bool,
 
     chunk_size: usize,
@@ -49,12 +54,35 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
         identity: String,
         epoch: u64,
         stats: Arc<BatchMetrics>,
+    ) -> Self {
+        Self::new_with_pk_bounds(
+            table,
+            (Bound::Unbounded, Bound::Unbounded),
+            chunk_size,
+            primary,
+            identity,
+            epoch,
+            stats,
+        )
+    }
+
+    /// Creates an executor which only scans the rows whose pk is within `pk_bounds`. See
+    /// [`CellBasedTable::iter_with_pk_bounds`].
+    pub fn new_with_pk_bounds(
+        table: CellBasedTable<S>,
+        pk_bounds: (Bound<Vec<u8>>, Bound<Vec<u8>>),
+        chunk_size: usize,
+        primary: bool,
+        identity: String,
+        epoch: u64,
+        stats: Arc<BatchMetrics>,
     ) -> Self {
         let schema = table.schema().clone();
 
         Self {
             table,
             iter: None,
+            pk_bounds,
             primary,
             chunk_size,
             schema,
@@ -77,6 +105,28 @@ pub struct RowSeqScanExecutorBuilder {}
 impl RowSeqScanExecutorBuilder {
     // TODO: decide the chunk size for row seq scan
     pub const DEFAULT_CHUNK_SIZE: usize = 1024;
+
+    /// Converts the scan range into pk bounds. The values of the range are encoded by the
+    /// frontend, so the bounds are simply the concatenation of the prefix and the bound values.
+    fn get_pk_bounds(scan_range: &ScanRange) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
+        let prefix = scan_range.eq_conds.concat();
+        let to_bound = |bound: &Option<ProstBound>| match bound {
+            Some(bound) => {
+                let key = [prefix.as_slice(), &bound.value].concat();
+                if bound.inclusive {
+                    Bound::Included(key)
+                } else {
+                    Bound::Excluded(key)
+                }
+            }
+            None if prefix.is_empty() => Bound::Unbounded,
+            None => Bound::Included(prefix.clone()),
+        };
+        (
+            to_bound(&scan_range.lower_bound),
+            to_bound(&scan_range.upper_bound),
+        )
+    }
 }
 
 impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
@@ -94,18 +144,29 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             .iter()
             .map(|column_desc| ColumnDesc::from(column_desc.clone()))
             .collect_vec();
+        // Scans with `FOR SYSTEM_TIME AS OF` read an earlier epoch, which is pinned by the frontend
+        // during the query. Otherwise `as_of_epoch` is 0 and the epoch of the task is used.
+        let epoch = match seq_scan_node.as_of_epoch {
+            0 => source.epoch,
+            as_of_epoch => as_of_epoch,
+        };
+        let pk_bounds = match &seq_scan_node.scan_range {
+            Some(scan_range) => Self::get_pk_bounds(scan_range),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        };
         dispatch_state_store!(source.global_batch_env().state_store(), state_store, {
             let keyspace = Keyspace::table_root(state_store.clone(), &table_id);
             let storage_stats = state_store.stats();
             let batch_stats = source.global_batch_env().stats();
             let table = CellBasedTable::new_adhoc(keyspace, column_descs, storage_stats);
             Ok(Box::new(
-                RowSeqScanExecutor::new(
+                RowSeqScanExecutor::new_with_pk_bounds(
                     table,
+                    pk_bounds,
                     RowSeqScanExecutorBuilder::DEFAULT_CHUNK_SIZE,
                     source.task_id.task_id == 0,
                     source.plan_node().get_identity().clone(),
-                    source.epoch,
+                    epoch,
                     batch_stats,
                 )
                 .fuse(),
@@ -122,7 +183,11 @@ impl<S: StateStore> Executor for RowSeqScanExecutor<S> {
             return Ok(());
         }
 
-        self.iter = Some(self.table.iter(self.epoch).await?);
+        self.iter = Some(
+            self.table
+                .iter_with_pk_bounds(self.epoch, self.pk_bounds.clone())
+                .await?,
+        );
         Ok(())
     }
 
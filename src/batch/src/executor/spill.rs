@@ -0,0 +1,407 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spilling of executor state to local disk.
+//!
+//! Executors reserve the memory of their state from the [`TaskMemoryTracker`] of the task. Once a
+//! reservation fails, they write data chunks to [`SpillFile`]s, which are anonymous temporary files
+//! in the spill directory and are removed as soon as they are dropped. A spilled partition that
+//! still doesn't fit in memory is partitioned again, up to [`MAX_SPILL_DEPTH`] times.
+
+use std::io::{ErrorKind, SeekFrom};
+use std::path::PathBuf;
+
+use itertools::Itertools;
+use prost::Message;
+use risingwave_common::array::{Array, ArrayImpl, DataChunk};
+use risingwave_common::buffer::BitmapBuilder;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::Result;
+use risingwave_common::util::hash_util::CRC32FastBuilder;
+use risingwave_pb::data::DataChunk as ProstDataChunk;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
+
+use super::monitor::OperatorStatsRef;
+use super::{Executor, ExecutorBuilder};
+use crate::task::{MemoryReservation, TaskMemoryTrackerRef};
+
+/// Number of partitions data is spilled into by [`PartitionedSpill`].
+pub(super) const SPILL_PARTITION_NUM: usize = 16;
+
+/// Maximum number of times rows are partitioned. Partitions at this depth are loaded into memory
+/// regardless of the budget, as rows with equal keys, which can't be split, may exceed it.
+pub(super) const MAX_SPILL_DEPTH: usize = 4;
+
+/// Estimated memory size of a value other than strings.
+const ESTIMATED_DATUM_SIZE: usize = 16;
+
+/// Memory tracker and spill directory of the task an executor runs in, with the statistics of the
+/// executor its memory is accounted to.
+#[derive(Clone)]
+pub(super) struct SpillContext {
+    memory_tracker: TaskMemoryTrackerRef,
+    spill_dir: PathBuf,
+    operator_stats: Option<OperatorStatsRef>,
+}
+
+impl SpillContext {
+    pub fn new(memory_tracker: TaskMemoryTrackerRef, spill_dir: PathBuf) -> Self {
+        Self {
+            memory_tracker,
+            spill_dir,
+            operator_stats: None,
+        }
+    }
+
+    pub fn from_builder(source: &ExecutorBuilder) -> Self {
+        let env = source.global_batch_env();
+        Self {
+            operator_stats: Some(source.operator_stats()),
+            ..Self::new(
+                env.memory_tracker(),
+                PathBuf::from(&env.config().spill_directory),
+            )
+        }
+    }
+
+    /// Creates a context spilling to the system temporary directory once `budget` is exceeded.
+    #[cfg(test)]
+    pub fn for_test(budget: usize) -> Self {
+        use crate::task::TaskMemoryTracker;
+
+        Self::new(
+            std::sync::Arc::new(TaskMemoryTracker::new(budget)),
+            std::env::temp_dir(),
+        )
+    }
+
+    pub fn reservation(&self) -> MemoryReservation {
+        let reservation = self.memory_tracker.reservation();
+        match &self.operator_stats {
+            Some(operator_stats) => reservation.with_operator_stats(operator_stats.clone()),
+            None => reservation,
+        }
+    }
+
+    pub async fn create_file(&self) -> Result<SpillFile> {
+        tokio::fs::create_dir_all(&self.spill_dir).await?;
+        let file = tempfile::tempfile_in(&self.spill_dir)?;
+        Ok(SpillFile {
+            writer: BufWriter::new(File::from_std(file)),
+            cardinality: 0,
+        })
+    }
+}
+
+/// Estimates the memory size of `chunk`, including the invisible rows.
+pub(super) fn estimate_chunk_size(chunk: &DataChunk) -> usize {
+    chunk
+        .columns()
+        .iter()
+        .map(|column| match column.array_ref() {
+            ArrayImpl::Utf8(array) => {
+                array.iter().flatten().map(str::len).sum::<usize>()
+                    + array.len() * std::mem::size_of::<usize>()
+            }
+            array => array.len() * ESTIMATED_DATUM_SIZE,
+        })
+        .sum()
+}
+
+/// A temporary file that data chunks are appended to. Call [`SpillFile::into_reader`] to read them
+/// back.
+pub(super) struct SpillFile {
+    writer: BufWriter<File>,
+    cardinality: usize,
+}
+
+impl SpillFile {
+    /// Appends the visible rows of `chunk` to the file.
+    pub async fn write_chunk(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact()?;
+        if chunk.cardinality() == 0 {
+            return Ok(());
+        }
+        self.cardinality += chunk.cardinality();
+        let buf = chunk.to_protobuf().encode_to_vec();
+        self.writer.write_u32_le(buf.len() as u32).await?;
+        self.writer.write_all(&buf).await?;
+        Ok(())
+    }
+
+    /// Number of rows written to the file.
+    pub fn cardinality(&self) -> usize {
+        self.cardinality
+    }
+
+    pub async fn into_reader(mut self) -> Result<SpillFileReader> {
+        self.writer.flush().await?;
+        let mut file = self.writer.into_inner();
+        file.seek(SeekFrom::Start(0)).await?;
+        Ok(SpillFileReader {
+            reader: BufReader::new(file),
+            cardinality: self.cardinality,
+        })
+    }
+}
+
+/// Reads the data chunks written to a [`SpillFile`] in order.
+pub(super) struct SpillFileReader {
+    reader: BufReader<File>,
+    cardinality: usize,
+}
+
+impl SpillFileReader {
+    pub async fn read_chunk(&mut self) -> Result<Option<DataChunk>> {
+        let len = match self.reader.read_u32_le().await {
+            Ok(len) => len as usize,
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let mut buf = vec![0; len];
+        self.reader.read_exact(&mut buf).await?;
+        let chunk = DataChunk::from_protobuf(&ProstDataChunk::decode(buf.as_slice())?)?;
+        Ok(Some(chunk))
+    }
+
+    /// Number of rows in the file.
+    pub fn cardinality(&self) -> usize {
+        self.cardinality
+    }
+}
+
+/// A partition written by [`PartitionedSpill`].
+pub(super) struct SpilledPartition {
+    pub reader: SpillFileReader,
+    /// Number of times the rows have been partitioned.
+    pub depth: usize,
+}
+
+/// Spills data chunks into [`SPILL_PARTITION_NUM`] files by the hash of the key columns, so that
+/// rows with equal keys end up in the same partition.
+pub(super) struct PartitionedSpill {
+    key_columns: Vec<usize>,
+    /// Number of times the rows written have been partitioned before.
+    depth: usize,
+    partitions: Vec<SpillFile>,
+}
+
+impl PartitionedSpill {
+    /// Creates a spill of rows at `depth`, which is 0 for the input of an executor and the depth of
+    /// the partition otherwise.
+    pub async fn new(
+        context: &SpillContext,
+        key_columns: Vec<usize>,
+        depth: usize,
+    ) -> Result<Self> {
+        assert!(depth < MAX_SPILL_DEPTH);
+        let mut partitions = Vec::with_capacity(SPILL_PARTITION_NUM);
+        for _ in 0..SPILL_PARTITION_NUM {
+            partitions.push(context.create_file().await?);
+        }
+        Ok(Self {
+            key_columns,
+            depth,
+            partitions,
+        })
+    }
+
+    pub async fn write_chunk(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact()?;
+        let cardinality = chunk.cardinality();
+        if cardinality == 0 {
+            return Ok(());
+        }
+        let hash_values = chunk.get_hash_values(&self.key_columns, CRC32FastBuilder)?;
+        let mut builders = (0..SPILL_PARTITION_NUM)
+            .map(|_| BitmapBuilder::with_capacity(cardinality))
+            .collect_vec();
+        for hash_value in hash_values {
+            let partition = partition_of(hash_value, self.depth);
+            for (idx, builder) in builders.iter_mut().enumerate() {
+                builder.append(idx == partition);
+            }
+        }
+        for (file, mut builder) in self.partitions.iter_mut().zip_eq(builders) {
+            file.write_chunk(chunk.with_visibility(builder.finish()))
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn into_partitions(self) -> Result<Vec<SpilledPartition>> {
+        let mut partitions = Vec::with_capacity(self.partitions.len());
+        for file in self.partitions {
+            partitions.push(SpilledPartition {
+                reader: file.into_reader().await?,
+                depth: self.depth + 1,
+            });
+        }
+        Ok(partitions)
+    }
+}
+
+/// Picks the partition of a row at `depth`. The hash value is mixed with the depth, so that rows
+/// of one partition are spread over all partitions when it is spilled again, and the bits picked
+/// are unrelated to those used by the hash tables that a partition is loaded into.
+fn partition_of(hash_value: u64, depth: usize) -> usize {
+    const PARTITION_BITS: u32 = SPILL_PARTITION_NUM.trailing_zeros();
+    // The finalizer of SplitMix64.
+    let mut x = hash_value ^ (depth as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    (x >> (u64::BITS - PARTITION_BITS)) as usize
+}
+
+/// Scans the data chunks of a spilled partition.
+pub(super) struct SpillReaderExecutor {
+    reader: SpillFileReader,
+    schema: Schema,
+    identity: String,
+}
+
+impl SpillReaderExecutor {
+    pub fn new(reader: SpillFileReader, schema: Schema, identity: String) -> Self {
+        Self {
+            reader,
+            schema,
+            identity,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Executor for SpillReaderExecutor {
+    async fn open(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        self.reader.read_chunk().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::{I32Array, Utf8Array};
+    use risingwave_common::array_nonnull;
+    use risingwave_common::buffer::Bitmap;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spill_file() {
+        let chunk = DataChunk::builder()
+            .columns(vec![
+                Column::new(Arc::new(array_nonnull! { I32Array, [1, 2, 3] }.into())),
+                Column::new(Arc::new(
+                    array_nonnull! { Utf8Array, ["a", "b", "c"] }.into(),
+                )),
+            ])
+            .build();
+        let context = SpillContext::for_test(0);
+        let mut file = context.create_file().await.unwrap();
+        file.write_chunk(chunk.clone()).await.unwrap();
+        file.write_chunk(
+            chunk.with_visibility(Bitmap::try_from(vec![false, true, false]).unwrap()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(file.cardinality(), 4);
+
+        let mut reader = file.into_reader().await.unwrap();
+        let first = reader.read_chunk().await.unwrap().unwrap();
+        assert_eq!(first.to_protobuf(), chunk.to_protobuf());
+        let second = reader.read_chunk().await.unwrap().unwrap();
+        assert_eq!(second.cardinality(), 1);
+        assert_eq!(second.row_at(0).unwrap().0, chunk.row_at(1).unwrap().0);
+        assert!(reader.read_chunk().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_partitioned_spill() {
+        let keys = (0..100).collect_vec();
+        let chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(
+                I32Array::from_slice(&keys.iter().map(|k| Some(*k)).collect_vec())
+                    .unwrap()
+                    .into(),
+            ))])
+            .build();
+        let context = SpillContext::for_test(0);
+        let mut spill = PartitionedSpill::new(&context, vec![0], 0).await.unwrap();
+        spill.write_chunk(chunk.clone()).await.unwrap();
+        spill.write_chunk(chunk).await.unwrap();
+
+        let partitions = spill.into_partitions().await.unwrap();
+        let non_empty = partitions
+            .iter()
+            .filter(|partition| partition.reader.cardinality() > 0)
+            .count();
+        assert!(non_empty > SPILL_PARTITION_NUM / 2);
+        let mut spilled_keys = vec![];
+        for SpilledPartition { mut reader, depth } in partitions {
+            assert_eq!(depth, 1);
+            let mut partition_keys = vec![];
+            while let Some(chunk) = reader.read_chunk().await.unwrap() {
+                partition_keys.extend(chunk.column_at(0).array().as_int32().iter().flatten());
+            }
+            assert_eq!(partition_keys.len(), reader.cardinality());
+            // Both copies of a key are in the same partition.
+            partition_keys.sort_unstable();
+            for pair in partition_keys.chunks(2) {
+                assert_eq!(pair[0], pair[1]);
+            }
+            spilled_keys.extend(partition_keys.into_iter().step_by(2));
+        }
+        spilled_keys.sort_unstable();
+        assert_eq!(spilled_keys, keys);
+    }
+
+    #[test]
+    fn test_repartition() {
+        let hash_values = (0..10000u64)
+            .map(|key| key.wrapping_mul(0x2545_f491_4f6c_dd1d))
+            .collect_vec();
+        // Rows of one partition are spread over all partitions at the next depth.
+        let partition = hash_values
+            .iter()
+            .copied()
+            .filter(|hash_value| partition_of(*hash_value, 0) == 0)
+            .collect_vec();
+        let mut counts = vec![0; SPILL_PARTITION_NUM];
+        for hash_value in &partition {
+            counts[partition_of(*hash_value, 1)] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 0), "{:?}", counts);
+        assert!(counts.iter().all(|count| *count < partition.len() / 4));
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::Instant;
+
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::Result;
+
+use crate::executor::monitor::OperatorStatsRef;
+use crate::executor::{BoxedExecutor, Executor};
+
+/// Records the output and the elapsed time of the underlying executor into its
+/// [`OperatorStats`](crate::executor::monitor::OperatorStats), which are reported by
+/// `EXPLAIN ANALYZE`.
+pub(super) struct StatsExecutor {
+    child: BoxedExecutor,
+    stats: OperatorStatsRef,
+}
+
+impl StatsExecutor {
+    pub fn new(child: BoxedExecutor, stats: OperatorStatsRef) -> Self {
+        stats.set_identity(child.identity());
+        Self { child, stats }
+    }
+}
+
+#[async_trait::async_trait]
+impl Executor for StatsExecutor {
+    async fn open(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let ret = self.child.open().await;
+        self.stats.record(None, start.elapsed());
+        ret
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        let start = Instant::now();
+        let ret = self.child.next().await;
+        let rows = match &ret {
+            Ok(Some(chunk)) => Some(chunk.cardinality()),
+            _ => None,
+        };
+        self.stats.record(rows, start.elapsed());
+        ret
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let ret = self.child.close().await;
+        self.stats.record(None, start.elapsed());
+        ret
+    }
+
+    fn schema(&self) -> &Schema {
+        self.child.schema()
+    }
+
+    fn identity(&self) -> &str {
+        "StatsExecutor"
+    }
+}
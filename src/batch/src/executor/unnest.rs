@@ -0,0 +1,205 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::vec;
+
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{DataChunk, I32Array};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Datum, ScalarImpl, ScalarRef};
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+use risingwave_expr::expr::{build_from_prost, BoxedExpression};
+use risingwave_pb::plan::plan_node::NodeBody;
+
+use crate::executor::{BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder};
+
+/// `UnnestExecutor` expands a constant array into a set of rows, one for each element. The array is
+/// either a list or a jsonb array.
+pub(super) struct UnnestExecutor {
+    array: BoxedExpression,
+    /// Elements of the evaluated array, or `None` before the first call to `next`.
+    values: Option<vec::IntoIter<Datum>>,
+    schema: Schema,
+    identity: String,
+    chunk_size: usize,
+}
+
+impl UnnestExecutor {
+    pub(crate) fn new(array: BoxedExpression, identity: String, chunk_size: usize) -> Result<Self> {
+        let element_type = match array.return_type() {
+            DataType::List { datatype } => *datatype,
+            DataType::Jsonb => DataType::Jsonb,
+            ty => {
+                return Err(ErrorCode::InternalError(format!(
+                    "unnest expects a list, got {:?}",
+                    ty
+                ))
+                .into())
+            }
+        };
+        Ok(Self {
+            array,
+            values: None,
+            schema: Schema::new(vec![Field::unnamed(element_type)]),
+            identity,
+            chunk_size,
+        })
+    }
+
+    fn eval_array(&self) -> Result<Vec<Datum>> {
+        // The array is constant, so we evaluate it on a one row chunk like `ValuesExecutor`.
+        let one_row_array = I32Array::from_slice(&[Some(1)])?;
+        let one_row_chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(one_row_array.into()))])
+            .build();
+        match self.array.eval(&one_row_chunk)?.datum_at(0) {
+            Some(ScalarImpl::List(list)) => Ok(list.values().to_vec()),
+            Some(ScalarImpl::Jsonb(jsonb)) => {
+                let elements = jsonb.as_ref().array_elements().ok_or_else(|| {
+                    ErrorCode::InvalidInputSyntax(format!(
+                        "cannot extract elements from {}",
+                        jsonb.as_ref().type_name()
+                    ))
+                })?;
+                Ok(elements
+                    .map(|e| Some(ScalarImpl::Jsonb(e.to_owned_scalar())))
+                    .collect())
+            }
+            None => Ok(vec![]),
+            Some(_) => unreachable!(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Executor for UnnestExecutor {
+    async fn open(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        if self.values.is_none() {
+            self.values = Some(self.eval_array()?.into_iter());
+        }
+        let values = self.values.as_mut().unwrap();
+        if values.len() == 0 {
+            return Ok(None);
+        }
+
+        let chunk_size = self.chunk_size.min(values.len());
+        let mut builder = self.schema.fields[0]
+            .data_type
+            .create_array_builder(chunk_size)?;
+        for value in values.take(chunk_size) {
+            builder.append_datum(&value)?;
+        }
+        let columns = vec![Column::new(Arc::new(builder.finish()?))];
+        Ok(Some(DataChunk::builder().columns(columns).build()))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+impl BoxedExecutorBuilder for UnnestExecutor {
+    fn new_boxed_executor(source: &ExecutorBuilder) -> Result<BoxedExecutor> {
+        let unnest_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::Unnest
+        )?;
+
+        Ok(Box::new(
+            Self::new(
+                build_from_prost(unnest_node.get_array()?)?,
+                source.plan_node().get_identity().clone(),
+                DEFAULT_CHUNK_BUFFER_SIZE,
+            )?
+            .fuse(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array;
+    use risingwave_common::array::{JsonbVal, ListValue};
+    use risingwave_expr::expr::LiteralExpression;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unnest_executor() {
+        let list = ListValue::new(vec![
+            Some(ScalarImpl::Int32(1)),
+            None,
+            Some(ScalarImpl::Int32(3)),
+        ]);
+        let array = Box::new(LiteralExpression::new(
+            DataType::List {
+                datatype: Box::new(DataType::Int32),
+            },
+            Some(ScalarImpl::List(list)),
+        ));
+        let mut executor = UnnestExecutor::new(array, "UnnestExecutor".to_string(), 2).unwrap();
+        assert_eq!(executor.schema().fields[0].data_type, DataType::Int32);
+
+        executor.open().await.unwrap();
+        let chunk = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            *chunk.column_at(0).array(),
+            array! {I32Array, [Some(1), None]}.into()
+        );
+        let chunk = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            *chunk.column_at(0).array(),
+            array! {I32Array, [Some(3)]}.into()
+        );
+        assert!(executor.next().await.unwrap().is_none());
+        executor.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unnest_jsonb() {
+        let jsonb: JsonbVal = r#"[1, "a", null]"#.parse().unwrap();
+        let array = Box::new(LiteralExpression::new(
+            DataType::Jsonb,
+            Some(ScalarImpl::Jsonb(jsonb)),
+        ));
+        let mut executor = UnnestExecutor::new(array, "UnnestExecutor".to_string(), 8).unwrap();
+        assert_eq!(executor.schema().fields[0].data_type, DataType::Jsonb);
+
+        executor.open().await.unwrap();
+        let chunk = executor.next().await.unwrap().unwrap();
+        let values = chunk
+            .column_at(0)
+            .array()
+            .iter()
+            .map(|v| v.unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["1", r#""a""#, "null"]);
+        assert!(executor.next().await.unwrap().is_none());
+        executor.close().await.unwrap();
+    }
+}
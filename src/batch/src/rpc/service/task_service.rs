@@ -21,7 +21,7 @@ use risingwave_pb::task_service::{
 };
 use tonic::{Request, Response, Status};
 
-use crate::task::{BatchEnvironment, BatchManager};
+use crate::task::{BatchEnvironment, BatchManager, TaskId};
 
 #[derive(Clone)]
 pub struct BatchServiceImpl {
@@ -62,9 +62,17 @@ impl TaskService for BatchServiceImpl {
     #[cfg_attr(coverage, no_coverage)]
     async fn get_task_info(
         &self,
-        _: Request<GetTaskInfoRequest>,
+        request: Request<GetTaskInfoRequest>,
     ) -> Result<Response<GetTaskInfoResponse>, Status> {
-        todo!()
+        let req = request.into_inner();
+        let task_id = TaskId::from(req.get_task_id().expect("no task id found"));
+        match self.mgr.get_task_info(&task_id) {
+            Ok(task_info) => Ok(Response::new(GetTaskInfoResponse {
+                status: None,
+                task_info: Some(task_info),
+            })),
+            Err(e) => Err(e.to_grpc_status()),
+        }
     }
 
     #[cfg_attr(coverage, no_coverage)]
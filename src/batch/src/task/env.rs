@@ -19,8 +19,8 @@ use risingwave_common::util::addr::HostAddr;
 use risingwave_source::{SourceManager, SourceManagerRef};
 use risingwave_storage::StateStoreImpl;
 
-use crate::executor::monitor::BatchMetrics;
-use crate::task::BatchManager;
+use crate::executor::monitor::{BatchMetrics, TaskStatsRef};
+use crate::task::{BatchManager, TaskMemoryTracker, TaskMemoryTrackerRef};
 
 pub(crate) type WorkerNodeId = u32;
 
@@ -48,6 +48,13 @@ pub struct BatchEnvironment {
 
     /// Statistics.
     stats: Arc<BatchMetrics>,
+
+    /// Memory tracker of the task this environment is cloned for. See
+    /// [`BatchEnvironment::clone_for_task`].
+    memory_tracker: TaskMemoryTrackerRef,
+
+    /// Runtime statistics of the operators of the task this environment is cloned for.
+    task_stats: TaskStatsRef,
 }
 
 impl BatchEnvironment {
@@ -60,7 +67,10 @@ impl BatchEnvironment {
         state_store: StateStoreImpl,
         stats: Arc<BatchMetrics>,
     ) -> Self {
+        let memory_tracker = Arc::new(TaskMemoryTracker::new(config.task_memory_budget));
         BatchEnvironment {
+            memory_tracker,
+            task_stats: TaskStatsRef::default(),
             server_addr,
             task_manager,
             source_manager,
@@ -77,11 +87,14 @@ impl BatchEnvironment {
         use risingwave_source::MemSourceManager;
         use risingwave_storage::monitor::StateStoreMetrics;
 
+        let config = Arc::new(BatchConfig::default());
         BatchEnvironment {
+            memory_tracker: Arc::new(TaskMemoryTracker::new(config.task_memory_budget)),
+            task_stats: TaskStatsRef::default(),
             task_manager: Arc::new(BatchManager::new()),
             server_addr: "127.0.0.1:5688".parse().unwrap(),
             source_manager: std::sync::Arc::new(MemSourceManager::new()),
-            config: Arc::new(BatchConfig::default()),
+            config,
             worker_id: WorkerNodeId::default(),
             state_store: StateStoreImpl::shared_in_memory_store(Arc::new(
                 StateStoreMetrics::unused(),
@@ -90,6 +103,16 @@ impl BatchEnvironment {
         }
     }
 
+    /// Clones the environment for a new task, with a memory tracker and statistics of its own.
+    #[must_use]
+    pub fn clone_for_task(&self) -> Self {
+        Self {
+            memory_tracker: Arc::new(TaskMemoryTracker::new(self.config.task_memory_budget)),
+            task_stats: TaskStatsRef::default(),
+            ..self.clone()
+        }
+    }
+
     pub fn server_address(&self) -> &HostAddr {
         &self.server_addr
     }
@@ -121,4 +144,12 @@ impl BatchEnvironment {
     pub fn stats(&self) -> Arc<BatchMetrics> {
         self.stats.clone()
     }
+
+    pub fn memory_tracker(&self) -> TaskMemoryTrackerRef {
+        self.memory_tracker.clone()
+    }
+
+    pub fn task_stats(&self) -> TaskStatsRef {
+        self.task_stats.clone()
+    }
 }
@@ -0,0 +1,176 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::executor::monitor::OperatorStatsRef;
+
+pub type TaskMemoryTrackerRef = Arc<TaskMemoryTracker>;
+
+/// Tracks the memory used by the executors of a task against the task's memory budget.
+///
+/// Executors holding unbounded state reserve memory through a [`MemoryReservation`] and spill to
+/// disk once a reservation can not be granted.
+#[derive(Debug)]
+pub struct TaskMemoryTracker {
+    budget: usize,
+    used: AtomicUsize,
+}
+
+impl TaskMemoryTracker {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Reserves `bytes` if the budget allows. Returns whether the memory is reserved.
+    fn try_reserve(&self, bytes: usize) -> bool {
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(bytes).filter(|&used| used <= self.budget)
+            })
+            .is_ok()
+    }
+
+    /// Reserves `bytes` regardless of the budget, for memory that can not be spilled.
+    fn force_reserve(&self, bytes: usize) {
+        self.used.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn release(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Creates an empty reservation, which releases its memory when dropped.
+    pub fn reservation(self: &Arc<Self>) -> MemoryReservation {
+        MemoryReservation {
+            tracker: self.clone(),
+            bytes: 0,
+            operator_stats: None,
+        }
+    }
+}
+
+/// Memory reserved by an executor from a [`TaskMemoryTracker`].
+#[derive(Debug)]
+pub struct MemoryReservation {
+    tracker: TaskMemoryTrackerRef,
+    bytes: usize,
+    /// Statistics of the operator owning the reservation, which records its peak memory.
+    operator_stats: Option<OperatorStatsRef>,
+}
+
+impl MemoryReservation {
+    /// Accounts the memory of the reservation to the statistics of an operator.
+    #[must_use]
+    pub fn with_operator_stats(mut self, operator_stats: OperatorStatsRef) -> Self {
+        operator_stats.grow_memory(self.bytes);
+        self.operator_stats = Some(operator_stats);
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.bytes
+    }
+
+    fn grow(&mut self, bytes: usize) {
+        self.bytes += bytes;
+        if let Some(operator_stats) = &self.operator_stats {
+            operator_stats.grow_memory(bytes);
+        }
+    }
+
+    /// Grows the reservation by `bytes` if the task budget allows. Returns whether it is grown.
+    pub fn try_grow(&mut self, bytes: usize) -> bool {
+        if self.tracker.try_reserve(bytes) {
+            self.grow(bytes);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Grows the reservation by `bytes` even if the task budget is exceeded.
+    pub fn force_grow(&mut self, bytes: usize) {
+        self.tracker.force_reserve(bytes);
+        self.grow(bytes);
+    }
+
+    /// Releases all memory of the reservation.
+    pub fn clear(&mut self) {
+        self.tracker.release(self.bytes);
+        if let Some(operator_stats) = &self.operator_stats {
+            operator_stats.shrink_memory(self.bytes);
+        }
+        self.bytes = 0;
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_reservation() {
+        let tracker = Arc::new(TaskMemoryTracker::new(100));
+        let mut r1 = tracker.reservation();
+        let mut r2 = tracker.reservation();
+        assert!(r1.try_grow(60));
+        assert!(!r2.try_grow(50));
+        assert!(r2.try_grow(40));
+        assert_eq!(tracker.used(), 100);
+
+        r2.force_grow(10);
+        assert_eq!(tracker.used(), 110);
+        assert!(!r1.try_grow(1));
+
+        r1.clear();
+        assert_eq!(r1.size(), 0);
+        assert_eq!(tracker.used(), 50);
+        drop(r2);
+        assert_eq!(tracker.used(), 0);
+    }
+
+    #[test]
+    fn test_memory_reservation_operator_stats() {
+        let tracker = Arc::new(TaskMemoryTracker::new(100));
+        let operator_stats = OperatorStatsRef::default();
+        let mut r = tracker
+            .reservation()
+            .with_operator_stats(operator_stats.clone());
+        assert!(r.try_grow(60));
+        r.clear();
+        r.force_grow(30);
+        drop(r);
+        assert_eq!(operator_stats.peak_memory_bytes(), 60);
+        assert_eq!(tracker.used(), 0);
+    }
+}
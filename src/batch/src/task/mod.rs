@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub use env::*;
+pub use memory_tracker::*;
 pub use task_::*;
 pub use task_manager::*;
 
@@ -21,6 +22,7 @@ mod channel;
 mod env;
 mod fifo_channel;
 mod hash_shuffle_channel;
+mod memory_tracker;
 mod task_;
 mod task_manager;
 
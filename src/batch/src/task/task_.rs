@@ -20,7 +20,7 @@ use risingwave_common::array::DataChunk;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_pb::plan::{PlanFragment, TaskId as ProstTaskId, TaskOutputId as ProstOutputId};
 use risingwave_pb::task_service::task_info::TaskStatus;
-use risingwave_pb::task_service::GetDataResponse;
+use risingwave_pb::task_service::{GetDataResponse, TaskInfo};
 use tracing_futures::Instrument;
 
 use crate::executor::{BoxedExecutor, ExecutorBuilder};
@@ -173,7 +173,7 @@ pub struct BatchTaskExecution {
     /// Receivers data of the task.   
     receivers: Mutex<Vec<Option<ChanReceiverImpl>>>,
 
-    /// Global environment of task execution.
+    /// Environment of task execution, cloned for this task.
     env: BatchEnvironment,
 
     /// The execution failure.
@@ -194,7 +194,7 @@ impl BatchTaskExecution {
             plan,
             state: Mutex::new(TaskStatus::Pending),
             receivers: Mutex::new(Vec::new()),
-            env,
+            env: env.clone_for_task(),
             failure: Arc::new(Mutex::new(None)),
             epoch,
         })
@@ -297,6 +297,15 @@ impl BatchTaskExecution {
         self.failure.lock().clone()
     }
 
+    /// Returns the status of the task and the runtime statistics of its operators.
+    pub fn get_task_info(&self) -> TaskInfo {
+        TaskInfo {
+            task_id: Some(self.task_id.to_prost()),
+            task_status: *self.state.lock() as i32,
+            operator_stats: self.env.task_stats().to_prost(),
+        }
+    }
+
     pub fn check_if_running(&self) -> Result<()> {
         if *self.state.lock() != TaskStatus::Running {
             return Err(ErrorCode::InternalError(format!(
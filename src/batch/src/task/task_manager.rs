@@ -19,6 +19,7 @@ use parking_lot::Mutex;
 use risingwave_common::error::ErrorCode::{self, TaskNotFound};
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::plan::{PlanFragment, TaskId as ProstTaskId, TaskOutputId as ProstOutputId};
+use risingwave_pb::task_service::TaskInfo;
 
 use crate::task::env::BatchEnvironment;
 use crate::task::{BatchTaskExecution, TaskId, TaskOutput};
@@ -88,6 +89,15 @@ impl BatchManager {
         }
     }
 
+    pub fn get_task_info(&self, task_id: &TaskId) -> Result<TaskInfo> {
+        Ok(self
+            .tasks
+            .lock()
+            .get(task_id)
+            .ok_or(TaskNotFound)?
+            .get_task_info())
+    }
+
     pub fn get_error(&self, task_id: &TaskId) -> Result<Option<RwError>> {
         Ok(self
             .tasks
@@ -176,4 +186,48 @@ mod tests {
             .to_string()
             .contains("can not create duplicate task with the same id"));
     }
+
+    #[tokio::test]
+    async fn test_task_info() {
+        use risingwave_pb::plan::*;
+
+        let manager = BatchManager::new();
+        let plan = PlanFragment {
+            root: Some(PlanNode {
+                children: vec![],
+                identity: "Values".to_string(),
+                node_body: Some(NodeBody::Values(ValuesNode {
+                    tuples: vec![values_node::ExprTuple { cells: vec![] }; 2],
+                    fields: vec![],
+                })),
+            }),
+            exchange_info: Some(ExchangeInfo {
+                mode: DistributionMode::Single as i32,
+                distribution: None,
+            }),
+        };
+        let task_id = TaskId {
+            ..Default::default()
+        };
+        manager
+            .fire_task(BatchEnvironment::for_test(), &task_id, plan, 0)
+            .unwrap();
+
+        let mut output = manager
+            .take_output(&ProstTaskOutputId {
+                task_id: Some(task_id.clone()),
+                output_id: 0,
+            })
+            .unwrap();
+        while output.direct_take_data().await.unwrap().is_some() {}
+
+        let task_info = manager
+            .get_task_info(&crate::task::TaskId::from(&task_id))
+            .unwrap();
+        assert_eq!(task_info.task_id, Some(task_id));
+        assert_eq!(task_info.operator_stats.len(), 1);
+        assert_eq!(task_info.operator_stats[0].identity, "Values");
+        assert_eq!(task_info.operator_stats[0].rows, 2);
+        assert_eq!(task_info.operator_stats[0].chunks, 1);
+    }
 }
@@ -37,6 +37,17 @@ impl BoolArray {
         }
         builder.finish()
     }
+
+    /// Builds an array directly from a bitmap of values and a null bitmap.
+    pub fn from_parts(data: Bitmap, bitmap: Bitmap) -> Self {
+        assert_eq!(data.len(), bitmap.len());
+        Self { bitmap, data }
+    }
+
+    /// Returns the bitmap of values. Bits of null slots are unspecified.
+    pub fn values(&self) -> &Bitmap {
+        &self.data
+    }
 }
 
 impl Array for BoolArray {
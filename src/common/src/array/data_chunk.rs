@@ -17,7 +17,7 @@ use std::fmt;
 use std::hash::BuildHasher;
 use std::sync::Arc;
 
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use risingwave_pb::data::DataChunk as ProstDataChunk;
 
 use crate::array::column::Column;
@@ -144,6 +144,15 @@ impl DataChunk {
         }
     }
 
+    /// Returns the indices of the visible rows in ascending order, i.e. the selection vector of the
+    /// chunk, without allocating it.
+    pub fn visible_row_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        match &self.visibility {
+            Some(vis) => Either::Left(vis.iter_ones()),
+            None => Either::Right(0..self.cardinality),
+        }
+    }
+
     pub fn builder() -> DataChunkBuilder {
         DataChunkBuilder::new()
     }
@@ -447,8 +456,11 @@ pub type DataChunkRef = Arc<DataChunk>;
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use crate::array::column::Column;
     use crate::array::*;
+    use crate::buffer::Bitmap;
     use crate::{column, column_nonnull};
 
     #[test]
@@ -535,6 +547,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_visible_row_indices() {
+        let chunk = DataChunk::new(vec![column_nonnull!(I64Array, [1, 2, 3, 4])], None);
+        assert_eq!(chunk.visible_row_indices().collect_vec(), vec![0, 1, 2, 3]);
+        let chunk = chunk.with_visibility(Bitmap::from_bool_slice(&[false, true, false, true]));
+        assert_eq!(chunk.visible_row_indices().collect_vec(), vec![1, 3]);
+    }
+
     #[test]
     fn test_to_pretty_string() {
         let chunk = DataChunk::new(
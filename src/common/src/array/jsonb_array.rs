@@ -0,0 +1,626 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::{fmt, io};
+
+use bytes::{Buf, BufMut};
+use risingwave_pb::data::buffer::CompressionType;
+use risingwave_pb::data::{Array as ProstArray, ArrayType, Buffer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+
+use super::{Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, ArrayIterator, ArrayMeta};
+use crate::array::NULL_VAL_FOR_HASH;
+use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::error::Result;
+use crate::types::OrderedF64;
+
+/// An owned JSONB value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonbVal(Box<Value>);
+
+/// A reference to a JSONB value, which may be nested in another JSONB value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JsonbRef<'a>(&'a Value);
+
+impl JsonbVal {
+    pub fn new(value: Value) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn into_inner(self) -> Value {
+        *self.0
+    }
+
+    /// Deserialize a value serialized by [`JsonbRef::serialize`].
+    pub fn deserialize(
+        deserializer: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        deserialize_value(deserializer).map(Self::new)
+    }
+}
+
+impl FromStr for JsonbVal {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        serde_json::from_str(s).map(Self::new)
+    }
+}
+
+impl From<Value> for JsonbVal {
+    fn from(value: Value) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Hash for JsonbVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+impl PartialOrd for JsonbVal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbVal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
+impl fmt::Display for JsonbVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl JsonbVal {
+    pub fn as_ref(&self) -> JsonbRef<'_> {
+        JsonbRef(&self.0)
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn value(self) -> &'a Value {
+        self.0
+    }
+
+    /// Returns the name of the JSON type, as `jsonb_typeof` does.
+    pub fn type_name(self) -> &'static str {
+        match self.0 {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Returns the field with the given key if this is an object, as `jsonb -> text` does.
+    pub fn access_object_field(self, key: &str) -> Option<Self> {
+        self.0.as_object()?.get(key).map(Self)
+    }
+
+    /// Returns the element at the given index if this is an array, as `jsonb -> int` does.
+    /// Negative indexes count from the end of the array.
+    pub fn access_array_element(self, idx: i64) -> Option<Self> {
+        let array = self.0.as_array()?;
+        let idx = if idx < 0 {
+            array.len().checked_sub(idx.unsigned_abs() as usize)?
+        } else {
+            idx as usize
+        };
+        array.get(idx).map(Self)
+    }
+
+    /// Follows the path of keys or array indexes, as `jsonb #> text[]` does.
+    pub fn access_path<'b>(self, path: impl IntoIterator<Item = &'b str>) -> Option<Self> {
+        let mut current = self;
+        for step in path {
+            current = match current.0 {
+                Value::Object(_) => current.access_object_field(step)?,
+                Value::Array(_) => current.access_array_element(step.parse().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Returns the text form of the value, as `->>` does. A JSON string is returned without
+    /// quotes and a JSON null is returned as `None`.
+    pub fn as_text(self) -> Option<String> {
+        match self.0 {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// Returns the elements if this is an array.
+    pub fn array_elements(self) -> Option<impl Iterator<Item = JsonbRef<'a>>> {
+        Some(self.0.as_array()?.iter().map(Self))
+    }
+
+    /// Whether `self` contains `other`, as `@>` does.
+    ///
+    /// An object contains another object if it has all the keys of the other and each value
+    /// contains the corresponding one. An array contains another array if each element of the
+    /// other is contained by some element of it. As a special case, an array also contains a
+    /// scalar that is one of its top-level elements.
+    pub fn contains(self, other: Self) -> bool {
+        match (self.0, other.0) {
+            (Value::Object(l), Value::Object(r)) => r.iter().all(|(key, r_value)| {
+                l.get(key)
+                    .map_or(false, |l_value| Self(l_value).contains(Self(r_value)))
+            }),
+            (Value::Array(l), Value::Array(r)) => r.iter().all(|r_value| {
+                l.iter()
+                    .any(|l_value| Self(l_value).contains(Self(r_value)))
+            }),
+            (Value::Array(l), r) if !r.is_object() => l.contains(r),
+            (l, r) => l == r,
+        }
+    }
+
+    /// Serialize to memcomparable format.
+    ///
+    /// The order of the encoded bytes is consistent with [`Ord`] on [`JsonbRef`].
+    pub fn serialize(
+        self,
+        serializer: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        type_rank(self.0).serialize(&mut *serializer)?;
+        match self.0 {
+            Value::Null => {}
+            Value::Bool(v) => v.serialize(&mut *serializer)?,
+            Value::Number(v) => {
+                number_to_f64(v).serialize(&mut *serializer)?;
+                v.to_string().serialize(&mut *serializer)?;
+            }
+            Value::String(v) => v.serialize(&mut *serializer)?,
+            Value::Array(v) => {
+                for element in v {
+                    1u8.serialize(&mut *serializer)?;
+                    Self(element).serialize(serializer)?;
+                }
+                0u8.serialize(&mut *serializer)?;
+            }
+            Value::Object(v) => {
+                for (key, value) in sorted_entries(v) {
+                    1u8.serialize(&mut *serializer)?;
+                    key.serialize(&mut *serializer)?;
+                    Self(value).serialize(serializer)?;
+                }
+                0u8.serialize(&mut *serializer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rank of each JSON type in the sort order, following PostgreSQL:
+/// `Object > Array > Boolean > Number > String > Null`.
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::String(_) => 1,
+        Value::Number(_) => 2,
+        Value::Bool(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+fn number_to_f64(number: &Number) -> f64 {
+    // Always `Some` without the `arbitrary_precision` feature.
+    number.as_f64().unwrap()
+}
+
+fn sorted_entries(map: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|(l, _), (r, _)| l.cmp(r));
+    entries
+}
+
+fn deserialize_value(
+    deserializer: &mut memcomparable::Deserializer<impl Buf>,
+) -> memcomparable::Result<Value> {
+    Ok(match u8::deserialize(&mut *deserializer)? {
+        0 => Value::Null,
+        1 => Value::String(String::deserialize(&mut *deserializer)?),
+        2 => {
+            f64::deserialize(&mut *deserializer)?;
+            let text = String::deserialize(&mut *deserializer)?;
+            Value::Number(
+                Number::from_str(&text)
+                    .map_err(|e| memcomparable::Error::Message(e.to_string()))?,
+            )
+        }
+        3 => Value::Bool(bool::deserialize(&mut *deserializer)?),
+        4 => {
+            let mut elements = vec![];
+            while u8::deserialize(&mut *deserializer)? == 1 {
+                elements.push(deserialize_value(deserializer)?);
+            }
+            Value::Array(elements)
+        }
+        5 => {
+            let mut map = Map::new();
+            while u8::deserialize(&mut *deserializer)? == 1 {
+                let key = String::deserialize(&mut *deserializer)?;
+                map.insert(key, deserialize_value(deserializer)?);
+            }
+            Value::Object(map)
+        }
+        tag => {
+            return Err(memcomparable::Error::Message(format!(
+                "invalid jsonb type tag: {}",
+                tag
+            )))
+        }
+    })
+}
+
+impl Hash for JsonbRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        type_rank(self.0).hash(state);
+        match self.0 {
+            Value::Null => {}
+            Value::Bool(v) => v.hash(state),
+            Value::Number(v) => v.to_string().hash(state),
+            Value::String(v) => v.hash(state),
+            Value::Array(v) => {
+                v.len().hash(state);
+                v.iter().for_each(|element| Self(element).hash(state));
+            }
+            Value::Object(v) => {
+                v.len().hash(state);
+                for (key, value) in sorted_entries(v) {
+                    key.hash(state);
+                    Self(value).hash(state);
+                }
+            }
+        }
+    }
+}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn cmp_seq(l: impl Iterator<Item = Ordering>, l_len: usize, r_len: usize) -> Ordering {
+            for ord in l {
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            l_len.cmp(&r_len)
+        }
+
+        match (self.0, other.0) {
+            (Value::Bool(l), Value::Bool(r)) => l.cmp(r),
+            (Value::Number(l), Value::Number(r)) => OrderedF64::from(number_to_f64(l))
+                .cmp(&OrderedF64::from(number_to_f64(r)))
+                .then_with(|| l.to_string().cmp(&r.to_string())),
+            (Value::String(l), Value::String(r)) => l.cmp(r),
+            (Value::Array(l), Value::Array(r)) => cmp_seq(
+                l.iter().zip(r).map(|(l, r)| Self(l).cmp(&Self(r))),
+                l.len(),
+                r.len(),
+            ),
+            (Value::Object(l), Value::Object(r)) => {
+                let (l, r) = (sorted_entries(l), sorted_entries(r));
+                cmp_seq(
+                    l.iter().zip(&r).map(|((lk, lv), (rk, rv))| {
+                        lk.cmp(rk).then_with(|| Self(lv).cmp(&Self(rv)))
+                    }),
+                    l.len(),
+                    r.len(),
+                )
+            }
+            (l, r) => type_rank(l).cmp(&type_rank(r)),
+        }
+    }
+}
+
+/// Formats JSON like PostgreSQL does, with a space after each `,` and `:`.
+struct PgFormatter;
+
+impl serde_json::ser::Formatter for PgFormatter {
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+}
+
+impl fmt::Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = vec![];
+        let mut serializer = serde_json::Serializer::with_formatter(&mut buf, PgFormatter);
+        self.0.serialize(&mut serializer).map_err(|_| fmt::Error)?;
+        // SAFETY: `serde_json` only writes valid UTF-8.
+        f.write_str(unsafe { std::str::from_utf8_unchecked(&buf) })
+    }
+}
+
+/// `JsonbArray` is a collection of JSONB values.
+#[derive(Debug)]
+pub struct JsonbArray {
+    bitmap: Bitmap,
+    data: Vec<Value>,
+}
+
+impl Array for JsonbArray {
+    type Builder = JsonbArrayBuilder;
+    type Iter<'a> = ArrayIterator<'a, Self>;
+    type OwnedItem = JsonbVal;
+    type RefItem<'a> = JsonbRef<'a>;
+
+    fn value_at(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null(idx) {
+            Some(JsonbRef(&self.data[idx]))
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayIterator::new(self)
+    }
+
+    /// Non-null values are encoded with [`JsonbRef::serialize`] one after another in a single
+    /// buffer.
+    fn to_protobuf(&self) -> ProstArray {
+        let mut serializer = memcomparable::Serializer::new(vec![]);
+        for value in self.iter().flatten() {
+            value.serialize(&mut serializer).unwrap();
+        }
+        let buffer = Buffer {
+            compression: CompressionType::None as i32,
+            body: serializer.into_inner(),
+        };
+        ProstArray {
+            null_bitmap: Some(self.null_bitmap().to_protobuf()),
+            values: vec![buffer],
+            array_type: ArrayType::Jsonb as i32,
+            struct_array_data: None,
+            list_array_data: None,
+        }
+    }
+
+    fn null_bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+    }
+
+    fn hash_at<H: Hasher>(&self, idx: usize, state: &mut H) {
+        if !self.is_null(idx) {
+            JsonbRef(&self.data[idx]).hash(state);
+        } else {
+            NULL_VAL_FOR_HASH.hash(state);
+        }
+    }
+
+    fn create_builder(&self, capacity: usize) -> Result<ArrayBuilderImpl> {
+        let array_builder = JsonbArrayBuilder::new(capacity)?;
+        Ok(ArrayBuilderImpl::Jsonb(array_builder))
+    }
+}
+
+impl JsonbArray {
+    pub fn from_protobuf(array: &ProstArray) -> Result<ArrayImpl> {
+        ensure!(
+            array.get_values().len() == 1,
+            "Must have only 1 buffer in a jsonb array"
+        );
+        let bitmap: Bitmap = array.get_null_bitmap()?.try_into()?;
+        let mut deserializer =
+            memcomparable::Deserializer::new(array.get_values()[0].get_body().as_slice());
+        let mut builder = JsonbArrayBuilder::new(bitmap.len())?;
+        for not_null in bitmap.iter() {
+            if not_null {
+                let value = JsonbVal::deserialize(&mut deserializer)?;
+                builder.append(Some(value.as_ref()))?;
+            } else {
+                builder.append(None)?;
+            }
+        }
+        Ok(builder.finish()?.into())
+    }
+}
+
+/// `JsonbArrayBuilder` use `JsonbRef` to build a `JsonbArray`.
+#[derive(Debug)]
+pub struct JsonbArrayBuilder {
+    bitmap: BitmapBuilder,
+    data: Vec<Value>,
+}
+
+impl ArrayBuilder for JsonbArrayBuilder {
+    type ArrayType = JsonbArray;
+
+    fn new_with_meta(capacity: usize, _meta: ArrayMeta) -> Result<Self> {
+        Ok(Self {
+            bitmap: BitmapBuilder::with_capacity(capacity),
+            data: Vec::with_capacity(capacity),
+        })
+    }
+
+    fn append(&mut self, value: Option<JsonbRef<'_>>) -> Result<()> {
+        match value {
+            Some(x) => {
+                self.bitmap.append(true);
+                self.data.push(x.0.clone());
+            }
+            None => {
+                self.bitmap.append(false);
+                self.data.push(Value::Null);
+            }
+        }
+        Ok(())
+    }
+
+    fn append_array(&mut self, other: &JsonbArray) -> Result<()> {
+        for bit in other.bitmap.iter() {
+            self.bitmap.append(bit);
+        }
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<JsonbArray> {
+        Ok(JsonbArray {
+            bitmap: self.bitmap.finish(),
+            data: self.data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_jsonb_display_and_access() {
+        let value = jsonb(r#"{"a": {"b": [1, "x", null]}, "c": true}"#);
+        assert_eq!(
+            value.to_string(),
+            r#"{"a": {"b": [1, "x", null]}, "c": true}"#
+        );
+
+        let value = value.as_ref();
+        assert_eq!(value.type_name(), "object");
+        let b = value.access_path(["a", "b"]).unwrap();
+        assert_eq!(b.type_name(), "array");
+        assert_eq!(b.access_array_element(1).unwrap().as_text().unwrap(), "x");
+        assert_eq!(b.access_array_element(-3).unwrap().to_string(), "1");
+        assert_eq!(b.access_array_element(-1).unwrap().as_text(), None);
+        assert!(b.access_array_element(3).is_none());
+        assert!(value.access_object_field("d").is_none());
+        assert!(value.access_path(["a", "b", "x"]).is_none());
+        assert_eq!(b.array_elements().unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_jsonb_contains() {
+        let contains = |l: &str, r: &str| jsonb(l).as_ref().contains(jsonb(r).as_ref());
+        assert!(contains(r#"{"a": 1, "b": {"c": 2}}"#, r#"{"b": {}}"#));
+        assert!(contains(r#"{"a": 1, "b": {"c": 2}}"#, r#"{"b": {"c": 2}}"#));
+        assert!(!contains(r#"{"a": 1}"#, r#"{"a": 2}"#));
+        assert!(contains("[1, [2, 3]]", "[[3], 1]"));
+        assert!(contains("[1, 2]", "2"));
+        assert!(!contains("[[1, 2]]", "1"));
+        assert!(contains(r#""a""#, r#""a""#));
+    }
+
+    #[test]
+    fn test_jsonb_serialize() {
+        let values = [
+            "null",
+            r#""""#,
+            r#""b""#,
+            "-1.5",
+            "1",
+            "1.0",
+            "2",
+            "false",
+            "true",
+            "[]",
+            "[1]",
+            "[1, 2]",
+            "[2]",
+            "{}",
+            r#"{"a": 2}"#,
+            r#"{"a": 2, "b": 1}"#,
+            r#"{"b": 1}"#,
+        ]
+        .map(jsonb);
+        let serialize = |value: &JsonbVal| {
+            let mut serializer = memcomparable::Serializer::new(vec![]);
+            value.as_ref().serialize(&mut serializer).unwrap();
+            serializer.into_inner()
+        };
+        for pair in values.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+            assert!(serialize(&pair[0]) < serialize(&pair[1]));
+        }
+        for value in &values {
+            let bytes = serialize(value);
+            let mut deserializer = memcomparable::Deserializer::new(bytes.as_slice());
+            assert_eq!(&JsonbVal::deserialize(&mut deserializer).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_jsonb_array_protobuf() {
+        let mut builder = JsonbArrayBuilder::new(3).unwrap();
+        builder.append(Some(jsonb("[1, 2]").as_ref())).unwrap();
+        builder.append(None).unwrap();
+        builder
+            .append(Some(jsonb(r#"{"a": "b"}"#).as_ref()))
+            .unwrap();
+        let array = builder.finish().unwrap();
+        let array: ArrayImpl = array.into();
+        assert_eq!(
+            ArrayImpl::from_protobuf(&array.to_protobuf(), 3).unwrap(),
+            array
+        );
+    }
+}
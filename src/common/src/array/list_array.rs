@@ -17,16 +17,21 @@ use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::hash::{Hash, Hasher};
 
+use bytes::{Buf, BufMut};
 use itertools::EitherOrBoth::{Both, Left, Right};
 use itertools::Itertools;
 use risingwave_pb::data::{Array as ProstArray, ArrayType as ProstArrayType, ListArrayData};
+use serde::{Deserialize, Serialize};
 
 use super::{
     Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, ArrayIterator, ArrayMeta, NULL_VAL_FOR_HASH,
 };
 use crate::buffer::{Bitmap, BitmapBuilder};
 use crate::error::Result;
-use crate::types::{DataType, Datum, DatumRef, Scalar, ScalarRefImpl};
+use crate::types::{
+    deserialize_datum_from, serialize_datum_ref_into, DataType, Datum, DatumRef, Scalar,
+    ScalarRefImpl,
+};
 
 /// This is a naive implementation of list array.
 /// We will eventually move to a more efficient flatten implementation.
@@ -172,7 +177,9 @@ impl Array for ListArray {
 
     fn hash_at<H: std::hash::Hasher>(&self, idx: usize, state: &mut H) {
         if !self.is_null(idx) {
-            self.value.hash_at(idx, state)
+            for value_idx in self.offsets[idx]..self.offsets[idx + 1] {
+                self.value.hash_at(value_idx, state);
+            }
         } else {
             NULL_VAL_FOR_HASH.hash(state);
         }
@@ -259,8 +266,8 @@ pub struct ListValue {
 }
 
 impl fmt::Display for ListValue {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_scalar_ref(), f)
     }
 }
 
@@ -280,6 +287,26 @@ impl ListValue {
     pub fn new(values: Vec<Datum>) -> Self {
         Self { values }
     }
+
+    pub fn values(&self) -> &[Datum] {
+        &self.values
+    }
+
+    /// Deserialize a list serialized by [`ListRef::serialize`].
+    pub fn deserialize(
+        datatype: &DataType,
+        deserializer: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        let mut values = vec![];
+        loop {
+            match u8::deserialize(&mut *deserializer)? {
+                0 => break,
+                1 => values.push(deserialize_datum_from(datatype, deserializer)?),
+                tag => return Err(memcomparable::Error::InvalidTagEncoding(tag as _)),
+            }
+        }
+        Ok(Self::new(values))
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -301,6 +328,19 @@ impl<'a> ListRef<'a> {
                 .collect::<Vec<DatumRef<'a>>>(),
         }
     }
+
+    /// Serialize the list in a memcomparable format. Every element is preceded by `1` and the list
+    /// ends with `0`, so a list sorts before the lists it is a prefix of.
+    pub fn serialize(
+        &self,
+        serializer: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        for datum_ref in self.values_ref() {
+            1u8.serialize(&mut *serializer)?;
+            serialize_datum_ref_into(&datum_ref, serializer)?;
+        }
+        0u8.serialize(serializer)
+    }
 }
 
 impl Hash for ListRef<'_> {
@@ -359,9 +399,20 @@ impl Debug for ListRef<'_> {
     }
 }
 
+/// Formats the list like postgres, e.g. `{1,NULL,3}`.
 impl Display for ListRef<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (idx, datum_ref) in self.values_ref().into_iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            match datum_ref {
+                Some(scalar) => write!(f, "{}", scalar)?,
+                None => write!(f, "NULL")?,
+            }
+        }
+        write!(f, "}}")
     }
 }
 
@@ -585,6 +636,38 @@ mod tests {
         assert_eq!(nestarray.values_vec(), nested_list_values);
     }
 
+    #[test]
+    fn test_list_display_and_serialize() {
+        let value = ListValue::new(vec![
+            Some(ScalarImpl::List(ListValue::new(vec![Some(1.into()), None]))),
+            Some(ScalarImpl::List(ListValue::new(vec![]))),
+        ]);
+        assert_eq!(value.to_string(), "{{1,NULL},{}}");
+
+        let datatype = DataType::List {
+            datatype: Box::new(DataType::Int32),
+        };
+        let mut serializer = memcomparable::Serializer::new(vec![]);
+        value.as_scalar_ref().serialize(&mut serializer).unwrap();
+        let bytes = serializer.into_inner();
+        let mut deserializer = memcomparable::Deserializer::new(bytes.as_slice());
+        assert_eq!(
+            ListValue::deserialize(&datatype, &mut deserializer).unwrap(),
+            value
+        );
+
+        // A list sorts before the lists it is a prefix of.
+        let serialize = |value: ListValue| {
+            let mut serializer = memcomparable::Serializer::new(vec![]);
+            value.as_scalar_ref().serialize(&mut serializer).unwrap();
+            serializer.into_inner()
+        };
+        assert_lt!(
+            serialize(ListValue::new(vec![Some(1.into())])),
+            serialize(ListValue::new(vec![Some(1.into()), Some(0.into())]))
+        );
+    }
+
     #[test]
     fn test_list_value_cmp() {
         // ARRAY[1, 1] < ARRAY[1, 2, 1]
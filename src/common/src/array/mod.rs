@@ -23,6 +23,7 @@ pub mod data_chunk_iter;
 mod decimal_array;
 pub mod interval_array;
 mod iterator;
+pub mod jsonb_array;
 pub mod list_array;
 mod macros;
 mod primitive_array;
@@ -47,6 +48,7 @@ pub use data_chunk_iter::{Row, RowDeserializer, RowRef};
 pub use decimal_array::{DecimalArray, DecimalArrayBuilder};
 pub use interval_array::{IntervalArray, IntervalArrayBuilder};
 pub use iterator::ArrayIterator;
+pub use jsonb_array::{JsonbArray, JsonbArrayBuilder, JsonbRef, JsonbVal};
 pub use list_array::{ListArray, ListArrayBuilder, ListRef, ListValue};
 use paste::paste;
 pub use primitive_array::{PrimitiveArray, PrimitiveArrayBuilder, PrimitiveArrayItemType};
@@ -211,12 +213,10 @@ trait CompactableArray: Array {
 
 impl<A: Array> CompactableArray for A {
     fn compact(&self, visibility: &Bitmap, cardinality: usize) -> Result<Self> {
-        use itertools::Itertools;
+        assert_eq!(self.len(), visibility.len());
         let mut builder = A::Builder::new_with_meta(cardinality, self.array_meta())?;
-        for (elem, visible) in self.iter().zip_eq(visibility.iter()) {
-            if visible {
-                builder.append(elem)?;
-            }
+        for idx in visibility.iter_ones() {
+            builder.append(self.value_at(idx))?;
         }
         builder.finish()
     }
@@ -248,7 +248,8 @@ macro_rules! for_all_variants {
             { NaiveDateTime, naivedatetime, NaiveDateTimeArray, NaiveDateTimeArrayBuilder },
             { NaiveTime, naivetime, NaiveTimeArray, NaiveTimeArrayBuilder },
             { Struct, struct, StructArray, StructArrayBuilder },
-            { List, list, ListArray, ListArrayBuilder }
+            { List, list, ListArray, ListArrayBuilder },
+            { Jsonb, jsonb, JsonbArray, JsonbArrayBuilder }
         }
     };
 }
@@ -312,6 +313,12 @@ impl From<NaiveTimeArray> for ArrayImpl {
     }
 }
 
+impl From<JsonbArray> for ArrayImpl {
+    fn from(arr: JsonbArray) -> Self {
+        Self::Jsonb(arr)
+    }
+}
+
 impl From<StructArray> for ArrayImpl {
     fn from(arr: StructArray) -> Self {
         Self::Struct(arr)
@@ -574,6 +581,7 @@ impl ArrayImpl {
             ProstArrayType::Interval => read_interval_unit_array(array, cardinality)?,
             ProstArrayType::Struct => StructArray::from_protobuf(array)?,
             ProstArrayType::List => ListArray::from_protobuf(array)?,
+            ProstArrayType::Jsonb => JsonbArray::from_protobuf(array)?,
         };
         Ok(array)
     }
@@ -100,6 +100,17 @@ impl<T: PrimitiveArrayItemType> PrimitiveArray<T> {
         }
         builder.finish()
     }
+
+    /// Builds an array directly from its value buffer and null bitmap.
+    pub fn from_parts(data: Vec<T>, bitmap: Bitmap) -> Self {
+        assert_eq!(data.len(), bitmap.len());
+        Self { bitmap, data }
+    }
+
+    /// Returns the value buffer. Slots of null values hold `T::default()`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
 }
 
 impl<T: PrimitiveArrayItemType> Array for PrimitiveArray<T> {
@@ -18,6 +18,7 @@ use std::fmt::{Debug, Display};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use bytes::{Buf, BufMut};
 use itertools::Itertools;
 use risingwave_pb::data::{Array as ProstArray, ArrayType as ProstArrayType, StructArrayData};
 
@@ -26,7 +27,10 @@ use super::{
 };
 use crate::buffer::{Bitmap, BitmapBuilder};
 use crate::error::Result;
-use crate::types::{DataType, Datum, DatumRef, Scalar, ScalarRefImpl};
+use crate::types::{
+    deserialize_datum_from, serialize_datum_ref_into, DataType, Datum, DatumRef, Scalar,
+    ScalarRefImpl,
+};
 
 /// This is a naive implementation of struct array.
 /// We will eventually move to a more efficient flatten implementation.
@@ -262,8 +266,8 @@ pub struct StructValue {
 }
 
 impl fmt::Display for StructValue {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_scalar_ref(), f)
     }
 }
 
@@ -283,6 +287,22 @@ impl StructValue {
     pub fn new(fields: Vec<Datum>) -> Self {
         Self { fields }
     }
+
+    pub fn fields(&self) -> &[Datum] {
+        &self.fields
+    }
+
+    /// Deserialize a struct serialized by [`StructRef::serialize`].
+    pub fn deserialize(
+        fields: &[DataType],
+        deserializer: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        fields
+            .iter()
+            .map(|field| deserialize_datum_from(field, deserializer))
+            .try_collect()
+            .map(Self::new)
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -304,6 +324,18 @@ impl<'a> StructRef<'a> {
                 .collect::<Vec<DatumRef<'a>>>(),
         }
     }
+
+    /// Serialize the fields one after another. The number of fields is given by the type, so no
+    /// length or terminator is needed.
+    pub fn serialize(
+        &self,
+        serializer: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        for datum_ref in self.fields_ref() {
+            serialize_datum_ref_into(&datum_ref, serializer)?;
+        }
+        Ok(())
+    }
 }
 
 impl Hash for StructRef<'_> {
@@ -365,9 +397,19 @@ impl Debug for StructRef<'_> {
     }
 }
 
+/// Formats the struct like postgres, e.g. `(1,,abc)`. Null fields are left empty.
 impl Display for StructRef<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (idx, datum_ref) in self.fields_ref().into_iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            if let Some(scalar) = datum_ref {
+                write!(f, "{}", scalar)?;
+            }
+        }
+        write!(f, ")")
     }
 }
 
@@ -385,6 +427,7 @@ mod tests {
     use more_asserts::assert_gt;
 
     use super::*;
+    use crate::types::ScalarImpl;
     use crate::{array, try_match_expand};
 
     // Empty struct is allowed in postgres.
@@ -486,4 +529,14 @@ mod tests {
             StructValue::new(vec![Some(1.into()), None]),
         );
     }
+
+    #[test]
+    fn test_struct_value_display() {
+        let value = StructValue::new(vec![
+            Some(1.into()),
+            None,
+            Some(ScalarImpl::Utf8("abc".to_string())),
+        ]);
+        assert_eq!(value.to_string(), "(1,,abc)");
+    }
 }
@@ -36,6 +36,7 @@
 use std::mem;
 use std::ops::{BitAnd, BitOr};
 
+use itertools::Itertools;
 use risingwave_pb::data::Buffer as ProstBuffer;
 
 use crate::array::{Array, BoolArray};
@@ -240,6 +241,63 @@ impl Bitmap {
         })
     }
 
+    /// Packs a slice of booleans into a bitmap, eight bits at a time.
+    pub fn from_bool_slice(bools: &[bool]) -> Bitmap {
+        let bytes = bools
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << i))
+            })
+            .collect::<Vec<_>>();
+        Self::from_bytes(&bytes, bools.len())
+    }
+
+    /// Returns an iterator over the indices of set bits. Zero bytes are skipped as a whole, so this
+    /// is much cheaper than [`Bitmap::iter`] on a sparse bitmap.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        let num_bits = self.num_bits;
+        self.bits.as_slice()[..Self::num_of_used_bytes(num_bits)]
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte != 0)
+            .flat_map(|(byte_idx, byte)| {
+                let byte = *byte;
+                (0..8)
+                    .filter(move |i| byte & (1 << i) != 0)
+                    .map(move |i| byte_idx * 8 + i)
+            })
+            .take_while(move |idx| *idx < num_bits)
+    }
+
+    fn from_bytes(bytes: &[u8], num_bits: usize) -> Bitmap {
+        let num_high_bits = bytes.iter().map(|x| x.count_ones()).sum::<u32>() as usize;
+        Bitmap {
+            bits: Buffer::from_slice(bytes).unwrap(),
+            num_bits,
+            num_high_bits,
+        }
+    }
+
+    fn num_of_used_bytes(num_bits: usize) -> usize {
+        (num_bits + 7) / 8
+    }
+
+    /// Applies a bitwise operation byte by byte. Only the bytes covering `num_bits` are used, so
+    /// bitmaps with different paddings can be combined.
+    fn bitwise_op(&self, rhs: &Bitmap, op: impl Fn(u8, u8) -> u8) -> Result<Bitmap> {
+        ensure!(self.num_bits == rhs.num_bits);
+        let len = Self::num_of_used_bytes(self.num_bits);
+        let bytes = self.bits.as_slice()[..len]
+            .iter()
+            .zip_eq(&rhs.bits.as_slice()[..len])
+            .map(|(a, b)| op(*a, *b))
+            .collect::<Vec<_>>();
+        Ok(Self::from_bytes(&bytes, self.num_bits))
+    }
+
     fn check_idx(&self, idx: usize) -> Result<()> {
         ensure!(idx < self.len());
         Ok(())
@@ -250,7 +308,7 @@ impl<'a, 'b> BitAnd<&'b Bitmap> for &'a Bitmap {
     type Output = Result<Bitmap>;
 
     fn bitand(self, rhs: &'b Bitmap) -> Result<Bitmap> {
-        Ok(Bitmap::from((&self.bits & &rhs.bits)?))
+        self.bitwise_op(rhs, |a, b| a & b)
     }
 }
 
@@ -258,7 +316,7 @@ impl<'a, 'b> BitOr<&'b Bitmap> for &'a Bitmap {
     type Output = Result<Bitmap>;
 
     fn bitor(self, rhs: &'b Bitmap) -> Result<Bitmap> {
-        Ok(Bitmap::from((&self.bits | &rhs.bits)?))
+        self.bitwise_op(rhs, |a, b| a | b)
     }
 }
 
@@ -277,11 +335,8 @@ impl TryFrom<&BoolArray> for Bitmap {
     type Error = RwError;
 
     fn try_from(bools: &BoolArray) -> Result<Bitmap> {
-        let mut builder = BitmapBuilder::default();
-        bools.iter().for_each(|e| {
-            builder.append(e.unwrap_or(false));
-        });
-        Ok(builder.finish())
+        // Null slots are treated as `false`.
+        bools.values() & bools.null_bitmap()
     }
 }
 
@@ -524,4 +579,28 @@ mod tests {
         let bm2 = (vec![false]).try_into().unwrap();
         assert_eq!(bm1, bm2);
     }
+
+    #[test]
+    fn test_bitmap_from_bool_slice() {
+        let bools = [
+            true, false, false, true, true, false, true, false, false, true,
+        ];
+        let bitmap = Bitmap::from_bool_slice(&bools);
+        let expected: Bitmap = bools.to_vec().try_into().unwrap();
+        assert_eq!(bitmap, expected);
+        assert_eq!(bitmap.num_high_bits(), 5);
+        assert_eq!(bitmap.iter_ones().collect_vec(), vec![0, 3, 4, 6, 9]);
+    }
+
+    #[test]
+    fn test_bitwise_and_unaligned() {
+        let b1 = Bitmap::from_bool_slice(&[true, true, false, true, true, false, true, true, true]);
+        let b2: Bitmap = Bitmap::new(9).unwrap();
+        let result = (&b1 & &b2).unwrap();
+        assert_eq!(result.len(), 9);
+        assert_eq!(result.num_high_bits(), 0);
+        let result = (&b1 | &b2).unwrap();
+        assert_eq!(result, b1);
+        assert!((&b1 & &Bitmap::new(8).unwrap()).is_err());
+    }
 }
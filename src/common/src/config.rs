@@ -64,6 +64,15 @@ impl Default for ServerConfig {
 pub struct BatchConfig {
     #[serde(default = "default::chunk_size")]
     pub chunk_size: u32,
+
+    /// Memory budget of each batch task in bytes. Hash aggregation, hash join and sort spill to
+    /// disk once the budget is exceeded.
+    #[serde(default = "default::task_memory_budget")]
+    pub task_memory_budget: usize,
+
+    /// Local directory for the spill files of batch tasks.
+    #[serde(default = "default::spill_directory")]
+    pub spill_directory: String,
 }
 
 impl Default for BatchConfig {
@@ -178,6 +187,18 @@ mod default {
         1024
     }
 
+    pub fn task_memory_budget() -> usize {
+        // 1 GB
+        1073741824
+    }
+
+    pub fn spill_directory() -> String {
+        std::env::temp_dir()
+            .join("risingwave_batch_spill")
+            .to_string_lossy()
+            .to_string()
+    }
+
     pub fn sst_size() -> u32 {
         // 256MB
         268435456
@@ -248,6 +269,7 @@ mod tests {
         let cfg: ComputeNodeConfig = toml::from_str(partial_toml_str).unwrap();
         assert_eq!(cfg.server.heartbeat_interval, 10);
         assert_eq!(cfg.batch.chunk_size, 256);
+        assert_eq!(cfg.batch.task_memory_budget, default::task_memory_budget());
         assert_eq!(cfg.storage.sstable_size, 1024);
         assert_eq!(cfg.storage.block_size, default::block_size());
         assert_eq!(
@@ -22,7 +22,7 @@ use chrono::{Datelike, Timelike};
 use itertools::Itertools;
 
 use crate::array::{
-    Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, DataChunk, ListRef, Row, StructRef,
+    Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, DataChunk, JsonbRef, ListRef, Row, StructRef,
 };
 use crate::error::Result;
 use crate::types::{
@@ -414,6 +414,20 @@ impl<'a> HashKeySerDe<'a> for ListRef<'a> {
     }
 }
 
+impl<'a> HashKeySerDe<'a> for JsonbRef<'a> {
+    type S = Vec<u8>;
+
+    /// This should never be called
+    fn serialize(self) -> Self::S {
+        todo!()
+    }
+
+    /// This should never be called
+    fn deserialize<R: Read>(_source: &mut R) -> Self {
+        todo!()
+    }
+}
+
 pub struct FixedSizeKeySerializer<const N: usize> {
     buffer: [u8; N],
     null_bitmap: u8,
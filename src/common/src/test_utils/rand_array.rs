@@ -25,7 +25,7 @@ use rand::prelude::Distribution;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
 
-use crate::array::{Array, ArrayBuilder, ArrayRef, ListValue, StructValue};
+use crate::array::{Array, ArrayBuilder, ArrayRef, JsonbVal, ListValue, StructValue};
 use crate::types::{
     Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, NativeType,
     Scalar,
@@ -115,6 +115,12 @@ impl RandValue for ListValue {
     }
 }
 
+impl RandValue for JsonbVal {
+    fn rand_value<R: rand::Rng>(rand: &mut R) -> Self {
+        JsonbVal::new(rand.gen::<i32>().into())
+    }
+}
+
 pub fn rand_array<A, R>(rand: &mut R, size: usize) -> A
 where
     A: Array,
@@ -18,7 +18,7 @@ use std::ops::{Add, Sub};
 
 use byteorder::{BigEndian, WriteBytesExt};
 use bytes::BytesMut;
-use num_traits::{CheckedAdd, CheckedSub};
+use num_traits::{CheckedAdd, CheckedNeg, CheckedSub};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
@@ -217,6 +217,15 @@ impl CheckedSub for IntervalUnit {
     }
 }
 
+impl CheckedNeg for IntervalUnit {
+    fn checked_neg(&self) -> Option<Self> {
+        let months = self.months.checked_neg()?;
+        let days = self.days.checked_neg()?;
+        let ms = self.ms.checked_neg()?;
+        Some(IntervalUnit { months, days, ms })
+    }
+}
+
 impl Display for IntervalUnit {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let years = self.months / 12;
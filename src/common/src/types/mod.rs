@@ -42,7 +42,8 @@ pub use ordered_float::IntoOrdered;
 use paste::paste;
 
 use crate::array::{
-    ArrayBuilderImpl, ListRef, ListValue, PrimitiveArrayItemType, StructRef, StructValue,
+    ArrayBuilderImpl, JsonbRef, JsonbVal, ListRef, ListValue, PrimitiveArrayItemType, StructRef,
+    StructValue,
 };
 
 pub type OrderedF32 = ordered_float::OrderedFloat<f32>;
@@ -65,6 +66,7 @@ pub enum DataType {
     Interval,
     Struct { fields: Arc<[DataType]> },
     List { datatype: Box<DataType> },
+    Jsonb,
 }
 
 const DECIMAL_DEFAULT_PRECISION: u32 = 20;
@@ -99,11 +101,14 @@ impl From<&ProstDataType> for DataType {
             TypeName::Interval => DataType::Interval,
             TypeName::Symbol => DataType::Varchar,
             TypeName::Struct => DataType::Struct {
-                fields: Arc::new([]),
+                fields: proto.field_type.iter().map(DataType::from).collect(),
             },
             TypeName::List => DataType::List {
-                datatype: Box::new(DataType::Int32),
+                datatype: Box::new(DataType::from(
+                    proto.field_type.get(0).expect("missing list element type"),
+                )),
             },
+            TypeName::Jsonb => DataType::Jsonb,
         }
     }
 }
@@ -125,9 +130,13 @@ impl DataType {
             DataType::Timestamp => NaiveDateTimeArrayBuilder::new(capacity)?.into(),
             DataType::Timestampz => PrimitiveArrayBuilder::<i64>::new(capacity)?.into(),
             DataType::Interval => IntervalArrayBuilder::new(capacity)?.into(),
-            DataType::Struct { .. } => {
-                todo!()
-            }
+            DataType::Struct { fields } => StructArrayBuilder::new_with_meta(
+                capacity,
+                ArrayMeta::Struct {
+                    children: fields.clone(),
+                },
+            )?
+            .into(),
             DataType::List { datatype } => ListArrayBuilder::new_with_meta(
                 capacity,
                 ArrayMeta::List {
@@ -135,6 +144,7 @@ impl DataType {
                 },
             )?
             .into(),
+            DataType::Jsonb => JsonbArrayBuilder::new(capacity)?.into(),
         })
     }
 
@@ -155,13 +165,20 @@ impl DataType {
             DataType::Interval => TypeName::Interval,
             DataType::Struct { .. } => TypeName::Struct,
             DataType::List { .. } => TypeName::List,
+            DataType::Jsonb => TypeName::Jsonb,
         }
     }
 
     pub fn to_protobuf(&self) -> ProstDataType {
+        let field_type = match self {
+            DataType::Struct { fields } => fields.iter().map(|f| f.to_protobuf()).collect(),
+            DataType::List { datatype } => vec![datatype.to_protobuf()],
+            _ => vec![],
+        };
         ProstDataType {
             type_name: self.prost_type_name() as i32,
             is_nullable: true,
+            field_type,
             ..Default::default()
         }
     }
@@ -184,6 +201,7 @@ impl DataType {
             DataType::Interval => DataSize::Variable,
             DataType::Struct { .. } => DataSize::Variable,
             DataType::List { .. } => DataSize::Variable,
+            DataType::Jsonb => DataSize::Variable,
         }
     }
 
@@ -274,7 +292,8 @@ macro_rules! for_all_scalar_variants {
             { NaiveDateTime, naivedatetime, NaiveDateTimeWrapper, NaiveDateTimeWrapper },
             { NaiveTime, naivetime, NaiveTimeWrapper, NaiveTimeWrapper },
             { Struct, struct, StructValue, StructRef<'scalar> },
-            { List, list, ListValue, ListRef<'scalar> }
+            { List, list, ListValue, ListRef<'scalar> },
+            { Jsonb, jsonb, JsonbVal, JsonbRef<'scalar> }
         }
     };
 }
@@ -539,6 +558,7 @@ impl std::hash::Hash for ScalarImpl {
                     Self::NaiveTime(naivetime) => naivetime.hash(state),
                     Self::Struct(v) => v.hash(state),
                     Self::List(v) => v.hash(state),
+                    Self::Jsonb(v) => v.hash(state),
                 }
             };
         }
@@ -603,6 +623,9 @@ impl ScalarRefImpl<'_> {
             &Self::NaiveTime(v) => {
                 ser.serialize_naivetime(v.0.num_seconds_from_midnight(), v.0.nanosecond())?
             }
+            Self::List(v) => v.serialize(ser)?,
+            Self::Struct(v) => v.serialize(ser)?,
+            Self::Jsonb(v) => v.serialize(ser)?,
             _ => {
                 panic!("Type is unable to be serialized.")
             }
@@ -657,6 +680,9 @@ impl ScalarImpl {
                 let days = de.deserialize_naivedate()?;
                 NaiveDateWrapper::new_with_days(days)?
             }),
+            Ty::List { datatype } => Self::List(ListValue::deserialize(&datatype, de)?),
+            Ty::Struct { fields } => Self::Struct(StructValue::deserialize(&fields, de)?),
+            Ty::Jsonb => Self::Jsonb(JsonbVal::deserialize(de)?),
             _ => {
                 panic!("Type is unable to be deserialized.")
             }
@@ -694,6 +720,39 @@ mod tests {
         assert!(memcmp_minus_1 < memcmp_45745);
     }
 
+    #[test]
+    fn test_nested_type_protobuf() {
+        let ty = DataType::Struct {
+            fields: vec![
+                DataType::Int32,
+                DataType::List {
+                    datatype: Box::new(DataType::Varchar),
+                },
+            ]
+            .into(),
+        };
+        assert_eq!(DataType::from(&ty.to_protobuf()), ty);
+    }
+
+    #[test]
+    fn test_struct_memcomparable() {
+        let ty = DataType::Struct {
+            fields: vec![DataType::Int32, DataType::Varchar].into(),
+        };
+        let datum = Some(ScalarImpl::Struct(StructValue::new(vec![
+            Some(ScalarImpl::Int32(1)),
+            None,
+        ])));
+        let mut serializer = memcomparable::Serializer::new(vec![]);
+        serialize_datum_into(&datum, &mut serializer).unwrap();
+        let data = serializer.into_inner();
+        let mut deserializer = memcomparable::Deserializer::new(data.as_slice());
+        assert_eq!(
+            deserialize_datum_from(&ty, &mut deserializer).unwrap(),
+            datum
+        );
+    }
+
     #[test]
     fn test_issue_2057_ordered_float_memcomparable() {
         use num_traits::*;
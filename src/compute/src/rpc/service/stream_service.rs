@@ -204,6 +204,17 @@ impl StreamService for StreamServiceImpl {
 
         Ok(Response::new(DropSourceResponse { status: None }))
     }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn get_actor_stats(
+        &self,
+        _request: Request<GetActorStatsRequest>,
+    ) -> Result<Response<GetActorStatsResponse>, Status> {
+        Ok(Response::new(GetActorStatsResponse {
+            status: None,
+            actor_stats: self.mgr.get_actor_stats(),
+        }))
+    }
 }
 
 impl StreamServiceImpl {
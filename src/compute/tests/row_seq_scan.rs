@@ -1,3 +1,4 @@
+use std::ops::Bound;
 use std::sync::Arc;
 
 use risingwave_batch::executor::monitor::BatchMetrics;
@@ -19,6 +20,7 @@ use risingwave_common::array::{Array, Row};
 use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema};
 use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
+use risingwave_common::util::ordered::OrderedRowSerializer;
 use risingwave_common::util::sort_util::OrderType;
 use risingwave_storage::memory::MemoryStateStore;
 use risingwave_storage::monitor::StateStoreMetrics;
@@ -128,3 +130,71 @@ async fn test_row_seq_scan() -> Result<()> {
     executor.close().await.unwrap();
     Ok(())
 }
+
+#[tokio::test]
+async fn test_row_seq_scan_with_pk_bounds() -> Result<()> {
+    let memory_state_store = MemoryStateStore::new();
+    let keyspace = Keyspace::executor_root(memory_state_store.clone(), 0x42);
+
+    let column_ids = vec![ColumnId::from(0), ColumnId::from(1)];
+    let mut state =
+        ManagedMViewState::new(keyspace.clone(), column_ids, vec![OrderType::Descending]);
+    for i in 1..=4_i32 {
+        state.put(
+            Row(vec![Some(i.into())]),
+            Row(vec![Some(i.into()), Some((i * 10).into())]),
+        );
+    }
+    state.flush(0).await.unwrap();
+
+    let column_descs = vec![
+        ColumnDesc::unnamed(ColumnId::from(0), DataType::Int32),
+        ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+    ];
+    let table = CellBasedTable::new_adhoc(
+        keyspace,
+        column_descs,
+        Arc::new(StateStoreMetrics::unused()),
+    );
+
+    // The pk is in descending order, so `pk < 4 AND pk >= 2` is the range `(4, 2]` of keys.
+    let encode = |pk: i32| {
+        let mut key = vec![];
+        OrderedRowSerializer::new(vec![OrderType::Descending])
+            .serialize(&Row(vec![Some(pk.into())]), &mut key);
+        key
+    };
+    let mut executor = RowSeqScanExecutor::new_with_pk_bounds(
+        table,
+        (Bound::Excluded(encode(4)), Bound::Included(encode(2))),
+        1024,
+        true,
+        "RowSeqScanExecutor".to_string(),
+        u64::MAX,
+        Arc::new(BatchMetrics::unused()),
+    );
+    executor.open().await.unwrap();
+
+    let res_chunk = executor.next().await?.unwrap();
+    assert_eq!(
+        res_chunk
+            .column_at(0)
+            .array()
+            .as_int32()
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![Some(3), Some(2)]
+    );
+    assert_eq!(
+        res_chunk
+            .column_at(1)
+            .array()
+            .as_int32()
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![Some(30), Some(20)]
+    );
+    assert!(executor.next().await?.is_none());
+    executor.close().await.unwrap();
+    Ok(())
+}
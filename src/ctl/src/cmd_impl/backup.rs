@@ -0,0 +1,54 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod create_backup;
+pub use create_backup::*;
+mod restore_backup;
+pub use restore_backup::*;
+use risingwave_storage::object::ObjectStoreImpl;
+
+/// Layout of a backup under its path:
+///
+/// * `meta`: encoded `MetaBackup`.
+/// * `ssts/{id}.data` and `ssts/{id}.meta`: copies of SSTs in the backed up version.
+/// * `manifest`: encoded `BackupManifest`, written last.
+fn meta_path(path: &str) -> String {
+    format!("{}/meta", path)
+}
+
+fn manifest_path(path: &str) -> String {
+    format!("{}/manifest", path)
+}
+
+fn sst_dir(path: &str) -> String {
+    format!("{}/ssts", path)
+}
+
+/// Copies both data and meta of an SST, whose paths are in the same format as `SstableStore`.
+async fn copy_sst(
+    src: &ObjectStoreImpl,
+    src_dir: &str,
+    dst: &ObjectStoreImpl,
+    dst_dir: &str,
+    sst_id: u64,
+) -> anyhow::Result<()> {
+    for suffix in ["data", "meta"] {
+        let obj = src
+            .read(&format!("{}/{}.{}", src_dir, sst_id, suffix), None)
+            .await?;
+        dst.upload(&format!("{}/{}.{}", dst_dir, sst_id, suffix), obj)
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use prost::Message;
+use risingwave_pb::meta::BackupManifest;
+use risingwave_rpc_client::HummockMetaClient;
+use risingwave_storage::object::parse_remote_object_store;
+
+use super::{copy_sst, manifest_path, meta_path, sst_dir};
+use crate::common::HummockServiceOpts;
+
+/// Backs up meta data and SSTs of the current Hummock version to `path` in object store
+/// `store_url`. SSTs are read from `hummock_dir` in the object store of Hummock.
+pub async fn create_backup(store_url: &str, path: &str, hummock_dir: &str) -> anyhow::Result<()> {
+    let hummock_opts = HummockServiceOpts::from_env()?;
+    let hummock_store = hummock_opts.create_object_store().await?;
+    let backup_store = parse_remote_object_store(store_url).await;
+
+    let meta_client = hummock_opts.meta_opts.create_meta_client().await?;
+    let backup = meta_client.backup_meta().await?;
+    let version = backup.hummock_version.clone().unwrap();
+    tracing::info!("backing up Hummock version {}", version.id);
+
+    // The version stays pinned until its SSTs are copied.
+    let result = async {
+        let mut sst_ids: Vec<_> = version
+            .levels
+            .iter()
+            .flat_map(|level| level.table_infos.iter().map(|sst| sst.id))
+            .collect();
+        sst_ids.sort_unstable();
+        for sst_id in &sst_ids {
+            copy_sst(
+                &hummock_store,
+                hummock_dir,
+                &backup_store,
+                &sst_dir(path),
+                *sst_id,
+            )
+            .await?;
+        }
+        backup_store
+            .upload(&meta_path(path), backup.encode_to_vec().into())
+            .await?;
+        let manifest = BackupManifest {
+            hummock_version_id: version.id,
+            sst_ids,
+        };
+        backup_store
+            .upload(&manifest_path(path), manifest.encode_to_vec().into())
+            .await?;
+        println!(
+            "backed up Hummock version {} with {} SSTs",
+            manifest.hummock_version_id,
+            manifest.sst_ids.len()
+        );
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+
+    meta_client.unpin_version(&[version.id]).await?;
+    result
+}
@@ -0,0 +1,72 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use prost::Message;
+use risingwave_pb::meta::{BackupManifest, MetaBackup};
+use risingwave_storage::object::parse_remote_object_store;
+
+use super::{copy_sst, manifest_path, meta_path, sst_dir};
+use crate::common::HummockServiceOpts;
+
+/// Copies SSTs in the backup at `path` of object store `store_url` back to Hummock, and writes meta
+/// data to local file `meta_backup_file`. SSTs are written to `hummock_dir` in the object store of
+/// Hummock.
+///
+/// The cluster is then rebuilt by starting meta node on an empty meta store with
+/// `--restore-meta-backup <meta_backup_file>`.
+pub async fn restore_backup(
+    store_url: &str,
+    path: &str,
+    meta_backup_file: &str,
+    hummock_dir: &str,
+) -> anyhow::Result<()> {
+    let hummock_store = HummockServiceOpts::from_env()?
+        .create_object_store()
+        .await?;
+    let backup_store = parse_remote_object_store(store_url).await;
+
+    let manifest = BackupManifest::decode(
+        backup_store
+            .read(&manifest_path(path), None)
+            .await
+            .map_err(|e| anyhow::anyhow!("backup {} is incomplete: {}", path, e))?,
+    )?;
+    let meta = backup_store.read(&meta_path(path), None).await?;
+    let backup = MetaBackup::decode(meta.clone())?;
+    anyhow::ensure!(
+        backup.hummock_version.unwrap().id == manifest.hummock_version_id,
+        "meta data and manifest of backup {} mismatch",
+        path
+    );
+
+    for sst_id in &manifest.sst_ids {
+        copy_sst(
+            &backup_store,
+            &sst_dir(path),
+            &hummock_store,
+            hummock_dir,
+            *sst_id,
+        )
+        .await?;
+    }
+    std::fs::write(meta_backup_file, meta)?;
+
+    println!(
+        "restored {} SSTs of Hummock version {}, start meta node with `--restore-meta-backup {}` to restore meta data",
+        manifest.sst_ids.len(),
+        manifest.hummock_version_id,
+        meta_backup_file
+    );
+    Ok(())
+}
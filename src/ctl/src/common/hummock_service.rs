@@ -20,6 +20,7 @@ use risingwave_common::config::StorageConfig;
 use risingwave_storage::hummock::hummock_meta_client::MonitoredHummockMetaClient;
 use risingwave_storage::hummock::HummockStorage;
 use risingwave_storage::monitor::{HummockMetrics, MonitoredStateStore, StateStoreMetrics};
+use risingwave_storage::object::{parse_remote_object_store, ObjectStoreImpl};
 use risingwave_storage::StateStoreImpl;
 
 use super::MetaServiceOpts;
@@ -76,4 +77,13 @@ impl HummockServiceOpts {
             Err(anyhow!("only Hummock state store is supported in risectl"))
         }
     }
+
+    /// Creates the object store used by Hummock.
+    pub async fn create_object_store(&self) -> Result<ObjectStoreImpl> {
+        let url = self
+            .hummock_url
+            .strip_prefix("hummock+")
+            .ok_or_else(|| anyhow!("invalid Hummock URL {}", self.hummock_url))?;
+        Ok(parse_remote_object_store(url).await)
+    }
 }
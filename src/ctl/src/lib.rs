@@ -31,6 +31,9 @@ enum Commands {
     /// Commands for Hummock
     #[clap(subcommand)]
     Hummock(HummockCommands),
+    /// Commands for backup and restore
+    #[clap(subcommand)]
+    Backup(BackupCommands),
 }
 
 #[derive(Subcommand)]
@@ -41,11 +44,58 @@ enum HummockCommands {
     ListKv,
 }
 
+#[derive(Subcommand)]
+enum BackupCommands {
+    /// back up meta data and SSTs of the current Hummock version
+    Create {
+        /// object store to write the backup to, e.g. `s3://bucket` or `disk:///path/to/dir`
+        #[clap(long)]
+        store: String,
+        /// path of the backup in the object store
+        path: String,
+        /// data directory of Hummock in its object store, i.e. `data_directory` in the storage
+        /// config of compute nodes
+        #[clap(long, default_value = "hummock_001")]
+        data_directory: String,
+    },
+    /// copy SSTs in a backup back to Hummock and write its meta data to a local file, which
+    /// should be passed to meta node with `--restore-meta-backup`
+    Restore {
+        /// object store to read the backup from
+        #[clap(long)]
+        store: String,
+        /// path of the backup in the object store
+        path: String,
+        /// local file to write meta data to
+        #[clap(long)]
+        meta_backup_file: String,
+        /// data directory of Hummock in its object store, i.e. `data_directory` in the storage
+        /// config of compute nodes
+        #[clap(long, default_value = "hummock_001")]
+        data_directory: String,
+    },
+}
+
 pub async fn start(opts: CliOpts) {
     match &opts.command {
         Commands::Hummock(HummockCommands::ListVersion) => {
             cmd_impl::hummock::list_version().await.unwrap()
         }
         Commands::Hummock(HummockCommands::ListKv) => cmd_impl::hummock::list_kv().await.unwrap(),
+        Commands::Backup(BackupCommands::Create {
+            store,
+            path,
+            data_directory,
+        }) => cmd_impl::backup::create_backup(store, path, data_directory)
+            .await
+            .unwrap(),
+        Commands::Backup(BackupCommands::Restore {
+            store,
+            path,
+            meta_backup_file,
+            data_directory,
+        }) => cmd_impl::backup::restore_backup(store, path, meta_backup_file, data_directory)
+            .await
+            .unwrap(),
     }
 }
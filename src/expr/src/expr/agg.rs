@@ -15,7 +15,9 @@
 use std::convert::TryFrom;
 
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::OrderedF64;
 use risingwave_pb::expr::agg_call::Type;
+use risingwave_pb::expr::AggCall;
 
 /// Kind of aggregation function
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -28,6 +30,20 @@ pub enum AggKind {
     Avg,
     StringAgg,
     SingleValue,
+    ApproxCountDistinct,
+    /// Approximate percentile of the given fraction in the range of [0, 1].
+    ApproxPercentile(OrderedF64),
+    BoolAnd,
+    BoolOr,
+    BitAnd,
+    BitOr,
+    ArrayAgg,
+    /// `StddevPop`, `StddevSamp`, `VarPop` and `VarSamp` are rewritten with `Sum` and `Count` by
+    /// the optimizer, and never reach the executors.
+    StddevPop,
+    StddevSamp,
+    VarPop,
+    VarSamp,
 }
 
 impl std::fmt::Display for AggKind {
@@ -41,6 +57,17 @@ impl std::fmt::Display for AggKind {
             AggKind::Avg => write!(f, "avg"),
             AggKind::StringAgg => write!(f, "string_agg"),
             AggKind::SingleValue => write!(f, "single_value"),
+            AggKind::ApproxCountDistinct => write!(f, "approx_count_distinct"),
+            AggKind::ApproxPercentile(percentile) => write!(f, "approx_percentile({})", percentile),
+            AggKind::BoolAnd => write!(f, "bool_and"),
+            AggKind::BoolOr => write!(f, "bool_or"),
+            AggKind::BitAnd => write!(f, "bit_and"),
+            AggKind::BitOr => write!(f, "bit_or"),
+            AggKind::ArrayAgg => write!(f, "array_agg"),
+            AggKind::StddevPop => write!(f, "stddev_pop"),
+            AggKind::StddevSamp => write!(f, "stddev_samp"),
+            AggKind::VarPop => write!(f, "var_pop"),
+            AggKind::VarSamp => write!(f, "var_samp"),
         }
     }
 }
@@ -57,12 +84,44 @@ impl TryFrom<Type> for AggKind {
             Type::Count => Ok(AggKind::Count),
             Type::StringAgg => Ok(AggKind::StringAgg),
             Type::SingleValue => Ok(AggKind::SingleValue),
+            Type::ApproxCountDistinct => Ok(AggKind::ApproxCountDistinct),
+            Type::BoolAnd => Ok(AggKind::BoolAnd),
+            Type::BoolOr => Ok(AggKind::BoolOr),
+            Type::BitAnd => Ok(AggKind::BitAnd),
+            Type::BitOr => Ok(AggKind::BitOr),
+            Type::ArrayAgg => Ok(AggKind::ArrayAgg),
             _ => Err(ErrorCode::InternalError("Unrecognized agg.".into()).into()),
         }
     }
 }
 
 impl AggKind {
+    /// Builds the kind of `prost`, including the parameters carried outside its type.
+    pub fn from_protobuf(prost: &AggCall) -> Result<Self> {
+        match prost.get_type()? {
+            Type::ApproxPercentile => {
+                let percentile = prost.percentile;
+                if !(0.0..=1.0).contains(&percentile) {
+                    return Err(ErrorCode::InternalError(format!(
+                        "percentile {} is out of range [0, 1]",
+                        percentile
+                    ))
+                    .into());
+                }
+                Ok(AggKind::ApproxPercentile(percentile.into()))
+            }
+            ty => AggKind::try_from(ty),
+        }
+    }
+
+    /// The fraction of [`AggKind::ApproxPercentile`].
+    pub fn percentile(&self) -> Option<f64> {
+        match self {
+            Self::ApproxPercentile(percentile) => Some(percentile.0),
+            _ => None,
+        }
+    }
+
     pub fn to_prost(&self) -> Type {
         match self {
             Self::Min => Type::Min,
@@ -72,9 +131,22 @@ impl AggKind {
             Self::Count => Type::Count,
             Self::StringAgg => Type::StringAgg,
             Self::SingleValue => Type::SingleValue,
+            Self::ApproxCountDistinct => Type::ApproxCountDistinct,
+            Self::ApproxPercentile(_) => Type::ApproxPercentile,
+            Self::BoolAnd => Type::BoolAnd,
+            Self::BoolOr => Type::BoolOr,
+            Self::BitAnd => Type::BitAnd,
+            Self::BitOr => Type::BitOr,
+            Self::ArrayAgg => Type::ArrayAgg,
             Self::RowCount => {
                 panic!("cannot convert RowCount to prost, TODO: remove RowCount from AggKind")
             }
+            Self::StddevPop | Self::StddevSamp | Self::VarPop | Self::VarSamp => {
+                panic!(
+                    "cannot convert {} to prost, it should have been rewritten",
+                    self
+                )
+            }
         }
     }
 }
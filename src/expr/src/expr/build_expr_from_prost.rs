@@ -15,20 +15,34 @@
 use risingwave_common::array::DataChunk;
 use risingwave_common::ensure;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_pb::expr::expr_node::RexNode;
 use risingwave_pb::expr::{expr_node, ExprNode};
 
-use crate::expr::expr_binary_bytes::new_substr_start;
+use crate::expr::expr_array::{
+    ArrayAccessExpression, ArrayContainsExpression, ArrayExpression, ArrayLengthExpression,
+};
+use crate::expr::expr_binary_bytes::{
+    new_concat_op, new_lpad, new_repeat, new_rpad, new_substr_start, new_timestamptz_to_str,
+    new_to_char,
+};
 use crate::expr::expr_binary_nonnull::{new_binary_expr, new_like_default};
 use crate::expr::expr_binary_nullable::new_nullable_binary_expr;
 use crate::expr::expr_case::{CaseExpression, WhenClause};
+use crate::expr::expr_concat_ws::{ConcatExpression, ConcatWsExpression};
 use crate::expr::expr_in::InExpression;
-use crate::expr::expr_ternary_bytes::{new_replace_expr, new_substr_start_end, new_translate_expr};
+use crate::expr::expr_regexp::{
+    RegexpContext, RegexpMatchExpression, RegexpReplaceExpression, SimilarToExpression,
+};
+use crate::expr::expr_struct::{FieldExpression, StructExpression};
+use crate::expr::expr_ternary_bytes::{
+    new_lpad_fill, new_replace_expr, new_rpad_fill, new_split_part_expr, new_substr_start_end,
+    new_translate_expr,
+};
 use crate::expr::expr_unary::{
     new_length_default, new_ltrim_expr, new_rtrim_expr, new_trim_expr, new_unary_expr,
 };
-use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression};
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, LiteralExpression};
 
 fn get_return_type_and_children(prost: &ExprNode) -> Result<(Vec<ExprNode>, DataType)> {
     let ret_type = DataType::from(prost.get_return_type()?);
@@ -213,6 +227,239 @@ pub fn build_translate_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     Ok(new_translate_expr(s, match_str, replace_str, ret_type))
 }
 
+pub fn build_array_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    let elements = children
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(ArrayExpression::new(ret_type, elements)))
+}
+
+pub fn build_array_access_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let array = expr_build_from_prost(&children[0])?;
+    let index = expr_build_from_prost(&children[1])?;
+    ensure!(index.return_type() == DataType::Int32);
+    Ok(Box::new(ArrayAccessExpression::new(ret_type, array, index)))
+}
+
+pub fn build_array_length_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, _) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 1);
+    let array = expr_build_from_prost(&children[0])?;
+    Ok(Box::new(ArrayLengthExpression::new(array)))
+}
+
+pub fn build_array_contains_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, _) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let array = expr_build_from_prost(&children[0])?;
+    let element = expr_build_from_prost(&children[1])?;
+    Ok(Box::new(ArrayContainsExpression::new(array, element)))
+}
+
+pub fn build_field_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let input = expr_build_from_prost(&children[0])?;
+    let index = match LiteralExpression::try_from(&children[1])?.literal() {
+        Some(ScalarImpl::Int32(index)) => index,
+        index => {
+            return Err(ErrorCode::InternalError(format!(
+                "field index must be a constant integer, got {:?}",
+                index
+            ))
+            .into())
+        }
+    };
+    Ok(Box::new(FieldExpression::new(
+        ret_type,
+        input,
+        index as usize,
+    )))
+}
+
+pub fn build_struct_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    let fields = children
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(StructExpression::new(ret_type, fields)))
+}
+
+pub fn build_split_part_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 3);
+    let s = expr_build_from_prost(&children[0])?;
+    let delimiter = expr_build_from_prost(&children[1])?;
+    let n = expr_build_from_prost(&children[2])?;
+    Ok(new_split_part_expr(s, delimiter, n, ret_type))
+}
+
+pub fn build_pad_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2 || children.len() == 3);
+    let s = expr_build_from_prost(&children[0])?;
+    let len = expr_build_from_prost(&children[1])?;
+    let is_left = prost.get_expr_type()? == expr_node::Type::Lpad;
+    if children.len() == 2 {
+        if is_left {
+            Ok(new_lpad(s, len, ret_type))
+        } else {
+            Ok(new_rpad(s, len, ret_type))
+        }
+    } else {
+        let fill = expr_build_from_prost(&children[2])?;
+        if is_left {
+            Ok(new_lpad_fill(s, len, fill, ret_type))
+        } else {
+            Ok(new_rpad_fill(s, len, fill, ret_type))
+        }
+    }
+}
+
+pub fn build_repeat_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let s = expr_build_from_prost(&children[0])?;
+    let count = expr_build_from_prost(&children[1])?;
+    Ok(new_repeat(s, count, ret_type))
+}
+
+pub fn build_concat_op_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let left = expr_build_from_prost(&children[0])?;
+    let right = expr_build_from_prost(&children[1])?;
+    Ok(new_concat_op(left, right, ret_type))
+}
+
+pub fn build_to_char_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let ts = expr_build_from_prost(&children[0])?;
+    let template = expr_build_from_prost(&children[1])?;
+    ensure!(ts.return_type() == DataType::Timestamp);
+    Ok(new_to_char(ts, template, ret_type))
+}
+
+/// Casts between `varchar` and `timestamptz` in the time zone given as the second child.
+pub fn build_cast_with_time_zone_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let input = expr_build_from_prost(&children[0])?;
+    let time_zone = expr_build_from_prost(&children[1])?;
+    match (input.return_type(), ret_type) {
+        (DataType::Timestampz, DataType::Varchar) => {
+            Ok(new_timestamptz_to_str(input, time_zone, DataType::Varchar))
+        }
+        (DataType::Varchar, DataType::Timestampz) => Ok(new_binary_expr(
+            expr_node::Type::CastWithTimeZone,
+            DataType::Timestampz,
+            input,
+            time_zone,
+        )),
+        (input_type, ret_type) => Err(ErrorCode::NotImplemented(
+            format!(
+                "cast from {:?} to {:?} with time zone",
+                input_type, ret_type
+            ),
+            None.into(),
+        )
+        .into()),
+    }
+}
+
+pub fn build_concat_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, _) = get_return_type_and_children(prost)?;
+    let args = children
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(ConcatExpression::new(args)))
+}
+
+pub fn build_concat_ws_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, _) = get_return_type_and_children(prost)?;
+    ensure!(!children.is_empty());
+    let separator = expr_build_from_prost(&children[0])?;
+    let args = children[1..]
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(ConcatWsExpression::new(separator, args)))
+}
+
+/// Gets the value of a constant string argument like a regex pattern, which may be null.
+fn get_constant_str(prost: &ExprNode, func: &str) -> Result<Option<String>> {
+    if prost.get_expr_type()? != expr_node::Type::ConstantValue {
+        return Err(ErrorCode::NotImplemented(
+            format!(
+                "{} only supports constant patterns, replacements and flags",
+                func
+            ),
+            None.into(),
+        )
+        .into());
+    }
+    match LiteralExpression::try_from(prost)?.literal() {
+        Some(ScalarImpl::Utf8(s)) => Ok(Some(s)),
+        None => Ok(None),
+        v => Err(ErrorCode::InternalError(format!("expect a string, got {:?}", v)).into()),
+    }
+}
+
+/// Gets the constant arguments after the text of a regex function. If any of them is null, the
+/// result is always null and `None` is returned.
+fn get_constant_strs(children: &[ExprNode], func: &str) -> Result<Option<Vec<String>>> {
+    children
+        .iter()
+        .map(|child| get_constant_str(child, func))
+        .collect()
+}
+
+pub fn build_regexp_match_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2 || children.len() == 3);
+    let Some(args) = get_constant_strs(&children[1..], "regexp_match")? else {
+        return Ok(Box::new(LiteralExpression::new(ret_type, None)));
+    };
+    let ctx = RegexpContext::new(&args[0], args.get(1).map_or("", |flags| flags.as_str()))?;
+    if ctx.is_global() {
+        return Err(ErrorCode::InvalidInputSyntax(
+            "regexp_match does not support the global option".to_string(),
+        )
+        .into());
+    }
+    let text = expr_build_from_prost(&children[0])?;
+    Ok(Box::new(RegexpMatchExpression::new(text, ctx)))
+}
+
+pub fn build_regexp_replace_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 3 || children.len() == 4);
+    let Some(args) = get_constant_strs(&children[1..], "regexp_replace")? else {
+        return Ok(Box::new(LiteralExpression::new(ret_type, None)));
+    };
+    let ctx = RegexpContext::new(&args[0], args.get(2).map_or("", |flags| flags.as_str()))?;
+    let text = expr_build_from_prost(&children[0])?;
+    Ok(Box::new(RegexpReplaceExpression::new(text, ctx, &args[1])))
+}
+
+pub fn build_similar_to_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let Some(pattern) = get_constant_str(&children[1], "SIMILAR TO")? else {
+        return Ok(Box::new(LiteralExpression::new(ret_type, None)));
+    };
+    let ctx = RegexpContext::from_similar_to(&pattern)?;
+    let text = expr_build_from_prost(&children[0])?;
+    Ok(Box::new(SimilarToExpression::new(text, ctx)))
+}
+
 #[cfg(test)]
 mod tests {
     use std::vec;
@@ -0,0 +1,288 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use risingwave_common::array::{
+    Array, ArrayBuilder, ArrayImpl, ArrayRef, BoolArrayBuilder, DataChunk, I32ArrayBuilder,
+    ListRef, ListValue,
+};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarImpl, ScalarRefImpl, ToOwnedDatum};
+
+use crate::expr::{BoxedExpression, Expression};
+
+fn list_at(array: &ArrayImpl, idx: usize) -> Result<Option<ListRef<'_>>> {
+    match array.value_at(idx) {
+        Some(ScalarRefImpl::List(list)) => Ok(Some(list)),
+        None => Ok(None),
+        Some(_) => Err(ErrorCode::InternalError(format!(
+            "expect a list, got {}",
+            array.get_ident()
+        ))
+        .into()),
+    }
+}
+
+/// Constructs a list from its elements, e.g. `ARRAY[1, 2, v1]`.
+#[derive(Debug)]
+pub struct ArrayExpression {
+    return_type: DataType,
+    elements: Vec<BoxedExpression>,
+}
+
+impl ArrayExpression {
+    pub fn new(return_type: DataType, elements: Vec<BoxedExpression>) -> Self {
+        Self {
+            return_type,
+            elements,
+        }
+    }
+}
+
+impl Expression for ArrayExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let elements: Vec<_> = self
+            .elements
+            .iter()
+            .map(|element| element.eval(input))
+            .try_collect()?;
+        let mut builder = self.return_type.create_array_builder(input.capacity())?;
+        for idx in 0..input.capacity() {
+            let values = elements
+                .iter()
+                .map(|element| element.value_at(idx).to_owned_datum())
+                .collect_vec();
+            builder.append_datum(&Some(ScalarImpl::List(ListValue::new(values))))?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+/// Accesses an element of a list by its 1-based index, e.g. `v1[2]`. Returns null when the index
+/// is out of bounds.
+#[derive(Debug)]
+pub struct ArrayAccessExpression {
+    return_type: DataType,
+    array: BoxedExpression,
+    index: BoxedExpression,
+}
+
+impl ArrayAccessExpression {
+    pub fn new(return_type: DataType, array: BoxedExpression, index: BoxedExpression) -> Self {
+        Self {
+            return_type,
+            array,
+            index,
+        }
+    }
+}
+
+impl Expression for ArrayAccessExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let array = self.array.eval(input)?;
+        let index = self.index.eval(input)?;
+        let index = index.as_int32();
+        let mut builder = self.return_type.create_array_builder(array.len())?;
+        for (idx, index) in index.iter().enumerate() {
+            let element = match (list_at(&array, idx)?, index) {
+                (Some(list), Some(index)) if index >= 1 => list
+                    .values_ref()
+                    .get(index as usize - 1)
+                    .copied()
+                    .flatten()
+                    .to_owned_datum(),
+                _ => None,
+            };
+            builder.append_datum(&element)?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+/// Returns the number of elements of a list, or null for an empty list like postgres.
+#[derive(Debug)]
+pub struct ArrayLengthExpression {
+    array: BoxedExpression,
+}
+
+impl ArrayLengthExpression {
+    pub fn new(array: BoxedExpression) -> Self {
+        Self { array }
+    }
+}
+
+impl Expression for ArrayLengthExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Int32
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let array = self.array.eval(input)?;
+        let mut builder = I32ArrayBuilder::new(array.len())?;
+        for idx in 0..array.len() {
+            let length = list_at(&array, idx)?
+                .map(|list| list.values_ref().len() as i32)
+                .filter(|length| *length > 0);
+            builder.append(length)?;
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+/// Checks whether a list contains an element, which is how `element = ANY(list)` is evaluated.
+///
+/// Following the semantics of `ANY`, the result is null rather than false if there is no match but
+/// either the element or some value in the list is null.
+#[derive(Debug)]
+pub struct ArrayContainsExpression {
+    array: BoxedExpression,
+    element: BoxedExpression,
+}
+
+impl ArrayContainsExpression {
+    pub fn new(array: BoxedExpression, element: BoxedExpression) -> Self {
+        Self { array, element }
+    }
+}
+
+impl Expression for ArrayContainsExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Boolean
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let array = self.array.eval(input)?;
+        let element = self.element.eval(input)?;
+        let mut builder = BoolArrayBuilder::new(array.len())?;
+        for idx in 0..array.len() {
+            let contains = match (list_at(&array, idx)?, element.value_at(idx)) {
+                (Some(list), Some(element)) => {
+                    let values = list.values_ref();
+                    if values.contains(&Some(element)) {
+                        Some(true)
+                    } else if values.contains(&None) {
+                        None
+                    } else {
+                        Some(false)
+                    }
+                }
+                (Some(list), None) if list.values_ref().is_empty() => Some(false),
+                _ => None,
+            };
+            builder.append(contains)?;
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::I32Array;
+    use risingwave_common::column;
+
+    use super::*;
+    use crate::expr::{InputRefExpression, LiteralExpression};
+
+    fn list_type() -> DataType {
+        DataType::List {
+            datatype: Box::new(DataType::Int32),
+        }
+    }
+
+    fn literal(v: Option<i32>) -> BoxedExpression {
+        Box::new(LiteralExpression::new(
+            DataType::Int32,
+            v.map(ScalarImpl::Int32),
+        ))
+    }
+
+    #[test]
+    fn test_array_expressions() {
+        let chunk = DataChunk::builder()
+            .columns(vec![column! {I32Array, [Some(1), None, Some(3)]}])
+            .build();
+        // ARRAY[v1, 2, NULL]
+        let array_expr = || {
+            Box::new(ArrayExpression::new(
+                list_type(),
+                vec![
+                    Box::new(InputRefExpression::new(DataType::Int32, 0)),
+                    literal(Some(2)),
+                    literal(None),
+                ],
+            )) as BoxedExpression
+        };
+        let arrays = array_expr().eval(&chunk).unwrap();
+        assert_eq!(
+            arrays.datum_at(1),
+            Some(ScalarImpl::List(ListValue::new(vec![
+                None,
+                Some(ScalarImpl::Int32(2)),
+                None
+            ])))
+        );
+
+        // ARRAY[v1, 2, NULL][2]
+        let access = ArrayAccessExpression::new(DataType::Int32, array_expr(), literal(Some(2)));
+        let elements = access.eval(&chunk).unwrap();
+        assert_eq!(
+            elements.as_int32().iter().collect_vec(),
+            vec![Some(2), Some(2), Some(2)]
+        );
+        let access = ArrayAccessExpression::new(DataType::Int32, array_expr(), literal(Some(4)));
+        assert_eq!(
+            access.eval(&chunk).unwrap().as_int32().iter().collect_vec(),
+            vec![None, None, None]
+        );
+
+        let length = ArrayLengthExpression::new(array_expr());
+        assert_eq!(
+            length.eval(&chunk).unwrap().as_int32().iter().collect_vec(),
+            vec![Some(3), Some(3), Some(3)]
+        );
+
+        // 3 = ANY(ARRAY[v1, 2, NULL])
+        let contains = ArrayContainsExpression::new(array_expr(), literal(Some(3)));
+        assert_eq!(
+            contains
+                .eval(&chunk)
+                .unwrap()
+                .as_bool()
+                .iter()
+                .collect_vec(),
+            vec![None, None, Some(true)]
+        );
+        // 2 = ANY(ARRAY[v1, 2, NULL])
+        let contains = ArrayContainsExpression::new(array_expr(), literal(Some(2)));
+        assert_eq!(
+            contains
+                .eval(&chunk)
+                .unwrap()
+                .as_bool()
+                .iter()
+                .collect_vec(),
+            vec![Some(true), Some(true), Some(true)]
+        );
+    }
+}
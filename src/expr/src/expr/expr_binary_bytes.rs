@@ -14,12 +14,17 @@
 
 //! For expression that only accept two arguments + 1 bytes writer as input.
 
-use risingwave_common::array::{I32Array, Utf8Array};
+use risingwave_common::array::{I32Array, I64Array, NaiveDateTimeArray, Utf8Array};
 use risingwave_common::types::DataType;
 
 use crate::expr::template::BinaryBytesExpression;
 use crate::expr::BoxedExpression;
+use crate::vector_op::concat_op::concat_op;
+use crate::vector_op::pad::{lpad, rpad};
+use crate::vector_op::repeat::repeat;
 use crate::vector_op::substr::*;
+use crate::vector_op::timestamptz::timestamptz_to_str;
+use crate::vector_op::to_char::to_char_timestamp;
 
 pub fn new_substr_start(
     expr_ia1: BoxedExpression,
@@ -48,6 +53,86 @@ pub fn new_substr_for(
     ))
 }
 
+pub fn new_concat_op(
+    left: BoxedExpression,
+    right: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryBytesExpression::<Utf8Array, Utf8Array, _>::new(
+        left,
+        right,
+        return_type,
+        concat_op,
+    ))
+}
+
+pub fn new_repeat(
+    s: BoxedExpression,
+    count: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryBytesExpression::<Utf8Array, I32Array, _>::new(
+        s,
+        count,
+        return_type,
+        repeat,
+    ))
+}
+
+pub fn new_lpad(
+    s: BoxedExpression,
+    len: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryBytesExpression::<Utf8Array, I32Array, _>::new(
+        s,
+        len,
+        return_type,
+        lpad,
+    ))
+}
+
+pub fn new_rpad(
+    s: BoxedExpression,
+    len: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryBytesExpression::<Utf8Array, I32Array, _>::new(
+        s,
+        len,
+        return_type,
+        rpad,
+    ))
+}
+
+pub fn new_to_char(
+    ts: BoxedExpression,
+    template: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(
+        BinaryBytesExpression::<NaiveDateTimeArray, Utf8Array, _>::new(
+            ts,
+            template,
+            return_type,
+            to_char_timestamp,
+        ),
+    )
+}
+
+pub fn new_timestamptz_to_str(
+    ts: BoxedExpression,
+    time_zone: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryBytesExpression::<I64Array, Utf8Array, _>::new(
+        ts,
+        time_zone,
+        return_type,
+        timestamptz_to_str,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::array::DataChunk;
@@ -13,22 +13,30 @@
 // limitations under the License.
 
 use risingwave_common::array::{
-    Array, BoolArray, DecimalArray, I32Array, IntervalArray, NaiveDateArray, NaiveDateTimeArray,
-    Utf8Array,
+    Array, BoolArray, DecimalArray, I32Array, I64Array, IntervalArray, NaiveDateArray,
+    NaiveDateTimeArray, Utf8Array,
 };
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::Result;
 use risingwave_common::types::*;
 use risingwave_pb::expr::expr_node::Type;
 
+use crate::expr::expr_binary_primitive::{is_primitive_binary_expr, new_primitive_binary_expr};
 use crate::expr::template::BinaryExpression;
 use crate::expr::BoxedExpression;
 use crate::vector_op::arithmetic_op::*;
 use crate::vector_op::cmp::*;
+use crate::vector_op::date_trunc::date_trunc_timestamp;
 use crate::vector_op::extract::{extract_from_date, extract_from_timestamp};
-use crate::vector_op::like::like_default;
+use crate::vector_op::jsonb::jsonb_contains;
+use crate::vector_op::like::{ilike_default, like_default};
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
+use crate::vector_op::starts_with::starts_with;
+use crate::vector_op::timestamptz::{
+    str_to_timestamptz, timestamp_at_time_zone, timestamptz_at_time_zone,
+};
+use crate::vector_op::to_char::to_timestamp_str;
 use crate::vector_op::tumble::{tumble_start_date, tumble_start_date_time};
 
 /// A placeholder function that returns bool in [`gen_binary_expr_atm`]
@@ -167,7 +175,8 @@ macro_rules! gen_binary_expr_cmp {
                     { date, date, date, $general_f },
                     { boolean, boolean, boolean, $general_f },
                     { timestamp, date, timestamp, $general_f },
-                    { date, timestamp, timestamp, $general_f }
+                    { date, timestamp, timestamp, $general_f },
+                    { timestampz, timestampz, timestampz, $general_f }
                 }
             }
         }
@@ -181,6 +190,7 @@ macro_rules! gen_binary_expr_cmp {
 /// * `general_f`: generic atm function (require a common ``TryInto`` type for two input)
 /// * `interval_date_f`: atm function between interval and date
 /// * `interval_date_f`: atm function between date and interval
+/// * The other functions are between the date/time types, intervals and integers named likewise
 macro_rules! gen_binary_expr_atm {
     (
         $macro:ident,
@@ -198,6 +208,13 @@ macro_rules! gen_binary_expr_atm {
             $((Interval, Interval) => $interval_interval_f:ident,)?
             $((Interval, Int) => $interval_int_f:ident,)?
             $((Int, Interval) => $int_interval_f:ident,)?
+            $((Date, Int) => $date_int_f:ident,)?
+            $((Int, Date) => $int_date_f:ident,)?
+            $((Date, Time) => $date_time_f:ident,)?
+            $((Time, Date) => $time_date_f:ident,)?
+            $((Time, Time) => $time_time_f:ident,)?
+            $((Time, Interval) => $time_interval_f:ident,)?
+            $((Interval, Time) => $interval_time_f:ident,)?
         } $(,)?
     ) => {
         $macro! {
@@ -250,6 +267,13 @@ macro_rules! gen_binary_expr_atm {
             $({ int16, interval, interval, $int_interval_f },)?
             $({ int32, interval, interval, $int_interval_f },)?
             $({ int64, interval, interval, $int_interval_f },)?
+            $({ date, int32, date, $date_int_f },)?
+            $({ int32, date, date, $int_date_f },)?
+            $({ date, time, timestamp, $date_time_f },)?
+            $({ time, date, timestamp, $time_date_f },)?
+            $({ time, time, interval, $time_time_f },)?
+            $({ time, interval, time, $time_interval_f },)?
+            $({ interval, time, time, $interval_time_f },)?
         }
     };
 }
@@ -284,6 +308,10 @@ pub fn new_binary_expr(
 ) -> BoxedExpression {
     use crate::expr::data_types::*;
 
+    if is_primitive_binary_expr(expr_type, &l.return_type(), &r.return_type()) {
+        return new_primitive_binary_expr(expr_type, ret, l, r);
+    }
+
     match expr_type {
         Type::Equal => {
             gen_binary_expr_cmp! {gen_cmp_impl, general_eq, str_eq, l, r, ret}
@@ -314,6 +342,12 @@ pub fn new_binary_expr(
                     (Interval, Date) => interval_date_add,
                     (Date, Interval) => date_interval_add,
                     (Interval, Interval) => general_add,
+                    (Date, Int) => date_int_add,
+                    (Int, Date) => int_date_add,
+                    (Date, Time) => date_time_add,
+                    (Time, Date) => time_date_add,
+                    (Time, Interval) => time_interval_add,
+                    (Interval, Time) => interval_time_add,
                 },
             }
         }
@@ -328,6 +362,9 @@ pub fn new_binary_expr(
                     (Date, Date) => date_date_sub,
                     (Date, Interval) => date_interval_sub,
                     (Interval, Interval) => general_sub,
+                    (Date, Int) => date_int_sub,
+                    (Time, Time) => time_time_sub,
+                    (Time, Interval) => time_interval_sub,
                 },
             }
         }
@@ -348,6 +385,7 @@ pub fn new_binary_expr(
                 l, r, ret,
                 {
                     General => general_div,
+                    (Interval, Int) => interval_int_div,
                 },
             }
         }
@@ -373,6 +411,47 @@ pub fn new_binary_expr(
             l, r, ret, position,
         )),
         Type::TumbleStart => new_tumble_start(l, r, ret),
+        Type::DateTrunc => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, date_trunc_timestamp)),
+        Type::ToTimestamp1 => Box::new(BinaryExpression::<
+            Utf8Array,
+            Utf8Array,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, to_timestamp_str)),
+        Type::AtTimeZone => new_at_time_zone(l, r, ret),
+        Type::CastWithTimeZone => {
+            Box::new(BinaryExpression::<Utf8Array, Utf8Array, I64Array, _>::new(
+                l,
+                r,
+                ret,
+                str_to_timestamptz,
+            ))
+        }
+        Type::Ilike => Box::new(BinaryExpression::<Utf8Array, Utf8Array, BoolArray, _>::new(
+            l,
+            r,
+            ret,
+            ilike_default,
+        )),
+        Type::StartsWith => Box::new(BinaryExpression::<Utf8Array, Utf8Array, BoolArray, _>::new(
+            l,
+            r,
+            ret,
+            starts_with,
+        )),
+        Type::JsonbContains => Box::new(
+            BinaryExpression::<JsonbArray, JsonbArray, BoolArray, _>::new(
+                l,
+                r,
+                ret,
+                jsonb_contains,
+            ),
+        ),
         tp => {
             unimplemented!(
                 "The expression {:?} using vectorized expression framework is not supported yet!",
@@ -411,6 +490,39 @@ fn new_tumble_start(
     }
 }
 
+/// `timestamp AT TIME ZONE zone` gives a `timestamptz`, and `timestamptz AT TIME ZONE zone` gives
+/// a `timestamp`.
+fn new_at_time_zone(
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    match expr_ia1.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            Utf8Array,
+            I64Array,
+            _,
+        >::new(
+            expr_ia1, expr_ia2, return_type, timestamp_at_time_zone
+        )),
+        DataType::Timestampz => {
+            Box::new(
+                BinaryExpression::<I64Array, Utf8Array, NaiveDateTimeArray, _>::new(
+                    expr_ia1,
+                    expr_ia2,
+                    return_type,
+                    timestamptz_at_time_zone,
+                ),
+            )
+        }
+        _ => unimplemented!(
+            "AT TIME ZONE is not supported for {:?}",
+            expr_ia1.return_type()
+        ),
+    }
+}
+
 pub fn new_like_default(
     expr_ia1: BoxedExpression,
     expr_ia2: BoxedExpression,
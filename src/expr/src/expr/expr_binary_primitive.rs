@@ -0,0 +1,445 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binary expressions on primitive arrays of the same type, evaluated over whole buffers with the
+//! kernels in [`crate::vector_op::kernels`] rather than row by row.
+
+use std::fmt;
+use std::sync::Arc;
+
+use risingwave_common::array::{
+    Array, ArrayImpl, ArrayRef, BoolArray, DataChunk, PrimitiveArray, PrimitiveArrayItemType,
+};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, OrderedF32, OrderedF64};
+use risingwave_pb::expr::expr_node::Type;
+
+use crate::expr::{compact_selected, has_all_rows, BoxedExpression, Expression};
+use crate::vector_op::kernels::{checked_binary_primitive, compare_primitive};
+
+/// Arithmetic that reports overflows instead of panicking, so that it can be applied to the
+/// placeholder values of null slots.
+trait OverflowingArithmetic: Sized {
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool);
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool);
+}
+
+macro_rules! impl_overflowing_arithmetic_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl OverflowingArithmetic for $t {
+                fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                    <$t>::overflowing_add(self, rhs)
+                }
+
+                fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                    <$t>::overflowing_sub(self, rhs)
+                }
+
+                fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                    <$t>::overflowing_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_overflowing_arithmetic_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl OverflowingArithmetic for $t {
+                fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                    (self + rhs, false)
+                }
+
+                fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                    (self - rhs, false)
+                }
+
+                fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                    (self * rhs, false)
+                }
+            }
+        )*
+    };
+}
+
+impl_overflowing_arithmetic_for_int! { i16, i32, i64 }
+impl_overflowing_arithmetic_for_float! { OrderedF32, OrderedF64 }
+
+pub struct PrimitiveBinaryExpression<T1, T2, OA, F>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    OA: Array,
+    F: Fn(&PrimitiveArray<T1>, &PrimitiveArray<T2>, Option<&Bitmap>) -> Result<OA>,
+{
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    return_type: DataType,
+    kernel: F,
+    _phantom: std::marker::PhantomData<(T1, T2, OA)>,
+}
+
+impl<T1, T2, OA, F> fmt::Debug for PrimitiveBinaryExpression<T1, T2, OA, F>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    OA: Array,
+    F: Fn(&PrimitiveArray<T1>, &PrimitiveArray<T2>, Option<&Bitmap>) -> Result<OA>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrimitiveBinaryExpression")
+            .field("kernel", &std::any::type_name::<F>())
+            .field("expr_ia1", &self.expr_ia1)
+            .field("expr_ia2", &self.expr_ia2)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+impl<T1, T2, OA, F> Expression for PrimitiveBinaryExpression<T1, T2, OA, F>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    OA: Array + Into<ArrayImpl>,
+    F: Fn(&PrimitiveArray<T1>, &PrimitiveArray<T2>, Option<&Bitmap>) -> Result<OA> + Sync + Send,
+    for<'a> &'a PrimitiveArray<T1>: From<&'a ArrayImpl>,
+    for<'a> &'a PrimitiveArray<T2>: From<&'a ArrayImpl>,
+{
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, data_chunk: &DataChunk) -> Result<ArrayRef> {
+        compact_selected(self.eval_selected(data_chunk)?, data_chunk)
+    }
+
+    /// If both operands have a slot per row, the kernel is applied to the whole buffers and the
+    /// result is left to be compacted once by the root of the expression tree. Otherwise the
+    /// operand with a slot per row, if any, is compacted so that the buffers line up.
+    fn eval_selected(&self, data_chunk: &DataChunk) -> Result<ArrayRef> {
+        let ret_ia1 = self.expr_ia1.eval_selected(data_chunk)?;
+        let ret_ia2 = self.expr_ia2.eval_selected(data_chunk)?;
+        let (ret_ia1, ret_ia2, selection) = match (
+            has_all_rows(&ret_ia1, data_chunk)?,
+            has_all_rows(&ret_ia2, data_chunk)?,
+        ) {
+            // Kernels compute every slot, so the invisible rows must be masked out, or else they
+            // could raise errors on rows that are filtered out.
+            (true, true) => (ret_ia1, ret_ia2, data_chunk.get_visibility_ref()),
+            (false, false) => (ret_ia1, ret_ia2, None),
+            _ => (
+                compact_selected(ret_ia1, data_chunk)?,
+                compact_selected(ret_ia2, data_chunk)?,
+                None,
+            ),
+        };
+        let arr_ia1: &PrimitiveArray<T1> = ret_ia1.as_ref().into();
+        let arr_ia2: &PrimitiveArray<T2> = ret_ia2.as_ref().into();
+        Ok(Arc::new((self.kernel)(arr_ia1, arr_ia2, selection)?.into()))
+    }
+}
+
+impl<T1, T2, OA, F> PrimitiveBinaryExpression<T1, T2, OA, F>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    OA: Array,
+    F: Fn(&PrimitiveArray<T1>, &PrimitiveArray<T2>, Option<&Bitmap>) -> Result<OA>,
+{
+    pub fn new(
+        expr_ia1: BoxedExpression,
+        expr_ia2: BoxedExpression,
+        return_type: DataType,
+        kernel: F,
+    ) -> Self {
+        Self {
+            expr_ia1,
+            expr_ia2,
+            return_type,
+            kernel,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Returns whether [`new_primitive_binary_expr`] can evaluate `expr_type` on operands of types `l`
+/// and `r`.
+pub fn is_primitive_binary_expr(expr_type: Type, l: &DataType, r: &DataType) -> bool {
+    l == r
+        && matches!(
+            l,
+            DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64
+        )
+        && matches!(
+            expr_type,
+            Type::Equal
+                | Type::NotEqual
+                | Type::LessThan
+                | Type::LessThanOrEqual
+                | Type::GreaterThan
+                | Type::GreaterThanOrEqual
+                | Type::Add
+                | Type::Subtract
+                | Type::Multiply
+        )
+}
+
+/// Creates a binary expression evaluated with kernels. It must only be called if
+/// [`is_primitive_binary_expr`] holds.
+pub fn new_primitive_binary_expr(
+    expr_type: Type,
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> BoxedExpression {
+    match l.return_type() {
+        DataType::Int16 => new_typed_binary_expr::<i16>(expr_type, ret, l, r),
+        DataType::Int32 => new_typed_binary_expr::<i32>(expr_type, ret, l, r),
+        DataType::Int64 => new_typed_binary_expr::<i64>(expr_type, ret, l, r),
+        DataType::Float32 => new_typed_binary_expr::<OrderedF32>(expr_type, ret, l, r),
+        DataType::Float64 => new_typed_binary_expr::<OrderedF64>(expr_type, ret, l, r),
+        other => unimplemented!("The primitive expression on {:?} is not supported", other),
+    }
+}
+
+fn new_typed_binary_expr<T>(
+    expr_type: Type,
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> BoxedExpression
+where
+    T: PrimitiveArrayItemType + PartialOrd + OverflowingArithmetic,
+    for<'a> &'a PrimitiveArray<T>: From<&'a ArrayImpl>,
+{
+    match expr_type {
+        Type::Equal => new_compare_expr(l, r, ret, |a: T, b: T| a == b),
+        Type::NotEqual => new_compare_expr(l, r, ret, |a: T, b: T| a != b),
+        Type::LessThan => new_compare_expr(l, r, ret, |a: T, b: T| a < b),
+        Type::LessThanOrEqual => new_compare_expr(l, r, ret, |a: T, b: T| a <= b),
+        Type::GreaterThan => new_compare_expr(l, r, ret, |a: T, b: T| a > b),
+        Type::GreaterThanOrEqual => new_compare_expr(l, r, ret, |a: T, b: T| a >= b),
+        Type::Add => new_arithmetic_expr(l, r, ret, T::overflowing_add),
+        Type::Subtract => new_arithmetic_expr(l, r, ret, T::overflowing_sub),
+        Type::Multiply => new_arithmetic_expr(l, r, ret, T::overflowing_mul),
+        _ => unimplemented!("The primitive expression {:?} is not supported", expr_type),
+    }
+}
+
+fn new_compare_expr<T, F>(
+    l: BoxedExpression,
+    r: BoxedExpression,
+    ret: DataType,
+    op: F,
+) -> BoxedExpression
+where
+    T: PrimitiveArrayItemType,
+    F: Fn(T, T) -> bool + Sync + Send + 'static,
+    for<'a> &'a PrimitiveArray<T>: From<&'a ArrayImpl>,
+{
+    Box::new(PrimitiveBinaryExpression::<T, T, BoolArray, _>::new(
+        l,
+        r,
+        ret,
+        move |l: &PrimitiveArray<T>, r: &PrimitiveArray<T>, _: Option<&Bitmap>| {
+            compare_primitive(l, r, &op)
+        },
+    ))
+}
+
+fn new_arithmetic_expr<T, F>(
+    l: BoxedExpression,
+    r: BoxedExpression,
+    ret: DataType,
+    op: F,
+) -> BoxedExpression
+where
+    T: PrimitiveArrayItemType,
+    F: Fn(T, T) -> (T, bool) + Sync + Send + 'static,
+    for<'a> &'a PrimitiveArray<T>: From<&'a ArrayImpl>,
+{
+    Box::new(
+        PrimitiveBinaryExpression::<T, T, PrimitiveArray<T>, _>::new(
+            l,
+            r,
+            ret,
+            move |l: &PrimitiveArray<T>, r: &PrimitiveArray<T>, selection: Option<&Bitmap>| {
+                checked_binary_primitive(l, r, selection, &op)
+            },
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::{F64Array, I64Array};
+    use risingwave_common::buffer::Bitmap;
+    use risingwave_pb::data::data_type::TypeName;
+
+    use super::*;
+    use crate::expr::template::BinaryExpression;
+    use crate::expr::test_utils::make_expression;
+    use crate::expr::{build_from_prost, InputRefExpression, LiteralExpression};
+    use crate::vector_op::arithmetic_op::general_add;
+
+    /// Returns the same array regardless of the input chunk and its visibility.
+    #[derive(Debug)]
+    struct ConstArrayExpression(ArrayRef);
+
+    impl Expression for ConstArrayExpression {
+        fn return_type(&self) -> DataType {
+            DataType::Int64
+        }
+
+        fn eval(&self, _input: &DataChunk) -> Result<ArrayRef> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_primitive_binary_expr_with_visibility() {
+        let lhs = I64Array::from_slice(&[Some(1), Some(i64::MAX), None, Some(4)]).unwrap();
+        let rhs = I64Array::from_slice(&[Some(2), Some(1), Some(3), Some(-4)]).unwrap();
+        let data_chunk = DataChunk::builder()
+            .columns(vec![
+                Column::new(Arc::new(lhs.into())),
+                Column::new(Arc::new(rhs.into())),
+            ])
+            .build();
+        let expr = build_from_prost(&make_expression(
+            Type::Add,
+            &[TypeName::Int64, TypeName::Int64],
+            &[0, 1],
+        ))
+        .unwrap();
+        assert!(expr.eval(&data_chunk).is_err());
+
+        // The overflowing row is filtered out.
+        let visibility = Bitmap::from_bool_slice(&[true, false, true, true]);
+        let data_chunk = data_chunk.with_visibility(visibility);
+        let res = expr.eval(&data_chunk).unwrap();
+        let arr: &I64Array = res.as_ref().into();
+        assert_eq!(arr.iter().collect_vec(), vec![Some(3), None, Some(0)]);
+
+        // Nested expressions only compact the result at the root.
+        let add = || {
+            build_from_prost(&make_expression(
+                Type::Add,
+                &[TypeName::Int64, TypeName::Int64],
+                &[0, 1],
+            ))
+            .unwrap()
+        };
+        let input_ref =
+            || -> BoxedExpression { Box::new(InputRefExpression::new(DataType::Int64, 0)) };
+        let vectorized = new_primitive_binary_expr(Type::Add, DataType::Int64, add(), input_ref());
+        assert_eq!(vectorized.eval_selected(&data_chunk).unwrap().len(), 4);
+        let row_wise = BinaryExpression::<I64Array, I64Array, I64Array, _>::new(
+            add(),
+            input_ref(),
+            DataType::Int64,
+            general_add::<i64, i64, i64>,
+        );
+        for res in [
+            vectorized.eval(&data_chunk).unwrap(),
+            row_wise.eval(&data_chunk).unwrap(),
+        ] {
+            let arr: &I64Array = res.as_ref().into();
+            assert_eq!(arr.iter().collect_vec(), vec![Some(4), None, Some(4)]);
+        }
+    }
+
+    #[test]
+    fn test_mixed_children_with_visibility() {
+        let col: ArrayRef = Arc::new(
+            I64Array::from_slice(&[Some(1), Some(2), None, Some(4)])
+                .unwrap()
+                .into(),
+        );
+        let data_chunk = DataChunk::builder()
+            .columns(vec![Column::new(col.clone())])
+            .build()
+            .with_visibility(Bitmap::from_bool_slice(&[false, true, true, true]));
+
+        // The full column must line up with the literal, which only has the visible rows.
+        let children = || -> (BoxedExpression, BoxedExpression) {
+            (
+                Box::new(ConstArrayExpression(col.clone())),
+                Box::new(LiteralExpression::new(DataType::Int64, Some(10i64.into()))),
+            )
+        };
+        let (l, r) = children();
+        let row_wise = BinaryExpression::<I64Array, I64Array, I64Array, _>::new(
+            l,
+            r,
+            DataType::Int64,
+            general_add::<i64, i64, i64>,
+        );
+        let (l, r) = children();
+        let vectorized = new_primitive_binary_expr(Type::Add, DataType::Int64, l, r);
+        for res in [
+            row_wise.eval(&data_chunk).unwrap(),
+            vectorized.eval(&data_chunk).unwrap(),
+        ] {
+            let arr: &I64Array = res.as_ref().into();
+            assert_eq!(arr.iter().collect_vec(), vec![Some(12), None, Some(14)]);
+        }
+
+        // Arrays of any other length are rejected rather than truncated.
+        let short: ArrayRef = Arc::new(I64Array::from_slice(&[Some(1), Some(2)]).unwrap().into());
+        let expr = BinaryExpression::<I64Array, I64Array, I64Array, _>::new(
+            Box::new(ConstArrayExpression(short)),
+            Box::new(InputRefExpression::new(DataType::Int64, 0)),
+            DataType::Int64,
+            general_add::<i64, i64, i64>,
+        );
+        assert!(expr.eval(&data_chunk).is_err());
+    }
+
+    #[test]
+    fn test_primitive_compare_float() {
+        let lhs = F64Array::from_slice(&[Some(1.0.into()), Some(f64::NAN.into()), None]).unwrap();
+        let rhs =
+            F64Array::from_slice(&[Some(2.0.into()), Some(1.0.into()), Some(0.0.into())]).unwrap();
+        let data_chunk = DataChunk::builder()
+            .columns(vec![
+                Column::new(Arc::new(lhs.into())),
+                Column::new(Arc::new(rhs.into())),
+            ])
+            .build();
+        let expr = build_from_prost(&make_expression(
+            Type::GreaterThan,
+            &[TypeName::Float64, TypeName::Float64],
+            &[0, 1],
+        ))
+        .unwrap();
+        let res = expr.eval(&data_chunk).unwrap();
+        let arr: &BoolArray = res.as_ref().into();
+        // NaN is greater than any other value, as in the row-wise comparison.
+        assert_eq!(
+            arr.iter().collect_vec(),
+            vec![Some(false), Some(true), None]
+        );
+    }
+}
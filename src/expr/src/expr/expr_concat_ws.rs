@@ -0,0 +1,141 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use risingwave_common::array::{Array, ArrayBuilder, ArrayRef, DataChunk, Utf8ArrayBuilder};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+
+use crate::expr::{BoxedExpression, Expression};
+
+/// `concat(text, ...)` concatenates the strings and ignores nulls. The arguments are cast to
+/// `varchar` by the frontend.
+#[derive(Debug)]
+pub struct ConcatExpression {
+    args: Vec<BoxedExpression>,
+}
+
+impl ConcatExpression {
+    pub fn new(args: Vec<BoxedExpression>) -> Self {
+        Self { args }
+    }
+}
+
+impl Expression for ConcatExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Varchar
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let args: Vec<_> = self.args.iter().map(|arg| arg.eval(input)).try_collect()?;
+        let args = args.iter().map(|arg| arg.as_utf8()).collect_vec();
+        let mut builder = Utf8ArrayBuilder::new(input.capacity())?;
+        for idx in 0..input.capacity() {
+            let mut writer = builder.writer().begin();
+            for arg in &args {
+                if let Some(s) = arg.value_at(idx) {
+                    writer.write_ref(s)?;
+                }
+            }
+            builder = writer.finish()?.into_inner();
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+/// `concat_ws(separator, text, ...)` concatenates the non-null strings with the separator. Returns
+/// null if the separator is null.
+#[derive(Debug)]
+pub struct ConcatWsExpression {
+    separator: BoxedExpression,
+    args: Vec<BoxedExpression>,
+}
+
+impl ConcatWsExpression {
+    pub fn new(separator: BoxedExpression, args: Vec<BoxedExpression>) -> Self {
+        Self { separator, args }
+    }
+}
+
+impl Expression for ConcatWsExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Varchar
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let separator = self.separator.eval(input)?;
+        let separator = separator.as_utf8();
+        let args: Vec<_> = self.args.iter().map(|arg| arg.eval(input)).try_collect()?;
+        let args = args.iter().map(|arg| arg.as_utf8()).collect_vec();
+        let mut builder = Utf8ArrayBuilder::new(input.capacity())?;
+        for idx in 0..input.capacity() {
+            let Some(separator) = separator.value_at(idx) else {
+                builder.append(None)?;
+                continue;
+            };
+            let mut writer = builder.writer().begin();
+            for (i, s) in args.iter().filter_map(|arg| arg.value_at(idx)).enumerate() {
+                if i > 0 {
+                    writer.write_ref(separator)?;
+                }
+                writer.write_ref(s)?;
+            }
+            builder = writer.finish()?.into_inner();
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::Utf8Array;
+
+    use super::*;
+    use crate::expr::InputRefExpression;
+
+    fn input(idx: usize) -> BoxedExpression {
+        Box::new(InputRefExpression::new(DataType::Varchar, idx))
+    }
+
+    fn chunk() -> DataChunk {
+        let columns = [
+            [Some(","), Some("-"), None],
+            [Some("a"), None, Some("c")],
+            [Some("b"), Some("b"), Some("d")],
+        ]
+        .iter()
+        .map(|column| Column::new(Arc::new(Utf8Array::from_slice(column).unwrap().into())))
+        .collect();
+        DataChunk::builder().columns(columns).build()
+    }
+
+    #[test]
+    fn test_concat() {
+        let expr = ConcatExpression::new(vec![input(0), input(1), input(2)]);
+        let result = expr.eval(&chunk()).unwrap();
+        let result = result.as_utf8().iter().collect_vec();
+        assert_eq!(result, [Some(",ab"), Some("-b"), Some("cd")]);
+    }
+
+    #[test]
+    fn test_concat_ws() {
+        let expr = ConcatWsExpression::new(input(0), vec![input(1), input(2)]);
+        let result = expr.eval(&chunk()).unwrap();
+        let result = result.as_utf8().iter().collect_vec();
+        assert_eq!(result, [Some("a,b"), Some("b"), None]);
+    }
+}
@@ -53,6 +53,11 @@ impl Expression for InputRefExpression {
             None => Ok(array),
         }
     }
+
+    /// Returns the whole column without compacting it, which is left to the parent expression.
+    fn eval_selected(&self, input: &DataChunk) -> Result<ArrayRef> {
+        Ok(input.column_at(self.idx).array())
+    }
 }
 
 impl InputRefExpression {
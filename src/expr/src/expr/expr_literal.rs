@@ -94,6 +94,7 @@ fn literal_type_match(return_type: &DataType, literal: Option<&ScalarImpl>) -> b
                     | (DataType::Int16, ScalarImpl::Int16(_))
                     | (DataType::Int32, ScalarImpl::Int32(_))
                     | (DataType::Int64, ScalarImpl::Int64(_))
+                    | (DataType::Timestampz, ScalarImpl::Int64(_))
                     | (DataType::Float32, ScalarImpl::Float32(_))
                     | (DataType::Float64, ScalarImpl::Float64(_))
                     | (DataType::Date, ScalarImpl::Int32(_))
@@ -103,6 +104,9 @@ fn literal_type_match(return_type: &DataType, literal: Option<&ScalarImpl>) -> b
                     | (DataType::Timestamp, ScalarImpl::NaiveDateTime(_))
                     | (DataType::Decimal, ScalarImpl::Decimal(_))
                     | (DataType::Interval, ScalarImpl::Interval(_))
+                    | (DataType::List { .. }, ScalarImpl::List(_))
+                    | (DataType::Struct { .. }, ScalarImpl::Struct(_))
+                    | (DataType::Jsonb, ScalarImpl::Jsonb(_))
             )
         }
         None => true,
@@ -183,7 +187,7 @@ impl<'a> TryFrom<&'a ExprNode> for LiteralExpression {
                         InternalError(format!("Failed to deserialize i32, reason: {:?}", e))
                     })?,
                 )),
-                TypeName::Int64 => ScalarImpl::Int64(i64::from_be_bytes(
+                TypeName::Int64 | TypeName::Timestampz => ScalarImpl::Int64(i64::from_be_bytes(
                     prost_value.get_body().as_slice().try_into().map_err(|e| {
                         InternalError(format!("Failed to deserialize i64, reason: {:?}", e))
                     })?,
@@ -227,6 +231,16 @@ impl<'a> TryFrom<&'a ExprNode> for LiteralExpression {
                         prost.get_return_type()?.get_interval_type()?,
                     )?)
                 }
+                TypeName::Jsonb => ScalarImpl::Jsonb(
+                    std::str::from_utf8(prost_value.get_body())
+                        .map_err(|e| {
+                            InternalError(format!("Failed to deserialize jsonb, reason: {:?}", e))
+                        })?
+                        .parse()
+                        .map_err(|e| {
+                            InternalError(format!("Failed to deserialize jsonb, reason: {:?}", e))
+                        })?,
+                ),
                 _ => {
                     return Err(InternalError(format!(
                         "Unrecognized type name: {:?}",
@@ -0,0 +1,358 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Regular expression functions. Their patterns must be constants, so that each regex is compiled
+//! only once when the expression is built rather than for every row.
+
+use std::sync::Arc;
+
+use regex::{Regex, RegexBuilder};
+use risingwave_common::array::{
+    Array, ArrayBuilder, ArrayRef, BoolArrayBuilder, DataChunk, ListValue, Utf8ArrayBuilder,
+};
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use crate::expr::{BoxedExpression, Expression};
+
+/// A compiled regex with the flags of postgres regex functions.
+#[derive(Debug, Clone)]
+pub struct RegexpContext {
+    regex: Regex,
+    /// Whether to replace all matches rather than only the first one, set by the `g` flag.
+    global: bool,
+}
+
+impl RegexpContext {
+    /// Compiles the pattern with postgres flags. Supported flags are `i` and `c` for case
+    /// insensitive and sensitive matching, `n` for newline-sensitive matching, and `g` for global
+    /// replacement.
+    pub fn new(pattern: &str, flags: &str) -> Result<Self> {
+        let mut builder = RegexBuilder::new(pattern);
+        // Unlike most regex engines, `.` matches newlines in postgres by default.
+        builder.dot_matches_new_line(true);
+        let mut global = false;
+        for flag in flags.chars() {
+            match flag {
+                'i' => {
+                    builder.case_insensitive(true);
+                }
+                'c' => {
+                    builder.case_insensitive(false);
+                }
+                'n' => {
+                    builder.dot_matches_new_line(false).multi_line(true);
+                }
+                'g' => global = true,
+                _ => {
+                    return Err(InvalidInputSyntax(format!(
+                        "invalid regular expression option: \"{}\"",
+                        flag
+                    ))
+                    .into())
+                }
+            }
+        }
+        Self::build(builder, global)
+    }
+
+    /// Compiles a `SIMILAR TO` pattern, which must match the whole string. `%` and `_` are the
+    /// wildcards of `LIKE`, `\` escapes the next character, and `|`, `*`, `+`, `?`, `{m,n}`,
+    /// parentheses and bracket expressions have their regex meanings.
+    pub fn from_similar_to(pattern: &str) -> Result<Self> {
+        let mut translated = String::from("^(?:");
+        let mut chars = pattern.chars();
+        let mut in_bracket = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(c) => translated.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                    None => {
+                        return Err(InvalidInputSyntax(
+                            "SIMILAR TO pattern must not end with escape character".to_string(),
+                        )
+                        .into())
+                    }
+                },
+                '[' if !in_bracket => {
+                    in_bracket = true;
+                    translated.push(c);
+                }
+                ']' if in_bracket => {
+                    in_bracket = false;
+                    translated.push(c);
+                }
+                _ if in_bracket => translated.push(c),
+                '%' => translated.push_str(".*"),
+                '_' => translated.push('.'),
+                '|' | '*' | '+' | '?' | '{' | '}' | '(' | ')' | ',' => translated.push(c),
+                _ => translated.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            }
+        }
+        translated.push_str(")$");
+        let mut builder = RegexBuilder::new(&translated);
+        builder.dot_matches_new_line(true);
+        Self::build(builder, false)
+    }
+
+    fn build(builder: RegexBuilder, global: bool) -> Result<Self> {
+        let regex = builder
+            .build()
+            .map_err(|e| InvalidInputSyntax(format!("invalid regular expression: {}", e)))?;
+        Ok(Self { regex, global })
+    }
+
+    /// Whether the `g` flag is set.
+    pub fn is_global(&self) -> bool {
+        self.global
+    }
+}
+
+/// `regexp_match(text, pattern[, flags])`. Returns the captured groups of the first match, or the
+/// whole match if the pattern has no groups. Returns null if there is no match.
+#[derive(Debug)]
+pub struct RegexpMatchExpression {
+    child: BoxedExpression,
+    ctx: RegexpContext,
+}
+
+impl RegexpMatchExpression {
+    pub fn new(child: BoxedExpression, ctx: RegexpContext) -> Self {
+        Self { child, ctx }
+    }
+
+    fn match_one(&self, text: &str) -> Option<ListValue> {
+        let captures = self.ctx.regex.captures(text)?;
+        let groups = if captures.len() == 1 {
+            vec![captures.get(0)]
+        } else {
+            captures.iter().skip(1).collect()
+        };
+        Some(ListValue::new(
+            groups
+                .into_iter()
+                .map(|m| m.map(|m| ScalarImpl::Utf8(m.as_str().to_string())))
+                .collect(),
+        ))
+    }
+}
+
+impl Expression for RegexpMatchExpression {
+    fn return_type(&self) -> DataType {
+        DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        }
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text = self.child.eval(input)?;
+        let text = text.as_utf8();
+        let mut builder = self.return_type().create_array_builder(text.len())?;
+        for text in text.iter() {
+            let matched = text.and_then(|text| self.match_one(text));
+            builder.append_datum(&matched.map(ScalarImpl::List))?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+/// `regexp_replace(text, pattern, replacement[, flags])`. Replaces the first match, or all matches
+/// with the `g` flag. In the replacement, `\n` refers to the `n`th group and `\&` to the whole
+/// match.
+#[derive(Debug)]
+pub struct RegexpReplaceExpression {
+    child: BoxedExpression,
+    ctx: RegexpContext,
+    /// The replacement in the syntax of `regex`, e.g. `${1}` for `\1`.
+    replacement: String,
+}
+
+impl RegexpReplaceExpression {
+    pub fn new(child: BoxedExpression, ctx: RegexpContext, replacement: &str) -> Self {
+        let mut translated = String::with_capacity(replacement.len());
+        let mut chars = replacement.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('\\', Some(d)) if d.is_ascii_digit() => {
+                    translated.push_str(&format!("${{{}}}", d));
+                    chars.next();
+                }
+                ('\\', Some('&')) => {
+                    translated.push_str("${0}");
+                    chars.next();
+                }
+                ('\\', Some('\\')) => {
+                    translated.push('\\');
+                    chars.next();
+                }
+                ('$', _) => translated.push_str("$$"),
+                _ => translated.push(c),
+            }
+        }
+        Self {
+            child,
+            ctx,
+            replacement: translated,
+        }
+    }
+}
+
+impl Expression for RegexpReplaceExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Varchar
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text = self.child.eval(input)?;
+        let text = text.as_utf8();
+        let mut builder = Utf8ArrayBuilder::new(text.len())?;
+        for text in text.iter() {
+            let replaced = text.map(|text| {
+                if self.ctx.global {
+                    self.ctx.regex.replace_all(text, self.replacement.as_str())
+                } else {
+                    self.ctx.regex.replace(text, self.replacement.as_str())
+                }
+            });
+            builder.append(replaced.as_deref())?;
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+/// `text SIMILAR TO pattern`.
+#[derive(Debug)]
+pub struct SimilarToExpression {
+    child: BoxedExpression,
+    ctx: RegexpContext,
+}
+
+impl SimilarToExpression {
+    pub fn new(child: BoxedExpression, ctx: RegexpContext) -> Self {
+        Self { child, ctx }
+    }
+}
+
+impl Expression for SimilarToExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Boolean
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text = self.child.eval(input)?;
+        let text = text.as_utf8();
+        let mut builder = BoolArrayBuilder::new(text.len())?;
+        for text in text.iter() {
+            builder.append(text.map(|text| self.ctx.regex.is_match(text)))?;
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::{ArrayImpl, Utf8Array};
+
+    use super::*;
+    use crate::expr::InputRefExpression;
+
+    fn eval_on(expr: &dyn Expression, texts: &[Option<&str>]) -> ArrayRef {
+        let array: ArrayImpl = Utf8Array::from_slice(texts).unwrap().into();
+        let chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(array))])
+            .build();
+        expr.eval(&chunk).unwrap()
+    }
+
+    fn input() -> BoxedExpression {
+        Box::new(InputRefExpression::new(DataType::Varchar, 0))
+    }
+
+    #[test]
+    fn test_regexp_match() {
+        let ctx = RegexpContext::new(r"(\d+)-(\d+)?", "").unwrap();
+        let expr = RegexpMatchExpression::new(input(), ctx);
+        let result = eval_on(&expr, &[Some("ab 12-34"), Some("12-"), Some("ab"), None]);
+        let list = |values: &[Option<&str>]| {
+            Some(ScalarImpl::List(ListValue::new(
+                values
+                    .iter()
+                    .map(|v| v.map(|v| ScalarImpl::Utf8(v.to_string())))
+                    .collect(),
+            )))
+        };
+        assert_eq!(result.datum_at(0), list(&[Some("12"), Some("34")]));
+        assert_eq!(result.datum_at(1), list(&[Some("12"), None]));
+        assert_eq!(result.datum_at(2), None);
+        assert_eq!(result.datum_at(3), None);
+
+        let ctx = RegexpContext::new("B.", "i").unwrap();
+        let expr = RegexpMatchExpression::new(input(), ctx);
+        let result = eval_on(&expr, &[Some("abc")]);
+        assert_eq!(result.datum_at(0), list(&[Some("bc")]));
+
+        assert!(RegexpContext::new("a", "x").is_err());
+        assert!(RegexpContext::new("(", "").is_err());
+    }
+
+    #[test]
+    fn test_regexp_replace() {
+        let expr = RegexpReplaceExpression::new(
+            input(),
+            RegexpContext::new("(a)(b)", "").unwrap(),
+            r"<\2\1\&$>",
+        );
+        let result = eval_on(&expr, &[Some("abab"), None]);
+        assert_eq!(result.as_utf8().value_at(0), Some("<baab$>ab"));
+        assert_eq!(result.as_utf8().value_at(1), None);
+
+        let ctx = RegexpContext::new("A", "gi").unwrap();
+        assert!(ctx.is_global());
+        let expr = RegexpReplaceExpression::new(input(), ctx, "x");
+        let result = eval_on(&expr, &[Some("banana")]);
+        assert_eq!(result.as_utf8().value_at(0), Some("bxnxnx"));
+    }
+
+    #[test]
+    fn test_similar_to() {
+        let cases = [
+            ("abc", "abc", true),
+            ("abc", "a", false),
+            ("abc", "%(b|d)%", true),
+            ("abc", "(b|c)%", false),
+            ("a.c", "a.c", true),
+            ("abc", "a.c", false),
+            ("abc", "a_c", true),
+            ("a%c", r"a\%c", true),
+            ("abbb", "ab+", true),
+            ("ab", "[a-c]{2}", true),
+            ("a^b$", "a^b$", true),
+        ];
+        for (text, pattern, expected) in cases {
+            let expr =
+                SimilarToExpression::new(input(), RegexpContext::from_similar_to(pattern).unwrap());
+            let result = eval_on(&expr, &[Some(text)]);
+            assert_eq!(
+                result.as_bool().value_at(0),
+                Some(expected),
+                "text={}, pattern={}",
+                text,
+                pattern
+            );
+        }
+        assert!(RegexpContext::from_similar_to("a\\").is_err());
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use risingwave_common::array::{ArrayImpl, ArrayRef, DataChunk, StructValue};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarImpl, ScalarRefImpl, ToOwnedDatum};
+
+use crate::expr::{BoxedExpression, Expression};
+
+/// Constructs a struct from its fields, e.g. `ROW(1, v1)` or `(1, v1)`.
+#[derive(Debug)]
+pub struct StructExpression {
+    return_type: DataType,
+    fields: Vec<BoxedExpression>,
+}
+
+impl StructExpression {
+    pub fn new(return_type: DataType, fields: Vec<BoxedExpression>) -> Self {
+        Self {
+            return_type,
+            fields,
+        }
+    }
+}
+
+impl Expression for StructExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let fields: Vec<_> = self
+            .fields
+            .iter()
+            .map(|field| field.eval(input))
+            .try_collect()?;
+        let mut builder = self.return_type.create_array_builder(input.capacity())?;
+        for idx in 0..input.capacity() {
+            let values = fields
+                .iter()
+                .map(|field| field.value_at(idx).to_owned_datum())
+                .collect_vec();
+            builder.append_datum(&Some(ScalarImpl::Struct(StructValue::new(values))))?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+/// Selects a field of a struct by its 0-based position, e.g. `(v1).a`. The field of a null struct
+/// is null.
+#[derive(Debug)]
+pub struct FieldExpression {
+    return_type: DataType,
+    input: BoxedExpression,
+    index: usize,
+}
+
+impl FieldExpression {
+    pub fn new(return_type: DataType, input: BoxedExpression, index: usize) -> Self {
+        Self {
+            return_type,
+            input,
+            index,
+        }
+    }
+}
+
+fn field_at(array: &ArrayImpl, idx: usize, index: usize) -> Result<Option<ScalarRefImpl<'_>>> {
+    match array.value_at(idx) {
+        Some(ScalarRefImpl::Struct(value)) => Ok(value.fields_ref()[index]),
+        None => Ok(None),
+        Some(_) => Err(ErrorCode::InternalError(format!(
+            "expect a struct, got {}",
+            array.get_ident()
+        ))
+        .into()),
+    }
+}
+
+impl Expression for FieldExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let array = self.input.eval(input)?;
+        let mut builder = self.return_type.create_array_builder(array.len())?;
+        for idx in 0..array.len() {
+            builder.append_datum_ref(field_at(&array, idx, self.index)?)?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{I32Array, Utf8Array};
+    use risingwave_common::column;
+
+    use super::*;
+    use crate::expr::{InputRefExpression, LiteralExpression};
+
+    #[test]
+    fn test_struct_expressions() {
+        let chunk = DataChunk::builder()
+            .columns(vec![
+                column! {I32Array, [Some(1), None]},
+                column! {Utf8Array, [Some("a"), Some("b")]},
+            ])
+            .build();
+        let struct_type = DataType::Struct {
+            fields: vec![DataType::Int32, DataType::Varchar].into(),
+        };
+        // ROW(v1, v2)
+        let struct_expr = || {
+            Box::new(StructExpression::new(
+                struct_type.clone(),
+                vec![
+                    Box::new(InputRefExpression::new(DataType::Int32, 0)),
+                    Box::new(InputRefExpression::new(DataType::Varchar, 1)),
+                ],
+            )) as BoxedExpression
+        };
+        let structs = struct_expr().eval(&chunk).unwrap();
+        assert_eq!(
+            structs.datum_at(1),
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                None,
+                Some(ScalarImpl::Utf8("b".to_string()))
+            ])))
+        );
+
+        // (ROW(v1, v2)).f1
+        let field = FieldExpression::new(DataType::Varchar, struct_expr(), 1);
+        assert_eq!(
+            field.eval(&chunk).unwrap().as_utf8().iter().collect_vec(),
+            vec![Some("a"), Some("b")]
+        );
+
+        // (NULL::STRUCT<f0 INT, f1 VARCHAR>).f0
+        let field = FieldExpression::new(
+            DataType::Int32,
+            Box::new(LiteralExpression::new(struct_type, None)),
+            0,
+        );
+        assert_eq!(
+            field.eval(&chunk).unwrap().as_int32().iter().collect_vec(),
+            vec![None, None]
+        );
+    }
+}
@@ -24,15 +24,19 @@ use crate::expr::expr_is_null::{IsNotNullExpression, IsNullExpression};
 use crate::expr::pg_sleep::PgSleepExpression;
 use crate::expr::template::UnaryNullableExpression;
 use crate::expr::BoxedExpression;
-use crate::vector_op::arithmetic_op::general_neg;
+use crate::vector_op::arithmetic_op::{general_neg, sqrt_f64};
 use crate::vector_op::ascii::ascii;
 use crate::vector_op::cast::*;
 use crate::vector_op::cmp::{is_false, is_not_false, is_not_true, is_true};
 use crate::vector_op::conjunction;
+use crate::vector_op::jsonb::jsonb_typeof;
 use crate::vector_op::length::length_default;
 use crate::vector_op::lower::lower;
 use crate::vector_op::ltrim::ltrim;
+use crate::vector_op::md5::md5;
+use crate::vector_op::reverse::reverse;
 use crate::vector_op::rtrim::rtrim;
+use crate::vector_op::timestamptz::f64_sec_to_timestamptz;
 use crate::vector_op::trim::trim;
 use crate::vector_op::upper::upper;
 
@@ -100,8 +104,10 @@ macro_rules! gen_cast {
             { varchar, float64, str_parse },
             { varchar, decimal, str_parse },
             { varchar, boolean, str_to_bool },
+            { varchar, jsonb, str_to_jsonb },
 
             { boolean, varchar, bool_to_str },
+            { jsonb, varchar, jsonb_to_str },
 
             { int16, int32, general_cast },
             { int16, int64, general_cast },
@@ -136,7 +142,9 @@ macro_rules! gen_cast {
             { decimal, float32, to_f32 },
             { decimal, float64, to_f64 },
 
-            { date, timestamp, date_to_timestamp }
+            { date, timestamp, date_to_timestamp },
+            { timestamp, date, timestamp_to_date },
+            { timestamp, time, timestamp_to_time }
         }
     };
 }
@@ -179,7 +187,8 @@ macro_rules! gen_neg {
             int64,
             float32,
             float64,
-            decimal
+            decimal,
+            interval
         }
     };
 }
@@ -240,6 +249,21 @@ pub fn new_unary_expr(
             return_type,
             lower,
         )),
+        (ProstType::JsonbTypeof, _, _) => Box::new(UnaryBytesExpression::<JsonbArray, _>::new(
+            child_expr,
+            return_type,
+            jsonb_typeof,
+        )),
+        (ProstType::Reverse, _, _) => Box::new(UnaryBytesExpression::<Utf8Array, _>::new(
+            child_expr,
+            return_type,
+            reverse,
+        )),
+        (ProstType::Md5, _, _) => Box::new(UnaryBytesExpression::<Utf8Array, _>::new(
+            child_expr,
+            return_type,
+            md5,
+        )),
         (ProstType::Ascii, _, _) => Box::new(UnaryExpression::<Utf8Array, I32Array, _>::new(
             child_expr,
             return_type,
@@ -248,6 +272,20 @@ pub fn new_unary_expr(
         (ProstType::Neg, _, _) => {
             gen_neg! { child_expr, return_type }
         }
+        (ProstType::Sqrt, DataType::Float64, DataType::Float64) => {
+            Box::new(UnaryExpression::<F64Array, F64Array, _>::new(
+                child_expr,
+                return_type,
+                sqrt_f64,
+            ))
+        }
+        (ProstType::ToTimestamp, DataType::Timestampz, DataType::Float64) => {
+            Box::new(UnaryExpression::<F64Array, I64Array, _>::new(
+                child_expr,
+                return_type,
+                f64_sec_to_timestamptz,
+            ))
+        }
         (ProstType::PgSleep, _, DataType::Decimal) => Box::new(PgSleepExpression::new(child_expr)),
 
         (expr, ret, child) => {
@@ -15,18 +15,23 @@
 mod agg;
 pub mod build_expr_from_prost;
 pub mod data_types;
+mod expr_array;
 mod expr_binary_bytes;
 pub mod expr_binary_nonnull;
 pub mod expr_binary_nullable;
+mod expr_binary_primitive;
 mod expr_case;
+mod expr_concat_ws;
 mod expr_in;
 mod expr_input_ref;
 mod expr_is_null;
 mod expr_literal;
+mod expr_regexp;
+mod expr_struct;
 mod expr_ternary_bytes;
 pub mod expr_unary;
 mod pg_sleep;
-mod template;
+pub mod template;
 
 use std::convert::TryFrom;
 use std::slice;
@@ -35,9 +40,9 @@ use std::sync::Arc;
 pub use agg::AggKind;
 pub use expr_input_ref::InputRefExpression;
 pub use expr_literal::*;
-use risingwave_common::array::{ArrayRef, DataChunk, Row};
+use risingwave_common::array::{ArrayImpl, ArrayRef, DataChunk, Row};
 use risingwave_common::error::ErrorCode::InternalError;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
 use risingwave_pb::expr::ExprNode;
 
@@ -56,6 +61,15 @@ pub trait Expression: std::fmt::Debug + Sync + Send {
     /// * `input` - input data of the Project Executor
     fn eval(&self, input: &DataChunk) -> Result<ArrayRef>;
 
+    /// Evaluates the expression on the visible rows of `input`, i.e. the ones in its selection
+    /// vector, for a parent expression. Unlike [`Expression::eval`], the result may also have one
+    /// slot per row of `input`, where the slots of invisible rows are arbitrary. This saves
+    /// compacting the results of children which are computed over whole columns, e.g.
+    /// [`InputRefExpression`]. Use [`has_all_rows`] to tell the two shapes apart.
+    fn eval_selected(&self, input: &DataChunk) -> Result<ArrayRef> {
+        self.eval(input)
+    }
+
     fn boxed(self) -> BoxedExpression
     where
         Self: Sized + Send + 'static,
@@ -66,18 +80,64 @@ pub trait Expression: std::fmt::Debug + Sync + Send {
 
 pub type BoxedExpression = Box<dyn Expression>;
 
+/// Whether `array` returned by [`Expression::eval_selected`] on `data_chunk` has one slot per row
+/// of the chunk, rather than one per visible row. Returns an error if it has neither. If all rows
+/// are visible, the two shapes are the same and `false` is returned.
+pub(crate) fn has_all_rows(array: &ArrayImpl, data_chunk: &DataChunk) -> Result<bool> {
+    let cardinality = data_chunk.cardinality();
+    if array.len() == cardinality {
+        Ok(false)
+    } else if array.len() == data_chunk.capacity() {
+        Ok(true)
+    } else {
+        Err(InternalError(format!(
+            "expected an array of {} visible rows or {} rows, got {}",
+            cardinality,
+            data_chunk.capacity(),
+            array.len()
+        ))
+        .into())
+    }
+}
+
+/// Compacts `array` returned by [`Expression::eval_selected`] on `data_chunk` to the visible rows,
+/// if it has one slot per row.
+pub(crate) fn compact_selected(array: ArrayRef, data_chunk: &DataChunk) -> Result<ArrayRef> {
+    match data_chunk.get_visibility_ref() {
+        Some(bitmap) if has_all_rows(&array, data_chunk)? => {
+            Ok(Arc::new(array.compact(bitmap, data_chunk.cardinality())?))
+        }
+        _ => Ok(array),
+    }
+}
+
 pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
     use risingwave_pb::expr::expr_node::Type::*;
 
     match prost.get_expr_type()? {
         Cast | Upper | Lower | Not | PgSleep | IsTrue | IsNotTrue | IsFalse | IsNotFalse
-        | IsNull | IsNotNull | Neg | Ascii => build_unary_expr_prost(prost),
+        | IsNull | IsNotNull | Neg | Ascii | Sqrt | JsonbTypeof | Reverse | Md5 | ToTimestamp => {
+            build_unary_expr_prost(prost)
+        }
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual => {
             build_binary_expr_prost(prost)
         }
         Add | Subtract | Multiply | Divide | Modulus => build_binary_expr_prost(prost),
-        Extract | RoundDigit | TumbleStart | Position => build_binary_expr_prost(prost),
-        StreamNullByRowCount | And | Or => build_nullable_binary_expr_prost(prost),
+        Extract | RoundDigit | TumbleStart | Position | JsonbContains | Ilike | StartsWith => {
+            build_binary_expr_prost(prost)
+        }
+        DateTrunc | ToTimestamp1 | AtTimeZone => build_binary_expr_prost(prost),
+        CastWithTimeZone => build_cast_with_time_zone_expr(prost),
+        // `now()` is replaced by the time of the pinned epoch in batch queries, and is not
+        // supported elsewhere yet.
+        Now => Err(ErrorCode::NotImplemented(
+            "now() is only supported in batch queries".to_string(),
+            None.into(),
+        )
+        .into()),
+        StreamNullByRowCount | And | Or | JsonbAccessInner | JsonbAccessStr | JsonbAccessPath => {
+            build_nullable_binary_expr_prost(prost)
+        }
         Substr => build_substr_expr(prost),
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
@@ -90,6 +150,22 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         Case => build_case_expr(prost),
         Translate => build_translate_expr(prost),
         In => build_in_expr(prost),
+        Array => build_array_expr(prost),
+        ArrayAccess => build_array_access_expr(prost),
+        ArrayLength => build_array_length_expr(prost),
+        ArrayContains => build_array_contains_expr(prost),
+        Field => build_field_expr(prost),
+        Row => build_struct_expr(prost),
+        SplitPart => build_split_part_expr(prost),
+        Lpad | Rpad => build_pad_expr(prost),
+        Repeat => build_repeat_expr(prost),
+        ConcatOp => build_concat_op_expr(prost),
+        Concat => build_concat_expr(prost),
+        ConcatWs => build_concat_ws_expr(prost),
+        ToChar => build_to_char_expr(prost),
+        RegexpMatch => build_regexp_match_expr(prost),
+        RegexpReplace => build_regexp_replace_expr(prost),
+        SimilarTo => build_similar_to_expr(prost),
         _ => Err(InternalError(format!(
             "Unsupported expression type: {:?}",
             prost.get_expr_type()
@@ -17,7 +17,7 @@
 use std::fmt;
 use std::sync::Arc;
 
-use itertools::{multizip, Itertools};
+use itertools::multizip;
 use paste::paste;
 use risingwave_common::array::{
     Array, ArrayBuilder, ArrayImpl, ArrayRef, BytesGuard, BytesWriter, DataChunk, Utf8Array,
@@ -25,36 +25,36 @@ use risingwave_common::array::{
 use risingwave_common::error::Result;
 use risingwave_common::types::{option_as_scalar_ref, DataType, Scalar};
 
-use crate::expr::{BoxedExpression, Expression};
+use crate::expr::{has_all_rows, BoxedExpression, Expression};
 
 macro_rules! gen_eval {
     { $macro:ident, $ty_name:ident, $OA:ty, $($arg:ident,)* } => {
         fn eval(&self, data_chunk: &DataChunk) -> Result<ArrayRef> {
             paste! {
                 $(
-                    let [<ret_ $arg:lower>] = self.[<expr_ $arg:lower>].eval(data_chunk)?;
+                    let [<ret_ $arg:lower>] = self.[<expr_ $arg:lower>].eval_selected(data_chunk)?;
                     let [<arr_ $arg:lower>]: &$arg = [<ret_ $arg:lower>].as_ref().into();
+                    let [<all_rows_ $arg:lower>] = has_all_rows(&[<ret_ $arg:lower>], data_chunk)?;
                 )*
 
-                let bitmap = data_chunk.get_visibility_ref();
-                let mut output_array = <$OA as Array>::Builder::new(data_chunk.capacity())?;
-                Ok(Arc::new(match bitmap {
-                    Some(bitmap) => {
-                        for (($([<v_ $arg:lower>], )*), visible) in multizip(($([<arr_ $arg:lower>].iter(), )*)).zip_eq(bitmap.iter()) {
-                            if !visible {
-                                continue;
-                            }
-                            $macro!(self, output_array, $([<v_ $arg:lower>],)*)
-                        }
-                        output_array.finish()?.into()
+                let mut output_array = <$OA as Array>::Builder::new(data_chunk.cardinality())?;
+                if [$([<all_rows_ $arg:lower>], )*].contains(&true) {
+                    // Children with a slot per row are read at the selection vector of the chunk,
+                    // and the compacted ones in order, so that no child is copied.
+                    for (pos, idx) in data_chunk.visible_row_indices().enumerate() {
+                        $(
+                            let [<v_ $arg:lower>] = [<arr_ $arg:lower>].value_at(
+                                if [<all_rows_ $arg:lower>] { idx } else { pos }
+                            );
+                        )*
+                        $macro!(self, output_array, $([<v_ $arg:lower>],)*)
                     }
-                    None => {
-                        for ($([<v_ $arg:lower>], )*) in multizip(($([<arr_ $arg:lower>].iter(), )*)) {
-                            $macro!(self, output_array, $([<v_ $arg:lower>],)*)
-                        }
-                        output_array.finish()?.into()
+                } else {
+                    for ($([<v_ $arg:lower>], )*) in multizip(($([<arr_ $arg:lower>].iter(), )*)) {
+                        $macro!(self, output_array, $([<v_ $arg:lower>],)*)
                     }
-                }))
+                }
+                Ok(Arc::new(output_array.finish()?.into()))
             }
         }
     }
@@ -18,6 +18,9 @@ use risingwave_common::types::*;
 use risingwave_pb::expr::AggCall;
 
 use crate::expr::AggKind;
+use crate::vector_op::agg::approx_count_distinct::ApproxCountDistinct;
+use crate::vector_op::agg::approx_percentile::ApproxPercentile;
+use crate::vector_op::agg::array_agg::ArrayAgg;
 use crate::vector_op::agg::count_star::CountStar;
 use crate::vector_op::agg::functions::*;
 use crate::vector_op::agg::general_agg::*;
@@ -68,7 +71,7 @@ pub struct AggStateFactory {
 impl AggStateFactory {
     pub fn new(prost: &AggCall) -> Result<Self> {
         let return_type = DataType::from(prost.get_return_type()?);
-        let agg_kind = AggKind::try_from(prost.get_type()?)?;
+        let agg_kind = AggKind::from_protobuf(prost)?;
         let distinct = prost.distinct;
         match &prost.get_args()[..] {
             [ref arg] => {
@@ -130,6 +133,33 @@ pub fn create_agg_state_unary(
 ) -> Result<Box<dyn Aggregator>> {
     use crate::expr::data_types::*;
 
+    // `approx_count_distinct` ignores duplicates anyway, so `distinct` makes no difference to it.
+    match (agg_type, &return_type) {
+        (AggKind::ApproxCountDistinct, DataType::Int64) => {
+            return Ok(Box::new(ApproxCountDistinct::new(
+                return_type,
+                input_col_idx,
+            )));
+        }
+        (AggKind::ApproxPercentile(percentile), DataType::Float64)
+            if input_type.is_numeric() && !distinct =>
+        {
+            return Ok(Box::new(ApproxPercentile::new(
+                return_type,
+                input_col_idx,
+                percentile.0,
+            )));
+        }
+        (AggKind::ArrayAgg, DataType::List { datatype }) if **datatype == input_type => {
+            return Ok(Box::new(ArrayAgg::new(
+                return_type,
+                input_col_idx,
+                distinct,
+            )));
+        }
+        _ => {}
+    }
+
     macro_rules! gen_arms {
         [$(($agg:ident, $fn:expr, $in:tt, $ret:tt)),* $(,)?] => {
             match (
@@ -198,6 +228,14 @@ pub fn create_agg_state_unary(
         (Max, max, decimal, decimal),
         (Max, max, boolean, boolean), // TODO(#359): remove once unnecessary
         (Max, max_str, varchar, varchar),
+        (BoolAnd, bool_and, boolean, boolean),
+        (BoolOr, bool_or, boolean, boolean),
+        (BitAnd, bit_and, int16, int16),
+        (BitAnd, bit_and, int32, int32),
+        (BitAnd, bit_and, int64, int64),
+        (BitOr, bit_or, int16, int16),
+        (BitOr, bit_or, int32, int32),
+        (BitOr, bit_or, int64, int64),
         // Global Agg
         (Sum, sum, int64, int64),
         // We remark that SingleValue does not produce a runtime error when it receives zero row.
@@ -267,5 +305,26 @@ mod tests {
         test_create! { decimal_type, SingleValue, decimal_type, is_ok }
         test_create! { bool_type, SingleValue, bool_type, is_ok }
         test_create! { char_type, SingleValue, char_type, is_ok }
+
+        test_create! { bool_type, BoolAnd, bool_type, is_ok }
+        test_create! { int64_type, BoolOr, bool_type, is_err }
+        test_create! { int64_type, BitAnd, int64_type, is_ok }
+        test_create! { decimal_type, BitOr, decimal_type, is_err }
+
+        test_create! { char_type, ApproxCountDistinct, int64_type, is_ok }
+        test_create! { decimal_type, ApproxCountDistinct, decimal_type, is_err }
+
+        let list_type = DataType::List {
+            datatype: Box::new(int64_type.clone()),
+        };
+        test_create! { int64_type, ArrayAgg, list_type, is_ok }
+        test_create! { char_type, ArrayAgg, list_type, is_err }
+
+        let median = AggKind::ApproxPercentile(0.5.into());
+        let float64_type = DataType::Float64;
+        assert!(
+            create_agg_state_unary(int64_type, 0, &median, float64_type.clone(), false).is_ok()
+        );
+        assert!(create_agg_state_unary(char_type, 0, &median, float64_type, false).is_err());
     }
 }
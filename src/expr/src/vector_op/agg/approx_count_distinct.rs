@@ -0,0 +1,241 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::Hasher;
+
+use risingwave_common::array::*;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::*;
+use twox_hash::XxHash64;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// Number of hash bits used to pick a register.
+const INDEX_BITS: u32 = 12;
+const NUM_REGISTERS: usize = 1 << INDEX_BITS;
+
+/// A `HyperLogLog` sketch estimating the number of distinct non-null values, with a standard error
+/// of about 1.6%.
+///
+/// Values are hashed with a fixed seed, so the sketch can be persisted and merged across runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self {
+            registers: vec![0; NUM_REGISTERS],
+        }
+    }
+
+    /// Adds the value at `idx` of `array` to the sketch. Nulls are ignored.
+    pub fn add(&mut self, array: &ArrayImpl, idx: usize) -> Result<()> {
+        if !array.null_bitmap().is_set(idx)? {
+            return Ok(());
+        }
+        let mut hasher = XxHash64::with_seed(0);
+        array.hash_at(idx, &mut hasher);
+        self.add_hash(hasher.finish());
+        Ok(())
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (u64::BITS - INDEX_BITS)) as usize;
+        // The sentinel bit bounds the rank when all remaining bits are zero.
+        let rank = ((hash << INDEX_BITS) | (1 << (INDEX_BITS - 1))).leading_zeros() + 1;
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other);
+        }
+    }
+
+    pub fn estimate(&self) -> i64 {
+        let m = NUM_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|register| 2f64.powi(-(*register as i32)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self
+            .registers
+            .iter()
+            .filter(|register| **register == 0)
+            .count();
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate for small cardinalities.
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as i64
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.registers.clone()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != NUM_REGISTERS {
+            return Err(ErrorCode::InternalError(format!(
+                "HyperLogLog sketch should have {} registers, got {}",
+                NUM_REGISTERS,
+                bytes.len()
+            ))
+            .into());
+        }
+        Ok(Self {
+            registers: bytes.to_vec(),
+        })
+    }
+}
+
+pub struct ApproxCountDistinct {
+    return_type: DataType,
+    input_col_idx: usize,
+    sketch: HyperLogLog,
+}
+
+impl ApproxCountDistinct {
+    pub fn new(return_type: DataType, input_col_idx: usize) -> Self {
+        Self {
+            return_type,
+            input_col_idx,
+            sketch: HyperLogLog::new(),
+        }
+    }
+}
+
+impl Aggregator for ApproxCountDistinct {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        self.sketch
+            .add(input.column_at(self.input_col_idx).array_ref(), row_id)
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        for row_id in 0..array.len() {
+            self.sketch.add(array, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        match builder {
+            ArrayBuilderImpl::Int64(b) => b.append(Some(self.sketch.estimate())),
+            _ => Err(ErrorCode::InternalError(
+                "Unexpected builder for approx_count_distinct.".into(),
+            )
+            .into()),
+        }
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        let mut groups_iter = groups.get_starting_indices().iter().peekable();
+        for row_id in 0..array.len() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                self.output(builder)?;
+                self.sketch = HyperLogLog::new();
+            }
+            self.sketch.add(array, row_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use itertools::Itertools;
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    fn sketch_of(values: impl Iterator<Item = i64>) -> HyperLogLog {
+        let values = values.map(Some).collect_vec();
+        let array: ArrayImpl = I64Array::from_slice(&values).unwrap().into();
+        let mut sketch = HyperLogLog::new();
+        for idx in 0..array.len() {
+            sketch.add(&array, idx).unwrap();
+        }
+        sketch
+    }
+
+    #[test]
+    fn test_hyper_log_log() {
+        assert_eq!(HyperLogLog::new().estimate(), 0);
+        // Small cardinalities are almost exact with linear counting.
+        let estimate = sketch_of((0..10).chain(0..10)).estimate();
+        assert!((9..=11).contains(&estimate), "estimate {} for 10", estimate);
+
+        for n in [1000, 100000] {
+            let estimate = sketch_of(0..n).estimate();
+            let error = (estimate - n).abs() as f64 / n as f64;
+            assert!(
+                error < 0.05,
+                "estimate {} for {} distinct values",
+                estimate,
+                n
+            );
+        }
+
+        let mut merged = sketch_of(0..5000);
+        merged.merge(&sketch_of(2500..10000));
+        assert_eq!(merged, sketch_of(0..10000));
+
+        let restored = HyperLogLog::from_bytes(&merged.to_bytes()).unwrap();
+        assert_eq!(restored, merged);
+        assert!(HyperLogLog::from_bytes(&[0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_approx_count_distinct() -> Result<()> {
+        let input = Utf8Array::from_slice(&[Some("a"), None, Some("b"), Some("a"), Some("c")])?;
+        let chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(input.into()))])
+            .build();
+        let mut agg = ApproxCountDistinct::new(DataType::Int64, 0);
+        agg.update(&chunk)?;
+        let mut builder = ArrayBuilderImpl::Int64(I64ArrayBuilder::new(0)?);
+        agg.output(&mut builder)?;
+        let output = builder.finish()?;
+        assert_eq!(output.as_int64().iter().collect_vec(), vec![Some(3)]);
+        Ok(())
+    }
+}
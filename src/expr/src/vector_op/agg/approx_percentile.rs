@@ -0,0 +1,338 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::{Buf, BufMut};
+use itertools::Itertools;
+use risingwave_common::array::*;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::*;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// Capacity of the top compactor. The rank error of a quantile is about `1.7 / k`.
+const DEFAULT_K: usize = 200;
+
+/// A KLL sketch estimating the quantiles of numeric values within a bounded memory.
+///
+/// Each compactor keeps sorted runs of values. Once a compactor is full, every other value of it
+/// is promoted to the next compactor, where it counts twice as much. Values are picked
+/// deterministically, so replaying the same input always produces the same sketch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KllSketch {
+    k: usize,
+    /// Number of values added.
+    count: u64,
+    /// Values in the compactor of level `h` weigh `2^h` each.
+    compactors: Vec<Compactor>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Compactor {
+    values: Vec<OrderedF64>,
+    /// Whether the next compaction promotes the values at odd positions. It alternates between
+    /// compactions to avoid biasing the quantiles towards either end.
+    promote_odd: bool,
+}
+
+impl Default for KllSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KllSketch {
+    pub fn new() -> Self {
+        Self::with_k(DEFAULT_K)
+    }
+
+    pub fn with_k(k: usize) -> Self {
+        Self {
+            k,
+            count: 0,
+            compactors: vec![Compactor::default()],
+        }
+    }
+
+    /// Number of values added to the sketch.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds the numeric value at `idx` of `array` to the sketch. Nulls are ignored.
+    pub fn add(&mut self, array: &ArrayImpl, idx: usize) -> Result<()> {
+        let value = match array {
+            ArrayImpl::Int16(array) => array.value_at(idx).map(|v| v as f64),
+            ArrayImpl::Int32(array) => array.value_at(idx).map(|v| v as f64),
+            ArrayImpl::Int64(array) => array.value_at(idx).map(|v| v as f64),
+            ArrayImpl::Float32(array) => array.value_at(idx).map(|v| v.0 as f64),
+            ArrayImpl::Float64(array) => array.value_at(idx).map(|v| v.0),
+            ArrayImpl::Decimal(array) => array
+                .value_at(idx)
+                .map(|v| {
+                    f64::try_from(v).map_err(|_| {
+                        ErrorCode::InternalError(format!("Failed to convert {} to f64", v))
+                    })
+                })
+                .transpose()?,
+            _ => {
+                return Err(ErrorCode::InternalError(format!(
+                    "approx_percentile does not support input of {}",
+                    array.get_ident()
+                ))
+                .into())
+            }
+        };
+        if let Some(value) = value {
+            self.add_value(value);
+        }
+        Ok(())
+    }
+
+    pub fn add_value(&mut self, value: f64) {
+        self.compactors[0].values.push(value.into());
+        self.count += 1;
+        self.compress();
+    }
+
+    /// Capacity of the compactor of `level`, which shrinks geometrically from the top one.
+    fn capacity(&self, level: usize) -> usize {
+        let depth = (self.compactors.len() - level - 1) as i32;
+        ((self.k as f64 * (2.0f64 / 3.0).powi(depth)).ceil() as usize).max(2)
+    }
+
+    fn compress(&mut self) {
+        let mut level = 0;
+        while level < self.compactors.len() {
+            if self.compactors[level].values.len() >= self.capacity(level) {
+                if level + 1 == self.compactors.len() {
+                    self.compactors.push(Compactor::default());
+                }
+                let compactor = &mut self.compactors[level];
+                let values = &mut compactor.values;
+                values.sort_unstable();
+                // An odd value stays in the compactor, as it cannot be paired.
+                let leftover = if values.len() % 2 == 1 {
+                    values.pop()
+                } else {
+                    None
+                };
+                let offset = compactor.promote_odd as usize;
+                let promoted = values.iter().skip(offset).step_by(2).copied().collect_vec();
+                values.clear();
+                values.extend(leftover);
+                compactor.promote_odd = !compactor.promote_odd;
+                self.compactors[level + 1].values.extend(promoted);
+            }
+            level += 1;
+        }
+    }
+
+    /// Returns the smallest value whose rank is no less than `fraction` of all values, or `None` if
+    /// the sketch is empty.
+    pub fn quantile(&self, fraction: f64) -> Option<f64> {
+        let mut items = self
+            .compactors
+            .iter()
+            .enumerate()
+            .flat_map(|(level, compactor)| {
+                compactor.values.iter().map(move |v| (*v, 1u64 << level))
+            })
+            .collect_vec();
+        items.sort_unstable_by_key(|(value, _)| *value);
+        let total: u64 = items.iter().map(|(_, weight)| weight).sum();
+        let target = fraction * total as f64;
+        let mut rank = 0;
+        for (value, weight) in &items {
+            rank += weight;
+            if rank as f64 >= target {
+                return Some(value.0);
+            }
+        }
+        items.last().map(|(value, _)| value.0)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.put_u32_le(self.k as u32);
+        buf.put_u64_le(self.count);
+        buf.put_u32_le(self.compactors.len() as u32);
+        for compactor in &self.compactors {
+            buf.put_u8(compactor.promote_odd as u8);
+            buf.put_u32_le(compactor.values.len() as u32);
+            for value in &compactor.values {
+                buf.put_f64_le(value.0);
+            }
+        }
+        buf
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self> {
+        fn check_remaining(bytes: &[u8], len: usize) -> Result<()> {
+            if bytes.remaining() < len {
+                return Err(ErrorCode::InternalError("Truncated KLL sketch".into()).into());
+            }
+            Ok(())
+        }
+
+        check_remaining(bytes, 16)?;
+        let k = bytes.get_u32_le() as usize;
+        let count = bytes.get_u64_le();
+        let levels = bytes.get_u32_le() as usize;
+        let mut compactors = Vec::with_capacity(levels);
+        for _ in 0..levels {
+            check_remaining(bytes, 5)?;
+            let promote_odd = bytes.get_u8() != 0;
+            let len = bytes.get_u32_le() as usize;
+            check_remaining(bytes, len * 8)?;
+            compactors.push(Compactor {
+                values: (0..len).map(|_| bytes.get_f64_le().into()).collect_vec(),
+                promote_odd,
+            });
+        }
+        if compactors.is_empty() || bytes.has_remaining() {
+            return Err(ErrorCode::InternalError("Malformed KLL sketch".into()).into());
+        }
+        Ok(Self {
+            k,
+            count,
+            compactors,
+        })
+    }
+}
+
+pub struct ApproxPercentile {
+    return_type: DataType,
+    input_col_idx: usize,
+    percentile: f64,
+    sketch: KllSketch,
+}
+
+impl ApproxPercentile {
+    pub fn new(return_type: DataType, input_col_idx: usize, percentile: f64) -> Self {
+        Self {
+            return_type,
+            input_col_idx,
+            percentile,
+            sketch: KllSketch::new(),
+        }
+    }
+}
+
+impl Aggregator for ApproxPercentile {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        self.sketch
+            .add(input.column_at(self.input_col_idx).array_ref(), row_id)
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        for row_id in 0..array.len() {
+            self.sketch.add(array, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        match builder {
+            ArrayBuilderImpl::Float64(b) => {
+                b.append(self.sketch.quantile(self.percentile).map(Into::into))
+            }
+            _ => Err(
+                ErrorCode::InternalError("Unexpected builder for approx_percentile.".into()).into(),
+            ),
+        }
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        let mut groups_iter = groups.get_starting_indices().iter().peekable();
+        for row_id in 0..array.len() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                self.output(builder)?;
+                self.sketch = KllSketch::new();
+            }
+            self.sketch.add(array, row_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    #[test]
+    fn test_kll_sketch() {
+        let mut sketch = KllSketch::new();
+        assert_eq!(sketch.quantile(0.5), None);
+
+        // Values are kept exactly before the first compaction.
+        for v in 1..=100 {
+            sketch.add_value(v as f64);
+        }
+        assert_eq!(sketch.quantile(0.0), Some(1.0));
+        assert_eq!(sketch.quantile(0.5), Some(50.0));
+        assert_eq!(sketch.quantile(1.0), Some(100.0));
+
+        let n = 100000;
+        let mut sketch = KllSketch::new();
+        for v in 0..n {
+            // Add the values out of order.
+            sketch.add_value(((v * 7919) % n) as f64);
+        }
+        assert_eq!(sketch.count(), n as u64);
+        for fraction in [0.1, 0.5, 0.9, 0.99] {
+            let estimate = sketch.quantile(fraction).unwrap();
+            let error = (estimate / n as f64 - fraction).abs();
+            assert!(error < 0.02, "estimate {} for {}", estimate, fraction);
+        }
+
+        let restored = KllSketch::from_bytes(&sketch.to_bytes()).unwrap();
+        assert_eq!(restored, sketch);
+        assert!(KllSketch::from_bytes(&sketch.to_bytes()[..20]).is_err());
+    }
+
+    #[test]
+    fn test_approx_percentile() -> Result<()> {
+        let input = I32Array::from_slice(&[Some(4), None, Some(1), Some(3), Some(2)])?;
+        let chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(input.into()))])
+            .build();
+        let mut agg = ApproxPercentile::new(DataType::Float64, 0, 0.5);
+        agg.update(&chunk)?;
+        let mut builder = ArrayBuilderImpl::Float64(F64ArrayBuilder::new(0)?);
+        agg.output(&mut builder)?;
+        let output = builder.finish()?;
+        assert_eq!(
+            output.as_float64().iter().collect_vec(),
+            vec![Some(2.0.into())]
+        );
+        Ok(())
+    }
+}
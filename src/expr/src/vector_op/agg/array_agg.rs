@@ -0,0 +1,169 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use risingwave_common::array::*;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::*;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// Collects the input values, including nulls, into a list. The output is null if there is no
+/// input row.
+pub struct ArrayAgg {
+    return_type: DataType,
+    input_col_idx: usize,
+    distinct: bool,
+    values: Vec<Datum>,
+    /// Values seen so far, only maintained for `array_agg(DISTINCT ...)`.
+    seen: HashSet<Datum>,
+    /// Whether any row has been aggregated, as nulls are collected as well.
+    has_rows: bool,
+}
+
+impl ArrayAgg {
+    pub fn new(return_type: DataType, input_col_idx: usize, distinct: bool) -> Self {
+        Self {
+            return_type,
+            input_col_idx,
+            distinct,
+            values: vec![],
+            seen: HashSet::new(),
+            has_rows: false,
+        }
+    }
+
+    fn add(&mut self, array: &ArrayImpl, row_id: usize) {
+        self.has_rows = true;
+        let datum = array.datum_at(row_id);
+        if self.distinct && !self.seen.insert(datum.clone()) {
+            return;
+        }
+        self.values.push(datum);
+    }
+
+    fn reset(&mut self) {
+        self.values.clear();
+        self.seen.clear();
+        self.has_rows = false;
+    }
+}
+
+impl Aggregator for ArrayAgg {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        self.add(input.column_at(self.input_col_idx).array_ref(), row_id);
+        Ok(())
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        for row_id in 0..array.len() {
+            self.add(array, row_id);
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        match builder {
+            ArrayBuilderImpl::List(b) => {
+                let list = ListValue::new(self.values.clone());
+                b.append(self.has_rows.then(|| list.as_scalar_ref()))
+            }
+            _ => Err(ErrorCode::InternalError("Unexpected builder for array_agg.".into()).into()),
+        }
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        let mut groups_iter = groups.get_starting_indices().iter().peekable();
+        for row_id in 0..array.len() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                self.output(builder)?;
+                self.reset();
+            }
+            self.add(array, row_id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use itertools::Itertools;
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    fn list_type() -> DataType {
+        DataType::List {
+            datatype: Box::new(DataType::Int32),
+        }
+    }
+
+    fn eval(agg: &mut ArrayAgg, input: &[Option<i32>]) -> Result<Vec<Option<ListValue>>> {
+        let input = I32Array::from_slice(input)?;
+        let chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(input.into()))])
+            .build();
+        agg.update(&chunk)?;
+        let mut builder = list_type().create_array_builder(0)?;
+        agg.output(&mut builder)?;
+        let output = builder.finish()?;
+        Ok(output
+            .as_list()
+            .iter()
+            .map(|v| v.map(|v| v.to_owned_scalar()))
+            .collect_vec())
+    }
+
+    #[test]
+    fn test_array_agg() -> Result<()> {
+        let mut agg = ArrayAgg::new(list_type(), 0, false);
+        assert_eq!(eval(&mut agg, &[])?, vec![None]);
+        assert_eq!(
+            eval(&mut agg, &[Some(2), None, Some(1), Some(2)])?,
+            vec![Some(ListValue::new(vec![
+                Some(2.into()),
+                None,
+                Some(1.into()),
+                Some(2.into())
+            ]))]
+        );
+
+        let mut agg = ArrayAgg::new(list_type(), 0, true);
+        assert_eq!(
+            eval(&mut agg, &[Some(2), None, Some(1), Some(2), None])?,
+            vec![Some(ListValue::new(vec![
+                Some(2.into()),
+                None,
+                Some(1.into())
+            ]))]
+        );
+        Ok(())
+    }
+}
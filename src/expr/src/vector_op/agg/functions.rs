@@ -52,7 +52,7 @@ where
 }
 
 use std::convert::From;
-use std::ops::Add;
+use std::ops::{Add, BitAnd, BitOr};
 
 use risingwave_common::types::ScalarRef;
 
@@ -100,6 +100,38 @@ pub fn max_str<'a>(r: Option<&'a str>, i: Option<&'a str>) -> Result<Option<&'a
     max(r, i)
 }
 
+pub fn bool_and(result: Option<bool>, input: Option<bool>) -> Result<Option<bool>> {
+    bit_and(result, input)
+}
+
+pub fn bool_or(result: Option<bool>, input: Option<bool>) -> Result<Option<bool>> {
+    bit_or(result, input)
+}
+
+pub fn bit_and<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitAnd<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r & i),
+    };
+    Ok(res)
+}
+
+pub fn bit_or<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitOr<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r | i),
+    };
+    Ok(res)
+}
+
 /// create table t(v1 int);
 /// insert into t values (null);
 /// select count(*) from t; gives 1.
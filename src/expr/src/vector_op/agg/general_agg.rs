@@ -176,7 +176,7 @@ impl_aggregator! { F32Array, Float32, F32Array, Float32 }
 impl_aggregator! { F64Array, Float64, F64Array, Float64 }
 impl_aggregator! { DecimalArray, Decimal, DecimalArray, Decimal }
 impl_aggregator! { Utf8Array, Utf8, Utf8Array, Utf8 }
-impl_aggregator! { BoolArray, Bool, BoolArray, Bool } // TODO(#359): remove once unnecessary
+impl_aggregator! { BoolArray, Bool, BoolArray, Bool }
 impl_aggregator! { I16Array, Int16, I64Array, Int64 }
 impl_aggregator! { I32Array, Int32, I64Array, Int64 }
 impl_aggregator! { F32Array, Float32, I64Array, Int64 }
@@ -377,4 +377,52 @@ mod tests {
         let expected = &[Some(0)];
         test_case(input.into(), expected)
     }
+
+    #[test]
+    fn vec_bool_and_or() -> Result<()> {
+        let test_case =
+            |agg_type: AggKind, input: &[Option<bool>], expected: Option<bool>| -> Result<()> {
+                let input = BoolArray::from_slice(input).unwrap();
+                let actual = eval_agg(
+                    DataType::Boolean,
+                    Arc::new(input.into()),
+                    &agg_type,
+                    DataType::Boolean,
+                    ArrayBuilderImpl::Bool(BoolArrayBuilder::new(0)?),
+                )?;
+                assert_eq!(actual.as_bool().iter().collect::<Vec<_>>(), &[expected]);
+                Ok(())
+            };
+        test_case(
+            AggKind::BoolAnd,
+            &[Some(true), None, Some(true)],
+            Some(true),
+        )?;
+        test_case(AggKind::BoolAnd, &[Some(true), Some(false)], Some(false))?;
+        test_case(
+            AggKind::BoolOr,
+            &[Some(false), None, Some(true)],
+            Some(true),
+        )?;
+        test_case(AggKind::BoolOr, &[Some(false)], Some(false))?;
+        test_case(AggKind::BoolOr, &[None], None)
+    }
+
+    #[test]
+    fn vec_bit_and_or_int32() -> Result<()> {
+        let test_case = |agg_type: AggKind, expected: Option<i32>| -> Result<()> {
+            let input = I32Array::from_slice(&[Some(0b1100), None, Some(0b1010)]).unwrap();
+            let actual = eval_agg(
+                DataType::Int32,
+                Arc::new(input.into()),
+                &agg_type,
+                DataType::Int32,
+                ArrayBuilderImpl::Int32(I32ArrayBuilder::new(0)?),
+            )?;
+            assert_eq!(actual.as_int32().iter().collect::<Vec<_>>(), &[expected]);
+            Ok(())
+        };
+        test_case(AggKind::BitAnd, Some(0b1000))?;
+        test_case(AggKind::BitOr, Some(0b1110))
+    }
 }
@@ -337,6 +337,7 @@ mod tests {
                 ..Default::default()
             }),
             distinct: false,
+            ..Default::default()
         };
         let mut a = AggStateFactory::new(&prost)
             .unwrap()
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 mod aggregator;
+mod approx_count_distinct;
+mod approx_percentile;
+mod array_agg;
 mod count_star;
 mod functions;
 mod general_agg;
@@ -20,4 +23,6 @@ mod general_distinct_agg;
 mod general_sorted_grouper;
 
 pub use aggregator::{AggStateFactory, BoxedAggState};
+pub use approx_count_distinct::HyperLogLog;
+pub use approx_percentile::KllSketch;
 pub use general_sorted_grouper::{create_sorted_grouper, BoxedSortedGrouper, EqGroups};
@@ -19,9 +19,13 @@ use std::fmt::Debug;
 
 use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
 use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub};
-use risingwave_common::error::ErrorCode::{InternalError, NumericValueOutOfRange};
+use risingwave_common::error::ErrorCode::{
+    InternalError, InvalidInputSyntax, NumericValueOutOfRange,
+};
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::{IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper};
+use risingwave_common::types::{
+    IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, OrderedF64,
+};
 
 use super::cast::date_to_timestamp;
 
@@ -144,8 +148,11 @@ pub fn timestamp_timestamp_sub<T1, T2, T3>(
     l: NaiveDateTimeWrapper,
     r: NaiveDateTimeWrapper,
 ) -> Result<IntervalUnit> {
-    let tmp = l.0 - r.0;
-    Ok(IntervalUnit::new(0, tmp.num_days() as i32, 0))
+    // As in postgres, whole days are kept in days and the rest in time.
+    let diff = l.0 - r.0;
+    let days = diff.num_days();
+    let ms = (diff - Duration::days(days)).num_milliseconds();
+    Ok(IntervalUnit::new(0, days as i32, ms))
 }
 
 #[inline(always)]
@@ -256,13 +263,113 @@ where
     interval_int_mul::<T2, T1, T3>(r, l)
 }
 
+#[inline(always)]
+pub fn interval_int_div<T1, T2, T3>(l: IntervalUnit, r: T2) -> Result<IntervalUnit>
+where
+    T2: TryInto<i64> + Debug,
+{
+    let r: i64 = r
+        .try_into()
+        .map_err(|_| RwError::from(NumericValueOutOfRange))?;
+    if r == 0 {
+        return Err(NumericValueOutOfRange.into());
+    }
+    // As in postgres, the remainder of months is carried to days as 30 days a month, and that of
+    // days to time as 24 hours a day.
+    let months = l.get_months() as i64;
+    let days = l.get_days() as i64 + months % r * 30;
+    let ms = l.get_ms() + days % r * 24 * 60 * 60 * 1000;
+    Ok(IntervalUnit::new(
+        (months / r) as i32,
+        (days / r) as i32,
+        ms / r,
+    ))
+}
+
+#[inline(always)]
+pub fn date_int_add<T1, T2, T3>(l: NaiveDateWrapper, r: i32) -> Result<NaiveDateWrapper> {
+    l.0.checked_add_signed(Duration::days(r as i64))
+        .map(NaiveDateWrapper::new)
+        .ok_or_else(|| InternalError("Date out of range".to_string()).into())
+}
+
+#[inline(always)]
+pub fn int_date_add<T1, T2, T3>(l: i32, r: NaiveDateWrapper) -> Result<NaiveDateWrapper> {
+    date_int_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn date_int_sub<T1, T2, T3>(l: NaiveDateWrapper, r: i32) -> Result<NaiveDateWrapper> {
+    date_int_add::<T1, T2, T3>(l, r.checked_neg().ok_or(NumericValueOutOfRange)?)
+}
+
+#[inline(always)]
+pub fn date_time_add<T1, T2, T3>(
+    l: NaiveDateWrapper,
+    r: NaiveTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    Ok(NaiveDateTimeWrapper::new(NaiveDateTime::new(l.0, r.0)))
+}
+
+#[inline(always)]
+pub fn time_date_add<T1, T2, T3>(
+    l: NaiveTimeWrapper,
+    r: NaiveDateWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    date_time_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn time_time_sub<T1, T2, T3>(l: NaiveTimeWrapper, r: NaiveTimeWrapper) -> Result<IntervalUnit> {
+    Ok(IntervalUnit::from_millis((l.0 - r.0).num_milliseconds()))
+}
+
+/// Adds the time part of an interval to a time of day, wrapping around midnight. Months and days
+/// are ignored as in postgres.
+#[inline(always)]
+pub fn time_interval_add<T1, T2, T3>(
+    l: NaiveTimeWrapper,
+    r: IntervalUnit,
+) -> Result<NaiveTimeWrapper> {
+    let (time, _) =
+        l.0.overflowing_add_signed(Duration::milliseconds(r.get_ms()));
+    Ok(NaiveTimeWrapper::new(time))
+}
+
+#[inline(always)]
+pub fn interval_time_add<T1, T2, T3>(
+    l: IntervalUnit,
+    r: NaiveTimeWrapper,
+) -> Result<NaiveTimeWrapper> {
+    time_interval_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn time_interval_sub<T1, T2, T3>(
+    l: NaiveTimeWrapper,
+    r: IntervalUnit,
+) -> Result<NaiveTimeWrapper> {
+    time_interval_add::<T1, T2, T3>(l, r.negative())
+}
+
+#[inline(always)]
+pub fn sqrt_f64(expr: OrderedF64) -> Result<OrderedF64> {
+    if expr.0 < 0.0 {
+        return Err(RwError::from(InvalidInputSyntax(
+            "cannot take square root of a negative number".to_string(),
+        )));
+    }
+    Ok(expr.0.sqrt().into())
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use risingwave_common::types::Decimal;
+    use chrono::{NaiveDate, NaiveTime};
+    use risingwave_common::types::{Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveTimeWrapper};
 
-    use crate::vector_op::arithmetic_op::general_add;
+    use crate::vector_op::arithmetic_op::*;
 
     #[test]
     fn test() {
@@ -271,4 +378,43 @@ mod tests {
             Decimal::from_str("2").unwrap()
         );
     }
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(sqrt_f64(4.0.into()).unwrap(), 2.0.into());
+        assert_eq!(sqrt_f64(0.0.into()).unwrap(), 0.0.into());
+        assert!(sqrt_f64((-1.0).into()).is_err());
+    }
+
+    #[test]
+    fn test_interval_arithmetic() {
+        let day = 24 * 60 * 60 * 1000;
+        assert_eq!(
+            interval_int_div::<IntervalUnit, i32, IntervalUnit>(IntervalUnit::new(1, 1, 0), 2)
+                .unwrap(),
+            IntervalUnit::new(0, 15, day / 2)
+        );
+        assert!(
+            interval_int_div::<IntervalUnit, i32, IntervalUnit>(IntervalUnit::from_days(1), 0)
+                .is_err()
+        );
+
+        let l = NaiveDateTimeWrapper::new(NaiveDate::from_ymd(2022, 6, 10).and_hms(14, 0, 0));
+        let r = NaiveDateTimeWrapper::new(NaiveDate::from_ymd(2022, 6, 9).and_hms(12, 0, 0));
+        assert_eq!(
+            timestamp_timestamp_sub::<(), (), ()>(l, r).unwrap(),
+            IntervalUnit::new(0, 1, 2 * 60 * 60 * 1000)
+        );
+
+        let time = NaiveTimeWrapper::new(NaiveTime::from_hms(23, 0, 0));
+        assert_eq!(
+            time_interval_add::<(), (), ()>(time, IntervalUnit::new(0, 1, 2 * 60 * 60 * 1000))
+                .unwrap(),
+            NaiveTimeWrapper::new(NaiveTime::from_hms(1, 0, 0))
+        );
+        assert_eq!(
+            time_time_sub::<(), (), ()>(time, NaiveTimeWrapper::new(NaiveTime::from_hms(1, 0, 0)))
+                .unwrap(),
+            IntervalUnit::from_millis(22 * 60 * 60 * 1000)
+        );
+    }
 }
@@ -18,6 +18,7 @@ use std::str::FromStr;
 
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
 use num_traits::ToPrimitive;
+use risingwave_common::array::{JsonbRef, JsonbVal};
 use risingwave_common::error::ErrorCode::{InternalError, InvalidInputSyntax, ParseError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{
@@ -105,6 +106,16 @@ pub fn date_to_timestamp(elem: NaiveDateWrapper) -> Result<NaiveDateTimeWrapper>
     Ok(NaiveDateTimeWrapper::new(elem.0.and_hms(0, 0, 0)))
 }
 
+#[inline(always)]
+pub fn timestamp_to_date(elem: NaiveDateTimeWrapper) -> Result<NaiveDateWrapper> {
+    Ok(NaiveDateWrapper::new(elem.0.date()))
+}
+
+#[inline(always)]
+pub fn timestamp_to_time(elem: NaiveDateTimeWrapper) -> Result<NaiveTimeWrapper> {
+    Ok(NaiveTimeWrapper::new(elem.0.time()))
+}
+
 /// Define the cast function to primitive types.
 ///
 /// Due to the orphan rule, some data can't implement `TryFrom` trait for basic type.
@@ -203,3 +214,15 @@ pub fn bool_to_str(input: bool) -> Result<String> {
         false => Ok("false".into()),
     }
 }
+
+#[inline(always)]
+pub fn str_to_jsonb(input: &str) -> Result<JsonbVal> {
+    input.parse().map_err(|e| {
+        InvalidInputSyntax(format!("invalid input syntax for type jsonb: {}", e)).into()
+    })
+}
+
+#[inline(always)]
+pub fn jsonb_to_str(input: JsonbRef) -> Result<String> {
+    Ok(input.to_string())
+}
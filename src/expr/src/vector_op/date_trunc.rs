@@ -0,0 +1,96 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, Timelike};
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::Result;
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+/// Truncates the timestamp to the precision given by `field`, e.g. `day` or `month`. The field
+/// is expected in lower case.
+#[inline(always)]
+pub fn date_trunc_timestamp(field: &str, ts: NaiveDateTimeWrapper) -> Result<NaiveDateTimeWrapper> {
+    let ts = ts.0;
+    let date = ts.date();
+    let year = date.year();
+    let first_day_of_year = |year: i32| NaiveDate::from_ymd(year, 1, 1).and_hms(0, 0, 0);
+    let truncated = match field {
+        "microseconds" => ts,
+        "milliseconds" => ts
+            .with_nanosecond(ts.nanosecond() / 1_000_000 * 1_000_000)
+            .unwrap(),
+        "second" => ts.with_nanosecond(0).unwrap(),
+        "minute" => date.and_hms(ts.hour(), ts.minute(), 0),
+        "hour" => date.and_hms(ts.hour(), 0, 0),
+        "day" => date.and_hms(0, 0, 0),
+        // Weeks start on Monday as in ISO 8601.
+        "week" => {
+            (date - Duration::days(date.weekday().num_days_from_monday() as i64)).and_hms(0, 0, 0)
+        }
+        "month" => NaiveDate::from_ymd(year, date.month(), 1).and_hms(0, 0, 0),
+        "quarter" => NaiveDate::from_ymd(year, (date.month() - 1) / 3 * 3 + 1, 1).and_hms(0, 0, 0),
+        "year" => first_day_of_year(year),
+        "decade" => first_day_of_year(year - year.rem_euclid(10)),
+        // The first century starts at year 1, so does the first millennium.
+        "century" => first_day_of_year(year - (year - 1).rem_euclid(100)),
+        "millennium" => first_day_of_year(year - (year - 1).rem_euclid(1000)),
+        _ => {
+            return Err(InvalidInputSyntax(format!(
+                "timestamp units \"{}\" not supported by date_trunc",
+                field
+            ))
+            .into())
+        }
+    };
+    Ok(NaiveDateTimeWrapper::new(truncated))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    #[test]
+    fn test_date_trunc() {
+        let ts = NaiveDateTimeWrapper::new(
+            NaiveDate::from_ymd(2022, 6, 9).and_hms_micro(13, 5, 9, 123456),
+        );
+        let cases = [
+            ("microseconds", "2022-06-09 13:05:09.123456"),
+            ("milliseconds", "2022-06-09 13:05:09.123"),
+            ("second", "2022-06-09 13:05:09"),
+            ("minute", "2022-06-09 13:05:00"),
+            ("hour", "2022-06-09 13:00:00"),
+            ("day", "2022-06-09 00:00:00"),
+            ("week", "2022-06-06 00:00:00"),
+            ("month", "2022-06-01 00:00:00"),
+            ("quarter", "2022-04-01 00:00:00"),
+            ("year", "2022-01-01 00:00:00"),
+            ("decade", "2020-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+            ("millennium", "2001-01-01 00:00:00"),
+        ];
+        for (field, expected) in cases {
+            let truncated = date_trunc_timestamp(field, ts).unwrap();
+            assert_eq!(
+                truncated.0,
+                NaiveDateTime::parse_from_str(expected, "%Y-%m-%d %H:%M:%S%.f").unwrap(),
+                "{}",
+                field
+            );
+        }
+        assert!(date_trunc_timestamp("fortnight", ts).is_err());
+    }
+}
@@ -17,6 +17,8 @@ use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{Decimal, NaiveDateTimeWrapper, NaiveDateWrapper};
 
+use super::cast::date_to_timestamp;
+
 fn extract_time<T>(time: T, time_unit: &str) -> Result<Decimal>
 where
     T: Timelike,
@@ -25,6 +27,14 @@ where
         "HOUR" => Ok(time.hour().into()),
         "MINUTE" => Ok(time.minute().into()),
         "SECOND" => Ok(time.second().into()),
+        "MILLISECONDS" => Ok(Decimal::from_i128_with_scale(
+            subsec_micros_with_seconds(&time),
+            3,
+        )),
+        "MICROSECONDS" => Ok(Decimal::from_i128_with_scale(
+            subsec_micros_with_seconds(&time),
+            0,
+        )),
         _ => Err(RwError::from(InternalError(format!(
             "Unsupported time unit {} in extract function",
             time_unit
@@ -32,6 +42,11 @@ where
     }
 }
 
+/// The seconds field in microseconds, including the fractional part.
+fn subsec_micros_with_seconds<T: Timelike>(time: &T) -> i128 {
+    time.second() as i128 * 1_000_000 + (time.nanosecond() / 1000) as i128
+}
+
 fn extract_date<T>(date: T, time_unit: &str) -> Result<Decimal>
 where
     T: Datelike,
@@ -43,6 +58,15 @@ where
         // Sun = 0 and Sat = 6
         "DOW" => Ok(date.weekday().num_days_from_sunday().into()),
         "DOY" => Ok(date.ordinal().into()),
+        // Mon = 1 and Sun = 7
+        "ISODOW" => Ok(date.weekday().number_from_monday().into()),
+        "WEEK" => Ok(date.iso_week().week().into()),
+        "ISOYEAR" => Ok(date.iso_week().year().into()),
+        "QUARTER" => Ok(((date.month() - 1) / 3 + 1).into()),
+        "DECADE" => Ok(date.year().div_euclid(10).into()),
+        // The first century starts at year 1, so does the first millennium.
+        "CENTURY" => Ok(((date.year() - 1).div_euclid(100) + 1).into()),
+        "MILLENNIUM" => Ok(((date.year() - 1).div_euclid(1000) + 1).into()),
         _ => Err(RwError::from(InternalError(format!(
             "Unsupported time unit {} in extract function",
             time_unit
@@ -51,11 +75,19 @@ where
 }
 
 pub fn extract_from_date(time_unit: &str, date: NaiveDateWrapper) -> Result<Decimal> {
+    if time_unit == "EPOCH" {
+        return extract_from_timestamp(time_unit, date_to_timestamp(date)?);
+    }
     extract_date(date.0, time_unit)
 }
 
 pub fn extract_from_timestamp(time_unit: &str, timestamp: NaiveDateTimeWrapper) -> Result<Decimal> {
     let time = timestamp.0;
+    if time_unit == "EPOCH" {
+        // Seconds since the unix epoch, as if the timestamp were in UTC.
+        let micros = time.timestamp() as i128 * 1_000_000 + time.timestamp_subsec_micros() as i128;
+        return Ok(Decimal::from_i128_with_scale(micros, 6).normalize());
+    }
     let mut res = extract_date(time, time_unit);
     if res.is_err() {
         res = extract_time(time, time_unit);
@@ -78,6 +110,13 @@ mod tests {
         assert_eq!(extract_from_date("YEAR", date).unwrap(), 2021.into());
         assert_eq!(extract_from_date("DOW", date).unwrap(), 1.into());
         assert_eq!(extract_from_date("DOY", date).unwrap(), 326.into());
+        assert_eq!(extract_from_date("ISODOW", date).unwrap(), 1.into());
+        assert_eq!(extract_from_date("WEEK", date).unwrap(), 47.into());
+        assert_eq!(extract_from_date("QUARTER", date).unwrap(), 4.into());
+        assert_eq!(extract_from_date("DECADE", date).unwrap(), 202.into());
+        assert_eq!(extract_from_date("CENTURY", date).unwrap(), 21.into());
+        assert_eq!(extract_from_date("MILLENNIUM", date).unwrap(), 3.into());
+        assert_eq!(extract_from_date("EPOCH", date).unwrap(), 1637539200.into());
     }
 
     #[test]
@@ -88,5 +127,13 @@ mod tests {
         assert_eq!(extract_from_timestamp("HOUR", time).unwrap(), 12.into());
         assert_eq!(extract_from_timestamp("MINUTE", time).unwrap(), 4.into());
         assert_eq!(extract_from_timestamp("SECOND", time).unwrap(), 2.into());
+        assert_eq!(
+            extract_from_timestamp("MILLISECONDS", time).unwrap(),
+            2000.into()
+        );
+        assert_eq!(
+            extract_from_timestamp("EPOCH", time).unwrap(),
+            1637582642.into()
+        );
     }
 }
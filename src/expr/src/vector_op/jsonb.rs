@@ -0,0 +1,163 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{BytesGuard, BytesWriter, JsonbRef, JsonbVal, ListRef};
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::Result;
+use risingwave_common::types::{ScalarRef, ScalarRefImpl};
+
+/// `jsonb -> text`. Returns null if the value is not an object or has no such key.
+#[inline(always)]
+pub fn jsonb_object_field(v: Option<JsonbRef>, key: Option<&str>) -> Result<Option<JsonbVal>> {
+    Ok(v.zip(key)
+        .and_then(|(v, key)| v.access_object_field(key))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb -> integer`. Returns null if the value is not an array or the index is out of bounds.
+#[inline(always)]
+pub fn jsonb_array_element(v: Option<JsonbRef>, idx: Option<i32>) -> Result<Option<JsonbVal>> {
+    Ok(v.zip(idx)
+        .and_then(|(v, idx)| v.access_array_element(idx as i64))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb ->> text`
+#[inline(always)]
+pub fn jsonb_object_field_text(v: Option<JsonbRef>, key: Option<&str>) -> Result<Option<String>> {
+    Ok(v.zip(key)
+        .and_then(|(v, key)| v.access_object_field(key))
+        .and_then(|v| v.as_text()))
+}
+
+/// `jsonb ->> integer`
+#[inline(always)]
+pub fn jsonb_array_element_text(v: Option<JsonbRef>, idx: Option<i32>) -> Result<Option<String>> {
+    Ok(v.zip(idx)
+        .and_then(|(v, idx)| v.access_array_element(idx as i64))
+        .and_then(|v| v.as_text()))
+}
+
+/// `jsonb #> text[]`. Returns null if any step of the path does not exist or is null.
+pub fn jsonb_extract_path(v: Option<JsonbRef>, path: Option<ListRef>) -> Result<Option<JsonbVal>> {
+    let (Some(v), Some(path)) = (v, path) else {
+        return Ok(None);
+    };
+    let mut steps = Vec::new();
+    for step in path.values_ref() {
+        match step {
+            Some(ScalarRefImpl::Utf8(step)) => steps.push(step),
+            None => return Ok(None),
+            Some(step) => {
+                return Err(InternalError(format!(
+                    "jsonb path must be a text array, got {:?}",
+                    step
+                ))
+                .into())
+            }
+        }
+    }
+    Ok(v.access_path(steps).map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb @> jsonb`
+#[inline(always)]
+pub fn jsonb_contains(l: JsonbRef, r: JsonbRef) -> Result<bool> {
+    Ok(l.contains(r))
+}
+
+/// `jsonb_typeof(jsonb)`
+#[inline(always)]
+pub fn jsonb_typeof(v: JsonbRef, writer: BytesWriter) -> Result<BytesGuard> {
+    writer.write_ref(v.type_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ListValue, Utf8ArrayBuilder};
+    use risingwave_common::types::{Scalar, ScalarImpl};
+
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_jsonb_access() {
+        let value = jsonb(r#"{"a": [1, "x", null], "b": {"c": "d"}}"#);
+        let value = Some(value.as_scalar_ref());
+        assert_eq!(
+            jsonb_object_field(value, Some("b")).unwrap(),
+            Some(jsonb(r#"{"c": "d"}"#))
+        );
+        assert_eq!(jsonb_object_field(value, Some("x")).unwrap(), None);
+        assert_eq!(jsonb_object_field(value, None).unwrap(), None);
+        assert_eq!(jsonb_array_element(value, Some(0)).unwrap(), None);
+
+        let array = jsonb_object_field(value, Some("a")).unwrap().unwrap();
+        let array = Some(array.as_scalar_ref());
+        assert_eq!(
+            jsonb_array_element(array, Some(0)).unwrap(),
+            Some(jsonb("1"))
+        );
+        assert_eq!(
+            jsonb_array_element_text(array, Some(1)).unwrap(),
+            Some("x".to_string())
+        );
+        assert_eq!(jsonb_array_element_text(array, Some(2)).unwrap(), None);
+        assert_eq!(
+            jsonb_object_field_text(value, Some("b")).unwrap(),
+            Some(r#"{"c": "d"}"#.to_string())
+        );
+
+        let path = |steps: &[Option<&str>]| {
+            ListValue::new(
+                steps
+                    .iter()
+                    .map(|s| s.map(|s| ScalarImpl::Utf8(s.to_string())))
+                    .collect(),
+            )
+        };
+        let p = path(&[Some("b"), Some("c")]);
+        assert_eq!(
+            jsonb_extract_path(value, Some(p.as_scalar_ref())).unwrap(),
+            Some(jsonb(r#""d""#))
+        );
+        let p = path(&[Some("a"), Some("-3")]);
+        assert_eq!(
+            jsonb_extract_path(value, Some(p.as_scalar_ref())).unwrap(),
+            Some(jsonb("1"))
+        );
+        let p = path(&[Some("a"), None]);
+        assert_eq!(
+            jsonb_extract_path(value, Some(p.as_scalar_ref())).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_jsonb_typeof() {
+        let mut builder = Utf8ArrayBuilder::new(0).unwrap();
+        for v in ["{}", "[]", r#""a""#, "1", "true", "null"] {
+            let guard = jsonb_typeof(jsonb(v).as_scalar_ref(), builder.writer()).unwrap();
+            builder = guard.into_inner();
+        }
+        let array = builder.finish().unwrap();
+        assert_eq!(
+            array.iter().flatten().collect::<Vec<_>>(),
+            ["object", "array", "string", "number", "boolean", "null"]
+        );
+    }
+}
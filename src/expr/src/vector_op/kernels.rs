@@ -0,0 +1,167 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Kernels evaluating binary operations over whole buffers of primitive arrays.
+//!
+//! Unlike the scalar functions driven by `expr::template`, a kernel never branches on nulls. It
+//! computes every slot, including the ones masked out by the null bitmap, and combines the null
+//! bitmaps of its inputs with bitwise operations. The inner loops are plain zips over slices, which
+//! the compiler auto-vectorizes.
+
+use itertools::Itertools;
+use risingwave_common::array::{Array, BoolArray, PrimitiveArray, PrimitiveArrayItemType};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::ErrorCode::NumericValueOutOfRange;
+use risingwave_common::error::Result;
+
+/// Applies `op` to each pair of values. A slot is null if it is null in either input.
+pub fn binary_primitive<T1, T2, O, F>(
+    l: &PrimitiveArray<T1>,
+    r: &PrimitiveArray<T2>,
+    op: F,
+) -> Result<PrimitiveArray<O>>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    O: PrimitiveArrayItemType,
+    F: Fn(T1, T2) -> O,
+{
+    let bitmap = (l.null_bitmap() & r.null_bitmap())?;
+    let data = l
+        .as_slice()
+        .iter()
+        .zip_eq(r.as_slice())
+        .map(|(a, b)| op(*a, *b))
+        .collect();
+    Ok(PrimitiveArray::from_parts(data, bitmap))
+}
+
+/// Like [`binary_primitive`], but `op` also reports whether the result overflowed, e.g.
+/// `i32::overflowing_add`. An overflow in a non-null slot is an error, while one in a null slot is
+/// ignored since its inputs are just placeholders. So is one in a slot not in `selection`, if
+/// given, which belongs to a row that is filtered out.
+pub fn checked_binary_primitive<T1, T2, O, F>(
+    l: &PrimitiveArray<T1>,
+    r: &PrimitiveArray<T2>,
+    selection: Option<&Bitmap>,
+    op: F,
+) -> Result<PrimitiveArray<O>>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    O: PrimitiveArrayItemType,
+    F: Fn(T1, T2) -> (O, bool),
+{
+    let bitmap = (l.null_bitmap() & r.null_bitmap())?;
+    let mut overflow = false;
+    let data = l
+        .as_slice()
+        .iter()
+        .zip_eq(r.as_slice())
+        .map(|(a, b)| {
+            let (value, overflowed) = op(*a, *b);
+            overflow |= overflowed;
+            value
+        })
+        .collect();
+
+    // Overflows are rare, so only find out whether a non-null slot overflowed when there is one.
+    if overflow {
+        let overflowed = |idx: usize| op(l.as_slice()[idx], r.as_slice()[idx]).1;
+        let overflowed = match selection {
+            Some(selection) => (&bitmap & selection)?.iter_ones().any(overflowed),
+            None => bitmap.iter_ones().any(overflowed),
+        };
+        if overflowed {
+            return Err(NumericValueOutOfRange.into());
+        }
+    }
+    Ok(PrimitiveArray::from_parts(data, bitmap))
+}
+
+/// Compares each pair of values with `op` and packs the results into a [`BoolArray`]. A slot is
+/// null if it is null in either input.
+pub fn compare_primitive<T1, T2, F>(
+    l: &PrimitiveArray<T1>,
+    r: &PrimitiveArray<T2>,
+    op: F,
+) -> Result<BoolArray>
+where
+    T1: PrimitiveArrayItemType,
+    T2: PrimitiveArrayItemType,
+    F: Fn(T1, T2) -> bool,
+{
+    let bitmap = (l.null_bitmap() & r.null_bitmap())?;
+    let bools = l
+        .as_slice()
+        .iter()
+        .zip_eq(r.as_slice())
+        .map(|(a, b)| op(*a, *b))
+        .collect_vec();
+    Ok(BoolArray::from_parts(
+        Bitmap::from_bool_slice(&bools),
+        bitmap,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{F64Array, I32Array};
+    use risingwave_common::types::OrderedF64;
+
+    use super::*;
+
+    #[test]
+    fn test_binary_primitive() {
+        let l = F64Array::from_slice(&[Some(1.0.into()), None, Some(3.0.into())]).unwrap();
+        let r = F64Array::from_slice(&[Some(2.0.into()), Some(2.0.into()), None]).unwrap();
+        let result = binary_primitive(&l, &r, |a: OrderedF64, b: OrderedF64| a * b).unwrap();
+        assert_eq!(
+            result.iter().collect_vec(),
+            vec![Some(OrderedF64::from(2.0)), None, None]
+        );
+    }
+
+    #[test]
+    fn test_checked_binary_primitive() {
+        let l = I32Array::from_slice(&[Some(1), Some(i32::MAX), None]).unwrap();
+        let r = I32Array::from_slice(&[Some(2), None, Some(i32::MAX)]).unwrap();
+        let result = checked_binary_primitive(&l, &r, None, i32::overflowing_add).unwrap();
+        assert_eq!(result.iter().collect_vec(), vec![Some(3), None, None]);
+
+        // `i32::MAX` is now added to itself in a non-null slot.
+        let r = I32Array::from_slice(&[Some(2), Some(i32::MAX), Some(1)]).unwrap();
+        assert!(checked_binary_primitive(&l, &r, None, i32::overflowing_add).is_err());
+        // The slot is not selected.
+        let selection = Bitmap::from_bool_slice(&[true, false, true]);
+        assert!(checked_binary_primitive(&l, &r, Some(&selection), i32::overflowing_add).is_ok());
+    }
+
+    #[test]
+    fn test_compare_primitive() {
+        let len = 19;
+        let l = I32Array::from_slice(&(0..len).map(Some).collect_vec()).unwrap();
+        let r = I32Array::from_slice(
+            &(0..len)
+                .map(|i| if i % 5 == 0 { None } else { Some(len - i) })
+                .collect_vec(),
+        )
+        .unwrap();
+        let result = compare_primitive(&l, &r, |a: i32, b: i32| a < b).unwrap();
+        let expected = (0..len)
+            .map(|i| if i % 5 == 0 { None } else { Some(i < len - i) })
+            .collect_vec();
+        assert_eq!(result.iter().collect_vec(), expected);
+    }
+}
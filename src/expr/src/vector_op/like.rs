@@ -55,9 +55,15 @@ pub fn like_default(s: &str, p: &str) -> Result<bool> {
     Ok(true)
 }
 
+/// Case-insensitive version of [`like_default`], for `ILIKE`.
+#[inline(always)]
+pub fn ilike_default(s: &str, p: &str) -> Result<bool> {
+    like_default(&s.to_lowercase(), &p.to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::like_default;
+    use super::{ilike_default, like_default};
 
     static CASES: &[(&str, &str, std::option::Option<bool>)] = &[
         (r#"ABCDE"#, r#"%abcde%"#, Some(false)),
@@ -94,4 +100,23 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_ilike() {
+        let cases = [
+            ("ABCDE", "%abcde%", true),
+            ("Like, Expression", "like, %", true),
+            ("like", "L_K%", false),
+            ("LIKE", "l_k%", true),
+        ];
+        for (target, pattern, expected) in cases {
+            assert_eq!(
+                ilike_default(target, pattern).unwrap(),
+                expected,
+                "target={}, pattern={}",
+                target,
+                pattern,
+            );
+        }
+    }
 }
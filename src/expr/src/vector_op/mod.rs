@@ -17,17 +17,29 @@ pub mod arithmetic_op;
 pub mod ascii;
 pub mod cast;
 pub mod cmp;
+pub mod concat_op;
 pub mod conjunction;
+pub mod date_trunc;
 pub mod extract;
+pub mod jsonb;
+pub mod kernels;
 pub mod length;
 pub mod like;
 pub mod lower;
 pub mod ltrim;
+pub mod md5;
+pub mod pad;
 pub mod position;
+pub mod repeat;
 pub mod replace;
+pub mod reverse;
 pub mod round;
 pub mod rtrim;
+pub mod split_part;
+pub mod starts_with;
 pub mod substr;
+pub mod timestamptz;
+pub mod to_char;
 pub mod translate;
 pub mod trim;
 pub mod tumble;
@@ -0,0 +1,114 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{BytesGuard, BytesWriter};
+use risingwave_common::error::Result;
+
+use super::repeat::check_repeated_len;
+
+/// Pads the string on the left to `len` characters with spaces.
+#[inline(always)]
+pub fn lpad(s: &str, len: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    lpad_fill(s, len, " ", writer)
+}
+
+/// Pads the string on the left to `len` characters with `fill`, which is repeated as needed. The
+/// string is truncated to `len` characters if it is longer. Returns an error if the result would be
+/// too long.
+#[inline(always)]
+pub fn lpad_fill(s: &str, len: i32, fill: &str, writer: BytesWriter) -> Result<BytesGuard> {
+    let len = len.max(0) as usize;
+    let s_len = s.chars().count();
+    if s_len >= len || fill.is_empty() {
+        writer.write_from_char_iter(s.chars().take(len))
+    } else {
+        check_repeated_len(s.len().saturating_add(fill_len(fill, len - s_len)))?;
+        writer.write_from_char_iter(fill.chars().cycle().take(len - s_len).chain(s.chars()))
+    }
+}
+
+/// Pads the string on the right to `len` characters with spaces.
+#[inline(always)]
+pub fn rpad(s: &str, len: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    rpad_fill(s, len, " ", writer)
+}
+
+/// Pads the string on the right to `len` characters with `fill`, which is repeated as needed. The
+/// string is truncated to `len` characters if it is longer. Returns an error if the result would
+/// be too long.
+#[inline(always)]
+pub fn rpad_fill(s: &str, len: i32, fill: &str, writer: BytesWriter) -> Result<BytesGuard> {
+    let len = len.max(0) as usize;
+    let s_len = s.chars().count();
+    if s_len >= len || fill.is_empty() {
+        writer.write_from_char_iter(s.chars().take(len))
+    } else {
+        check_repeated_len(s.len().saturating_add(fill_len(fill, len - s_len)))?;
+        writer.write_from_char_iter(s.chars().chain(fill.chars().cycle().take(len - s_len)))
+    }
+}
+
+/// Returns the length in bytes of the first `chars` characters of `fill` repeated infinitely.
+fn fill_len(fill: &str, chars: usize) -> usize {
+    let fill_chars = fill.chars().count();
+    let rest: usize = fill
+        .chars()
+        .take(chars % fill_chars)
+        .map(char::len_utf8)
+        .sum();
+    (chars / fill_chars)
+        .saturating_mul(fill.len())
+        .saturating_add(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_pad() -> Result<()> {
+        let cases = [
+            ("hi", 5, "xy", "xyxhi", "hixyx"),
+            ("hi", 4, " ", "  hi", "hi  "),
+            ("hello", 2, "xy", "he", "he"),
+            ("奇点", 3, "无限", "无奇点", "奇点无"),
+            ("hi", 5, "", "hi", "hi"),
+            ("hi", -1, "xy", "", ""),
+        ];
+
+        for (s, len, fill, expected_l, expected_r) in cases {
+            let writer = Utf8ArrayBuilder::new(1)?.writer();
+            let array = lpad_fill(s, len, fill, writer)?.into_inner().finish()?;
+            assert_eq!(array.value_at(0).unwrap(), expected_l);
+
+            let writer = Utf8ArrayBuilder::new(1)?.writer();
+            let array = rpad_fill(s, len, fill, writer)?.into_inner().finish()?;
+            assert_eq!(array.value_at(0).unwrap(), expected_r);
+        }
+
+        let writer = Utf8ArrayBuilder::new(1)?.writer();
+        let array = lpad("ab", 3, writer)?.into_inner().finish()?;
+        assert_eq!(array.value_at(0).unwrap(), " ab");
+
+        // Each `奇` takes 3 bytes, so the result would be about 6 GB.
+        let writer = Utf8ArrayBuilder::new(1)?.writer();
+        assert!(lpad_fill("a", i32::MAX, "奇", writer).is_err());
+        let writer = Utf8ArrayBuilder::new(1)?.writer();
+        assert!(rpad_fill("a", i32::MAX, "奇", writer).is_err());
+        assert_eq!(fill_len("奇点a", 5), 13);
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{BytesGuard, BytesWriter};
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::Result;
+
+/// Maximum length in bytes of a string built by repeating its input, such as `repeat` and `lpad`,
+/// which is the limit of a field in postgres.
+const MAX_REPEATED_LEN: usize = 1 << 30;
+
+/// Returns an error if a string of `len` bytes is longer than [`MAX_REPEATED_LEN`].
+pub(crate) fn check_repeated_len(len: usize) -> Result<()> {
+    if len > MAX_REPEATED_LEN {
+        return Err(InvalidInputSyntax("requested length too large".to_string()).into());
+    }
+    Ok(())
+}
+
+/// Repeats the string `count` times. Returns an empty string if `count` is not positive, and an
+/// error if the result would be longer than [`MAX_REPEATED_LEN`].
+#[inline(always)]
+pub fn repeat(s: &str, count: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    check_repeated_len(s.len().saturating_mul(count.max(0) as usize))?;
+    let mut writer = writer.begin();
+    for _ in 0..count {
+        writer.write_ref(s)?;
+    }
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_repeat() -> Result<()> {
+        let cases = [
+            ("ab", 3, "ababab"),
+            ("奇点", 2, "奇点奇点"),
+            ("a", 0, ""),
+            ("a", -1, ""),
+        ];
+
+        for (s, count, expected) in cases {
+            let builder = Utf8ArrayBuilder::new(1)?;
+            let writer = builder.writer();
+            let guard = repeat(s, count, writer)?;
+            let array = guard.into_inner().finish()?;
+            let v = array.value_at(0).unwrap();
+            assert_eq!(v, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeat_too_large() -> Result<()> {
+        for (s, count) in [
+            ("ab", i32::MAX),
+            ("a", (1 << 30) + 1),
+            ("ab", (1 << 29) + 1),
+        ] {
+            let builder = Utf8ArrayBuilder::new(1)?;
+            assert!(repeat(s, count, builder.writer()).is_err());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{BytesGuard, BytesWriter};
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::Result;
+
+/// Splits the string at occurrences of `delimiter` and returns the `n`th field, counting from 1.
+/// A negative `n` counts from the end. Returns an empty string if there are not enough fields.
+#[inline(always)]
+pub fn split_part(s: &str, delimiter: &str, n: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    if n == 0 {
+        return Err(InvalidInputSyntax("field position must not be zero".to_string()).into());
+    }
+    let nth = n.unsigned_abs() as usize - 1;
+    let part = if delimiter.is_empty() {
+        // The whole string is the only field.
+        if nth == 0 {
+            s
+        } else {
+            ""
+        }
+    } else if n > 0 {
+        s.split(delimiter).nth(nth).unwrap_or("")
+    } else {
+        s.rsplit(delimiter).nth(nth).unwrap_or("")
+    };
+    writer.write_ref(part)
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_split_part() -> Result<()> {
+        let cases = [
+            ("abc~@~def~@~ghi", "~@~", 2, "def"),
+            ("abc~@~def~@~ghi", "~@~", -1, "ghi"),
+            ("abc~@~def~@~ghi", "~@~", 4, ""),
+            ("abc,def", "", 1, "abc,def"),
+            ("abc,def", "", 2, ""),
+            ("奇点,无限", ",", 2, "无限"),
+        ];
+
+        for (s, delimiter, n, expected) in cases {
+            let builder = Utf8ArrayBuilder::new(1)?;
+            let writer = builder.writer();
+            let guard = split_part(s, delimiter, n, writer)?;
+            let array = guard.into_inner().finish()?;
+            let v = array.value_at(0).unwrap();
+            assert_eq!(v, expected);
+        }
+
+        let writer = Utf8ArrayBuilder::new(1)?.writer();
+        assert!(split_part("a,b", ",", 0, writer).is_err());
+        Ok(())
+    }
+}
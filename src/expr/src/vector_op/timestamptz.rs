@@ -0,0 +1,237 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use risingwave_common::array::{BytesGuard, BytesWriter};
+use risingwave_common::error::ErrorCode::{InvalidInputSyntax, ParseError};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{NaiveDateTimeWrapper, OrderedF64};
+
+/// A `timestamptz` is stored as the microseconds since the unix epoch, and is only read in a time
+/// zone when converted from or to a local time.
+const MICROS_PER_SEC: i64 = 1_000_000;
+
+/// The earliest `timestamptz` in postgres, `4714-11-24 00:00:00+00 BC`, which is the julian day 0.
+/// The latest one in postgres, in 294276 AD, is beyond the range of `i64` microseconds.
+const MIN_TIMESTAMPTZ_MICROS: i64 = -210_866_803_200 * MICROS_PER_SEC;
+
+/// Looks up a time zone by its IANA name, e.g. `UTC` or `Asia/Shanghai`.
+pub fn lookup_time_zone(time_zone: &str) -> Result<Tz> {
+    time_zone.parse().map_err(|_| {
+        InvalidInputSyntax(format!("time zone \"{}\" not recognized", time_zone)).into()
+    })
+}
+
+/// Returns an error if `micros` is beyond the range of `chrono`, which is about 262,000 years.
+fn micros_to_utc(micros: i64) -> Result<NaiveDateTime> {
+    NaiveDateTime::from_timestamp_opt(
+        micros.div_euclid(MICROS_PER_SEC),
+        (micros.rem_euclid(MICROS_PER_SEC) * 1000) as u32,
+    )
+    .ok_or_else(|| InvalidInputSyntax(format!("timestamp out of range: {}", micros)).into())
+}
+
+fn utc_to_micros(utc: NaiveDateTime) -> i64 {
+    utc.timestamp() * MICROS_PER_SEC + utc.timestamp_subsec_micros() as i64
+}
+
+/// Reads a local time in `tz`. As in postgres, an ambiguous local time, which is repeated when
+/// clocks are turned back, is read as the earlier instant, and a local time skipped when clocks
+/// are turned forward is read with the offset before the transition.
+fn local_to_micros(tz: Tz, local: NaiveDateTime) -> i64 {
+    let utc = match tz.from_local_datetime(&local) {
+        LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => t.naive_utc(),
+        LocalResult::None => local - tz.offset_from_utc_datetime(&local).fix(),
+    };
+    utc_to_micros(utc)
+}
+
+/// `timestamp AT TIME ZONE zone`, which reads the timestamp as a local time in the zone.
+#[inline(always)]
+pub fn timestamp_at_time_zone(input: NaiveDateTimeWrapper, time_zone: &str) -> Result<i64> {
+    Ok(local_to_micros(lookup_time_zone(time_zone)?, input.0))
+}
+
+/// `timestamptz AT TIME ZONE zone`, which is the local time of the instant in the zone.
+#[inline(always)]
+pub fn timestamptz_at_time_zone(input: i64, time_zone: &str) -> Result<NaiveDateTimeWrapper> {
+    let tz = lookup_time_zone(time_zone)?;
+    Ok(NaiveDateTimeWrapper::new(
+        tz.from_utc_datetime(&micros_to_utc(input)?).naive_local(),
+    ))
+}
+
+/// Parses a `timestamptz`. An explicit offset like `+08:00` is honored, otherwise the input is read
+/// as a local time in `time_zone`.
+#[inline(always)]
+pub fn str_to_timestamptz(elem: &str, time_zone: &str) -> Result<i64> {
+    for format in ["%Y-%m-%d %H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M:%S%.f%#z"] {
+        if let Ok(t) = DateTime::parse_from_str(elem, format) {
+            return Ok(utc_to_micros(t.naive_utc()));
+        }
+    }
+    let local = match NaiveDateTime::parse_from_str(elem, "%Y-%m-%d %H:%M:%S%.f") {
+        Ok(local) => local,
+        Err(e) => NaiveDate::parse_from_str(elem, "%Y-%m-%d")
+            .map(|date| date.and_hms(0, 0, 0))
+            .map_err(|_| RwError::from(ParseError(Box::new(e))))?,
+    };
+    Ok(local_to_micros(lookup_time_zone(time_zone)?, local))
+}
+
+/// Formats a `timestamptz` in `tz` as postgres does, e.g. `2022-06-09 21:05:09.5+08`, where
+/// trailing zeros of the fraction and a zero minute of the offset are omitted.
+pub fn format_timestamptz(elem: i64, tz: Tz) -> Result<String> {
+    let local = tz.from_utc_datetime(&micros_to_utc(elem)?);
+    let mut output = local.format("%Y-%m-%d %H:%M:%S").to_string();
+    let micros = local.timestamp_subsec_micros();
+    if micros != 0 {
+        output.push_str(format!(".{:06}", micros).trim_end_matches('0'));
+    }
+    let offset = local.offset().fix().local_minus_utc();
+    let (sign, offset) = if offset < 0 {
+        ('-', -offset)
+    } else {
+        ('+', offset)
+    };
+    write!(output, "{}{:02}", sign, offset / 3600).unwrap();
+    if offset % 3600 != 0 {
+        write!(output, ":{:02}", offset / 60 % 60).unwrap();
+        if offset % 60 != 0 {
+            write!(output, ":{:02}", offset % 60).unwrap();
+        }
+    }
+    Ok(output)
+}
+
+#[inline(always)]
+pub fn timestamptz_to_str(elem: i64, time_zone: &str, writer: BytesWriter) -> Result<BytesGuard> {
+    writer.write_ref(&format_timestamptz(elem, lookup_time_zone(time_zone)?)?)
+}
+
+/// `to_timestamp(double precision)`, from the seconds since the unix epoch. Returns an error if the
+/// result is beyond the range of `timestamptz`.
+#[inline(always)]
+pub fn f64_sec_to_timestamptz(elem: OrderedF64) -> Result<i64> {
+    let micros = (elem.0 * MICROS_PER_SEC as f64).round();
+    if !micros.is_finite()
+        || micros < MIN_TIMESTAMPTZ_MICROS as f64
+        || micros >= i64::MAX as f64
+        || micros_to_utc(micros as i64).is_err()
+    {
+        return Err(InvalidInputSyntax(format!("timestamp out of range: {}", elem.0)).into());
+    }
+    Ok(micros as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_time_zone() {
+        let local = NaiveDateTimeWrapper::new(NaiveDate::from_ymd(2022, 6, 9).and_hms(21, 5, 9));
+        let instant = timestamp_at_time_zone(local, "Asia/Shanghai").unwrap();
+        assert_eq!(instant, 1654779909 * MICROS_PER_SEC);
+        assert_eq!(
+            timestamptz_at_time_zone(instant, "UTC").unwrap(),
+            NaiveDateTimeWrapper::new(NaiveDate::from_ymd(2022, 6, 9).and_hms(13, 5, 9))
+        );
+        assert!(timestamp_at_time_zone(local, "Mars/Olympus").is_err());
+
+        // 02:30 is skipped in New York on 2022-03-13, and 01:30 happens twice on 2022-11-06.
+        let skipped = NaiveDateTimeWrapper::new(NaiveDate::from_ymd(2022, 3, 13).and_hms(2, 30, 0));
+        assert_eq!(
+            format_timestamptz(
+                timestamp_at_time_zone(skipped, "America/New_York").unwrap(),
+                Tz::America__New_York
+            )
+            .unwrap(),
+            "2022-03-13 03:30:00-04"
+        );
+        let repeated =
+            NaiveDateTimeWrapper::new(NaiveDate::from_ymd(2022, 11, 6).and_hms(1, 30, 0));
+        assert_eq!(
+            format_timestamptz(
+                timestamp_at_time_zone(repeated, "America/New_York").unwrap(),
+                Tz::America__New_York
+            )
+            .unwrap(),
+            "2022-11-06 01:30:00-04"
+        );
+    }
+
+    #[test]
+    fn test_str_to_timestamptz() {
+        let expected = 1654779909 * MICROS_PER_SEC;
+        for input in [
+            "2022-06-09 21:05:09+08",
+            "2022-06-09 21:05:09+08:00",
+            "2022-06-09T13:05:09+0000",
+        ] {
+            assert_eq!(str_to_timestamptz(input, "UTC").unwrap(), expected);
+        }
+        assert_eq!(
+            str_to_timestamptz("2022-06-09 21:05:09", "Asia/Shanghai").unwrap(),
+            expected
+        );
+        assert_eq!(
+            str_to_timestamptz("2022-06-09", "UTC").unwrap(),
+            1654732800 * MICROS_PER_SEC
+        );
+        assert!(str_to_timestamptz("2022-06-09 25:00:00", "UTC").is_err());
+    }
+
+    #[test]
+    fn test_format_timestamptz() {
+        let instant = 1654779909 * MICROS_PER_SEC + 500_000;
+        assert_eq!(
+            format_timestamptz(instant, Tz::Asia__Shanghai).unwrap(),
+            "2022-06-09 21:05:09.5+08"
+        );
+        assert_eq!(
+            format_timestamptz(instant, Tz::Asia__Kolkata).unwrap(),
+            "2022-06-09 18:35:09.5+05:30"
+        );
+        assert_eq!(
+            format_timestamptz(-MICROS_PER_SEC, Tz::UTC).unwrap(),
+            "1969-12-31 23:59:59+00"
+        );
+        // Beyond the range of chrono.
+        assert!(format_timestamptz(i64::MAX, Tz::UTC).is_err());
+        assert!(timestamptz_at_time_zone(i64::MIN, "UTC").is_err());
+    }
+
+    #[test]
+    fn test_f64_sec_to_timestamptz() {
+        assert_eq!(
+            f64_sec_to_timestamptz(1654779909.5.into()).unwrap(),
+            1654779909 * MICROS_PER_SEC + 500_000
+        );
+        assert!(f64_sec_to_timestamptz(f64::INFINITY.into()).is_err());
+        assert!(f64_sec_to_timestamptz(9e12.into()).is_err());
+        assert!(f64_sec_to_timestamptz((-210_866_803_201.0).into()).is_err());
+        assert_eq!(
+            format_timestamptz(
+                f64_sec_to_timestamptz((-210_866_803_200.0).into()).unwrap(),
+                Tz::UTC
+            )
+            .unwrap(),
+            "-4713-11-24 00:00:00+00"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use risingwave_common::array::{BytesGuard, BytesWriter};
+use risingwave_common::error::ErrorCode::ParseError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+/// Template patterns of postgres and the corresponding `chrono` specifiers. Longer patterns come
+/// first so that e.g. `HH24` is not taken as `HH`.
+const PATTERNS: &[(&str, &str)] = &[
+    ("Month", "%B"),
+    ("YYYY", "%Y"),
+    ("HH24", "%H"),
+    ("HH12", "%I"),
+    ("DDD", "%j"),
+    ("Mon", "%b"),
+    ("Day", "%A"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DD", "%d"),
+    ("Dy", "%a"),
+    ("HH", "%I"),
+    ("MI", "%M"),
+    ("SS", "%S"),
+    ("MS", "%3f"),
+    ("US", "%6f"),
+    ("AM", "%p"),
+    ("PM", "%p"),
+    ("am", "%P"),
+    ("pm", "%P"),
+];
+
+/// Translates a postgres template like `YYYY-MM-DD HH24:MI:SS` to a `chrono` format string. Text
+/// in double quotes and characters that are not patterns are copied literally.
+fn compile_template(template: &str) -> String {
+    let mut format = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            let (literal, next) = match rest[1..].find('"') {
+                Some(i) => (&rest[1..i + 1], i + 2),
+                None => (&rest[1..], rest.len()),
+            };
+            format.push_str(&literal.replace('%', "%%"));
+            rest = &rest[next..];
+        } else if let Some((pattern, specifier)) = PATTERNS
+            .iter()
+            .find(|(pattern, _)| rest.starts_with(pattern))
+        {
+            format.push_str(specifier);
+            rest = &rest[pattern.len()..];
+        } else {
+            if c == '%' {
+                format.push('%');
+            }
+            format.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    format
+}
+
+/// Formats the timestamp with a postgres template. Only the common patterns in [`PATTERNS`] are
+/// supported, and names of months and days are not padded.
+#[inline(always)]
+pub fn to_char_timestamp(
+    ts: NaiveDateTimeWrapper,
+    template: &str,
+    writer: BytesWriter,
+) -> Result<BytesGuard> {
+    writer.write_ref(&ts.0.format(&compile_template(template)).to_string())
+}
+
+/// Parses a timestamp with a postgres template, the inverse of [`to_char_timestamp`]. The time
+/// is midnight if the template has no time fields.
+#[inline(always)]
+pub fn to_timestamp_str(elem: &str, template: &str) -> Result<NaiveDateTimeWrapper> {
+    let format = compile_template(template);
+    let ts = match NaiveDateTime::parse_from_str(elem, &format) {
+        Ok(ts) => ts,
+        Err(e) => NaiveDate::parse_from_str(elem, &format)
+            .map(|date| date.and_hms(0, 0, 0))
+            .map_err(|_| RwError::from(ParseError(Box::new(e))))?,
+    };
+    Ok(NaiveDateTimeWrapper::new(ts))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_to_char() -> Result<()> {
+        let ts = NaiveDateTimeWrapper(NaiveDate::from_ymd(2022, 4, 8).and_hms_milli(13, 5, 9, 42));
+        let cases = [
+            ("YYYY-MM-DD HH24:MI:SS", "2022-04-08 13:05:09"),
+            ("HH12:MI:SS.MS PM", "01:05:09.042 PM"),
+            ("Dy, DD Mon YY", "Fri, 08 Apr 22"),
+            ("Day Month", "Friday April"),
+            (r#"YYYY "YYYY" 100%"#, "2022 YYYY 100%"),
+        ];
+
+        for (template, expected) in cases {
+            let writer = Utf8ArrayBuilder::new(1)?.writer();
+            let guard = to_char_timestamp(ts, template, writer)?;
+            let array = guard.into_inner().finish()?;
+            assert_eq!(array.value_at(0).unwrap(), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_timestamp_str() {
+        assert_eq!(
+            to_timestamp_str("2022-04-08 13:05:09", "YYYY-MM-DD HH24:MI:SS").unwrap(),
+            NaiveDateTimeWrapper(NaiveDate::from_ymd(2022, 4, 8).and_hms(13, 5, 9))
+        );
+        assert_eq!(
+            to_timestamp_str("08/04/2022", "DD/MM/YYYY").unwrap(),
+            NaiveDateTimeWrapper(NaiveDate::from_ymd(2022, 4, 8).and_hms(0, 0, 0))
+        );
+        assert!(to_timestamp_str("2022-04-08", "DD/MM/YYYY").is_err());
+    }
+}
@@ -15,32 +15,25 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use risingwave_common::catalog::Field;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
 
 #[derive(Debug)]
 pub struct ColumnBinding {
     pub table_name: String,
-    pub column_name: String,
     pub index: usize,
-    pub data_type: DataType,
     pub is_hidden: bool,
+    /// The name and type of the column. The names of nested fields are kept for struct columns.
+    pub field: Field,
 }
 
 impl ColumnBinding {
-    pub fn new(
-        table_name: String,
-        column_name: String,
-        index: usize,
-        data_type: DataType,
-        is_hidden: bool,
-    ) -> Self {
+    pub fn new(table_name: String, index: usize, is_hidden: bool, field: Field) -> Self {
         ColumnBinding {
             table_name,
-            column_name,
             index,
-            data_type,
             is_hidden,
+            field,
         }
     }
 }
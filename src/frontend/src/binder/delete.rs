@@ -35,7 +35,7 @@ impl Binder {
         source_name: ObjectName,
         selection: Option<Expr>,
     ) -> Result<BoundDelete> {
-        let (schema_name, table_name) = Self::resolve_table_name(source_name.clone())?;
+        let (schema_name, table_name) = self.resolve_table_name(source_name.clone())?;
         let table_source = self.bind_table_source(source_name)?;
         let table = self.bind_table(&schema_name, &table_name, None)?;
         let delete = BoundDelete {
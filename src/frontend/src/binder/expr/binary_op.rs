@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{BinaryOperator, Expr};
 
 use crate::binder::Binder;
-use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall};
+use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall, SessionTimezone};
 
 impl Binder {
     pub(super) fn bind_binary_op(
@@ -25,6 +26,11 @@ impl Binder {
         op: BinaryOperator,
         right: Expr,
     ) -> Result<FunctionCall> {
+        match right {
+            Expr::AnyOp(array) => return self.bind_any_all_op(left, op, *array, true),
+            Expr::AllOp(array) => return self.bind_any_all_op(left, op, *array, false),
+            _ => {}
+        }
         let bound_left = self.bind_expr(left)?;
         let bound_right = self.bind_expr(right)?;
         let func_type = match op {
@@ -42,21 +48,156 @@ impl Binder {
             BinaryOperator::And => ExprType::And,
             BinaryOperator::Or => ExprType::Or,
             BinaryOperator::Like => ExprType::Like,
-            BinaryOperator::NotLike => return self.bind_not_like(bound_left, bound_right),
+            BinaryOperator::NotLike => {
+                return Self::bind_negated(ExprType::Like, op, bound_left, bound_right)
+            }
+            BinaryOperator::ILike => ExprType::Ilike,
+            BinaryOperator::NotILike => {
+                return Self::bind_negated(ExprType::Ilike, op, bound_left, bound_right)
+            }
+            BinaryOperator::SimilarTo => ExprType::SimilarTo,
+            BinaryOperator::NotSimilarTo => {
+                return Self::bind_negated(ExprType::SimilarTo, op, bound_left, bound_right)
+            }
+            BinaryOperator::StringConcat => return Self::bind_concat_op(bound_left, bound_right),
+            BinaryOperator::Arrow => ExprType::JsonbAccessInner,
+            BinaryOperator::LongArrow => ExprType::JsonbAccessStr,
+            BinaryOperator::HashArrow => {
+                return Self::bind_jsonb_access_path(bound_left, bound_right)
+            }
+            BinaryOperator::AtArrow => return Self::bind_jsonb_contains(bound_left, bound_right),
             _ => return Err(ErrorCode::NotImplemented(format!("{:?}", op), 112.into()).into()),
         };
+        if bound_left.return_type() == DataType::Timestampz
+            || bound_right.return_type() == DataType::Timestampz
+        {
+            return self.bind_timestamptz_op(op, func_type, bound_left, bound_right);
+        }
         FunctionCall::new_or_else(func_type, vec![bound_left, bound_right], |inputs| {
             Self::err_unsupported_binary_op(op, inputs)
         })
     }
 
-    /// Apply a NOT on top of LIKE.
-    fn bind_not_like(&mut self, left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
+    /// Binds operators on `timestamptz`. The other side of a comparison is cast to `timestamptz`
+    /// if it's a `date`, `timestamp` or string. Intervals are added in the session time zone, so
+    /// that adding `'1 day'` keeps the local time, while the difference of two `timestamptz`
+    /// doesn't depend on the time zone.
+    fn bind_timestamptz_op(
+        &self,
+        op: BinaryOperator,
+        func_type: ExprType,
+        left: ExprImpl,
+        right: ExprImpl,
+    ) -> Result<FunctionCall> {
+        let session_timezone = self.session_timezone();
+        let add_interval = |func_type: ExprType, timestamptz: ExprImpl, interval: ExprImpl| {
+            let local = FunctionCall::new_with_return_type(
+                func_type,
+                vec![session_timezone.at_time_zone(timestamptz).into(), interval],
+                DataType::Timestamp,
+            );
+            session_timezone.at_time_zone(local.into())
+        };
+        match (func_type, left.return_type(), right.return_type()) {
+            (ExprType::Add | ExprType::Subtract, DataType::Timestampz, DataType::Interval) => {
+                Ok(add_interval(func_type, left, right))
+            }
+            (ExprType::Add, DataType::Interval, DataType::Timestampz) => {
+                Ok(add_interval(func_type, right, left))
+            }
+            (ExprType::Subtract, DataType::Timestampz, DataType::Timestampz) => {
+                let utc = SessionTimezone::new("UTC".to_string());
+                Ok(FunctionCall::new_with_return_type(
+                    ExprType::Subtract,
+                    vec![
+                        utc.at_time_zone(left).into(),
+                        utc.at_time_zone(right).into(),
+                    ],
+                    DataType::Interval,
+                ))
+            }
+            (
+                ExprType::Equal
+                | ExprType::NotEqual
+                | ExprType::LessThan
+                | ExprType::LessThanOrEqual
+                | ExprType::GreaterThan
+                | ExprType::GreaterThanOrEqual,
+                _,
+                _,
+            ) => {
+                let cast = |expr: ExprImpl| match expr.return_type() {
+                    DataType::Date | DataType::Timestamp | DataType::Varchar => {
+                        expr.cast_assign(DataType::Timestampz)
+                    }
+                    _ => Ok(expr),
+                };
+                FunctionCall::new_or_else(func_type, vec![cast(left)?, cast(right)?], |inputs| {
+                    Self::err_unsupported_binary_op(op, inputs)
+                })
+            }
+            _ => FunctionCall::new_or_else(func_type, vec![left, right], |inputs| {
+                Self::err_unsupported_binary_op(op, inputs)
+            }),
+        }
+    }
+
+    /// Binds `left = ANY(array)` as whether the array contains `left`, and `left <> ALL(array)`
+    /// as the negation of it. Other comparisons with `ANY` and `ALL` are not supported yet.
+    fn bind_any_all_op(
+        &mut self,
+        left: Expr,
+        op: BinaryOperator,
+        array: Expr,
+        any: bool,
+    ) -> Result<FunctionCall> {
+        let negated = match (&op, any) {
+            (BinaryOperator::Eq, true) => false,
+            (BinaryOperator::NotEq, false) => true,
+            _ => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("{:?} {}", op, if any { "ANY" } else { "ALL" }),
+                    112.into(),
+                )
+                .into())
+            }
+        };
+        let bound_left = self.bind_expr(left)?;
+        let bound_array = self.bind_expr(array)?;
+        let element_type = match bound_array.return_type() {
+            DataType::List { datatype } => *datatype,
+            t => {
+                return Err(ErrorCode::BindError(format!(
+                    "op ANY/ALL (array) requires array on right side, got {:?}",
+                    t
+                ))
+                .into())
+            }
+        };
+        let contains = FunctionCall::new_with_return_type(
+            ExprType::ArrayContains,
+            vec![bound_array, bound_left.cast_implicit(element_type)?],
+            DataType::Boolean,
+        );
+        if negated {
+            Ok(FunctionCall::new(ExprType::Not, vec![contains.into()]).unwrap())
+        } else {
+            Ok(contains)
+        }
+    }
+
+    /// Apply a NOT on top of a pattern match, e.g. `NOT LIKE`, `NOT ILIKE` or `NOT SIMILAR TO`.
+    fn bind_negated(
+        func_type: ExprType,
+        op: BinaryOperator,
+        left: ExprImpl,
+        right: ExprImpl,
+    ) -> Result<FunctionCall> {
         Ok(FunctionCall::new(
             ExprType::Not,
             vec![
-                FunctionCall::new_or_else(ExprType::Like, vec![left, right], |inputs| {
-                    Self::err_unsupported_binary_op(BinaryOperator::NotLike, inputs)
+                FunctionCall::new_or_else(func_type, vec![left, right], |inputs| {
+                    Self::err_unsupported_binary_op(op, inputs)
                 })?
                 .into(),
             ],
@@ -64,6 +205,57 @@ impl Binder {
         .unwrap())
     }
 
+    /// Binds `left || right`. As in postgres, a non-string operand is cast to `varchar` as long as
+    /// the other one is a string, so that `'id: ' || 1` works.
+    fn bind_concat_op(left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
+        let (left, right) = match (left.return_type(), right.return_type()) {
+            (DataType::Varchar, DataType::Varchar) => (left, right),
+            (DataType::Varchar, _) => (left, right.cast_assign(DataType::Varchar)?),
+            (_, DataType::Varchar) => (left.cast_assign(DataType::Varchar)?, right),
+            _ => {
+                return Err(Self::err_unsupported_binary_op(
+                    BinaryOperator::StringConcat,
+                    &[left, right],
+                ))
+            }
+        };
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::ConcatOp,
+            vec![left, right],
+            DataType::Varchar,
+        ))
+    }
+
+    /// Binds `jsonb #> text[]`. The path must be an array of strings, e.g. `ARRAY['a', '0']`.
+    fn bind_jsonb_access_path(left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
+        let path_type = DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        };
+        if left.return_type() != DataType::Jsonb || right.return_type() != path_type {
+            return Err(Self::err_unsupported_binary_op(
+                BinaryOperator::HashArrow,
+                &[left, right],
+            ));
+        }
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::JsonbAccessPath,
+            vec![left, right],
+            DataType::Jsonb,
+        ))
+    }
+
+    /// Binds `jsonb @> jsonb`. As string literals are bound as `varchar`, a `varchar` on the right
+    /// side is cast to `jsonb` to allow `data @> '{"a": 1}'`.
+    fn bind_jsonb_contains(left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
+        let right = match (left.return_type(), right.return_type()) {
+            (DataType::Jsonb, DataType::Varchar) => right.cast_assign(DataType::Jsonb)?,
+            _ => right,
+        };
+        FunctionCall::new_or_else(ExprType::JsonbContains, vec![left, right], |inputs| {
+            Self::err_unsupported_binary_op(BinaryOperator::AtArrow, inputs)
+        })
+    }
+
     fn err_unsupported_binary_op(op: BinaryOperator, inputs: &[ExprImpl]) -> RwError {
         let bound_left = inputs.get(0).unwrap();
         let bound_right = inputs.get(1).unwrap();
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::catalog::Field;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::Ident;
 
@@ -20,6 +21,12 @@ use crate::expr::{CorrelatedInputRef, ExprImpl, InputRef};
 
 impl Binder {
     pub fn bind_column(&mut self, idents: &[Ident]) -> Result<ExprImpl> {
+        self.bind_column_with_field(idents).map(|(expr, _)| expr)
+    }
+
+    /// Binds a column and also returns its [`Field`], which keeps the names of nested fields of a
+    /// struct column.
+    pub(super) fn bind_column_with_field(&mut self, idents: &[Ident]) -> Result<(ExprImpl, Field)> {
         // TODO: check quote style of `ident`.
         let (_schema_name, table_name, column_name) = match idents {
             [column] => (None, None, &column.value),
@@ -34,7 +41,10 @@ impl Binder {
 
         if let Ok(index) = self.context.get_column_binding(table_name, column_name) {
             let column = &self.context.columns[index];
-            return Ok(InputRef::new(column.index, column.data_type.clone()).into());
+            return Ok((
+                InputRef::new(column.index, column.field.data_type.clone()).into(),
+                column.field.clone(),
+            ));
         }
 
         // Try to find a correlated column in `upper_contexts`, starting from the innermost context.
@@ -45,12 +55,15 @@ impl Binder {
             match context.get_column_binding(table_name, column_name) {
                 Ok(index) => {
                     let column = &context.columns[index];
-                    return Ok(CorrelatedInputRef::new(
-                        column.index,
-                        column.data_type.clone(),
-                        depth,
-                    )
-                    .into());
+                    return Ok((
+                        CorrelatedInputRef::new(
+                            column.index,
+                            column.field.data_type.clone(),
+                            depth,
+                        )
+                        .into(),
+                        column.field.clone(),
+                    ));
                 }
                 Err(e) => {
                     err = e;
@@ -14,13 +14,17 @@
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr};
+use risingwave_meta::manager::Epoch;
+use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, WindowSpec};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::Binder;
-use crate::expr::{AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal};
+use crate::expr::{
+    AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, WindowFunction, WindowFunctionType,
+};
+use crate::optimizer::property::Direction;
 
 impl Binder {
     pub(super) fn bind_function(&mut self, f: Function) -> Result<ExprImpl> {
@@ -34,17 +38,33 @@ impl Binder {
         if f.name.0.len() == 1 {
             let function_name = f.name.0.get(0).unwrap().value.as_str();
             let function_name = function_name.to_lowercase();
+            if let Some(window_spec) = f.over {
+                return self.bind_window_function(&function_name, inputs, window_spec);
+            }
             let agg_kind = match function_name.as_str() {
                 "count" => Some(AggKind::Count),
                 "sum" => Some(AggKind::Sum),
                 "min" => Some(AggKind::Min),
                 "max" => Some(AggKind::Max),
                 "avg" => Some(AggKind::Avg),
+                "bool_and" | "every" => Some(AggKind::BoolAnd),
+                "bool_or" => Some(AggKind::BoolOr),
+                "bit_and" => Some(AggKind::BitAnd),
+                "bit_or" => Some(AggKind::BitOr),
+                "stddev_pop" => Some(AggKind::StddevPop),
+                "stddev_samp" | "stddev" => Some(AggKind::StddevSamp),
+                "var_pop" => Some(AggKind::VarPop),
+                "var_samp" | "variance" => Some(AggKind::VarSamp),
+                "approx_count_distinct" => Some(AggKind::ApproxCountDistinct),
+                "array_agg" => Some(AggKind::ArrayAgg),
+                "approx_percentile" => Some(Self::bind_approx_percentile(&mut inputs)?),
                 _ => None,
             };
             if let Some(kind) = agg_kind {
                 self.ensure_aggregate_allowed()?;
-                return Ok(ExprImpl::AggCall(Box::new(AggCall::new(kind, inputs)?)));
+                return Ok(ExprImpl::AggCall(Box::new(AggCall::new(
+                    kind, inputs, f.distinct,
+                )?)));
             }
             let function_type = match function_name.as_str() {
                 "substr" => ExprType::Substr,
@@ -57,6 +77,26 @@ impl Binder {
                 "position" => ExprType::Position,
                 "ltrim" => ExprType::Ltrim,
                 "rtrim" => ExprType::Rtrim,
+                "split_part" => ExprType::SplitPart,
+                "lpad" => ExprType::Lpad,
+                "rpad" => ExprType::Rpad,
+                "repeat" => ExprType::Repeat,
+                "reverse" => ExprType::Reverse,
+                "starts_with" => ExprType::StartsWith,
+                "md5" => ExprType::Md5,
+                "to_char" => {
+                    inputs = Self::rewrite_to_char_args(inputs);
+                    ExprType::ToChar
+                }
+                "concat" => return Self::bind_concat(ExprType::Concat, "concat", inputs),
+                "concat_ws" => return Self::bind_concat(ExprType::ConcatWs, "concat_ws", inputs),
+                "regexp_match" => {
+                    return Self::bind_regexp(ExprType::RegexpMatch, "regexp_match", 2, inputs)
+                }
+                "regexp_replace" => {
+                    return Self::bind_regexp(ExprType::RegexpReplace, "regexp_replace", 3, inputs)
+                }
+                "jsonb_typeof" => ExprType::JsonbTypeof,
                 "case" => ExprType::Case,
                 "is true" => ExprType::IsTrue,
                 "is not true" => ExprType::IsNotTrue,
@@ -68,6 +108,16 @@ impl Binder {
                     inputs = Self::rewrite_round_args(inputs);
                     ExprType::RoundDigit
                 }
+                "array_length" => return Self::bind_array_length(inputs),
+                "now" | "current_timestamp" => return self.bind_now(&function_name, inputs),
+                "current_date" => {
+                    return self
+                        .bind_now(&function_name, inputs)?
+                        .cast_assign(DataType::Date)
+                }
+                "date_trunc" => return self.bind_date_trunc(inputs),
+                "date_part" => return self.bind_date_part(inputs),
+                "to_timestamp" => return self.bind_to_timestamp(inputs),
                 _ => {
                     return Err(ErrorCode::NotImplemented(
                         format!("unsupported function: {:?}", function_name),
@@ -101,6 +151,223 @@ impl Binder {
         .into()
     }
 
+    /// Takes the constant fraction out of the arguments of `approx_percentile(expr, fraction)`.
+    fn bind_approx_percentile(inputs: &mut Vec<ExprImpl>) -> Result<AggKind> {
+        let fraction = match &inputs[..] {
+            [_, ExprImpl::Literal(fraction)] => match fraction.get_data() {
+                Some(ScalarImpl::Decimal(v)) => f64::try_from(*v).ok(),
+                Some(ScalarImpl::Float64(v)) => Some(v.0),
+                Some(ScalarImpl::Float32(v)) => Some(v.0 as f64),
+                Some(ScalarImpl::Int32(v)) => Some(*v as f64),
+                _ => None,
+            },
+            _ => None,
+        };
+        match fraction {
+            Some(fraction) if (0.0..=1.0).contains(&fraction) => {
+                inputs.pop();
+                Ok(AggKind::ApproxPercentile(fraction.into()))
+            }
+            _ => Err(ErrorCode::InvalidInputSyntax(
+                "approx_percentile expects a constant fraction between 0 and 1 as its second \
+                 argument"
+                    .to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Binds `array_length(array[, dimension])`. Only one-dimensional arrays are supported, so the
+    /// dimension must be the constant 1 if given.
+    fn bind_array_length(mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if inputs.len() == 2 {
+            match inputs.pop().unwrap() {
+                ExprImpl::Literal(dimension)
+                    if dimension.get_data() == &Some(ScalarImpl::Int32(1)) => {}
+                _ => {
+                    return Err(ErrorCode::NotImplemented(
+                        "array_length only supports the dimension 1".to_string(),
+                        None.into(),
+                    )
+                    .into())
+                }
+            }
+        }
+        if !matches!(&inputs[..], [array] if matches!(array.return_type(), DataType::List { .. })) {
+            return Err(Self::err_unsupported_func("array_length", &inputs));
+        }
+        Ok(
+            FunctionCall::new_with_return_type(ExprType::ArrayLength, inputs, DataType::Int32)
+                .into(),
+        )
+    }
+
+    /// Binds `now()` and `current_timestamp` to the physical time of the epoch pinned for the
+    /// query, so that they are the same in all rows. In `EXPLAIN`, which pins no epoch, they are
+    /// kept as a `Now` call. They are rejected in other statements without a pinned epoch, e.g.
+    /// `CREATE MATERIALIZED VIEW`, as streaming has no notion of the current time yet.
+    fn bind_now(&self, function_name: &str, inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if !inputs.is_empty() {
+            return Err(Self::err_unsupported_func(function_name, &inputs));
+        }
+        match self.query_epoch {
+            Some(epoch) => {
+                let micros = Epoch::from(epoch).physical_time() as i64 * 1000;
+                Ok(Literal::new(Some(ScalarImpl::Int64(micros)), DataType::Timestampz).into())
+            }
+            None if self.explain => {
+                Ok(
+                    FunctionCall::new_with_return_type(ExprType::Now, vec![], DataType::Timestampz)
+                        .into(),
+                )
+            }
+            None => Err(ErrorCode::BindError(format!(
+                "`{}` is only supported in batch queries",
+                function_name
+            ))
+            .into()),
+        }
+    }
+
+    /// Binds `date_trunc(field, source)`. A `date` is truncated as a `timestamp`, and a
+    /// `timestamptz` is truncated in the session time zone.
+    fn bind_date_trunc(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if inputs.len() != 2 {
+            return Err(Self::err_unsupported_func("date_trunc", &inputs));
+        }
+        let mut source = inputs.pop().unwrap();
+        let field = Self::normalize_time_field(inputs.pop().unwrap(), ExprType::Lower);
+        if source.return_type() == DataType::Date {
+            source = source.cast_implicit(DataType::Timestamp)?;
+        }
+        let date_trunc = |field: ExprImpl, source: ExprImpl| {
+            FunctionCall::new_or_else(ExprType::DateTrunc, vec![field, source], |inputs| {
+                Self::err_unsupported_func("date_trunc", inputs)
+            })
+        };
+        if source.return_type() == DataType::Timestampz {
+            let session_timezone = self.session_timezone();
+            let truncated = date_trunc(field, session_timezone.at_time_zone(source).into())?;
+            Ok(session_timezone.at_time_zone(truncated.into()).into())
+        } else {
+            Ok(date_trunc(field, source)?.into())
+        }
+    }
+
+    /// Binds `date_part(field, source)`, which is `EXTRACT(field FROM source)` as a `double`.
+    fn bind_date_part(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if inputs.len() != 2 {
+            return Err(Self::err_unsupported_func("date_part", &inputs));
+        }
+        let source = inputs.pop().unwrap();
+        let field = Self::normalize_time_field(inputs.pop().unwrap(), ExprType::Upper);
+        self.bind_extract_from(field, source)?
+            .cast_implicit(DataType::Float64)
+    }
+
+    /// Binds `to_timestamp(seconds)`, which is the `timestamptz` of the seconds since the unix
+    /// epoch, and `to_timestamp(string, template)`, which parses a local time in the session time
+    /// zone.
+    fn bind_to_timestamp(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        match inputs.len() {
+            1 if inputs[0].return_type().is_numeric() => {
+                let seconds = inputs.pop().unwrap().cast_implicit(DataType::Float64)?;
+                Ok(FunctionCall::new(ExprType::ToTimestamp, vec![seconds])
+                    .unwrap()
+                    .into())
+            }
+            2 => {
+                let local = FunctionCall::new_or_else(ExprType::ToTimestamp1, inputs, |inputs| {
+                    Self::err_unsupported_func("to_timestamp", inputs)
+                })?;
+                Ok(self.session_timezone().at_time_zone(local.into()).into())
+            }
+            _ => Err(Self::err_unsupported_func("to_timestamp", &inputs)),
+        }
+    }
+
+    /// Changes the case of a time field like `'Day'`, which is expected in lower case by
+    /// `date_trunc` and in upper case by `extract`. `func_type` is either `Lower` or `Upper`.
+    fn normalize_time_field(field: ExprImpl, func_type: ExprType) -> ExprImpl {
+        if let ExprImpl::Literal(literal) = &field
+            && let Some(ScalarImpl::Utf8(s)) = literal.get_data()
+        {
+            let s = match func_type {
+                ExprType::Upper => s.to_uppercase(),
+                _ => s.to_lowercase(),
+            };
+            return Literal::new(Some(ScalarImpl::Utf8(s)), DataType::Varchar).into();
+        }
+        if field.return_type() == DataType::Varchar {
+            return FunctionCall::new_with_return_type(func_type, vec![field], DataType::Varchar)
+                .into();
+        }
+        field
+    }
+
+    /// Binds `concat(...)` and `concat_ws(separator, ...)`. Arguments of any type are cast to
+    /// `varchar`, and nulls among them are skipped when evaluating.
+    fn bind_concat(
+        func_type: ExprType,
+        function_name: &str,
+        inputs: Vec<ExprImpl>,
+    ) -> Result<ExprImpl> {
+        if inputs.is_empty() {
+            return Err(Self::err_unsupported_func(function_name, &inputs));
+        }
+        let inputs = inputs
+            .into_iter()
+            .map(|input| input.cast_assign(DataType::Varchar))
+            .try_collect()?;
+        Ok(FunctionCall::new_with_return_type(func_type, inputs, DataType::Varchar).into())
+    }
+
+    /// Binds `regexp_match(string, pattern[, flags])` and
+    /// `regexp_replace(string, pattern, replacement[, flags])`. The regex is compiled once when
+    /// building the expression, so every argument but the first must be a constant.
+    fn bind_regexp(
+        func_type: ExprType,
+        function_name: &str,
+        min_args: usize,
+        inputs: Vec<ExprImpl>,
+    ) -> Result<ExprImpl> {
+        if !(min_args..=min_args + 1).contains(&inputs.len())
+            || inputs.iter().any(|i| i.return_type() != DataType::Varchar)
+        {
+            return Err(Self::err_unsupported_func(function_name, &inputs));
+        }
+        if !inputs[1..]
+            .iter()
+            .all(|i| matches!(i, ExprImpl::Literal(_)))
+        {
+            return Err(ErrorCode::NotImplemented(
+                format!(
+                    "{} only supports constant patterns, replacements and flags",
+                    function_name
+                ),
+                None.into(),
+            )
+            .into());
+        }
+        let return_type = match func_type {
+            ExprType::RegexpMatch => DataType::List {
+                datatype: Box::new(DataType::Varchar),
+            },
+            _ => DataType::Varchar,
+        };
+        Ok(FunctionCall::new_with_return_type(func_type, inputs, return_type).into())
+    }
+
+    /// Rewrite `to_char(Date, Varchar)` to `to_char(Timestamp, Varchar)`, as only timestamps are
+    /// formatted by the backend.
+    fn rewrite_to_char_args(mut inputs: Vec<ExprImpl>) -> Vec<ExprImpl> {
+        if inputs.len() == 2 && inputs[0].return_type() == DataType::Date {
+            let input = inputs.remove(0);
+            inputs.insert(0, input.cast_implicit(DataType::Timestamp).unwrap());
+        }
+        inputs
+    }
+
     /// Rewrite the arguments to be consistent with the `round` signature:
     /// - round(Decimal, Int32) -> Decimal
     /// - round(Decimal) -> Decimal
@@ -131,6 +398,53 @@ impl Binder {
         }
     }
 
+    fn bind_window_function(
+        &mut self,
+        function_name: &str,
+        inputs: Vec<ExprImpl>,
+        window_spec: WindowSpec,
+    ) -> Result<ExprImpl> {
+        self.ensure_window_function_allowed()?;
+        let function_type = match function_name {
+            "row_number" if inputs.is_empty() => WindowFunctionType::RowNumber,
+            _ => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("unsupported window function: {:?}", function_name),
+                    None.into(),
+                )
+                .into())
+            }
+        };
+        let partition_by = window_spec
+            .partition_by
+            .into_iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let order_by = window_spec
+            .order_by
+            .into_iter()
+            .map(|order_by_expr| {
+                let direct = match order_by_expr.asc {
+                    None | Some(true) => Direction::Asc,
+                    Some(false) => Direction::Desc,
+                };
+                Ok::<_, RwError>((self.bind_expr(order_by_expr.expr)?, direct))
+            })
+            .try_collect()?;
+        Ok(WindowFunction::new(function_type, partition_by, order_by).into())
+    }
+
+    fn ensure_window_function_allowed(&self) -> Result<()> {
+        if let Some(clause) = self.context.clause {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "window functions are not allowed in {}",
+                clause
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
     fn ensure_aggregate_allowed(&self) -> Result<()> {
         if let Some(clause) = self.context.clause {
             if clause == Clause::Values || clause == Clause::Where {
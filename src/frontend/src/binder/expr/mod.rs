@@ -13,14 +13,20 @@
 // limitations under the License.
 
 use itertools::zip_eq;
+use risingwave_common::catalog::Field;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_expr::vector_op::timestamptz::lookup_time_zone;
 use risingwave_sqlparser::ast::{
-    BinaryOperator, DataType as AstDataType, DateTimeField, Expr, TrimWhereField, UnaryOperator,
+    BinaryOperator, DataType as AstDataType, DateTimeField, Expr, Ident, TrimWhereField,
+    UnaryOperator,
 };
 
 use crate::binder::Binder;
-use crate::expr::{least_restrictive, Expr as _, ExprImpl, ExprType, FunctionCall, SubqueryKind};
+use crate::expr::{
+    least_restrictive, Expr as _, ExprImpl, ExprRewriter, ExprType, FunctionCall, Literal,
+    SessionTimezone, SubqueryKind,
+};
 
 mod binary_op;
 mod column;
@@ -29,7 +35,18 @@ mod subquery;
 mod value;
 
 impl Binder {
+    /// Binds an expression. Casts from and to `timestamp with time zone` in it are rewritten to
+    /// take the session time zone, see [`SessionTimezone`].
     pub(super) fn bind_expr(&mut self, expr: Expr) -> Result<ExprImpl> {
+        let expr = self.bind_expr_inner(expr)?;
+        Ok(self.session_timezone().rewrite_expr(expr))
+    }
+
+    pub(super) fn session_timezone(&self) -> SessionTimezone {
+        SessionTimezone::new(self.timezone.clone())
+    }
+
+    fn bind_expr_inner(&mut self, expr: Expr) -> Result<ExprImpl> {
         match expr {
             Expr::IsNull(expr) => Ok(ExprImpl::FunctionCall(Box::new(
                 self.bind_is_operator(ExprType::IsNull, *expr)?,
@@ -88,6 +105,14 @@ impl Binder {
                 self.bind_between(*expr, negated, *low, *high)?,
             ))),
             Expr::Extract { field, expr } => self.bind_extract(field, *expr),
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => self.bind_at_time_zone(*timestamp, time_zone),
+            Expr::Array(exprs) => self.bind_array(exprs),
+            Expr::MapAccess { column, keys } => self.bind_array_access(*column, keys),
+            Expr::FieldIdentifier(expr, idents) => self.bind_field_access(*expr, idents),
+            Expr::Row(exprs) => self.bind_row(exprs),
             _ => Err(ErrorCode::NotImplemented(
                 format!("unsupported expression {:?}", expr),
                 112.into(),
@@ -97,18 +122,61 @@ impl Binder {
     }
 
     pub(super) fn bind_extract(&mut self, field: DateTimeField, expr: Expr) -> Result<ExprImpl> {
+        let field = self.bind_string(field.to_string())?.into();
+        let source = self.bind_expr(expr)?;
+        self.bind_extract_from(field, source)
+    }
+
+    /// Binds `EXTRACT(field FROM source)`. Fields of a `timestamptz` are extracted in the session
+    /// time zone, except `EPOCH` which doesn't depend on the time zone.
+    pub(super) fn bind_extract_from(&self, field: ExprImpl, source: ExprImpl) -> Result<ExprImpl> {
+        let source = if source.return_type() == DataType::Timestampz {
+            let epoch = Some(ScalarImpl::Utf8("EPOCH".to_string()));
+            let timezone = match &field {
+                ExprImpl::Literal(field) if field.get_data() == &epoch => {
+                    SessionTimezone::new("UTC".to_string())
+                }
+                _ => self.session_timezone(),
+            };
+            timezone.at_time_zone(source).into()
+        } else {
+            source
+        };
+        Ok(
+            FunctionCall::new_or_else(ExprType::Extract, vec![field, source], |inputs| {
+                ErrorCode::NotImplemented(
+                    format!(
+                        "function extract({:?} from {:?}) doesn't exist",
+                        inputs[0],
+                        inputs[1].return_type()
+                    ),
+                    112.into(),
+                )
+                .into()
+            })?
+            .into(),
+        )
+    }
+
+    /// Binds `timestamp AT TIME ZONE zone`. A `date` is read as a `timestamp` at midnight.
+    pub(super) fn bind_at_time_zone(
+        &mut self,
+        timestamp: Expr,
+        time_zone: String,
+    ) -> Result<ExprImpl> {
+        lookup_time_zone(&time_zone)?;
+        let mut timestamp = self.bind_expr(timestamp)?;
+        if timestamp.return_type() == DataType::Date {
+            timestamp = timestamp.cast_implicit(DataType::Timestamp)?;
+        }
         Ok(FunctionCall::new_or_else(
-            ExprType::Extract,
-            vec![
-                self.bind_string(field.to_string())?.into(),
-                self.bind_expr(expr)?,
-            ],
+            ExprType::AtTimeZone,
+            vec![timestamp, self.bind_string(time_zone)?.into()],
             |inputs| {
                 ErrorCode::NotImplemented(
                     format!(
-                        "function extract({} from {:?}) doesn't exist",
-                        field,
-                        inputs[1].return_type()
+                        "function timezone(varchar, {:?}) doesn't exist",
+                        inputs[0].return_type()
                     ),
                     112.into(),
                 )
@@ -282,6 +350,146 @@ impl Binder {
         ))
     }
 
+    /// Binds `ARRAY[...]`. The element type is the least restrictive type of the non-null
+    /// elements, and `ARRAY[NULL]` is a list of varchar like postgres.
+    pub(super) fn bind_array(&mut self, exprs: Vec<Expr>) -> Result<ExprImpl> {
+        let exprs: Vec<ExprImpl> = exprs
+            .into_iter()
+            .map(|e| self.bind_expr(e))
+            .collect::<Result<_>>()?;
+        if exprs.is_empty() {
+            return Err(
+                ErrorCode::BindError("cannot determine type of empty array".to_string()).into(),
+            );
+        }
+        let mut element_type = None;
+        for expr in exprs.iter().filter(|e| !e.is_null()) {
+            element_type = Some(match element_type {
+                Some(t) => least_restrictive(t, expr.return_type())?,
+                None => expr.return_type(),
+            });
+        }
+        let element_type = element_type.unwrap_or(DataType::Varchar);
+        let exprs = exprs
+            .into_iter()
+            .map(|e| e.cast_implicit(element_type.clone()))
+            .collect::<Result<_>>()?;
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::Array,
+            exprs,
+            DataType::List {
+                datatype: Box::new(element_type),
+            },
+        )
+        .into())
+    }
+
+    /// Binds subscripts like `v1[1][2]` as nested array accesses.
+    pub(super) fn bind_array_access(&mut self, array: Expr, keys: Vec<Expr>) -> Result<ExprImpl> {
+        let mut array = self.bind_expr(array)?;
+        for key in keys {
+            let element_type = match array.return_type() {
+                DataType::List { datatype } => *datatype,
+                t => {
+                    return Err(ErrorCode::BindError(format!(
+                        "cannot subscript type {:?} because it is not an array",
+                        t
+                    ))
+                    .into())
+                }
+            };
+            let index = self.bind_expr(key)?;
+            if !matches!(index.return_type(), DataType::Int16 | DataType::Int32) {
+                return Err(ErrorCode::BindError(format!(
+                    "array subscript must have type integer, got {:?}",
+                    index.return_type()
+                ))
+                .into());
+            }
+            let index = index.cast_implicit(DataType::Int32)?;
+            array = FunctionCall::new_with_return_type(
+                ExprType::ArrayAccess,
+                vec![array, index],
+                element_type,
+            )
+            .into();
+        }
+        Ok(array)
+    }
+
+    /// Binds `ROW(e1, e2, ...)`, or `(e1, e2, ...)` without the keyword, as a struct.
+    pub(super) fn bind_row(&mut self, exprs: Vec<Expr>) -> Result<ExprImpl> {
+        let exprs: Vec<ExprImpl> = exprs
+            .into_iter()
+            .map(|e| self.bind_expr(e))
+            .collect::<Result<_>>()?;
+        let data_type = DataType::Struct {
+            fields: exprs.iter().map(|e| e.return_type()).collect(),
+        };
+        Ok(FunctionCall::new_with_return_type(ExprType::Row, exprs, data_type).into())
+    }
+
+    /// Binds field selections like `(v1).a.b` as nested field accesses. Since [`DataType::Struct`]
+    /// doesn't carry field names, they are looked up in the [`Field`] of the struct, which is only
+    /// known for columns and casts to a struct type.
+    pub(super) fn bind_field_access(&mut self, expr: Expr, idents: Vec<Ident>) -> Result<ExprImpl> {
+        let mut idents = idents.into_iter();
+        let (mut expr, mut field) = match expr {
+            // `(t).v1.a` selects the column `v1` of table `t`.
+            Expr::Identifier(table)
+                if self.context.range_of.contains_key(&table.value)
+                    && self.context.get_column_binding(None, &table.value).is_err() =>
+            {
+                let column = idents.next().ok_or_else(|| {
+                    ErrorCode::BindError(format!("cannot select fields of table {}", table))
+                })?;
+                self.bind_column_with_field(&[table, column])?
+            }
+            Expr::Identifier(column) => self.bind_column_with_field(&[column])?,
+            Expr::CompoundIdentifier(idents) => self.bind_column_with_field(&idents)?,
+            Expr::Cast { expr, data_type } => {
+                let field = bind_struct_field(String::new(), &data_type)?;
+                (self.bind_cast(*expr, data_type)?, field)
+            }
+            expr => {
+                let expr = self.bind_expr(expr)?;
+                let field = Field::unnamed(expr.return_type());
+                (expr, field)
+            }
+        };
+        for ident in idents {
+            if !matches!(field.data_type, DataType::Struct { .. }) {
+                return Err(ErrorCode::BindError(format!(
+                    "column notation .{} applied to type {:?}, which is not a composite type",
+                    ident, field.data_type
+                ))
+                .into());
+            }
+            // Names of nested fields are paths like `v1.a`, so we match the last part.
+            let index = field
+                .sub_fields
+                .iter()
+                .position(|f| f.name.rsplit('.').next() == Some(ident.value.as_str()))
+                .ok_or_else(|| {
+                    ErrorCode::BindError(format!(
+                        "could not identify column \"{}\" in record data type",
+                        ident
+                    ))
+                })?;
+            field = field.sub_fields.swap_remove(index);
+            expr = FunctionCall::new_with_return_type(
+                ExprType::Field,
+                vec![
+                    expr,
+                    Literal::new(Some(ScalarImpl::Int32(index as i32)), DataType::Int32).into(),
+                ],
+                field.data_type.clone(),
+            )
+            .into();
+        }
+        Ok(expr)
+    }
+
     pub(super) fn bind_is_operator(
         &mut self,
         func_type: ExprType,
@@ -294,11 +502,45 @@ impl Binder {
     }
 
     pub(super) fn bind_cast(&mut self, expr: Expr, data_type: AstDataType) -> Result<ExprImpl> {
-        self.bind_expr(expr)?
-            .cast_explicit(bind_data_type(&data_type)?)
+        let data_type = bind_data_type(&data_type)?;
+        // A row is cast to a struct field by field, e.g. `ROW(1, '2')::STRUCT<a INT, b INT>`.
+        let (exprs, fields) = match (expr, &data_type) {
+            (Expr::Row(exprs), DataType::Struct { fields }) => (exprs, fields.clone()),
+            (expr, _) => return self.bind_expr(expr)?.cast_explicit(data_type),
+        };
+        if exprs.len() != fields.len() {
+            return Err(ErrorCode::BindError(format!(
+                "cannot cast a row with {} fields to {:?}",
+                exprs.len(),
+                data_type
+            ))
+            .into());
+        }
+        let exprs = zip_eq(exprs, fields.iter())
+            .map(|(e, t)| self.bind_expr(e)?.cast_explicit(t.clone()))
+            .collect::<Result<_>>()?;
+        Ok(FunctionCall::new_with_return_type(ExprType::Row, exprs, data_type).into())
     }
 }
 
+/// Builds the [`Field`] of a struct type with the names of its nested fields, which are paths like
+/// `name.a` the same as [`ColumnDesc`](risingwave_common::catalog::ColumnDesc)s of a struct column.
+pub fn bind_struct_field(name: String, data_type: &AstDataType) -> Result<Field> {
+    let sub_fields = match data_type {
+        AstDataType::Struct(fields) => fields
+            .iter()
+            .map(|f| bind_struct_field(format!("{}.{}", name, f.name.value), &f.data_type))
+            .collect::<Result<_>>()?,
+        _ => vec![],
+    };
+    Ok(Field::with_struct(
+        bind_data_type(data_type)?,
+        name,
+        sub_fields,
+        String::new(),
+    ))
+}
+
 pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
     let data_type = match data_type {
         AstDataType::Boolean => DataType::Boolean,
@@ -314,9 +556,17 @@ pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
         AstDataType::Timestamp(false) => DataType::Timestamp,
         AstDataType::Timestamp(true) => DataType::Timestampz,
         AstDataType::Interval => DataType::Interval,
+        AstDataType::Jsonb => DataType::Jsonb,
         AstDataType::Array(datatype) => DataType::List {
             datatype: Box::new(bind_data_type(datatype)?),
         },
+        AstDataType::Struct(fields) => DataType::Struct {
+            fields: fields
+                .iter()
+                .map(|f| bind_data_type(&f.data_type))
+                .collect::<Result<Vec<_>>>()?
+                .into(),
+        },
         AstDataType::Char(..) => {
             return Err(ErrorCode::NotImplemented(
                 "CHAR is not supported, please use VARCHAR instead\n".to_string(),
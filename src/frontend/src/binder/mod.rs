@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
 use risingwave_common::error::Result;
 use risingwave_sqlparser::ast::Statement;
 
@@ -31,7 +32,10 @@ pub use bind_context::BindContext;
 pub use delete::BoundDelete;
 pub use insert::BoundInsert;
 pub use query::BoundQuery;
-pub use relation::{BoundBaseTable, BoundJoin, BoundSource, BoundTableSource, Relation};
+pub use relation::{
+    BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundTableSource, BoundUnnest,
+    Relation,
+};
 pub use select::BoundSelect;
 pub use set_expr::BoundSetExpr;
 pub use statement::BoundStatement;
@@ -39,6 +43,7 @@ pub use values::BoundValues;
 pub use window_table_function::{BoundWindowTableFunction, WindowTableFunctionKind};
 
 use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::catalog::system_catalog::SystemCatalogSnapshot;
 
 /// `Binder` binds the identifiers in AST to columns in relations
 pub struct Binder {
@@ -52,6 +57,24 @@ pub struct Binder {
     upper_contexts: Vec<BindContext>,
 
     next_subquery_id: usize,
+
+    /// The session time zone, in which `timestamp with time zone` values are read and written.
+    timezone: String,
+    /// The epoch pinned for the query, which `now()` is evaluated at. It's `None` when the
+    /// statement is not going to be executed as a batch query, e.g. `EXPLAIN`.
+    query_epoch: Option<u64>,
+    /// Whether the statement is only explained rather than executed, so that `now()` and the
+    /// catalog tables in `pg_catalog` and `information_schema` may be bound without
+    /// `query_epoch`, and the system tables in `rw_catalog` without `system_catalog`.
+    explain: bool,
+    /// The snapshot the system tables in `rw_catalog` are read from. Without it, the tables can
+    /// only be bound for `explain`, as empty tables.
+    system_catalog: Option<SystemCatalogSnapshot>,
+    /// Whether any system table in `rw_catalog` has been bound.
+    reads_system_catalog: bool,
+    /// The schemas in which the relations referred to by unqualified names are looked up, in
+    /// order.
+    search_path: Vec<String>,
 }
 
 impl Binder {
@@ -62,14 +85,50 @@ impl Binder {
             context: BindContext::new(),
             upper_contexts: vec![],
             next_subquery_id: 0,
+            timezone: "UTC".to_string(),
+            query_epoch: None,
+            explain: false,
+            system_catalog: None,
+            reads_system_catalog: false,
+            search_path: vec![DEFAULT_SCHEMA_NAME.to_string()],
         }
     }
 
+    pub fn with_timezone(mut self, timezone: String) -> Binder {
+        self.timezone = timezone;
+        self
+    }
+
+    pub fn with_query_epoch(mut self, query_epoch: u64) -> Binder {
+        self.query_epoch = Some(query_epoch);
+        self
+    }
+
+    pub fn for_explain(mut self) -> Binder {
+        self.explain = true;
+        self
+    }
+
+    pub fn with_system_catalog(mut self, system_catalog: SystemCatalogSnapshot) -> Binder {
+        self.system_catalog = Some(system_catalog);
+        self
+    }
+
+    pub fn with_search_path(mut self, search_path: Vec<String>) -> Binder {
+        self.search_path = search_path;
+        self
+    }
+
     /// Bind a [`Statement`].
     pub fn bind(&mut self, stmt: Statement) -> Result<BoundStatement> {
         self.bind_statement(stmt)
     }
 
+    /// Whether the bound statement reads any system table in `rw_catalog`.
+    pub fn reads_system_catalog(&self) -> bool {
+        self.reads_system_catalog
+    }
+
     fn push_context(&mut self) {
         let new_context = std::mem::take(&mut self.context);
         self.upper_contexts.push(new_context);
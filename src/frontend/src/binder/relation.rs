@@ -16,21 +16,28 @@ use std::collections::hash_map::Entry;
 use std::str::FromStr;
 
 use itertools::Itertools;
-use risingwave_common::catalog::{ColumnDesc, DEFAULT_SCHEMA_NAME};
+use risingwave_common::catalog::{ColumnDesc, Field, Schema};
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, Datum};
+use risingwave_expr::vector_op::timestamptz::str_to_timestamptz;
+use risingwave_meta::manager::Epoch;
 use risingwave_pb::plan::JoinType;
 use risingwave_sqlparser::ast::{
-    JoinConstraint, JoinOperator, ObjectName, Query, TableAlias, TableFactor, TableWithJoins,
+    DataType as AstDataType, Expr as AstExpr, FunctionArg, Ident, JoinConstraint, JoinOperator,
+    ObjectName, Query, TableAlias, TableFactor, TableWithJoins, Value,
 };
 
 use super::bind_context::ColumnBinding;
 use super::{BoundQuery, BoundWindowTableFunction, WindowTableFunctionKind, UNNAMED_SUBQUERY};
 use crate::binder::Binder;
+use crate::catalog::pg_catalog::{
+    is_pg_catalog_schema, pg_catalog_table_columns, pg_catalog_table_rows,
+};
 use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::system_catalog::{system_table_columns, RW_CATALOG_SCHEMA_NAME};
 use crate::catalog::table_catalog::TableCatalog;
 use crate::catalog::{CatalogError, TableId};
-use crate::expr::{Expr, ExprImpl};
+use crate::expr::{Expr, ExprImpl, Literal};
 
 /// A validated item that refers to a table-like entity, including base table, subquery, join, etc.
 /// It is usually part of the `from` clause.
@@ -41,6 +48,8 @@ pub enum Relation {
     Subquery(Box<BoundSubquery>),
     Join(Box<BoundJoin>),
     WindowTableFunction(Box<BoundWindowTableFunction>),
+    Unnest(Box<BoundUnnest>),
+    SystemTable(Box<BoundSystemTable>),
 }
 
 #[derive(Debug)]
@@ -56,6 +65,10 @@ pub struct BoundBaseTable {
     pub name: String, // explain-only
     pub table_id: TableId,
     pub table_catalog: TableCatalog,
+    /// The epoch to read the table as of, given by `FOR SYSTEM_TIME AS OF`.
+    pub as_of_epoch: Option<u64>,
+    /// The indexes built on the table, ordered by their ids.
+    pub indexes: Vec<TableCatalog>,
 }
 
 impl From<&TableCatalog> for BoundBaseTable {
@@ -64,6 +77,8 @@ impl From<&TableCatalog> for BoundBaseTable {
             name: t.name.clone(),
             table_id: t.id,
             table_catalog: t.clone(),
+            as_of_epoch: None,
+            indexes: vec![],
         }
     }
 }
@@ -73,6 +88,21 @@ pub struct BoundSubquery {
     pub query: BoundQuery,
 }
 
+/// `unnest(array)` in the `from` clause, which expands a constant array into a set of rows. The
+/// array can also be a jsonb array, from `jsonb_array_elements(jsonb)`.
+#[derive(Debug)]
+pub struct BoundUnnest {
+    pub array: ExprImpl,
+}
+
+/// A read-only system table, whose rows are generated by the frontend when binding.
+#[derive(Debug)]
+pub struct BoundSystemTable {
+    pub name: String, // explain-only
+    pub rows: Vec<Vec<ExprImpl>>,
+    pub schema: Schema,
+}
+
 /// `BoundTableSource` is used by DML statement on table source like insert, updata
 #[derive(Debug)]
 pub struct BoundTableSource {
@@ -165,10 +195,33 @@ impl Binder {
 
     pub(super) fn bind_table_factor(&mut self, table_factor: TableFactor) -> Result<Relation> {
         match table_factor {
-            TableFactor::Table { name, alias, args } => {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                as_of,
+            } => {
                 if args.is_empty() {
-                    let (schema_name, table_name) = Self::resolve_table_name(name)?;
-                    self.bind_table_or_source(&schema_name, &table_name, alias)
+                    let (schema_name, table_name) = self.resolve_table_name(name)?;
+                    let relation = self.bind_table_or_source(&schema_name, &table_name, alias)?;
+                    match as_of {
+                        Some(as_of) => self.bind_as_of(relation, as_of),
+                        None => Ok(relation),
+                    }
+                } else if as_of.is_some() {
+                    Err(ErrorCode::NotImplemented(
+                        "FOR SYSTEM_TIME AS OF on table functions".into(),
+                        None.into(),
+                    )
+                    .into())
+                } else if name.0.len() == 1
+                    && ["unnest", "jsonb_array_elements"]
+                        .contains(&name.0[0].value.to_lowercase().as_str())
+                {
+                    let func_name = name.0[0].value.to_lowercase();
+                    Ok(Relation::Unnest(Box::new(
+                        self.bind_unnest(&func_name, args, alias)?,
+                    )))
                 } else {
                     let kind =
                         WindowTableFunctionKind::from_str(&name.0[0].value).map_err(|_| {
@@ -203,26 +256,152 @@ impl Binder {
         }
     }
 
+    /// Binds `unnest(array)`, or `jsonb_array_elements(jsonb)` which is planned as an unnest over
+    /// the elements of a constant jsonb array.
+    fn bind_unnest(
+        &mut self,
+        func_name: &str,
+        args: Vec<FunctionArg>,
+        alias: Option<TableAlias>,
+    ) -> Result<BoundUnnest> {
+        let expects_jsonb = func_name == "jsonb_array_elements";
+        let arg_desc = if expects_jsonb { "a jsonb" } else { "an array" };
+        let mut args = args.into_iter();
+        let (Some(arg), None) = (args.next(), args.next()) else {
+            return Err(ErrorCode::BindError(format!(
+                "{} expects {} as its only argument",
+                func_name, arg_desc
+            ))
+            .into());
+        };
+        let mut array = match &self.bind_function_arg(arg)?[..] {
+            [array] => array.clone(),
+            _ => {
+                return Err(ErrorCode::BindError(format!(
+                    "{} expects {} as its only argument",
+                    func_name, arg_desc
+                ))
+                .into())
+            }
+        };
+        if array.has_input_ref()
+            || array.has_correlated_input_ref()
+            || array.has_subquery()
+            || array.has_agg_call()
+        {
+            return Err(ErrorCode::NotImplemented(
+                format!("{} only supports constant arguments", func_name),
+                None.into(),
+            )
+            .into());
+        }
+        if expects_jsonb && array.return_type() == DataType::Varchar {
+            array = array.cast_assign(DataType::Jsonb)?;
+        }
+        let (element_type, column_name) = match array.return_type() {
+            DataType::List { datatype } if !expects_jsonb => (*datatype, "unnest"),
+            DataType::Jsonb if expects_jsonb => (DataType::Jsonb, "value"),
+            t => {
+                return Err(ErrorCode::BindError(format!(
+                    "{} expects {}, got {:?}",
+                    func_name, arg_desc, t
+                ))
+                .into())
+            }
+        };
+        self.bind_context(
+            [(false, Field::with_name(element_type, column_name))],
+            func_name.to_string(),
+            alias,
+        )?;
+        Ok(BoundUnnest { array })
+    }
+
+    /// Binds `FOR SYSTEM_TIME AS OF <timestamp>` of a base table. The timestamp is read in the
+    /// session time zone unless it has an explicit offset, and is mapped to the smallest epoch
+    /// generated at that time. Whether the epoch is still readable is checked
+    /// by meta when the query pins it.
+    fn bind_as_of(&mut self, relation: Relation, as_of: AstExpr) -> Result<Relation> {
+        let mut base_table = match relation {
+            Relation::BaseTable(base_table) => base_table,
+            _ => {
+                return Err(ErrorCode::NotImplemented(
+                    "FOR SYSTEM_TIME AS OF on sources".into(),
+                    None.into(),
+                )
+                .into())
+            }
+        };
+        let micros = match &as_of {
+            AstExpr::Value(Value::SingleQuotedString(value))
+            | AstExpr::TypedString {
+                data_type: AstDataType::Timestamp(_),
+                value,
+            } => str_to_timestamptz(value, &self.timezone)?,
+            _ => {
+                return Err(ErrorCode::BindError(format!(
+                    "FOR SYSTEM_TIME AS OF expects a timestamp literal, got {}",
+                    as_of
+                ))
+                .into())
+            }
+        };
+        let physical_time = u64::try_from(micros.div_euclid(1000)).map_err(|_| {
+            ErrorCode::BindError(format!(
+                "FOR SYSTEM_TIME AS OF timestamp {} is before the unix epoch",
+                as_of
+            ))
+        })?;
+        base_table.as_of_epoch = Some(Epoch::from_physical_time(physical_time).into_inner());
+        Ok(Relation::BaseTable(base_table))
+    }
+
     pub(super) fn bind_table_or_source(
         &mut self,
         schema_name: &str,
         table_name: &str,
         alias: Option<TableAlias>,
     ) -> Result<Relation> {
-        if schema_name == "pg_catalog" {
-            // TODO: support pg_catalog.
-            return Err(ErrorCode::NotImplemented(
-                // TODO: We can ref the document of `SHOW` commands here if ready.
-                r###"pg_catalog is not supported, please use `SHOW` commands for now.
-`SHOW TABLES`,
-`SHOW MATERIALIZED VIEWS`,
-`DESCRIBE <table>`,
-`SHOW COLUMNS FROM [table]`
-"###
-                .into(),
-                1695.into(),
-            )
-            .into());
+        if schema_name == RW_CATALOG_SCHEMA_NAME {
+            let fields = system_table_columns(table_name).ok_or_else(|| {
+                RwError::from(CatalogError::NotFound(
+                    "system table",
+                    table_name.to_string(),
+                ))
+            })?;
+            let rows = match &self.system_catalog {
+                Some(system_catalog) => system_catalog.rows(table_name),
+                None if self.explain => vec![],
+                None => {
+                    return Err(ErrorCode::BindError(format!(
+                        "system table {}.{} is only supported in batch queries",
+                        RW_CATALOG_SCHEMA_NAME, table_name
+                    ))
+                    .into())
+                }
+            };
+            self.reads_system_catalog = true;
+            return self.bind_system_table(table_name, fields, rows, alias);
+        }
+        if is_pg_catalog_schema(schema_name) {
+            let fields = pg_catalog_table_columns(schema_name, table_name).ok_or_else(|| {
+                RwError::from(CatalogError::NotFound(
+                    "catalog table",
+                    format!("{}.{}", schema_name, table_name),
+                ))
+            })?;
+            // The rows are read from the catalog when binding, which a streaming job couldn't
+            // maintain.
+            if self.query_epoch.is_none() && !self.explain {
+                return Err(ErrorCode::BindError(format!(
+                    "catalog table {}.{} is only supported in batch queries",
+                    schema_name, table_name
+                ))
+                .into());
+            }
+            let rows =
+                pg_catalog_table_rows(&self.catalog, &self.db_name, schema_name, table_name)?;
+            return self.bind_system_table(table_name, fields, rows, alias);
         }
 
         let (ret, columns) = {
@@ -234,10 +413,7 @@ impl Binder {
                 .or_else(|_| {
                     catalog
                         .get_source_by_name(&self.db_name, schema_name, table_name)
-                        .map(|s| {
-                            let source = s.clone().flatten();
-                            (Relation::Source(Box::new((&source).into())), source.columns)
-                        })
+                        .map(|s| (Relation::Source(Box::new(s.into())), s.columns.clone()))
                 })
                 .map_err(|_| {
                     RwError::from(CatalogError::NotFound(
@@ -250,14 +426,43 @@ impl Binder {
         self.bind_context(
             columns
                 .iter()
-                .cloned()
-                .map(|c| (c.name().to_string(), c.data_type().clone(), c.is_hidden)),
+                .map(|c| (c.is_hidden, Field::from(&c.column_desc))),
             table_name.to_string(),
             alias,
         )?;
         Ok(ret)
     }
 
+    /// Binds a read-only system table to the given rows, which are planned as constant values.
+    fn bind_system_table(
+        &mut self,
+        table_name: &str,
+        fields: Vec<Field>,
+        rows: Vec<Vec<Datum>>,
+        alias: Option<TableAlias>,
+    ) -> Result<Relation> {
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .zip_eq(&fields)
+                    .map(|(datum, field)| Literal::new(datum, field.data_type()).into())
+                    .collect()
+            })
+            .collect();
+
+        self.bind_context(
+            fields.iter().map(|f| (false, f.clone())),
+            table_name.to_string(),
+            alias,
+        )?;
+        Ok(Relation::SystemTable(Box::new(BoundSystemTable {
+            name: table_name.to_string(),
+            rows,
+            schema: Schema { fields },
+        })))
+    }
+
     pub(super) fn bind_table(
         &mut self,
         schema_name: &str,
@@ -273,38 +478,68 @@ impl Binder {
         self.bind_context(
             columns
                 .iter()
-                .cloned()
-                .map(|c| (c.name().to_string(), c.data_type().clone(), c.is_hidden)),
+                .map(|c| (c.is_hidden, Field::from(&c.column_desc))),
             table_name.to_string(),
             alias,
         )?;
 
         let table_id = table_catalog.id();
+        let mut indexes = self
+            .catalog
+            .get_schema_by_name(&self.db_name, schema_name)?
+            .iter_index_on(table_id)
+            .cloned()
+            .collect_vec();
+        indexes.sort_by_key(|index| index.id().table_id);
+
         Ok(BoundBaseTable {
             name: table_name.to_string(),
             table_id,
             table_catalog,
+            as_of_epoch: None,
+            indexes,
         })
     }
 
-    /// return the (`schema_name`, `table_name`)
-    pub fn resolve_table_name(name: ObjectName) -> Result<(String, String)> {
+    /// return the (`schema_name`, `table_name`), where `schema_name` is `None` if the name is not
+    /// qualified.
+    pub fn resolve_schema_qualified_name(name: ObjectName) -> Result<(Option<String>, String)> {
         let mut identifiers = name.0;
         let table_name = identifiers
             .pop()
             .ok_or_else(|| ErrorCode::InternalError("empty table name".into()))?
             .value;
 
-        let schema_name = identifiers
-            .pop()
-            .map(|ident| ident.value)
-            .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.into());
+        let schema_name = identifiers.pop().map(|ident| ident.value);
 
         Ok((schema_name, table_name))
     }
 
+    /// return the name of a database or schema, which can't be qualified.
+    pub fn resolve_unqualified_name(name: ObjectName) -> Result<String> {
+        match <[Ident; 1]>::try_from(name.0) {
+            Ok([ident]) => Ok(ident.value),
+            Err(identifiers) => Err(ErrorCode::InvalidInputSyntax(format!(
+                "unexpected qualified name: {}",
+                ObjectName(identifiers)
+            ))
+            .into()),
+        }
+    }
+
+    /// return the (`schema_name`, `table_name`), looking up the schema of an unqualified name in
+    /// the `search_path`.
+    pub(super) fn resolve_table_name(&self, name: ObjectName) -> Result<(String, String)> {
+        let (schema_name, table_name) = Self::resolve_schema_qualified_name(name)?;
+        let schema_name = schema_name.unwrap_or_else(|| {
+            self.catalog
+                .resolve_schema_name(&self.db_name, &self.search_path, &table_name)
+        });
+        Ok((schema_name, table_name))
+    }
+
     pub(super) fn bind_table_source(&mut self, name: ObjectName) -> Result<BoundTableSource> {
-        let (schema_name, source_name) = Self::resolve_table_name(name)?;
+        let (schema_name, source_name) = self.resolve_table_name(name)?;
         let source = self
             .catalog
             .get_source_by_name(&self.db_name, &schema_name, &source_name)?;
@@ -330,7 +565,7 @@ impl Binder {
     /// Fill the [`BindContext`](super::BindContext) for table.
     pub(super) fn bind_context(
         &mut self,
-        columns: impl IntoIterator<Item = (String, DataType, bool)>,
+        columns: impl IntoIterator<Item = (bool, Field)>,
         table_name: String,
         alias: Option<TableAlias>,
     ) -> Result<()> {
@@ -346,17 +581,16 @@ impl Binder {
         columns
             .into_iter()
             .enumerate()
-            .for_each(|(index, (name, data_type, is_hidden))| {
-                let name = match is_hidden {
-                    true => name,
-                    false => alias_iter.next().map(|t| t.value).unwrap_or(name),
-                };
+            .for_each(|(index, (is_hidden, mut field))| {
+                if !is_hidden && let Some(alias) = alias_iter.next() {
+                    field.name = alias.value;
+                }
+                let name = field.name.clone();
                 self.context.columns.push(ColumnBinding::new(
                     table_name.clone(),
-                    name.clone(),
                     begin + index,
-                    data_type,
                     is_hidden,
+                    field,
                 ));
                 self.context
                     .indexs_of
@@ -400,7 +634,7 @@ impl Binder {
                 .names()
                 .into_iter()
                 .zip_eq(query.data_types().into_iter())
-                .map(|(x, y)| (x, y, false)),
+                .map(|(name, data_type)| (false, Field::with_name(data_type, name))),
             format!("{}_{}", UNNAMED_SUBQUERY, sub_query_id),
             alias,
         )?;
@@ -158,8 +158,8 @@ impl Binder {
             .iter()
             .map(|column| {
                 (
-                    InputRef::new(column.index, column.data_type.clone()).into(),
-                    Some(column.column_name.clone()),
+                    InputRef::new(column.index, column.field.data_type.clone()).into(),
+                    Some(column.field.name.clone()),
                 )
             })
             .unzip();
@@ -174,8 +174,8 @@ impl Binder {
             .filter_map(|column| {
                 if !column.is_hidden {
                     Some((
-                        InputRef::new(column.index, column.data_type.clone()).into(),
-                        Some(column.column_name.clone()),
+                        InputRef::new(column.index, column.field.data_type.clone()).into(),
+                        Some(column.field.name.clone()),
                     ))
                 } else {
                     None
@@ -20,7 +20,7 @@ use risingwave_sqlparser::ast::Values;
 
 use super::bind_context::Clause;
 use crate::binder::Binder;
-use crate::expr::{least_restrictive, Expr as _, ExprImpl};
+use crate::expr::{least_restrictive, Expr as _, ExprImpl, ExprRewriter};
 
 #[derive(Debug)]
 pub struct BoundValues {
@@ -75,7 +75,9 @@ impl Binder {
             }
         };
 
-        // Insert casts.
+        // Insert casts, which are rewritten like the ones in expressions if they involve
+        // `timestamptz`.
+        let mut session_timezone = self.session_timezone();
         let rows = bound
             .into_iter()
             .map(|vec| {
@@ -85,7 +87,10 @@ impl Binder {
                     // When `types` are from `least_restrictive`, it's always ok.
                     // Because `least_restrictive` uses implicit cast, all of which allowed in
                     // assign context.
-                    .map(|(expr, ty)| expr.cast_assign(ty))
+                    .map(|(expr, ty)| {
+                        expr.cast_assign(ty)
+                            .map(|expr| session_timezone.rewrite_expr(expr))
+                    })
                     .try_collect()
             })
             .try_collect()?;
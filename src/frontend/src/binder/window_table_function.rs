@@ -15,6 +15,7 @@
 use std::str::FromStr;
 
 use itertools::Itertools;
+use risingwave_common::catalog::Field;
 use risingwave_common::error::{ErrorCode, RwError};
 use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, ObjectName};
@@ -74,7 +75,7 @@ impl Binder {
             )
             .into()),
         }?;
-        let (schema_name, table_name) = Self::resolve_table_name(table_name)?;
+        let (schema_name, table_name) = self.resolve_table_name(table_name)?;
 
         // TODO: support alias.
         let base = self.bind_table_or_source(&schema_name, &table_name, None)?;
@@ -111,17 +112,11 @@ impl Binder {
 
         let columns = columns
             .iter()
-            .map(|c| {
-                (
-                    c.column_desc.name.clone(),
-                    c.column_desc.data_type.clone(),
-                    c.is_hidden,
-                )
-            })
+            .map(|c| (c.is_hidden, Field::from(&c.column_desc)))
             .chain(
                 [
-                    ("window_start".to_string(), DataType::Timestamp, false),
-                    ("window_end".to_string(), DataType::Timestamp, false),
+                    (false, Field::with_name(DataType::Timestamp, "window_start")),
+                    (false, Field::with_name(DataType::Timestamp, "window_end")),
                 ]
                 .into_iter(),
             );
@@ -21,13 +21,14 @@ use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
+    TableStatistics as ProstTableStatistics,
 };
 use risingwave_pb::stream_plan::StreamNode;
 use risingwave_rpc_client::MetaClient;
 use tokio::sync::watch::Receiver;
 
 use super::root_catalog::Catalog;
-use super::DatabaseId;
+use super::{DatabaseId, SchemaId};
 
 pub type CatalogReadGuard = ArcRwLockReadGuard<RawRwLock, Catalog>;
 
@@ -49,10 +50,15 @@ impl CatalogReader {
 /// the version.
 #[async_trait::async_trait]
 pub trait CatalogWriter: Send + Sync {
+    /// Creates a database together with the default schema in it.
     async fn create_database(&self, db_name: &str) -> Result<()>;
 
     async fn create_schema(&self, db_id: DatabaseId, schema_name: &str) -> Result<()>;
 
+    async fn drop_database(&self, db_id: DatabaseId) -> Result<()>;
+
+    async fn drop_schema(&self, schema_id: SchemaId) -> Result<()>;
+
     async fn create_materialized_view(&self, table: ProstTable, plan: StreamNode) -> Result<()>;
 
     async fn create_materialized_source(
@@ -69,6 +75,12 @@ pub trait CatalogWriter: Send + Sync {
     async fn drop_materialized_view(&self, table_id: TableId) -> Result<()>;
 
     async fn drop_source(&self, source_id: u32) -> Result<()>;
+
+    async fn update_table_stats(
+        &self,
+        table_id: TableId,
+        stats: ProstTableStatistics,
+    ) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -102,6 +114,16 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn drop_database(&self, db_id: DatabaseId) -> Result<()> {
+        let version = self.meta_client.drop_database(db_id).await?;
+        self.wait_version(version).await
+    }
+
+    async fn drop_schema(&self, schema_id: SchemaId) -> Result<()> {
+        let version = self.meta_client.drop_schema(schema_id).await?;
+        self.wait_version(version).await
+    }
+
     // TODO: maybe here to pass a materialize plan node
     async fn create_materialized_view(&self, table: ProstTable, plan: StreamNode) -> Result<()> {
         let (_, version) = self
@@ -146,6 +168,15 @@ impl CatalogWriter for CatalogWriterImpl {
         let version = self.meta_client.drop_source(source_id).await?;
         self.wait_version(version).await
     }
+
+    async fn update_table_stats(
+        &self,
+        table_id: TableId,
+        stats: ProstTableStatistics,
+    ) -> Result<()> {
+        let version = self.meta_client.update_table_stats(table_id, stats).await?;
+        self.wait_version(version).await
+    }
 }
 
 impl CatalogWriterImpl {
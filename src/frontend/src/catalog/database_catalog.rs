@@ -48,10 +48,19 @@ impl DatabaseCatalog {
         self.schema_by_name.keys().cloned().collect_vec()
     }
 
+    pub fn iter_schemas(&self) -> impl Iterator<Item = &SchemaCatalog> {
+        self.schema_by_name.values()
+    }
+
     pub fn get_schema_by_name(&self, name: &str) -> Option<&SchemaCatalog> {
         self.schema_by_name.get(name)
     }
 
+    pub fn get_schema_by_id(&self, schema_id: SchemaId) -> Option<&SchemaCatalog> {
+        let name = self.schema_name_by_id.get(&schema_id)?;
+        self.schema_by_name.get(name)
+    }
+
     pub fn get_schema_mut(&mut self, schema_id: SchemaId) -> Option<&mut SchemaCatalog> {
         let name = self.schema_name_by_id.get(&schema_id).unwrap();
         self.schema_by_name.get_mut(name)
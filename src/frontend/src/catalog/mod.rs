@@ -20,10 +20,13 @@ pub(crate) mod catalog_service;
 
 pub(crate) mod column_catalog;
 pub(crate) mod database_catalog;
+pub(crate) mod pg_catalog;
 pub(crate) mod root_catalog;
 pub(crate) mod schema_catalog;
 pub(crate) mod source_catalog;
+pub(crate) mod system_catalog;
 pub(crate) mod table_catalog;
+pub(crate) mod table_statistics;
 
 #[allow(dead_code)]
 pub(crate) type SourceId = u32;
@@ -0,0 +1,437 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+
+use super::column_catalog::ColumnCatalog;
+use super::root_catalog::Catalog;
+use super::schema_catalog::SchemaCatalog;
+use crate::handler::util::data_type_to_type_oid;
+
+pub const PG_CATALOG_SCHEMA_NAME: &str = "pg_catalog";
+pub const INFORMATION_SCHEMA_SCHEMA_NAME: &str = "information_schema";
+
+pub const PG_NAMESPACE_TABLE_NAME: &str = "pg_namespace";
+pub const PG_CLASS_TABLE_NAME: &str = "pg_class";
+pub const PG_ATTRIBUTE_TABLE_NAME: &str = "pg_attribute";
+pub const PG_TYPE_TABLE_NAME: &str = "pg_type";
+pub const INFORMATION_SCHEMA_TABLES_TABLE_NAME: &str = "tables";
+pub const INFORMATION_SCHEMA_COLUMNS_TABLE_NAME: &str = "columns";
+
+/// The types listed in `pg_type`, with their names in postgres.
+const PG_TYPES: &[(DataType, &str)] = &[
+    (DataType::Boolean, "bool"),
+    (DataType::Int16, "int2"),
+    (DataType::Int32, "int4"),
+    (DataType::Int64, "int8"),
+    (DataType::Float32, "float4"),
+    (DataType::Float64, "float8"),
+    (DataType::Decimal, "numeric"),
+    (DataType::Varchar, "varchar"),
+    (DataType::Date, "date"),
+    (DataType::Time, "time"),
+    (DataType::Timestamp, "timestamp"),
+    (DataType::Timestampz, "timestamptz"),
+];
+
+/// Returns whether `schema_name` is a schema of the postgres-compatible catalog tables.
+pub fn is_pg_catalog_schema(schema_name: &str) -> bool {
+    schema_name == PG_CATALOG_SCHEMA_NAME || schema_name == INFORMATION_SCHEMA_SCHEMA_NAME
+}
+
+/// Returns the columns of the catalog table `table_name` in `schema_name`, or `None` if there is
+/// no such table. Only the commonly used columns of the postgres tables are provided.
+pub fn pg_catalog_table_columns(schema_name: &str, table_name: &str) -> Option<Vec<Field>> {
+    let columns: &[(DataType, &str)] = match (schema_name, table_name) {
+        (PG_CATALOG_SCHEMA_NAME, PG_NAMESPACE_TABLE_NAME) => {
+            &[(DataType::Int32, "oid"), (DataType::Varchar, "nspname")]
+        }
+        (PG_CATALOG_SCHEMA_NAME, PG_CLASS_TABLE_NAME) => &[
+            (DataType::Int32, "oid"),
+            (DataType::Varchar, "relname"),
+            (DataType::Int32, "relnamespace"),
+            (DataType::Varchar, "relkind"),
+            (DataType::Int16, "relnatts"),
+        ],
+        (PG_CATALOG_SCHEMA_NAME, PG_ATTRIBUTE_TABLE_NAME) => &[
+            (DataType::Int32, "attrelid"),
+            (DataType::Varchar, "attname"),
+            (DataType::Int32, "atttypid"),
+            (DataType::Int16, "attnum"),
+            (DataType::Boolean, "attnotnull"),
+            (DataType::Boolean, "attisdropped"),
+        ],
+        (PG_CATALOG_SCHEMA_NAME, PG_TYPE_TABLE_NAME) => {
+            &[(DataType::Int32, "oid"), (DataType::Varchar, "typname")]
+        }
+        (INFORMATION_SCHEMA_SCHEMA_NAME, INFORMATION_SCHEMA_TABLES_TABLE_NAME) => &[
+            (DataType::Varchar, "table_catalog"),
+            (DataType::Varchar, "table_schema"),
+            (DataType::Varchar, "table_name"),
+            (DataType::Varchar, "table_type"),
+        ],
+        (INFORMATION_SCHEMA_SCHEMA_NAME, INFORMATION_SCHEMA_COLUMNS_TABLE_NAME) => &[
+            (DataType::Varchar, "table_catalog"),
+            (DataType::Varchar, "table_schema"),
+            (DataType::Varchar, "table_name"),
+            (DataType::Varchar, "column_name"),
+            (DataType::Int32, "ordinal_position"),
+            (DataType::Varchar, "is_nullable"),
+            (DataType::Varchar, "data_type"),
+        ],
+        _ => return None,
+    };
+    Some(
+        columns
+            .iter()
+            .map(|(data_type, name)| Field::with_name(data_type.clone(), *name))
+            .collect(),
+    )
+}
+
+/// Generates the rows of the catalog table `table_name` in `schema_name` for the database
+/// `db_name`, whose columns are given by [`pg_catalog_table_columns`].
+pub fn pg_catalog_table_rows(
+    catalog: &Catalog,
+    db_name: &str,
+    schema_name: &str,
+    table_name: &str,
+) -> Result<Vec<Vec<Datum>>> {
+    let schemas = catalog
+        .get_database_by_name(db_name)?
+        .iter_schemas()
+        .sorted_by_key(|schema| schema.id())
+        .collect_vec();
+    let rows = match (schema_name, table_name) {
+        (PG_CATALOG_SCHEMA_NAME, PG_NAMESPACE_TABLE_NAME) => schemas
+            .into_iter()
+            .map(|schema| {
+                vec![
+                    Some(ScalarImpl::Int32(schema.id() as i32)),
+                    Some(ScalarImpl::Utf8(schema.name().to_string())),
+                ]
+            })
+            .collect(),
+        (PG_CATALOG_SCHEMA_NAME, PG_CLASS_TABLE_NAME) => schemas
+            .into_iter()
+            .flat_map(|schema| {
+                relations(schema).into_iter().map(move |relation| {
+                    vec![
+                        Some(ScalarImpl::Int32(relation.id as i32)),
+                        Some(ScalarImpl::Utf8(relation.name.to_string())),
+                        Some(ScalarImpl::Int32(schema.id() as i32)),
+                        Some(ScalarImpl::Utf8(relation.kind.relkind().to_string())),
+                        Some(ScalarImpl::Int16(relation.visible_columns().count() as i16)),
+                    ]
+                })
+            })
+            .collect(),
+        (PG_CATALOG_SCHEMA_NAME, PG_ATTRIBUTE_TABLE_NAME) => schemas
+            .into_iter()
+            .flat_map(relations)
+            .flat_map(|relation| {
+                relation
+                    .visible_columns()
+                    .enumerate()
+                    .map(|(index, column)| {
+                        vec![
+                            Some(ScalarImpl::Int32(relation.id as i32)),
+                            Some(ScalarImpl::Utf8(column.name().to_string())),
+                            Some(ScalarImpl::Int32(
+                                data_type_to_type_oid(column.data_type().clone()).as_number(),
+                            )),
+                            Some(ScalarImpl::Int16(index as i16 + 1)),
+                            Some(ScalarImpl::Bool(false)),
+                            Some(ScalarImpl::Bool(false)),
+                        ]
+                    })
+                    .collect_vec()
+            })
+            .collect(),
+        (PG_CATALOG_SCHEMA_NAME, PG_TYPE_TABLE_NAME) => PG_TYPES
+            .iter()
+            .map(|(data_type, name)| {
+                vec![
+                    Some(ScalarImpl::Int32(
+                        data_type_to_type_oid(data_type.clone()).as_number(),
+                    )),
+                    Some(ScalarImpl::Utf8(name.to_string())),
+                ]
+            })
+            .collect(),
+        (INFORMATION_SCHEMA_SCHEMA_NAME, INFORMATION_SCHEMA_TABLES_TABLE_NAME) => schemas
+            .into_iter()
+            .flat_map(|schema| {
+                relations(schema)
+                    .into_iter()
+                    .filter_map(|relation| Some((relation.kind.table_type()?, relation)))
+                    .map(|(table_type, relation)| {
+                        vec![
+                            Some(ScalarImpl::Utf8(db_name.to_string())),
+                            Some(ScalarImpl::Utf8(schema.name().to_string())),
+                            Some(ScalarImpl::Utf8(relation.name.to_string())),
+                            Some(ScalarImpl::Utf8(table_type.to_string())),
+                        ]
+                    })
+                    .collect_vec()
+            })
+            .collect(),
+        (INFORMATION_SCHEMA_SCHEMA_NAME, INFORMATION_SCHEMA_COLUMNS_TABLE_NAME) => schemas
+            .into_iter()
+            .flat_map(|schema| {
+                relations(schema)
+                    .into_iter()
+                    .filter(|relation| relation.kind.table_type().is_some())
+                    .flat_map(|relation| {
+                        relation
+                            .visible_columns()
+                            .enumerate()
+                            .map(|(index, column)| {
+                                vec![
+                                    Some(ScalarImpl::Utf8(db_name.to_string())),
+                                    Some(ScalarImpl::Utf8(schema.name().to_string())),
+                                    Some(ScalarImpl::Utf8(relation.name.to_string())),
+                                    Some(ScalarImpl::Utf8(column.name().to_string())),
+                                    Some(ScalarImpl::Int32(index as i32 + 1)),
+                                    Some(ScalarImpl::Utf8("YES".to_string())),
+                                    Some(ScalarImpl::Utf8(
+                                        pg_type_name(column.data_type()).to_string(),
+                                    )),
+                                ]
+                            })
+                            .collect_vec()
+                    })
+                    .collect_vec()
+            })
+            .collect(),
+        _ => vec![],
+    };
+    Ok(rows)
+}
+
+#[derive(Clone, Copy)]
+enum RelationKind {
+    Table,
+    MaterializedView,
+    Index,
+    Source,
+}
+
+impl RelationKind {
+    /// The `relkind` in `pg_class`. Sources are shown as foreign tables.
+    fn relkind(self) -> &'static str {
+        match self {
+            RelationKind::Table => "r",
+            RelationKind::MaterializedView => "m",
+            RelationKind::Index => "i",
+            RelationKind::Source => "f",
+        }
+    }
+
+    /// The `table_type` in `information_schema.tables`, which doesn't list indexes.
+    fn table_type(self) -> Option<&'static str> {
+        match self {
+            RelationKind::Table => Some("BASE TABLE"),
+            RelationKind::MaterializedView => Some("VIEW"),
+            RelationKind::Index => None,
+            RelationKind::Source => Some("FOREIGN"),
+        }
+    }
+}
+
+/// A table-like entity listed in `pg_class`.
+struct RelationInfo<'a> {
+    id: u32,
+    name: &'a str,
+    kind: RelationKind,
+    columns: &'a [ColumnCatalog],
+}
+
+impl<'a> RelationInfo<'a> {
+    fn visible_columns(&self) -> impl Iterator<Item = &'a ColumnCatalog> {
+        self.columns.iter().filter(|column| !column.is_hidden())
+    }
+}
+
+/// Lists the relations in the schema, ordered by their ids. A materialized source is listed once
+/// as a table.
+fn relations(schema: &SchemaCatalog) -> Vec<RelationInfo<'_>> {
+    let tables = schema
+        .iter_table()
+        .map(|t| (t, RelationKind::Table))
+        .chain(
+            schema
+                .iter_mv()
+                .map(|t| (t, RelationKind::MaterializedView)),
+        )
+        .chain(schema.iter_index().map(|t| (t, RelationKind::Index)))
+        .map(|(table, kind)| RelationInfo {
+            id: table.id().table_id,
+            name: table.name(),
+            kind,
+            columns: table.columns(),
+        });
+    let sources = schema
+        .iter_source()
+        .filter(|source| schema.get_table_by_name(&source.name).is_none())
+        .map(|source| RelationInfo {
+            id: source.id,
+            name: &source.name,
+            kind: RelationKind::Source,
+            columns: &source.columns,
+        });
+    tables
+        .chain(sources)
+        .sorted_by_key(|relation| relation.id)
+        .collect()
+}
+
+/// The name of the type in `information_schema.columns`.
+fn pg_type_name(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Boolean => "boolean",
+        DataType::Int16 => "smallint",
+        DataType::Int32 => "integer",
+        DataType::Int64 => "bigint",
+        DataType::Float32 => "real",
+        DataType::Float64 => "double precision",
+        DataType::Decimal => "numeric",
+        DataType::Varchar => "character varying",
+        DataType::Date => "date",
+        DataType::Time => "time without time zone",
+        DataType::Timestamp => "timestamp without time zone",
+        DataType::Timestampz => "timestamp with time zone",
+        DataType::Interval => "interval",
+        DataType::Struct { .. } => "record",
+        DataType::List { .. } => "ARRAY",
+        DataType::Jsonb => "jsonb",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use super::*;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_pg_catalog_table_rows() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1 from t")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog = session.env().catalog_reader().read_guard();
+        let rows = |schema_name, table_name| {
+            let rows =
+                pg_catalog_table_rows(&catalog, DEFAULT_DATABASE_NAME, schema_name, table_name)
+                    .unwrap();
+            let columns = pg_catalog_table_columns(schema_name, table_name).unwrap();
+            assert!(rows.iter().all(|row| row.len() == columns.len()));
+            rows
+        };
+        let utf8 = |s: &str| Some(ScalarImpl::Utf8(s.to_string()));
+
+        let namespaces = rows(PG_CATALOG_SCHEMA_NAME, PG_NAMESPACE_TABLE_NAME);
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0][1], utf8(DEFAULT_SCHEMA_NAME));
+
+        let classes = rows(PG_CATALOG_SCHEMA_NAME, PG_CLASS_TABLE_NAME);
+        assert_eq!(
+            classes
+                .iter()
+                .map(|row| (row[1].clone(), row[3].clone(), row[4].clone()))
+                .collect_vec(),
+            vec![
+                (utf8("t"), utf8("r"), Some(ScalarImpl::Int16(2))),
+                (utf8("mv"), utf8("m"), Some(ScalarImpl::Int16(1))),
+            ]
+        );
+
+        let attributes = rows(PG_CATALOG_SCHEMA_NAME, PG_ATTRIBUTE_TABLE_NAME);
+        assert_eq!(
+            attributes
+                .iter()
+                .map(|row| (row[1].clone(), row[2].clone(), row[3].clone()))
+                .collect_vec(),
+            vec![
+                (
+                    utf8("v1"),
+                    Some(ScalarImpl::Int32(23)),
+                    Some(ScalarImpl::Int16(1))
+                ),
+                (
+                    utf8("v2"),
+                    Some(ScalarImpl::Int32(1043)),
+                    Some(ScalarImpl::Int16(2))
+                ),
+                (
+                    utf8("v1"),
+                    Some(ScalarImpl::Int32(23)),
+                    Some(ScalarImpl::Int16(1))
+                ),
+            ]
+        );
+
+        let tables = rows(
+            INFORMATION_SCHEMA_SCHEMA_NAME,
+            INFORMATION_SCHEMA_TABLES_TABLE_NAME,
+        );
+        assert_eq!(
+            tables,
+            vec![
+                vec![
+                    utf8(DEFAULT_DATABASE_NAME),
+                    utf8(DEFAULT_SCHEMA_NAME),
+                    utf8("t"),
+                    utf8("BASE TABLE"),
+                ],
+                vec![
+                    utf8(DEFAULT_DATABASE_NAME),
+                    utf8(DEFAULT_SCHEMA_NAME),
+                    utf8("mv"),
+                    utf8("VIEW"),
+                ],
+            ]
+        );
+
+        let columns = rows(
+            INFORMATION_SCHEMA_SCHEMA_NAME,
+            INFORMATION_SCHEMA_COLUMNS_TABLE_NAME,
+        );
+        assert_eq!(
+            columns
+                .iter()
+                .map(|row| (row[2].clone(), row[3].clone(), row[6].clone()))
+                .collect_vec(),
+            vec![
+                (utf8("t"), utf8("v1"), utf8("integer")),
+                (utf8("t"), utf8("v2"), utf8("character varying")),
+                (utf8("mv"), utf8("v1"), utf8("integer")),
+            ]
+        );
+
+        assert!(pg_catalog_table_columns(PG_CATALOG_SCHEMA_NAME, "pg_unknown").is_none());
+    }
+}
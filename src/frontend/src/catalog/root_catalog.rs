@@ -15,13 +15,15 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use risingwave_common::catalog::{CatalogVersion, TableId};
+use risingwave_common::catalog::{CatalogVersion, TableId, DEFAULT_SCHEMA_NAME};
 use risingwave_common::error::Result;
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
 };
 
+use super::pg_catalog::{is_pg_catalog_schema, pg_catalog_table_columns, PG_CATALOG_SCHEMA_NAME};
 use super::source_catalog::SourceCatalog;
+use super::system_catalog::{system_table_columns, RW_CATALOG_SCHEMA_NAME};
 use super::{CatalogError, SourceId};
 use crate::catalog::database_catalog::DatabaseCatalog;
 use crate::catalog::schema_catalog::SchemaCatalog;
@@ -90,6 +92,14 @@ impl Catalog {
             .create_table(proto);
     }
 
+    pub fn update_table(&mut self, proto: &ProstTable) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .update_table(proto);
+    }
+
     pub fn create_source(&mut self, proto: ProstSource) {
         self.get_database_mut(proto.database_id)
             .unwrap()
@@ -143,6 +153,61 @@ impl Catalog {
             .ok_or_else(|| CatalogError::NotFound("schema", schema_name.to_string()).into())
     }
 
+    /// Returns the first schema in `search_path` that exists in the database, where new relations
+    /// with unqualified names are created.
+    pub fn first_valid_schema(
+        &self,
+        db_name: &str,
+        search_path: &[String],
+    ) -> Result<&SchemaCatalog> {
+        let db = self.get_database_by_name(db_name)?;
+        search_path
+            .iter()
+            .find_map(|schema_name| db.get_schema_by_name(schema_name))
+            .ok_or_else(|| CatalogError::NotFound("schema", search_path.join(", ")).into())
+    }
+
+    /// Resolves the schema of a relation referred to by the unqualified `relation_name`, i.e. the
+    /// first schema in `search_path` that contains a table, materialized view or source with that
+    /// name. If there is no such schema, the first one in `search_path` is returned, so that the
+    /// lookups fail with the relation not found.
+    ///
+    /// As in PostgreSQL, `pg_catalog` is searched first unless it's explicitly in `search_path`.
+    pub fn resolve_schema_name(
+        &self,
+        db_name: &str,
+        search_path: &[String],
+        relation_name: &str,
+    ) -> String {
+        let db = self.database_by_name.get(db_name);
+        let implicit_pg_catalog = if search_path.iter().any(|s| s == PG_CATALOG_SCHEMA_NAME) {
+            None
+        } else {
+            Some(PG_CATALOG_SCHEMA_NAME)
+        };
+        implicit_pg_catalog
+            .into_iter()
+            .chain(search_path.iter().map(String::as_str))
+            .find(|schema_name| {
+                // The system tables in `rw_catalog`, `pg_catalog` and `information_schema` are not
+                // kept in the catalog.
+                if *schema_name == RW_CATALOG_SCHEMA_NAME {
+                    return system_table_columns(relation_name).is_some();
+                }
+                if is_pg_catalog_schema(schema_name) {
+                    return pg_catalog_table_columns(schema_name, relation_name).is_some();
+                }
+                db.and_then(|db| db.get_schema_by_name(schema_name))
+                    .map_or(false, |schema| {
+                        schema.get_table_by_name(relation_name).is_some()
+                            || schema.get_source_by_name(relation_name).is_some()
+                    })
+            })
+            .or_else(|| search_path.first().map(String::as_str))
+            .unwrap_or(DEFAULT_SCHEMA_NAME)
+            .to_string()
+    }
+
     pub fn get_table_by_name(
         &self,
         db_name: &str,
@@ -154,6 +219,19 @@ impl Catalog {
             .ok_or_else(|| CatalogError::NotFound("table", table_name.to_string()).into())
     }
 
+    pub fn get_table_by_id(
+        &self,
+        db_id: DatabaseId,
+        schema_id: SchemaId,
+        table_id: TableId,
+    ) -> Option<&TableCatalog> {
+        let name = self.db_name_by_id.get(&db_id)?;
+        self.database_by_name
+            .get(name)?
+            .get_schema_by_id(schema_id)?
+            .get_table_by_id(table_id)
+    }
+
     pub fn get_source_by_name(
         &self,
         db_name: &str,
@@ -26,7 +26,6 @@ use crate::catalog::SchemaId;
 #[derive(Clone, Debug)]
 pub struct SchemaCatalog {
     id: SchemaId,
-    #[allow(dead_code)]
     name: String,
     table_by_name: HashMap<String, TableCatalog>,
     table_name_by_id: HashMap<TableId, String>,
@@ -44,6 +43,12 @@ impl SchemaCatalog {
         self.table_name_by_id.try_insert(id, name).unwrap();
     }
 
+    /// Replace the table of the same id, e.g. after its statistics are updated.
+    pub fn update_table(&mut self, prost: &ProstTable) {
+        let name = self.table_name_by_id.get(&prost.id.into()).unwrap();
+        self.table_by_name.insert(name.clone(), prost.into());
+    }
+
     pub fn drop_table(&mut self, id: TableId) {
         let name = self.table_name_by_id.remove(&id).unwrap();
         self.table_by_name.remove(&name).unwrap();
@@ -74,10 +79,23 @@ impl SchemaCatalog {
     pub fn iter_mv(&self) -> impl Iterator<Item = &TableCatalog> {
         self.table_by_name
             .iter()
-            .filter(|(_, v)| v.associated_source_id.is_none())
+            .filter(|(_, v)| v.associated_source_id.is_none() && !v.is_index())
+            .map(|(_, v)| v)
+    }
+
+    pub fn iter_index(&self) -> impl Iterator<Item = &TableCatalog> {
+        self.table_by_name
+            .iter()
+            .filter(|(_, v)| v.is_index())
             .map(|(_, v)| v)
     }
 
+    /// Iterate the indexes built on the given table or materialized view.
+    pub fn iter_index_on(&self, table_id: TableId) -> impl Iterator<Item = &TableCatalog> {
+        self.iter_index()
+            .filter(move |index| index.index_on_id() == Some(table_id))
+    }
+
     /// Iterate all sources, including the materialized sources.
     pub fn iter_source(&self) -> impl Iterator<Item = &SourceCatalog> {
         self.source_by_name
@@ -101,6 +119,11 @@ impl SchemaCatalog {
         self.table_by_name.get(table_name)
     }
 
+    pub fn get_table_by_id(&self, table_id: TableId) -> Option<&TableCatalog> {
+        let name = self.table_name_by_id.get(&table_id)?;
+        self.table_by_name.get(name)
+    }
+
     pub fn get_source_by_name(&self, source_name: &str) -> Option<&SourceCatalog> {
         self.source_by_name.get(source_name)
     }
@@ -108,6 +131,10 @@ impl SchemaCatalog {
     pub fn id(&self) -> SchemaId {
         self.id
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl From<&ProstSchema> for SchemaCatalog {
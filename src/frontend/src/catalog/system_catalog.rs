@@ -0,0 +1,317 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::future::try_join_all;
+use itertools::Itertools;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_meta::manager::Epoch;
+use risingwave_pb::meta::table_fragments::fragment::{FragmentDistributionType, FragmentType};
+use risingwave_pb::meta::table_fragments::ActorState;
+use risingwave_pb::meta::TableFragments;
+use risingwave_rpc_client::ComputeClient;
+
+use crate::session::SessionImpl;
+
+/// The schema of the read-only system tables that show the streaming jobs running in the cluster.
+pub const RW_CATALOG_SCHEMA_NAME: &str = "rw_catalog";
+
+pub const RW_FRAGMENTS_TABLE_NAME: &str = "rw_fragments";
+pub const RW_ACTORS_TABLE_NAME: &str = "rw_actors";
+pub const RW_ACTOR_STATS_TABLE_NAME: &str = "rw_actor_stats";
+
+/// Returns the columns of the system table `table_name` in `rw_catalog`, or `None` if there is no
+/// such table.
+pub fn system_table_columns(table_name: &str) -> Option<Vec<Field>> {
+    let columns: &[(DataType, &str)] = match table_name {
+        RW_FRAGMENTS_TABLE_NAME => &[
+            (DataType::Int32, "fragment_id"),
+            (DataType::Int32, "table_id"),
+            (DataType::Varchar, "fragment_type"),
+            (DataType::Varchar, "distribution_type"),
+            (DataType::Int32, "parallelism"),
+        ],
+        RW_ACTORS_TABLE_NAME => &[
+            (DataType::Int32, "actor_id"),
+            (DataType::Int32, "fragment_id"),
+            (DataType::Int32, "table_id"),
+            (DataType::Int32, "worker_node_id"),
+            (DataType::Varchar, "state"),
+        ],
+        RW_ACTOR_STATS_TABLE_NAME => &[
+            (DataType::Int32, "actor_id"),
+            (DataType::Int32, "worker_node_id"),
+            (DataType::Int64, "row_count"),
+            (DataType::Int64, "output_blocking_ms"),
+            (DataType::Int64, "latest_epoch"),
+            (DataType::Int64, "epoch_lag_ms"),
+        ],
+        _ => return None,
+    };
+    Some(
+        columns
+            .iter()
+            .map(|(data_type, name)| Field::with_name(data_type.clone(), *name))
+            .collect(),
+    )
+}
+
+/// The actor statistics reported by a compute node, with the id of the node.
+#[derive(Debug)]
+pub struct WorkerActorStats {
+    pub worker_node_id: u32,
+    pub actor_stats: Vec<risingwave_pb::stream_service::ActorStats>,
+}
+
+/// The streaming jobs and their runtime statistics read from meta and compute nodes, from which
+/// the rows of the system tables in `rw_catalog` are generated.
+#[derive(Debug, Default)]
+pub struct SystemCatalogSnapshot {
+    table_fragments: Vec<TableFragments>,
+    worker_actor_stats: Vec<WorkerActorStats>,
+}
+
+impl SystemCatalogSnapshot {
+    pub fn new(
+        table_fragments: Vec<TableFragments>,
+        worker_actor_stats: Vec<WorkerActorStats>,
+    ) -> Self {
+        Self {
+            table_fragments,
+            worker_actor_stats,
+        }
+    }
+
+    /// Lists the fragments from meta, and collects the actor statistics from every compute node.
+    pub async fn read(session: &SessionImpl) -> Result<Self> {
+        let table_fragments = session.env().meta_client().list_table_fragments().await?;
+        let worker_nodes = session.env().worker_node_manager().list_worker_nodes();
+        let worker_actor_stats = try_join_all(worker_nodes.into_iter().map(|node| async move {
+            let client = ComputeClient::new(node.host.as_ref().unwrap().into()).await?;
+            Ok::<_, RwError>(WorkerActorStats {
+                worker_node_id: node.id,
+                actor_stats: client.get_actor_stats().await?,
+            })
+        }))
+        .await?;
+        Ok(Self::new(table_fragments, worker_actor_stats))
+    }
+
+    /// Generates the rows of the system table `table_name`, whose columns are given by
+    /// [`system_table_columns`].
+    pub fn rows(&self, table_name: &str) -> Vec<Vec<Datum>> {
+        match table_name {
+            RW_FRAGMENTS_TABLE_NAME => self.fragment_rows(),
+            RW_ACTORS_TABLE_NAME => self.actor_rows(),
+            RW_ACTOR_STATS_TABLE_NAME => self.actor_stats_rows(),
+            _ => vec![],
+        }
+    }
+
+    fn fragment_rows(&self) -> Vec<Vec<Datum>> {
+        self.table_fragments
+            .iter()
+            .flat_map(|table_fragments| {
+                table_fragments
+                    .fragments
+                    .values()
+                    .sorted_by_key(|fragment| fragment.fragment_id)
+                    .map(|fragment| {
+                        let fragment_type = FragmentType::from_i32(fragment.fragment_type)
+                            .map_or("UNKNOWN".to_string(), |t| format!("{:?}", t).to_uppercase());
+                        let distribution_type =
+                            FragmentDistributionType::from_i32(fragment.distribution_type)
+                                .map_or("UNKNOWN".to_string(), |t| {
+                                    format!("{:?}", t).to_uppercase()
+                                });
+                        vec![
+                            Some(ScalarImpl::Int32(fragment.fragment_id as i32)),
+                            Some(ScalarImpl::Int32(table_fragments.table_id as i32)),
+                            Some(ScalarImpl::Utf8(fragment_type)),
+                            Some(ScalarImpl::Utf8(distribution_type)),
+                            Some(ScalarImpl::Int32(fragment.actors.len() as i32)),
+                        ]
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn actor_rows(&self) -> Vec<Vec<Datum>> {
+        self.table_fragments
+            .iter()
+            .flat_map(|table_fragments| {
+                table_fragments
+                    .fragments
+                    .values()
+                    .flat_map(|fragment| fragment.actors.iter())
+                    .sorted_by_key(|actor| actor.actor_id)
+                    .map(|actor| {
+                        let status = table_fragments.actor_status.get(&actor.actor_id);
+                        let state = status
+                            .and_then(|status| ActorState::from_i32(status.state))
+                            .map(|state| format!("{:?}", state).to_uppercase());
+                        vec![
+                            Some(ScalarImpl::Int32(actor.actor_id as i32)),
+                            Some(ScalarImpl::Int32(actor.fragment_id as i32)),
+                            Some(ScalarImpl::Int32(table_fragments.table_id as i32)),
+                            status.map(|status| ScalarImpl::Int32(status.node_id as i32)),
+                            state.map(ScalarImpl::Utf8),
+                        ]
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn actor_stats_rows(&self) -> Vec<Vec<Datum>> {
+        let physical_now = Epoch::physical_now();
+        self.worker_actor_stats
+            .iter()
+            .flat_map(|worker| {
+                worker.actor_stats.iter().map(move |stats| {
+                    // An actor has no epoch before it dispatches the first barrier.
+                    let (latest_epoch, epoch_lag_ms) = if stats.latest_epoch == 0 {
+                        (None, None)
+                    } else {
+                        let physical_time = Epoch::from(stats.latest_epoch).physical_time();
+                        (
+                            Some(ScalarImpl::Int64(stats.latest_epoch as i64)),
+                            Some(ScalarImpl::Int64(
+                                physical_now.saturating_sub(physical_time) as i64,
+                            )),
+                        )
+                    };
+                    vec![
+                        Some(ScalarImpl::Int32(stats.actor_id as i32)),
+                        Some(ScalarImpl::Int32(worker.worker_node_id as i32)),
+                        Some(ScalarImpl::Int64(stats.row_count as i64)),
+                        Some(ScalarImpl::Int64(
+                            (stats.output_blocking_duration_ns / 1_000_000) as i64,
+                        )),
+                        latest_epoch,
+                        epoch_lag_ms,
+                    ]
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use risingwave_pb::meta::table_fragments::{ActorStatus, Fragment};
+    use risingwave_pb::stream_plan::StreamActor;
+    use risingwave_pb::stream_service::ActorStats;
+
+    use super::*;
+
+    #[test]
+    fn test_system_table_rows() {
+        let actors = (1..=2)
+            .map(|actor_id| StreamActor {
+                actor_id,
+                fragment_id: 10,
+                ..Default::default()
+            })
+            .collect_vec();
+        let table_fragments = TableFragments {
+            table_id: 100,
+            fragments: HashMap::from([(
+                10,
+                Fragment {
+                    fragment_id: 10,
+                    fragment_type: FragmentType::Sink as i32,
+                    distribution_type: FragmentDistributionType::Hash as i32,
+                    actors,
+                },
+            )]),
+            actor_status: HashMap::from([(
+                1,
+                ActorStatus {
+                    node_id: 7,
+                    state: ActorState::Running as i32,
+                },
+            )]),
+        };
+        let snapshot = SystemCatalogSnapshot::new(
+            vec![table_fragments],
+            vec![WorkerActorStats {
+                worker_node_id: 7,
+                actor_stats: vec![ActorStats {
+                    actor_id: 1,
+                    row_count: 42,
+                    output_blocking_duration_ns: 3_000_000,
+                    latest_epoch: 0,
+                }],
+            }],
+        );
+
+        assert_eq!(
+            snapshot.rows(RW_FRAGMENTS_TABLE_NAME),
+            vec![vec![
+                Some(ScalarImpl::Int32(10)),
+                Some(ScalarImpl::Int32(100)),
+                Some(ScalarImpl::Utf8("SINK".to_string())),
+                Some(ScalarImpl::Utf8("HASH".to_string())),
+                Some(ScalarImpl::Int32(2)),
+            ]]
+        );
+        assert_eq!(
+            snapshot.rows(RW_ACTORS_TABLE_NAME),
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Int32(10)),
+                    Some(ScalarImpl::Int32(100)),
+                    Some(ScalarImpl::Int32(7)),
+                    Some(ScalarImpl::Utf8("RUNNING".to_string())),
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Int32(10)),
+                    Some(ScalarImpl::Int32(100)),
+                    None,
+                    None,
+                ],
+            ]
+        );
+        assert_eq!(
+            snapshot.rows(RW_ACTOR_STATS_TABLE_NAME),
+            vec![vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Int32(7)),
+                Some(ScalarImpl::Int64(42)),
+                Some(ScalarImpl::Int64(3)),
+                None,
+                None,
+            ]]
+        );
+        for table_name in [
+            RW_FRAGMENTS_TABLE_NAME,
+            RW_ACTORS_TABLE_NAME,
+            RW_ACTOR_STATS_TABLE_NAME,
+        ] {
+            let columns = system_table_columns(table_name).unwrap();
+            assert!(snapshot
+                .rows(table_name)
+                .iter()
+                .all(|row| row.len() == columns.len()));
+        }
+        assert!(system_table_columns("rw_unknown").is_none());
+    }
+}
@@ -17,11 +17,12 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 use risingwave_common::catalog::{ColumnDesc, OrderedColumnDesc, TableDesc};
 use risingwave_common::util::sort_util::OrderType;
-use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+use risingwave_pb::catalog::table::{OptionalAssociatedSourceId, OptionalIndexOnId};
 use risingwave_pb::catalog::Table as ProstTable;
 use risingwave_pb::plan::OrderType as ProstOrderType;
 
 use super::column_catalog::ColumnCatalog;
+use super::table_statistics::TableStatistics;
 use super::{DatabaseId, SchemaId};
 use crate::catalog::TableId;
 
@@ -32,6 +33,10 @@ pub struct TableCatalog {
     pub name: String,
     pub columns: Vec<ColumnCatalog>,
     pub pk_desc: Vec<OrderedColumnDesc>,
+    /// Statistics collected by the last `ANALYZE`, if any.
+    pub stats: Option<TableStatistics>,
+    /// The table or materialized view this index is built on, if this is an index.
+    pub index_on_id: Option<TableId>,
 }
 
 impl TableCatalog {
@@ -56,6 +61,20 @@ impl TableCatalog {
         self.pk_desc.as_ref()
     }
 
+    /// Get a reference to the table catalog's statistics.
+    pub fn stats(&self) -> Option<&TableStatistics> {
+        self.stats.as_ref()
+    }
+
+    /// Get the table this index is built on, or `None` if it's not an index.
+    pub fn index_on_id(&self) -> Option<TableId> {
+        self.index_on_id
+    }
+
+    pub fn is_index(&self) -> bool {
+        self.index_on_id.is_some()
+    }
+
     /// Get a [`TableDesc`] of the table.
     pub fn table_desc(&self) -> TableDesc {
         TableDesc {
@@ -94,6 +113,10 @@ impl TableCatalog {
             optional_associated_source_id: self
                 .associated_source_id
                 .map(|source_id| OptionalAssociatedSourceId::AssociatedSourceId(source_id.into())),
+            stats: self.stats.as_ref().map(TableStatistics::to_prost),
+            optional_index_on_id: self
+                .index_on_id
+                .map(|table_id| OptionalIndexOnId::IndexOnId(table_id.table_id)),
         }
     }
 }
@@ -104,7 +127,11 @@ impl From<ProstTable> for TableCatalog {
         let associated_source_id = tb.optional_associated_source_id.map(|id| match id {
             OptionalAssociatedSourceId::AssociatedSourceId(id) => id,
         });
+        let index_on_id = tb.optional_index_on_id.map(|id| match id {
+            OptionalIndexOnId::IndexOnId(id) => id,
+        });
         let name = tb.name.clone();
+        let stats = tb.stats.as_ref().map(TableStatistics::from);
         let mut col_names = HashSet::new();
         let mut col_descs: HashMap<i32, ColumnDesc> = HashMap::new();
         let columns: Vec<ColumnCatalog> = tb.columns.into_iter().map(ColumnCatalog::from).collect();
@@ -139,6 +166,8 @@ impl From<ProstTable> for TableCatalog {
             name,
             pk_desc,
             columns,
+            stats,
+            index_on_id: index_on_id.map(Into::into),
         }
     }
 }
@@ -200,6 +229,8 @@ mod tests {
             dependent_relations: vec![],
             optional_associated_source_id: OptionalAssociatedSourceId::AssociatedSourceId(233)
                 .into(),
+            stats: None,
+            optional_index_on_id: None,
         }
         .into();
 
@@ -242,7 +273,9 @@ mod tests {
                 pk_desc: vec![OrderedColumnDesc {
                     column_desc: row_id_column_desc(),
                     order: OrderType::Ascending
-                }]
+                }],
+                stats: None,
+                index_on_id: None,
             }
         );
     }
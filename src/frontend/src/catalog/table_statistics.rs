@@ -0,0 +1,244 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use num_traits::ToPrimitive;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_pb::catalog::{
+    ColumnStatistics as ProstColumnStatistics, TableStatistics as ProstTableStatistics,
+};
+
+use super::ColumnId;
+
+/// Number of buckets in the histogram of a column. Every bound is computed by an
+/// `approx_percentile` aggregate of its own, so this is kept small.
+const HISTOGRAM_BUCKETS: usize = 20;
+
+/// Statistics of a table collected by `ANALYZE`, used by the optimizer to estimate cardinalities.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableStatistics {
+    pub row_count: u64,
+    pub columns: HashMap<ColumnId, ColumnStatistics>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnStatistics {
+    pub null_count: u64,
+    /// Estimated number of distinct non-null values.
+    pub ndv: u64,
+    /// Ascending bounds of an equi-depth histogram over the non-null values, i.e. every two
+    /// adjacent bounds enclose the same number of values. Empty for non-numeric columns.
+    pub histogram_bounds: Vec<f64>,
+}
+
+impl TableStatistics {
+    /// Get the statistics of a column, if it has been analyzed.
+    pub fn column(&self, column_id: ColumnId) -> Option<&ColumnStatistics> {
+        self.columns.get(&column_id)
+    }
+
+    pub fn to_prost(&self) -> ProstTableStatistics {
+        ProstTableStatistics {
+            row_count: self.row_count,
+            columns: self
+                .columns
+                .iter()
+                .map(|(column_id, stats)| ProstColumnStatistics {
+                    column_id: column_id.get_id(),
+                    null_count: stats.null_count,
+                    ndv: stats.ndv,
+                    histogram_bounds: stats.histogram_bounds.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ColumnStatistics {
+    /// Builds the statistics of a column from the results of the aggregates returned by
+    /// [`column_statistics_aggs`], given the number of rows in the table.
+    pub fn from_agg_results(row_count: u64, results: &[Datum]) -> Result<Self> {
+        let as_u64 = |datum: &Datum| match datum {
+            Some(ScalarImpl::Int64(v)) => Ok(*v as u64),
+            other => Err(ErrorCode::InternalError(format!(
+                "expect a count from ANALYZE, got {:?}",
+                other
+            ))),
+        };
+        let (non_null_count, ndv, percentiles) = match results {
+            [count, ndv, percentiles @ ..] => (as_u64(count)?, as_u64(ndv)?, percentiles),
+            _ => {
+                return Err(ErrorCode::InternalError(format!(
+                    "expect the results of column_statistics_aggs, got {:?}",
+                    results
+                ))
+                .into())
+            }
+        };
+        // Percentiles are null if all values are null.
+        let histogram_bounds = percentiles
+            .iter()
+            .filter_map(|datum| datum.as_ref().and_then(scalar_to_f64))
+            .collect();
+        Ok(Self {
+            null_count: row_count.saturating_sub(non_null_count),
+            ndv,
+            histogram_bounds,
+        })
+    }
+
+    /// Fraction of the non-null values which are less than `value`, interpolated linearly within
+    /// the histogram bucket containing it. Returns `None` without a histogram.
+    pub fn fraction_below(&self, value: f64) -> Option<f64> {
+        let bounds = &self.histogram_bounds;
+        let (first, last) = (*bounds.first()?, *bounds.last()?);
+        if value <= first {
+            return Some(0.0);
+        }
+        if value > last {
+            return Some(1.0);
+        }
+        if bounds.len() == 1 {
+            return Some(0.5);
+        }
+        let buckets = (bounds.len() - 1) as f64;
+        // the first bucket whose upper bound is not less than `value`
+        let bucket = bounds[1..].partition_point(|bound| *bound < value);
+        let (lower, upper) = (bounds[bucket], bounds[bucket + 1]);
+        let within = if upper > lower {
+            (value - lower) / (upper - lower)
+        } else {
+            0.5
+        };
+        Some((bucket as f64 + within) / buckets)
+    }
+}
+
+/// Returns the aggregate calls over `column` that `ANALYZE` pushes down to the compute nodes to
+/// collect the statistics of the column, so that only their results are sent back. Their values
+/// are turned into [`ColumnStatistics`] by [`ColumnStatistics::from_agg_results`].
+///
+/// The aggregates are `count`, `approx_count_distinct` and, for numeric columns, one
+/// `approx_percentile` for every histogram bound. All of them keep bounded states.
+pub fn column_statistics_aggs(column: &str, data_type: &DataType) -> Vec<String> {
+    let mut aggs = vec![
+        format!("count({})", column),
+        format!("approx_count_distinct({})", column),
+    ];
+    if data_type.is_numeric() {
+        aggs.extend((0..=HISTOGRAM_BUCKETS).map(|i| {
+            format!(
+                "approx_percentile({}, {})",
+                column,
+                i as f64 / HISTOGRAM_BUCKETS as f64
+            )
+        }));
+    }
+    aggs
+}
+
+/// The value of a numeric scalar as `f64`, which is what histograms are built on. Returns `None`
+/// for the other types.
+pub fn scalar_to_f64(scalar: &ScalarImpl) -> Option<f64> {
+    match scalar {
+        ScalarImpl::Int16(v) => Some(*v as f64),
+        ScalarImpl::Int32(v) => Some(*v as f64),
+        ScalarImpl::Int64(v) => Some(*v as f64),
+        ScalarImpl::Float32(v) => Some(v.0 as f64),
+        ScalarImpl::Float64(v) => Some(v.0),
+        ScalarImpl::Decimal(v) => v.to_f64(),
+        _ => None,
+    }
+}
+
+impl From<&ProstTableStatistics> for TableStatistics {
+    fn from(stats: &ProstTableStatistics) -> Self {
+        Self {
+            row_count: stats.row_count,
+            columns: stats
+                .columns
+                .iter()
+                .map(|column| {
+                    (
+                        ColumnId::new(column.column_id),
+                        ColumnStatistics {
+                            null_count: column.null_count,
+                            ndv: column.ndv,
+                            histogram_bounds: column.histogram_bounds.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction_below() {
+        let stats = ColumnStatistics {
+            null_count: 0,
+            ndv: 100,
+            histogram_bounds: vec![0.0, 10.0, 20.0, 100.0],
+        };
+        assert_eq!(stats.fraction_below(-1.0), Some(0.0));
+        assert_eq!(stats.fraction_below(5.0), Some(0.5 / 3.0));
+        assert_eq!(stats.fraction_below(20.0), Some(2.0 / 3.0));
+        assert_eq!(stats.fraction_below(60.0), Some(2.5 / 3.0));
+        assert_eq!(stats.fraction_below(101.0), Some(1.0));
+        assert_eq!(ColumnStatistics::default().fraction_below(1.0), None);
+    }
+
+    #[test]
+    fn test_column_statistics_from_agg_results() {
+        let aggs = column_statistics_aggs("\"v\"", &DataType::Int32);
+        assert_eq!(aggs.len(), 2 + HISTOGRAM_BUCKETS + 1);
+        assert_eq!(aggs[0], "count(\"v\")");
+        assert_eq!(aggs[2], "approx_percentile(\"v\", 0)");
+        assert_eq!(aggs.last().unwrap(), "approx_percentile(\"v\", 1)");
+        assert_eq!(column_statistics_aggs("s", &DataType::Varchar).len(), 2);
+
+        let results = [
+            Some(ScalarImpl::Int64(4)),
+            Some(ScalarImpl::Int64(3)),
+            Some(ScalarImpl::Float64(1.0.into())),
+            Some(ScalarImpl::Float64(3.0.into())),
+        ];
+        assert_eq!(
+            ColumnStatistics::from_agg_results(6, &results).unwrap(),
+            ColumnStatistics {
+                null_count: 2,
+                ndv: 3,
+                histogram_bounds: vec![1.0, 3.0],
+            }
+        );
+
+        // All values are null.
+        let results = [Some(ScalarImpl::Int64(0)), Some(ScalarImpl::Int64(0)), None];
+        assert_eq!(
+            ColumnStatistics::from_agg_results(2, &results).unwrap(),
+            ColumnStatistics {
+                null_count: 2,
+                ndv: 0,
+                histogram_bounds: vec![],
+            }
+        );
+        assert!(ColumnStatistics::from_agg_results(2, &results[..1]).is_err());
+    }
+}
@@ -24,6 +24,7 @@ pub struct AggCall {
     agg_kind: AggKind,
     return_type: DataType,
     inputs: Vec<ExprImpl>,
+    distinct: bool,
 }
 
 impl std::fmt::Debug for AggCall {
@@ -33,9 +34,15 @@ impl std::fmt::Debug for AggCall {
                 .field("agg_kind", &self.agg_kind)
                 .field("return_type", &self.return_type)
                 .field("inputs", &self.inputs)
+                .field("distinct", &self.distinct)
                 .finish()
         } else {
-            let mut builder = f.debug_tuple(&format!("{}", self.agg_kind));
+            let name = if self.distinct {
+                format!("{} distinct", self.agg_kind)
+            } else {
+                format!("{}", self.agg_kind)
+            };
+            let mut builder = f.debug_tuple(&name);
             self.inputs.iter().for_each(|child| {
                 builder.field(child);
             });
@@ -70,6 +77,27 @@ impl AggCall {
                 _ => return None,
             },
             (AggKind::Count, _) => DataType::Int64,
+            (AggKind::BoolAnd | AggKind::BoolOr, [DataType::Boolean]) => DataType::Boolean,
+            (AggKind::BitAnd | AggKind::BitOr, [input]) => match input {
+                DataType::Int16 | DataType::Int32 | DataType::Int64 => input.clone(),
+                _ => return None,
+            },
+            (
+                AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp,
+                [input],
+            ) => match input {
+                DataType::Int16 | DataType::Int32 | DataType::Int64 | DataType::Decimal => {
+                    DataType::Decimal
+                }
+                DataType::Float32 | DataType::Float64 => DataType::Float64,
+                _ => return None,
+            },
+            (AggKind::ApproxCountDistinct, [_]) => DataType::Int64,
+            (AggKind::ArrayAgg, [input]) => DataType::List {
+                datatype: Box::new(input.clone()),
+            },
+            (AggKind::ApproxPercentile(_), [input]) if input.is_numeric() => DataType::Float64,
+            (AggKind::ApproxPercentile(_), _) => return None,
             (other_kind, other_inputs) => {
                 todo!(
                     "Unsupported aggregate function: {:?} with {} inputs",
@@ -83,7 +111,7 @@ impl AggCall {
 
     /// Returns error if the function name matches with an existing function
     /// but with illegal arguments.
-    pub fn new(agg_kind: AggKind, inputs: Vec<ExprImpl>) -> Result<Self> {
+    pub fn new(agg_kind: AggKind, inputs: Vec<ExprImpl>, distinct: bool) -> Result<Self> {
         // TODO(TaoWu): Add arguments validator.
         let data_types = inputs.iter().map(ExprImpl::return_type).collect_vec();
         let return_type = Self::infer_return_type(&agg_kind, &data_types).ok_or_else(|| {
@@ -97,11 +125,12 @@ impl AggCall {
             agg_kind,
             return_type,
             inputs,
+            distinct,
         })
     }
 
-    pub fn decompose(self) -> (AggKind, Vec<ExprImpl>) {
-        (self.agg_kind, self.inputs)
+    pub fn decompose(self) -> (AggKind, Vec<ExprImpl>, bool) {
+        (self.agg_kind, self.inputs, self.distinct)
     }
 
     pub fn agg_kind(&self) -> AggKind {
@@ -112,6 +141,11 @@ impl AggCall {
     pub fn inputs(&self) -> &[ExprImpl] {
         self.inputs.as_ref()
     }
+
+    /// Whether the agg call only aggregates distinct values of its inputs.
+    pub fn distinct(&self) -> bool {
+        self.distinct
+    }
 }
 impl Expr for AggCall {
     fn return_type(&self) -> DataType {
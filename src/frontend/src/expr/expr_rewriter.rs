@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery};
+use super::{
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
+    WindowFunction,
+};
 
 /// By default, `ExprRewriter` simply traverses the expression tree and leaves nodes unchanged.
 /// Implementations can override a subset of methods and perform transformation on some particular
@@ -26,6 +29,7 @@ pub trait ExprRewriter {
             ExprImpl::AggCall(inner) => self.rewrite_agg_call(*inner),
             ExprImpl::Subquery(inner) => self.rewrite_subquery(*inner),
             ExprImpl::CorrelatedInputRef(inner) => self.rewrite_correlated_input_ref(*inner),
+            ExprImpl::WindowFunction(inner) => self.rewrite_window_function(*inner),
         }
     }
     fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
@@ -37,12 +41,28 @@ pub trait ExprRewriter {
         FunctionCall::new_with_return_type(func_type, inputs, ret).into()
     }
     fn rewrite_agg_call(&mut self, agg_call: AggCall) -> ExprImpl {
-        let (func_type, inputs) = agg_call.decompose();
+        let (func_type, inputs, distinct) = agg_call.decompose();
         let inputs = inputs
             .into_iter()
             .map(|expr| self.rewrite_expr(expr))
             .collect();
-        AggCall::new(func_type, inputs).unwrap().into()
+        AggCall::new(func_type, inputs, distinct).unwrap().into()
+    }
+    fn rewrite_window_function(&mut self, window_function: WindowFunction) -> ExprImpl {
+        let WindowFunction {
+            function_type,
+            partition_by,
+            order_by,
+        } = window_function;
+        let partition_by = partition_by
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        let order_by = order_by
+            .into_iter()
+            .map(|(expr, direct)| (self.rewrite_expr(expr), direct))
+            .collect();
+        WindowFunction::new(function_type, partition_by, order_by).into()
     }
     fn rewrite_literal(&mut self, literal: Literal) -> ExprImpl {
         literal.into()
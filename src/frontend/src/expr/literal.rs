@@ -33,9 +33,10 @@ impl std::fmt::Debug for Literal {
         } else {
             match &self.data {
                 None => write!(f, "null"),
-                // Add single quotation marks for string and interval literals
+                // Add single quotation marks for string, interval and jsonb literals
                 Some(ScalarImpl::Utf8(v)) => write!(f, "'{}'", v),
                 Some(ScalarImpl::Interval(v)) => write!(f, "'{}'", v),
+                Some(ScalarImpl::Jsonb(v)) => write!(f, "'{}'", v),
                 Some(v) => write!(f, "{}", v),
             }?;
             write!(f, ":{:?}", self.data_type)
@@ -94,6 +95,7 @@ fn literal_to_protobuf(d: &Datum) -> Option<RexNode> {
         ScalarImpl::NaiveTime(_) => todo!(),
         ScalarImpl::Struct(_) => todo!(),
         ScalarImpl::List(_) => todo!(),
+        ScalarImpl::Jsonb(v) => v.to_string().into_bytes(),
     };
     Some(RexNode::Constant(ConstantValue { body }))
 }
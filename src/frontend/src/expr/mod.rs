@@ -26,9 +26,11 @@ mod function_call;
 mod input_ref;
 mod literal;
 mod subquery;
+mod window_function;
 
 mod expr_rewriter;
 mod expr_visitor;
+mod session_timezone;
 mod type_inference;
 mod utils;
 
@@ -38,11 +40,13 @@ pub use function_call::FunctionCall;
 pub use input_ref::{as_alias_display, input_ref_to_column_indices, InputRef, InputRefDisplay};
 pub use literal::Literal;
 pub use subquery::{Subquery, SubqueryKind};
+pub use window_function::{WindowFunction, WindowFunctionType};
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
 
 pub use expr_rewriter::ExprRewriter;
 pub use expr_visitor::ExprVisitor;
+pub use session_timezone::SessionTimezone;
 pub use type_inference::{cast_ok, infer_type, least_restrictive, CastContext};
 pub use utils::*;
 
@@ -64,6 +68,7 @@ pub enum ExprImpl {
     FunctionCall(Box<FunctionCall>),
     AggCall(Box<AggCall>),
     Subquery(Box<Subquery>),
+    WindowFunction(Box<WindowFunction>),
 }
 
 impl ExprImpl {
@@ -82,7 +87,7 @@ impl ExprImpl {
     /// A `count(*)` aggregate function.
     #[inline(always)]
     pub fn count_star() -> Self {
-        AggCall::new(AggKind::Count, vec![]).unwrap().into()
+        AggCall::new(AggKind::Count, vec![], false).unwrap().into()
     }
 
     /// Collect all `InputRef`s' indexes in the expression.
@@ -148,7 +153,7 @@ macro_rules! impl_has_variant {
     };
 }
 
-impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery}
+impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, WindowFunction}
 
 impl ExprImpl {
     // We need to traverse inside subqueries.
@@ -192,6 +197,7 @@ impl Expr for ExprImpl {
             ExprImpl::AggCall(expr) => expr.return_type(),
             ExprImpl::Subquery(expr) => expr.return_type(),
             ExprImpl::CorrelatedInputRef(expr) => expr.return_type(),
+            ExprImpl::WindowFunction(expr) => expr.return_type(),
         }
     }
 
@@ -203,6 +209,7 @@ impl Expr for ExprImpl {
             ExprImpl::AggCall(e) => e.to_protobuf(),
             ExprImpl::Subquery(e) => e.to_protobuf(),
             ExprImpl::CorrelatedInputRef(e) => e.to_protobuf(),
+            ExprImpl::WindowFunction(e) => e.to_protobuf(),
         }
     }
 }
@@ -237,6 +244,12 @@ impl From<Subquery> for ExprImpl {
     }
 }
 
+impl From<WindowFunction> for ExprImpl {
+    fn from(window_function: WindowFunction) -> Self {
+        ExprImpl::WindowFunction(Box::new(window_function))
+    }
+}
+
 impl From<CorrelatedInputRef> for ExprImpl {
     fn from(correlated_input_ref: CorrelatedInputRef) -> Self {
         ExprImpl::CorrelatedInputRef(Box::new(correlated_input_ref))
@@ -268,6 +281,7 @@ impl std::fmt::Debug for ExprImpl {
                 Self::CorrelatedInputRef(arg0) => {
                     f.debug_tuple("CorrelatedInputRef").field(arg0).finish()
                 }
+                Self::WindowFunction(arg0) => f.debug_tuple("WindowFunction").field(arg0).finish(),
             };
         }
         match self {
@@ -277,6 +291,7 @@ impl std::fmt::Debug for ExprImpl {
             Self::AggCall(x) => write!(f, "{:?}", x),
             Self::Subquery(x) => write!(f, "{:?}", x),
             Self::CorrelatedInputRef(x) => write!(f, "{:?}", x),
+            Self::WindowFunction(x) => write!(f, "{:?}", x),
         }
     }
 }
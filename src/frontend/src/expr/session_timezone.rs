@@ -0,0 +1,127 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use super::{Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, Literal};
+
+/// `SessionTimezone` rewrites the casts from and to `timestamp with time zone`, whose results
+/// depend on the session time zone, into functions taking the time zone as an argument, so that
+/// the backend doesn't need to know about the session.
+pub struct SessionTimezone {
+    timezone: String,
+}
+
+impl SessionTimezone {
+    pub fn new(timezone: String) -> Self {
+        Self { timezone }
+    }
+
+    /// The session time zone as a `varchar` literal.
+    pub fn timezone_literal(&self) -> ExprImpl {
+        Literal::new(
+            Some(ScalarImpl::Utf8(self.timezone.clone())),
+            DataType::Varchar,
+        )
+        .into()
+    }
+
+    /// `input AT TIME ZONE <session time zone>`, where `input` is a `timestamp` or a
+    /// `timestamptz`.
+    pub fn at_time_zone(&self, input: ExprImpl) -> FunctionCall {
+        let return_type = match input.return_type() {
+            DataType::Timestamp => DataType::Timestampz,
+            _ => DataType::Timestamp,
+        };
+        FunctionCall::new_with_return_type(
+            ExprType::AtTimeZone,
+            vec![input, self.timezone_literal()],
+            return_type,
+        )
+    }
+
+    fn rewrite_cast(&self, input: ExprImpl, target: DataType) -> Option<ExprImpl> {
+        let rewritten = match (input.return_type(), &target) {
+            (DataType::Varchar, DataType::Timestampz)
+            | (DataType::Timestampz, DataType::Varchar) => FunctionCall::new_with_return_type(
+                ExprType::CastWithTimeZone,
+                vec![input, self.timezone_literal()],
+                target,
+            )
+            .into(),
+            (DataType::Timestamp, DataType::Timestampz)
+            | (DataType::Timestampz, DataType::Timestamp) => self.at_time_zone(input).into(),
+            (DataType::Date, DataType::Timestampz) => self
+                .at_time_zone(input.cast_implicit(DataType::Timestamp).unwrap())
+                .into(),
+            (DataType::Timestampz, DataType::Date | DataType::Time) => {
+                FunctionCall::new_with_return_type(
+                    ExprType::Cast,
+                    vec![self.at_time_zone(input).into()],
+                    target,
+                )
+                .into()
+            }
+            _ => return None,
+        };
+        Some(rewritten)
+    }
+}
+
+impl ExprRewriter for SessionTimezone {
+    fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
+        let (func_type, inputs, ret) = func_call.decompose();
+        let inputs: Vec<ExprImpl> = inputs
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        if func_type == ExprType::Cast {
+            if let Some(rewritten) = self.rewrite_cast(inputs[0].clone(), ret.clone()) {
+                return rewritten;
+            }
+        }
+        FunctionCall::new_with_return_type(func_type, inputs, ret).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::InputRef;
+
+    #[test]
+    fn test_rewrite_timestamptz_casts() {
+        let mut rewriter = SessionTimezone::new("Asia/Shanghai".to_string());
+        let input: ExprImpl = InputRef::new(0, DataType::Timestampz).into();
+
+        let cast = input.clone().cast_assign(DataType::Varchar).unwrap();
+        assert_eq!(
+            format!("{:?}", rewriter.rewrite_expr(cast)),
+            "CastWithTimeZone($0, 'Asia/Shanghai':Varchar)"
+        );
+
+        let cast = input.cast_assign(DataType::Date).unwrap();
+        assert_eq!(
+            format!("{:?}", rewriter.rewrite_expr(cast)),
+            "AtTimeZone($0, 'Asia/Shanghai':Varchar)::Date"
+        );
+
+        let date: ExprImpl = InputRef::new(1, DataType::Date).into();
+        let cast = date.cast_implicit(DataType::Timestampz).unwrap();
+        assert_eq!(
+            format!("{:?}", rewriter.rewrite_expr(cast)),
+            "AtTimeZone($1::Timestamp, 'Asia/Shanghai':Varchar)"
+        );
+    }
+}
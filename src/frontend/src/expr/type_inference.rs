@@ -43,6 +43,7 @@ enum DataTypeName {
     Interval,
     Struct,
     List,
+    Jsonb,
 }
 
 fn name_of(ty: &DataType) -> DataTypeName {
@@ -62,6 +63,7 @@ fn name_of(ty: &DataType) -> DataTypeName {
         DataType::Interval => DataTypeName::Interval,
         DataType::Struct { .. } => DataTypeName::Struct,
         DataType::List { .. } => DataTypeName::List,
+        DataType::Jsonb => DataTypeName::Jsonb,
     }
 }
 
@@ -91,6 +93,7 @@ pub fn infer_type(func_type: ExprType, inputs_type: Vec<DataType>) -> Option<Dat
         DataTypeName::List => DataType::List {
             datatype: Box::new(DataType::Int32),
         },
+        DataTypeName::Jsonb => DataType::Jsonb,
     })
 }
 
@@ -252,6 +255,8 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
     for t in num_types.clone() {
         map.insert(FuncSign::new_unary(E::Neg, t), t);
     }
+    map.insert(FuncSign::new_unary(E::Neg, T::Interval), T::Interval);
+    map.insert(FuncSign::new_unary(E::Sqrt, T::Float64), T::Float64);
     build_binary_funcs(&mut map, &cmp_exprs, &num_types, &num_types, T::Boolean);
     build_binary_funcs(&mut map, &cmp_exprs, &str_types, &str_types, T::Boolean);
     build_binary_funcs(
@@ -270,6 +275,13 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Date, T::Timestamp],
         T::Boolean,
     );
+    build_binary_funcs(
+        &mut map,
+        &cmp_exprs,
+        &[T::Timestampz],
+        &[T::Timestampz],
+        T::Boolean,
+    );
     // Date/Timestamp/Interval arithmetic
     build_commutative_binary_funcs(
         &mut map,
@@ -292,6 +304,46 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Int16, T::Int32, T::Int64],
         T::Interval,
     );
+    build_binary_funcs(
+        &mut map,
+        &[E::Divide],
+        &[T::Interval],
+        &[T::Int16, T::Int32, T::Int64],
+        T::Interval,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::Add, E::Subtract],
+        &[T::Interval],
+        &[T::Interval],
+        T::Interval,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::Subtract],
+        &[T::Timestamp],
+        &[T::Timestamp],
+        T::Interval,
+    );
+    build_binary_funcs(&mut map, &[E::Subtract], &[T::Date], &[T::Date], T::Int32);
+    build_commutative_binary_funcs(&mut map, &[E::Add], &[T::Date], &[T::Int32], T::Date);
+    build_binary_funcs(&mut map, &[E::Subtract], &[T::Date], &[T::Int32], T::Date);
+    build_commutative_binary_funcs(&mut map, &[E::Add], &[T::Date], &[T::Time], T::Timestamp);
+    build_binary_funcs(
+        &mut map,
+        &[E::Subtract],
+        &[T::Time],
+        &[T::Time],
+        T::Interval,
+    );
+    build_commutative_binary_funcs(&mut map, &[E::Add], &[T::Time], &[T::Interval], T::Time);
+    build_binary_funcs(
+        &mut map,
+        &[E::Subtract],
+        &[T::Time],
+        &[T::Interval],
+        T::Time,
+    );
 
     build_binary_funcs(
         &mut map,
@@ -335,7 +387,44 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &str_types,
         T::Varchar,
     );
-    build_binary_funcs(&mut map, &[E::Like], &str_types, &str_types, T::Boolean);
+    build_binary_funcs(
+        &mut map,
+        &[E::Like, E::Ilike, E::SimilarTo, E::StartsWith],
+        &str_types,
+        &str_types,
+        T::Boolean,
+    );
+    build_unary_funcs(&mut map, &[E::Reverse, E::Md5], &str_types, T::Varchar);
+    build_binary_funcs(
+        &mut map,
+        &[E::Repeat, E::Lpad, E::Rpad],
+        &str_types,
+        &[T::Int32],
+        T::Varchar,
+    );
+    build_ternary_funcs(
+        &mut map,
+        &[E::Lpad, E::Rpad],
+        &str_types,
+        &[T::Int32],
+        &str_types,
+        T::Varchar,
+    );
+    build_ternary_funcs(
+        &mut map,
+        &[E::SplitPart],
+        &str_types,
+        &str_types,
+        &[T::Int32],
+        T::Varchar,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::ToChar],
+        &[T::Timestamp],
+        &str_types,
+        T::Varchar,
+    );
     build_ternary_funcs(
         &mut map,
         &[E::Replace],
@@ -358,6 +447,35 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Timestamp, T::Time, T::Date],
         T::Decimal,
     );
+    build_binary_funcs(
+        &mut map,
+        &[E::DateTrunc],
+        &[T::Varchar], // Time field, "year", "day", etc
+        &[T::Timestamp],
+        T::Timestamp,
+    );
+    build_unary_funcs(&mut map, &[E::ToTimestamp], &[T::Float64], T::Timestampz);
+    build_binary_funcs(
+        &mut map,
+        &[E::ToTimestamp1],
+        &str_types,
+        &str_types,
+        T::Timestamp,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::AtTimeZone],
+        &[T::Timestamp],
+        &str_types,
+        T::Timestampz,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::AtTimeZone],
+        &[T::Timestampz],
+        &str_types,
+        T::Timestamp,
+    );
     build_binary_funcs(
         &mut map,
         &[E::TumbleStart],
@@ -365,6 +483,28 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Interval],
         T::Timestamp,
     );
+    build_binary_funcs(
+        &mut map,
+        &[E::JsonbAccessInner],
+        &[T::Jsonb],
+        &[T::Varchar, T::Int32],
+        T::Jsonb,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::JsonbAccessStr],
+        &[T::Jsonb],
+        &[T::Varchar, T::Int32],
+        T::Varchar,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::JsonbContains],
+        &[T::Jsonb],
+        &[T::Jsonb],
+        T::Boolean,
+    );
+    build_unary_funcs(&mut map, &[E::JsonbTypeof], &[T::Jsonb], T::Varchar);
     map
 }
 
@@ -452,6 +592,7 @@ fn build_cast_map() -> HashMap<(DataTypeName, DataTypeName), CastContext> {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Jsonb,
     ] {
         m.insert((t, T::Varchar), CastContext::Assign);
         // Casting from string is explicit-only in PG.
@@ -565,6 +706,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_date_time_arithmetics() {
+        use DataType::*;
+        let table = vec![
+            (ExprType::Add, Date, Int32, Date),
+            (ExprType::Add, Int32, Date, Date),
+            (ExprType::Subtract, Date, Int32, Date),
+            (ExprType::Subtract, Date, Date, Int32),
+            (ExprType::Add, Date, Time, Timestamp),
+            (ExprType::Add, Time, Date, Timestamp),
+            (ExprType::Subtract, Timestamp, Timestamp, Interval),
+            (ExprType::Subtract, Time, Time, Interval),
+            (ExprType::Add, Time, Interval, Time),
+            (ExprType::Add, Interval, Time, Time),
+            (ExprType::Subtract, Time, Interval, Time),
+            (ExprType::Add, Interval, Interval, Interval),
+            (ExprType::Subtract, Interval, Interval, Interval),
+            (ExprType::Divide, Interval, Int32, Interval),
+        ];
+        for (expr, t1, t2, tr) in table {
+            test_simple_infer_type(expr, vec![t1, t2], tr);
+        }
+        test_infer_type_not_exist(ExprType::Add, vec![Time, Time]);
+    }
+
     #[test]
     fn test_bool_num_not_exist() {
         let exprs = vec![
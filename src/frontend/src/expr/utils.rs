@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use fixedbitset::FixedBitSet;
-use risingwave_common::types::ScalarImpl;
+use risingwave_common::array::DataChunk;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_expr::expr::build_from_prost;
 use risingwave_pb::expr::expr_node::Type;
 
-use super::{ExprImpl, ExprRewriter, ExprVisitor, FunctionCall, InputRef};
+use super::{Expr, ExprImpl, ExprRewriter, ExprVisitor, FunctionCall, InputRef, Literal};
 use crate::expr::ExprType;
 
 fn split_expr_by(expr: ExprImpl, op: ExprType, rets: &mut Vec<ExprImpl>) {
@@ -146,6 +148,97 @@ fn boolean_constant_fold_or(constant_lhs: ExprImpl, rhs: ExprImpl) -> ExprImpl {
     }
 }
 
+/// Evaluate constant sub-expressions, e.g. `$0 > 2 + 3` will become `$0 > 5`.
+///
+/// A function call is folded when all of its inputs are literals, or when it returns null on any
+/// null input and one of its inputs is a null literal. Calls that fail to evaluate, e.g. `1 / 0`,
+/// are left as they are, so that the error is only raised if they are evaluated at runtime.
+pub fn fold_const(expr: ExprImpl) -> ExprImpl {
+    let mut rewriter = ConstEval {};
+    rewriter.rewrite_expr(expr)
+}
+
+struct ConstEval {}
+
+impl ExprRewriter for ConstEval {
+    fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
+        let (func_type, inputs, ret) = func_call.decompose();
+        let inputs: Vec<_> = inputs
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        let func_call = FunctionCall::new_with_return_type(func_type, inputs, ret);
+
+        if is_null_propagating(func_type) && func_call.inputs().iter().any(ExprImpl::is_null) {
+            return Literal::new(None, func_call.return_type()).into();
+        }
+        if is_volatile(func_type)
+            || !is_const_evaluable(&func_call.return_type())
+            || !func_call.inputs().iter().all(|input| match input {
+                ExprImpl::Literal(literal) => {
+                    literal.get_data().is_none() || is_const_evaluable(&literal.return_type())
+                }
+                _ => false,
+            })
+        {
+            return func_call.into();
+        }
+        match eval_const(&func_call) {
+            Some(literal) => literal.into(),
+            None => func_call.into(),
+        }
+    }
+}
+
+/// Evaluate a function call on literals with the expressions of compute nodes.
+fn eval_const(func_call: &FunctionCall) -> Option<Literal> {
+    let expr = build_from_prost(&func_call.to_protobuf()).ok()?;
+    let array = expr.eval(&DataChunk::new_dummy(1)).ok()?;
+    Some(Literal::new(array.datum_at(0), func_call.return_type()))
+}
+
+/// Functions whose result differs between calls with the same inputs, or that have side effects.
+fn is_volatile(func_type: ExprType) -> bool {
+    matches!(func_type, Type::Now | Type::PgSleep)
+}
+
+/// Functions which return null if any of their inputs is null.
+fn is_null_propagating(func_type: ExprType) -> bool {
+    matches!(
+        func_type,
+        Type::Add
+            | Type::Subtract
+            | Type::Multiply
+            | Type::Divide
+            | Type::Modulus
+            | Type::Neg
+            | Type::Equal
+            | Type::NotEqual
+            | Type::LessThan
+            | Type::LessThanOrEqual
+            | Type::GreaterThan
+            | Type::GreaterThanOrEqual
+            | Type::Cast
+    )
+}
+
+/// Types whose literals can be serialized, i.e. be shipped to compute nodes for evaluation.
+fn is_const_evaluable(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Boolean
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal
+            | DataType::Varchar
+            | DataType::Interval
+            | DataType::Timestampz
+    )
+}
+
 /// Expand [`Type::Not`] expressions.
 /// e.g. Not(A And B) will become (Not A) Or (Not B)
 pub fn push_down_not(expr: ExprImpl) -> ExprImpl {
@@ -279,8 +372,8 @@ mod tests {
     use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_pb::expr::expr_node::Type;
 
-    use super::{fold_boolean_constant, push_down_not};
-    use crate::expr::{ExprImpl, FunctionCall, InputRef};
+    use super::{fold_boolean_constant, fold_const, push_down_not};
+    use crate::expr::{Expr, ExprImpl, FunctionCall, InputRef, Literal};
 
     #[test]
     fn constant_boolean_folding_basic_and() {
@@ -465,4 +558,60 @@ mod tests {
         assert_eq!(rhs_type, Type::Not);
         assert!(rhs_input.as_input_ref().is_some());
     }
+
+    #[test]
+    fn constant_folding() {
+        // expr := $0 > (1 + 2)
+        let expr: ExprImpl = FunctionCall::new(
+            Type::GreaterThan,
+            vec![
+                InputRef::new(0, DataType::Int32).into(),
+                FunctionCall::new(
+                    Type::Add,
+                    vec![ExprImpl::literal_int(1), ExprImpl::literal_int(2)],
+                )
+                .unwrap()
+                .into(),
+            ],
+        )
+        .unwrap()
+        .into();
+
+        let res = fold_const(expr);
+        let (_, _, rhs) = res
+            .as_function_call()
+            .unwrap()
+            .clone()
+            .decompose_as_binary();
+        assert_eq!(
+            *rhs.as_literal().unwrap().get_data(),
+            Some(ScalarImpl::Int32(3))
+        );
+
+        // expr := $0 + null
+        let expr: ExprImpl = FunctionCall::new(
+            Type::Add,
+            vec![
+                InputRef::new(0, DataType::Int32).into(),
+                Literal::new(None, DataType::Int32).into(),
+            ],
+        )
+        .unwrap()
+        .into();
+
+        let res = fold_const(expr);
+        assert!(res.is_null());
+        assert_eq!(res.return_type(), DataType::Int32);
+
+        // expr := 1 / 0, which is kept to fail at runtime
+        let expr: ExprImpl = FunctionCall::new(
+            Type::Divide,
+            vec![ExprImpl::literal_int(1), ExprImpl::literal_int(0)],
+        )
+        .unwrap()
+        .into();
+
+        let res = fold_const(expr.clone());
+        assert_eq!(res, expr);
+    }
 }
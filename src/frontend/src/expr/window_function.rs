@@ -0,0 +1,109 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt;
+
+use risingwave_common::types::DataType;
+
+use super::{Expr, ExprImpl};
+use crate::optimizer::property::Direction;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WindowFunctionType {
+    RowNumber,
+}
+
+impl WindowFunctionType {
+    pub fn return_type(&self) -> DataType {
+        match self {
+            WindowFunctionType::RowNumber => DataType::Int64,
+        }
+    }
+}
+
+impl fmt::Display for WindowFunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunctionType::RowNumber => write!(f, "row_number"),
+        }
+    }
+}
+
+/// A window function call like `row_number() OVER (PARTITION BY a ORDER BY b DESC)`. It's only
+/// allowed in the select list, where it is planned into a
+/// [`LogicalOverAgg`](crate::optimizer::plan_node::LogicalOverAgg).
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct WindowFunction {
+    pub function_type: WindowFunctionType,
+    pub partition_by: Vec<ExprImpl>,
+    pub order_by: Vec<(ExprImpl, Direction)>,
+}
+
+impl std::fmt::Debug for WindowFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.debug_struct("WindowFunction")
+                .field("function_type", &self.function_type)
+                .field("partition_by", &self.partition_by)
+                .field("order_by", &self.order_by)
+                .finish()
+        } else {
+            write!(f, "{}() over (", self.function_type)?;
+            let mut delim = "";
+            if !self.partition_by.is_empty() {
+                delim = " ";
+                write!(f, "partition by {:?}", self.partition_by)?;
+            }
+            if !self.order_by.is_empty() {
+                write!(f, "{}order by [", delim)?;
+                for (i, (expr, direct)) in self.order_by.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?} {}", expr, direct)?;
+                }
+                write!(f, "]")?;
+            }
+            write!(f, ")")
+        }
+    }
+}
+
+impl WindowFunction {
+    pub fn new(
+        function_type: WindowFunctionType,
+        partition_by: Vec<ExprImpl>,
+        order_by: Vec<(ExprImpl, Direction)>,
+    ) -> Self {
+        WindowFunction {
+            function_type,
+            partition_by,
+            order_by,
+        }
+    }
+}
+
+impl Expr for WindowFunction {
+    fn return_type(&self) -> DataType {
+        self.function_type.return_type()
+    }
+
+    fn to_protobuf(&self) -> risingwave_pb::expr::ExprNode {
+        // Like `AggCall`, a window function is planned into an operator instead of being
+        // evaluated as an expression.
+        unreachable!(
+            "WindowFunction {:?} has not been rewritten to over aggregation operators",
+            self
+        )
+    }
+}
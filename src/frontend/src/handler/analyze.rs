@@ -0,0 +1,125 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use futures_async_stream::for_await;
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::array::Row;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::ScalarImpl;
+use risingwave_sqlparser::ast::{Ident, ObjectName};
+use risingwave_sqlparser::parser::Parser;
+
+use crate::catalog::table_statistics::{column_statistics_aggs, ColumnStatistics, TableStatistics};
+use crate::handler::query::distribute_execute;
+use crate::handler::util::pin_snapshot;
+use crate::session::OptimizerContext;
+
+/// Collects the statistics of a table or materialized view over all of its visible columns, and
+/// stores them in the catalog for the optimizer. The statistics are computed by aggregates with
+/// bounded states on the compute nodes, so only a single row is sent back.
+pub async fn handle_analyze(
+    context: OptimizerContext,
+    table_name: ObjectName,
+) -> Result<PgResponse> {
+    let session = context.session_ctx.clone();
+    let (schema_name, name) = session.resolve_table_name(table_name.clone())?;
+
+    let (table_id, columns) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let table = reader.get_table_by_name(session.database(), &schema_name, &name)?;
+        let columns = table
+            .columns()
+            .iter()
+            .filter(|c| !c.is_hidden())
+            .map(|c| {
+                let aggs = column_statistics_aggs(
+                    &Ident::with_quote('"', c.name()).to_string(),
+                    c.data_type(),
+                );
+                (c.column_id(), aggs)
+            })
+            .collect_vec();
+        (table.id(), columns)
+    };
+
+    let select_list = std::iter::once("count(*)".to_string())
+        .chain(columns.iter().flat_map(|(_, aggs)| aggs.iter().cloned()))
+        .join(", ");
+    let sql = format!("SELECT {} FROM {}", select_list, table_name);
+    let stmt = Parser::parse_sql(&sql)
+        .map_err(|e| ErrorCode::InternalError(format!("failed to parse {:?}: {}", sql, e)))?
+        .swap_remove(0);
+
+    let epoch = pin_snapshot(&session).await?;
+    let data_stream = match distribute_execute(context, stmt, epoch).await {
+        Ok((data_stream, ..)) => data_stream,
+        Err(e) => {
+            session.env().meta_client().unpin_snapshot(epoch).await?;
+            return Err(e);
+        }
+    };
+
+    let mut results = None;
+    #[for_await]
+    for chunk in data_stream {
+        let chunk = chunk?;
+        for row in chunk.rows() {
+            results = Some(Row::from(row).0);
+        }
+    }
+    let results = results
+        .ok_or_else(|| ErrorCode::InternalError("no result is returned by ANALYZE".to_string()))?;
+
+    let num_aggs = 1 + columns.iter().map(|(_, aggs)| aggs.len()).sum::<usize>();
+    if results.len() != num_aggs {
+        return Err(ErrorCode::InternalError(format!(
+            "expect {} results from ANALYZE, got {}",
+            num_aggs,
+            results.len()
+        ))
+        .into());
+    }
+
+    let row_count = match &results[0] {
+        Some(ScalarImpl::Int64(count)) => *count as u64,
+        other => {
+            return Err(
+                ErrorCode::InternalError(format!("expect a row count, got {:?}", other)).into(),
+            )
+        }
+    };
+    let mut offset = 1;
+    let mut column_stats = HashMap::new();
+    for (column_id, aggs) in columns {
+        let stats =
+            ColumnStatistics::from_agg_results(row_count, &results[offset..offset + aggs.len()])?;
+        offset += aggs.len();
+        column_stats.insert(column_id, stats);
+    }
+    let stats = TableStatistics {
+        row_count,
+        columns: column_stats,
+    };
+
+    session
+        .env()
+        .catalog_writer()
+        .update_table_stats(table_id, stats.to_prost())
+        .await?;
+
+    Ok(PgResponse::empty_result(StatementType::ANALYZE))
+}
@@ -0,0 +1,71 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+/// Creates a database, together with the default schema in it.
+pub async fn handle_create_database(
+    context: OptimizerContext,
+    database_name: ObjectName,
+    if_not_exists: bool,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let database_name = Binder::resolve_unqualified_name(database_name)?;
+
+    {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        if catalog_reader.get_database_by_name(&database_name).is_ok() {
+            return if if_not_exists {
+                Ok(PgResponse::empty_result(StatementType::CREATE_DATABASE))
+            } else {
+                Err(CatalogError::Duplicated("database", database_name).into())
+            };
+        }
+    }
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.create_database(&database_name).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_DATABASE))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_database_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create database db").await.unwrap();
+        assert!(frontend.run_sql("create database db").await.is_err());
+        frontend
+            .run_sql("create database if not exists db")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader
+            .get_schema_by_name("db", DEFAULT_SCHEMA_NAME)
+            .is_ok());
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use fixedbitset::FixedBitSet;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::catalog::table::OptionalIndexOnId;
+use risingwave_pb::catalog::Table as ProstTable;
+use risingwave_sqlparser::ast::{Expr, ObjectName, OrderByExpr};
+
+use crate::optimizer::plan_node::LogicalScan;
+use crate::optimizer::property::{Direction, Distribution, FieldOrder, Order};
+use crate::optimizer::{PlanRef, PlanRoot};
+use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
+
+/// Generate the plan to build an index, which materializes all columns of the table ordered by
+/// the index columns. Returns `None` if the index exists and `if_not_exists` is given.
+pub fn gen_create_index_plan(
+    session: &SessionImpl,
+    context: OptimizerContextRef,
+    name: ObjectName,
+    table_name: ObjectName,
+    columns: Vec<OrderByExpr>,
+    if_not_exists: bool,
+) -> Result<Option<(PlanRef, ProstTable)>> {
+    let (schema_name, index_name) = session.resolve_new_table_name(name)?;
+    let (table_schema_name, table_name) = session.resolve_table_name(table_name)?;
+    let catalog_reader = session.env().catalog_reader().read_guard();
+
+    if if_not_exists
+        && catalog_reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .get_table_by_name(&index_name)
+            .is_some()
+    {
+        return Ok(None);
+    }
+    let (database_id, schema_id) = catalog_reader.check_relation_name_duplicated(
+        session.database(),
+        &schema_name,
+        &index_name,
+    )?;
+
+    let table =
+        catalog_reader.get_table_by_name(session.database(), &table_schema_name, &table_name)?;
+    if table.is_index() {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "\"{}\" is an index and cannot be indexed",
+            table_name
+        ))
+        .into());
+    }
+
+    // The hidden columns, e.g. the row id, are only scanned if they are in the primary key of
+    // the table, which will be appended to the columns when generating the stream plan.
+    let visible_col_idx = table
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_hidden)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let field_order = columns
+        .into_iter()
+        .map(|column| {
+            let name = match column.expr {
+                Expr::Identifier(ident) => ident.value,
+                expr => {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("index on expression {}", expr),
+                        None.into(),
+                    )
+                    .into())
+                }
+            };
+            let index = visible_col_idx
+                .iter()
+                .position(|i| table.columns()[*i].column_desc.name == name)
+                .ok_or_else(|| ErrorCode::ItemNotFound(format!("Invalid column: {}", name)))?;
+            let direct = match column.asc {
+                Some(false) => Direction::Desc,
+                _ => Direction::Asc,
+            };
+            Ok(FieldOrder { index, direct })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut out_fields = FixedBitSet::with_capacity(visible_col_idx.len());
+    out_fields.insert_range(..);
+    let scan = LogicalScan::new(
+        table_name,
+        visible_col_idx,
+        Rc::new(table.table_desc()),
+        None,
+        vec![],
+        None,
+        context,
+    );
+    let mut plan_root = PlanRoot::new(
+        scan.into(),
+        Distribution::any().clone(),
+        Order::new(field_order),
+        out_fields,
+    );
+    let materialize = plan_root.gen_create_mv_plan(index_name)?;
+    let mut index = materialize.table().to_prost(schema_id, database_id);
+    index.optional_index_on_id = Some(OptionalIndexOnId::IndexOnId(table.id().table_id));
+    let plan: PlanRef = materialize.into();
+
+    Ok(Some((plan, index)))
+}
+
+pub async fn handle_create_index(
+    context: OptimizerContext,
+    name: ObjectName,
+    table_name: ObjectName,
+    columns: Vec<OrderByExpr>,
+    unique: bool,
+    if_not_exists: bool,
+) -> Result<PgResponse> {
+    if unique {
+        return Err(ErrorCode::NotImplemented("CREATE UNIQUE INDEX".into(), None.into()).into());
+    }
+    let session = context.session_ctx.clone();
+
+    let (index, stream_plan) = {
+        match gen_create_index_plan(
+            &session,
+            context.into(),
+            name,
+            table_name,
+            columns,
+            if_not_exists,
+        )? {
+            Some((plan, index)) => (index, plan.to_stream_prost()),
+            None => return Ok(PgResponse::empty_result(StatementType::CREATE_INDEX)),
+        }
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer
+        .create_materialized_view(index, stream_plan)
+        .await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_INDEX))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use risingwave_common::util::sort_util::OrderType;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_index_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create index idx on t(v2 desc)")
+            .await
+            .unwrap();
+        // Creating it again is a no-op with `IF NOT EXISTS`.
+        frontend
+            .run_sql("create index if not exists idx on t(v1)")
+            .await
+            .unwrap();
+        assert!(frontend.run_sql("create index idx on t(v1)").await.is_err());
+        assert!(frontend
+            .run_sql("create index idx2 on t(v3)")
+            .await
+            .is_err());
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let table = catalog_reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap();
+        let index = catalog_reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "idx")
+            .unwrap();
+        assert_eq!(index.index_on_id(), Some(table.id()));
+        assert_eq!(index.columns().len(), table.columns().len());
+        // The index is ordered by the index columns, then the primary key of the table.
+        let pk = index
+            .pk_desc()
+            .iter()
+            .map(|c| (c.column_desc.name.as_str(), c.order))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pk,
+            vec![
+                ("v2", OrderType::Descending),
+                ("_row_id#0", OrderType::Ascending)
+            ]
+        );
+
+        let schema = catalog_reader
+            .get_schema_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME)
+            .unwrap();
+        assert_eq!(schema.iter_mv().count(), 0);
+        assert_eq!(schema.iter_index_on(table.id()).count(), 1);
+    }
+}
@@ -30,7 +30,7 @@ pub fn gen_create_mv_plan(
     query: Box<Query>,
     name: ObjectName,
 ) -> Result<(PlanRef, ProstTable)> {
-    let (schema_name, table_name) = Binder::resolve_table_name(name)?;
+    let (schema_name, table_name) = session.resolve_new_table_name(name)?;
     let (database_id, schema_id) = session
         .env()
         .catalog_reader()
@@ -41,7 +41,9 @@ pub fn gen_create_mv_plan(
         let mut binder = Binder::new(
             session.env().catalog_reader().read_guard(),
             session.database().to_string(),
-        );
+        )
+        .with_timezone(session.timezone())
+        .with_search_path(session.search_path());
         binder.bind_query(*query)?
     };
 
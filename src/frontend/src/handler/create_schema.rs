@@ -0,0 +1,118 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+/// Creates a schema in the session database.
+pub async fn handle_create_schema(
+    context: OptimizerContext,
+    schema_name: ObjectName,
+    if_not_exists: bool,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let schema_name = Binder::resolve_unqualified_name(schema_name)?;
+
+    let database_id = {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let database = catalog_reader.get_database_by_name(session.database())?;
+        if database.get_schema_by_name(&schema_name).is_some() {
+            return if if_not_exists {
+                Ok(PgResponse::empty_result(StatementType::CREATE_SCHEMA))
+            } else {
+                Err(CatalogError::Duplicated("schema", schema_name).into())
+            };
+        }
+        database.id()
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer
+        .create_schema(database_id, &schema_name)
+        .await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_SCHEMA))
+}
+
+#[cfg(test)]
+mod tests {
+    use pgwire::pg_server::Session;
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_schema_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create schema s").await.unwrap();
+        assert!(frontend.run_sql("create schema s").await.is_err());
+        frontend
+            .run_sql("create schema if not exists s")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader
+            .get_schema_by_name(DEFAULT_DATABASE_NAME, "s")
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_path() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        frontend.run_sql("create schema s").await.unwrap();
+        session
+            .clone()
+            .run_statement(&format!("set search_path = s, {}", DEFAULT_SCHEMA_NAME))
+            .await
+            .unwrap();
+
+        // New relations are created in the first schema of the `search_path`.
+        session
+            .clone()
+            .run_statement("create table t (v1 int)")
+            .await
+            .unwrap();
+        {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            assert!(catalog_reader
+                .get_table_by_name(DEFAULT_DATABASE_NAME, "s", "t")
+                .is_ok());
+        }
+
+        // Unqualified names are looked up in the schemas of the `search_path` in order.
+        session
+            .clone()
+            .run_statement("explain select * from t")
+            .await
+            .unwrap();
+        session
+            .clone()
+            .run_statement("explain select * from s.t")
+            .await
+            .unwrap();
+        assert!(session
+            .clone()
+            .run_statement(&format!("explain select * from {}.t", DEFAULT_SCHEMA_NAME))
+            .await
+            .is_err());
+    }
+}
@@ -16,18 +16,17 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
-use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::ErrorCode::{self, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::{Source as ProstSource, StreamSourceInfo};
 use risingwave_pb::plan::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
 use risingwave_source::ProtobufParser;
 use risingwave_sqlparser::ast::{
-    CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema, SqlOption, Value,
+    CreateSourceStatement, DataType, ObjectName, ProtobufSchema, SourceSchema, SqlOption, Value,
 };
 
 use super::create_table::{bind_sql_columns, gen_materialized_source_plan};
-use crate::binder::Binder;
 use crate::catalog::column_catalog::ColumnCatalog;
 use crate::session::{OptimizerContext, SessionImpl};
 
@@ -36,7 +35,7 @@ pub(crate) fn make_prost_source(
     name: ObjectName,
     source_info: Info,
 ) -> Result<ProstSource> {
-    let (schema_name, name) = Binder::resolve_table_name(name)?;
+    let (schema_name, name) = session.resolve_new_table_name(name)?;
 
     let (database_id, schema_id) = session
         .env()
@@ -105,6 +104,23 @@ pub async fn handle_create_source(
             columns: bind_sql_columns(stmt.columns)?,
             pk_column_ids: vec![0],
         },
+        SourceSchema::JsonRaw => {
+            // The whole payload goes to the only user column.
+            if !matches!(&stmt.columns[..], [column] if column.data_type == DataType::Jsonb) {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "ROW FORMAT JSON RAW expects exactly one column of type JSONB".to_string(),
+                )
+                .into());
+            }
+            StreamSourceInfo {
+                properties: handle_source_with_properties(stmt.with_properties.0)?,
+                row_format: RowFormatType::JsonRaw as i32,
+                row_schema_location: "".to_string(),
+                row_id_index: 0,
+                columns: bind_sql_columns(stmt.columns)?,
+                pk_column_ids: vec![0],
+            }
+        }
     };
 
     let session = context.session_ctx.clone();
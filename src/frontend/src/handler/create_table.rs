@@ -17,7 +17,7 @@ use std::rc::Rc;
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
-use risingwave_common::catalog::{ColumnDesc, ColumnId};
+use risingwave_common::catalog::ColumnDesc;
 use risingwave_common::error::Result;
 use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::{Source as ProstSource, Table as ProstTable, TableSourceInfo};
@@ -25,7 +25,7 @@ use risingwave_pb::plan::ColumnCatalog;
 use risingwave_sqlparser::ast::{ColumnDef, ObjectName};
 
 use super::create_source::make_prost_source;
-use crate::binder::expr::bind_data_type;
+use crate::binder::expr::bind_struct_field;
 use crate::catalog::{check_valid_column_name, row_id_column_desc};
 use crate::optimizer::plan_node::{LogicalSource, StreamSource};
 use crate::optimizer::property::{Distribution, Order};
@@ -39,16 +39,16 @@ pub fn bind_sql_columns(columns: Vec<ColumnDef>) -> Result<Vec<ColumnCatalog>> {
         let mut column_descs = Vec::with_capacity(columns.len() + 1);
         // Put the hidden row id column in the first column. This is used for PK.
         column_descs.push(row_id_column_desc());
-        // Then user columns.
-        for (i, column) in columns.into_iter().enumerate() {
+        // Then user columns. The fields of a struct column are also described by nested column
+        // descs, and each of them is assigned a column id.
+        for column in columns {
             check_valid_column_name(&column.name.value)?;
-            column_descs.push(ColumnDesc {
-                data_type: bind_data_type(&column.data_type)?,
-                column_id: ColumnId::new((i + 1) as i32),
-                name: column.name.value,
-                field_descs: vec![],
-                type_name: "".to_string(),
-            });
+            let field = bind_struct_field(column.name.value, &column.data_type)?;
+            column_descs.push(ColumnDesc::from(&field));
+        }
+        let mut column_id = 0;
+        for column_desc in &mut column_descs {
+            column_desc.generate_increment_id(&mut column_id);
         }
         column_descs
     };
@@ -192,4 +192,42 @@ mod tests {
 
         assert_eq!(columns, expected_columns);
     }
+
+    #[tokio::test]
+    async fn test_create_table_with_struct() {
+        let sql = "create table t (v1 int, v2 struct<a int, b struct<c varchar>>);";
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let table = catalog_reader
+            .read_guard()
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap()
+            .clone();
+
+        // Nested fields are described by column descs with their own column ids.
+        let columns = table
+            .columns()
+            .iter()
+            .flat_map(|c| c.column_desc.get_column_descs())
+            .map(|c| (c.name, (c.column_id.get_id(), c.data_type)))
+            .collect::<HashMap<String, (i32, DataType)>>();
+
+        let b_type = DataType::Struct {
+            fields: vec![DataType::Varchar].into(),
+        };
+        let expected_columns = maplit::hashmap! {
+            gen_row_id_column_name(0) => (0, DataType::Int64),
+            "v1".to_string() => (1, DataType::Int32),
+            "v2".to_string() => (2, DataType::Struct {
+                fields: vec![DataType::Int32, b_type.clone()].into(),
+            }),
+            "v2.a".to_string() => (3, DataType::Int32),
+            "v2.b".to_string() => (4, b_type),
+            "v2.b.c".to_string() => (5, DataType::Varchar),
+        };
+        assert_eq!(columns, expected_columns);
+    }
 }
@@ -21,7 +21,6 @@ use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::ObjectName;
 
-use crate::binder::Binder;
 use crate::session::OptimizerContext;
 
 /// Convert column descs to rows which conclude name and type
@@ -54,7 +53,7 @@ pub async fn handle_describe(
     table_name: ObjectName,
 ) -> Result<PgResponse> {
     let session = context.session_ctx;
-    let (schema_name, table_name) = Binder::resolve_table_name(table_name)?;
+    let (schema_name, table_name) = session.resolve_table_name(table_name)?;
 
     let catalog_reader = session.env().catalog_reader().read_guard();
 
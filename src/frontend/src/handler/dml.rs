@@ -13,13 +13,19 @@
 // limitations under the License.
 
 use futures_async_stream::for_await;
+use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_pb::plan::PlanNode as BatchPlanProst;
 use risingwave_sqlparser::ast::Statement;
 
 use super::query::IMPLICIT_FLUSH;
 use crate::binder::Binder;
-use crate::handler::util::{to_pg_field, to_pg_rows};
+use crate::catalog::system_catalog::SystemCatalogSnapshot;
+use crate::handler::util::{
+    pin_snapshot, read_system_catalog, session_timezone, to_pg_field, to_pg_rows,
+};
 use crate::planner::Planner;
 use crate::scheduler::{ExecutionContext, ExecutionContextRef};
 use crate::session::{OptimizerContext, SessionImpl};
@@ -27,24 +33,17 @@ use crate::session::{OptimizerContext, SessionImpl};
 pub async fn handle_dml(context: OptimizerContext, stmt: Statement) -> Result<PgResponse> {
     let stmt_type = to_statement_type(&stmt);
     let session = context.session_ctx.clone();
-
-    let bound = {
-        let mut binder = Binder::new(
-            session.env().catalog_reader().read_guard(),
-            session.database().to_string(),
-        );
-        binder.bind(stmt)?
-    };
-
-    let (plan, pg_descs) = {
-        // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
-        let plan = Planner::new(context.into())
-            .plan(bound)?
-            .gen_batch_query_plan();
-
-        let pg_descs = plan.schema().fields().iter().map(to_pg_field).collect();
-
-        (plan.to_batch_prost(), pg_descs)
+    let timezone = session_timezone(&session)?;
+
+    let system_catalog = read_system_catalog(&session, &stmt).await?;
+    // The snapshot is pinned before binding, so that `now()` is the time of the epoch read.
+    let epoch = pin_snapshot(&session).await?;
+    let (plan, pg_descs, column_types) = match plan_dml(context, stmt, epoch, system_catalog) {
+        Ok(result) => result,
+        Err(e) => {
+            session.env().meta_client().unpin_snapshot(epoch).await?;
+            return Err(e);
+        }
     };
 
     let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
@@ -53,10 +52,10 @@ pub async fn handle_dml(context: OptimizerContext, stmt: Statement) -> Result<Pg
     let mut rows = vec![];
     #[for_await]
     for chunk in query_manager
-        .schedule_single(execution_context, plan)
+        .schedule_single(execution_context, plan, epoch)
         .await?
     {
-        rows.extend(to_pg_rows(chunk?));
+        rows.extend(to_pg_rows(chunk?, &column_types, timezone)?);
     }
 
     let rows_count = match stmt_type {
@@ -82,6 +81,37 @@ pub async fn handle_dml(context: OptimizerContext, stmt: Statement) -> Result<Pg
     Ok(PgResponse::new(stmt_type, rows_count, rows, pg_descs))
 }
 
+fn plan_dml(
+    context: OptimizerContext,
+    stmt: Statement,
+    epoch: u64,
+    system_catalog: Option<SystemCatalogSnapshot>,
+) -> Result<(BatchPlanProst, Vec<PgFieldDescriptor>, Vec<DataType>)> {
+    let session = context.session_ctx.clone();
+    let bound = {
+        let mut binder = Binder::new(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+        )
+        .with_timezone(session.timezone())
+        .with_search_path(session.search_path())
+        .with_query_epoch(epoch);
+        if let Some(system_catalog) = system_catalog {
+            binder = binder.with_system_catalog(system_catalog);
+        }
+        binder.bind(stmt)?
+    };
+
+    let plan = Planner::new(context.into())
+        .plan(bound)?
+        .gen_batch_query_plan()?;
+
+    let pg_descs = plan.schema().fields().iter().map(to_pg_field).collect();
+    let column_types = plan.schema().data_types();
+
+    Ok((plan.to_batch_prost(), pg_descs, column_types))
+}
+
 async fn flush_for_write(session: &SessionImpl, stmt_type: StatementType) -> Result<()> {
     match stmt_type {
         StatementType::INSERT | StatementType::DELETE | StatementType::UPDATE => {
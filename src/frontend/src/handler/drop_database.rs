@@ -0,0 +1,79 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::binder::Binder;
+use crate::session::OptimizerContext;
+
+/// Drops a database, whose schemas are dropped with it. Meta refuses to drop a database that still
+/// contains relations.
+pub async fn handle_drop_database(
+    context: OptimizerContext,
+    database_name: ObjectName,
+    if_exists: bool,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let database_name = Binder::resolve_unqualified_name(database_name)?;
+    if database_name == session.database() {
+        return Err(RwError::from(ErrorCode::InvalidInputSyntax(
+            "cannot drop the currently open database".to_owned(),
+        )));
+    }
+
+    let database_id = {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        match catalog_reader.get_database_by_name(&database_name) {
+            Ok(database) => database.id(),
+            Err(_) if if_exists => {
+                return Ok(PgResponse::empty_result(StatementType::DROP_DATABASE))
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_database(database_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_DATABASE))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::DEFAULT_DATABASE_NAME;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_database_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create database db").await.unwrap();
+        assert!(frontend
+            .run_sql(format!("drop database {}", DEFAULT_DATABASE_NAME))
+            .await
+            .is_err());
+        frontend.run_sql("drop database db").await.unwrap();
+        assert!(frontend.run_sql("drop database db").await.is_err());
+        frontend
+            .run_sql("drop database if exists db")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader.get_database_by_name("db").is_err());
+    }
+}
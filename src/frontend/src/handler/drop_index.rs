@@ -0,0 +1,76 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::session::OptimizerContext;
+
+pub async fn handle_drop_index(
+    context: OptimizerContext,
+    index_name: ObjectName,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, index_name) = session.resolve_table_name(index_name)?;
+
+    let catalog_reader = session.env().catalog_reader();
+
+    let index_id = {
+        let reader = catalog_reader.read_guard();
+        let index = reader.get_table_by_name(session.database(), &schema_name, &index_name)?;
+        if !index.is_index() {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is not an index",
+                index_name
+            ))));
+        }
+        index.id()
+    };
+
+    // An index is maintained as a materialized view.
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_materialized_view(index_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_INDEX))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_index_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int)")
+            .await
+            .unwrap();
+        frontend.run_sql("create index idx on t(v1)").await.unwrap();
+        assert!(frontend
+            .run_sql("drop materialized view idx")
+            .await
+            .is_err());
+        assert!(frontend.run_sql("drop index t").await.is_err());
+        frontend.run_sql("drop index idx").await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "idx")
+            .is_err());
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::binder::Binder;
+use crate::session::OptimizerContext;
+
+/// Drops a schema of the session database. Meta refuses to drop a schema that still contains
+/// relations.
+pub async fn handle_drop_schema(
+    context: OptimizerContext,
+    schema_name: ObjectName,
+    if_exists: bool,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let schema_name = Binder::resolve_unqualified_name(schema_name)?;
+
+    let schema_id = {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        match catalog_reader.get_schema_by_name(session.database(), &schema_name) {
+            Ok(schema) => schema.id(),
+            Err(_) if if_exists => return Ok(PgResponse::empty_result(StatementType::DROP_SCHEMA)),
+            Err(e) => return Err(e),
+        }
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_schema(schema_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_SCHEMA))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::DEFAULT_DATABASE_NAME;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_schema_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create schema s").await.unwrap();
+        frontend.run_sql("drop schema s").await.unwrap();
+        assert!(frontend.run_sql("drop schema s").await.is_err());
+        frontend.run_sql("drop schema if exists s").await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader
+            .get_schema_by_name(DEFAULT_DATABASE_NAME, "s")
+            .is_err());
+    }
+}
@@ -15,20 +15,49 @@
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::Statement;
 
 use super::create_mv::gen_create_mv_plan;
 use super::create_table::gen_create_table_plan;
+use super::query::gen_distributed_query;
+use super::util::{pin_snapshot, read_system_catalog};
 use crate::binder::Binder;
 use crate::planner::Planner;
+use crate::scheduler::{ExecutionContext, ExecutionContextRef};
 use crate::session::OptimizerContext;
 
-pub(super) fn handle_explain(
+pub(super) async fn handle_explain(
     context: OptimizerContext,
     stmt: Statement,
     _verbose: bool,
+    analyze: bool,
+    distsql: bool,
 ) -> Result<PgResponse> {
+    let output = if analyze || distsql {
+        explain_distributed(context, stmt, analyze).await?
+    } else {
+        explain_plan(context, stmt)?
+    };
+
+    let rows = output
+        .lines()
+        .map(|s| Row::new(vec![Some(s.into())]))
+        .collect::<Vec<_>>();
+
+    Ok(PgResponse::new(
+        StatementType::EXPLAIN,
+        rows.len() as i32,
+        rows,
+        vec![PgFieldDescriptor::new(
+            "QUERY PLAN".to_owned(),
+            TypeOid::Varchar,
+        )],
+    ))
+}
+
+/// Explains the optimized plan of a statement.
+fn explain_plan(context: OptimizerContext, stmt: Statement) -> Result<String> {
     let session = context.session_ctx.clone();
     // bind, plan, optimize, and serialize here
     let mut planner = Planner::new(context.into());
@@ -51,28 +80,54 @@ pub(super) fn handle_explain(
                 let mut binder = Binder::new(
                     session.env().catalog_reader().read_guard(),
                     session.database().to_string(),
-                );
+                )
+                .with_timezone(session.timezone())
+                .with_search_path(session.search_path())
+                .for_explain();
                 binder.bind(stmt)?
             };
             let logical = planner.plan(bound)?;
-            logical.gen_batch_query_plan()
+            logical.gen_batch_query_plan()?
         }
     };
 
-    let output = plan.explain_to_string()?;
+    plan.explain_to_string()
+}
+
+/// Explains the stages a batch query is split into for distributed execution. With `analyze`, the
+/// query is run, and the runtime statistics of each operator are shown as well.
+async fn explain_distributed(
+    context: OptimizerContext,
+    stmt: Statement,
+    analyze: bool,
+) -> Result<String> {
+    if !matches!(stmt, Statement::Query(_)) {
+        return Err(ErrorCode::NotImplemented(
+            "EXPLAIN ANALYZE or DISTSQL of statements other than queries".to_string(),
+            None.into(),
+        )
+        .into());
+    }
 
-    let rows = output
-        .lines()
-        .map(|s| Row::new(vec![Some(s.into())]))
-        .collect::<Vec<_>>();
+    if !analyze {
+        let (query, ..) = gen_distributed_query(context, stmt, None, None)?;
+        return Ok(query.explain_to_string(None));
+    }
 
-    Ok(PgResponse::new(
-        StatementType::EXPLAIN,
-        rows.len() as i32,
-        rows,
-        vec![PgFieldDescriptor::new(
-            "QUERY PLAN".to_owned(),
-            TypeOid::Varchar,
-        )],
-    ))
+    let session = context.session_ctx.clone();
+    let system_catalog = read_system_catalog(&session, &stmt).await?;
+    let epoch = pin_snapshot(&session).await?;
+    let query = match gen_distributed_query(context, stmt, Some(epoch), system_catalog) {
+        Ok((query, ..)) => query,
+        Err(e) => {
+            session.env().meta_client().unpin_snapshot(epoch).await?;
+            return Err(e);
+        }
+    };
+    let query_manager = session.env().query_manager().clone();
+    let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
+    let (query, stats) = query_manager
+        .analyze(execution_context, query, epoch)
+        .await?;
+    Ok(query.explain_to_string(Some(&stats)))
 }
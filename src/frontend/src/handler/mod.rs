@@ -16,22 +16,29 @@ use std::sync::Arc;
 
 use pgwire::pg_response::PgResponse;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::{DropStatement, ObjectName, ObjectType, Statement};
+use risingwave_sqlparser::ast::{DropStatement, ObjectType, Statement};
 
 use crate::session::{OptimizerContext, SessionImpl};
 
+mod analyze;
+mod create_database;
+pub mod create_index;
 pub mod create_mv;
+mod create_schema;
 pub mod create_source;
 pub mod create_table;
 mod describe;
 pub mod dml;
+mod drop_database;
+pub mod drop_index;
 pub mod drop_mv;
+mod drop_schema;
 pub mod drop_table;
 mod explain;
 mod flush;
 #[allow(dead_code)]
 pub mod query;
-mod set;
+pub mod set;
 mod show;
 pub mod util;
 
@@ -39,8 +46,12 @@ pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result
     let context = OptimizerContext::new(session.clone());
     match stmt {
         Statement::Explain {
-            statement, verbose, ..
-        } => explain::handle_explain(context, *statement, verbose),
+            statement,
+            verbose,
+            analyze,
+            distsql,
+            ..
+        } => explain::handle_explain(context, *statement, verbose, analyze, distsql).await,
         Statement::CreateSource {
             is_materialized,
             stmt,
@@ -52,26 +63,39 @@ pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result
         // TODO: support complex sql for `show columns from <table>`
         Statement::ShowColumn { name } => describe::handle_describe(context, name).await,
         Statement::ShowObjects(show_object) => show::handle_show_object(context, show_object).await,
+        Statement::CreateDatabase {
+            db_name,
+            if_not_exists,
+            ..
+        } => create_database::handle_create_database(context, db_name, if_not_exists).await,
+        Statement::CreateSchema {
+            schema_name,
+            if_not_exists,
+        } => create_schema::handle_create_schema(context, schema_name, if_not_exists).await,
         Statement::Drop(DropStatement {
-            object_type, name, ..
-        }) => {
-            let name = ObjectName(vec![name]);
-            match object_type {
-                ObjectType::Table => drop_table::handle_drop_table(context, name).await,
-                ObjectType::MaterializedView => drop_mv::handle_drop_mv(context, name).await,
-                ObjectType::MaterializedSource => {
-                    // FIXME: We currently treat MATERIALIZE SOURCE as an alias TABLE, while
-                    // this assumption is not correct. DROP MATERIALIZE SOURCE should only drops
-                    // materialized sources.
-                    drop_table::handle_drop_table(context, name).await
-                }
-                _ => Err(ErrorCode::InvalidInputSyntax(format!(
-                    "DROP {} is unsupported",
-                    object_type
-                ))
-                .into()),
+            object_type,
+            name,
+            if_exists,
+            ..
+        }) => match object_type {
+            ObjectType::Database => {
+                drop_database::handle_drop_database(context, name, if_exists).await
             }
-        }
+            ObjectType::Schema => drop_schema::handle_drop_schema(context, name, if_exists).await,
+            ObjectType::Table => drop_table::handle_drop_table(context, name).await,
+            ObjectType::MaterializedView => drop_mv::handle_drop_mv(context, name).await,
+            ObjectType::Index => drop_index::handle_drop_index(context, name).await,
+            ObjectType::MaterializedSource => {
+                // FIXME: We currently treat MATERIALIZE SOURCE as an alias TABLE, while
+                // this assumption is not correct. DROP MATERIALIZE SOURCE should only drops
+                // materialized sources.
+                drop_table::handle_drop_table(context, name).await
+            }
+            _ => Err(
+                ErrorCode::InvalidInputSyntax(format!("DROP {} is unsupported", object_type))
+                    .into(),
+            ),
+        },
         Statement::Query(_) => query::handle_query(context, stmt).await,
         Statement::Insert { .. } | Statement::Delete { .. } => dml::handle_dml(context, stmt).await,
         Statement::CreateView {
@@ -81,7 +105,25 @@ pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result
             query,
             ..
         } => create_mv::handle_create_mv(context, name, query).await,
+        Statement::CreateIndex {
+            name,
+            table_name,
+            columns,
+            unique,
+            if_not_exists,
+        } => {
+            create_index::handle_create_index(
+                context,
+                name,
+                table_name,
+                columns,
+                unique,
+                if_not_exists,
+            )
+            .await
+        }
         Statement::Flush => flush::handle_flush(context).await,
+        Statement::Analyze { table_name } => analyze::handle_analyze(context, table_name).await,
         Statement::SetVariable {
             local: _,
             variable,
@@ -16,13 +16,17 @@ use futures_async_stream::for_await;
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::Statement;
 use tracing::info;
 
-use crate::binder::{Binder, BoundStatement};
-use crate::handler::util::{to_pg_field, to_pg_rows};
+use crate::binder::Binder;
+use crate::catalog::system_catalog::SystemCatalogSnapshot;
+use crate::handler::util::{
+    pin_snapshot, read_system_catalog, session_timezone, to_pg_field, to_pg_rows,
+};
 use crate::planner::Planner;
-use crate::scheduler::plan_fragmenter::BatchPlanFragmenter;
+use crate::scheduler::plan_fragmenter::{BatchPlanFragmenter, Query};
 use crate::scheduler::{DataChunkStream, ExecutionContext, ExecutionContextRef};
 use crate::session::{OptimizerContext, SessionImpl};
 
@@ -35,21 +39,23 @@ pub static IMPLICIT_FLUSH: &str = "RW_IMPLICIT_FLUSH";
 pub async fn handle_query(context: OptimizerContext, stmt: Statement) -> Result<PgResponse> {
     let stmt_type = to_statement_type(&stmt);
     let session = context.session_ctx.clone();
-
-    let bound = {
-        let mut binder = Binder::new(
-            session.env().catalog_reader().read_guard(),
-            session.database().to_string(),
-        );
-        binder.bind(stmt)?
+    let timezone = session_timezone(&session)?;
+
+    // The snapshot is pinned before binding, so that `now()` is the time of the epoch read.
+    let epoch = pin_snapshot(&session).await?;
+    let (data_stream, pg_descs, column_types) = match distribute_execute(context, stmt, epoch).await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            session.env().meta_client().unpin_snapshot(epoch).await?;
+            return Err(e);
+        }
     };
 
-    let (data_stream, pg_descs) = distribute_execute(context, bound).await?;
-
     let mut rows = vec![];
     #[for_await]
     for chunk in data_stream {
-        rows.extend(to_pg_rows(chunk?));
+        rows.extend(to_pg_rows(chunk?, &column_types, timezone)?);
     }
 
     let rows_count = match stmt_type {
@@ -98,39 +104,74 @@ fn to_statement_type(stmt: &Statement) -> StatementType {
     }
 }
 
-async fn distribute_execute(
+/// Binds, plans and splits a query into stages for distributed execution, reading the snapshot of
+/// `epoch` if given, or only to be explained otherwise. The system tables in `rw_catalog` are read
+/// from `system_catalog`.
+pub(super) fn gen_distributed_query(
     context: OptimizerContext,
-    stmt: BoundStatement,
-) -> Result<(impl DataChunkStream, Vec<PgFieldDescriptor>)> {
+    stmt: Statement,
+    epoch: Option<u64>,
+    system_catalog: Option<SystemCatalogSnapshot>,
+) -> Result<(Query, Vec<PgFieldDescriptor>, Vec<DataType>)> {
     let session = context.session_ctx.clone();
-    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
-    let (query, pg_descs) = {
-        let plan = Planner::new(context.into())
-            .plan(stmt)?
-            .gen_dist_batch_query_plan();
-
-        info!(
-            "Generated distributed plan: {:?}",
-            plan.explain_to_string()?
-        );
-
-        let pg_descs = plan
-            .schema()
-            .fields()
-            .iter()
-            .map(to_pg_field)
-            .collect::<Vec<PgFieldDescriptor>>();
-
-        let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
-        let query = plan_fragmenter.split(plan)?;
-        info!("Generated query after plan fragmenter: {:?}", &query);
-        (query, pg_descs)
+    let bound = {
+        let mut binder = Binder::new(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+        )
+        .with_timezone(session.timezone())
+        .with_search_path(session.search_path());
+        binder = match epoch {
+            Some(epoch) => binder.with_query_epoch(epoch),
+            None => binder.for_explain(),
+        };
+        if let Some(system_catalog) = system_catalog {
+            binder = binder.with_system_catalog(system_catalog);
+        }
+        binder.bind(stmt)?
     };
 
+    let plan = Planner::new(context.into())
+        .plan(bound)?
+        .gen_dist_batch_query_plan()?;
+
+    info!(
+        "Generated distributed plan: {:?}",
+        plan.explain_to_string()?
+    );
+
+    let pg_descs = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(to_pg_field)
+        .collect::<Vec<PgFieldDescriptor>>();
+    let column_types = plan.schema().data_types();
+
+    let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
+    let query = plan_fragmenter.split(plan)?;
+    info!("Generated query after plan fragmenter: {:?}", &query);
+    Ok((query, pg_descs, column_types))
+}
+
+pub(super) async fn distribute_execute(
+    context: OptimizerContext,
+    stmt: Statement,
+    epoch: u64,
+) -> Result<(impl DataChunkStream, Vec<PgFieldDescriptor>, Vec<DataType>)> {
+    let session = context.session_ctx.clone();
+    let system_catalog = read_system_catalog(&session, &stmt).await?;
+    // PlanRef (an Rc) is dropped in `gen_distributed_query`, before `await` below.
+    let (query, pg_descs, column_types) =
+        gen_distributed_query(context, stmt, Some(epoch), system_catalog)?;
+
     let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
     let query_manager = execution_context.session().env().query_manager().clone();
     Ok((
-        query_manager.schedule(execution_context, query).await?,
+        query_manager
+            .schedule(execution_context, query, epoch)
+            .await?,
         pg_descs,
+        column_types,
     ))
 }
@@ -12,17 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
-use risingwave_sqlparser::ast::{Ident, SetVariableValue};
+use risingwave_expr::vector_op::timestamptz::lookup_time_zone;
+use risingwave_sqlparser::ast::{Ident, SetVariableValue, Value};
 
-use crate::session::OptimizerContext;
+use crate::session::{OptimizerContext, SEARCH_PATH, TIMEZONE};
 
-pub(super) fn handle_set(
+pub fn handle_set(
     context: OptimizerContext,
     name: Ident,
     value: Vec<SetVariableValue>,
 ) -> Result<PgResponse> {
+    if name.value.eq_ignore_ascii_case(TIMEZONE) {
+        let time_zone = to_unquoted_string(&value[0]);
+        // Reject unknown time zones here rather than when a query uses them.
+        lookup_time_zone(&time_zone)?;
+        context.session_ctx.set_config(TIMEZONE, &time_zone);
+        return Ok(PgResponse::empty_result(StatementType::SET_OPTION));
+    }
+
+    if name.value.eq_ignore_ascii_case(SEARCH_PATH) {
+        let search_path = value.iter().map(to_unquoted_string).join(", ");
+        context.session_ctx.set_config(SEARCH_PATH, &search_path);
+        return Ok(PgResponse::empty_result(StatementType::SET_OPTION));
+    }
+
     let string_val = to_string(&value[0]);
     // Currently store the config variable simply as String -> ConfigEntry(String).
     // In future we can add converter/parser to make the API more robust.
@@ -31,6 +47,15 @@ pub(super) fn handle_set(
     Ok(PgResponse::empty_result(StatementType::SET_OPTION))
 }
 
+/// Convert a set variable naming something, e.g. a time zone or a schema, to the name.
+fn to_unquoted_string(value: &SetVariableValue) -> String {
+    match value {
+        SetVariableValue::Literal(Value::SingleQuotedString(s)) => s.clone(),
+        SetVariableValue::Ident(ident) => ident.value.clone(),
+        value => to_string(value),
+    }
+}
+
 /// Convert any set variable to String.
 /// For example, TRUE -> "TRUE", 1 -> "1".
 fn to_string(value: &SetVariableValue) -> String {
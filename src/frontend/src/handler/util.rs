@@ -12,13 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono_tz::Tz;
 use itertools::Itertools;
 use num_traits::Float;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::types::Row;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Field;
+use risingwave_common::error::Result;
 use risingwave_common::types::{DataType, ScalarRefImpl};
+use risingwave_expr::vector_op::timestamptz::{format_timestamptz, lookup_time_zone};
+use risingwave_sqlparser::ast::Statement;
+
+use crate::binder::Binder;
+use crate::catalog::system_catalog::SystemCatalogSnapshot;
+use crate::session::SessionImpl;
 
 /// Format scalars according to postgres convention.
 fn pg_value_format(d: ScalarRefImpl) -> String {
@@ -45,17 +53,69 @@ fn pg_float_format<T: Float + ToString>(v: T) -> String {
     }
 }
 
-pub fn to_pg_rows(chunk: DataChunk) -> Vec<Row> {
+/// Formats the rows of `chunk`, whose columns are of `column_types`. As a `timestamptz` is stored
+/// like an `int64`, it's told by the column type and displayed in `timezone`.
+pub fn to_pg_rows(chunk: DataChunk, column_types: &[DataType], timezone: Tz) -> Result<Vec<Row>> {
     chunk
         .rows()
         .map(|r| {
-            Row::new(
+            Ok(Row::new(
                 r.0.into_iter()
-                    .map(|data| data.map(pg_value_format))
-                    .collect_vec(),
-            )
+                    .zip_eq(column_types)
+                    .map(|(data, data_type)| {
+                        data.map(|d| match (d, data_type) {
+                            (ScalarRefImpl::Int64(v), DataType::Timestampz) => {
+                                format_timestamptz(v, timezone)
+                            }
+                            (d, _) => Ok(pg_value_format(d)),
+                        })
+                        .transpose()
+                    })
+                    .collect::<Result<_>>()?,
+            ))
         })
-        .collect_vec()
+        .collect()
+}
+
+/// Pins the latest snapshot for a batch query. It should be unpinned by the caller if the query
+/// fails before it's scheduled.
+pub async fn pin_snapshot(session: &SessionImpl) -> Result<u64> {
+    // TODO: Hummock snapshot should maintain as cache instead of RPC each query.
+    // TODO: Use u64::MAX for `last_pinned` so it always return the greatest current epoch. Use
+    // correct `last_pinned` when retrying this RPC.
+    let last_pinned = u64::MAX;
+    session.env().meta_client().pin_snapshot(last_pinned).await
+}
+
+/// Reads the system catalog snapshot from meta and compute nodes if the statement reads any system
+/// table in `rw_catalog`, so that other queries are not slowed down by the RPCs. To find out, the
+/// statement is bound without the snapshot, which resolves the relations it refers to in the
+/// session `search_path`.
+pub async fn read_system_catalog(
+    session: &SessionImpl,
+    stmt: &Statement,
+) -> Result<Option<SystemCatalogSnapshot>> {
+    let reads_system_catalog = {
+        let mut binder = Binder::new(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+        )
+        .with_timezone(session.timezone())
+        .with_search_path(session.search_path())
+        .for_explain();
+        binder.bind(stmt.clone())?;
+        binder.reads_system_catalog()
+    };
+    if reads_system_catalog {
+        Ok(Some(SystemCatalogSnapshot::read(session).await?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Looks up the session time zone, which is validated when it's set.
+pub fn session_timezone(session: &SessionImpl) -> Result<Tz> {
+    lookup_time_zone(&session.timezone())
 }
 
 /// Convert from [`Field`] to [`PgFieldDescriptor`].
@@ -80,6 +140,7 @@ pub fn data_type_to_type_oid(data_type: DataType) -> TypeOid {
         DataType::Interval => TypeOid::Varchar,
         DataType::Struct { .. } => TypeOid::Varchar,
         DataType::List { .. } => TypeOid::Varchar,
+        DataType::Jsonb => TypeOid::Varchar,
     }
 }
 
@@ -112,7 +173,17 @@ mod tests {
             ],
             None,
         );
-        let rows = to_pg_rows(chunk);
+        let rows = to_pg_rows(
+            chunk,
+            &[
+                DataType::Int32,
+                DataType::Int64,
+                DataType::Float32,
+                DataType::Varchar,
+            ],
+            Tz::UTC,
+        )
+        .unwrap();
         let expected = vec![
             vec![
                 Some("1".to_string()),
@@ -137,6 +208,26 @@ mod tests {
         assert_eq!(vec, expected);
     }
 
+    #[test]
+    fn test_timestamptz_to_pg_rows() {
+        let chunk = DataChunk::new(
+            vec![column!(I64Array, [Some(1_654_779_909_500_000), None])],
+            None,
+        );
+        let rows = to_pg_rows(chunk, &[DataType::Timestampz], Tz::Asia__Shanghai).unwrap();
+        let vec = rows
+            .into_iter()
+            .map(|r| r.values().iter().cloned().collect_vec())
+            .collect_vec();
+        assert_eq!(
+            vec,
+            vec![
+                vec![Some("2022-06-09 21:05:09.5+08".to_string())],
+                vec![None],
+            ]
+        );
+    }
+
     #[test]
     fn test_value_format() {
         use ScalarRefImpl as S;
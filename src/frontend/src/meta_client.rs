@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use risingwave_common::error::Result;
+use risingwave_pb::meta::TableFragments;
 use risingwave_rpc_client::{HummockMetaClient, MetaClient};
 
 /// A wrapper around the `MetaClient` that only provides a minor set of meta rpc.
@@ -24,9 +25,13 @@ use risingwave_rpc_client::{HummockMetaClient, MetaClient};
 pub trait FrontendMetaClient: Send + Sync {
     async fn pin_snapshot(&self, last_pinned: u64) -> Result<u64>;
 
+    async fn pin_snapshot_as_of(&self, epoch: u64) -> Result<u64>;
+
     async fn flush(&self) -> Result<()>;
 
     async fn unpin_snapshot(&self, epoch: u64) -> Result<()>;
+
+    async fn list_table_fragments(&self) -> Result<Vec<TableFragments>>;
 }
 
 pub struct FrontendMetaClientImpl(pub MetaClient);
@@ -37,6 +42,10 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
         self.0.pin_snapshot(last_pinned).await
     }
 
+    async fn pin_snapshot_as_of(&self, epoch: u64) -> Result<u64> {
+        self.0.pin_snapshot_as_of(epoch).await
+    }
+
     async fn flush(&self) -> Result<()> {
         self.0.flush().await
     }
@@ -44,4 +53,8 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
     async fn unpin_snapshot(&self, epoch: u64) -> Result<()> {
         self.0.unpin_snapshot(&[epoch]).await
     }
+
+    async fn list_table_fragments(&self) -> Result<Vec<TableFragments>> {
+        self.0.list_table_fragments().await
+    }
 }
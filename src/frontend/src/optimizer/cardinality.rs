@@ -0,0 +1,369 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cardinality estimation of logical plans, based on the statistics collected by `ANALYZE`.
+
+use risingwave_common::types::ScalarImpl;
+use risingwave_pb::plan::JoinType;
+
+use super::plan_node::{PlanNode, PlanRef, PlanTreeNodeBinary, PlanTreeNodeUnary};
+use crate::catalog::table_statistics::{scalar_to_f64, ColumnStatistics};
+use crate::expr::{ExprImpl, ExprType};
+use crate::utils::Condition;
+
+/// Row count assumed for a table which has not been analyzed.
+const DEFAULT_ROW_COUNT: f64 = 1000.0;
+/// Selectivity of an equality predicate when the column has no statistics.
+const DEFAULT_EQ_SELECTIVITY: f64 = 0.1;
+/// Selectivity of a range predicate when the column has no histogram.
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+/// Selectivity of any other predicate.
+const DEFAULT_SELECTIVITY: f64 = 0.25;
+
+impl dyn PlanNode {
+    /// Estimate the number of rows produced by the logical plan.
+    pub fn estimate_row_count(&self) -> f64 {
+        if let Some(scan) = self.as_logical_scan() {
+            scan.stats()
+                .map_or(DEFAULT_ROW_COUNT, |stats| stats.row_count as f64)
+        } else if let Some(values) = self.as_logical_values() {
+            values.rows().len() as f64
+        } else if let Some(filter) = self.as_logical_filter() {
+            let input = Inputs::new(vec![filter.input()]);
+            input.row_count() * input.selectivity(filter.predicate())
+        } else if let Some(agg) = self.as_logical_agg() {
+            if agg.group_keys().is_empty() {
+                return 1.0;
+            }
+            let input = agg.input();
+            let input_rows = input.estimate_row_count();
+            let groups: f64 = agg
+                .group_keys()
+                .iter()
+                .map(|key| input.estimate_ndv(*key).unwrap_or(input_rows))
+                .product();
+            groups.min(input_rows)
+        } else if let Some(join) = self.as_logical_join() {
+            let left_rows = join.left().estimate_row_count();
+            let right_rows = join.right().estimate_row_count();
+            let input = Inputs::new(vec![join.left(), join.right()]);
+            let inner = left_rows * right_rows * input.selectivity(join.on());
+            match join.join_type() {
+                JoinType::Inner => inner,
+                JoinType::LeftOuter => inner.max(left_rows),
+                JoinType::RightOuter => inner.max(right_rows),
+                JoinType::FullOuter => inner.max(left_rows).max(right_rows),
+                JoinType::LeftSemi => inner.min(left_rows),
+                JoinType::LeftAnti => left_rows - inner.min(left_rows),
+                JoinType::RightSemi => inner.min(right_rows),
+                JoinType::RightAnti => right_rows - inner.min(right_rows),
+            }
+        } else if let Some(limit) = self.as_logical_limit() {
+            apply_limit(
+                limit.input().estimate_row_count(),
+                limit.limit(),
+                limit.offset(),
+            )
+        } else if let Some(top_n) = self.as_logical_top_n() {
+            apply_limit(
+                top_n.input().estimate_row_count(),
+                top_n.limit(),
+                top_n.offset(),
+            )
+        } else {
+            // e.g. projects produce exactly the rows of their input
+            self.inputs()
+                .first()
+                .map_or(DEFAULT_ROW_COUNT, |input| input.estimate_row_count())
+        }
+    }
+
+    /// Get the statistics of an output column, if it comes directly from an analyzed table.
+    pub fn column_statistics(&self, index: usize) -> Option<ColumnStatistics> {
+        if let Some(scan) = self.as_logical_scan() {
+            let column_idx = scan.required_col_idx()[index];
+            let column_id = scan.table_desc().columns[column_idx].column_id;
+            scan.stats()?.column(column_id).cloned()
+        } else if let Some(filter) = self.as_logical_filter() {
+            filter.input().column_statistics(index)
+        } else if let Some(limit) = self.as_logical_limit() {
+            limit.input().column_statistics(index)
+        } else if let Some(top_n) = self.as_logical_top_n() {
+            top_n.input().column_statistics(index)
+        } else if let Some(project) = self.as_logical_project() {
+            let input_ref = project.exprs()[index].as_input_ref()?;
+            project.input().column_statistics(input_ref.index())
+        } else if let Some(agg) = self.as_logical_agg() {
+            let key = agg.group_keys().get(index)?;
+            agg.input().column_statistics(*key)
+        } else if let Some(join) = self.as_logical_join() {
+            if let Some(left_idx) = join.o2l_col_mapping().try_map(index) {
+                join.left().column_statistics(left_idx)
+            } else {
+                let right_idx = join.o2r_col_mapping().try_map(index)?;
+                join.right().column_statistics(right_idx)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Estimate the number of distinct values of an output column, if it has statistics.
+    pub fn estimate_ndv(&self, index: usize) -> Option<f64> {
+        let stats = self.column_statistics(index)?;
+        Some((stats.ndv as f64).min(self.estimate_row_count()).max(1.0))
+    }
+}
+
+fn apply_limit(input_rows: f64, limit: usize, offset: usize) -> f64 {
+    (input_rows - offset as f64).max(0.0).min(limit as f64)
+}
+
+/// The inputs of a plan node whose columns are concatenated, against which the selectivity of a
+/// predicate is estimated.
+struct Inputs {
+    inputs: Vec<PlanRef>,
+}
+
+impl Inputs {
+    fn new(inputs: Vec<PlanRef>) -> Self {
+        Self { inputs }
+    }
+
+    fn row_count(&self) -> f64 {
+        self.inputs
+            .iter()
+            .map(|input| input.estimate_row_count())
+            .product()
+    }
+
+    /// Locate the input of a column and its index in that input.
+    fn locate(&self, mut index: usize) -> (&PlanRef, usize) {
+        for input in &self.inputs {
+            let len = input.schema().len();
+            if index < len {
+                return (input, index);
+            }
+            index -= len;
+        }
+        panic!("column index out of range")
+    }
+
+    fn column_statistics(&self, index: usize) -> Option<ColumnStatistics> {
+        let (input, index) = self.locate(index);
+        input.column_statistics(index)
+    }
+
+    /// Number of distinct values of a column, assuming all values are distinct without
+    /// statistics.
+    fn ndv(&self, index: usize) -> f64 {
+        let (input, index) = self.locate(index);
+        input
+            .estimate_ndv(index)
+            .unwrap_or_else(|| input.estimate_row_count().max(1.0))
+    }
+
+    /// Estimate the fraction of rows satisfying the condition, assuming the conjunctions are
+    /// independent.
+    fn selectivity(&self, cond: &Condition) -> f64 {
+        cond.conjunctions
+            .iter()
+            .map(|expr| self.expr_selectivity(expr))
+            .product::<f64>()
+            .clamp(0.0, 1.0)
+    }
+
+    fn expr_selectivity(&self, expr: &ExprImpl) -> f64 {
+        match expr {
+            ExprImpl::Literal(literal) => match literal.get_data() {
+                Some(ScalarImpl::Bool(true)) => 1.0,
+                _ => 0.0,
+            },
+            ExprImpl::FunctionCall(func_call) => {
+                let inputs = func_call.inputs();
+                match func_call.get_expr_type() {
+                    ExprType::And => inputs
+                        .iter()
+                        .map(|input| self.expr_selectivity(input))
+                        .product(),
+                    ExprType::Or => {
+                        let (a, b) = (
+                            self.expr_selectivity(&inputs[0]),
+                            self.expr_selectivity(&inputs[1]),
+                        );
+                        a + b - a * b
+                    }
+                    ExprType::Not => 1.0 - self.expr_selectivity(&inputs[0]),
+                    ExprType::Equal => self.eq_selectivity(&inputs[0], &inputs[1]),
+                    ExprType::NotEqual => 1.0 - self.eq_selectivity(&inputs[0], &inputs[1]),
+                    expr_type @ (ExprType::LessThan
+                    | ExprType::LessThanOrEqual
+                    | ExprType::GreaterThan
+                    | ExprType::GreaterThanOrEqual) => {
+                        self.range_selectivity(expr_type, &inputs[0], &inputs[1])
+                    }
+                    ExprType::IsNull => DEFAULT_EQ_SELECTIVITY,
+                    ExprType::IsNotNull => 1.0 - DEFAULT_EQ_SELECTIVITY,
+                    _ => DEFAULT_SELECTIVITY,
+                }
+            }
+            _ => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    fn eq_selectivity(&self, lhs: &ExprImpl, rhs: &ExprImpl) -> f64 {
+        match (lhs, rhs) {
+            (ExprImpl::InputRef(lhs), ExprImpl::InputRef(rhs)) => {
+                1.0 / self.ndv(lhs.index()).max(self.ndv(rhs.index()))
+            }
+            (ExprImpl::InputRef(input_ref), ExprImpl::Literal(_))
+            | (ExprImpl::Literal(_), ExprImpl::InputRef(input_ref)) => {
+                match self.column_statistics(input_ref.index()) {
+                    Some(stats) if stats.ndv > 0 => 1.0 / stats.ndv as f64,
+                    _ => DEFAULT_EQ_SELECTIVITY,
+                }
+            }
+            _ => DEFAULT_EQ_SELECTIVITY,
+        }
+    }
+
+    fn range_selectivity(&self, expr_type: ExprType, lhs: &ExprImpl, rhs: &ExprImpl) -> f64 {
+        // normalize to `column <op> literal`
+        let (input_ref, literal, expr_type) = match (lhs, rhs) {
+            (ExprImpl::InputRef(input_ref), ExprImpl::Literal(literal)) => {
+                (input_ref, literal, expr_type)
+            }
+            (ExprImpl::Literal(literal), ExprImpl::InputRef(input_ref)) => {
+                let flipped = match expr_type {
+                    ExprType::LessThan => ExprType::GreaterThan,
+                    ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+                    ExprType::GreaterThan => ExprType::LessThan,
+                    _ => ExprType::LessThanOrEqual,
+                };
+                (input_ref, literal, flipped)
+            }
+            _ => return DEFAULT_RANGE_SELECTIVITY,
+        };
+        let below = literal
+            .get_data()
+            .as_ref()
+            .and_then(scalar_to_f64)
+            .zip(self.column_statistics(input_ref.index()))
+            .and_then(|(value, stats)| stats.fraction_below(value));
+        match below {
+            Some(below) => match expr_type {
+                ExprType::LessThan | ExprType::LessThanOrEqual => below,
+                _ => 1.0 - below,
+            },
+            None => DEFAULT_RANGE_SELECTIVITY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use risingwave_common::catalog::{ColumnDesc, TableDesc};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::catalog::table_statistics::TableStatistics;
+    use crate::expr::{FunctionCall, InputRef, Literal};
+    use crate::optimizer::plan_node::{LogicalAgg, LogicalFilter, LogicalJoin, LogicalScan};
+    use crate::session::{OptimizerContext, OptimizerContextRef};
+
+    /// A table with a single int column `k`, whose values are `0..ndv` with each repeated
+    /// `row_count / ndv` times.
+    fn scan(ctx: OptimizerContextRef, row_count: u64, ndv: u64) -> PlanRef {
+        let column = ColumnDesc::unnamed(0.into(), DataType::Int32);
+        let stats = TableStatistics {
+            row_count,
+            columns: HashMap::from([(
+                column.column_id,
+                ColumnStatistics {
+                    null_count: 0,
+                    ndv,
+                    histogram_bounds: (0..=ndv).map(|i| i as f64).collect(),
+                },
+            )]),
+        };
+        let table_desc = TableDesc {
+            table_id: 0.into(),
+            pk: vec![],
+            columns: vec![column],
+        };
+        LogicalScan::create(
+            "t".to_string(),
+            Rc::new(table_desc),
+            Some(Rc::new(stats)),
+            vec![],
+            None,
+            ctx,
+        )
+        .unwrap()
+    }
+
+    fn compare(expr_type: ExprType, index: usize, value: i32) -> ExprImpl {
+        FunctionCall::new(
+            expr_type,
+            vec![
+                InputRef::new(index, DataType::Int32).into(),
+                Literal::new(Some(ScalarImpl::Int32(value)), DataType::Int32).into(),
+            ],
+        )
+        .unwrap()
+        .into()
+    }
+
+    #[tokio::test]
+    async fn test_estimate_filter() {
+        let ctx = OptimizerContext::mock().await;
+        let input = scan(ctx, 1000, 100);
+        assert_eq!(input.estimate_row_count(), 1000.0);
+
+        let eq = LogicalFilter::create_with_expr(input.clone(), compare(ExprType::Equal, 0, 5));
+        assert_eq!(eq.estimate_row_count(), 10.0);
+
+        let lt = LogicalFilter::create_with_expr(input.clone(), compare(ExprType::LessThan, 0, 25));
+        assert!((lt.estimate_row_count() - 250.0).abs() < 1e-6);
+
+        let ge =
+            LogicalFilter::create_with_expr(input, compare(ExprType::GreaterThanOrEqual, 0, 25));
+        assert!((ge.estimate_row_count() - 750.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_join_and_agg() {
+        let ctx = OptimizerContext::mock().await;
+        let left = scan(ctx.clone(), 1000, 1000);
+        let right = scan(ctx, 100, 100);
+        let on = FunctionCall::new(
+            ExprType::Equal,
+            vec![
+                InputRef::new(0, DataType::Int32).into(),
+                InputRef::new(1, DataType::Int32).into(),
+            ],
+        )
+        .unwrap()
+        .into();
+        let join = LogicalJoin::create(left, right.clone(), JoinType::Inner, on);
+        assert_eq!(join.estimate_row_count(), 100.0);
+        assert_eq!(join.estimate_ndv(1), Some(100.0));
+
+        let agg: PlanRef = LogicalAgg::new(vec![], vec![], vec![0], right).into();
+        assert_eq!(agg.estimate_row_count(), 100.0);
+    }
+}
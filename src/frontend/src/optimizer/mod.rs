@@ -16,6 +16,7 @@ pub mod plan_node;
 pub use plan_node::PlanRef;
 pub mod property;
 
+mod cardinality;
 mod heuristic;
 mod plan_rewriter;
 mod plan_visitor;
@@ -25,12 +26,14 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools as _;
 use property::{Distribution, Order};
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_expr::expr::AggKind;
 
 use self::heuristic::{ApplyOrder, HeuristicOptimizer};
 use self::plan_node::{Convention, LogicalProject, StreamMaterialize};
 use self::rule::*;
 use crate::expr::InputRef;
+use crate::session::SPLIT_DISTINCT_AGG;
 
 /// `PlanRoot` is used to describe a plan. planner will construct a `PlanRoot` with `LogicalNode`.
 /// and required distribution and order. And `PlanRoot` can generate corresponding streaming or
@@ -115,6 +118,13 @@ impl PlanRoot {
     pub fn gen_optimized_logical_plan(&self) -> PlanRef {
         let mut plan = self.plan.clone();
 
+        // Constant Folding
+        plan = {
+            let rules = vec![ConstEvalRule::create()];
+            let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::BottomUp, rules);
+            heuristic_optimizer.optimize(plan)
+        };
+
         // Predicate Push-down
         plan = {
             let rules = vec![
@@ -138,12 +148,60 @@ impl PlanRoot {
             heuristic_optimizer.optimize(plan)
         };
 
+        // Split the distinct aggregations if the session asks for it.
+        let split_distinct_agg = plan
+            .ctx()
+            .inner()
+            .session_ctx
+            .get_config(SPLIT_DISTINCT_AGG)
+            .map(|entry| entry.is_set(false))
+            .unwrap_or(false);
+        if split_distinct_agg {
+            let rules = vec![SplitDistinctAggRule::create()];
+            let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::TopDown, rules);
+            plan = heuristic_optimizer.optimize(plan);
+        }
+
         plan
     }
 
     /// optimize and generate a batch query plan
-    pub fn gen_batch_query_plan(&self) -> PlanRef {
+    pub fn gen_batch_query_plan(&self) -> Result<PlanRef> {
         let mut plan = self.gen_optimized_logical_plan();
+        if has_over_agg(&plan) {
+            return Err(ErrorCode::NotImplemented(
+                "window functions in batch queries".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+
+        // Reorder the inner joins of analyzed tables by their estimated cardinalities.
+        plan = {
+            let rules = vec![JoinReorderRule::create()];
+            let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::TopDown, rules);
+            heuristic_optimizer.optimize(plan)
+        };
+        plan = {
+            let rules = vec![ProjectMergeRule::create(), ProjectEliminateRule::create()];
+            let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::BottomUp, rules);
+            heuristic_optimizer.optimize(plan)
+        };
+
+        // Read from the indexes of tables instead if they match the filters.
+        plan = {
+            let rules = vec![IndexScanRule::create()];
+            let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::TopDown, rules);
+            heuristic_optimizer.optimize(plan)
+        };
+
+        // Prune the sub-plans which produce no rows. Streaming has no values executor yet, so it
+        // is only done for batch.
+        plan = {
+            let rules = vec![PruneEmptyRule::create()];
+            let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::BottomUp, rules);
+            heuristic_optimizer.optimize(plan)
+        };
 
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order);
@@ -154,16 +212,16 @@ impl PlanRoot {
         // TODO: do a final column pruning after add the batch project, but now the column
         // pruning is not used in batch node, need to think.
 
-        plan
+        Ok(plan)
     }
 
     /// Optimize and generate a batch query plan.
     /// Currently only used by test runner (Have distributed plan but not schedule yet).
     /// Will be removed after dist execution.
-    pub fn gen_dist_batch_query_plan(&self) -> PlanRef {
-        let plan = self.gen_batch_query_plan();
+    pub fn gen_dist_batch_query_plan(&self) -> Result<PlanRef> {
+        let plan = self.gen_batch_query_plan()?;
 
-        plan.to_distributed_with_required(&self.required_order, &self.required_dist)
+        Ok(plan.to_distributed_with_required(&self.required_order, &self.required_dist))
     }
 
     /// Optimize and generate a create materialize view plan.
@@ -173,7 +231,46 @@ impl PlanRoot {
     pub fn gen_create_mv_plan(&mut self, mv_name: String) -> Result<StreamMaterialize> {
         let stream_plan = match self.plan.convention() {
             Convention::Logical => {
-                let plan = self.gen_optimized_logical_plan();
+                let mut plan = self.gen_optimized_logical_plan();
+                // Plan the top-N pattern of window functions as group top-N.
+                plan = {
+                    let rules = vec![OverAggToTopNRule::create()];
+                    let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::TopDown, rules);
+                    heuristic_optimizer.optimize(plan)
+                };
+                if has_over_agg(&plan) {
+                    return Err(ErrorCode::NotImplemented(
+                        "window functions other than `row_number() <= N` with a partition in \
+                         streaming queries"
+                            .to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                if has_as_of_scan(&plan) {
+                    return Err(ErrorCode::NotImplemented(
+                        "FOR SYSTEM_TIME AS OF in streaming queries".to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                if has_unnest(&plan) {
+                    return Err(ErrorCode::NotImplemented(
+                        "unnest in streaming queries".to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                if has_distinct_agg(&plan) {
+                    return Err(ErrorCode::NotImplemented(
+                        format!(
+                            "DISTINCT aggregation in streaming queries without {} on",
+                            SPLIT_DISTINCT_AGG
+                        ),
+                        None.into(),
+                    )
+                    .into());
+                }
                 let (plan, out_col_change) = plan.logical_rewrite_for_stream();
                 self.required_dist = out_col_change
                     .rewrite_required_distribution(&self.required_dist)
@@ -183,7 +280,17 @@ impl PlanRoot {
                     .unwrap();
                 self.out_fields = out_col_change.rewrite_bitset(&self.out_fields);
                 self.schema = plan.schema().clone();
-                plan.to_stream_with_dist_required(&self.required_dist)
+                let plan = plan.to_stream_with_dist_required(&self.required_dist);
+                if has_retracted_approx_agg(&plan) {
+                    return Err(ErrorCode::NotImplemented(
+                        "approx_count_distinct and approx_percentile over inputs that are not \
+                         append-only in streaming queries"
+                            .to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                plan
             }
             Convention::Stream => self
                 .required_dist
@@ -211,6 +318,56 @@ impl PlanRoot {
     }
 }
 
+/// Whether there is any [`LogicalAgg`](plan_node::LogicalAgg) with distinct agg calls in the
+/// plan, which is not supported by the streaming executors yet.
+fn has_distinct_agg(plan: &PlanRef) -> bool {
+    plan.as_logical_agg()
+        .map(|agg| agg.agg_calls().iter().any(|agg_call| agg_call.distinct))
+        .unwrap_or(false)
+        || plan.inputs().iter().any(has_distinct_agg)
+}
+
+/// Whether there is any [`LogicalScan`](plan_node::LogicalScan) reading a table as of a past
+/// epoch, which streaming table scans can't do.
+fn has_as_of_scan(plan: &PlanRef) -> bool {
+    plan.as_logical_scan()
+        .map(|scan| scan.as_of_epoch().is_some())
+        .unwrap_or(false)
+        || plan.inputs().iter().any(has_as_of_scan)
+}
+
+/// Whether there is any [`LogicalUnnest`](plan_node::LogicalUnnest) in the plan, which has no
+/// streaming executor yet.
+fn has_unnest(plan: &PlanRef) -> bool {
+    plan.as_logical_unnest().is_some() || plan.inputs().iter().any(has_unnest)
+}
+
+/// Whether there is any streaming aggregation of `approx_count_distinct` or `approx_percentile`
+/// over an input that is not append-only, whose sketches can't retract the deleted rows.
+fn has_retracted_approx_agg(plan: &PlanRef) -> bool {
+    let agg_calls = match (plan.as_stream_hash_agg(), plan.as_stream_simple_agg()) {
+        (Some(agg), _) => Some(agg.agg_calls()),
+        (_, Some(agg)) => Some(agg.agg_calls()),
+        _ => None,
+    };
+    let retracted = agg_calls.map_or(false, |agg_calls| {
+        !plan.inputs()[0].append_only()
+            && agg_calls.iter().any(|agg_call| {
+                matches!(
+                    agg_call.agg_kind,
+                    AggKind::ApproxCountDistinct | AggKind::ApproxPercentile(_)
+                )
+            })
+    });
+    retracted || plan.inputs().iter().any(has_retracted_approx_agg)
+}
+
+/// Whether there is any [`LogicalOverAgg`](plan_node::LogicalOverAgg) in the plan, which has no
+/// executors.
+fn has_over_agg(plan: &PlanRef) -> bool {
+    plan.as_logical_over_agg().is_some() || plan.inputs().iter().any(has_over_agg)
+}
+
 #[cfg(test)]
 mod tests {
 
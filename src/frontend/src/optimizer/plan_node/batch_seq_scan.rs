@@ -13,36 +13,50 @@
 // limitations under the License.
 
 use std::fmt;
+use std::ops::Bound;
 
+use risingwave_meta::manager::INVALID_EPOCH;
 use risingwave_pb::plan::plan_node::NodeBody;
 use risingwave_pb::plan::{CellBasedTableDesc, ColumnDesc as ProstColumnDesc, RowSeqScanNode};
 
 use super::{PlanBase, PlanRef, ToBatchProst, ToDistributedBatch};
+use crate::expr::Literal;
 use crate::optimizer::plan_node::LogicalScan;
 use crate::optimizer::property::{Distribution, Order};
+use crate::utils::ScanRange;
 
 /// `BatchSeqScan` implements [`super::LogicalScan`] to scan from a row-oriented table
 #[derive(Debug, Clone)]
 pub struct BatchSeqScan {
     pub base: PlanBase,
     logical: LogicalScan,
+    scan_range: ScanRange,
 }
 
 impl BatchSeqScan {
-    pub fn new_inner(logical: LogicalScan, dist: Distribution) -> Self {
+    pub fn new_inner(logical: LogicalScan, dist: Distribution, scan_range: ScanRange) -> Self {
         let ctx = logical.base.ctx.clone();
         // TODO: derive from input
         let base = PlanBase::new_batch(ctx, logical.schema().clone(), dist, Order::any().clone());
 
-        Self { base, logical }
+        Self {
+            base,
+            logical,
+            scan_range,
+        }
     }
 
     pub fn new(logical: LogicalScan) -> Self {
-        Self::new_inner(logical, Distribution::Any)
+        Self::new_inner(logical, Distribution::Any, ScanRange::full_table_scan())
     }
 
-    pub fn new_with_dist(logical: LogicalScan) -> Self {
-        Self::new_inner(logical, Distribution::AnyShard)
+    /// Create a scan which only reads the rows with pk in the `scan_range`.
+    pub fn new_with_scan_range(logical: LogicalScan, scan_range: ScanRange) -> Self {
+        Self::new_inner(logical, Distribution::Any, scan_range)
+    }
+
+    pub fn new_with_dist(logical: LogicalScan, scan_range: ScanRange) -> Self {
+        Self::new_inner(logical, Distribution::AnyShard, scan_range)
     }
 
     /// Get a reference to the batch seq scan's logical.
@@ -50,6 +64,47 @@ impl BatchSeqScan {
     pub fn logical(&self) -> &LogicalScan {
         &self.logical
     }
+
+    /// Get the range of pks to scan.
+    pub fn scan_range(&self) -> &ScanRange {
+        &self.scan_range
+    }
+
+    /// The conditions of the scan range on the pk columns, for explain.
+    fn scan_range_conditions(&self) -> Vec<String> {
+        let pk = &self.logical.table_desc().pk;
+        let literal =
+            |i: usize, value| Literal::new(Some(value), pk[i].column_desc.data_type.clone());
+        let mut conditions = self
+            .scan_range
+            .eq_conds
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                format!(
+                    "{} = {:?}",
+                    pk[i].column_desc.name,
+                    literal(i, value.clone())
+                )
+            })
+            .collect::<Vec<_>>();
+        let i = self.scan_range.eq_conds.len();
+        let (lower, upper) = &self.scan_range.range;
+        for (bound, included_op, excluded_op) in [(lower, ">=", ">"), (upper, "<=", "<")] {
+            let (op, value) = match bound {
+                Bound::Included(value) => (included_op, value),
+                Bound::Excluded(value) => (excluded_op, value),
+                Bound::Unbounded => continue,
+            };
+            conditions.push(format!(
+                "{} {} {:?}",
+                pk[i].column_desc.name,
+                op,
+                literal(i, value.clone())
+            ));
+        }
+        conditions
+    }
 }
 
 impl_plan_tree_node_for_leaf! { BatchSeqScan }
@@ -58,16 +113,27 @@ impl fmt::Display for BatchSeqScan {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "BatchScan {{ table: {}, columns: [{}] }}",
+            "BatchScan {{ table: {}, columns: [{}]",
             self.logical.table_name(),
             self.logical.column_names().join(", ")
-        )
+        )?;
+        if let Some(as_of_epoch) = self.logical.as_of_epoch() {
+            write!(f, ", as_of_epoch: {}", as_of_epoch)?;
+        }
+        if !self.scan_range.is_full_table_scan() {
+            write!(
+                f,
+                ", scan_range: [{}]",
+                self.scan_range_conditions().join(", ")
+            )?;
+        }
+        write!(f, " }}")
     }
 }
 
 impl ToDistributedBatch for BatchSeqScan {
     fn to_distributed(&self) -> PlanRef {
-        Self::new_with_dist(self.logical.clone()).into()
+        Self::new_with_dist(self.logical.clone(), self.scan_range.clone()).into()
     }
 }
 
@@ -86,6 +152,19 @@ impl ToBatchProst for BatchSeqScan {
                 pk: vec![], // TODO:
             }),
             column_descs,
+            as_of_epoch: self.logical.as_of_epoch().unwrap_or(INVALID_EPOCH),
+            scan_range: if self.scan_range.is_full_table_scan() {
+                None
+            } else {
+                let order_types = self
+                    .logical
+                    .table_desc()
+                    .pk
+                    .iter()
+                    .map(|c| c.order)
+                    .collect::<Vec<_>>();
+                Some(self.scan_range.to_protobuf(&order_types))
+            },
         })
     }
 }
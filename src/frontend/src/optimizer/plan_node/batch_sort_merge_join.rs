@@ -0,0 +1,156 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::plan::plan_node::NodeBody;
+use risingwave_pb::plan::{OrderType as ProstOrderType, SortMergeJoinNode};
+
+use super::{
+    EqJoinPredicate, LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, ToBatchProst,
+    ToDistributedBatch,
+};
+use crate::optimizer::property::{Distribution, FieldOrder, Order};
+
+/// `BatchSortMergeJoin` implements an inner [`super::LogicalJoin`] on equal keys by merging two
+/// inputs both sorted ascendingly on the keys. Its output is sorted on the left keys as well.
+#[derive(Debug, Clone)]
+pub struct BatchSortMergeJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The join condition must be equivalent to `logical.on`, and only has the equal part.
+    eq_join_predicate: EqJoinPredicate,
+}
+
+impl BatchSortMergeJoin {
+    pub fn new(logical: LogicalJoin, eq_join_predicate: EqJoinPredicate) -> Self {
+        assert!(!eq_join_predicate.has_non_eq());
+        let ctx = logical.base.ctx.clone();
+        let dist = Self::derive_dist(
+            logical.left().distribution(),
+            logical.right().distribution(),
+        );
+        // The output columns start with the left ones, so the left key order is kept as is.
+        let order = Self::left_order(&eq_join_predicate);
+        let base = PlanBase::new_batch(ctx, logical.schema().clone(), dist, order);
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+        }
+    }
+
+    fn derive_dist(left: &Distribution, right: &Distribution) -> Distribution {
+        match (left, right) {
+            (Distribution::Any, Distribution::Any) => Distribution::Any,
+            (Distribution::Single, Distribution::Single) => Distribution::Single,
+            (_, _) => panic!(),
+        }
+    }
+
+    /// The order the left input is required to provide.
+    pub fn left_order(eq_join_predicate: &EqJoinPredicate) -> Order {
+        Order::new(
+            eq_join_predicate
+                .left_eq_indexes()
+                .into_iter()
+                .map(FieldOrder::ascending)
+                .collect(),
+        )
+    }
+
+    /// The order the right input is required to provide.
+    pub fn right_order(eq_join_predicate: &EqJoinPredicate) -> Order {
+        Order::new(
+            eq_join_predicate
+                .right_eq_indexes()
+                .into_iter()
+                .map(FieldOrder::ascending)
+                .collect(),
+        )
+    }
+
+    /// Get a reference to the batch sort merge join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+}
+
+impl fmt::Display for BatchSortMergeJoin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "BatchSortMergeJoin {{ type: {:?}, predicate: {} }}",
+            self.logical.join_type(),
+            self.eq_join_predicate()
+        )
+    }
+}
+
+impl PlanTreeNodeBinary for BatchSortMergeJoin {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.logical.clone_with_left_right(left, right),
+            self.eq_join_predicate.clone(),
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { BatchSortMergeJoin }
+
+impl ToDistributedBatch for BatchSortMergeJoin {
+    fn to_distributed(&self) -> PlanRef {
+        let left = self.left().to_distributed_with_required(
+            &Self::left_order(&self.eq_join_predicate),
+            &Distribution::Single,
+        );
+        let right = self.right().to_distributed_with_required(
+            &Self::right_order(&self.eq_join_predicate),
+            &Distribution::Single,
+        );
+
+        self.clone_with_left_right(left, right).into()
+    }
+}
+
+impl ToBatchProst for BatchSortMergeJoin {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::SortMergeJoin(SortMergeJoinNode {
+            join_type: self.logical.join_type() as i32,
+            left_keys: self
+                .eq_join_predicate
+                .left_eq_indexes()
+                .into_iter()
+                .map(|a| a as i32)
+                .collect(),
+            right_keys: self
+                .eq_join_predicate
+                .right_eq_indexes()
+                .into_iter()
+                .map(|a| a as i32)
+                .collect(),
+            direction: ProstOrderType::Ascending as i32,
+        })
+    }
+}
@@ -19,15 +19,18 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_expr::expr::AggKind;
 use risingwave_pb::expr::AggCall as ProstAggCall;
 
 use super::{
     BatchHashAgg, BatchSimpleAgg, ColPrunable, PlanBase, PlanNode, PlanRef, PlanTreeNodeUnary,
-    StreamHashAgg, StreamSimpleAgg, ToBatch, ToStream,
+    StreamDistinct, StreamExchange, StreamHashAgg, StreamLocalHashAgg, StreamSimpleAgg, ToBatch,
+    ToStream,
+};
+use crate::expr::{
+    AggCall, CastContext, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Literal,
 };
-use crate::expr::{AggCall, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef};
 use crate::optimizer::plan_node::LogicalProject;
 use crate::optimizer::property::Distribution;
 use crate::utils::ColIndexMapping;
@@ -43,10 +46,24 @@ pub struct PlanAggCall {
 
     /// Column indexes of input columns
     pub inputs: Vec<InputRef>,
+
+    /// Whether only the distinct values of the inputs are aggregated
+    pub distinct: bool,
 }
 
 impl fmt::Debug for PlanAggCall {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.distinct {
+            return write!(
+                f,
+                "{}(distinct {})",
+                self.agg_kind,
+                self.inputs
+                    .iter()
+                    .map(|input| format!("{:?}", input))
+                    .join(", ")
+            );
+        }
         let mut builder = f.debug_tuple(&format!("{}", self.agg_kind));
         self.inputs.iter().for_each(|child| {
             builder.field(child);
@@ -65,8 +82,8 @@ impl PlanAggCall {
                 .iter()
                 .map(InputRef::to_agg_arg_protobuf)
                 .collect(),
-            // TODO: support distinct
-            distinct: false,
+            distinct: self.distinct,
+            percentile: self.agg_kind.percentile().unwrap_or_default(),
         }
     }
 
@@ -75,6 +92,7 @@ impl PlanAggCall {
             agg_kind: AggKind::Count,
             return_type: DataType::Int64,
             inputs: vec![],
+            distinct: false,
         }
     }
 }
@@ -142,6 +160,130 @@ impl ExprHandler {
             error: None,
         })
     }
+
+    /// Adds `expr` to the project below the agg unless it's already there, and returns the
+    /// reference to it.
+    fn push_project(&mut self, expr: ExprImpl) -> InputRef {
+        let return_type = expr.return_type();
+        let index = match self.expr_index.get(&expr) {
+            Some(index) => *index,
+            None => {
+                let index = self.project.len();
+                self.expr_index.insert(expr.clone(), index);
+                self.project.push(expr);
+                index
+            }
+        };
+        InputRef::new(index, return_type)
+    }
+
+    /// Adds an agg call and returns the reference to its output.
+    fn push_agg_call(
+        &mut self,
+        agg_kind: AggKind,
+        return_type: DataType,
+        inputs: Vec<InputRef>,
+    ) -> ExprImpl {
+        self.agg_calls.push(PlanAggCall {
+            agg_kind,
+            return_type: return_type.clone(),
+            inputs,
+            distinct: false,
+        });
+        InputRef::new(self.group_key_len + self.agg_calls.len() - 1, return_type).into()
+    }
+
+    /// Rewrites `var_pop(x)`, `var_samp(x)`, `stddev_pop(x)` and `stddev_samp(x)` with `sum(x)`,
+    /// `sum(x * x)` and `count(x)`, which are retractable in streaming:
+    ///
+    /// ```text
+    /// var_pop(x) = (sum(x * x) - sum(x) * sum(x) / count(x)) / count(x)
+    /// var_samp(x) = (sum(x * x) - sum(x) * sum(x) / count(x)) / (count(x) - 1)
+    /// ```
+    ///
+    /// For exact inputs, whose result is a decimal, the computation is done in decimal, so the
+    /// subtraction does not lose precision. It fails with an out of range error if `sum(x * x)`
+    /// exceeds the range of decimal. For floating-point inputs it is done in double precision,
+    /// which loses precision when the values are large and their variance is small. The
+    /// numerator is clamped at zero against such rounding errors, and the result is null if
+    /// there are too few values.
+    fn rewrite_variance(
+        &mut self,
+        agg_kind: AggKind,
+        inputs: Vec<ExprImpl>,
+        return_type: DataType,
+    ) -> ExprImpl {
+        let binary = |func_type, left: ExprImpl, right: ExprImpl| -> ExprImpl {
+            FunctionCall::new(func_type, vec![left, right])
+                .unwrap()
+                .into()
+        };
+        let cast = |expr: ExprImpl, data_type: DataType| -> ExprImpl {
+            FunctionCall::new_cast(expr, data_type, CastContext::Explicit).unwrap()
+        };
+        // Either decimal or double precision.
+        let calc_type = return_type.clone();
+        let constant = |v: i64| -> ExprImpl {
+            cast(
+                Literal::new(Some(ScalarImpl::Int64(v)), DataType::Int64).into(),
+                calc_type.clone(),
+            )
+        };
+
+        let [input]: [ExprImpl; 1] = inputs.try_into().unwrap();
+        let input = cast(input, calc_type.clone());
+        let squared = binary(ExprType::Multiply, input.clone(), input.clone());
+        let input = self.push_project(input);
+        let squared = self.push_project(squared);
+
+        let sum = self.push_agg_call(AggKind::Sum, calc_type.clone(), vec![input.clone()]);
+        let sum_of_squares = self.push_agg_call(AggKind::Sum, calc_type.clone(), vec![squared]);
+        let count = self.push_agg_call(AggKind::Count, DataType::Int64, vec![input]);
+
+        let min_count = match agg_kind {
+            AggKind::VarPop | AggKind::StddevPop => 0,
+            _ => 1,
+        };
+        let count_calc = cast(count.clone(), calc_type.clone());
+        let numerator = binary(
+            ExprType::Subtract,
+            sum_of_squares,
+            binary(
+                ExprType::Divide,
+                binary(ExprType::Multiply, sum.clone(), sum),
+                count_calc.clone(),
+            ),
+        );
+        let denominator = binary(ExprType::Subtract, count_calc, constant(min_count));
+        let variance = FunctionCall::new_with_return_type(
+            ExprType::Case,
+            vec![
+                binary(
+                    ExprType::LessThanOrEqual,
+                    count,
+                    Literal::new(Some(ScalarImpl::Int64(min_count)), DataType::Int64).into(),
+                ),
+                Literal::new(None, calc_type.clone()).into(),
+                binary(ExprType::GreaterThan, numerator.clone(), constant(0)),
+                binary(ExprType::Divide, numerator, denominator),
+                constant(0),
+            ],
+            calc_type,
+        )
+        .into();
+
+        let result = match agg_kind {
+            // The square root is only available in double precision, and is taken after the
+            // variance is computed exactly.
+            AggKind::StddevPop | AggKind::StddevSamp => {
+                FunctionCall::new(ExprType::Sqrt, vec![cast(variance, DataType::Float64)])
+                    .unwrap()
+                    .into()
+            }
+            _ => variance,
+        };
+        cast(result, return_type)
+    }
 }
 
 impl ExprRewriter for ExprHandler {
@@ -153,7 +295,22 @@ impl ExprRewriter for ExprHandler {
     // Note that the rewriter does not traverse into inputs of agg calls.
     fn rewrite_agg_call(&mut self, agg_call: AggCall) -> ExprImpl {
         let return_type = agg_call.return_type();
-        let (agg_kind, inputs) = agg_call.decompose();
+        let (agg_kind, inputs, distinct) = agg_call.decompose();
+        // The extreme of the distinct values is just the extreme of all values.
+        let distinct = distinct && !matches!(agg_kind, AggKind::Min | AggKind::Max);
+
+        if matches!(
+            agg_kind,
+            AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp
+        ) {
+            if distinct {
+                self.error = Some(ErrorCode::NotImplemented(
+                    format!("{} with DISTINCT", agg_kind),
+                    None.into(),
+                ));
+            }
+            return self.rewrite_variance(agg_kind, inputs, return_type);
+        }
 
         let mut index = self.project.len();
         let mut input_refs = vec![];
@@ -180,6 +337,7 @@ impl ExprRewriter for ExprHandler {
                 agg_kind: AggKind::Sum,
                 return_type: left_return_type.clone(),
                 inputs: input_refs.clone(),
+                distinct,
             });
             let left = ExprImpl::from(InputRef::new(
                 self.group_key_len + self.agg_calls.len() - 1,
@@ -196,6 +354,7 @@ impl ExprRewriter for ExprHandler {
                 agg_kind: AggKind::Count,
                 return_type: right_return_type.clone(),
                 inputs: input_refs,
+                distinct,
             });
 
             let right = InputRef::new(
@@ -209,6 +368,7 @@ impl ExprRewriter for ExprHandler {
                 agg_kind,
                 return_type: return_type.clone(),
                 inputs: input_refs,
+                distinct,
             });
             ExprImpl::from(InputRef::new(
                 self.group_key_len + self.agg_calls.len() - 1,
@@ -500,9 +660,62 @@ impl ToBatch for LogicalAgg {
     }
 }
 
+impl LogicalAgg {
+    /// Whether the partial results of all agg calls can be merged by `sum`, even when computed
+    /// from retractions.
+    fn is_two_phase_decomposable(&self) -> bool {
+        self.agg_calls.iter().all(|agg_call| {
+            !agg_call.distinct && matches!(agg_call.agg_kind, AggKind::Count | AggKind::Sum)
+        })
+    }
+
+    /// Generates a [`StreamLocalHashAgg`] pre-aggregating the rows in each actor, and a
+    /// [`StreamHashAgg`] merging the partial results after shuffling them by the group keys.
+    fn to_two_phase_stream(&self, input: PlanRef) -> PlanRef {
+        let group_key_len = self.group_keys.len();
+        let local_agg = StreamLocalHashAgg::new(LogicalAgg::new(
+            self.agg_calls.clone(),
+            vec![None; self.agg_calls.len()],
+            self.group_keys.clone(),
+            input,
+        ));
+        let exchange = StreamExchange::new(
+            local_agg.into(),
+            Distribution::HashShard((0..group_key_len).collect()),
+        );
+        let global_agg_calls = self
+            .agg_calls
+            .iter()
+            .enumerate()
+            .map(|(i, agg_call)| PlanAggCall {
+                agg_kind: AggKind::Sum,
+                return_type: agg_call.return_type.clone(),
+                inputs: vec![InputRef::new(
+                    group_key_len + i,
+                    agg_call.return_type.clone(),
+                )],
+                distinct: false,
+            })
+            .collect();
+        StreamHashAgg::new(LogicalAgg::new(
+            global_agg_calls,
+            self.agg_call_alias.clone(),
+            (0..group_key_len).collect(),
+            exchange.into(),
+        ))
+        .into()
+    }
+}
+
 impl ToStream for LogicalAgg {
     fn to_stream(&self) -> PlanRef {
-        if self.group_keys().is_empty() {
+        if self.agg_calls().is_empty() && !self.group_keys().is_empty() {
+            let required_dist = Distribution::HashShard(self.group_keys().to_vec());
+            StreamDistinct::new(
+                self.clone_with_input(self.input().to_stream_with_dist_required(&required_dist)),
+            )
+            .into()
+        } else if self.group_keys().is_empty() {
             StreamSimpleAgg::new(
                 self.clone_with_input(
                     self.input()
@@ -511,10 +724,24 @@ impl ToStream for LogicalAgg {
             )
             .into()
         } else {
+            let required_dist = Distribution::HashShard(self.group_keys().to_vec());
+            if self.is_two_phase_decomposable() {
+                let input = self.input().to_stream();
+                // Rows of a group must all be in one shard, so the input can only be reused if it
+                // is sharded by a subset of the group keys.
+                let grouped = match input.distribution() {
+                    Distribution::HashShard(keys) => {
+                        !keys.is_empty() && keys.iter().all(|key| self.group_keys.contains(key))
+                    }
+                    _ => false,
+                };
+                if !grouped {
+                    return self.to_two_phase_stream(input);
+                }
+                return StreamHashAgg::new(self.clone_with_input(input)).into();
+            }
             StreamHashAgg::new(
-                self.clone_with_input(self.input().to_stream_with_dist_required(
-                    &Distribution::HashShard(self.group_keys().to_vec()),
-                )),
+                self.clone_with_input(self.input().to_stream_with_dist_required(&required_dist)),
             )
             .into()
         }
@@ -523,6 +750,11 @@ impl ToStream for LogicalAgg {
     fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
         let (input, input_col_change) = self.input.logical_rewrite_for_stream();
         let (agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        // An agg without agg calls is planned as a `StreamDistinct`, which keeps the row counts
+        // in its own state.
+        if agg.agg_calls().is_empty() && !agg.group_keys().is_empty() {
+            return (agg.into(), out_col_change);
+        }
 
         // To rewrite StreamAgg, there are two things to do:
         // 1. insert a RowCount(Count with zero argument) at the beginning of agg_calls of
@@ -538,6 +770,7 @@ impl ToStream for LogicalAgg {
                 agg_kind: AggKind::Count,
                 return_type: DataType::Int64,
                 inputs: vec![],
+                distinct: false,
             },
         );
         agg_call_alias.insert(0, None);
@@ -619,7 +852,8 @@ mod tests {
 
         // Test case: select v1, min(v2) from test group by v1;
         {
-            let min_v2 = AggCall::new(AggKind::Min, vec![input_ref_2.clone().into()]).unwrap();
+            let min_v2 =
+                AggCall::new(AggKind::Min, vec![input_ref_2.clone().into()], false).unwrap();
             let select_exprs = vec![input_ref_1.clone().into(), min_v2.into()];
             let group_exprs = vec![input_ref_1.clone().into()];
 
@@ -637,8 +871,10 @@ mod tests {
 
         // Test case: select v1, min(v2) + max(v3) from t group by v1;
         {
-            let min_v2 = AggCall::new(AggKind::Min, vec![input_ref_2.clone().into()]).unwrap();
-            let max_v3 = AggCall::new(AggKind::Max, vec![input_ref_3.clone().into()]).unwrap();
+            let min_v2 =
+                AggCall::new(AggKind::Min, vec![input_ref_2.clone().into()], false).unwrap();
+            let max_v3 =
+                AggCall::new(AggKind::Max, vec![input_ref_3.clone().into()], false).unwrap();
             let func_call =
                 FunctionCall::new(ExprType::Add, vec![min_v2.into(), max_v3.into()]).unwrap();
             let select_exprs = vec![input_ref_1.clone().into(), ExprImpl::from(func_call)];
@@ -671,7 +907,7 @@ mod tests {
                 vec![input_ref_1.into(), input_ref_3.into()],
             )
             .unwrap();
-            let agg_call = AggCall::new(AggKind::Min, vec![v1_mult_v3.into()]).unwrap();
+            let agg_call = AggCall::new(AggKind::Min, vec![v1_mult_v3.into()], false).unwrap();
             let select_exprs = vec![input_ref_2.clone().into(), agg_call.into()];
             let group_exprs = vec![input_ref_2.into()];
 
@@ -716,6 +952,7 @@ mod tests {
             agg_kind: AggKind::Min,
             return_type: ty.clone(),
             inputs: vec![InputRef::new(2, ty.clone())],
+            distinct: false,
         };
         let agg = LogicalAgg::new(
             vec![agg_call],
@@ -775,6 +1012,7 @@ mod tests {
             agg_kind: AggKind::Min,
             return_type: ty.clone(),
             inputs: vec![InputRef::new(2, ty.clone())],
+            distinct: false,
         };
         let agg = LogicalAgg::new(
             vec![agg_call],
@@ -843,11 +1081,13 @@ mod tests {
                 agg_kind: AggKind::Min,
                 return_type: ty.clone(),
                 inputs: vec![InputRef::new(2, ty.clone())],
+                distinct: false,
             },
             PlanAggCall {
                 agg_kind: AggKind::Max,
                 return_type: ty.clone(),
                 inputs: vec![InputRef::new(1, ty.clone())],
+                distinct: false,
             },
         ];
         let agg = LogicalAgg::new(
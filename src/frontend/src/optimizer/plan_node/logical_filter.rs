@@ -21,7 +21,7 @@ use super::{
     ToBatch, ToStream,
 };
 use crate::expr::{assert_input_ref, ExprImpl};
-use crate::optimizer::plan_node::{BatchFilter, StreamFilter};
+use crate::optimizer::plan_node::{BatchFilter, BatchSeqScan, StreamFilter};
 use crate::utils::{ColIndexMapping, Condition};
 
 /// `LogicalFilter` iterates over its input and returns elements for which `predicate` evaluates to
@@ -127,6 +127,22 @@ impl ColPrunable for LogicalFilter {
 
 impl ToBatch for LogicalFilter {
     fn to_batch(&self) -> PlanRef {
+        // Push the conditions on the pk into the scan as a range of pks to read.
+        if let Some(scan) = self.input().as_logical_scan() {
+            let (scan_range, predicate) = self
+                .predicate()
+                .clone()
+                .split_to_scan_range(&scan.pk_prefix_indices());
+            if !scan_range.is_full_table_scan() {
+                let scan: PlanRef =
+                    BatchSeqScan::new_with_scan_range(scan.clone(), scan_range).into();
+                return match predicate.always_true() {
+                    true => scan,
+                    false => BatchFilter::new(LogicalFilter::new(scan, predicate)).into(),
+                };
+            }
+        }
+
         let new_input = self.input().to_batch();
         let new_logical = self.clone_with_input(new_input);
         BatchFilter::new(new_logical).into()
@@ -24,11 +24,16 @@ use super::{
 };
 use crate::expr::ExprImpl;
 use crate::optimizer::plan_node::{
-    BatchFilter, BatchHashJoin, CollectInputRef, EqJoinPredicate, LogicalFilter, StreamFilter,
+    BatchFilter, BatchHashJoin, BatchNestedLoopJoin, BatchSortMergeJoin, CollectInputRef,
+    EqJoinPredicate, LogicalFilter, StreamFilter,
 };
-use crate::optimizer::property::Distribution;
+use crate::optimizer::property::{Distribution, Order};
 use crate::utils::{ColIndexMapping, Condition};
 
+/// Cost of building the hash table with a row relative to probing it, used to choose the
+/// physical join.
+const HASH_BUILD_COST: f64 = 2.0;
+
 /// `LogicalJoin` combines two relations according to some condition.
 ///
 /// Each output row has fields from the left and right inputs. The set of output rows is a subset
@@ -330,28 +335,78 @@ impl ToBatch for LogicalJoin {
 
         let left = self.left().to_batch();
         let right = self.right().to_batch();
-        let logical_join = self.clone_with_left_right(left, right);
 
-        if predicate.has_eq() {
-            // Convert to Hash Join for equal joins
-            // For inner joins, pull non-equal conditions to a filter operator on top of it
-            let pull_filter = self.join_type == JoinType::Inner && predicate.has_non_eq();
-            if pull_filter {
-                let eq_cond = EqJoinPredicate::new(
-                    Condition::true_cond(),
-                    predicate.eq_keys().to_vec(),
-                    self.left.schema().len(),
-                );
-                let logical_join = logical_join.clone_with_cond(eq_cond.eq_cond());
-                let hash_join = BatchHashJoin::new(logical_join, eq_cond).into();
-                let logical_filter = LogicalFilter::new(hash_join, predicate.non_eq_cond());
-                BatchFilter::new(logical_filter).into()
-            } else {
-                BatchHashJoin::new(logical_join, predicate).into()
+        if !predicate.has_eq() {
+            // Convert to Nested-loop Join for non-equal joins
+            let logical_join = self.clone_with_left_right(left, right);
+            return BatchNestedLoopJoin::new(logical_join).into();
+        }
+
+        // Choose the physical join with the lowest cost, estimated on the logical inputs.
+        let left_rows = self.left.estimate_row_count();
+        let right_rows = self.right.estimate_row_count();
+        let hash_cost = left_rows + HASH_BUILD_COST * right_rows;
+        let nested_loop_cost = match self.join_type {
+            // The nested loop join executor does not support full outer join yet.
+            JoinType::FullOuter => f64::INFINITY,
+            _ => left_rows * right_rows,
+        };
+        if nested_loop_cost < hash_cost {
+            let logical_join = self.clone_with_left_right(left, right);
+            return BatchNestedLoopJoin::new(logical_join).into();
+        }
+
+        // For inner joins, pull non-equal conditions to a filter operator on top of it
+        let pull_filter = self.join_type == JoinType::Inner && predicate.has_non_eq();
+        let eq_cond = if pull_filter {
+            EqJoinPredicate::new(
+                Condition::true_cond(),
+                predicate.eq_keys().to_vec(),
+                self.left.schema().len(),
+            )
+        } else {
+            predicate.clone()
+        };
+
+        // The sort merge join executor only supports inner join without non-equal conditions.
+        let left_order = BatchSortMergeJoin::left_order(&eq_cond);
+        let right_order = BatchSortMergeJoin::right_order(&eq_cond);
+        let sort_merge_cost = match self.join_type {
+            JoinType::Inner => {
+                let sort_cost = |plan: &PlanRef, order: &Order, rows: f64| {
+                    if plan.order().satisfies(order) {
+                        0.0
+                    } else {
+                        rows * rows.max(2.0).log2()
+                    }
+                };
+                left_rows
+                    + right_rows
+                    + sort_cost(&left, &left_order, left_rows)
+                    + sort_cost(&right, &right_order, right_rows)
             }
+            _ => f64::INFINITY,
+        };
+
+        let join: PlanRef = if sort_merge_cost < hash_cost {
+            let left = left_order.enforce_if_not_satisfies(left);
+            let right = right_order.enforce_if_not_satisfies(right);
+            let logical_join = self
+                .clone_with_left_right(left, right)
+                .clone_with_cond(eq_cond.eq_cond());
+            BatchSortMergeJoin::new(logical_join, eq_cond).into()
         } else {
-            // Convert to Nested-loop Join for non-equal joins
-            todo!("nested loop join")
+            let logical_join = self
+                .clone_with_left_right(left, right)
+                .clone_with_cond(eq_cond.all_cond());
+            BatchHashJoin::new(logical_join, eq_cond).into()
+        };
+
+        if pull_filter {
+            let logical_filter = LogicalFilter::new(join, predicate.non_eq_cond());
+            BatchFilter::new(logical_filter).into()
+        } else {
+            join
         }
     }
 }
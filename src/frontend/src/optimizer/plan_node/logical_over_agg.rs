@@ -0,0 +1,216 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
+
+use super::{
+    ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, PlanTreeNodeUnary, ToBatch, ToStream,
+};
+use crate::expr::{InputRefDisplay, WindowFunctionType};
+use crate::optimizer::property::{FieldOrder, Order};
+use crate::utils::ColIndexMapping;
+
+/// Rewritten version of [`WindowFunction`](crate::expr::WindowFunction) which uses column indices
+/// of the input instead of expressions.
+#[derive(Clone)]
+pub struct PlanWindowFunction {
+    pub function_type: WindowFunctionType,
+    pub partition_by: Vec<usize>,
+    pub order_by: Order,
+}
+
+impl fmt::Debug for PlanWindowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}() over (", self.function_type)?;
+        let mut delim = "";
+        if !self.partition_by.is_empty() {
+            delim = " ";
+            write!(
+                f,
+                "partition by {:?}",
+                self.partition_by
+                    .iter()
+                    .copied()
+                    .map(InputRefDisplay)
+                    .collect_vec()
+            )?;
+        }
+        if !self.order_by.field_order.is_empty() {
+            write!(f, "{}order by {}", delim, self.order_by)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl PlanWindowFunction {
+    fn rewrite_with_mapping(&self, mapping: &ColIndexMapping) -> Self {
+        PlanWindowFunction {
+            function_type: self.function_type,
+            partition_by: self
+                .partition_by
+                .iter()
+                .map(|&index| mapping.map(index))
+                .collect(),
+            order_by: Order::new(
+                self.order_by
+                    .field_order
+                    .iter()
+                    .map(|field_order| FieldOrder {
+                        index: mapping.map(field_order.index),
+                        direct: field_order.direct,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// `LogicalOverAgg` appends the result of a window function to each row of its input, e.g. the
+/// `row_number()` of the row in its partition. It can't be executed directly and is rewritten into
+/// other operators by the optimizer, such as [`LogicalTopN`](super::LogicalTopN) for the top-N
+/// pattern.
+#[derive(Debug, Clone)]
+pub struct LogicalOverAgg {
+    pub base: PlanBase,
+    window_function: PlanWindowFunction,
+    input: PlanRef,
+}
+
+impl LogicalOverAgg {
+    fn new(window_function: PlanWindowFunction, input: PlanRef) -> Self {
+        let ctx = input.ctx();
+        let mut fields = input.schema().fields().to_vec();
+        fields.push(Field::with_name(
+            window_function.function_type.return_type(),
+            window_function.function_type.to_string(),
+        ));
+        let pk_indices = input.pk_indices().to_vec();
+        let base = PlanBase::new_logical(ctx, Schema::new(fields), pk_indices);
+        LogicalOverAgg {
+            base,
+            window_function,
+            input,
+        }
+    }
+
+    pub fn create(window_function: PlanWindowFunction, input: PlanRef) -> PlanRef {
+        Self::new(window_function, input).into()
+    }
+
+    /// Get a reference to the logical over agg's window function.
+    pub fn window_function(&self) -> &PlanWindowFunction {
+        &self.window_function
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalOverAgg {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.window_function.clone(), input)
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let window_function = self.window_function.rewrite_with_mapping(&input_col_change);
+        // The result of the window function is still the last column.
+        let (mut map, input_len) = input_col_change.into_parts();
+        map.push(Some(input_len));
+        (
+            Self::new(window_function, input),
+            ColIndexMapping::with_target_size(map, input_len + 1),
+        )
+    }
+}
+impl_plan_tree_node_for_unary! {LogicalOverAgg}
+
+impl fmt::Display for LogicalOverAgg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogicalOverAgg")
+            .field("window_function", &self.window_function)
+            .finish()
+    }
+}
+
+impl ColPrunable for LogicalOverAgg {
+    fn prune_col(&self, required_cols: &FixedBitSet) -> PlanRef {
+        self.must_contain_columns(required_cols);
+
+        let input_len = self.input.schema().len();
+        let mut input_required_cols = FixedBitSet::with_capacity(input_len);
+        input_required_cols.extend(required_cols.ones().filter(|&index| index < input_len));
+        // The window function can be removed if its result is not required.
+        if !required_cols.contains(input_len) {
+            return self.input.prune_col(&input_required_cols);
+        }
+
+        let required_input_cols_num = input_required_cols.count_ones(..);
+        input_required_cols.extend(self.window_function.partition_by.iter().copied());
+        input_required_cols.extend(
+            self.window_function
+                .order_by
+                .field_order
+                .iter()
+                .map(|field_order| field_order.index),
+        );
+        let mapping = ColIndexMapping::with_remaining_columns(&input_required_cols);
+        let window_function = self.window_function.rewrite_with_mapping(&mapping);
+        let over_agg = Self::new(window_function, self.input.prune_col(&input_required_cols));
+
+        if input_required_cols.count_ones(..) == required_input_cols_num {
+            over_agg.into()
+        } else {
+            let new_input_len = input_required_cols.count_ones(..);
+            let mut remaining_columns = FixedBitSet::with_capacity(new_input_len + 1);
+            remaining_columns.extend(required_cols.ones().map(|index| {
+                if index == input_len {
+                    new_input_len
+                } else {
+                    mapping.map(index)
+                }
+            }));
+            LogicalProject::with_mapping(
+                over_agg.into(),
+                ColIndexMapping::with_remaining_columns(&remaining_columns),
+            )
+        }
+    }
+}
+
+impl ToBatch for LogicalOverAgg {
+    fn to_batch(&self) -> PlanRef {
+        panic!("there is no batch operator for window functions");
+    }
+}
+
+impl ToStream for LogicalOverAgg {
+    fn to_stream(&self) -> PlanRef {
+        panic!("there is no stream operator for window functions");
+    }
+
+    fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream();
+        let (over_agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        (over_agg.into(), out_col_change)
+    }
+}
@@ -22,6 +22,8 @@ use risingwave_common::catalog::{ColumnDesc, Schema, TableDesc};
 use risingwave_common::error::Result;
 
 use super::{ColPrunable, PlanBase, PlanNode, PlanRef, StreamTableScan, ToBatch, ToStream};
+use crate::catalog::table_catalog::TableCatalog;
+use crate::catalog::table_statistics::TableStatistics;
 use crate::optimizer::plan_node::BatchSeqScan;
 use crate::session::OptimizerContextRef;
 use crate::utils::ColIndexMapping;
@@ -33,6 +35,12 @@ pub struct LogicalScan {
     table_name: String, // explain-only
     required_col_idx: Vec<usize>,
     table_desc: Rc<TableDesc>,
+    /// Collected by `ANALYZE`, used to estimate the cardinality.
+    stats: Option<Rc<TableStatistics>>,
+    /// The indexes of the table, which the batch scan may read from instead.
+    indexes: Vec<Rc<TableCatalog>>,
+    /// Set by `FOR SYSTEM_TIME AS OF`. Only batch scans read the table as of this epoch.
+    as_of_epoch: Option<u64>,
 }
 
 impl LogicalScan {
@@ -41,6 +49,9 @@ impl LogicalScan {
         table_name: String,           // explain-only
         required_col_idx: Vec<usize>, // the column index in the table
         table_desc: Rc<TableDesc>,
+        stats: Option<Rc<TableStatistics>>,
+        indexes: Vec<Rc<TableCatalog>>,
+        as_of_epoch: Option<u64>,
         ctx: OptimizerContextRef,
     ) -> Self {
         // here we have 3 concepts
@@ -74,6 +85,9 @@ impl LogicalScan {
             table_name,
             required_col_idx,
             table_desc,
+            stats,
+            indexes,
+            as_of_epoch,
         }
     }
 
@@ -81,12 +95,18 @@ impl LogicalScan {
     pub fn create(
         table_name: String, // explain-only
         table_desc: Rc<TableDesc>,
+        stats: Option<Rc<TableStatistics>>,
+        indexes: Vec<Rc<TableCatalog>>,
+        as_of_epoch: Option<u64>,
         ctx: OptimizerContextRef,
     ) -> Result<PlanRef> {
         Ok(Self::new(
             table_name,
             (0..table_desc.columns.len()).into_iter().collect(),
             table_desc,
+            stats,
+            indexes,
+            as_of_epoch,
             ctx,
         )
         .into())
@@ -104,12 +124,75 @@ impl LogicalScan {
         &self.table_name
     }
 
+    /// Get the indices of the scanned columns in the table desc.
+    pub fn required_col_idx(&self) -> &[usize] {
+        &self.required_col_idx
+    }
+
     /// Get a reference to the logical scan's table desc.
     #[must_use]
     pub fn table_desc(&self) -> &TableDesc {
         self.table_desc.as_ref()
     }
 
+    /// Get the indices of the leading pk columns in the output, in the order of the pk. Stops at
+    /// the first pk column which is not scanned.
+    pub fn pk_prefix_indices(&self) -> Vec<usize> {
+        self.table_desc
+            .pk
+            .iter()
+            .map_while(|c| {
+                self.required_col_idx.iter().position(|idx| {
+                    self.table_desc.columns[*idx].column_id == c.column_desc.column_id
+                })
+            })
+            .collect()
+    }
+
+    /// Get the statistics of the scanned table, if it has been analyzed.
+    pub fn stats(&self) -> Option<&TableStatistics> {
+        self.stats.as_deref()
+    }
+
+    /// Get the indexes built on the scanned table.
+    pub fn indexes(&self) -> &[Rc<TableCatalog>] {
+        &self.indexes
+    }
+
+    /// Scan the same columns from an index of the table instead. Returns `None` if the index
+    /// does not contain all of them, or if the scan reads a past epoch, when the index may not
+    /// have existed.
+    pub fn to_index_scan(&self, index: &TableCatalog) -> Option<LogicalScan> {
+        if self.as_of_epoch.is_some() {
+            return None;
+        }
+        let index_col_idx = self
+            .required_col_idx
+            .iter()
+            .map(|idx| {
+                let name = &self.table_desc.columns[*idx].name;
+                index
+                    .columns()
+                    .iter()
+                    .position(|c| &c.column_desc.name == name)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::new(
+            index.name().to_string(),
+            index_col_idx,
+            Rc::new(index.table_desc()),
+            index.stats().cloned().map(Rc::new),
+            vec![],
+            None,
+            self.base.ctx.clone(),
+        ))
+    }
+
+    /// The epoch to read the table as of, if any.
+    pub fn as_of_epoch(&self) -> Option<u64> {
+        self.as_of_epoch
+    }
+
     /// Get a reference to the logical scan's table desc.
     #[must_use]
     pub fn column_descs(&self) -> Vec<ColumnDesc> {
@@ -126,10 +209,14 @@ impl fmt::Display for LogicalScan {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "LogicalScan {{ table: {}, columns: [{}] }}",
+            "LogicalScan {{ table: {}, columns: [{}]",
             self.table_name,
             self.column_names().join(", ")
-        )
+        )?;
+        if let Some(as_of_epoch) = self.as_of_epoch {
+            write!(f, ", as_of_epoch: {}", as_of_epoch)?;
+        }
+        write!(f, " }}")
     }
 }
 
@@ -145,6 +232,9 @@ impl ColPrunable for LogicalScan {
             self.table_name.clone(),
             required_col_idx,
             self.table_desc.clone(),
+            self.stats.clone(),
+            self.indexes.clone(),
+            self.as_of_epoch,
             self.base.ctx.clone(),
         )
         .into()
@@ -188,6 +278,9 @@ impl ToStream for LogicalScan {
                         self.table_name.clone(),
                         required_col_idx,
                         self.table_desc.clone(),
+                        self.stats.clone(),
+                        self.indexes.clone(),
+                        self.as_of_epoch,
                         self.base.ctx.clone(),
                     )
                     .into(),
@@ -15,13 +15,18 @@
 use std::fmt;
 
 use fixedbitset::FixedBitSet;
+use itertools::Itertools;
 
-use super::{ColPrunable, PlanBase, PlanNode, PlanRef, PlanTreeNodeUnary, ToBatch, ToStream};
+use super::{
+    ColPrunable, PlanBase, PlanNode, PlanRef, PlanTreeNodeUnary, StreamGroupTopN, ToBatch, ToStream,
+};
+use crate::expr::InputRefDisplay;
 use crate::optimizer::plan_node::LogicalProject;
-use crate::optimizer::property::{FieldOrder, Order};
+use crate::optimizer::property::{Distribution, FieldOrder, Order};
 use crate::utils::ColIndexMapping;
 
-/// `LogicalTopN` sorts the input data and fetches up to `limit` rows from `offset`
+/// `LogicalTopN` sorts the input data and fetches up to `limit` rows from `offset`. If there are
+/// group keys, the rows are fetched for each group.
 #[derive(Debug, Clone)]
 pub struct LogicalTopN {
     pub base: PlanBase,
@@ -29,10 +34,17 @@ pub struct LogicalTopN {
     limit: usize,
     offset: usize,
     order: Order,
+    group_keys: Vec<usize>,
 }
 
 impl LogicalTopN {
-    fn new(input: PlanRef, limit: usize, offset: usize, order: Order) -> Self {
+    fn new(
+        input: PlanRef,
+        limit: usize,
+        offset: usize,
+        order: Order,
+        group_keys: Vec<usize>,
+    ) -> Self {
         let ctx = input.ctx();
         let schema = input.schema().clone();
         let pk_indices = input.pk_indices().to_vec();
@@ -43,12 +55,60 @@ impl LogicalTopN {
             limit,
             offset,
             order,
+            group_keys,
         }
     }
 
     /// the function will check if the cond is bool expression
     pub fn create(input: PlanRef, limit: usize, offset: usize, order: Order) -> PlanRef {
-        Self::new(input, limit, offset, order).into()
+        Self::new(input, limit, offset, order, vec![]).into()
+    }
+
+    /// Create a [`LogicalTopN`] which fetches the rows of each group.
+    pub fn with_group(
+        input: PlanRef,
+        limit: usize,
+        offset: usize,
+        order: Order,
+        group_keys: Vec<usize>,
+    ) -> PlanRef {
+        Self::new(input, limit, offset, order, group_keys).into()
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn order(&self) -> &Order {
+        &self.order
+    }
+
+    pub fn group_keys(&self) -> &[usize] {
+        &self.group_keys
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        let mut builder = f.debug_struct(name);
+        builder
+            .field("order", &format_args!("{}", self.order))
+            .field("limit", &self.limit)
+            .field("offset", &self.offset);
+        if !self.group_keys.is_empty() {
+            builder.field(
+                "group_keys",
+                &self
+                    .group_keys
+                    .iter()
+                    .copied()
+                    .map(InputRefDisplay)
+                    .collect_vec(),
+            );
+        }
+        builder.finish()
     }
 }
 
@@ -58,7 +118,13 @@ impl PlanTreeNodeUnary for LogicalTopN {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(input, self.limit, self.offset, self.order.clone())
+        Self::new(
+            input,
+            self.limit,
+            self.offset,
+            self.order.clone(),
+            self.group_keys.clone(),
+        )
     }
 
     #[must_use]
@@ -75,6 +141,10 @@ impl PlanTreeNodeUnary for LogicalTopN {
                 input_col_change
                     .rewrite_required_order(&self.order)
                     .unwrap(),
+                self.group_keys
+                    .iter()
+                    .map(|&key| input_col_change.map(key))
+                    .collect(),
             ),
             input_col_change,
         )
@@ -82,8 +152,8 @@ impl PlanTreeNodeUnary for LogicalTopN {
 }
 impl_plan_tree_node_for_unary! {LogicalTopN}
 impl fmt::Display for LogicalTopN {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalTopN")
     }
 }
 
@@ -96,6 +166,7 @@ impl ColPrunable for LogicalTopN {
             .field_order
             .iter()
             .for_each(|fo| input_required_cols.insert(fo.index));
+        input_required_cols.extend(self.group_keys.iter().copied());
 
         let mapping = ColIndexMapping::with_remaining_columns(&input_required_cols);
         let new_order = Order {
@@ -109,8 +180,20 @@ impl ColPrunable for LogicalTopN {
                 })
                 .collect(),
         };
-        let new_input = self.input.prune_col(required_cols);
-        let top_n = Self::new(new_input, self.limit, self.offset, new_order).into();
+        let new_group_keys = self
+            .group_keys
+            .iter()
+            .map(|&key| mapping.map(key))
+            .collect();
+        let new_input = self.input.prune_col(&input_required_cols);
+        let top_n = Self::new(
+            new_input,
+            self.limit,
+            self.offset,
+            new_order,
+            new_group_keys,
+        )
+        .into();
 
         if *required_cols == input_required_cols {
             top_n
@@ -133,7 +216,14 @@ impl ToBatch for LogicalTopN {
 
 impl ToStream for LogicalTopN {
     fn to_stream(&self) -> PlanRef {
-        todo!()
+        assert!(
+            !self.group_keys.is_empty(),
+            "there is no top-n stream operator without group keys"
+        );
+        let input = self
+            .input()
+            .to_stream_with_dist_required(&Distribution::HashShard(self.group_keys.clone()));
+        StreamGroupTopN::new(self.clone_with_input(input)).into()
     }
 
     fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
@@ -0,0 +1,90 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::DataType;
+
+use super::{
+    BatchUnnest, ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, ToBatch, ToStream,
+};
+use crate::expr::{Expr, ExprImpl};
+use crate::session::OptimizerContextRef;
+use crate::utils::ColIndexMapping;
+
+/// `LogicalUnnest` expands a constant array into a set of rows, one for each element. The array is
+/// either a list or a jsonb array.
+#[derive(Debug, Clone)]
+pub struct LogicalUnnest {
+    pub base: PlanBase,
+    array: ExprImpl,
+}
+
+impl LogicalUnnest {
+    pub fn new(array: ExprImpl, ctx: OptimizerContextRef) -> Self {
+        let (element_type, column_name) = match array.return_type() {
+            DataType::List { datatype } => (*datatype, "unnest"),
+            DataType::Jsonb => (DataType::Jsonb, "value"),
+            t => panic!("unnest expects an array, got {:?}", t),
+        };
+        let schema = Schema::new(vec![Field::with_name(element_type, column_name)]);
+        let base = PlanBase::new_logical(ctx, schema, vec![]);
+        Self { base, array }
+    }
+
+    pub fn create(array: ExprImpl, ctx: OptimizerContextRef) -> PlanRef {
+        Self::new(array, ctx).into()
+    }
+
+    /// Get a reference to the logical unnest's array.
+    pub fn array(&self) -> &ExprImpl {
+        &self.array
+    }
+}
+
+impl_plan_tree_node_for_leaf! { LogicalUnnest }
+
+impl fmt::Display for LogicalUnnest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogicalUnnest")
+            .field("array", &self.array)
+            .finish()
+    }
+}
+
+impl ColPrunable for LogicalUnnest {
+    fn prune_col(&self, required_cols: &FixedBitSet) -> PlanRef {
+        self.must_contain_columns(required_cols);
+        let mapping = ColIndexMapping::with_remaining_columns(required_cols);
+        LogicalProject::with_mapping(self.clone().into(), mapping)
+    }
+}
+
+impl ToBatch for LogicalUnnest {
+    fn to_batch(&self) -> PlanRef {
+        BatchUnnest::new(self.clone()).into()
+    }
+}
+
+impl ToStream for LogicalUnnest {
+    fn to_stream(&self) -> PlanRef {
+        unimplemented!("Stream unnest executor is unimplemented!")
+    }
+
+    fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
+        unimplemented!("Stream unnest executor is unimplemented!")
+    }
+}
@@ -216,10 +216,13 @@ mod batch_hash_agg;
 mod batch_hash_join;
 mod batch_insert;
 mod batch_limit;
+mod batch_nested_loop_join;
 mod batch_project;
 mod batch_seq_scan;
 mod batch_simple_agg;
 mod batch_sort;
+mod batch_sort_merge_join;
+mod batch_unnest;
 mod batch_values;
 mod logical_agg;
 mod logical_apply;
@@ -228,15 +231,20 @@ mod logical_filter;
 mod logical_insert;
 mod logical_join;
 mod logical_limit;
+mod logical_over_agg;
 mod logical_project;
 mod logical_scan;
 mod logical_source;
 mod logical_topn;
+mod logical_unnest;
 mod logical_values;
+mod stream_distinct;
 mod stream_exchange;
 mod stream_filter;
+mod stream_group_topn;
 mod stream_hash_agg;
 mod stream_hash_join;
+mod stream_local_hash_agg;
 mod stream_materialize;
 mod stream_project;
 mod stream_simple_agg;
@@ -250,10 +258,13 @@ pub use batch_hash_agg::BatchHashAgg;
 pub use batch_hash_join::BatchHashJoin;
 pub use batch_insert::BatchInsert;
 pub use batch_limit::BatchLimit;
+pub use batch_nested_loop_join::BatchNestedLoopJoin;
 pub use batch_project::BatchProject;
 pub use batch_seq_scan::BatchSeqScan;
 pub use batch_simple_agg::BatchSimpleAgg;
 pub use batch_sort::BatchSort;
+pub use batch_sort_merge_join::BatchSortMergeJoin;
+pub use batch_unnest::BatchUnnest;
 pub use batch_values::BatchValues;
 pub use logical_agg::{LogicalAgg, PlanAggCall};
 pub use logical_apply::LogicalApply;
@@ -262,15 +273,20 @@ pub use logical_filter::LogicalFilter;
 pub use logical_insert::LogicalInsert;
 pub use logical_join::LogicalJoin;
 pub use logical_limit::LogicalLimit;
+pub use logical_over_agg::{LogicalOverAgg, PlanWindowFunction};
 pub use logical_project::LogicalProject;
 pub use logical_scan::LogicalScan;
 pub use logical_source::LogicalSource;
 pub use logical_topn::LogicalTopN;
+pub use logical_unnest::LogicalUnnest;
 pub use logical_values::LogicalValues;
+pub use stream_distinct::StreamDistinct;
 pub use stream_exchange::StreamExchange;
 pub use stream_filter::StreamFilter;
+pub use stream_group_topn::StreamGroupTopN;
 pub use stream_hash_agg::StreamHashAgg;
 pub use stream_hash_join::StreamHashJoin;
+pub use stream_local_hash_agg::StreamLocalHashAgg;
 pub use stream_materialize::StreamMaterialize;
 pub use stream_project::StreamProject;
 pub use stream_simple_agg::StreamSimpleAgg;
@@ -306,8 +322,10 @@ macro_rules! for_all_plan_nodes {
             ,{ Logical, Delete }
             ,{ Logical, Join }
             ,{ Logical, Values }
+            ,{ Logical, Unnest }
             ,{ Logical, Limit }
             ,{ Logical, TopN }
+            ,{ Logical, OverAgg }
             // ,{ Logical, Sort } we don't need a LogicalSort, just require the Order
             ,{ Batch, SimpleAgg }
             ,{ Batch, HashAgg }
@@ -317,7 +335,10 @@ macro_rules! for_all_plan_nodes {
             ,{ Batch, Delete }
             ,{ Batch, SeqScan }
             ,{ Batch, HashJoin }
+            ,{ Batch, NestedLoopJoin }
+            ,{ Batch, SortMergeJoin }
             ,{ Batch, Values }
+            ,{ Batch, Unnest }
             ,{ Batch, Sort }
             ,{ Batch, Exchange }
             ,{ Batch, Limit }
@@ -328,8 +349,11 @@ macro_rules! for_all_plan_nodes {
             ,{ Stream, HashJoin }
             ,{ Stream, Exchange }
             ,{ Stream, HashAgg }
+            ,{ Stream, LocalHashAgg }
             ,{ Stream, SimpleAgg }
             ,{ Stream, Materialize }
+            ,{ Stream, GroupTopN }
+            ,{ Stream, Distinct }
         }
     };
 }
@@ -349,8 +373,10 @@ macro_rules! for_logical_plan_nodes {
             ,{ Logical, Delete }
             ,{ Logical, Join }
             ,{ Logical, Values }
+            ,{ Logical, Unnest }
             ,{ Logical, Limit }
             ,{ Logical, TopN }
+            ,{ Logical, OverAgg }
             // ,{ Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
             // if we dont support thatk, we don't need LogicalSort, just require the Order at the top of query
         }
@@ -369,7 +395,10 @@ macro_rules! for_batch_plan_nodes {
             ,{ Batch, Filter }
             ,{ Batch, SeqScan }
             ,{ Batch, HashJoin }
+            ,{ Batch, NestedLoopJoin }
+            ,{ Batch, SortMergeJoin }
             ,{ Batch, Values }
+            ,{ Batch, Unnest }
             ,{ Batch, Limit }
             ,{ Batch, Sort }
             ,{ Batch, Exchange }
@@ -392,8 +421,11 @@ macro_rules! for_stream_plan_nodes {
             ,{ Stream, TableScan }
             ,{ Stream, Source }
             ,{ Stream, HashAgg }
+            ,{ Stream, LocalHashAgg }
             ,{ Stream, SimpleAgg }
             ,{ Stream, Materialize }
+            ,{ Stream, GroupTopN }
+            ,{ Stream, Distinct }
         }
     };
 }
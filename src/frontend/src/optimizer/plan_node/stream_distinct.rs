@@ -0,0 +1,111 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_pb::stream_plan::stream_node::Node as ProstStreamNode;
+
+use super::{LogicalAgg, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::expr::InputRefDisplay;
+use crate::optimizer::property::Distribution;
+
+/// `StreamDistinct` outputs the distinct rows of the group keys of a [`LogicalAgg`] without agg
+/// calls. If the input is append-only, it is executed by the cheaper append-only dedup executor,
+/// which keeps the output append-only.
+#[derive(Debug, Clone)]
+pub struct StreamDistinct {
+    pub base: PlanBase,
+    logical: LogicalAgg,
+}
+
+impl StreamDistinct {
+    pub fn new(logical: LogicalAgg) -> Self {
+        assert!(logical.agg_calls().is_empty() && !logical.group_keys().is_empty());
+        let ctx = logical.base.ctx.clone();
+        let pk_indices = (0..logical.group_keys().len()).collect();
+        let input = logical.input();
+        let input_dist = input.distribution();
+        let dist = match input_dist {
+            Distribution::Single => Distribution::Single,
+            Distribution::HashShard(_) => {
+                assert!(
+                    input_dist.satisfies(&Distribution::HashShard(logical.group_keys().to_vec()))
+                );
+                logical
+                    .i2o_col_mapping()
+                    .rewrite_provided_distribution(input_dist)
+            }
+            _ => panic!(),
+        };
+        let append_only = input.append_only();
+        let base =
+            PlanBase::new_stream(ctx, logical.schema().clone(), pk_indices, dist, append_only);
+        StreamDistinct { base, logical }
+    }
+
+    pub fn distinct_keys(&self) -> &[usize] {
+        self.logical.group_keys()
+    }
+}
+
+impl fmt::Display for StreamDistinct {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = if self.append_only() {
+            "StreamAppendOnlyDedup"
+        } else {
+            "StreamDistinct"
+        };
+        f.debug_struct(name)
+            .field(
+                "distinct_keys",
+                &self
+                    .distinct_keys()
+                    .iter()
+                    .copied()
+                    .map(InputRefDisplay)
+                    .collect_vec(),
+            )
+            .finish()
+    }
+}
+
+impl PlanTreeNodeUnary for StreamDistinct {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+impl_plan_tree_node_for_unary! { StreamDistinct }
+
+impl ToStreamProst for StreamDistinct {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+
+        let node = DistinctNode {
+            distinct_keys: self
+                .distinct_keys()
+                .iter()
+                .map(|idx| *idx as i32)
+                .collect_vec(),
+        };
+        if self.append_only() {
+            ProstStreamNode::AppendOnlyDedupNode(node)
+        } else {
+            ProstStreamNode::DistinctNode(node)
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_pb::stream_plan::stream_node::Node as ProstStreamNode;
+
+use super::{LogicalTopN, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::optimizer::property::Direction;
+
+/// `StreamGroupTopN` keeps the top `limit` rows of each group. The executor orders the rows by the
+/// primary key, so the order columns are put at the front of the primary key of the input.
+#[derive(Debug, Clone)]
+pub struct StreamGroupTopN {
+    pub base: PlanBase,
+    logical: LogicalTopN,
+}
+
+impl StreamGroupTopN {
+    pub fn new(logical: LogicalTopN) -> Self {
+        assert!(!logical.group_keys().is_empty());
+        assert_eq!(
+            logical.offset(),
+            0,
+            "offset of group top-n is not supported"
+        );
+        let ctx = logical.base.ctx.clone();
+        let input = logical.input();
+        let mut pk_indices = logical
+            .order()
+            .field_order
+            .iter()
+            .map(|field_order| field_order.index)
+            .collect_vec();
+        for index in input.pk_indices() {
+            if !pk_indices.contains(index) {
+                pk_indices.push(*index);
+            }
+        }
+        let dist = input.distribution().clone();
+        let base = PlanBase::new_stream(ctx, logical.base.schema.clone(), pk_indices, dist, false);
+        StreamGroupTopN { base, logical }
+    }
+
+    /// The order types of the primary key, where the columns which are not order columns are
+    /// ascending.
+    fn pk_order_types(&self) -> Vec<Direction> {
+        let field_order = &self.logical.order().field_order;
+        (0..self.base.pk_indices.len())
+            .map(|i| match field_order.get(i) {
+                Some(field_order) => field_order.direct,
+                None => Direction::Asc,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for StreamGroupTopN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamGroupTopN")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamGroupTopN {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+impl_plan_tree_node_for_unary! { StreamGroupTopN }
+
+impl ToStreamProst for StreamGroupTopN {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+
+        let group_keys = self
+            .logical
+            .group_keys()
+            .iter()
+            .map(|idx| *idx as i32)
+            .collect_vec();
+        ProstStreamNode::GroupTopNNode(GroupTopNNode {
+            order_types: self
+                .pk_order_types()
+                .into_iter()
+                .map(|direct| direct.to_protobuf() as i32)
+                .collect_vec(),
+            limit: self.logical.limit() as u64,
+            group_keys: group_keys.clone(),
+            distribution_keys: group_keys,
+        })
+    }
+}
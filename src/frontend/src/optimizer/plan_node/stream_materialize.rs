@@ -151,6 +151,8 @@ impl StreamMaterialize {
             name: mv_name,
             columns,
             pk_desc,
+            stats: None,
+            index_on_id: None,
         };
 
         Ok(Self { base, input, table })
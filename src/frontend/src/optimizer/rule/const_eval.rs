@@ -0,0 +1,84 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::super::plan_node::*;
+use super::{BoxedRule, Rule};
+use crate::expr::{fold_const, ExprImpl};
+
+/// Evaluates the constant sub-expressions in the expressions of [`LogicalFilter`],
+/// [`LogicalProject`], [`LogicalJoin`] and [`LogicalValues`].
+pub struct ConstEvalRule {}
+impl Rule for ConstEvalRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        if let Some(filter) = plan.as_logical_filter() {
+            let predicate = filter.predicate().clone().fold_const();
+            if predicate.conjunctions == filter.predicate().conjunctions {
+                return None;
+            }
+            return Some(LogicalFilter::create(filter.input(), predicate));
+        }
+        if let Some(project) = plan.as_logical_project() {
+            let exprs = fold_exprs(project.exprs())?;
+            return Some(
+                LogicalProject::new(project.input(), exprs, project.expr_alias().to_vec()).into(),
+            );
+        }
+        if let Some(join) = plan.as_logical_join() {
+            let on = join.on().clone().fold_const();
+            if on.conjunctions == join.on().conjunctions {
+                return None;
+            }
+            return Some(join.clone_with_cond(on).into());
+        }
+        if let Some(values) = plan.as_logical_values() {
+            let mut changed = false;
+            let rows = values
+                .rows()
+                .iter()
+                .map(|row| match fold_exprs(row) {
+                    Some(row) => {
+                        changed = true;
+                        row
+                    }
+                    None => row.clone(),
+                })
+                .collect();
+            if !changed {
+                return None;
+            }
+            return Some(LogicalValues::create(
+                rows,
+                values.schema().clone(),
+                values.base.ctx.clone(),
+            ));
+        }
+        None
+    }
+}
+
+/// Folds the expressions, returning `None` if none of them changes.
+fn fold_exprs(exprs: &[ExprImpl]) -> Option<Vec<ExprImpl>> {
+    let folded: Vec<_> = exprs.iter().cloned().map(fold_const).collect();
+    if folded[..] == exprs[..] {
+        None
+    } else {
+        Some(folded)
+    }
+}
+
+impl ConstEvalRule {
+    pub fn create() -> BoxedRule {
+        Box::new(ConstEvalRule {})
+    }
+}
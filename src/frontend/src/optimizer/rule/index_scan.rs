@@ -0,0 +1,91 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::super::plan_node::*;
+use super::Rule;
+use crate::catalog::table_catalog::TableCatalog;
+use crate::expr::{ExprImpl, ExprType};
+use crate::optimizer::rule::BoxedRule;
+
+/// Rewrites a filter on a table scan into a filter on the scan of an index of the table, if the
+/// leading column of the index is compared with a constant by the predicate. Indexes with an
+/// equality on the leading column are preferred over those with only a range. Scans of past epochs
+/// are left unchanged, as the index may not have existed then.
+pub struct IndexScanRule {}
+
+impl Rule for IndexScanRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let filter = plan.as_logical_filter()?;
+        let input = filter.input();
+        let scan = input.as_logical_scan()?;
+        if scan.indexes().is_empty() || scan.as_of_epoch().is_some() {
+            return None;
+        }
+
+        // The names of the columns compared with constants, with whether it's an equality.
+        let constrained = filter
+            .predicate()
+            .conjunctions
+            .iter()
+            .filter_map(constrained_column)
+            .map(|(index, is_eq)| {
+                let column_idx = scan.required_col_idx()[index];
+                (scan.table_desc().columns[column_idx].name.as_str(), is_eq)
+            })
+            .collect::<Vec<_>>();
+        let leading_column = |index: &TableCatalog| {
+            let name = index.pk_desc().first()?.column_desc.name.as_str();
+            constrained
+                .iter()
+                .filter(|(column, _)| *column == name)
+                .map(|(_, is_eq)| *is_eq)
+                .max()
+        };
+
+        let (_, index) = scan
+            .indexes()
+            .iter()
+            .filter_map(|index| Some((leading_column(index.as_ref())?, index)))
+            // `max_by_key` returns the last maximum, so reverse to prefer the first index
+            .rev()
+            .max_by_key(|(is_eq, _)| *is_eq)?;
+        let index_scan = scan.to_index_scan(index)?;
+        Some(filter.clone_with_input(index_scan.into()).into())
+    }
+}
+
+impl IndexScanRule {
+    pub fn create() -> BoxedRule {
+        Box::new(IndexScanRule {})
+    }
+}
+
+/// If the expression compares a column with a literal, returns the column index and whether the
+/// comparison is an equality.
+fn constrained_column(expr: &ExprImpl) -> Option<(usize, bool)> {
+    let func_call = expr.as_function_call()?;
+    let is_eq = match func_call.get_expr_type() {
+        ExprType::Equal => true,
+        ExprType::LessThan
+        | ExprType::LessThanOrEqual
+        | ExprType::GreaterThan
+        | ExprType::GreaterThanOrEqual => false,
+        _ => return None,
+    };
+    match func_call.inputs() {
+        [ExprImpl::InputRef(input_ref), ExprImpl::Literal(_)]
+        | [ExprImpl::Literal(_), ExprImpl::InputRef(input_ref)] => Some((input_ref.index(), is_eq)),
+        _ => None,
+    }
+}
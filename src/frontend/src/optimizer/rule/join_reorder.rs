@@ -0,0 +1,390 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use fixedbitset::FixedBitSet;
+use risingwave_pb::plan::JoinType;
+
+use super::super::plan_node::*;
+use super::Rule;
+use crate::expr::ExprImpl;
+use crate::optimizer::rule::BoxedRule;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// The reordered joins are only taken when they reduce the estimated cost at least by this ratio,
+/// so that plans stay stable under small estimation errors.
+const MIN_COST_RATIO: f64 = 0.9;
+
+/// Reorders a tree of inner joins by the estimated cardinalities of the intermediate results.
+///
+/// The inner joins are flattened into a list of relations and the conjunctions of all their `on`
+/// conditions. The new left-deep tree starts from the connected pair with the smallest join
+/// result, and then greedily joins the connected relation producing the fewest rows. A cross join
+/// is only introduced when no remaining relation is connected.
+///
+/// The rule only applies when every table below the joins has been analyzed, and a project is put
+/// on top of the new tree to restore the original column order.
+pub struct JoinReorderRule {}
+
+impl Rule for JoinReorderRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let join = plan.as_logical_join()?;
+        if join.join_type() != JoinType::Inner {
+            return None;
+        }
+
+        let mut graph = JoinGraph::default();
+        let original_cost = graph.flatten(plan.clone());
+        if graph.relations.len() < 3 || !graph.relations.iter().all(is_analyzed) {
+            return None;
+        }
+
+        let (tree, cost) = graph.reorder()?;
+        if cost >= original_cost * MIN_COST_RATIO {
+            return None;
+        }
+        let mapping = ColIndexMapping::new(tree.columns.into_iter().map(Some).collect());
+        Some(LogicalProject::with_mapping(tree.plan, mapping))
+    }
+}
+
+impl JoinReorderRule {
+    pub fn create() -> BoxedRule {
+        Box::new(JoinReorderRule {})
+    }
+}
+
+/// Whether all the tables scanned by the plan have statistics.
+fn is_analyzed(plan: &PlanRef) -> bool {
+    match plan.as_logical_scan() {
+        Some(scan) => scan.stats().is_some(),
+        None => plan.inputs().iter().all(is_analyzed),
+    }
+}
+
+/// Relations joined by inner joins, whose columns are concatenated into a single column space.
+#[derive(Default)]
+struct JoinGraph {
+    relations: Vec<PlanRef>,
+    /// The first column of each relation.
+    offsets: Vec<usize>,
+    /// The conjunctions of the join conditions, with the relations referenced by each of them.
+    conjunctions: Vec<(ExprImpl, FixedBitSet)>,
+}
+
+impl JoinGraph {
+    fn column_num(&self) -> usize {
+        self.relations.iter().map(|r| r.schema().len()).sum()
+    }
+
+    /// Collect the relations and conditions of the inner joins, returning the estimated cost of
+    /// the joins, i.e. the total number of rows they produce.
+    fn flatten(&mut self, plan: PlanRef) -> f64 {
+        let join = match plan.as_logical_join() {
+            Some(join) if join.join_type() == JoinType::Inner => join,
+            _ => {
+                self.offsets.push(self.column_num());
+                self.relations.push(plan);
+                return 0.0;
+            }
+        };
+        let offset = self.column_num();
+        let cost = self.flatten(join.left()) + self.flatten(join.right());
+        let mut shift = ColIndexMapping::with_shift_offset(join.schema().len(), offset as isize);
+        let on = join.on().clone().rewrite_expr(&mut shift);
+        self.conjunctions.extend(
+            on.conjunctions
+                .into_iter()
+                .map(|expr| (expr, FixedBitSet::with_capacity(0))),
+        );
+        cost + plan.estimate_row_count()
+    }
+
+    fn relation_of(&self, column: usize) -> usize {
+        self.offsets.partition_point(|offset| *offset <= column) - 1
+    }
+
+    /// Fill in the relations referenced by each conjunction.
+    fn resolve_references(&mut self) {
+        let column_num = self.column_num();
+        let relation_num = self.relations.len();
+        let references = self
+            .conjunctions
+            .iter()
+            .map(|(expr, _)| {
+                let mut relations = FixedBitSet::with_capacity(relation_num);
+                relations.extend(
+                    expr.collect_input_refs(column_num)
+                        .ones()
+                        .map(|column| self.relation_of(column)),
+                );
+                relations
+            })
+            .collect::<Vec<_>>();
+        for ((_, relations), resolved) in self.conjunctions.iter_mut().zip(references) {
+            *relations = resolved;
+        }
+    }
+
+    /// Whether some conjunction can be evaluated after joining `right` with `left`, and refers to
+    /// both of them.
+    fn connected(&self, left: &FixedBitSet, right: usize) -> bool {
+        self.conjunctions.iter().any(|(_, relations)| {
+            relations.contains(right)
+                && relations.ones().any(|r| left.contains(r))
+                && relations.ones().all(|r| r == right || left.contains(r))
+        })
+    }
+
+    /// Start a join tree from a single relation.
+    fn leaf(&self, relation: usize) -> JoinTree {
+        let mut relations = FixedBitSet::with_capacity(self.relations.len());
+        relations.insert(relation);
+        let offset = self.offsets[relation];
+        JoinTree {
+            plan: self.relations[relation].clone(),
+            columns: (offset..offset + self.relations[relation].schema().len()).collect(),
+            relations,
+            applied: FixedBitSet::with_capacity(self.conjunctions.len()),
+        }
+    }
+
+    /// Join the relation `right` to the tree. All the conjunctions which can be evaluated on the
+    /// join and are not applied yet are used as its condition.
+    fn join(&self, left: &JoinTree, right: usize) -> JoinTree {
+        let right = self.leaf(right);
+        let mut relations = left.relations.clone();
+        relations.union_with(&right.relations);
+        let columns = [left.columns.as_slice(), right.columns.as_slice()].concat();
+
+        let mut map = vec![None; self.column_num()];
+        for (position, column) in columns.iter().enumerate() {
+            map[*column] = Some(position);
+        }
+        let mut mapping = ColIndexMapping::with_target_size(map, columns.len());
+
+        let mut applied = left.applied.clone();
+        let mut on = Condition::true_cond();
+        for (i, (expr, referenced)) in self.conjunctions.iter().enumerate() {
+            if !applied.contains(i) && referenced.is_subset(&relations) {
+                applied.insert(i);
+                on = on.and(Condition::with_expr(expr.clone()).rewrite_expr(&mut mapping));
+            }
+        }
+        let plan = LogicalJoin::new(left.plan.clone(), right.plan, JoinType::Inner, on).into();
+        JoinTree {
+            plan,
+            columns,
+            relations,
+            applied,
+        }
+    }
+
+    /// Build a greedily ordered left-deep join tree, returning it with its estimated cost.
+    fn reorder(&mut self) -> Option<(JoinTree, f64)> {
+        self.resolve_references();
+        let relation_num = self.relations.len();
+
+        // Start from the connected pair with the smallest result, probing with the larger one.
+        let mut best: Option<(JoinTree, f64)> = None;
+        for left in 0..relation_num {
+            let left = self.leaf(left);
+            let left_rows = left.plan.estimate_row_count();
+            for right in 0..relation_num {
+                if left.relations.contains(right)
+                    || !self.connected(&left.relations, right)
+                    || self.relations[right].estimate_row_count() > left_rows
+                {
+                    continue;
+                }
+                let join = self.join(&left, right);
+                let rows = join.plan.estimate_row_count();
+                if best
+                    .as_ref()
+                    .map_or(true, |(_, best_rows)| rows < *best_rows)
+                {
+                    best = Some((join, rows));
+                }
+            }
+        }
+        let (mut tree, mut cost) = best?;
+
+        while tree.relations.count_ones(..) < relation_num {
+            let remaining = (0..relation_num).filter(|r| !tree.relations.contains(*r));
+            let connected = remaining
+                .clone()
+                .filter(|r| self.connected(&tree.relations, *r))
+                .collect::<Vec<_>>();
+            let candidates = if connected.is_empty() {
+                remaining.collect()
+            } else {
+                connected
+            };
+
+            let (join, rows) = candidates
+                .into_iter()
+                .map(|right| {
+                    let join = self.join(&tree, right);
+                    let rows = join.plan.estimate_row_count();
+                    (join, rows)
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .unwrap();
+            tree = join;
+            cost += rows;
+        }
+
+        Some((tree, cost))
+    }
+}
+
+/// A partially built join tree.
+struct JoinTree {
+    plan: PlanRef,
+    /// The index of each output column in the flattened columns.
+    columns: Vec<usize>,
+    /// The joined relations.
+    relations: FixedBitSet,
+    /// The conjunctions already used as join conditions.
+    applied: FixedBitSet,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use risingwave_common::catalog::{ColumnDesc, TableDesc};
+    use risingwave_common::types::DataType;
+    use risingwave_pb::expr::expr_node::Type;
+
+    use super::*;
+    use crate::catalog::table_statistics::{ColumnStatistics, TableStatistics};
+    use crate::expr::{FunctionCall, InputRef};
+    use crate::session::{OptimizerContext, OptimizerContextRef};
+
+    /// A table with a single unique int column.
+    fn scan(ctx: OptimizerContextRef, name: &str, row_count: u64) -> PlanRef {
+        let column = ColumnDesc::unnamed(0.into(), DataType::Int32);
+        let stats = TableStatistics {
+            row_count,
+            columns: HashMap::from([(
+                column.column_id,
+                ColumnStatistics {
+                    null_count: 0,
+                    ndv: row_count,
+                    histogram_bounds: vec![],
+                },
+            )]),
+        };
+        let table_desc = TableDesc {
+            table_id: 0.into(),
+            pk: vec![],
+            columns: vec![column],
+        };
+        LogicalScan::create(
+            name.to_string(),
+            Rc::new(table_desc),
+            Some(Rc::new(stats)),
+            vec![],
+            None,
+            ctx,
+        )
+        .unwrap()
+    }
+
+    fn eq(left: usize, right: usize) -> ExprImpl {
+        FunctionCall::new(
+            Type::Equal,
+            vec![
+                InputRef::new(left, DataType::Int32).into(),
+                InputRef::new(right, DataType::Int32).into(),
+            ],
+        )
+        .unwrap()
+        .into()
+    }
+
+    fn table_name(plan: &PlanRef) -> &str {
+        plan.as_logical_scan().unwrap().table_name()
+    }
+
+    /// Reorder
+    /// ```text
+    /// Join(on: $1 = $2)
+    ///   Join(on: $0 = $1)
+    ///     Scan(a, 1000 rows)
+    ///     Scan(b, 1000 rows)
+    ///   Scan(c, 10 rows)
+    /// ```
+    /// into
+    /// ```text
+    /// Project($2, $0, $1)
+    ///   Join(on: $2 = $0)
+    ///     Join(on: $0 = $1)
+    ///       Scan(b)
+    ///       Scan(c)
+    ///     Scan(a)
+    /// ```
+    #[tokio::test]
+    async fn test_join_reorder() {
+        let ctx = OptimizerContext::mock().await;
+        let a = scan(ctx.clone(), "a", 1000);
+        let b = scan(ctx.clone(), "b", 1000);
+        let c = scan(ctx, "c", 10);
+        let ab = LogicalJoin::create(a, b, JoinType::Inner, eq(0, 1));
+        let abc = LogicalJoin::create(ab, c, JoinType::Inner, eq(1, 2));
+
+        let plan = JoinReorderRule::create().apply(abc).unwrap();
+        let project = plan.as_logical_project().unwrap();
+        let output = project
+            .exprs()
+            .iter()
+            .map(|expr| expr.as_input_ref().unwrap().index())
+            .collect::<Vec<_>>();
+        assert_eq!(output, vec![2, 0, 1]);
+
+        let top = project.input();
+        let top = top.as_logical_join().unwrap();
+        assert_eq!(table_name(&top.right()), "a");
+        assert_eq!(top.on().to_string(), "($2 = $0)");
+        let bottom = top.left();
+        let bottom = bottom.as_logical_join().unwrap();
+        assert_eq!(table_name(&bottom.left()), "b");
+        assert_eq!(table_name(&bottom.right()), "c");
+        assert_eq!(bottom.on().to_string(), "($0 = $1)");
+    }
+
+    #[tokio::test]
+    async fn test_join_reorder_without_stats() {
+        let ctx = OptimizerContext::mock().await;
+        let a = scan(ctx.clone(), "a", 1000);
+        let b = scan(ctx.clone(), "b", 1000);
+        let c = LogicalScan::create(
+            "c".to_string(),
+            Rc::new(TableDesc {
+                table_id: 0.into(),
+                pk: vec![],
+                columns: vec![ColumnDesc::unnamed(0.into(), DataType::Int32)],
+            }),
+            None,
+            vec![],
+            None,
+            ctx,
+        )
+        .unwrap();
+        let ab = LogicalJoin::create(a, b, JoinType::Inner, eq(0, 1));
+        let abc = LogicalJoin::create(ab, c, JoinType::Inner, eq(1, 2));
+        assert!(JoinReorderRule::create().apply(abc).is_none());
+    }
+}
@@ -37,3 +37,15 @@ mod project_elim;
 pub use project_elim::*;
 mod project_merge;
 pub use project_merge::*;
+mod const_eval;
+pub use const_eval::*;
+mod prune_empty;
+pub use prune_empty::*;
+mod join_reorder;
+pub use join_reorder::*;
+mod index_scan;
+pub use index_scan::*;
+mod split_distinct_agg;
+pub use split_distinct_agg::*;
+mod over_agg_to_top_n;
+pub use over_agg_to_top_n::*;
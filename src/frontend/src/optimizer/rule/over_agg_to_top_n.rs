@@ -0,0 +1,93 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use fixedbitset::FixedBitSet;
+use risingwave_common::types::ScalarImpl;
+
+use super::super::plan_node::*;
+use super::{BoxedRule, Rule};
+use crate::expr::{ExprType, WindowFunctionType};
+use crate::utils::as_comparison_with_const;
+
+/// Rewrites the top-N pattern of `row_number()` into a [`LogicalTopN`] with group keys:
+///
+/// ```text
+/// LogicalProject { exprs: [$0, $1] }
+///   LogicalFilter { predicate: ($2 <= 10:Int64) }
+///     LogicalOverAgg { window_function: row_number() over (partition by [$0] order by [$1 DESC]) }
+/// =>
+/// LogicalProject { exprs: [$0, $1] }
+///   LogicalTopN { order: [$1 DESC], limit: 10, offset: 0, group_keys: [$0] }
+/// ```
+///
+/// The result of `row_number()` can only be used by the filter, since the top-N doesn't output it.
+pub struct OverAggToTopNRule {}
+impl Rule for OverAggToTopNRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let project = plan.as_logical_project()?;
+        let project_input = project.input();
+        let filter = project_input.as_logical_filter()?;
+        let filter_input = filter.input();
+        let over_agg = filter_input.as_logical_over_agg()?;
+        let window_function = over_agg.window_function();
+        if window_function.function_type != WindowFunctionType::RowNumber
+            || window_function.partition_by.is_empty()
+        {
+            return None;
+        }
+
+        let input_len = over_agg.input().schema().len();
+        let mut rn_column = FixedBitSet::with_capacity(input_len + 1);
+        rn_column.insert(input_len);
+        if project
+            .exprs()
+            .iter()
+            .any(|expr| expr.collect_input_refs(input_len + 1).contains(input_len))
+        {
+            return None;
+        }
+        let (rn_predicate, other_predicate) = filter.predicate().clone().split_disjoint(&rn_column);
+        let rn_condition = match rn_predicate.conjunctions.as_slice() {
+            [rn_condition] => rn_condition,
+            _ => return None,
+        };
+        let limit = match as_comparison_with_const(rn_condition)? {
+            (_, ExprType::LessThanOrEqual, ScalarImpl::Int64(value)) => value,
+            (_, ExprType::LessThan, ScalarImpl::Int64(value)) => value - 1,
+            _ => return None,
+        };
+        if limit < 1 {
+            return None;
+        }
+
+        let top_n = LogicalTopN::with_group(
+            over_agg.input(),
+            limit as usize,
+            0,
+            window_function.order_by.clone(),
+            window_function.partition_by.clone(),
+        );
+        let input = if other_predicate.always_true() {
+            top_n
+        } else {
+            LogicalFilter::create(top_n, other_predicate)
+        };
+        Some(project.clone_with_input(input).into())
+    }
+}
+
+impl OverAggToTopNRule {
+    pub fn create() -> BoxedRule {
+        Box::new(OverAggToTopNRule {})
+    }
+}
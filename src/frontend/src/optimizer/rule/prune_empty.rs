@@ -0,0 +1,65 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::plan::JoinType;
+
+use super::super::plan_node::*;
+use super::{BoxedRule, Rule};
+
+/// Replaces plans that can never produce any row with an empty [`LogicalValues`], i.e. a
+/// [`LogicalFilter`] with an always-false predicate, an inner [`LogicalJoin`] with an always-false
+/// condition or an empty input, and a [`LogicalFilter`], [`LogicalProject`], [`LogicalLimit`] or
+/// [`LogicalTopN`] over an empty input.
+///
+/// The result can only be converted to a batch plan, as there is no streaming `Values` yet.
+pub struct PruneEmptyRule {}
+impl Rule for PruneEmptyRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let is_empty = if let Some(filter) = plan.as_logical_filter() {
+            filter.predicate().always_false() || is_empty_values(&filter.input())
+        } else if let Some(project) = plan.as_logical_project() {
+            is_empty_values(&project.input())
+        } else if let Some(limit) = plan.as_logical_limit() {
+            is_empty_values(&limit.input())
+        } else if let Some(top_n) = plan.as_logical_top_n() {
+            is_empty_values(&top_n.input())
+        } else if let Some(join) = plan.as_logical_join() {
+            join.join_type() == JoinType::Inner
+                && (join.on().always_false()
+                    || is_empty_values(&join.left())
+                    || is_empty_values(&join.right()))
+        } else {
+            false
+        };
+        if is_empty {
+            Some(LogicalValues::create(
+                vec![],
+                plan.schema().clone(),
+                plan.ctx(),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn is_empty_values(plan: &PlanRef) -> bool {
+    matches!(plan.as_logical_values(), Some(values) if values.rows().is_empty())
+}
+
+impl PruneEmptyRule {
+    pub fn create() -> BoxedRule {
+        Box::new(PruneEmptyRule {})
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use risingwave_expr::expr::AggKind;
+
+use super::super::plan_node::*;
+use super::{BoxedRule, Rule};
+use crate::expr::InputRef;
+
+/// Splits a [`LogicalAgg`] whose distinct agg calls share the same inputs into two
+/// [`LogicalAgg`]s. The lower one groups by both the group keys and the distinct inputs, which
+/// dedups the distinct inputs and pre-aggregates the other agg calls, and the upper one merges the
+/// results by the group keys:
+///
+/// ```text
+/// LogicalAgg { group_keys: [$0], aggs: [count(distinct $1), sum($2)] }
+/// =>
+/// LogicalAgg { group_keys: [$0], aggs: [count($1), sum($2)] }
+///   LogicalAgg { group_keys: [$0, $1], aggs: [sum($2)] }
+/// ```
+///
+/// The rows of a hot group key are spread by the distinct inputs in the lower agg. The other agg
+/// calls must be decomposable, so that their partial results can be merged.
+pub struct SplitDistinctAggRule {}
+impl Rule for SplitDistinctAggRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let agg = plan.as_logical_agg()?;
+        let group_keys = agg.group_keys();
+        let (distinct_calls, other_calls): (Vec<_>, Vec<_>) = agg
+            .agg_calls()
+            .iter()
+            .partition(|agg_call| agg_call.distinct);
+
+        let distinct_inputs = distinct_calls.first()?.inputs.clone();
+        if distinct_calls.iter().any(|agg_call| {
+            agg_call
+                .inputs
+                .iter()
+                .map(InputRef::index)
+                .ne(distinct_inputs.iter().map(InputRef::index))
+        }) {
+            return None;
+        }
+        let decomposable = other_calls.iter().all(|agg_call| match agg_call.agg_kind {
+            AggKind::Sum | AggKind::Min | AggKind::Max => true,
+            // The count of no rows is 0, but the sum of no partial counts is null.
+            AggKind::Count => !group_keys.is_empty(),
+            _ => false,
+        });
+        if !decomposable {
+            return None;
+        }
+
+        // The lower agg outputs the group keys, then the distinct inputs which are not group keys,
+        // and then the partial results of the other agg calls.
+        let mut lower_group_keys = group_keys.to_vec();
+        for input in &distinct_inputs {
+            if !lower_group_keys.contains(&input.index()) {
+                lower_group_keys.push(input.index());
+            }
+        }
+        let input_to_lower: HashMap<_, _> = lower_group_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (*key, i))
+            .collect();
+        let lower_calls = other_calls.into_iter().cloned().collect_vec();
+        let partial_offset = lower_group_keys.len();
+        let lower = LogicalAgg::new(
+            lower_calls.clone(),
+            vec![None; lower_calls.len()],
+            lower_group_keys,
+            agg.input(),
+        );
+
+        let mut partial_idx = partial_offset;
+        let upper_calls = agg
+            .agg_calls()
+            .iter()
+            .map(|agg_call| {
+                if agg_call.distinct {
+                    PlanAggCall {
+                        agg_kind: agg_call.agg_kind.clone(),
+                        return_type: agg_call.return_type.clone(),
+                        inputs: agg_call
+                            .inputs
+                            .iter()
+                            .map(|input| {
+                                InputRef::new(input_to_lower[&input.index()], input.return_type())
+                            })
+                            .collect(),
+                        distinct: false,
+                    }
+                } else {
+                    let agg_kind = match agg_call.agg_kind {
+                        AggKind::Count => AggKind::Sum,
+                        ref agg_kind => agg_kind.clone(),
+                    };
+                    let input = InputRef::new(partial_idx, agg_call.return_type.clone());
+                    partial_idx += 1;
+                    PlanAggCall {
+                        agg_kind,
+                        return_type: agg_call.return_type.clone(),
+                        inputs: vec![input],
+                        distinct: false,
+                    }
+                }
+            })
+            .collect();
+
+        Some(
+            LogicalAgg::new(
+                upper_calls,
+                agg.agg_call_alias().to_vec(),
+                (0..group_keys.len()).collect(),
+                lower.into(),
+            )
+            .into(),
+        )
+    }
+}
+
+impl SplitDistinctAggRule {
+    pub fn create() -> BoxedRule {
+        Box::new(SplitDistinctAggRule {})
+    }
+}
@@ -18,12 +18,12 @@ use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
 
 use crate::binder::{
-    BoundBaseTable, BoundJoin, BoundSource, BoundWindowTableFunction, Relation,
-    WindowTableFunctionKind,
+    BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundUnnest,
+    BoundWindowTableFunction, Relation, WindowTableFunctionKind,
 };
 use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef};
 use crate::optimizer::plan_node::{
-    LogicalJoin, LogicalProject, LogicalScan, LogicalSource, PlanRef,
+    LogicalJoin, LogicalProject, LogicalScan, LogicalSource, LogicalUnnest, LogicalValues, PlanRef,
 };
 use crate::planner::Planner;
 
@@ -36,6 +36,8 @@ impl Planner {
             Relation::Join(join) => self.plan_join(*join),
             Relation::WindowTableFunction(tf) => self.plan_window_table_function(*tf),
             Relation::Source(s) => self.plan_source(*s),
+            Relation::Unnest(u) => self.plan_unnest(*u),
+            Relation::SystemTable(t) => self.plan_system_table(*t),
         }
     }
 
@@ -43,6 +45,9 @@ impl Planner {
         LogicalScan::create(
             base_table.name,
             Rc::new(base_table.table_catalog.table_desc()),
+            base_table.table_catalog.stats.map(Rc::new),
+            base_table.indexes.into_iter().map(Rc::new).collect(),
+            base_table.as_of_epoch,
             self.ctx(),
         )
     }
@@ -51,6 +56,18 @@ impl Planner {
         Ok(LogicalSource::new(Rc::new(source.catalog), self.ctx()).into())
     }
 
+    pub(super) fn plan_unnest(&mut self, unnest: BoundUnnest) -> Result<PlanRef> {
+        Ok(LogicalUnnest::create(unnest.array, self.ctx()))
+    }
+
+    pub(super) fn plan_system_table(&mut self, system_table: BoundSystemTable) -> Result<PlanRef> {
+        Ok(LogicalValues::create(
+            system_table.rows,
+            system_table.schema,
+            self.ctx(),
+        ))
+    }
+
     pub(super) fn plan_join(&mut self, join: BoundJoin) -> Result<PlanRef> {
         let left = self.plan_relation(join.left)?;
         let right = self.plan_relation(join.right)?;
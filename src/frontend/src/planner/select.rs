@@ -20,12 +20,15 @@ use risingwave_pb::plan::JoinType;
 
 use crate::binder::BoundSelect;
 use crate::expr::{
-    Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Subquery, SubqueryKind,
+    Expr, ExprImpl, ExprRewriter, ExprType, ExprVisitor, FunctionCall, InputRef, Subquery,
+    SubqueryKind, WindowFunction,
 };
 pub use crate::optimizer::plan_node::LogicalFilter;
 use crate::optimizer::plan_node::{
-    LogicalAgg, LogicalApply, LogicalJoin, LogicalProject, LogicalValues, PlanAggCall, PlanRef,
+    LogicalAgg, LogicalApply, LogicalJoin, LogicalOverAgg, LogicalProject, LogicalValues,
+    PlanAggCall, PlanRef, PlanWindowFunction,
 };
+use crate::optimizer::property::{FieldOrder, Order};
 use crate::planner::Planner;
 use crate::utils::Condition;
 impl Planner {
@@ -37,6 +40,7 @@ impl Planner {
             mut select_items,
             group_by,
             aliases,
+            distinct,
             ..
         }: BoundSelect,
     ) -> Result<PlanRef> {
@@ -52,14 +56,31 @@ impl Planner {
         // Plan the SELECT clause.
         // TODO: select-agg, group-by, having can also contain subquery exprs.
         let has_agg_call = select_items.iter().any(|expr| expr.has_agg_call());
-        if !group_by.is_empty() || has_agg_call {
-            LogicalAgg::create(select_items, aliases, group_by, root)
+        let has_window_function = select_items.iter().any(|expr| expr.has_window_function());
+        if has_window_function && (!group_by.is_empty() || has_agg_call) {
+            return Err(ErrorCode::NotImplemented(
+                "window functions with aggregation".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+        root = if !group_by.is_empty() || has_agg_call {
+            LogicalAgg::create(select_items, aliases, group_by, root)?
         } else {
+            if has_window_function {
+                (root, select_items) = self.substitute_window_function(root, select_items)?;
+            }
             if select_items.iter().any(|e| e.has_subquery()) {
                 (root, select_items) = self.substitute_subqueries(root, select_items)?;
             }
-            Ok(LogicalProject::create(root, select_items, aliases))
+            LogicalProject::create(root, select_items, aliases)
+        };
+        // Plan the DISTINCT clause as an aggregation grouped by all the columns.
+        if distinct {
+            let group_keys = (0..root.schema().len()).collect();
+            root = LogicalAgg::new(vec![], vec![], group_keys, root).into();
         }
+        Ok(root)
     }
 
     /// Helper to create a dummy node as child of [`LogicalProject`].
@@ -226,6 +247,105 @@ impl Planner {
         Ok((root, exprs))
     }
 
+    /// Substitutes the [`WindowFunction`] in `exprs` with an [`InputRef`] to the last column of a
+    /// new [`LogicalOverAgg`] on top of `root`. Only one window function is supported for now.
+    ///
+    /// If some partition or order expressions are not [`InputRef`]s, they are evaluated by a
+    /// [`LogicalProject`] below the [`LogicalOverAgg`], which keeps all columns of `root` in front.
+    fn substitute_window_function(
+        &mut self,
+        mut root: PlanRef,
+        exprs: Vec<ExprImpl>,
+    ) -> Result<(PlanRef, Vec<ExprImpl>)> {
+        #[derive(Default)]
+        struct CollectWindowFunctions {
+            window_functions: Vec<WindowFunction>,
+        }
+
+        impl ExprVisitor for CollectWindowFunctions {
+            fn visit_window_function(&mut self, window_function: &WindowFunction) {
+                self.window_functions.push(window_function.clone());
+            }
+        }
+
+        struct SubstituteWindowFunction {
+            input_ref: InputRef,
+        }
+
+        impl ExprRewriter for SubstituteWindowFunction {
+            fn rewrite_window_function(&mut self, _: WindowFunction) -> ExprImpl {
+                self.input_ref.clone().into()
+            }
+        }
+
+        let mut collector = CollectWindowFunctions::default();
+        exprs.iter().for_each(|expr| collector.visit_expr(expr));
+        let WindowFunction {
+            function_type,
+            partition_by,
+            order_by,
+        } = match collector.window_functions.into_iter().exactly_one() {
+            Ok(window_function) => window_function,
+            Err(_) => {
+                return Err(ErrorCode::NotImplemented(
+                    "multiple window functions in a select list".to_string(),
+                    None.into(),
+                )
+                .into())
+            }
+        };
+
+        let input_len = root.schema().len();
+        let mut pre_project_exprs: Vec<ExprImpl> = root
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+            .collect();
+        let mut to_column_index = |expr: ExprImpl| match expr {
+            ExprImpl::InputRef(input_ref) => input_ref.index(),
+            expr => {
+                pre_project_exprs.push(expr);
+                pre_project_exprs.len() - 1
+            }
+        };
+        let partition_by = partition_by
+            .into_iter()
+            .map(&mut to_column_index)
+            .collect_vec();
+        let order_by = Order::new(
+            order_by
+                .into_iter()
+                .map(|(expr, direct)| FieldOrder {
+                    index: to_column_index(expr),
+                    direct,
+                })
+                .collect(),
+        );
+        if pre_project_exprs.len() > input_len {
+            let aliases = vec![None; pre_project_exprs.len()];
+            root = LogicalProject::create(root, pre_project_exprs, aliases);
+        }
+
+        root = LogicalOverAgg::create(
+            PlanWindowFunction {
+                function_type,
+                partition_by,
+                order_by,
+            },
+            root,
+        );
+        let mut rewriter = SubstituteWindowFunction {
+            input_ref: InputRef::new(root.schema().len() - 1, function_type.return_type()),
+        };
+        let exprs = exprs
+            .into_iter()
+            .map(|expr| rewriter.rewrite_expr(expr))
+            .collect();
+        Ok((root, exprs))
+    }
+
     fn create_apply_or_join(
         is_correlated: bool,
         left: PlanRef,
@@ -32,7 +32,7 @@ use crate::scheduler::execution::StageEvent::Scheduled;
 use crate::scheduler::execution::{StageExecution, ROOT_TASK_ID, ROOT_TASK_OUTPUT_ID};
 use crate::scheduler::plan_fragmenter::{Query, StageId};
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
-use crate::scheduler::QueryResultFetcher;
+use crate::scheduler::{QueryResultFetcher, QueryStats};
 
 /// Message sent to a `QueryRunner` to control its execution.
 #[derive(Debug)]
@@ -197,6 +197,21 @@ impl QueryExecution {
         }
     }
 
+    pub fn query(&self) -> Arc<Query> {
+        self.query.clone()
+    }
+
+    /// Collects the runtime statistics of the operators of all tasks of the query.
+    pub async fn collect_stats(&self) -> Result<QueryStats> {
+        let mut stats = QueryStats::default();
+        for (stage_id, stage_execution) in self.stage_executions.iter() {
+            for task_info in stage_execution.collect_task_infos().await? {
+                stats.add_task(*stage_id, task_info.operator_stats);
+            }
+        }
+        Ok(stats)
+    }
+
     /// Cancel execution of this query.
     pub async fn abort(&mut self) -> Result<()> {
         todo!()
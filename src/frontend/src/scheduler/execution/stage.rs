@@ -25,6 +25,7 @@ use risingwave_pb::plan::{
     ExchangeNode, ExchangeSource, MergeSortExchangeNode, PlanFragment, PlanNode as PlanNodeProst,
     TaskId as TaskIdProst, TaskOutputId,
 };
+use risingwave_pb::task_service::TaskInfo;
 use risingwave_rpc_client::ComputeClient;
 use tokio::spawn;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
@@ -201,6 +202,28 @@ impl StageExecution {
         self.tasks[&task_id].get_status()
     }
 
+    /// Fetches the status and runtime statistics of all tasks of the stage from the compute nodes
+    /// they run on. Tasks not scheduled yet are skipped.
+    pub async fn collect_task_infos(&self) -> Result<Vec<TaskInfo>> {
+        let mut task_infos = Vec::with_capacity(self.tasks.len());
+        for (task_id, status_holder) in self.tasks.iter() {
+            let location = match status_holder.get_status().location.clone() {
+                Some(location) => location,
+                None => continue,
+            };
+            let compute_client = ComputeClient::new((&location).into()).await?;
+            let task_info = compute_client
+                .get_task_info(TaskIdProst {
+                    query_id: self.stage.query_id.id.clone(),
+                    stage_id: self.stage.id,
+                    task_id: *task_id,
+                })
+                .await?;
+            task_infos.push(task_info);
+        }
+        Ok(task_infos)
+    }
+
     /// Returns all exchange sources for `output_id`. Each `ExchangeSource` is identified by
     /// producer `TaskId` and `output_id`, since each task may produce output to several channels.
     ///
@@ -22,6 +22,8 @@ mod execution;
 pub mod plan_fragmenter;
 mod query_manager;
 pub use query_manager::*;
+mod query_stats;
+pub use query_stats::*;
 pub mod worker_node_manager;
 
 /// Context for mpp query execution.
@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Formatter, Write};
 use std::sync::Arc;
 
 use risingwave_common::error::Result;
+use risingwave_meta::manager::INVALID_EPOCH;
+use risingwave_pb::plan::exchange_info::DistributionMode;
 use risingwave_pb::plan::plan_node::NodeBody;
 use risingwave_pb::plan::{ExchangeInfo, Field as FieldProst};
 use uuid::Uuid;
@@ -25,6 +27,7 @@ use crate::optimizer::plan_node::{PlanNodeId, PlanNodeType};
 use crate::optimizer::property::Distribution;
 use crate::optimizer::PlanRef;
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
+use crate::scheduler::{explain_operator_stats, QueryStats};
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub struct QueryId {
@@ -40,6 +43,8 @@ pub struct ExecutionPlanNode {
     pub plan_node_type: PlanNodeType,
     pub node: NodeBody,
     pub schema: Vec<FieldProst>,
+    /// Description of the plan node, shown by `EXPLAIN (DISTSQL)`.
+    pub identity: String,
 
     pub children: Vec<Arc<ExecutionPlanNode>>,
 
@@ -58,6 +63,7 @@ impl From<PlanRef> for ExecutionPlanNode {
             node: plan_node.to_batch_prost_body(),
             children: vec![],
             schema: plan_node.schema().to_prost(),
+            identity: format!("{}", plan_node),
             stage_id: None,
         }
     }
@@ -67,6 +73,32 @@ impl ExecutionPlanNode {
     pub fn node_type(&self) -> PlanNodeType {
         self.plan_node_type
     }
+
+    /// Explains the node and its children, with the runtime statistics of the `index`-th operator
+    /// of `stage_id` in pre-order. Returns the index of the next operator.
+    fn explain(
+        &self,
+        stage_id: StageId,
+        mut index: usize,
+        level: usize,
+        stats: Option<&QueryStats>,
+        f: &mut String,
+    ) -> usize {
+        write!(f, "{}{}", " ".repeat(level * 2), self.identity).unwrap();
+        if let Some(child_stage_id) = self.stage_id {
+            write!(f, " <- Stage {}", child_stage_id).unwrap();
+        }
+        if let Some(stats) = stats.and_then(|stats| stats.get(stage_id, index)) {
+            write!(f, " ({})", explain_operator_stats(stats)).unwrap();
+        }
+        writeln!(f).unwrap();
+
+        index += 1;
+        for child in &self.children {
+            index = child.explain(stage_id, index, level + 1, stats, f);
+        }
+        index
+    }
 }
 
 /// `BatchPlanFragmenter` splits a query plan into fragments.
@@ -126,6 +158,49 @@ impl Query {
     pub fn root_stage_id(&self) -> StageId {
         self.stage_graph.root_stage_id
     }
+
+    /// Explains the stages of the query in order of their ids, with the runtime statistics of
+    /// the operators if collected by `EXPLAIN ANALYZE`.
+    pub fn explain_to_string(&self, stats: Option<&QueryStats>) -> String {
+        let mut output = String::new();
+        let mut stage_ids = self.stage_graph.stages.keys().copied().collect::<Vec<_>>();
+        stage_ids.sort_unstable();
+        for stage_id in stage_ids {
+            let stage = &self.stage_graph.stages[&stage_id];
+            let mode = DistributionMode::from_i32(stage.exchange_info.mode)
+                .unwrap_or(DistributionMode::Single);
+            writeln!(
+                output,
+                "Stage {}: parallelism {}, output {:?}",
+                stage_id, stage.parallelism, mode
+            )
+            .unwrap();
+            stage.root.explain(stage_id, 0, 1, stats, &mut output);
+        }
+        output
+    }
+
+    /// The smallest epoch read by scans with `FOR SYSTEM_TIME AS OF`, if any.
+    pub fn min_as_of_epoch(&self) -> Option<u64> {
+        fn visit(node: &ExecutionPlanNode) -> Option<u64> {
+            let as_of_epoch = match &node.node {
+                NodeBody::RowSeqScan(scan) if scan.as_of_epoch != INVALID_EPOCH => {
+                    Some(scan.as_of_epoch)
+                }
+                _ => None,
+            };
+            node.children
+                .iter()
+                .filter_map(|child| visit(child))
+                .chain(as_of_epoch)
+                .min()
+        }
+        self.stage_graph
+            .stages
+            .values()
+            .filter_map(|stage| visit(&stage.root))
+            .min()
+    }
 }
 
 /// Fragment part of `Query`.
@@ -443,6 +518,9 @@ mod tests {
                     },
                 ],
             }),
+            None,
+            vec![],
+            None,
             ctx,
         ))
         .into();
@@ -581,6 +659,21 @@ mod tests {
         assert_eq!(scan_node2.root.node_type(), PlanNodeType::BatchSeqScan);
         assert_eq!(scan_node2.root.stage_id, None);
         assert_eq!(0, scan_node2.root.children.len());
+
+        // Check the explained stages.
+        let explain = query.explain_to_string(None);
+        let lines = explain.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "Stage 0: parallelism 1, output Single");
+        assert!(lines[1].starts_with("  BatchExchange"));
+        assert!(lines[1].ends_with(" <- Stage 1"));
+        assert_eq!(lines[2], "Stage 1: parallelism 3, output Single");
+        assert!(lines[3].starts_with("  BatchHashJoin"));
+        assert!(lines[4].starts_with("    BatchExchange"));
+        assert!(lines[4].ends_with(" <- Stage 2"));
+        assert!(lines[5].ends_with(" <- Stage 3"));
+        assert_eq!(lines[6], "Stage 2: parallelism 3, output Hash");
+        assert!(lines[7].starts_with("  BatchScan"));
     }
 
     fn generate_parallel_units(start_id: u32, node_id: u32) -> Vec<ParallelUnit> {
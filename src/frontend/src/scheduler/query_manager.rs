@@ -16,7 +16,7 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 use futures::Stream;
-use futures_async_stream::try_stream;
+use futures_async_stream::{for_await, try_stream};
 use log::debug;
 use risingwave_common::array::DataChunk;
 use risingwave_common::error::{Result, RwError};
@@ -29,13 +29,15 @@ use crate::meta_client::FrontendMetaClient;
 use crate::scheduler::execution::QueryExecution;
 use crate::scheduler::plan_fragmenter::Query;
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
-use crate::scheduler::ExecutionContextRef;
+use crate::scheduler::{ExecutionContextRef, QueryStats};
 
 pub trait DataChunkStream = Stream<Item = Result<DataChunk>>;
 
 pub struct QueryResultFetcher {
     // TODO: Remove these after implemented worker node level snapshot pinnning
     epoch: u64,
+    /// The earlier snapshot pinned for scans with `FOR SYSTEM_TIME AS OF`, if any.
+    as_of_epoch: Option<u64>,
     meta_client: Arc<dyn FrontendMetaClient>,
 
     task_output_id: TaskOutputId,
@@ -57,11 +59,13 @@ impl QueryManager {
 
     /// Schedule query to single node.
     ///
-    /// This is kept for dml only.
+    /// This is kept for dml only. The snapshot of `epoch` must have been pinned by the caller, and
+    /// is unpinned after all results are fetched.
     pub async fn schedule_single(
         &self,
         context: ExecutionContextRef,
         plan: BatchPlanProst,
+        epoch: u64,
     ) -> Result<impl Stream<Item = Result<DataChunk>>> {
         let session = context.session();
         let worker_node_addr = self.worker_node_manager.next_random()?.host.unwrap();
@@ -80,54 +84,102 @@ impl QueryManager {
 
         let meta_client = session.env().meta_client_ref();
 
-        // Pin snapshot in meta.
-        // TODO: Hummock snapshot should maintain as cache instead of RPC each query.
-        // TODO: Use u64::MAX for `last_pinned` so it always return the greatest current epoch. Use
-        // correct `last_pinned` when retrying this RPC.
-        let last_pinned = u64::MAX;
-        let epoch = meta_client.pin_snapshot(last_pinned).await?;
-
         compute_client
             .create_task(task_id.clone(), plan, epoch)
             .await?;
 
-        let query_result_fetcher = QueryResultFetcher {
-            epoch,
-            meta_client,
-            task_output_id,
-            task_host: worker_node_addr,
-        };
+        let query_result_fetcher =
+            QueryResultFetcher::new(epoch, meta_client, task_output_id, worker_node_addr);
 
         Ok(query_result_fetcher.run())
     }
 
+    /// Schedules a distributed query reading the snapshot of `epoch`, which must have been pinned
+    /// by the caller, and is unpinned after all results are fetched.
     pub async fn schedule(
         &self,
         context: ExecutionContextRef,
         query: Query,
+        epoch: u64,
     ) -> Result<impl DataChunkStream> {
         // Cheat compiler to resolve type
         let session = context.session();
 
         let meta_client = session.env().meta_client_ref();
 
-        // Pin snapshot in meta.
-        // TODO: Hummock snapshot should maintain as cache instead of RPC each query.
-        // TODO: Use u64::MAX for `last_pinned` so it always return the greatest current epoch. Use
-        // correct `last_pinned` when retrying this RPC.
-        let last_pinned = u64::MAX;
-        let epoch = meta_client.pin_snapshot(last_pinned).await?;
+        // Scans with `FOR SYSTEM_TIME AS OF` read earlier epochs. Pinning the smallest one keeps
+        // the data of all of them from being compacted during the query.
+        let as_of_epoch = match query.min_as_of_epoch() {
+            Some(as_of_epoch) => Some(meta_client.pin_snapshot_as_of(as_of_epoch).await?),
+            None => None,
+        };
 
         let query_execution = QueryExecution::new(
             query,
             epoch,
-            meta_client,
+            meta_client.clone(),
             session.env().worker_node_manager_ref(),
         );
 
-        let query_result_fetcher = query_execution.start().await?;
+        let query_result_fetcher =
+            start_query(&query_execution, meta_client.as_ref(), as_of_epoch).await?;
 
-        Ok(query_result_fetcher.run())
+        Ok(query_result_fetcher.with_as_of_epoch(as_of_epoch).run())
+    }
+
+    /// Runs a distributed query to completion like [`QueryManager::schedule`], discarding its
+    /// results, and collects the runtime statistics of its operators for `EXPLAIN ANALYZE`.
+    pub async fn analyze(
+        &self,
+        context: ExecutionContextRef,
+        query: Query,
+        epoch: u64,
+    ) -> Result<(Arc<Query>, QueryStats)> {
+        let session = context.session();
+
+        let meta_client = session.env().meta_client_ref();
+
+        let as_of_epoch = match query.min_as_of_epoch() {
+            Some(as_of_epoch) => Some(meta_client.pin_snapshot_as_of(as_of_epoch).await?),
+            None => None,
+        };
+
+        let query_execution = QueryExecution::new(
+            query,
+            epoch,
+            meta_client.clone(),
+            session.env().worker_node_manager_ref(),
+        );
+
+        let query_result_fetcher =
+            start_query(&query_execution, meta_client.as_ref(), as_of_epoch).await?;
+
+        #[for_await]
+        for chunk in query_result_fetcher.with_as_of_epoch(as_of_epoch).run() {
+            chunk?;
+        }
+
+        let stats = query_execution.collect_stats().await?;
+        Ok((query_execution.query(), stats))
+    }
+}
+
+/// Starts `query_execution`, unpinning the snapshot of `as_of_epoch` if it fails to start, in which
+/// case no result fetcher is left to unpin it. The snapshot of the query epoch is unpinned by the
+/// caller.
+async fn start_query(
+    query_execution: &QueryExecution,
+    meta_client: &dyn FrontendMetaClient,
+    as_of_epoch: Option<u64>,
+) -> Result<QueryResultFetcher> {
+    match query_execution.start().await {
+        Ok(query_result_fetcher) => Ok(query_result_fetcher),
+        Err(e) => {
+            if let Some(as_of_epoch) = as_of_epoch {
+                meta_client.unpin_snapshot(as_of_epoch).await?;
+            }
+            Err(e)
+        }
     }
 }
 
@@ -140,12 +192,19 @@ impl QueryResultFetcher {
     ) -> Self {
         Self {
             epoch,
+            as_of_epoch: None,
             meta_client,
             task_output_id,
             task_host,
         }
     }
 
+    /// Sets the snapshot pinned for `FOR SYSTEM_TIME AS OF`, which is unpinned with `epoch`.
+    pub fn with_as_of_epoch(mut self, as_of_epoch: Option<u64>) -> Self {
+        self.as_of_epoch = as_of_epoch;
+        self
+    }
+
     #[try_stream(ok = DataChunk, error = RwError)]
     async fn run(self) {
         debug!(
@@ -162,6 +221,9 @@ impl QueryResultFetcher {
         let epoch = self.epoch;
         // Unpin corresponding snapshot.
         self.meta_client.unpin_snapshot(epoch).await?;
+        if let Some(as_of_epoch) = self.as_of_epoch {
+            self.meta_client.unpin_snapshot(as_of_epoch).await?;
+        }
     }
 }
 
@@ -169,6 +231,7 @@ impl Debug for QueryResultFetcher {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryResultFetcher")
             .field("epoch", &self.epoch)
+            .field("as_of_epoch", &self.as_of_epoch)
             .field("task_output_id", &self.task_output_id)
             .field("task_host", &self.task_host)
             .finish()
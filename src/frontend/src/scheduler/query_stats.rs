@@ -0,0 +1,90 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use risingwave_pb::task_service::OperatorStats;
+
+use crate::scheduler::plan_fragmenter::StageId;
+
+/// Runtime statistics of the operators of a query collected by `EXPLAIN ANALYZE`, aggregated over
+/// the tasks of each stage.
+#[derive(Debug, Default)]
+pub struct QueryStats {
+    /// Statistics of the operators of each stage, in pre-order of the stage's plan tree.
+    stages: HashMap<StageId, Vec<OperatorStats>>,
+}
+
+impl QueryStats {
+    /// Merges the statistics of a task of `stage_id`. Rows and chunks are summed over the tasks,
+    /// while elapsed time and peak memory are the maximum of a single task, as tasks run in
+    /// parallel.
+    pub fn add_task(&mut self, stage_id: StageId, operator_stats: Vec<OperatorStats>) {
+        let stage = self.stages.entry(stage_id).or_default();
+        if stage.is_empty() {
+            *stage = operator_stats;
+            return;
+        }
+        for (merged, stats) in stage.iter_mut().zip(operator_stats) {
+            merged.rows += stats.rows;
+            merged.chunks += stats.chunks;
+            merged.elapsed_nanos = merged.elapsed_nanos.max(stats.elapsed_nanos);
+            merged.peak_memory_bytes = merged.peak_memory_bytes.max(stats.peak_memory_bytes);
+        }
+    }
+
+    /// Returns the statistics of the `index`-th operator of a stage in pre-order.
+    pub fn get(&self, stage_id: StageId, index: usize) -> Option<&OperatorStats> {
+        self.stages.get(&stage_id)?.get(index)
+    }
+}
+
+/// Formats the statistics of an operator to be appended to its line in the plan.
+pub fn explain_operator_stats(stats: &OperatorStats) -> String {
+    format!(
+        "rows: {}, chunks: {}, time: {:?}, peak memory: {} bytes",
+        stats.rows,
+        stats.chunks,
+        Duration::from_nanos(stats.elapsed_nanos),
+        stats.peak_memory_bytes
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_task_stats() {
+        let task_stats = |rows, elapsed_nanos, peak_memory_bytes| OperatorStats {
+            identity: "HashAggExecutor".to_string(),
+            rows,
+            chunks: 1,
+            elapsed_nanos,
+            peak_memory_bytes,
+        };
+
+        let mut stats = QueryStats::default();
+        stats.add_task(1, vec![task_stats(10, 2_000_000, 100)]);
+        stats.add_task(1, vec![task_stats(5, 3_000_000, 50)]);
+
+        let merged = stats.get(1, 0).unwrap();
+        assert_eq!(
+            explain_operator_stats(merged),
+            "rows: 15, chunks: 2, time: 3ms, peak memory: 100 bytes"
+        );
+        assert!(stats.get(1, 1).is_none());
+        assert!(stats.get(0, 0).is_none());
+    }
+}
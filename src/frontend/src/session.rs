@@ -24,16 +24,19 @@ use std::time::Duration;
 use parking_lot::RwLock;
 use pgwire::pg_response::PgResponse;
 use pgwire::pg_server::{Session, SessionManager};
+use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
 use risingwave_common::config::FrontendConfig;
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_pb::common::WorkerType;
 use risingwave_rpc_client::MetaClient;
+use risingwave_sqlparser::ast::ObjectName;
 use risingwave_sqlparser::parser::Parser;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
+use crate::binder::Binder;
 use crate::catalog::catalog_service::{CatalogReader, CatalogWriter, CatalogWriterImpl};
 use crate::catalog::root_catalog::Catalog;
 use crate::handler::handle;
@@ -266,8 +269,26 @@ impl ConfigEntry {
     pub fn is_set(&self, default: bool) -> bool {
         self.str_val.parse().unwrap_or(default)
     }
+
+    /// Only used for string configurations.
+    pub fn get_val(&self) -> &str {
+        &self.str_val
+    }
 }
 
+/// The session time zone, used to interpret and display `timestamp with time zone` values. It can
+/// be changed by `SET timezone = 'Asia/Shanghai'` or `SET TIME ZONE 'Asia/Shanghai'`.
+pub static TIMEZONE: &str = "timezone";
+
+/// The schemas in which the relations referred to by unqualified names are looked up, separated by
+/// commas. New relations with unqualified names are created in the first schema that exists.
+pub static SEARCH_PATH: &str = "search_path";
+
+/// If `RW_SPLIT_DISTINCT_AGG` is on, the aggregations with `DISTINCT` are computed in two phases,
+/// where the distinct inputs are first used as extra group keys. This spreads the rows of a hot
+/// group key over more actors, and is required by `DISTINCT` aggregations in streaming queries.
+pub static SPLIT_DISTINCT_AGG: &str = "RW_SPLIT_DISTINCT_AGG";
+
 impl SessionImpl {
     pub fn new(env: FrontendEnv, database: String) -> Self {
         Self {
@@ -308,6 +329,59 @@ impl SessionImpl {
         reader.get(key).cloned()
     }
 
+    /// Get the name of the session time zone.
+    pub fn timezone(&self) -> String {
+        self.get_config(TIMEZONE)
+            .map(|entry| entry.get_val().to_string())
+            .unwrap_or_else(|| "UTC".to_string())
+    }
+
+    /// Get the schemas in the session `search_path`.
+    pub fn search_path(&self) -> Vec<String> {
+        self.get_config(SEARCH_PATH)
+            .map(|entry| {
+                entry
+                    .get_val()
+                    .split(',')
+                    .map(|schema_name| schema_name.trim().to_string())
+                    .filter(|schema_name| !schema_name.is_empty())
+                    .collect()
+            })
+            .unwrap_or_else(|| vec![DEFAULT_SCHEMA_NAME.to_string()])
+    }
+
+    /// Resolves the name of an existing relation to (`schema_name`, `relation_name`). The schema of
+    /// an unqualified name is the first one in the `search_path` containing the relation.
+    pub fn resolve_table_name(&self, name: ObjectName) -> Result<(String, String)> {
+        let (schema_name, table_name) = Binder::resolve_schema_qualified_name(name)?;
+        let schema_name = match schema_name {
+            Some(schema_name) => schema_name,
+            None => self.env.catalog_reader().read_guard().resolve_schema_name(
+                &self.database,
+                &self.search_path(),
+                &table_name,
+            ),
+        };
+        Ok((schema_name, table_name))
+    }
+
+    /// Resolves the name of a relation to create to (`schema_name`, `relation_name`). The schema of
+    /// an unqualified name is the first one in the `search_path` that exists.
+    pub fn resolve_new_table_name(&self, name: ObjectName) -> Result<(String, String)> {
+        let (schema_name, table_name) = Binder::resolve_schema_qualified_name(name)?;
+        let schema_name = match schema_name {
+            Some(schema_name) => schema_name,
+            None => self
+                .env
+                .catalog_reader()
+                .read_guard()
+                .first_valid_schema(&self.database, &self.search_path())?
+                .name()
+                .to_string(),
+        };
+        Ok((schema_name, table_name))
+    }
+
     fn init_config_map() -> RwLock<HashMap<String, ConfigEntry>> {
         let mut map = HashMap::new();
         // FIXME: May need better init way + default config.
@@ -315,6 +389,15 @@ impl SessionImpl {
             IMPLICIT_FLUSH.to_string(),
             ConfigEntry::new("false".to_string()),
         );
+        map.insert(TIMEZONE.to_string(), ConfigEntry::new("UTC".to_string()));
+        map.insert(
+            SEARCH_PATH.to_string(),
+            ConfigEntry::new(DEFAULT_SCHEMA_NAME.to_string()),
+        );
+        map.insert(
+            SPLIT_DISTINCT_AGG.to_string(),
+            ConfigEntry::new("false".to_string()),
+        );
         RwLock::new(map)
     }
 }
@@ -331,6 +414,11 @@ impl SessionManager for SessionManagerImpl {
         &self,
         database: &str,
     ) -> std::result::Result<Arc<dyn Session>, Box<dyn Error + Send + Sync>> {
+        // Reject the connection if the requested database doesn't exist.
+        self.env
+            .catalog_reader()
+            .read_guard()
+            .get_database_by_name(database)?;
         Ok(Arc::new(SessionImpl::new(
             self.env.clone(),
             database.to_string(),
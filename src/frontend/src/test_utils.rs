@@ -26,7 +26,9 @@ use risingwave_common::error::Result;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
+    TableStatistics as ProstTableStatistics,
 };
+use risingwave_pb::meta::TableFragments;
 use risingwave_pb::stream_plan::StreamNode;
 use risingwave_sqlparser::ast::Statement;
 use risingwave_sqlparser::parser::Parser;
@@ -94,10 +96,10 @@ impl LocalFrontend {
                 );
                 binder.bind(Statement::Query(query.clone()))?
             };
-            Ok(Planner::new(OptimizerContext::new(session).into())
+            Planner::new(OptimizerContext::new(session).into())
                 .plan(bound)
                 .unwrap()
-                .gen_batch_query_plan())
+                .gen_batch_query_plan()
         } else {
             unreachable!()
         }
@@ -120,19 +122,33 @@ pub struct MockCatalogWriter {
 #[async_trait::async_trait]
 impl CatalogWriter for MockCatalogWriter {
     async fn create_database(&self, db_name: &str) -> Result<()> {
+        let database_id = self.gen_id();
         self.catalog.write().create_database(ProstDatabase {
             name: db_name.to_string(),
-            id: 0,
+            id: database_id,
         });
-        Ok(())
+        self.create_schema(database_id, DEFAULT_SCHEMA_NAME).await
     }
 
     async fn create_schema(&self, db_id: DatabaseId, schema_name: &str) -> Result<()> {
+        let id = self.gen_id();
         self.catalog.write().create_schema(ProstSchema {
-            id: 0,
+            id,
             name: schema_name.to_string(),
             database_id: db_id,
         });
+        self.add_id(id, db_id, id);
+        Ok(())
+    }
+
+    async fn drop_database(&self, db_id: DatabaseId) -> Result<()> {
+        self.catalog.write().drop_database(db_id);
+        Ok(())
+    }
+
+    async fn drop_schema(&self, schema_id: SchemaId) -> Result<()> {
+        let (database_id, _) = self.drop_id(schema_id);
+        self.catalog.write().drop_schema(database_id, schema_id);
         Ok(())
     }
 
@@ -192,6 +208,25 @@ impl CatalogWriter for MockCatalogWriter {
             .drop_table(database_id, schema_id, table_id);
         Ok(())
     }
+
+    async fn update_table_stats(
+        &self,
+        table_id: TableId,
+        stats: ProstTableStatistics,
+    ) -> Result<()> {
+        let (database_id, schema_id) =
+            *self.id_to_schema_id.read().get(&table_id.table_id).unwrap();
+        let mut catalog = self.catalog.write();
+        let table = catalog
+            .get_table_by_id(database_id, schema_id, table_id)
+            .unwrap()
+            .to_prost(schema_id, database_id);
+        catalog.update_table(&ProstTable {
+            stats: Some(stats),
+            ..table
+        });
+        Ok(())
+    }
 }
 
 impl MockCatalogWriter {
@@ -207,8 +242,10 @@ impl MockCatalogWriter {
         });
         Self {
             catalog,
-            id: AtomicU32::new(0),
-            id_to_schema_id: Default::default(),
+            // Starts from 1 so that the ids of new databases and schemas don't conflict with the
+            // default ones.
+            id: AtomicU32::new(1),
+            id_to_schema_id: RwLock::new(HashMap::from([(0, (0, 0))])),
         }
     }
 
@@ -242,6 +279,10 @@ impl FrontendMetaClient for MockFrontendMetaClient {
         Ok(0)
     }
 
+    async fn pin_snapshot_as_of(&self, epoch: u64) -> Result<u64> {
+        Ok(epoch)
+    }
+
     async fn flush(&self) -> Result<()> {
         Ok(())
     }
@@ -249,6 +290,10 @@ impl FrontendMetaClient for MockFrontendMetaClient {
     async fn unpin_snapshot(&self, _epoch: u64) -> Result<()> {
         Ok(())
     }
+
+    async fn list_table_fragments(&self) -> Result<Vec<TableFragments>> {
+        Ok(vec![])
+    }
 }
 pub static PROTO_FILE_DATA: &str = r#"
     syntax = "proto3";
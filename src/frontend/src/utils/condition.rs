@@ -13,14 +13,17 @@
 // limitations under the License.
 
 use std::fmt;
+use std::ops::Bound;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
+use risingwave_common::types::ScalarImpl;
 
 use crate::expr::{
-    fold_boolean_constant, push_down_not, to_conjunctions, try_get_bool_constant, ExprImpl,
-    ExprRewriter, ExprType, ExprVisitor, InputRef,
+    fold_boolean_constant, fold_const, push_down_not, to_conjunctions, try_get_bool_constant, Expr,
+    ExprImpl, ExprRewriter, ExprType, ExprVisitor, InputRef,
 };
+use crate::utils::ScanRange;
 
 #[derive(Debug, Clone)]
 pub struct Condition {
@@ -72,6 +75,12 @@ impl Condition {
         self.conjunctions.is_empty()
     }
 
+    /// Whether the condition rejects every row. A simplified condition is always false if and only
+    /// if it is a single `false` conjunction.
+    pub fn always_false(&self) -> bool {
+        matches!(&self.conjunctions[..], [expr] if try_get_bool_constant(expr) == Some(false))
+    }
+
     /// Convert condition to an expression. If always true, return `None`.
     pub fn as_expr_unless_true(&self) -> Option<ExprImpl> {
         if self.always_true() {
@@ -174,6 +183,49 @@ impl Condition {
         .unwrap()
     }
 
+    #[must_use]
+    /// Split the condition into a [`ScanRange`] and the remaining conditions. `pk_indices` are the
+    /// indices of the leading pk columns in the input, in the order of the pk.
+    ///
+    /// The scan range takes the values of the pk columns fixed by equalities with constants, and
+    /// then the bounds of the next pk column given by comparisons with constants. The conditions
+    /// taken by the scan range are removed.
+    pub fn split_to_scan_range(self, pk_indices: &[usize]) -> (ScanRange, Self) {
+        let mut conjunctions = self.conjunctions;
+        let mut scan_range = ScanRange::full_table_scan();
+
+        // Remove the first conjunction comparing the column with a constant by one of `ops`.
+        let mut take = |column: usize, ops: &[ExprType]| {
+            let i = conjunctions.iter().position(|expr| {
+                matches!(as_comparison_with_const(expr), Some((index, op, _))
+                    if index == column && ops.contains(&op))
+            })?;
+            let (_, op, value) = as_comparison_with_const(&conjunctions.remove(i))?;
+            Some(match op {
+                ExprType::GreaterThan | ExprType::LessThan => Bound::Excluded(value),
+                _ => Bound::Included(value),
+            })
+        };
+        for &column in pk_indices {
+            if let Some(Bound::Included(value)) = take(column, &[ExprType::Equal]) {
+                scan_range.eq_conds.push(value);
+                continue;
+            }
+            let lower = take(
+                column,
+                &[ExprType::GreaterThan, ExprType::GreaterThanOrEqual],
+            );
+            let upper = take(column, &[ExprType::LessThan, ExprType::LessThanOrEqual]);
+            scan_range.range = (
+                lower.unwrap_or(Bound::Unbounded),
+                upper.unwrap_or(Bound::Unbounded),
+            );
+            break;
+        }
+
+        (scan_range, Self { conjunctions })
+    }
+
     #[must_use]
     /// Split the condition expressions into `N` groups.
     /// An expression `expr` is in the `i`-th group if `f(expr)==i`.
@@ -211,6 +263,15 @@ impl Condition {
         }
     }
 
+    /// Evaluate constant sub-expressions of the conjunctions and simplify the result.
+    #[must_use]
+    pub fn fold_const(self) -> Self {
+        Self {
+            conjunctions: self.conjunctions.into_iter().map(fold_const).collect(),
+        }
+        .simplify()
+    }
+
     pub fn visit_expr(&self, visitor: &mut impl ExprVisitor) {
         self.conjunctions
             .iter()
@@ -231,14 +292,20 @@ impl Condition {
 
         let mut res: Vec<ExprImpl> = Vec::new();
         for i in conjunctions {
-            if let Some(v) = try_get_bool_constant(&i) {
+            // a `null` in conditions rejects rows just like `false`
+            let constant = if i.is_null() {
+                Some(false)
+            } else {
+                try_get_bool_constant(&i)
+            };
+            if let Some(v) = constant {
                 if !v {
                     // if there is a `false` in conjunctions, the whole condition will be `false`
                     res.clear();
                     res.push(ExprImpl::literal_bool(false));
                     break;
                 }
-            } else {
+            } else if !res.contains(&i) {
                 res.push(i);
             }
         }
@@ -246,13 +313,49 @@ impl Condition {
     }
 }
 
+/// If the expression compares a column with a non-null constant of the same type, returns the
+/// index of the column, the comparison with the column on the left side, and the constant.
+pub fn as_comparison_with_const(expr: &ExprImpl) -> Option<(usize, ExprType, ScalarImpl)> {
+    let func_call = expr.as_function_call()?;
+    let (input_ref, op, literal) = match func_call.inputs() {
+        [ExprImpl::InputRef(input_ref), ExprImpl::Literal(literal)] => {
+            (input_ref, func_call.get_expr_type(), literal)
+        }
+        [ExprImpl::Literal(literal), ExprImpl::InputRef(input_ref)] => {
+            let op = match func_call.get_expr_type() {
+                ExprType::Equal => ExprType::Equal,
+                ExprType::LessThan => ExprType::GreaterThan,
+                ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+                ExprType::GreaterThan => ExprType::LessThan,
+                ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+                _ => return None,
+            };
+            (input_ref, op, literal)
+        }
+        _ => return None,
+    };
+    if input_ref.return_type() != literal.return_type() {
+        return None;
+    }
+    match op {
+        ExprType::Equal
+        | ExprType::LessThan
+        | ExprType::LessThanOrEqual
+        | ExprType::GreaterThan
+        | ExprType::GreaterThanOrEqual => {
+            Some((input_ref.index(), op, literal.get_data().clone()?))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::Rng;
     use risingwave_common::types::DataType;
 
     use super::*;
-    use crate::expr::{FunctionCall, InputRef};
+    use crate::expr::{FunctionCall, InputRef, Literal};
 
     #[test]
     fn test_split() {
@@ -310,4 +413,44 @@ mod tests {
         assert_eq!(res.1.conjunctions, vec![right]);
         assert_eq!(res.2.conjunctions, vec![other]);
     }
+
+    #[test]
+    fn test_split_to_scan_range() {
+        let ty = DataType::Int32;
+        let cmp = |op, index, value: i32| -> ExprImpl {
+            FunctionCall::new(
+                op,
+                vec![
+                    InputRef::new(index, ty.clone()).into(),
+                    Literal::new(Some(value.into()), ty.clone()).into(),
+                ],
+            )
+            .unwrap()
+            .into()
+        };
+        let eq = cmp(ExprType::Equal, 1, 1);
+        let lower = cmp(ExprType::GreaterThan, 0, 2);
+        let upper = cmp(ExprType::LessThanOrEqual, 0, 3);
+        let other = cmp(ExprType::Equal, 2, 4);
+        let cond = Condition::with_expr(lower)
+            .and(Condition::with_expr(other.clone()))
+            .and(Condition::with_expr(upper))
+            .and(Condition::with_expr(eq));
+
+        // pk: [$1, $0, $2]
+        let (scan_range, cond) = cond.split_to_scan_range(&[1, 0, 2]);
+        assert_eq!(
+            scan_range,
+            ScanRange {
+                eq_conds: vec![1_i32.into()],
+                range: (Bound::Excluded(2_i32.into()), Bound::Included(3_i32.into())),
+            }
+        );
+        assert_eq!(cond.conjunctions, vec![other]);
+
+        // pk: [$2, $1]
+        let (scan_range, cond) = cond.split_to_scan_range(&[2, 1]);
+        assert_eq!(scan_range.eq_conds, vec![4_i32.into()]);
+        assert!(cond.always_true());
+    }
 }
@@ -16,6 +16,8 @@ mod column_index_mapping;
 pub use column_index_mapping::*;
 mod condition;
 pub use condition::*;
+mod scan_range;
+pub use scan_range::*;
 
 use crate::expr::{Expr, ExprImpl, ExprRewriter, InputRef};
 
@@ -0,0 +1,108 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Bound;
+
+use risingwave_common::array::Row;
+use risingwave_common::types::{Datum, ScalarImpl};
+use risingwave_common::util::ordered::OrderedRowSerializer;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::plan::scan_range::Bound as ProstBound;
+use risingwave_pb::plan::ScanRange as ProstScanRange;
+
+/// The range of pks to scan, which is extracted from a predicate by
+/// [`Condition::split_to_scan_range`].
+///
+/// [`Condition::split_to_scan_range`]: super::Condition::split_to_scan_range
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanRange {
+    /// The values of the leading pk columns, fixed by equality conditions.
+    pub eq_conds: Vec<ScalarImpl>,
+    /// The bounds of the pk column right after `eq_conds`.
+    pub range: (Bound<ScalarImpl>, Bound<ScalarImpl>),
+}
+
+impl ScanRange {
+    pub fn full_table_scan() -> Self {
+        Self {
+            eq_conds: vec![],
+            range: (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
+    pub fn is_full_table_scan(&self) -> bool {
+        self.eq_conds.is_empty() && self.range == (Bound::Unbounded, Bound::Unbounded)
+    }
+
+    /// Encodes the values in memcomparable format. `order_types` are the orders of the pk
+    /// columns, which decide the encoding of the values and the direction of the range.
+    pub fn to_protobuf(&self, order_types: &[OrderType]) -> ProstScanRange {
+        let encode = |datum: Datum, order_type: OrderType| {
+            let mut value = vec![];
+            OrderedRowSerializer::new(vec![order_type]).serialize(&Row(vec![datum]), &mut value);
+            value
+        };
+        let eq_conds = self
+            .eq_conds
+            .iter()
+            .zip(order_types)
+            .map(|(value, order_type)| encode(Some(value.clone()), *order_type))
+            .collect();
+
+        let to_prost_bound = |bound: Bound<Datum>, order_type: OrderType| match bound {
+            Bound::Included(datum) => Some(ProstBound {
+                value: encode(datum, order_type),
+                inclusive: true,
+            }),
+            Bound::Excluded(datum) => Some(ProstBound {
+                value: encode(datum, order_type),
+                inclusive: false,
+            }),
+            Bound::Unbounded => None,
+        };
+        let (lower_bound, upper_bound) = match &self.range {
+            (Bound::Unbounded, Bound::Unbounded) => (None, None),
+            (lower, upper) => {
+                let (lower, upper) = match lower {
+                    // `NULL` is encoded as the smallest value, which fails any comparison.
+                    Bound::Unbounded => (Bound::Excluded(None), to_datum_bound(upper)),
+                    lower => (to_datum_bound(lower), to_datum_bound(upper)),
+                };
+                let order_type = order_types[self.eq_conds.len()];
+                let (lower, upper) = match order_type {
+                    OrderType::Ascending => (lower, upper),
+                    OrderType::Descending => (upper, lower),
+                };
+                (
+                    to_prost_bound(lower, order_type),
+                    to_prost_bound(upper, order_type),
+                )
+            }
+        };
+
+        ProstScanRange {
+            eq_conds,
+            lower_bound,
+            upper_bound,
+        }
+    }
+}
+
+fn to_datum_bound(bound: &Bound<ScalarImpl>) -> Bound<Datum> {
+    match bound {
+        Bound::Included(value) => Bound::Included(Some(value.clone())),
+        Bound::Excluded(value) => Bound::Excluded(Some(value.clone())),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
@@ -22,7 +22,9 @@ use std::sync::Arc;
 use anyhow::{anyhow, Result};
 pub use resolve_id::*;
 use risingwave_frontend::binder::Binder;
-use risingwave_frontend::handler::{create_mv, create_source, create_table, drop_table};
+use risingwave_frontend::handler::{
+    create_index, create_mv, create_source, create_table, drop_table, set,
+};
 use risingwave_frontend::optimizer::PlanRef;
 use risingwave_frontend::planner::Planner;
 use risingwave_frontend::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
@@ -76,6 +78,9 @@ pub struct TestCase {
     /// Error of optimizer
     pub optimizer_error: Option<String>,
 
+    /// Error of stream planning `.gen_create_mv_plan()`
+    pub stream_error: Option<String>,
+
     /// Support using file content or file location to create source.
     pub create_source: Option<CreateSource>,
 }
@@ -119,6 +124,9 @@ pub struct TestCaseResult {
 
     /// Error of optimizer
     pub optimizer_error: Option<String>,
+
+    /// Error of stream planning `.gen_create_mv_plan()`
+    pub stream_error: Option<String>,
 }
 
 impl TestCaseResult {
@@ -133,6 +141,9 @@ impl TestCaseResult {
         if original_test_case.optimizer_error.is_none() && let Some(ref err) = self.optimizer_error {
             return Err(anyhow!("unexpected optimizer error: {}", err));
         }
+        if original_test_case.stream_error.is_none() && let Some(ref err) = self.stream_error {
+            return Err(anyhow!("unexpected stream error: {}", err));
+        }
 
         let case = TestCase {
             id: original_test_case.id.clone(),
@@ -147,6 +158,7 @@ impl TestCaseResult {
             batch_plan_proto: self.batch_plan_proto,
             planner_error: self.planner_error,
             optimizer_error: self.optimizer_error,
+            stream_error: self.stream_error,
             binder_error: self.binder_error,
             create_source: original_test_case.create_source.clone(),
         };
@@ -251,10 +263,34 @@ impl TestCase {
                 } => {
                     create_mv::handle_create_mv(context, name, query).await?;
                 }
+                Statement::CreateIndex {
+                    name,
+                    table_name,
+                    columns,
+                    unique,
+                    if_not_exists,
+                } => {
+                    create_index::handle_create_index(
+                        context,
+                        name,
+                        table_name,
+                        columns,
+                        unique,
+                        if_not_exists,
+                    )
+                    .await?;
+                }
                 Statement::Drop(drop_statement) => {
                     let table_object_name = ObjectName(vec![drop_statement.name]);
                     drop_table::handle_drop_table(context, table_object_name).await?;
                 }
+                Statement::SetVariable {
+                    local: _,
+                    variable,
+                    value,
+                } => {
+                    set::handle_set(context, variable, value)?;
+                }
                 _ => return Err(anyhow!("Unsupported statement type")),
             }
         }
@@ -273,7 +309,10 @@ impl TestCase {
             let mut binder = Binder::new(
                 session.env().catalog_reader().read_guard(),
                 session.database().to_string(),
-            );
+            )
+            .with_timezone(session.timezone())
+            .with_search_path(session.search_path())
+            .for_explain();
             match binder.bind(stmt.clone()) {
                 Ok(bound) => bound,
                 Err(err) => {
@@ -305,7 +344,13 @@ impl TestCase {
         }
 
         if self.batch_plan.is_some() || self.batch_plan_proto.is_some() {
-            let batch_plan = logical_plan.gen_dist_batch_query_plan();
+            let batch_plan = match logical_plan.gen_dist_batch_query_plan() {
+                Ok(batch_plan) => batch_plan,
+                Err(err) => {
+                    ret.optimizer_error = Some(err.to_string());
+                    return Ok(ret);
+                }
+            };
 
             // Only generate batch_plan if it is specified in test case
             if self.batch_plan.is_some() {
@@ -320,19 +365,28 @@ impl TestCase {
             }
         }
 
-        if self.stream_plan.is_some() || self.stream_plan_proto.is_some() {
+        if self.stream_plan.is_some()
+            || self.stream_plan_proto.is_some()
+            || self.stream_error.is_some()
+        {
             let q = if let Statement::Query(q) = stmt {
                 q.as_ref().clone()
             } else {
                 return Err(anyhow!("expect a query"));
             };
 
-            let (stream_plan, table) = create_mv::gen_create_mv_plan(
+            let (stream_plan, table) = match create_mv::gen_create_mv_plan(
                 &session,
                 context,
                 Box::new(q),
                 ObjectName(vec!["test".into()]),
-            )?;
+            ) {
+                Ok(result) => result,
+                Err(err) => {
+                    ret.stream_error = Some(err.to_string());
+                    return Ok(ret);
+                }
+            };
 
             // Only generate stream_plan if it is specified in test case
             if self.stream_plan.is_some() {
@@ -364,6 +418,7 @@ fn check_result(expected: &TestCase, actual: &TestCaseResult) -> Result<()> {
         &expected.optimizer_error,
         &actual.optimizer_error,
     )?;
+    check_err("stream", &expected.stream_error, &actual.stream_error)?;
     check_option_plan_eq("logical_plan", &expected.logical_plan, &actual.logical_plan)?;
     check_option_plan_eq(
         "optimized_logical_plan",
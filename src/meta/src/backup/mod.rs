@@ -0,0 +1,271 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backup and restore of meta data.
+//!
+//! A backup consists of column families of the meta store, taken from the same snapshot in which
+//! the pinned hummock version is the current one. Copying the SSTs referenced by the version is up
+//! to the caller, who should unpin the version afterwards.
+
+use itertools::Itertools;
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_hummock_sdk::HummockContextId;
+use risingwave_pb::meta::meta_backup::ColumnFamily;
+use risingwave_pb::meta::MetaBackup;
+
+use crate::hummock::{HummockManager, HUMMOCK_CF_NAMES};
+use crate::model::{
+    CATALOG_DATABASE_CF_NAME, CATALOG_SCHEMA_CF_NAME, CATALOG_SOURCE_CF_NAME,
+    CATALOG_TABLE_CF_NAME, CATALOG_VERSION_CF_NAME, DATABASE_CF_NAME, HASH_MAPPING_CF_NAME,
+    SCHEMA_CF_NAME, TABLE_CF_NAME, TABLE_FRAGMENTS_CF_NAME,
+};
+use crate::storage::{self, MetaStore, Snapshot, Transaction, DEFAULT_COLUMN_FAMILY};
+
+/// Column family holding the state of restoring, which is not part of any backup.
+const RESTORE_STATE_CF_NAME: &str = "cf/meta_restore_state";
+const RESTORE_STATE_KEY: &[u8] = b"state";
+const RESTORE_IN_PROGRESS: &[u8] = b"in_progress";
+const RESTORE_COMPLETE: &[u8] = b"complete";
+
+/// Restored keys are written in transactions of at most this many operations or bytes, which are
+/// below the default limits of etcd (128 operations and 1.5 MiB per request).
+const RESTORE_BATCH_MAX_OPS: usize = 64;
+const RESTORE_BATCH_MAX_BYTES: usize = 1024 * 1024;
+
+/// Column families included in a backup.
+///
+/// Workers are excluded, as they only make sense in the cluster being backed up. Hummock versions
+/// and snapshots pinned by them are released when the restored cluster starts.
+fn backup_column_families() -> Vec<&'static str> {
+    // The default column family holds the id generators.
+    let mut cfs = vec![DEFAULT_COLUMN_FAMILY];
+    // Catalog.
+    cfs.extend([
+        DATABASE_CF_NAME,
+        SCHEMA_CF_NAME,
+        TABLE_CF_NAME,
+        CATALOG_VERSION_CF_NAME,
+        CATALOG_DATABASE_CF_NAME,
+        CATALOG_SCHEMA_CF_NAME,
+        CATALOG_TABLE_CF_NAME,
+        CATALOG_SOURCE_CF_NAME,
+    ]);
+    // Fragments.
+    cfs.extend([TABLE_FRAGMENTS_CF_NAME, HASH_MAPPING_CF_NAME]);
+    // Hummock.
+    cfs.extend(HUMMOCK_CF_NAMES);
+    cfs
+}
+
+/// Pins the current hummock version for `context_id` and exports meta data.
+pub async fn backup_meta<S: MetaStore>(
+    hummock_manager: &HummockManager<S>,
+    context_id: HummockContextId,
+) -> Result<MetaBackup> {
+    let (hummock_version, snapshot) = hummock_manager
+        .pin_version_with_snapshot(context_id)
+        .await?;
+    let mut column_families = vec![];
+    for cf in backup_column_families() {
+        let (keys, values) = snapshot.list_cf_kv(cf).await?.into_iter().unzip();
+        column_families.push(ColumnFamily {
+            name: cf.to_string(),
+            keys,
+            values,
+        });
+    }
+    Ok(MetaBackup {
+        hummock_version: Some(hummock_version),
+        column_families,
+    })
+}
+
+/// Writes `backup` into `meta_store`, which must not contain any data of the backed up column
+/// families. Should be called before any manager is created on `meta_store`.
+///
+/// Keys are written in bounded batches. A marker is written before the first batch and updated
+/// after the last one, so that a partially restored store is rejected by
+/// [`check_meta_restore_state`] instead of being used.
+pub async fn restore_meta<S: MetaStore>(meta_store: &S, backup: MetaBackup) -> Result<()> {
+    check_meta_restore_state(meta_store).await?;
+    let snapshot = meta_store.snapshot().await;
+    for cf in backup_column_families() {
+        if !snapshot.list_cf(cf).await?.is_empty() {
+            return Err(RwError::from(InternalError(format!(
+                "failed to restore meta: column family {} is not empty",
+                cf
+            ))));
+        }
+    }
+    drop(snapshot);
+
+    let cfs = backup_column_families();
+    if let Some(cf) = backup
+        .column_families
+        .iter()
+        .find(|cf| !cfs.contains(&cf.name.as_str()))
+    {
+        return Err(RwError::from(InternalError(format!(
+            "failed to restore meta: unknown column family {}",
+            cf.name
+        ))));
+    }
+
+    meta_store
+        .put_cf(
+            RESTORE_STATE_CF_NAME,
+            RESTORE_STATE_KEY.to_vec(),
+            RESTORE_IN_PROGRESS.to_vec(),
+        )
+        .await?;
+    let mut trx = Transaction::default();
+    let (mut batch_ops, mut batch_bytes) = (0, 0);
+    for cf in backup.column_families {
+        for (key, value) in cf.keys.into_iter().zip_eq(cf.values) {
+            let bytes = key.len() + value.len();
+            if batch_ops > 0
+                && (batch_ops >= RESTORE_BATCH_MAX_OPS
+                    || batch_bytes + bytes > RESTORE_BATCH_MAX_BYTES)
+            {
+                meta_store.txn(std::mem::take(&mut trx)).await?;
+                batch_ops = 0;
+                batch_bytes = 0;
+            }
+            trx.put(cf.name.clone(), key, value);
+            batch_ops += 1;
+            batch_bytes += bytes;
+        }
+    }
+    if batch_ops > 0 {
+        meta_store.txn(trx).await?;
+    }
+    meta_store
+        .put_cf(
+            RESTORE_STATE_CF_NAME,
+            RESTORE_STATE_KEY.to_vec(),
+            RESTORE_COMPLETE.to_vec(),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Fails if a restore into `meta_store` was started but not completed, e.g. because meta crashed
+/// in the middle of it. Such a store holds an arbitrary part of the backup and must be cleared
+/// before restoring again.
+pub async fn check_meta_restore_state<S: MetaStore>(meta_store: &S) -> Result<()> {
+    match meta_store
+        .get_cf(RESTORE_STATE_CF_NAME, RESTORE_STATE_KEY)
+        .await
+    {
+        Ok(state) if state == RESTORE_IN_PROGRESS => Err(RwError::from(InternalError(
+            "meta store is partially restored from a backup, clear it and restore again"
+                .to_string(),
+        ))),
+        Ok(_) | Err(storage::Error::ItemNotFound(_)) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cluster::ClusterManager;
+    use crate::hummock::test_utils::{add_test_tables, setup_compute_env};
+    use crate::manager::{MemEpochGenerator, MetaOpts, MetaSrvEnv};
+    use crate::rpc::metrics::MetaMetrics;
+    use crate::storage::MemStore;
+
+    #[tokio::test]
+    async fn test_backup_restore() {
+        let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+        let context_id = worker_node.id;
+        add_test_tables(hummock_manager.as_ref(), context_id).await;
+
+        let backup = backup_meta(hummock_manager.as_ref(), context_id)
+            .await
+            .unwrap();
+        let version = backup.hummock_version.clone().unwrap();
+        assert_eq!(version, hummock_manager.get_current_version().await);
+        assert_eq!(
+            hummock_manager
+                .get_version_pin_count(version.id)
+                .await
+                .unwrap(),
+            1
+        );
+
+        let meta_store = Arc::new(MemStore::default());
+        restore_meta(meta_store.as_ref(), backup.clone())
+            .await
+            .unwrap();
+        check_meta_restore_state(meta_store.as_ref()).await.unwrap();
+        // Restoring into a store with data fails.
+        restore_meta(meta_store.as_ref(), backup.clone())
+            .await
+            .unwrap_err();
+
+        // A restore interrupted after the marker is written is detected.
+        let partial_store = MemStore::default();
+        partial_store
+            .put_cf(
+                RESTORE_STATE_CF_NAME,
+                RESTORE_STATE_KEY.to_vec(),
+                RESTORE_IN_PROGRESS.to_vec(),
+            )
+            .await
+            .unwrap();
+        check_meta_restore_state(&partial_store).await.unwrap_err();
+        restore_meta(&partial_store, backup).await.unwrap_err();
+
+        let env = MetaSrvEnv::new(
+            MetaOpts::default(),
+            meta_store,
+            Arc::new(MemEpochGenerator::new()),
+        )
+        .await;
+        let cluster_manager = Arc::new(
+            ClusterManager::new(env.clone(), Duration::from_secs(1))
+                .await
+                .unwrap(),
+        );
+        let restored_hummock_manager =
+            HummockManager::new(env, cluster_manager, Arc::new(MetaMetrics::new()))
+                .await
+                .unwrap();
+        assert_eq!(
+            restored_hummock_manager.get_current_version().await,
+            version
+        );
+        // Pins of workers in the backed up cluster are released.
+        assert_eq!(
+            restored_hummock_manager
+                .get_version_pin_count(version.id)
+                .await
+                .unwrap(),
+            0
+        );
+        // Ids are not reused.
+        let max_sst_id = version
+            .levels
+            .iter()
+            .flat_map(|level| level.table_infos.iter().map(|sst| sst.id))
+            .max()
+            .unwrap();
+        assert!(restored_hummock_manager.get_new_table_id().await.unwrap() > max_sst_id);
+    }
+}
@@ -14,19 +14,15 @@
 
 use std::io::Cursor;
 
-use bytes::Bytes;
 use itertools::{EitherOrBoth, Itertools};
 use prost::Message;
-use rand::seq::SliceRandom;
-use rand::thread_rng;
 use risingwave_common::error::Result;
-use risingwave_hummock_sdk::key::{user_key, FullKey};
-use risingwave_hummock_sdk::key_range::KeyRange;
 use risingwave_hummock_sdk::{HummockEpoch, HummockSSTableId};
 use risingwave_pb::hummock::{
-    CompactMetrics, CompactTask, Level, LevelEntry, LevelType, SstableInfo, TableSetStatistics,
+    CompactMetrics, CompactTask, Level, LevelEntry, LevelType, TableSetStatistics,
 };
 
+use crate::hummock::compaction_picker::{CompactionConfig, LevelCompactionPicker, PickResult};
 use crate::hummock::level_handler::{LevelHandler, SSTableStat};
 use crate::hummock::model::HUMMOCK_DEFAULT_CF_NAME;
 use crate::model::Transactional;
@@ -44,15 +40,13 @@ pub struct CompactStatus {
 }
 
 impl CompactStatus {
-    pub fn new() -> CompactStatus {
-        let vec_handler_having_l0 = vec![
-            LevelHandler::Overlapping(vec![], vec![]),
-            LevelHandler::Nonoverlapping(vec![], vec![]),
-        ];
-        CompactStatus {
-            level_handlers: vec_handler_having_l0,
+    pub fn new(max_level: usize) -> CompactStatus {
+        let mut compact_status = CompactStatus {
+            level_handlers: vec![LevelHandler::Overlapping(vec![], vec![])],
             next_compact_task_id: 1,
-        }
+        };
+        compact_status.ensure_levels(max_level);
+        compact_status
     }
 
     fn cf_name() -> &'static str {
@@ -80,323 +74,90 @@ impl CompactStatus {
         }
     }
 
-    pub fn get_compact_task(&mut self) -> Option<CompactTask> {
+    pub fn get_compact_task(&mut self, config: &CompactionConfig) -> Option<CompactTask> {
         // When we compact the files, we must make the result of compaction meet the following
         // conditions, for any user key, the epoch of it in the file existing in the lower
         // layer must be larger.
-        let num_levels = self.level_handlers.len();
-        let mut idle_levels = Vec::with_capacity(num_levels - 1);
-        for (level_handler_idx, level_handler) in
-            self.level_handlers[..num_levels - 1].iter().enumerate()
-        {
-            match level_handler {
-                LevelHandler::Overlapping(_, compacting_key_ranges)
-                | LevelHandler::Nonoverlapping(_, compacting_key_ranges) => {
-                    if compacting_key_ranges.is_empty() {
-                        idle_levels.push(level_handler_idx);
-                    }
-                }
-            }
-        }
-        let select_level = if idle_levels.is_empty() {
-            0
-        } else {
-            *idle_levels.first().unwrap() as u32
-        };
-
-        enum SearchResult {
-            Found(Vec<u64>, Vec<u64>, Vec<KeyRange>),
-            NotFound,
-        }
-
-        let mut found = SearchResult::NotFound;
         let next_task_id = self.next_compact_task_id;
-        let (prior, posterior) = self.level_handlers.split_at_mut(select_level as usize + 1);
-        let target_level = select_level + 1;
-        let (prior, posterior) = (prior.last_mut().unwrap(), posterior.first_mut().unwrap());
-        let is_select_level_leveling = matches!(prior, LevelHandler::Nonoverlapping(_, _));
-        let is_target_level_leveling = matches!(posterior, LevelHandler::Nonoverlapping(_, _));
-        // Try to select and merge table(s) in `select_level` into `target_level`
-        match prior {
-            LevelHandler::Overlapping(l_n, compacting_key_ranges)
-            | LevelHandler::Nonoverlapping(l_n, compacting_key_ranges) => {
-                let l_n_len = l_n.len();
-                let mut polysst_candidates = Vec::with_capacity(l_n_len);
-                {
-                    let mut sst_idx = 0;
-                    while sst_idx < l_n_len {
-                        let mut next_sst_idx = sst_idx + 1;
-                        let SSTableStat {
-                            key_range: sst_key_range,
-                            table_id,
-                            ..
-                        } = &l_n[sst_idx];
-                        let mut select_level_inputs = vec![*table_id];
-                        let key_range;
-                        let mut tier_key_range;
-                        // Must ensure that there exists no SSTs in `select_level` which have
-                        // overlapping user key with `select_level_inputs`
-                        if !is_select_level_leveling {
-                            tier_key_range = sst_key_range.clone();
-
-                            next_sst_idx = sst_idx;
-                            for (
-                                delta_idx,
-                                SSTableStat {
-                                    key_range: other_key_range,
-                                    table_id: other_table_id,
-                                    ..
-                                },
-                            ) in l_n[sst_idx + 1..].iter().enumerate()
-                            {
-                                if user_key(&other_key_range.left)
-                                    <= user_key(&tier_key_range.right)
-                                {
-                                    select_level_inputs.push(*other_table_id);
-                                    tier_key_range.full_key_extend(other_key_range);
-                                } else {
-                                    next_sst_idx = sst_idx + 1 + delta_idx;
-                                    break;
-                                }
-                            }
-                            if next_sst_idx == sst_idx {
-                                next_sst_idx = l_n_len;
-                            }
-
-                            key_range = &tier_key_range;
-                        } else {
-                            key_range = sst_key_range;
-                        }
-
-                        polysst_candidates.push((
-                            (sst_idx, next_sst_idx),
-                            select_level_inputs,
-                            key_range.clone(),
-                        ));
-
-                        sst_idx = next_sst_idx;
-                    }
-                }
-
-                let mut rng = thread_rng();
-                polysst_candidates.shuffle(&mut rng);
-
-                for ((sst_idx, next_sst_idx), select_level_inputs, key_range) in polysst_candidates
-                {
-                    let mut is_select_idle = true;
-                    for SSTableStat { compact_task, .. } in &l_n[sst_idx..next_sst_idx] {
-                        if compact_task.is_some() {
-                            is_select_idle = false;
-                            break;
-                        }
-                    }
-
-                    if is_select_idle {
-                        let insert_point =
-                            compacting_key_ranges.partition_point(|(ongoing_key_range, _, _)| {
-                                user_key(&ongoing_key_range.right) < user_key(&key_range.left)
-                            });
-                        // if following condition is not satisfied, it may result in two overlapping
-                        // SSTs in target level
-                        if insert_point >= compacting_key_ranges.len()
-                            || user_key(&compacting_key_ranges[insert_point].0.left)
-                                > user_key(&key_range.right)
-                        {
-                            match posterior {
-                                LevelHandler::Overlapping(_, _) => unimplemented!(),
-                                LevelHandler::Nonoverlapping(l_n_suc, _) => {
-                                    let mut overlap_all_idle = true;
-                                    let overlap_begin = l_n_suc.partition_point(|table_status| {
-                                        user_key(&table_status.key_range.right)
-                                            < user_key(&key_range.left)
-                                    });
-                                    let mut overlap_end = overlap_begin;
-                                    let l_n_suc_len = l_n_suc.len();
-                                    while overlap_end < l_n_suc_len
-                                        && user_key(&l_n_suc[overlap_end].key_range.left)
-                                            <= user_key(&key_range.right)
-                                    {
-                                        if l_n_suc[overlap_end].compact_task.is_some() {
-                                            overlap_all_idle = false;
-                                            break;
-                                        }
-                                        overlap_end += 1;
-                                    }
-                                    if overlap_all_idle {
-                                        // Here, we have known that `select_level_input` is valid
-                                        compacting_key_ranges.insert(
-                                            insert_point,
-                                            (
-                                                key_range,
-                                                next_task_id,
-                                                select_level_inputs.len() as u64,
-                                            ),
-                                        );
-
-                                        let mut suc_table_ids =
-                                            Vec::with_capacity(overlap_end - overlap_begin);
-
-                                        let mut splits =
-                                            Vec::with_capacity(overlap_end - overlap_begin);
-                                        splits.push(KeyRange::new(Bytes::new(), Bytes::new()));
-                                        let mut key_split_append = |key_before_last: &Bytes| {
-                                            splits.last_mut().unwrap().right =
-                                                key_before_last.clone();
-                                            splits.push(KeyRange::new(
-                                                key_before_last.clone(),
-                                                Bytes::new(),
-                                            ));
-                                        };
+        let PickResult {
+            select_level,
+            target_level,
+            select_level_inputs,
+            target_level_inputs,
+            splits,
+            ..
+        } = LevelCompactionPicker::new(config)
+            .pick_compaction(&mut self.level_handlers, next_task_id)?;
+        self.next_compact_task_id += 1;
+
+        let level_type = |level_idx: usize| match self.level_handlers[level_idx] {
+            LevelHandler::Overlapping(_, _) => LevelType::Overlapping,
+            LevelHandler::Nonoverlapping(_, _) => LevelType::Nonoverlapping,
+        };
+        let is_target_level_leveling = level_type(target_level) == LevelType::Nonoverlapping;
+        let table_set_statistics = |level_idx: usize, ssts: &[SSTableStat]| TableSetStatistics {
+            level_idx: level_idx as u32,
+            size_gb: ssts.iter().map(|sst| sst.file_size).sum::<u64>() as f64
+                / (1024 * 1024 * 1024) as f64,
+            cnt: ssts.len() as u64,
+        };
+        let compact_task = CompactTask {
+            input_ssts: vec![
+                LevelEntry {
+                    level_idx: select_level as u32,
+                    level: Some(Level {
+                        level_type: level_type(select_level) as i32,
+                        table_infos: select_level_inputs.iter().map_into().collect_vec(),
+                    }),
+                },
+                LevelEntry {
+                    level_idx: target_level as u32,
+                    level: Some(Level {
+                        level_type: level_type(target_level) as i32,
+                        table_infos: target_level_inputs.iter().map_into().collect_vec(),
+                    }),
+                },
+            ],
+            splits: splits.into_iter().map_into().collect_vec(),
+            watermark: HummockEpoch::MAX,
+            sorted_output_ssts: vec![],
+            task_id: next_task_id,
+            target_level: target_level as u32,
+            is_target_ultimate_and_leveling: target_level == self.level_handlers.len() - 1
+                && is_target_level_leveling,
+            metrics: Some(CompactMetrics {
+                read_level_n: Some(table_set_statistics(select_level, &select_level_inputs)),
+                read_level_nplus1: Some(table_set_statistics(target_level, &target_level_inputs)),
+                write: Some(TableSetStatistics {
+                    level_idx: target_level as u32,
+                    size_gb: 0f64,
+                    cnt: 0,
+                }),
+            }),
+            task_status: false,
+        };
+        Some(compact_task)
+    }
 
-                                        let mut overlap_idx = overlap_begin;
-                                        while overlap_idx < overlap_end {
-                                            l_n_suc[overlap_idx].compact_task = Some(next_task_id);
-                                            suc_table_ids.push(l_n_suc[overlap_idx].table_id);
-                                            if overlap_idx > overlap_begin {
-                                                // TODO: We do not need to add splits every time. We
-                                                // can add every K SSTs.
-                                                key_split_append(
-                                                    &FullKey::from_user_key_slice(
-                                                        user_key(
-                                                            &l_n_suc[overlap_idx].key_range.left,
-                                                        ),
-                                                        HummockEpoch::MAX,
-                                                    )
-                                                    .into_inner()
-                                                    .into(),
-                                                );
-                                            }
-                                            overlap_idx += 1;
-                                        }
+    /// Whether `compact_task` is a trivial move, which can be applied by meta directly with its
+    /// input SSTs as output. See [`PickResult::is_trivial_move`].
+    pub fn is_trivial_move_task(compact_task: &CompactTask) -> bool {
+        let (select, target) = match compact_task.input_ssts.as_slice() {
+            [select, target] => (select, target),
+            _ => return false,
+        };
+        select.level_idx != 0
+            && target
+                .level
+                .as_ref()
+                .map_or(true, |level| level.table_infos.is_empty())
+    }
 
-                                        found = SearchResult::Found(
-                                            select_level_inputs,
-                                            suc_table_ids,
-                                            splits,
-                                        );
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                match &found {
-                    SearchResult::Found(select_ln_ids, _, _) => {
-                        let mut select_ln_iter = select_ln_ids.iter();
-                        if let Some(first_id) = select_ln_iter.next() {
-                            let mut current_id = first_id;
-                            for SSTableStat {
-                                table_id,
-                                compact_task,
-                                ..
-                            } in l_n
-                            {
-                                if table_id == current_id {
-                                    *compact_task = Some(next_task_id);
-                                    match select_ln_iter.next() {
-                                        Some(next_id) => {
-                                            current_id = next_id;
-                                        }
-                                        None => break,
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    SearchResult::NotFound => {}
-                }
-            }
-        }
-        match found {
-            SearchResult::Found(select_ln_ids, select_lnsuc_ids, splits) => {
-                self.next_compact_task_id += 1;
-                let compact_task = CompactTask {
-                    input_ssts: vec![
-                        LevelEntry {
-                            level_idx: select_level,
-                            level: if is_select_level_leveling {
-                                Some(Level {
-                                    level_type: LevelType::Nonoverlapping as i32,
-                                    table_infos: select_ln_ids
-                                        .into_iter()
-                                        .map(|id| SstableInfo {
-                                            id,
-                                            // compact node will never use key_range in SstableInfo.
-                                            key_range: None,
-                                        })
-                                        .collect_vec(),
-                                })
-                            } else {
-                                Some(Level {
-                                    level_type: LevelType::Overlapping as i32,
-                                    table_infos: select_ln_ids
-                                        .into_iter()
-                                        .map(|id| SstableInfo {
-                                            id,
-                                            key_range: None,
-                                        })
-                                        .collect_vec(),
-                                })
-                            },
-                        },
-                        LevelEntry {
-                            level_idx: target_level,
-                            level: if is_target_level_leveling {
-                                Some(Level {
-                                    level_type: LevelType::Nonoverlapping as i32,
-                                    table_infos: select_lnsuc_ids
-                                        .into_iter()
-                                        .map(|id| SstableInfo {
-                                            id,
-                                            key_range: None,
-                                        })
-                                        .collect_vec(),
-                                })
-                            } else {
-                                Some(Level {
-                                    level_type: LevelType::Overlapping as i32,
-                                    table_infos: select_lnsuc_ids
-                                        .into_iter()
-                                        .map(|id| SstableInfo {
-                                            id,
-                                            key_range: None,
-                                        })
-                                        .collect_vec(),
-                                })
-                            },
-                        },
-                    ],
-                    splits: splits.iter().map(|v| v.clone().into()).collect_vec(),
-                    watermark: HummockEpoch::MAX,
-                    sorted_output_ssts: vec![],
-                    task_id: next_task_id,
-                    target_level,
-                    is_target_ultimate_and_leveling: target_level as usize
-                        == self.level_handlers.len() - 1
-                        && is_target_level_leveling,
-                    metrics: Some(CompactMetrics {
-                        read_level_n: Some(TableSetStatistics {
-                            level_idx: select_level,
-                            size_gb: 0f64,
-                            cnt: 0,
-                        }),
-                        read_level_nplus1: Some(TableSetStatistics {
-                            level_idx: target_level,
-                            size_gb: 0f64,
-                            cnt: 0,
-                        }),
-                        write: Some(TableSetStatistics {
-                            level_idx: target_level,
-                            size_gb: 0f64,
-                            cnt: 0,
-                        }),
-                    }),
-                    task_status: false,
-                };
-                Some(compact_task)
-            }
-            SearchResult::NotFound => None,
+    /// Appends empty non-overlapping levels until `max_level` is the bottommost level.
+    pub fn ensure_levels(&mut self, max_level: usize) {
+        while self.level_handlers.len() <= max_level {
+            self.level_handlers
+                .push(LevelHandler::Nonoverlapping(vec![], vec![]));
         }
     }
 
@@ -423,6 +184,12 @@ impl CompactStatus {
                     // The task has been processed previously.
                     return None;
                 }
+                // SSTs relinked by a trivial move are still alive in the target level.
+                delete_table_ids.retain(|table_id| {
+                    !output_table_compact_entries
+                        .iter()
+                        .any(|output| output.table_id == *table_id)
+                });
                 match &mut self.level_handlers[compact_task.target_level as usize] {
                     LevelHandler::Overlapping(l_n, _) | LevelHandler::Nonoverlapping(l_n, _) => {
                         let old_ln = std::mem::take(l_n);
@@ -484,7 +251,7 @@ impl Transactional for CompactStatus {
 
 impl Default for CompactStatus {
     fn default() -> Self {
-        Self::new()
+        Self::new(CompactionConfig::default().max_level)
     }
 }
 
@@ -0,0 +1,479 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use bytes::Bytes;
+use risingwave_hummock_sdk::key::{user_key, FullKey};
+use risingwave_hummock_sdk::key_range::KeyRange;
+use risingwave_hummock_sdk::HummockEpoch;
+
+use crate::hummock::level_handler::{LevelHandler, SSTableStat};
+
+/// Options of the leveled compaction picker.
+///
+/// L0 is an overlapping level fed by shared buffer flushes. `L1..=max_level` are non-overlapping
+/// levels, and the target size of each of them grows by `max_bytes_for_level_multiplier`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactionConfig {
+    /// Index of the bottommost level.
+    pub max_level: usize,
+    /// Target size of L1 in bytes.
+    pub max_bytes_for_level_base: u64,
+    /// Target size of `L(n+1)` is `max_bytes_for_level_multiplier` times the target size of `Ln`.
+    pub max_bytes_for_level_multiplier: u64,
+    /// L0 is compacted once it holds this many SSTs which are not being compacted.
+    pub level0_trigger_file_num: usize,
+    /// Upper bound of the input size of a task picked from a non-overlapping level. A single SST
+    /// and the SSTs it overlaps with in the next level are always allowed.
+    pub max_compaction_bytes: u64,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            max_level: 6,
+            max_bytes_for_level_base: 512 * 1024 * 1024,
+            max_bytes_for_level_multiplier: 10,
+            level0_trigger_file_num: 4,
+            max_compaction_bytes: 2 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+impl CompactionConfig {
+    /// Target size of `Ln` in bytes, `n >= 1`.
+    pub fn max_bytes_for_level(&self, level_idx: usize) -> u64 {
+        debug_assert!(level_idx >= 1);
+        self.max_bytes_for_level_base.saturating_mul(
+            self.max_bytes_for_level_multiplier
+                .saturating_pow(level_idx as u32 - 1),
+        )
+    }
+}
+
+/// SSTs picked by [`LevelCompactionPicker`] for a compaction task.
+#[derive(Debug, PartialEq)]
+pub struct PickResult {
+    pub select_level: usize,
+    pub target_level: usize,
+    pub select_level_inputs: Vec<SSTableStat>,
+    pub target_level_inputs: Vec<SSTableStat>,
+    /// Key range covered by `select_level_inputs`.
+    pub key_range: KeyRange,
+    pub splits: Vec<KeyRange>,
+}
+
+impl PickResult {
+    /// A trivial move relinks SSTs of a non-overlapping level to the next level without rewriting
+    /// them, because nothing in the next level overlaps with them.
+    pub fn is_trivial_move(&self) -> bool {
+        self.select_level != 0 && self.target_level_inputs.is_empty()
+    }
+}
+
+/// Picks compaction tasks based on the score of each level.
+///
+/// * The score of L0 is the number of idle SSTs divided by `level0_trigger_file_num`.
+/// * The score of `Ln` is the size of idle SSTs divided by the target size of `Ln`.
+///
+/// Levels with score no less than 1 are tried in descending order of score. Tasks only take SSTs
+/// that are not being compacted, and key ranges being compacted are recorded in
+/// [`LevelHandler`], so tasks over disjoint key ranges can run in parallel.
+pub struct LevelCompactionPicker<'a> {
+    config: &'a CompactionConfig,
+}
+
+impl<'a> LevelCompactionPicker<'a> {
+    pub fn new(config: &'a CompactionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns `(score, level_idx)` of levels that need compaction, with higher score first.
+    /// The bottommost level is never scored because it has no level to be compacted into.
+    pub fn level_scores(&self, levels: &[LevelHandler]) -> Vec<(f64, usize)> {
+        if levels.len() < 2 {
+            return vec![];
+        }
+        let mut scores = levels[..levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level_idx, level)| {
+                let score = if level_idx == 0 {
+                    level.idle_file_count() as f64
+                        / self.config.level0_trigger_file_num.max(1) as f64
+                } else {
+                    level.idle_file_size() as f64
+                        / self.config.max_bytes_for_level(level_idx).max(1) as f64
+                };
+                (score, level_idx)
+            })
+            .filter(|(score, _)| *score >= 1.0)
+            .collect::<Vec<_>>();
+        // Stable sort, so upper levels win ties.
+        scores.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        scores
+    }
+
+    /// Picks a task and marks its input SSTs in `levels` as being compacted by `task_id`.
+    pub fn pick_compaction(&self, levels: &mut [LevelHandler], task_id: u64) -> Option<PickResult> {
+        for (_, select_level) in self.level_scores(levels) {
+            let picked = if select_level == 0 {
+                self.pick_l0(levels)
+            } else {
+                self.pick_ln(levels, select_level)
+            };
+            if let Some(result) = picked {
+                let select_ids = result
+                    .select_level_inputs
+                    .iter()
+                    .map(|sst| sst.table_id)
+                    .collect::<Vec<_>>();
+                let target_ids = result
+                    .target_level_inputs
+                    .iter()
+                    .map(|sst| sst.table_id)
+                    .collect::<Vec<_>>();
+                levels[result.select_level].add_compacting_key_range(
+                    result.key_range.clone(),
+                    task_id,
+                    select_ids.len() as u64,
+                );
+                levels[result.select_level].assign_task(task_id, &select_ids);
+                levels[result.target_level].assign_task(task_id, &target_ids);
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// L0 SSTs whose user key ranges overlap transitively must be compacted together, otherwise
+    /// an older version of a key may end up above a newer one.
+    fn pick_l0(&self, levels: &[LevelHandler]) -> Option<PickResult> {
+        let l0 = &levels[0];
+        let target = levels[1].ssts();
+        let ssts = l0.ssts();
+
+        // Sweep the SSTs in the order of their left user keys, so that an SST joins the current
+        // group iff it overlaps with the union of the group's key ranges.
+        let mut sorted_indices = (0..ssts.len()).collect::<Vec<_>>();
+        sorted_indices.sort_by(|a, b| {
+            user_key(&ssts[*a].key_range.left).cmp(user_key(&ssts[*b].key_range.left))
+        });
+        let mut groups: Vec<(Vec<usize>, KeyRange)> = vec![];
+        for idx in sorted_indices {
+            let sst = &ssts[idx];
+            match groups.last_mut() {
+                Some((indices, key_range))
+                    if user_key(&sst.key_range.left) <= user_key(&key_range.right) =>
+                {
+                    indices.push(idx);
+                    key_range.full_key_extend(&sst.key_range);
+                }
+                _ => groups.push((vec![idx], sst.key_range.clone())),
+            }
+        }
+        // Compacting more SSTs at once reduces read amplification the most.
+        groups.sort_by_key(|(indices, _)| std::cmp::Reverse(indices.len()));
+
+        for (mut indices, key_range) in groups {
+            if indices.iter().any(|idx| ssts[*idx].compact_task.is_some())
+                || is_compacting(l0, &key_range)
+            {
+                continue;
+            }
+            let target_range = overlapping_ssts(target, &key_range);
+            if target[target_range.clone()]
+                .iter()
+                .any(|sst| sst.compact_task.is_some())
+            {
+                continue;
+            }
+            let target_level_inputs = target[target_range].to_vec();
+            // Keep the SSTs in the order they were added to L0.
+            indices.sort_unstable();
+            return Some(PickResult {
+                select_level: 0,
+                target_level: 1,
+                select_level_inputs: indices.into_iter().map(|idx| ssts[idx].clone()).collect(),
+                splits: gen_splits(&target_level_inputs),
+                target_level_inputs,
+                key_range,
+            });
+        }
+        None
+    }
+
+    /// Picks the SST of `Ln` whose compaction rewrites the least data of `L(n+1)` relative to its
+    /// own size, then extends the input with following SSTs while `max_compaction_bytes` allows.
+    fn pick_ln(&self, levels: &[LevelHandler], select_level: usize) -> Option<PickResult> {
+        let select = &levels[select_level];
+        let ssts = select.ssts();
+        let target = levels[select_level + 1].ssts();
+
+        let mut candidates = ssts
+            .iter()
+            .enumerate()
+            .filter(|(_, sst)| sst.compact_task.is_none() && !is_compacting(select, &sst.key_range))
+            .filter_map(|(idx, sst)| {
+                let target_range = overlapping_ssts(target, &sst.key_range);
+                if target[target_range.clone()]
+                    .iter()
+                    .any(|sst| sst.compact_task.is_some())
+                {
+                    return None;
+                }
+                let overlap_size = total_file_size(&target[target_range]);
+                Some((idx, overlap_size))
+            })
+            .collect::<Vec<_>>();
+        // Compare `overlap_size / file_size` without losing precision.
+        candidates.sort_by(|(idx_a, overlap_a), (idx_b, overlap_b)| {
+            let size_a = ssts[*idx_a].file_size.max(1) as u128;
+            let size_b = ssts[*idx_b].file_size.max(1) as u128;
+            (*overlap_a as u128 * size_b).cmp(&(*overlap_b as u128 * size_a))
+        });
+
+        let (seed, _) = candidates.into_iter().next()?;
+        let mut select_end = seed + 1;
+        let mut key_range = ssts[seed].key_range.clone();
+        let mut target_range = overlapping_ssts(target, &key_range);
+        let is_trivial_move = target_range.is_empty();
+        while select_end < ssts.len() {
+            let next = &ssts[select_end];
+            if next.compact_task.is_some() || is_compacting(select, &next.key_range) {
+                break;
+            }
+            let mut expanded_key_range = key_range.clone();
+            expanded_key_range.full_key_extend(&next.key_range);
+            let expanded_target_range = overlapping_ssts(target, &expanded_key_range);
+            // Keep a trivial move trivial.
+            if is_trivial_move && !expanded_target_range.is_empty() {
+                break;
+            }
+            if target[expanded_target_range.clone()]
+                .iter()
+                .any(|sst| sst.compact_task.is_some())
+            {
+                break;
+            }
+            let input_size = total_file_size(&ssts[seed..select_end + 1])
+                + total_file_size(&target[expanded_target_range.clone()]);
+            if input_size > self.config.max_compaction_bytes {
+                break;
+            }
+            key_range = expanded_key_range;
+            target_range = expanded_target_range;
+            select_end += 1;
+        }
+
+        let target_level_inputs = target[target_range].to_vec();
+        let splits = if target_level_inputs.is_empty() {
+            vec![]
+        } else {
+            gen_splits(&target_level_inputs)
+        };
+        Some(PickResult {
+            select_level,
+            target_level: select_level + 1,
+            select_level_inputs: ssts[seed..select_end].to_vec(),
+            target_level_inputs,
+            key_range,
+            splits,
+        })
+    }
+}
+
+/// Returns the index range of SSTs in a non-overlapping level which overlap with `key_range` in
+/// user key.
+fn overlapping_ssts(ssts: &[SSTableStat], key_range: &KeyRange) -> Range<usize> {
+    let begin =
+        ssts.partition_point(|sst| user_key(&sst.key_range.right) < user_key(&key_range.left));
+    let mut end = begin;
+    while end < ssts.len() && user_key(&ssts[end].key_range.left) <= user_key(&key_range.right) {
+        end += 1;
+    }
+    begin..end
+}
+
+fn is_compacting(level: &LevelHandler, key_range: &KeyRange) -> bool {
+    level
+        .compacting_key_ranges()
+        .iter()
+        .any(|(compacting_key_range, _, _)| {
+            user_key(&compacting_key_range.left) <= user_key(&key_range.right)
+                && user_key(&key_range.left) <= user_key(&compacting_key_range.right)
+        })
+}
+
+fn total_file_size(ssts: &[SSTableStat]) -> u64 {
+    ssts.iter().map(|sst| sst.file_size).sum()
+}
+
+/// Splits the task at the boundaries of SSTs in the target level, so that each split can be
+/// compacted in parallel by the compactor.
+fn gen_splits(target_level_inputs: &[SSTableStat]) -> Vec<KeyRange> {
+    let mut splits = Vec::with_capacity(target_level_inputs.len().max(1));
+    splits.push(KeyRange::new(Bytes::new(), Bytes::new()));
+    // TODO: We do not need to add splits every time. We can add every K SSTs.
+    for sst in target_level_inputs.iter().skip(1) {
+        let key_before_last: Bytes =
+            FullKey::from_user_key_slice(user_key(&sst.key_range.left), HummockEpoch::MAX)
+                .into_inner()
+                .into();
+        splits.last_mut().unwrap().right = key_before_last.clone();
+        splits.push(KeyRange::new(key_before_last, Bytes::new()));
+    }
+    splits
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_hummock_sdk::key::key_with_epoch;
+
+    use super::*;
+
+    fn sst(table_id: u64, left: usize, right: usize, file_size: u64) -> SSTableStat {
+        SSTableStat {
+            key_range: KeyRange::new(
+                key_with_epoch(format!("key_{:05}", left).into_bytes(), 1).into(),
+                key_with_epoch(format!("key_{:05}", right).into_bytes(), 1).into(),
+            ),
+            table_id,
+            file_size,
+            compact_task: None,
+        }
+    }
+
+    fn config() -> CompactionConfig {
+        CompactionConfig {
+            max_level: 3,
+            max_bytes_for_level_base: 100,
+            max_bytes_for_level_multiplier: 10,
+            level0_trigger_file_num: 2,
+            max_compaction_bytes: 1000,
+        }
+    }
+
+    #[test]
+    fn test_level_scores() {
+        let config = config();
+        let picker = LevelCompactionPicker::new(&config);
+        let levels = vec![
+            LevelHandler::Overlapping(vec![sst(1, 0, 10, 10)], vec![]),
+            LevelHandler::Nonoverlapping(vec![sst(2, 0, 10, 150), sst(3, 20, 30, 150)], vec![]),
+            LevelHandler::Nonoverlapping(vec![sst(4, 0, 10, 500)], vec![]),
+            LevelHandler::Nonoverlapping(vec![sst(5, 0, 10, 100_000)], vec![]),
+        ];
+        // L0: 1 / 2, L1: 300 / 100, L2: 500 / 1000. The bottommost level is never scored.
+        assert_eq!(picker.level_scores(&levels), vec![(3.0, 1)]);
+        assert_eq!(config.max_bytes_for_level(3), 10_000);
+    }
+
+    #[test]
+    fn test_pick_l0_overlapping_group() {
+        let config = config();
+        let picker = LevelCompactionPicker::new(&config);
+        let mut levels = vec![
+            LevelHandler::Overlapping(
+                vec![sst(1, 0, 10, 1), sst(2, 5, 20, 1), sst(3, 30, 40, 1)],
+                vec![],
+            ),
+            LevelHandler::Nonoverlapping(vec![sst(4, 0, 5, 1), sst(5, 15, 25, 1)], vec![]),
+        ];
+        let result = picker.pick_compaction(&mut levels, 1).unwrap();
+        assert_eq!(result.select_level, 0);
+        assert_eq!(result.target_level, 1);
+        assert_eq!(
+            result
+                .select_level_inputs
+                .iter()
+                .map(|sst| sst.table_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            result
+                .target_level_inputs
+                .iter()
+                .map(|sst| sst.table_id)
+                .collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert_eq!(result.splits.len(), 2);
+        assert!(!result.is_trivial_move());
+
+        // SST 3 forms a disjoint group and can be compacted in parallel. L0 now only has one idle
+        // SST, which is below the trigger.
+        assert_eq!(picker.pick_compaction(&mut levels, 2), None);
+        if let LevelHandler::Overlapping(ssts, _) = &mut levels[0] {
+            ssts.push(sst(6, 45, 50, 1));
+        }
+        let result = picker.pick_compaction(&mut levels, 2).unwrap();
+        assert_eq!(result.select_level_inputs[0].table_id, 3);
+        assert!(result.target_level_inputs.is_empty());
+    }
+
+    #[test]
+    fn test_pick_l0_transitive_overlap() {
+        let config = config();
+        let picker = LevelCompactionPicker::new(&config);
+        // C overlaps both A and B, so all three must be compacted together even though B doesn't
+        // overlap A.
+        let mut levels = vec![
+            LevelHandler::Overlapping(
+                vec![sst(1, 0, 10, 1), sst(2, 20, 30, 1), sst(3, 5, 25, 1)],
+                vec![],
+            ),
+            LevelHandler::Nonoverlapping(vec![], vec![]),
+        ];
+        let result = picker.pick_compaction(&mut levels, 1).unwrap();
+        assert_eq!(
+            result
+                .select_level_inputs
+                .iter()
+                .map(|sst| sst.table_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(levels[0].idle_file_count(), 0);
+    }
+
+    #[test]
+    fn test_pick_ln_trivial_move() {
+        let config = config();
+        let picker = LevelCompactionPicker::new(&config);
+        let mut levels = vec![
+            LevelHandler::Overlapping(vec![], vec![]),
+            LevelHandler::Nonoverlapping(
+                vec![sst(1, 0, 10, 60), sst(2, 20, 30, 60), sst(3, 40, 50, 60)],
+                vec![],
+            ),
+            LevelHandler::Nonoverlapping(vec![sst(4, 0, 10, 100)], vec![]),
+        ];
+        let result = picker.pick_compaction(&mut levels, 1).unwrap();
+        assert!(result.is_trivial_move());
+        assert_eq!(
+            result
+                .select_level_inputs
+                .iter()
+                .map(|sst| sst.table_id)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(result.splits.is_empty());
+        assert_eq!(levels[1].idle_file_count(), 1);
+    }
+}
@@ -40,10 +40,10 @@ use crate::hummock::model::{
     sstable_id_info, CurrentHummockVersionId, HummockPinnedSnapshotExt, HummockPinnedVersionExt,
     INVALID_TIMESTAMP,
 };
-use crate::manager::{IdCategory, MetaSrvEnv};
+use crate::manager::{Epoch, IdCategory, MetaSrvEnv};
 use crate::model::{MetadataModel, ValTransaction, VarTransaction, Worker};
 use crate::rpc::metrics::MetaMetrics;
-use crate::storage::{Error, MetaStore, Transaction};
+use crate::storage::{Error, MetaStore, Snapshot, Transaction};
 
 // Update to states are performed as follow:
 // - Initialize ValTransaction for the meta state to update
@@ -121,6 +121,7 @@ where
         cluster_manager: ClusterManagerRef<S>,
         metrics: Arc<MetaMetrics>,
     ) -> Result<HummockManager<S>> {
+        let max_level = env.opts.compaction_config.max_level;
         let instance = HummockManager {
             env,
             versioning: RwLock::new(Versioning {
@@ -132,7 +133,7 @@ where
                 sstable_id_infos: Default::default(),
             }),
             compaction: Mutex::new(Compaction {
-                compact_status: CompactStatus::new(),
+                compact_status: CompactStatus::new(max_level),
                 compact_task_assignment: Default::default(),
             }),
             metrics,
@@ -150,9 +151,13 @@ where
     /// Load state from meta store.
     async fn load_meta_store_state(&self) -> Result<()> {
         let mut compaction_guard = self.compaction.lock().await;
+        let max_level = self.env.opts.compaction_config.max_level;
         compaction_guard.compact_status = CompactStatus::get(self.env.meta_store())
             .await?
-            .unwrap_or_else(CompactStatus::new);
+            .unwrap_or_else(|| CompactStatus::new(max_level));
+        // Levels may have been added to the config since last start. New levels are empty, so they
+        // are not persisted until the compact status changes.
+        compaction_guard.compact_status.ensure_levels(max_level);
 
         compaction_guard.compact_task_assignment =
             CompactTaskAssignment::list(self.env.meta_store())
@@ -176,16 +181,18 @@ where
         if versioning_guard.hummock_versions.is_empty() {
             let init_version = HummockVersion {
                 id: versioning_guard.current_version_id.id(),
-                levels: vec![
-                    Level {
-                        level_type: LevelType::Overlapping as i32,
-                        table_infos: vec![],
-                    },
-                    Level {
-                        level_type: LevelType::Nonoverlapping as i32,
+                levels: compaction_guard
+                    .compact_status
+                    .level_handlers
+                    .iter()
+                    .map(|level_handler| Level {
+                        level_type: match level_handler {
+                            LevelHandler::Overlapping(_, _) => LevelType::Overlapping,
+                            LevelHandler::Nonoverlapping(_, _) => LevelType::Nonoverlapping,
+                        } as i32,
                         table_infos: vec![],
-                    },
-                ],
+                    })
+                    .collect(),
                 uncommitted_epochs: vec![],
                 max_committed_epoch: INVALID_EPOCH,
                 safe_epoch: INVALID_EPOCH,
@@ -256,7 +263,48 @@ where
         last_pinned: HummockVersionId,
     ) -> Result<HummockVersion> {
         let mut versioning_guard = self.versioning.write().await;
-        let versioning = versioning_guard.deref_mut();
+        let ret = self
+            .pin_version_impl(versioning_guard.deref_mut(), context_id, last_pinned)
+            .await;
+
+        #[cfg(test)]
+        {
+            drop(versioning_guard);
+            self.check_state_consistency().await;
+        }
+
+        ret
+    }
+
+    /// Pins the current version for `context_id`, and takes a snapshot of meta store in which the
+    /// pinned version is still the current one. Used by backup.
+    pub async fn pin_version_with_snapshot(
+        &self,
+        context_id: HummockContextId,
+    ) -> Result<(HummockVersion, S::Snapshot)> {
+        // Holding the lock prevents new versions from being committed before the snapshot is
+        // taken.
+        let mut versioning_guard = self.versioning.write().await;
+        let version = self
+            .pin_version_impl(
+                versioning_guard.deref_mut(),
+                context_id,
+                HummockVersionId::MAX,
+            )
+            .await?;
+        let snapshot = self.env.meta_store().snapshot().await;
+        // A snapshot may be lazily bound to a revision on the first read, so make a read before
+        // releasing the lock.
+        snapshot.list_cf(&HummockVersion::cf_name()).await?;
+        Ok((version, snapshot))
+    }
+
+    async fn pin_version_impl(
+        &self,
+        versioning: &mut Versioning,
+        context_id: HummockContextId,
+        last_pinned: HummockVersionId,
+    ) -> Result<HummockVersion> {
         let mut pinned_versions = VarTransaction::new(&mut versioning.pinned_versions);
         let hummock_versions = &versioning.hummock_versions;
         let current_version_id = versioning.current_version_id.clone();
@@ -292,15 +340,7 @@ where
             abort_multi_var!(context_pinned_version);
         }
 
-        let ret = Ok(hummock_versions.get(&version_id).unwrap().clone());
-
-        #[cfg(test)]
-        {
-            drop(versioning_guard);
-            self.check_state_consistency().await;
-        }
-
-        ret
+        Ok(hummock_versions.get(&version_id).unwrap().clone())
     }
 
     pub async fn unpin_version(
@@ -492,6 +532,65 @@ where
         Ok(HummockSnapshot { epoch })
     }
 
+    /// Pin the hummock snapshot of an earlier `epoch`, which is read by time travel queries. The
+    /// pin belongs to `context_id` and is unpinned by `unpin_snapshot`.
+    /// `epoch` must be committed, and no smaller than the safe epoch of the current version or
+    /// the start of the time travel retention window.
+    pub async fn pin_snapshot_as_of(
+        &self,
+        context_id: HummockContextId,
+        epoch: HummockEpoch,
+    ) -> Result<HummockSnapshot> {
+        let mut versioning_guard = self.versioning.write().await;
+
+        let version_id = versioning_guard.current_version_id.id();
+        let current_version = versioning_guard.hummock_versions.get(&version_id).unwrap();
+        if epoch > current_version.max_committed_epoch {
+            return Err(ErrorCode::MetaError(format!(
+                "epoch {} is not committed, max committed epoch {}",
+                epoch, current_version.max_committed_epoch
+            ))
+            .into());
+        }
+        let min_epoch = max(
+            current_version.safe_epoch,
+            self.time_travel_retention_epoch(),
+        );
+        if epoch < min_epoch {
+            return Err(ErrorCode::MetaError(format!(
+                "epoch {} is out of the time travel retention window, min readable epoch {}",
+                epoch, min_epoch
+            ))
+            .into());
+        }
+
+        let mut pinned_snapshots = VarTransaction::new(&mut versioning_guard.pinned_snapshots);
+        let mut context_pinned_snapshot = pinned_snapshots.new_entry_txn_or_default(
+            context_id,
+            HummockPinnedSnapshot {
+                context_id,
+                snapshot_id: vec![],
+            },
+        );
+        context_pinned_snapshot.pin_snapshot(epoch);
+        commit_multi_var!(self, Some(context_id), context_pinned_snapshot)?;
+
+        #[cfg(test)]
+        {
+            drop(versioning_guard);
+            self.check_state_consistency().await;
+        }
+
+        Ok(HummockSnapshot { epoch })
+    }
+
+    /// Epochs smaller than the returned one are out of the time travel retention window, so their
+    /// data may be dropped by compaction.
+    fn time_travel_retention_epoch(&self) -> HummockEpoch {
+        let retention_ms = self.env.opts.time_travel_retention.as_millis() as u64;
+        Epoch::from_physical_time(Epoch::physical_now().saturating_sub(retention_ms)).into_inner()
+    }
+
     pub async fn unpin_snapshot(
         &self,
         context_id: HummockContextId,
@@ -523,6 +622,45 @@ where
     pub async fn get_compact_task(
         &self,
         assignee_context_id: HummockContextId,
+    ) -> Result<Option<CompactTask>> {
+        loop {
+            match self.get_compact_task_impl(assignee_context_id).await? {
+                Some(compact_task) if CompactStatus::is_trivial_move_task(&compact_task) => {
+                    self.apply_trivial_move_task(compact_task).await?;
+                }
+                compact_task => return Ok(compact_task),
+            }
+        }
+    }
+
+    /// Applies a trivial move by reporting its input SSTs as output, so that they are relinked
+    /// to the target level without being sent to any compactor.
+    async fn apply_trivial_move_task(&self, mut compact_task: CompactTask) -> Result<()> {
+        compact_task.sorted_output_ssts = compact_task.input_ssts[0]
+            .level
+            .as_ref()
+            .unwrap()
+            .table_infos
+            .clone();
+        // No version is dropped by a trivial move, so the safe epoch must not advance.
+        compact_task.watermark = INVALID_EPOCH;
+        compact_task.task_status = true;
+        tracing::debug!(
+            "Trivially move SSTs {:?} to level {}",
+            compact_task
+                .sorted_output_ssts
+                .iter()
+                .map(|sst| sst.id)
+                .collect_vec(),
+            compact_task.target_level
+        );
+        self.report_compact_task(compact_task).await?;
+        Ok(())
+    }
+
+    async fn get_compact_task_impl(
+        &self,
+        assignee_context_id: HummockContextId,
     ) -> Result<Option<CompactTask>> {
         let mut compaction_guard = self.compaction.lock().await;
 
@@ -537,7 +675,7 @@ where
             }
         }
 
-        let compact_task = compact_status.get_compact_task();
+        let compact_task = compact_status.get_compact_task(&self.env.opts.compaction_config);
         let mut should_commit = false;
         let ret = match compact_task {
             None => Ok(None),
@@ -558,11 +696,14 @@ where
                         .get(&current_version_id)
                         .unwrap()
                         .max_committed_epoch;
+                    // Keep all versions of keys within the time travel retention window.
+                    let max_watermark =
+                        std::cmp::min(max_committed_epoch, self.time_travel_retention_epoch());
                     versioning_guard
                         .pinned_snapshots
                         .values()
                         .flat_map(|v| v.snapshot_id.clone())
-                        .fold(max_committed_epoch, std::cmp::min)
+                        .fold(max_watermark, std::cmp::min)
                 };
                 Ok(Some(compact_task))
             }
@@ -655,39 +796,11 @@ where
                     .map(|level_handler| match level_handler {
                         LevelHandler::Overlapping(l_n, _) => Level {
                             level_type: LevelType::Overlapping as i32,
-                            table_infos: l_n
-                                .iter()
-                                .map(
-                                    |SSTableStat {
-                                         table_id,
-                                         key_range,
-                                         ..
-                                     }| {
-                                        SstableInfo {
-                                            id: *table_id,
-                                            key_range: Some(key_range.clone().into()),
-                                        }
-                                    },
-                                )
-                                .collect(),
+                            table_infos: l_n.iter().map_into().collect(),
                         },
                         LevelHandler::Nonoverlapping(l_n, _) => Level {
                             level_type: LevelType::Nonoverlapping as i32,
-                            table_infos: l_n
-                                .iter()
-                                .map(
-                                    |SSTableStat {
-                                         table_id,
-                                         key_range,
-                                         ..
-                                     }| {
-                                        SstableInfo {
-                                            id: *table_id,
-                                            key_range: Some(key_range.clone().into()),
-                                        }
-                                    },
-                                )
-                                .collect(),
+                            table_infos: l_n.iter().map_into().collect(),
                         },
                     })
                     .collect(),
@@ -1046,6 +1159,19 @@ where
         Ok(count as HummockRefCount)
     }
 
+    /// Whether the max committed epoch of `version_id` is within the time travel retention window.
+    pub async fn is_version_in_time_travel_retention(
+        &self,
+        version_id: HummockVersionId,
+    ) -> Result<bool> {
+        let versioning_guard = self.versioning.read().await;
+        Ok(versioning_guard
+            .hummock_versions
+            .get(&version_id)
+            .map(|version| version.max_committed_epoch >= self.time_travel_retention_epoch())
+            .unwrap_or(false))
+    }
+
     /// Get the `SSTable` ids which are guaranteed not to be included after `version_id`, thus they
     /// can be deleted if all versions LE than `version_id` are not referenced.
     #[cfg(test)]
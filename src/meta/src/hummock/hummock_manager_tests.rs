@@ -18,18 +18,22 @@ use std::time::Duration;
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_hummock_sdk::compact::compact_task_to_string;
+use risingwave_hummock_sdk::key::{key_with_epoch, user_key};
 use risingwave_hummock_sdk::{
     HummockContextId, HummockSSTableId, FIRST_VERSION_ID, INVALID_EPOCH, INVALID_VERSION_ID,
 };
 use risingwave_pb::common::{HostAddress, WorkerType};
 use risingwave_pb::hummock::{
-    HummockPinnedSnapshot, HummockPinnedVersion, HummockSnapshot, HummockVersion,
-    HummockVersionRefId,
+    CompactTask, HummockPinnedSnapshot, HummockPinnedVersion, HummockSnapshot, HummockVersion,
+    HummockVersionRefId, KeyRange, SstableInfo,
 };
 
 use crate::hummock::model::CurrentHummockVersionId;
 use crate::hummock::test_utils::*;
+use crate::hummock::{CompactionConfig, HummockManager};
+use crate::manager::{Epoch, MetaOpts};
 use crate::model::MetadataModel;
+use crate::storage::MetaStore;
 
 fn pin_versions_sum(pin_versions: &[HummockPinnedVersion]) -> usize {
     pin_versions.iter().map(|p| p.version_id.len()).sum()
@@ -866,6 +870,72 @@ async fn test_retryable_pin_snapshot() {
     assert_eq!(snapshot_3.epoch, snapshot_2.epoch + 2);
 }
 
+#[tokio::test]
+async fn test_pin_snapshot_as_of() {
+    let retention = Duration::from_secs(60 * 60);
+    let (env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env_with_opts(
+        80,
+        MetaOpts {
+            compaction_config: CompactionConfig {
+                max_level: 1,
+                level0_trigger_file_num: 1,
+                ..Default::default()
+            },
+            time_travel_retention: retention,
+            ..Default::default()
+        },
+    )
+    .await;
+    let context_id = worker_node.id;
+
+    let physical_now = Epoch::physical_now();
+    let expired_epoch =
+        Epoch::from_physical_time(physical_now - 2 * retention.as_millis() as u64).into_inner();
+    let epoch = Epoch::from_physical_time(physical_now - 1000).into_inner();
+    for epoch in [expired_epoch, epoch] {
+        let test_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+        hummock_manager
+            .add_tables(context_id, test_tables, epoch)
+            .await
+            .unwrap();
+        hummock_manager.commit_epoch(epoch).await.unwrap();
+    }
+
+    // Uncommitted epoch.
+    hummock_manager
+        .pin_snapshot_as_of(context_id, epoch + 1)
+        .await
+        .unwrap_err();
+    // Epoch out of the retention window.
+    hummock_manager
+        .pin_snapshot_as_of(context_id, expired_epoch)
+        .await
+        .unwrap_err();
+    let snapshot = hummock_manager
+        .pin_snapshot_as_of(context_id, epoch)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, epoch);
+    assert_eq!(
+        pin_snapshots_sum(&HummockPinnedSnapshot::list(env.meta_store()).await.unwrap()),
+        1
+    );
+
+    // Compaction keeps all versions of keys within the retention window.
+    let compact_task = hummock_manager
+        .get_compact_task(context_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(compact_task.watermark > expired_epoch);
+    assert!(compact_task.watermark < epoch);
+
+    hummock_manager
+        .unpin_snapshot(context_id, [snapshot])
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_print_compact_task() -> Result<()> {
     let (_, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
@@ -970,3 +1040,369 @@ async fn test_mark_orphan_ssts() {
         )
     );
 }
+
+/// Generates an SST containing user keys `key_{left}..=key_{right}` written in `epoch`.
+fn generate_sst(
+    table_id: HummockSSTableId,
+    left: usize,
+    right: usize,
+    epoch: u64,
+    file_size: u64,
+) -> SstableInfo {
+    SstableInfo {
+        id: table_id,
+        key_range: Some(KeyRange {
+            left: key_with_epoch(format!("key_{:05}", left).into_bytes(), epoch),
+            right: key_with_epoch(format!("key_{:05}", right).into_bytes(), epoch),
+            inf: false,
+        }),
+        file_size,
+    }
+}
+
+/// Simulates a compactor, which rewrites each group of input SSTs overlapping in user key into
+/// one output SST, and reports the task.
+async fn simulate_compaction<S: MetaStore>(
+    hummock_manager: &HummockManager<S>,
+    mut compact_task: CompactTask,
+) {
+    let mut inputs = compact_task
+        .input_ssts
+        .iter()
+        .flat_map(|level_entry| level_entry.level.as_ref().unwrap().table_infos.clone())
+        .collect_vec();
+    inputs.sort_by(|a, b| {
+        user_key(&a.key_range.as_ref().unwrap().left)
+            .cmp(user_key(&b.key_range.as_ref().unwrap().left))
+    });
+    let mut outputs: Vec<SstableInfo> = vec![];
+    for input in inputs {
+        let input_key_range = input.key_range.as_ref().unwrap();
+        match outputs.last_mut() {
+            Some(output)
+                if user_key(&input_key_range.left)
+                    <= user_key(&output.key_range.as_ref().unwrap().right) =>
+            {
+                let output_key_range = output.key_range.as_mut().unwrap();
+                if user_key(&input_key_range.right) > user_key(&output_key_range.right) {
+                    output_key_range.right = input_key_range.right.clone();
+                }
+                output.file_size += input.file_size;
+            }
+            _ => outputs.push(SstableInfo {
+                id: 0,
+                ..input.clone()
+            }),
+        }
+    }
+    for output in &mut outputs {
+        output.id = hummock_manager.get_new_table_id().await.unwrap();
+    }
+    compact_task.sorted_output_ssts = outputs;
+    compact_task.task_status = true;
+    assert!(hummock_manager
+        .report_compact_task(compact_task)
+        .await
+        .unwrap());
+}
+
+/// Asserts that SSTs in each non-overlapping level are sorted and don't overlap in user key.
+fn assert_levels_nonoverlapping(version: &HummockVersion) {
+    for level in version.levels.iter().skip(1) {
+        for (prev, next) in level.table_infos.iter().tuple_windows() {
+            assert!(
+                user_key(&prev.key_range.as_ref().unwrap().right)
+                    < user_key(&next.key_range.as_ref().unwrap().left)
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_compaction_picker_simulation() {
+    let config = CompactionConfig {
+        max_level: 3,
+        max_bytes_for_level_base: 8 * 1024,
+        max_bytes_for_level_multiplier: 4,
+        level0_trigger_file_num: 2,
+        max_compaction_bytes: 64 * 1024,
+    };
+    let (_env, hummock_manager, _cluster_manager, worker_node) =
+        setup_compute_env_with_config(80, config.clone()).await;
+    let context_id = worker_node.id;
+    assert_eq!(
+        hummock_manager.get_current_version().await.levels.len(),
+        config.max_level + 1
+    );
+
+    for epoch in 1..=40u64 {
+        let mut ssts = vec![];
+        for i in 0..2 {
+            let left = ((epoch as usize) * 37 + i * 401) % 1000;
+            ssts.push(generate_sst(
+                hummock_manager.get_new_table_id().await.unwrap(),
+                left,
+                left + 20,
+                epoch,
+                1024,
+            ));
+        }
+        hummock_manager
+            .add_tables(context_id, ssts, epoch)
+            .await
+            .unwrap();
+        hummock_manager.commit_epoch(epoch).await.unwrap();
+
+        while let Some(compact_task) = hummock_manager.get_compact_task(context_id).await.unwrap() {
+            simulate_compaction(&hummock_manager, compact_task).await;
+            assert_levels_nonoverlapping(&hummock_manager.get_current_version().await);
+        }
+
+        // No level needs compaction after all tasks are finished.
+        let version = hummock_manager.get_current_version().await;
+        assert!(version.levels[0].table_infos.len() < config.level0_trigger_file_num);
+        for (level_idx, level) in version.levels.iter().enumerate().take(config.max_level) {
+            if level_idx == 0 {
+                continue;
+            }
+            let level_size: u64 = level.table_infos.iter().map(|sst| sst.file_size).sum();
+            assert!(level_size < config.max_bytes_for_level(level_idx));
+        }
+    }
+    // Data has been compacted down to the bottommost level, and no SST is lost.
+    let version = hummock_manager.get_current_version().await;
+    assert!(!version.levels[config.max_level].table_infos.is_empty());
+    let total_size: u64 = version
+        .levels
+        .iter()
+        .flat_map(|level| level.table_infos.iter())
+        .map(|sst| sst.file_size)
+        .sum();
+    assert_eq!(total_size, 40 * 2 * 1024);
+}
+
+#[tokio::test]
+async fn test_compaction_picker_trivial_move() {
+    let config = CompactionConfig {
+        max_level: 2,
+        max_bytes_for_level_base: 1,
+        level0_trigger_file_num: 1,
+        ..Default::default()
+    };
+    let (_env, hummock_manager, _cluster_manager, worker_node) =
+        setup_compute_env_with_config(80, config).await;
+    let context_id = worker_node.id;
+
+    let epoch = 1;
+    let sst = generate_sst(
+        hummock_manager.get_new_table_id().await.unwrap(),
+        0,
+        10,
+        epoch,
+        1024,
+    );
+    hummock_manager
+        .add_tables(context_id, vec![sst], epoch)
+        .await
+        .unwrap();
+    hummock_manager.commit_epoch(epoch).await.unwrap();
+
+    // L0 is always rewritten.
+    let compact_task = hummock_manager
+        .get_compact_task(context_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(compact_task.input_ssts[0].level_idx, 0);
+    assert_eq!(compact_task.target_level, 1);
+    simulate_compaction(&hummock_manager, compact_task).await;
+    let version = hummock_manager.get_current_version().await;
+    let l1_sst_ids = get_sorted_sstable_ids(&version.levels[1].table_infos);
+    assert_eq!(l1_sst_ids.len(), 1);
+
+    // L1 exceeds its target size and nothing in L2 overlaps with it, so the SST is moved to L2 by
+    // meta directly and no task is sent to compactor.
+    assert_eq!(
+        hummock_manager.get_compact_task(context_id).await.unwrap(),
+        None
+    );
+    let moved_version = hummock_manager.get_current_version().await;
+    assert_eq!(moved_version.id, version.id + 1);
+    assert!(moved_version.levels[1].table_infos.is_empty());
+    assert_eq!(
+        get_sorted_sstable_ids(&moved_version.levels[2].table_infos),
+        l1_sst_ids
+    );
+    assert!(hummock_manager
+        .get_ssts_to_delete(version.id)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(moved_version.safe_epoch, version.safe_epoch);
+}
+
+#[tokio::test]
+async fn test_compaction_picker_transitive_overlap() {
+    let config = CompactionConfig {
+        level0_trigger_file_num: 3,
+        ..Default::default()
+    };
+    let (_env, hummock_manager, _cluster_manager, worker_node) =
+        setup_compute_env_with_config(80, config).await;
+    let context_id = worker_node.id;
+
+    // A [0, 10] and B [20, 30] don't overlap, but C [5, 25] written later overlaps both of them.
+    let mut ssts = vec![];
+    for (epoch, key_ranges) in [(1, vec![(0, 10), (20, 30)]), (2, vec![(5, 25)])] {
+        let mut epoch_ssts = vec![];
+        for (left, right) in key_ranges {
+            epoch_ssts.push(generate_sst(
+                hummock_manager.get_new_table_id().await.unwrap(),
+                left,
+                right,
+                epoch,
+                1024,
+            ));
+        }
+        ssts.extend(epoch_ssts.clone());
+        hummock_manager
+            .add_tables(context_id, epoch_ssts, epoch)
+            .await
+            .unwrap();
+        hummock_manager.commit_epoch(epoch).await.unwrap();
+    }
+
+    // All of them are compacted in one task, otherwise a task of {B, C} would put the newer
+    // versions in C below the older versions in A.
+    let compact_task = hummock_manager
+        .get_compact_task(context_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        get_sorted_sstable_ids(
+            &compact_task.input_ssts[0]
+                .level
+                .as_ref()
+                .unwrap()
+                .table_infos
+        ),
+        get_sorted_sstable_ids(&ssts)
+    );
+    assert_eq!(
+        hummock_manager.get_compact_task(context_id).await.unwrap(),
+        None
+    );
+
+    simulate_compaction(&hummock_manager, compact_task).await;
+    let version = hummock_manager.get_current_version().await;
+    assert!(version.levels[0].table_infos.is_empty());
+    assert_eq!(version.levels[1].table_infos.len(), 1);
+    assert_levels_nonoverlapping(&version);
+}
+
+#[tokio::test]
+async fn test_compaction_picker_parallel_tasks() {
+    let (_env, hummock_manager, cluster_manager, worker_node) = setup_compute_env(80).await;
+    let mut context_ids = vec![worker_node.id];
+    for port in 81..83 {
+        let (worker_node, _) = cluster_manager
+            .add_worker_node(
+                HostAddress {
+                    host: "127.0.0.1".to_string(),
+                    port,
+                },
+                WorkerType::ComputeNode,
+            )
+            .await
+            .unwrap();
+        context_ids.push(worker_node.id);
+    }
+
+    let epoch = 1;
+    let ssts = vec![
+        generate_sst(
+            hummock_manager.get_new_table_id().await.unwrap(),
+            0,
+            10,
+            epoch,
+            1024,
+        ),
+        generate_sst(
+            hummock_manager.get_new_table_id().await.unwrap(),
+            100,
+            110,
+            epoch,
+            1024,
+        ),
+    ];
+    hummock_manager
+        .add_tables(context_ids[0], ssts.clone(), epoch)
+        .await
+        .unwrap();
+    hummock_manager.commit_epoch(epoch).await.unwrap();
+
+    // SSTs with disjoint key ranges are compacted by different compactors in parallel.
+    let task_1 = hummock_manager
+        .get_compact_task(context_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    let task_2 = hummock_manager
+        .get_compact_task(context_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    let input_ids = |compact_task: &CompactTask| {
+        get_sorted_sstable_ids(
+            &compact_task.input_ssts[0]
+                .level
+                .as_ref()
+                .unwrap()
+                .table_infos,
+        )
+    };
+    assert_eq!(
+        input_ids(&task_1)
+            .into_iter()
+            .chain(input_ids(&task_2))
+            .sorted()
+            .collect_vec(),
+        get_sorted_sstable_ids(&ssts)
+    );
+
+    // An SST overlapping with a key range being compacted must wait.
+    let epoch = 2;
+    hummock_manager
+        .add_tables(
+            context_ids[0],
+            vec![generate_sst(
+                hummock_manager.get_new_table_id().await.unwrap(),
+                5,
+                15,
+                epoch,
+                1024,
+            )],
+            epoch,
+        )
+        .await
+        .unwrap();
+    hummock_manager.commit_epoch(epoch).await.unwrap();
+    assert_eq!(
+        hummock_manager
+            .get_compact_task(context_ids[2])
+            .await
+            .unwrap(),
+        None
+    );
+
+    simulate_compaction(&hummock_manager, task_1).await;
+    simulate_compaction(&hummock_manager, task_2).await;
+    let task_3 = hummock_manager
+        .get_compact_task(context_ids[2])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(input_ids(&task_3).len(), 1);
+    assert_levels_nonoverlapping(&hummock_manager.get_current_version().await);
+}
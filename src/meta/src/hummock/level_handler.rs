@@ -21,6 +21,7 @@ use risingwave_pb::hummock::SstableInfo;
 pub struct SSTableStat {
     pub key_range: KeyRange,
     pub table_id: u64,
+    pub file_size: u64,
     pub compact_task: Option<u64>,
 }
 
@@ -29,6 +30,7 @@ impl From<&SstableInfo> for SSTableStat {
         SSTableStat {
             key_range: info.key_range.as_ref().unwrap().into(),
             table_id: info.id,
+            file_size: info.file_size,
             compact_task: None,
         }
     }
@@ -42,6 +44,7 @@ impl From<&SSTableStat> for risingwave_pb::hummock::SstableStat {
             compact_task: stat
                 .compact_task
                 .map(|it| risingwave_pb::hummock::sstable_stat::CompactTaskId { id: it }),
+            file_size: stat.file_size,
         }
     }
 }
@@ -51,6 +54,7 @@ impl From<&risingwave_pb::hummock::SstableStat> for SSTableStat {
         SSTableStat {
             key_range: stat.key_range.as_ref().unwrap().into(),
             table_id: stat.table_id,
+            file_size: stat.file_size,
             compact_task: stat.compact_task.as_ref().map(|it| it.id),
         }
     }
@@ -66,7 +70,47 @@ pub enum LevelHandler {
     Overlapping(Vec<SSTableStat>, Vec<(KeyRange, u64, u64)>),
 }
 
+impl From<&SSTableStat> for SstableInfo {
+    fn from(stat: &SSTableStat) -> Self {
+        SstableInfo {
+            id: stat.table_id,
+            key_range: Some(stat.key_range.clone().into()),
+            file_size: stat.file_size,
+        }
+    }
+}
+
 impl LevelHandler {
+    pub fn ssts(&self) -> &[SSTableStat] {
+        match self {
+            LevelHandler::Overlapping(l_n, _) | LevelHandler::Nonoverlapping(l_n, _) => l_n,
+        }
+    }
+
+    pub fn compacting_key_ranges(&self) -> &[(KeyRange, u64, u64)] {
+        match self {
+            LevelHandler::Overlapping(_, compacting_key_ranges)
+            | LevelHandler::Nonoverlapping(_, compacting_key_ranges) => compacting_key_ranges,
+        }
+    }
+
+    /// Total size of SSTs in this level which are not being compacted.
+    pub fn idle_file_size(&self) -> u64 {
+        self.ssts()
+            .iter()
+            .filter(|sst| sst.compact_task.is_none())
+            .map(|sst| sst.file_size)
+            .sum()
+    }
+
+    /// Number of SSTs in this level which are not being compacted.
+    pub fn idle_file_count(&self) -> usize {
+        self.ssts()
+            .iter()
+            .filter(|sst| sst.compact_task.is_none())
+            .count()
+    }
+
     fn clear_compacting_range(&mut self, clear_task_id: u64) {
         match self {
             LevelHandler::Overlapping(_, compacting_key_ranges)
@@ -92,6 +136,35 @@ impl LevelHandler {
         changed
     }
 
+    /// Marks SSTs in `table_ids` as inputs of compact task `task_id`.
+    pub fn assign_task(&mut self, task_id: u64, table_ids: &[u64]) {
+        match self {
+            LevelHandler::Overlapping(l_n, _) | LevelHandler::Nonoverlapping(l_n, _) => {
+                for SSTableStat {
+                    table_id,
+                    compact_task,
+                    ..
+                } in l_n
+                {
+                    if table_ids.contains(table_id) {
+                        *compact_task = Some(task_id);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn add_compacting_key_range(&mut self, key_range: KeyRange, task_id: u64, ssts: u64) {
+        match self {
+            LevelHandler::Overlapping(_, compacting_key_ranges)
+            | LevelHandler::Nonoverlapping(_, compacting_key_ranges) => {
+                let insert_point = compacting_key_ranges
+                    .partition_point(|(ongoing_key_range, _, _)| ongoing_key_range < &key_range);
+                compacting_key_ranges.insert(insert_point, (key_range, task_id, ssts));
+            }
+        }
+    }
+
     pub fn pop_task_input(&mut self, finished_task_id: u64) -> Vec<u64> {
         self.clear_compacting_range(finished_task_id);
 
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod compaction;
+mod compaction_picker;
 mod compactor_manager;
 mod hummock_manager;
 #[cfg(test)]
@@ -22,12 +23,14 @@ mod metrics_utils;
 #[cfg(any(test, feature = "test"))]
 pub mod mock_hummock_meta_client;
 mod model;
+pub(crate) use model::HUMMOCK_CF_NAMES;
 #[cfg(any(test, feature = "test"))]
 pub mod test_utils;
 mod vacuum;
 use std::sync::Arc;
 use std::time::Duration;
 
+pub use compaction_picker::CompactionConfig;
 pub use compactor_manager::*;
 pub use hummock_manager::*;
 use itertools::Itertools;
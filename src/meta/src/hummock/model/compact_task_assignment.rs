@@ -18,7 +18,7 @@ use risingwave_pb::hummock::{CompactTaskAssignment, CompactTaskRefId};
 use crate::model::MetadataModel;
 
 /// `cf(compact_task_assignment)`: `CompactTaskRefId` -> `CompactTaskAssignment`
-const HUMMOCK_COMPACT_TASK_ASSIGNMENT: &str = "cf/compact_task_assignment";
+pub(crate) const HUMMOCK_COMPACT_TASK_ASSIGNMENT: &str = "cf/compact_task_assignment";
 
 /// `AssignedCompactTasks` tracks compact tasks assigned to context id.
 impl MetadataModel for CompactTaskAssignment {
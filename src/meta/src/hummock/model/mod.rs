@@ -29,3 +29,14 @@ pub use version::*;
 
 /// Column family name for hummock epoch.
 pub(crate) const HUMMOCK_DEFAULT_CF_NAME: &str = "cf/hummock_default";
+
+/// All column families used by hummock.
+pub(crate) const HUMMOCK_CF_NAMES: &[&str] = &[
+    HUMMOCK_DEFAULT_CF_NAME,
+    compact_task_assignment::HUMMOCK_COMPACT_TASK_ASSIGNMENT,
+    pinned_snapshot::HUMMOCK_PINNED_SNAPSHOT_CF_NAME,
+    pinned_version::HUMMOCK_PINNED_VERSION_CF_NAME,
+    sstable_id_info::HUMMOCK_SSTABLE_ID_CF_NAME,
+    stale_sstables::HUMMOCK_STALE_SSTABLES_CF_NAME,
+    version::HUMMOCK_VERSION_CF_NAME,
+];
@@ -20,7 +20,7 @@ use crate::model::MetadataModel;
 
 /// Column family name for hummock pinned version
 /// `cf(hummock_pinned_version)`: `HummockContextRefId` -> `HummockPinnedVersion`
-const HUMMOCK_PINNED_VERSION_CF_NAME: &str = "cf/hummock_pinned_version";
+pub(crate) const HUMMOCK_PINNED_VERSION_CF_NAME: &str = "cf/hummock_pinned_version";
 
 /// `HummockPinnedVersion` tracks pinned versions by given context id.
 impl MetadataModel for HummockPinnedVersion {
@@ -21,7 +21,7 @@ use crate::model::MetadataModel;
 
 /// Column family name for hummock sstable id.
 /// `cf(hummock_sstable_id)`: `SstableRefId` -> `SstableIdInfo`
-const HUMMOCK_SSTABLE_ID_CF_NAME: &str = "cf/hummock_sstable_id";
+pub(crate) const HUMMOCK_SSTABLE_ID_CF_NAME: &str = "cf/hummock_sstable_id";
 
 pub const INVALID_TIMESTAMP: u64 = 0;
 
@@ -19,7 +19,7 @@ use crate::model::MetadataModel;
 
 /// Column family name for stale hummock sstables.
 /// `cf(hummock_stale_sstables)`: `HummockVersionRefId` -> `HummockStaleSstables`
-const HUMMOCK_STALE_SSTABLES_CF_NAME: &str = "cf/hummock_stale_sstables";
+pub(crate) const HUMMOCK_STALE_SSTABLES_CF_NAME: &str = "cf/hummock_stale_sstables";
 
 /// `HummockStaleSstables` tracks `SSTables` no longer needed after the given version.
 impl MetadataModel for HummockStaleSstables {
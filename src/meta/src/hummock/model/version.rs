@@ -19,7 +19,7 @@ use crate::model::MetadataModel;
 
 /// Column family name for hummock version.
 /// `cf(hummock_version)`: `HummockVersionRefId` -> `HummockVersion`
-const HUMMOCK_VERSION_CF_NAME: &str = "cf/hummock_version";
+pub(crate) const HUMMOCK_VERSION_CF_NAME: &str = "cf/hummock_version";
 
 /// `HummockVersion` tracks `SSTables` in given version.
 impl MetadataModel for HummockVersion {
@@ -22,8 +22,8 @@ use risingwave_pb::common::{HostAddress, WorkerNode, WorkerType};
 use risingwave_pb::hummock::{HummockVersion, KeyRange, SstableInfo};
 
 use crate::cluster::{ClusterManager, ClusterManagerRef};
-use crate::hummock::{HummockManager, HummockManagerRef};
-use crate::manager::MetaSrvEnv;
+use crate::hummock::{CompactionConfig, HummockManager, HummockManagerRef};
+use crate::manager::{MetaOpts, MetaSrvEnv};
 use crate::rpc::metrics::MetaMetrics;
 use crate::storage::{MemStore, MetaStore};
 
@@ -93,6 +93,7 @@ pub fn generate_test_tables(epoch: u64, table_ids: Vec<u64>) -> Vec<SstableInfo>
                 right: iterator_test_key_of_epoch(table_id, (i + 1) * 10, epoch),
                 inf: false,
             }),
+            file_size: 1024,
         });
     }
     sst_info
@@ -130,7 +131,44 @@ pub async fn setup_compute_env(
     ClusterManagerRef<MemStore>,
     WorkerNode,
 ) {
-    let env = MetaSrvEnv::for_test().await;
+    // Only L0 and L1, and compact L0 as soon as there is any SST in it.
+    let config = CompactionConfig {
+        max_level: 1,
+        level0_trigger_file_num: 1,
+        ..Default::default()
+    };
+    setup_compute_env_with_config(port, config).await
+}
+
+pub async fn setup_compute_env_with_config(
+    port: i32,
+    config: CompactionConfig,
+) -> (
+    MetaSrvEnv<MemStore>,
+    HummockManagerRef<MemStore>,
+    ClusterManagerRef<MemStore>,
+    WorkerNode,
+) {
+    setup_compute_env_with_opts(
+        port,
+        MetaOpts {
+            compaction_config: config,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+pub async fn setup_compute_env_with_opts(
+    port: i32,
+    opts: MetaOpts,
+) -> (
+    MetaSrvEnv<MemStore>,
+    HummockManagerRef<MemStore>,
+    ClusterManagerRef<MemStore>,
+    WorkerNode,
+) {
+    let env = MetaSrvEnv::for_test_opts(Arc::new(opts)).await;
     let cluster_manager = Arc::new(
         ClusterManager::new(env.clone(), Duration::from_secs(1))
             .await
@@ -105,6 +105,8 @@ where
     ///   version.
     /// - And it's not being referenced, and we know it won't be referenced in the future because
     ///   only greatest version can be newly referenced.
+    /// - And its max committed epoch is out of the time travel retention window, so that SSTs
+    ///   holding data of epochs still readable by `FOR SYSTEM_TIME AS OF` are kept.
     async fn vacuum_version_metadata(
         vacuum: &VacuumTrigger<S>,
     ) -> risingwave_common::error::Result<u64> {
@@ -123,6 +125,14 @@ where
                 // The smallest version is still referenced.
                 return Ok(vacuum_count);
             }
+            if vacuum
+                .hummock_manager
+                .is_version_in_time_travel_retention(*version_id)
+                .await?
+            {
+                // The smallest version and all greater ones are still retained.
+                return Ok(vacuum_count);
+            }
 
             // Delete version metadata and mark SST as orphan (set meta_delete_timestamp).
             // TODO delete in batch
@@ -33,6 +33,7 @@
 #![feature(drain_filter)]
 #![cfg_attr(coverage, feature(no_coverage))]
 
+mod backup;
 mod barrier;
 pub mod cluster;
 mod dashboard;
@@ -48,6 +49,7 @@ use std::time::Duration;
 
 use clap::{ArgEnum, Parser};
 
+use crate::hummock::CompactionConfig;
 use crate::manager::MetaOpts;
 use crate::rpc::server::{rpc_serve, MetaStoreBackend};
 
@@ -86,6 +88,35 @@ pub struct MetaNodeOpts {
     /// e2e tests.
     #[clap(long)]
     disable_recovery: bool,
+
+    /// Path of a meta backup file to restore from on start, written by `risectl backup restore`.
+    /// The meta store must be empty.
+    #[clap(long)]
+    restore_meta_backup: Option<String>,
+
+    /// How long in seconds committed data stay readable by `FOR SYSTEM_TIME AS OF`.
+    #[clap(long, default_value = "0")]
+    time_travel_retention_sec: u64,
+
+    /// Index of the bottommost level of hummock, i.e. the number of levels below L0.
+    #[clap(long, default_value = "6")]
+    compaction_max_level: usize,
+
+    /// Target size of L1 in bytes.
+    #[clap(long, default_value = "536870912")]
+    compaction_max_bytes_for_level_base: u64,
+
+    /// Target size of each level below L1 is this times the target size of the level above.
+    #[clap(long, default_value = "10")]
+    compaction_max_bytes_for_level_multiplier: u64,
+
+    /// L0 is compacted once it holds this many SSTs which are not being compacted.
+    #[clap(long, default_value = "4")]
+    compaction_level0_trigger_file_num: usize,
+
+    /// Upper bound of the input size in bytes of a compaction task from a level below L0.
+    #[clap(long, default_value = "2147483648")]
+    compaction_max_compaction_bytes: u64,
 }
 
 /// Start meta node
@@ -115,6 +146,15 @@ pub async fn start(opts: MetaNodeOpts) {
         opts.dashboard_ui_path,
         MetaOpts {
             enable_recovery: !opts.disable_recovery,
+            compaction_config: CompactionConfig {
+                max_level: opts.compaction_max_level,
+                max_bytes_for_level_base: opts.compaction_max_bytes_for_level_base,
+                max_bytes_for_level_multiplier: opts.compaction_max_bytes_for_level_multiplier,
+                level0_trigger_file_num: opts.compaction_level0_trigger_file_num,
+                max_compaction_bytes: opts.compaction_max_compaction_bytes,
+            },
+            restore_meta_backup: opts.restore_meta_backup.map(Into::into),
+            time_travel_retention: Duration::from_secs(opts.time_travel_retention_sec),
         },
     )
     .await
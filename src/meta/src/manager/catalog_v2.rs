@@ -19,12 +19,13 @@ use std::option::Option::Some;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use itertools::Itertools;
 use risingwave_common::catalog::{CatalogVersion, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
 use risingwave_common::ensure;
 use risingwave_common::error::ErrorCode::{CatalogError, InternalError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
-use risingwave_pb::catalog::{Database, Schema, Source, Table};
+use risingwave_pb::catalog::{Database, Schema, Source, Table, TableStatistics};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use tokio::sync::{Mutex, MutexGuard};
 
@@ -73,7 +74,12 @@ where
                 .id_gen_manager()
                 .generate::<{ IdCategory::Database }>()
                 .await? as u32;
-            self.create_database(&database).await?;
+            let schema_id = self
+                .env
+                .id_gen_manager()
+                .generate::<{ IdCategory::Schema }>()
+                .await? as u32;
+            self.create_database(&database, schema_id).await?;
         }
         let databases = Database::list(self.env.meta_store())
             .await?
@@ -109,16 +115,35 @@ where
         core.get_catalog().await
     }
 
-    pub async fn create_database(&self, database: &Database) -> Result<CatalogVersion> {
+    /// Creates the database together with its default schema, whose id is `schema_id`.
+    pub async fn create_database(
+        &self,
+        database: &Database,
+        schema_id: SchemaId,
+    ) -> Result<CatalogVersion> {
         let mut core = self.core.lock().await;
         if !core.has_database(database) {
-            database.insert(self.env.meta_store()).await?;
+            let schema = Schema {
+                id: schema_id,
+                name: DEFAULT_SCHEMA_NAME.to_string(),
+                database_id: database.id,
+            };
+
+            let mut transaction = Transaction::default();
+            database.upsert_in_transaction(&mut transaction)?;
+            schema.upsert_in_transaction(&mut transaction)?;
+            self.env.meta_store().txn(transaction).await?;
             core.add_database(database);
+            core.add_schema(&schema);
 
+            self.env
+                .notification_manager()
+                .notify_frontend(Operation::Add, &Info::DatabaseV2(database.to_owned()))
+                .await;
             let version = self
                 .env
                 .notification_manager()
-                .notify_frontend(Operation::Add, &Info::DatabaseV2(database.to_owned()))
+                .notify_frontend(Operation::Add, &Info::SchemaV2(schema))
                 .await
                 .into_inner();
 
@@ -130,13 +155,39 @@ where
         }
     }
 
+    /// Drops the database together with its schemas, which must contain no relations.
     pub async fn drop_database(&self, database_id: DatabaseId) -> Result<CatalogVersion> {
         let mut core = self.core.lock().await;
         let database = Database::select(self.env.meta_store(), &database_id).await?;
         if let Some(database) = database {
-            Database::delete(self.env.meta_store(), &database_id).await?;
+            if core.has_relation_in_database(database_id) {
+                return Err(CatalogError(
+                    anyhow!(
+                        "Fail to delete database `{}` because it's not empty.",
+                        database.name
+                    )
+                    .into(),
+                )
+                .into());
+            }
+            let schemas = Schema::list(self.env.meta_store())
+                .await?
+                .into_iter()
+                .filter(|schema| schema.database_id == database_id)
+                .collect_vec();
+
+            let mut transaction = Transaction::default();
+            for schema in &schemas {
+                schema.delete_in_transaction(&mut transaction)?;
+            }
+            database.delete_in_transaction(&mut transaction)?;
+            self.env.meta_store().txn(transaction).await?;
+            for schema in &schemas {
+                core.drop_schema(schema);
+            }
             core.drop_database(&database);
 
+            // The schemas are dropped with the database in frontend.
             let version = self
                 .env
                 .notification_manager()
@@ -154,6 +205,14 @@ where
 
     pub async fn create_schema(&self, schema: &Schema) -> Result<CatalogVersion> {
         let mut core = self.core.lock().await;
+        if Database::select(self.env.meta_store(), &schema.database_id)
+            .await?
+            .is_none()
+        {
+            return Err(RwError::from(InternalError(
+                "database doesn't exist".to_string(),
+            )));
+        }
         if !core.has_schema(schema) {
             schema.insert(self.env.meta_store()).await?;
             core.add_schema(schema);
@@ -177,6 +236,16 @@ where
         let mut core = self.core.lock().await;
         let schema = Schema::select(self.env.meta_store(), &schema_id).await?;
         if let Some(schema) = schema {
+            if core.has_relation_in_schema(schema.database_id, schema_id) {
+                return Err(CatalogError(
+                    anyhow!(
+                        "Fail to delete schema `{}` because it's not empty.",
+                        schema.name
+                    )
+                    .into(),
+                )
+                .into());
+            }
             Schema::delete(self.env.meta_store(), &schema_id).await?;
             core.drop_schema(&schema);
 
@@ -312,6 +381,33 @@ where
         }
     }
 
+    /// Replace the statistics of a table, which are collected by `ANALYZE` in frontend.
+    pub async fn update_table_stats(
+        &self,
+        table_id: TableId,
+        stats: TableStatistics,
+    ) -> Result<CatalogVersion> {
+        let _core = self.core.lock().await;
+        let table = Table::select(self.env.meta_store(), &table_id).await?;
+        if let Some(mut table) = table {
+            table.stats = Some(stats);
+            table.insert(self.env.meta_store()).await?;
+
+            let version = self
+                .env
+                .notification_manager()
+                .notify_frontend(Operation::Update, &Info::TableV2(table))
+                .await
+                .into_inner();
+
+            Ok(version)
+        } else {
+            Err(RwError::from(InternalError(
+                "table doesn't exist".to_string(),
+            )))
+        }
+    }
+
     pub async fn start_create_source_procedure(&self, source: &Source) -> Result<()> {
         let mut core = self.core.lock().await;
         let key = (source.database_id, source.schema_id, source.name.clone());
@@ -693,6 +789,21 @@ where
             .remove(&(schema.database_id, schema.name.clone()))
     }
 
+    fn has_relation_in_database(&self, database_id: DatabaseId) -> bool {
+        self.tables
+            .iter()
+            .chain(self.sources.iter())
+            .any(|(relation_database_id, ..)| *relation_database_id == database_id)
+    }
+
+    fn has_relation_in_schema(&self, database_id: DatabaseId, schema_id: SchemaId) -> bool {
+        self.tables.iter().chain(self.sources.iter()).any(
+            |(relation_database_id, relation_schema_id, _)| {
+                *relation_database_id == database_id && *relation_schema_id == schema_id
+            },
+        )
+    }
+
     fn has_table(&self, table: &Table) -> bool {
         self.tables
             .contains(&(table.database_id, table.schema_id, table.name.clone()))
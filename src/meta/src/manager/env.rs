@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::{StreamClients, StreamClientsRef};
+use crate::hummock::CompactionConfig;
 #[cfg(any(test, feature = "test"))]
 use crate::manager::MemEpochGenerator;
 use crate::manager::{
@@ -56,6 +59,14 @@ where
 #[derive(Default)]
 pub struct MetaOpts {
     pub enable_recovery: bool,
+    /// Options of hummock compaction picker.
+    pub compaction_config: CompactionConfig,
+    /// If set, meta data in the backup file is written into the empty meta store on start. See
+    /// `risectl backup restore`.
+    pub restore_meta_backup: Option<PathBuf>,
+    /// How long committed epochs stay readable by `FOR SYSTEM_TIME AS OF`. Compaction and vacuum
+    /// keep the data of epochs within this window.
+    pub time_travel_retention: Duration,
 }
 
 impl<S> MetaSrvEnv<S>
@@ -127,6 +138,10 @@ where
 impl MetaSrvEnv<MemStore> {
     // Instance for test.
     pub async fn for_test() -> Self {
+        Self::for_test_opts(MetaOpts::default().into()).await
+    }
+
+    pub async fn for_test_opts(opts: Arc<MetaOpts>) -> Self {
         // change to sync after refactor `IdGeneratorManager::new` sync.
         let meta_store = Arc::new(MemStore::default());
         let id_gen_manager = Arc::new(IdGeneratorManager::new(meta_store.clone()).await);
@@ -140,7 +155,7 @@ impl MetaSrvEnv<MemStore> {
             epoch_generator,
             notification_manager,
             stream_clients,
-            opts: MetaOpts::default().into(),
+            opts,
         }
     }
 }
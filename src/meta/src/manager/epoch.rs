@@ -30,6 +30,11 @@ impl Epoch {
         Epoch(Epoch::physical_now() << EPOCH_PHYSICAL_SHIFT_BITS)
     }
 
+    /// The smallest epoch generated at `physical_time`, in milliseconds since the unix epoch.
+    pub fn from_physical_time(physical_time: u64) -> Self {
+        Epoch(physical_time << EPOCH_PHYSICAL_SHIFT_BITS)
+    }
+
     pub fn physical_time(&self) -> u64 {
         self.0 >> EPOCH_PHYSICAL_SHIFT_BITS
     }
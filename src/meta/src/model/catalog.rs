@@ -21,13 +21,13 @@ use crate::model::MetadataModel;
 use crate::storage::MetaStore;
 
 /// Column family name for table.
-const TABLE_CF_NAME: &str = "cf/table";
+pub(crate) const TABLE_CF_NAME: &str = "cf/table";
 /// Column family name for schema.
-const SCHEMA_CF_NAME: &str = "cf/schema";
+pub(crate) const SCHEMA_CF_NAME: &str = "cf/schema";
 /// Column family name for database.
-const DATABASE_CF_NAME: &str = "cf/database";
+pub(crate) const DATABASE_CF_NAME: &str = "cf/database";
 /// Column family name for catalog version.
-const CATALOG_VERSION_CF_NAME: &str = "cf/catalog_version";
+pub(crate) const CATALOG_VERSION_CF_NAME: &str = "cf/catalog_version";
 /// Catalog version id key.
 /// `cf(catalog_version)`: `catalog_version_id_key` -> `CatalogVersion`
 const CATALOG_VERSION_ID_KEY: &str = "version_id";
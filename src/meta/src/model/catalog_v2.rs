@@ -18,13 +18,13 @@ use risingwave_pb::catalog::{Database, Schema, Source, Table};
 use crate::model::MetadataModel;
 
 /// Column family name for source catalog.
-const CATALOG_SOURCE_CF_NAME: &str = "cf/catalog_source";
+pub(crate) const CATALOG_SOURCE_CF_NAME: &str = "cf/catalog_source";
 /// Column family name for table catalog.
-const CATALOG_TABLE_CF_NAME: &str = "cf/catalog_table";
+pub(crate) const CATALOG_TABLE_CF_NAME: &str = "cf/catalog_table";
 /// Column family name for schema catalog.
-const CATALOG_SCHEMA_CF_NAME: &str = "cf/catalog_schema";
+pub(crate) const CATALOG_SCHEMA_CF_NAME: &str = "cf/catalog_schema";
 /// Column family name for database catalog.
-const CATALOG_DATABASE_CF_NAME: &str = "cf/catalog_database";
+pub(crate) const CATALOG_DATABASE_CF_NAME: &str = "cf/catalog_database";
 
 macro_rules! impl_model_for_catalog {
     ($name:ident, $cf:ident, $key_ty:ty, $key_fn:ident) => {
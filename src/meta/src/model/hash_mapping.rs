@@ -26,7 +26,7 @@ use crate::cluster::ParallelUnitId;
 pub type VirtualKey = usize;
 
 /// Column family name for hash mapping.
-const HASH_MAPPING_CF_NAME: &str = "cf/hash_mapping";
+pub(crate) const HASH_MAPPING_CF_NAME: &str = "cf/hash_mapping";
 /// Hardcoded key for mapping storage.
 const HASH_MAPPING_KEY: &str = "consistent_hash_mapping";
 
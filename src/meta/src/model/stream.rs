@@ -30,7 +30,7 @@ use crate::manager::SourceId;
 use crate::model::MetadataModel;
 
 /// Column family name for table fragments.
-const TABLE_FRAGMENTS_CF_NAME: &str = "cf/table_fragments";
+pub(crate) const TABLE_FRAGMENTS_CF_NAME: &str = "cf/table_fragments";
 
 /// Fragments of a materialized view
 ///
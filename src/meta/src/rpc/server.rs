@@ -17,16 +17,19 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use etcd_client::{Client as EtcdClient, ConnectOptions};
+use prost::Message;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::ddl_service::ddl_service_server::DdlServiceServer;
 use risingwave_pb::hummock::hummock_manager_service_server::HummockManagerServiceServer;
+use risingwave_pb::meta::backup_service_server::BackupServiceServer;
 use risingwave_pb::meta::catalog_service_server::CatalogServiceServer;
 use risingwave_pb::meta::cluster_service_server::ClusterServiceServer;
 use risingwave_pb::meta::epoch_service_server::EpochServiceServer;
 use risingwave_pb::meta::heartbeat_service_server::HeartbeatServiceServer;
 use risingwave_pb::meta::notification_service_server::NotificationServiceServer;
 use risingwave_pb::meta::stream_manager_service_server::StreamManagerServiceServer;
+use risingwave_pb::meta::MetaBackup;
 use tokio::net::TcpListener;
 use tokio::sync::mpsc::{self, UnboundedSender};
 use tokio::task::JoinHandle;
@@ -34,6 +37,7 @@ use tokio::task::JoinHandle;
 use super::intercept::MetricsMiddlewareLayer;
 use super::service::notification_service::NotificationServiceImpl;
 use super::DdlServiceImpl;
+use crate::backup::{check_meta_restore_state, restore_meta};
 use crate::barrier::GlobalBarrierManager;
 use crate::cluster::ClusterManager;
 use crate::dashboard::DashboardService;
@@ -42,6 +46,7 @@ use crate::manager::{
     CatalogManager, MemEpochGenerator, MetaOpts, MetaSrvEnv, StoredCatalogManager,
 };
 use crate::rpc::metrics::MetaMetrics;
+use crate::rpc::service::backup_service::BackupServiceImpl;
 use crate::rpc::service::catalog_service::CatalogServiceImpl;
 use crate::rpc::service::cluster_service::ClusterServiceImpl;
 use crate::rpc::service::epoch_service::EpochServiceImpl;
@@ -66,7 +71,7 @@ pub async fn rpc_serve(
     ui_path: Option<String>,
     opts: MetaOpts,
 ) -> Result<(JoinHandle<()>, UnboundedSender<()>)> {
-    Ok(match meta_store_backend {
+    match meta_store_backend {
         MetaStoreBackend::Etcd { endpoints } => {
             let client = EtcdClient::connect(
                 endpoints,
@@ -102,7 +107,7 @@ pub async fn rpc_serve(
             )
             .await
         }
-    })
+    }
 }
 
 pub async fn rpc_serve_with_store<S: MetaStore>(
@@ -113,8 +118,25 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     max_heartbeat_interval: Duration,
     ui_path: Option<String>,
     opts: MetaOpts,
-) -> (JoinHandle<()>, UnboundedSender<()>) {
+) -> Result<(JoinHandle<()>, UnboundedSender<()>)> {
     let listener = TcpListener::bind(addr).await.unwrap();
+    if let Some(path) = &opts.restore_meta_backup {
+        let buf = tokio::fs::read(path).await.map_err(|e| {
+            RwError::from(InternalError(format!(
+                "failed to read meta backup {:?}: {}",
+                path, e
+            )))
+        })?;
+        let backup = MetaBackup::decode(buf.as_slice()).map_err(|e| {
+            RwError::from(InternalError(format!(
+                "failed to decode meta backup {:?}: {}",
+                path, e
+            )))
+        })?;
+        restore_meta(meta_store.as_ref(), backup).await?;
+        tracing::info!("Restored meta from backup {:?}", path);
+    }
+    check_meta_restore_state(meta_store.as_ref()).await?;
     let epoch_generator = Arc::new(MemEpochGenerator::new());
     let env = MetaSrvEnv::<S>::new(opts, meta_store.clone(), epoch_generator.clone()).await;
 
@@ -214,6 +236,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         fragment_manager.clone(),
         cluster_manager.clone(),
     );
+    let backup_srv = BackupServiceImpl::new(hummock_manager.clone());
     let hummock_srv = HummockServiceImpl::new(
         hummock_manager.clone(),
         compactor_manager.clone(),
@@ -255,6 +278,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             .add_service(ClusterServiceServer::new(cluster_srv))
             .add_service(StreamManagerServiceServer::new(stream_srv))
             .add_service(HummockManagerServiceServer::new(hummock_srv))
+            .add_service(BackupServiceServer::new(backup_srv))
             .add_service(NotificationServiceServer::new(notification_srv))
             .add_service(DdlServiceServer::new(ddl_srv))
             .serve_with_incoming_shutdown(
@@ -280,5 +304,5 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             .unwrap();
     });
 
-    (join_handle, shutdown_send)
+    Ok((join_handle, shutdown_send))
 }
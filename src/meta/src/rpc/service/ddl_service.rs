@@ -84,11 +84,17 @@ where
             .generate::<{ IdCategory::Database }>()
             .await
             .map_err(tonic_err)? as u32;
+        let schema_id = self
+            .env
+            .id_gen_manager()
+            .generate::<{ IdCategory::Schema }>()
+            .await
+            .map_err(tonic_err)? as u32;
         let mut database = req.get_db().map_err(tonic_err)?.clone();
         database.id = id;
         let version = self
             .catalog_manager
-            .create_database(&database)
+            .create_database(&database, schema_id)
             .await
             .map_err(tonic_err)?;
 
@@ -371,6 +377,23 @@ where
             version,
         }))
     }
+
+    async fn update_table_stats(
+        &self,
+        request: Request<UpdateTableStatsRequest>,
+    ) -> Result<Response<UpdateTableStatsResponse>, Status> {
+        let request = request.into_inner();
+        let version = self
+            .catalog_manager
+            .update_table_stats(request.table_id, request.stats.unwrap_or_default())
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(UpdateTableStatsResponse {
+            status: None,
+            version,
+        }))
+    }
 }
 
 impl<S> DdlServiceImpl<S>
@@ -145,6 +145,24 @@ where
         }
     }
 
+    async fn pin_snapshot_as_of(
+        &self,
+        request: Request<PinSnapshotAsOfRequest>,
+    ) -> Result<Response<PinSnapshotAsOfResponse>, Status> {
+        let req = request.into_inner();
+        let result = self
+            .hummock_manager
+            .pin_snapshot_as_of(req.context_id, req.epoch)
+            .await;
+        match result {
+            Ok(hummock_snapshot) => Ok(Response::new(PinSnapshotAsOfResponse {
+                status: None,
+                snapshot: Some(hummock_snapshot),
+            })),
+            Err(e) => Err(e.to_grpc_status()),
+        }
+    }
+
     async fn unpin_snapshot(
         &self,
         request: Request<UnpinSnapshotRequest>,
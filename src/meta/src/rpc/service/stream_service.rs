@@ -20,7 +20,7 @@ use tonic::{Request, Response, Status};
 
 use crate::cluster::ClusterManagerRef;
 use crate::manager::MetaSrvEnv;
-use crate::model::TableFragments;
+use crate::model::{MetadataModel, TableFragments};
 use crate::storage::MetaStore;
 use crate::stream::{FragmentManagerRef, GlobalStreamManagerRef, StreamFragmenter};
 
@@ -130,4 +130,25 @@ where
             .map_err(|e| e.to_grpc_status())?;
         Ok(Response::new(FlushResponse { status: None }))
     }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn list_table_fragments(
+        &self,
+        request: Request<ListTableFragmentsRequest>,
+    ) -> TonicResponse<ListTableFragmentsResponse> {
+        let _req = request.into_inner();
+
+        let table_fragments = self
+            .fragment_manager
+            .list_table_fragments()
+            .await
+            .map_err(|e| e.to_grpc_status())?;
+        Ok(Response::new(ListTableFragmentsResponse {
+            status: None,
+            table_fragments: table_fragments
+                .iter()
+                .map(|table_fragments| table_fragments.to_protobuf())
+                .collect(),
+        }))
+    }
 }
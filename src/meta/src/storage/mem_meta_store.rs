@@ -56,6 +56,14 @@ impl Snapshot for MemSnapshot {
         })
     }
 
+    #[inline(always)]
+    async fn list_cf_kv(&self, cf: &str) -> Result<Vec<(Key, Value)>> {
+        Ok(match self.0.cf_ref(cf) {
+            Some(cf) => cf.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            None => vec![],
+        })
+    }
+
     #[inline(always)]
     async fn get_cf(&self, cf: &str, key: &[u8]) -> Result<Value> {
         self.0
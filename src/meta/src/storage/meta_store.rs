@@ -25,6 +25,8 @@ pub const DEFAULT_COLUMN_FAMILY: &str = "default";
 #[async_trait]
 pub trait Snapshot: Sync + Send + 'static {
     async fn list_cf(&self, cf: &str) -> Result<Vec<Vec<u8>>>;
+    /// Lists key-value pairs in `cf`, ordered by key.
+    async fn list_cf_kv(&self, cf: &str) -> Result<Vec<(Key, Value)>>;
     async fn get_cf(&self, cf: &str, key: &[u8]) -> Result<Vec<u8>>;
 }
 
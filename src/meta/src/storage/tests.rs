@@ -207,6 +207,15 @@ async fn test_meta_store_keys_share_prefix<S: MetaStore>(meta_store: &S) -> Resu
         meta_store.put_cf(cf, key, value).await?;
     }
     assert_eq!(3, meta_store.list_cf(cf).await.unwrap().len());
+    let kvs = meta_store.snapshot().await.list_cf_kv(cf).await?;
+    assert_eq!(
+        kvs,
+        vec![
+            (b"key1".to_vec(), b"value1".to_vec()),
+            (b"key11".to_vec(), b"value11".to_vec()),
+            (b"key111".to_vec(), b"value111".to_vec()),
+        ]
+    );
 
     Ok(())
 }
@@ -513,6 +513,13 @@ mod tests {
         ) -> std::result::Result<Response<SyncSourcesResponse>, Status> {
             Ok(Response::new(SyncSourcesResponse::default()))
         }
+
+        async fn get_actor_stats(
+            &self,
+            _request: Request<GetActorStatsRequest>,
+        ) -> std::result::Result<Response<GetActorStatsResponse>, Status> {
+            unimplemented!()
+        }
     }
 
     struct MockServices {
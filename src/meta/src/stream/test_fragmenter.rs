@@ -73,6 +73,7 @@ fn make_sum_aggcall(idx: i32) -> AggCall {
             ..Default::default()
         }),
         distinct: false,
+        ..Default::default()
     }
 }
 
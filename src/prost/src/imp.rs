@@ -104,6 +104,10 @@ impl crate::plan::ColumnDesc {
             column_type: Some(DataType {
                 type_name: TypeName::Struct as i32,
                 is_nullable: true,
+                field_type: fields
+                    .iter()
+                    .map(|f| f.column_type.clone().unwrap())
+                    .collect(),
                 ..Default::default()
             }),
             column_id,
@@ -23,11 +23,13 @@ use risingwave_common::error::{Result, ToRwResult};
 use risingwave_common::util::addr::HostAddr;
 use risingwave_pb::plan::exchange_info::DistributionMode;
 use risingwave_pb::plan::{ExchangeInfo, PlanFragment, PlanNode, TaskId, TaskOutputId};
+use risingwave_pb::stream_service::stream_service_client::StreamServiceClient;
+use risingwave_pb::stream_service::{ActorStats, GetActorStatsRequest};
 use risingwave_pb::task_service::exchange_service_client::ExchangeServiceClient;
 use risingwave_pb::task_service::task_service_client::TaskServiceClient;
 use risingwave_pb::task_service::{
     CreateTaskRequest, CreateTaskResponse, GetDataRequest, GetDataResponse, GetStreamRequest,
-    GetStreamResponse,
+    GetStreamResponse, GetTaskInfoRequest, TaskInfo,
 };
 use tonic::transport::{Channel, Endpoint};
 use tonic::Streaming;
@@ -36,6 +38,7 @@ use tonic::Streaming;
 pub struct ComputeClient {
     pub exchange_client: ExchangeServiceClient<Channel>,
     pub task_client: TaskServiceClient<Channel>,
+    pub stream_client: StreamServiceClient<Channel>,
     pub addr: HostAddr,
 }
 
@@ -48,10 +51,12 @@ impl ComputeClient {
             .await
             .to_rw_result_with(|| format!("failed to connect to {}", &addr))?;
         let exchange_client = ExchangeServiceClient::new(channel.clone());
-        let task_client = TaskServiceClient::new(channel);
+        let task_client = TaskServiceClient::new(channel.clone());
+        let stream_client = StreamServiceClient::new(channel);
         Ok(Self {
             exchange_client,
             task_client,
+            stream_client,
             addr,
         })
     }
@@ -134,6 +139,31 @@ impl ComputeClient {
         Ok(())
     }
 
+    pub async fn get_task_info(&self, task_id: TaskId) -> Result<TaskInfo> {
+        let resp = self
+            .task_client
+            .to_owned()
+            .get_task_info(GetTaskInfoRequest {
+                task_id: Some(task_id),
+            })
+            .await
+            .to_rw_result()?
+            .into_inner();
+        Ok(resp.task_info.unwrap_or_default())
+    }
+
+    /// Returns the runtime statistics of the streaming actors on the compute node.
+    pub async fn get_actor_stats(&self) -> Result<Vec<ActorStats>> {
+        let resp = self
+            .stream_client
+            .to_owned()
+            .get_actor_stats(GetActorStatsRequest {})
+            .await
+            .to_rw_result()?
+            .into_inner();
+        Ok(resp.actor_stats)
+    }
+
     async fn create_task_inner(&self, req: CreateTaskRequest) -> Result<CreateTaskResponse> {
         Ok(self
             .task_client
@@ -24,6 +24,7 @@ use risingwave_common::util::addr::HostAddr;
 use risingwave_hummock_sdk::{HummockEpoch, HummockSSTableId, HummockVersionId};
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
+    TableStatistics as ProstTableStatistics,
 };
 use risingwave_pb::common::{WorkerNode, WorkerType};
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
@@ -31,20 +32,22 @@ use risingwave_pb::ddl_service::{
     CreateDatabaseRequest, CreateDatabaseResponse, CreateMaterializedSourceRequest,
     CreateMaterializedSourceResponse, CreateMaterializedViewRequest,
     CreateMaterializedViewResponse, CreateSchemaRequest, CreateSchemaResponse, CreateSourceRequest,
-    CreateSourceResponse, DropMaterializedSourceRequest, DropMaterializedSourceResponse,
-    DropMaterializedViewRequest, DropMaterializedViewResponse, DropSourceRequest,
-    DropSourceResponse,
+    CreateSourceResponse, DropDatabaseRequest, DropDatabaseResponse, DropMaterializedSourceRequest,
+    DropMaterializedSourceResponse, DropMaterializedViewRequest, DropMaterializedViewResponse,
+    DropSchemaRequest, DropSchemaResponse, DropSourceRequest, DropSourceResponse,
+    UpdateTableStatsRequest, UpdateTableStatsResponse,
 };
 use risingwave_pb::hummock::hummock_manager_service_client::HummockManagerServiceClient;
 use risingwave_pb::hummock::{
     AbortEpochRequest, AbortEpochResponse, AddTablesRequest, AddTablesResponse, CommitEpochRequest,
     CommitEpochResponse, CompactTask, GetNewTableIdRequest, GetNewTableIdResponse, HummockSnapshot,
-    HummockVersion, PinSnapshotRequest, PinSnapshotResponse, PinVersionRequest, PinVersionResponse,
-    ReportCompactionTasksRequest, ReportCompactionTasksResponse, ReportVacuumTaskRequest,
-    ReportVacuumTaskResponse, SstableInfo, SubscribeCompactTasksRequest,
-    SubscribeCompactTasksResponse, UnpinSnapshotRequest, UnpinSnapshotResponse,
-    UnpinVersionRequest, UnpinVersionResponse, VacuumTask,
+    HummockVersion, PinSnapshotAsOfRequest, PinSnapshotAsOfResponse, PinSnapshotRequest,
+    PinSnapshotResponse, PinVersionRequest, PinVersionResponse, ReportCompactionTasksRequest,
+    ReportCompactionTasksResponse, ReportVacuumTaskRequest, ReportVacuumTaskResponse, SstableInfo,
+    SubscribeCompactTasksRequest, SubscribeCompactTasksResponse, UnpinSnapshotRequest,
+    UnpinSnapshotResponse, UnpinVersionRequest, UnpinVersionResponse, VacuumTask,
 };
+use risingwave_pb::meta::backup_service_client::BackupServiceClient;
 use risingwave_pb::meta::catalog_service_client::CatalogServiceClient;
 use risingwave_pb::meta::cluster_service_client::ClusterServiceClient;
 use risingwave_pb::meta::heartbeat_service_client::HeartbeatServiceClient;
@@ -52,9 +55,10 @@ use risingwave_pb::meta::notification_service_client::NotificationServiceClient;
 use risingwave_pb::meta::stream_manager_service_client::StreamManagerServiceClient;
 use risingwave_pb::meta::{
     ActivateWorkerNodeRequest, ActivateWorkerNodeResponse, AddWorkerNodeRequest,
-    AddWorkerNodeResponse, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse, FlushRequest,
-    FlushResponse, HeartbeatRequest, HeartbeatResponse, ListAllNodesRequest, ListAllNodesResponse,
-    SubscribeRequest, SubscribeResponse,
+    AddWorkerNodeResponse, BackupMetaRequest, BackupMetaResponse, DeleteWorkerNodeRequest,
+    DeleteWorkerNodeResponse, FlushRequest, FlushResponse, HeartbeatRequest, HeartbeatResponse,
+    ListAllNodesRequest, ListAllNodesResponse, ListTableFragmentsRequest,
+    ListTableFragmentsResponse, MetaBackup, SubscribeRequest, SubscribeResponse, TableFragments,
 };
 use risingwave_pb::stream_plan::StreamNode;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
@@ -152,6 +156,18 @@ impl MetaClient {
         Ok((resp.schema_id, resp.version))
     }
 
+    pub async fn drop_database(&self, database_id: DatabaseId) -> Result<CatalogVersion> {
+        let request = DropDatabaseRequest { database_id };
+        let resp = self.inner.drop_database(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn drop_schema(&self, schema_id: SchemaId) -> Result<CatalogVersion> {
+        let request = DropSchemaRequest { schema_id };
+        let resp = self.inner.drop_schema(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn create_materialized_view(
         &self,
         table: ProstTable,
@@ -220,6 +236,19 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn update_table_stats(
+        &self,
+        table_id: TableId,
+        stats: ProstTableStatistics,
+    ) -> Result<CatalogVersion> {
+        let request = UpdateTableStatsRequest {
+            table_id: table_id.table_id(),
+            stats: Some(stats),
+        };
+        let resp = self.inner.update_table_stats(request).await?;
+        Ok(resp.version)
+    }
+
     /// Unregister the current node to the cluster.
     pub async fn unregister(&self, addr: HostAddr) -> Result<()> {
         let request = DeleteWorkerNodeRequest {
@@ -289,6 +318,33 @@ impl MetaClient {
         self.inner.flush(request).await?;
         Ok(())
     }
+
+    /// Lists the fragments and actors of all materialized views.
+    pub async fn list_table_fragments(&self) -> Result<Vec<TableFragments>> {
+        let request = ListTableFragmentsRequest::default();
+        let resp = self.inner.list_table_fragments(request).await?;
+        Ok(resp.table_fragments)
+    }
+
+    /// Pins the hummock snapshot of an earlier `epoch`, which is used by time travel queries.
+    /// Unpinned by `unpin_snapshot`.
+    pub async fn pin_snapshot_as_of(&self, epoch: HummockEpoch) -> Result<HummockEpoch> {
+        let request = PinSnapshotAsOfRequest {
+            context_id: self.worker_id(),
+            epoch,
+        };
+        let resp = self.inner.pin_snapshot_as_of(request).await?;
+        Ok(resp.snapshot.unwrap().epoch)
+    }
+
+    /// Exports meta data, with the current hummock version pinned for this worker.
+    pub async fn backup_meta(&self) -> Result<MetaBackup> {
+        let request = BackupMetaRequest {
+            context_id: self.worker_id(),
+        };
+        let resp = self.inner.backup_meta(request).await?;
+        Ok(resp.backup.unwrap())
+    }
 }
 
 #[async_trait]
@@ -399,6 +455,7 @@ pub struct GrpcMetaClient {
     pub hummock_client: HummockManagerServiceClient<Channel>,
     pub notification_client: NotificationServiceClient<Channel>,
     pub stream_client: StreamManagerServiceClient<Channel>,
+    pub backup_client: BackupServiceClient<Channel>,
 }
 
 impl GrpcMetaClient {
@@ -416,7 +473,8 @@ impl GrpcMetaClient {
         let ddl_client = DdlServiceClient::new(channel.clone());
         let hummock_client = HummockManagerServiceClient::new(channel.clone());
         let notification_client = NotificationServiceClient::new(channel.clone());
-        let stream_client = StreamManagerServiceClient::new(channel);
+        let stream_client = StreamManagerServiceClient::new(channel.clone());
+        let backup_client = BackupServiceClient::new(channel);
         Ok(Self {
             cluster_client,
             heartbeat_client,
@@ -425,6 +483,7 @@ impl GrpcMetaClient {
             hummock_client,
             notification_client,
             stream_client,
+            backup_client,
         })
     }
 }
@@ -457,17 +516,22 @@ macro_rules! for_all_meta_rpc {
             ,{ cluster_client, list_all_nodes, ListAllNodesRequest, ListAllNodesResponse }
             ,{ heartbeat_client, heartbeat, HeartbeatRequest, HeartbeatResponse }
             ,{ stream_client, flush, FlushRequest, FlushResponse }
+            ,{ stream_client, list_table_fragments, ListTableFragmentsRequest, ListTableFragmentsResponse }
             ,{ ddl_client, create_materialized_source, CreateMaterializedSourceRequest, CreateMaterializedSourceResponse }
             ,{ ddl_client, create_materialized_view, CreateMaterializedViewRequest, CreateMaterializedViewResponse }
             ,{ ddl_client, create_source, CreateSourceRequest, CreateSourceResponse }
             ,{ ddl_client, create_schema, CreateSchemaRequest, CreateSchemaResponse }
             ,{ ddl_client, create_database, CreateDatabaseRequest, CreateDatabaseResponse }
+            ,{ ddl_client, drop_schema, DropSchemaRequest, DropSchemaResponse }
+            ,{ ddl_client, drop_database, DropDatabaseRequest, DropDatabaseResponse }
             ,{ ddl_client, drop_materialized_source, DropMaterializedSourceRequest, DropMaterializedSourceResponse }
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
+            ,{ ddl_client, update_table_stats, UpdateTableStatsRequest, UpdateTableStatsResponse }
             ,{ hummock_client, pin_version, PinVersionRequest, PinVersionResponse }
             ,{ hummock_client, unpin_version, UnpinVersionRequest, UnpinVersionResponse }
             ,{ hummock_client, pin_snapshot, PinSnapshotRequest, PinSnapshotResponse }
+            ,{ hummock_client, pin_snapshot_as_of, PinSnapshotAsOfRequest, PinSnapshotAsOfResponse }
             ,{ hummock_client, unpin_snapshot, UnpinSnapshotRequest, UnpinSnapshotResponse }
             ,{ hummock_client, add_tables, AddTablesRequest, AddTablesResponse }
             ,{ hummock_client, report_compaction_tasks, ReportCompactionTasksRequest, ReportCompactionTasksResponse }
@@ -476,6 +540,7 @@ macro_rules! for_all_meta_rpc {
             ,{ hummock_client, report_vacuum_task, ReportVacuumTaskRequest, ReportVacuumTaskResponse }
             ,{ hummock_client, commit_epoch, CommitEpochRequest, CommitEpochResponse }
             ,{ hummock_client, abort_epoch, AbortEpochRequest, AbortEpochResponse }
+            ,{ backup_client, backup_meta, BackupMetaRequest, BackupMetaResponse }
         }
     };
 }
@@ -64,6 +64,7 @@ pub enum SourceConfig {
 pub enum SourceFormat {
     Invalid,
     Json,
+    JsonRaw,
     Protobuf,
     DebeziumJson,
     Avro,
@@ -30,8 +30,8 @@ use risingwave_pb::catalog::{RowFormatType, StreamSourceInfo};
 use crate::connector_source::ConnectorSource;
 use crate::table_v2::TableSourceV2;
 use crate::{
-    DebeziumJsonParser, HighLevelKafkaSource, JSONParser, ProtobufParser, SourceConfig,
-    SourceFormat, SourceImpl, SourceParser,
+    DebeziumJsonParser, HighLevelKafkaSource, JSONParser, JsonRawParser, ProtobufParser,
+    SourceConfig, SourceFormat, SourceImpl, SourceParser,
 };
 
 pub type SourceRef = Arc<SourceImpl>;
@@ -75,6 +75,8 @@ pub struct SourceColumnDesc {
     pub data_type: DataType,
     pub column_id: ColumnId,
     pub skip_parse: bool,
+    /// The nested fields of a struct column.
+    pub fields: Vec<SourceColumnDesc>,
 }
 
 impl From<&ColumnDesc> for SourceColumnDesc {
@@ -84,6 +86,7 @@ impl From<&ColumnDesc> for SourceColumnDesc {
             data_type: c.data_type.clone(),
             column_id: c.column_id,
             skip_parse: false,
+            fields: c.field_descs.iter().map(SourceColumnDesc::from).collect(),
         }
     }
 }
@@ -156,6 +159,7 @@ impl SourceManager for MemSourceManager {
     async fn create_source_v2(&self, source_id: &TableId, info: StreamSourceInfo) -> Result<()> {
         let format = match info.get_row_format()? {
             RowFormatType::Json => SourceFormat::Json,
+            RowFormatType::JsonRaw => SourceFormat::JsonRaw,
             RowFormatType::Protobuf => SourceFormat::Protobuf,
             RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
             RowFormatType::Avro => SourceFormat::Avro,
@@ -175,12 +179,10 @@ impl SourceManager for MemSourceManager {
             .iter()
             .enumerate()
             .map(|(idx, c)| {
-                let c = c.column_desc.as_ref().unwrap().clone();
+                let c = ColumnDesc::from(c.column_desc.as_ref().unwrap().clone());
                 SourceColumnDesc {
-                    name: c.name.clone(),
-                    data_type: DataType::from(&c.column_type.unwrap()),
-                    column_id: ColumnId::from(c.column_id),
                     skip_parse: idx as i32 == info.row_id_index,
+                    ..(&c).into()
                 }
             })
             .collect::<Vec<SourceColumnDesc>>();
@@ -317,6 +319,10 @@ fn build_source_parser(
             let parser: Arc<dyn SourceParser + Send + Sync> = Arc::new(JSONParser {});
             Ok(parser)
         }
+        SourceFormat::JsonRaw => {
+            let parser: Arc<dyn SourceParser + Send + Sync> = Arc::new(JsonRawParser {});
+            Ok(parser)
+        }
         SourceFormat::Protobuf => {
             let message_name = properties.get(PROTOBUF_MESSAGE_KEY)?;
             let parser: Arc<dyn SourceParser + Send + Sync> =
@@ -378,6 +384,7 @@ mod tests {
                 data_type: c.data_type.clone(),
                 column_id: c.column_id,
                 skip_parse: false,
+                fields: vec![],
             })
             .collect();
 
@@ -14,6 +14,7 @@
 
 use chrono::NaiveDate;
 use num_traits::FromPrimitive;
+use risingwave_common::array::StructValue;
 use risingwave_common::error::ErrorCode::{self, InternalError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{DataType, Decimal, NaiveDateWrapper, ScalarImpl, ScalarRef};
@@ -95,6 +96,23 @@ pub(crate) fn json_parse_value(
                 Err(e) => Err(e),
             },
         },
+        DataType::Struct { .. } => match value.and_then(|v| v.as_object()) {
+            // Names of nested columns are paths like `country.city`, so we look up the last part.
+            Some(object) => Ok(ScalarImpl::Struct(StructValue::new(
+                column
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let name = field.name.rsplit('.').next().unwrap();
+                        json_parse_value(field, object.get(name)).ok()
+                    })
+                    .collect(),
+            ))),
+            None => Err(RwError::from(InternalError("json parse error".to_string()))),
+        },
+        DataType::Jsonb => {
+            make_ScalarImpl!(value, |v: &Value| ScalarImpl::Jsonb(v.clone().into()))
+        }
         _ => Err(ErrorCode::NotImplemented(
             "unsupported type for json_parse_value".to_string(),
             None.into(),
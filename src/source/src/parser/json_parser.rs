@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_common::array::Op;
+use risingwave_common::array::{JsonbVal, Op};
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::Datum;
+use risingwave_common::types::{Datum, ScalarImpl};
 use serde_json::Value;
 
 use crate::parser::common::json_parse_value;
@@ -46,13 +46,41 @@ impl SourceParser for JSONParser {
     }
 }
 
+/// Parser for JSON format that keeps the whole payload in a single JSONB column, so that events
+/// without a fixed set of top-level keys can be queried with the jsonb operators.
+#[derive(Debug)]
+pub struct JsonRawParser;
+
+impl SourceParser for JsonRawParser {
+    fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
+        let value: Value = serde_json::from_slice(payload)
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        let mut value = Some(JsonbVal::from(value));
+
+        Ok(Event {
+            ops: vec![Op::Insert],
+            rows: vec![columns
+                .iter()
+                .map(|column| {
+                    if column.skip_parse {
+                        None
+                    } else {
+                        value.take().map(ScalarImpl::Jsonb)
+                    }
+                })
+                .collect::<Vec<Datum>>()],
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use risingwave_common::array::StructValue;
     use risingwave_common::catalog::ColumnId;
     use risingwave_common::types::{DataType, ScalarImpl};
 
     use super::super::common::str_to_date;
-    use crate::{JSONParser, SourceColumnDesc, SourceParser};
+    use crate::{JSONParser, JsonRawParser, SourceColumnDesc, SourceParser};
 
     #[test]
     fn test_json_parser() {
@@ -65,48 +93,56 @@ mod tests {
                 data_type: DataType::Int32,
                 column_id: ColumnId::from(0),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "bool".to_string(),
                 data_type: DataType::Boolean,
                 column_id: ColumnId::from(2),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "i16".to_string(),
                 data_type: DataType::Int16,
                 column_id: ColumnId::from(3),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "i64".to_string(),
                 data_type: DataType::Int64,
                 column_id: ColumnId::from(4),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "f32".to_string(),
                 data_type: DataType::Float32,
                 column_id: ColumnId::from(5),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "f64".to_string(),
                 data_type: DataType::Float64,
                 column_id: ColumnId::from(6),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "varchar".to_string(),
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(7),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "date".to_string(),
                 data_type: DataType::Date,
                 column_id: ColumnId::from(8),
                 skip_parse: false,
+                fields: vec![],
             },
         ];
 
@@ -139,4 +175,90 @@ mod tests {
         let result = parser.parse(payload, &descs);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_json_parse_struct() {
+        let parser = JSONParser {};
+
+        let field = |name: &str, data_type: DataType, column_id: i32| SourceColumnDesc {
+            name: name.to_string(),
+            data_type,
+            column_id: ColumnId::from(column_id),
+            skip_parse: false,
+            fields: vec![],
+        };
+        let descs = vec![SourceColumnDesc {
+            name: "country".to_string(),
+            data_type: DataType::Struct {
+                fields: vec![DataType::Varchar, DataType::Int32].into(),
+            },
+            column_id: ColumnId::from(0),
+            skip_parse: false,
+            fields: vec![
+                field("country.address", DataType::Varchar, 1),
+                field("country.zipcode", DataType::Int32, 2),
+            ],
+        }];
+
+        let payload = r#"{"country":{"address":"a","zipcode":123}}"#.as_bytes();
+        let event = parser.parse(payload, &descs).unwrap();
+        assert_eq!(
+            event.rows[0][0],
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                Some(ScalarImpl::Utf8("a".to_string())),
+                Some(ScalarImpl::Int32(123)),
+            ])))
+        );
+
+        let payload = r#"{"country":{"zipcode":123}}"#.as_bytes();
+        let event = parser.parse(payload, &descs).unwrap();
+        assert_eq!(
+            event.rows[0][0],
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                None,
+                Some(ScalarImpl::Int32(123)),
+            ])))
+        );
+
+        let payload = r#"{"country":null}"#.as_bytes();
+        let event = parser.parse(payload, &descs).unwrap();
+        assert_eq!(event.rows[0][0], None);
+    }
+
+    #[test]
+    fn test_json_parse_jsonb() {
+        let column = |name: &str, skip_parse: bool| SourceColumnDesc {
+            name: name.to_string(),
+            data_type: DataType::Jsonb,
+            column_id: ColumnId::from(0),
+            skip_parse,
+            fields: vec![],
+        };
+        let payload = r#"{"tags":["a","b"],"user":{"id":1}}"#.as_bytes();
+
+        let event = JSONParser {}
+            .parse(payload, &[column("user", false)])
+            .unwrap();
+        assert_eq!(
+            event.rows[0][0],
+            Some(ScalarImpl::Jsonb(r#"{"id": 1}"#.parse().unwrap()))
+        );
+
+        let event = JsonRawParser {}
+            .parse(
+                payload,
+                &[column("_row_id", true), column("payload", false)],
+            )
+            .unwrap();
+        assert_eq!(event.rows[0][0], None);
+        assert_eq!(
+            event.rows[0][1],
+            Some(ScalarImpl::Jsonb(
+                r#"{"tags": ["a", "b"], "user": {"id": 1}}"#.parse().unwrap()
+            ))
+        );
+        assert!(JsonRawParser {}
+            .parse(b"{", &[column("payload", false)])
+            .is_err());
+    }
 }
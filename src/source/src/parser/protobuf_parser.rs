@@ -16,7 +16,7 @@ use std::path::Path;
 
 use protobuf::descriptor::FileDescriptorSet;
 use protobuf::RepeatedField;
-use risingwave_common::array::Op;
+use risingwave_common::array::{Op, StructValue};
 use risingwave_common::error::ErrorCode::{self, InternalError, ItemNotFound, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{DataType, Datum, Decimal, OrderedF32, OrderedF64, ScalarImpl};
@@ -224,6 +224,68 @@ fn protobuf_type_mapping(f: &FieldDescriptor, descriptors: &Descriptors) -> Resu
     Ok(t)
 }
 
+/// Converts a decoded protobuf value to a datum of the column. Nested messages are converted to
+/// structs.
+fn protobuf_parse_value(column: &SourceColumnDesc, value: Option<Value>) -> Datum {
+    match column.data_type {
+        DataType::Boolean => {
+            protobuf_match_type!(value, ScalarImpl::Bool, { Bool }, bool)
+        }
+        DataType::Int16 => {
+            protobuf_match_type!(value, ScalarImpl::Int16, { I8, I16, U8 }, i16)
+        }
+        DataType::Int32 => {
+            protobuf_match_type!(value, ScalarImpl::Int32, { I8, I16, I32, U8, U16 }, i32)
+        }
+        DataType::Int64 => {
+            protobuf_match_type!(value, ScalarImpl::Int64, { I8, I16, I32, I64, U8, U16, U32 }, i64)
+        }
+        DataType::Float32 => {
+            protobuf_match_type!(value, ScalarImpl::Float32, { I8, I16, U8, U16, F32 }, OrderedF32)
+        }
+        DataType::Float64 => {
+            protobuf_match_type!(value, ScalarImpl::Float64, { I8, I16, I32, U8, U16, U32, F32, F64}, OrderedF64)
+        }
+        DataType::Decimal => {
+            protobuf_match_type!(value, ScalarImpl::Decimal, { I8, I16, I32, I64, U8, U16, U32, U64}, Decimal)
+        }
+        DataType::Varchar => {
+            protobuf_match_type!(value, ScalarImpl::Utf8, { String }, String)
+        }
+        DataType::Date => value
+            .and_then(|v| match v {
+                Value::String(b) => str_to_date(&b).ok(),
+                Value::Option(Some(boxed_value)) => match *boxed_value {
+                    Value::String(b) => str_to_date(&b).ok(),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .map(ScalarImpl::NaiveDate),
+        DataType::Struct { .. } => {
+            let mut map = match value {
+                Some(Value::Map(m)) => m,
+                Some(Value::Option(Some(boxed_value))) => match *boxed_value {
+                    Value::Map(m) => m,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            // Names of nested columns are paths like `country.city`, so we look up the last part.
+            let fields = column
+                .fields
+                .iter()
+                .map(|field| {
+                    let name = field.name.rsplit('.').next().unwrap();
+                    protobuf_parse_value(field, map.remove(&Value::String(name.to_string())))
+                })
+                .collect();
+            Some(ScalarImpl::Struct(StructValue::new(fields)))
+        }
+        _ => unimplemented!(),
+    }
+}
+
 impl SourceParser for ProtobufParser {
     fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
         let mut map = match self.decode(payload)? {
@@ -231,53 +293,16 @@ impl SourceParser for ProtobufParser {
             _ => return Err(RwError::from(ProtocolError("".to_string()))),
         };
 
-        let row = columns.iter().map(|column| {
-            if column.skip_parse {
-                return None;
-            }
-
-            let key = Value::String(column.name.clone());
-
-            // Use `remove` instead of `get` to take the ownership of the value
-            let value = map.remove(&key);
-            match column.data_type {
-                DataType::Boolean => {
-                    protobuf_match_type!(value, ScalarImpl::Bool, { Bool }, bool)
-                }
-                DataType::Int16 => {
-                    protobuf_match_type!(value, ScalarImpl::Int16, { I8, I16, U8 }, i16)
-                }
-                DataType::Int32 => {
-                    protobuf_match_type!(value, ScalarImpl::Int32, { I8, I16, I32, U8, U16 }, i32)
-                }
-                DataType::Int64 => {
-                    protobuf_match_type!(value, ScalarImpl::Int64, { I8, I16, I32, I64, U8, U16, U32 }, i64)
-                }
-                DataType::Float32 => {
-                    protobuf_match_type!(value, ScalarImpl::Float32, { I8, I16, U8, U16, F32 }, OrderedF32)
-                }
-                DataType::Float64 => {
-                    protobuf_match_type!(value, ScalarImpl::Float64, { I8, I16, I32, U8, U16, U32, F32, F64}, OrderedF64)
-                }
-                DataType::Decimal => {
-                    protobuf_match_type!(value, ScalarImpl::Decimal, { I8, I16, I32, I64, U8, U16, U32, U64}, Decimal)
-                }
-                DataType::Varchar => {
-                    protobuf_match_type!(value, ScalarImpl::Utf8, { String }, String)
-                }
-                DataType::Date => {
-                    value.and_then(|v| match v {
-                        Value::String(b) => str_to_date(&b).ok(),
-                        Value::Option(Some(boxed_value)) => match *boxed_value {
-                            Value::String(b) => str_to_date(&b).ok(),
-                            _ => None,
-                        }
-                        _ => None,
-                    }).map(ScalarImpl::NaiveDate)
+        let row = columns
+            .iter()
+            .map(|column| {
+                if column.skip_parse {
+                    return None;
                 }
-                _ => unimplemented!(),
-            }
-        }).collect::<Vec<Datum>>();
+                // Use `remove` instead of `get` to take the ownership of the value
+                protobuf_parse_value(column, map.remove(&Value::String(column.name.clone())))
+            })
+            .collect::<Vec<Datum>>();
 
         Ok(Event {
             ops: vec![Op::Insert],
@@ -291,6 +316,7 @@ mod tests {
     use std::io::Write;
 
     use maplit::hashmap;
+    use risingwave_common::array::StructValue;
     use risingwave_common::catalog::ColumnId;
     use risingwave_common::error::Result;
     use risingwave_common::types::{DataType, ScalarImpl};
@@ -298,7 +324,7 @@ mod tests {
     use serde_value::Value;
     use tempfile::Builder;
 
-    use super::str_to_date;
+    use super::{protobuf_parse_value, str_to_date};
     use crate::{ProtobufParser, SourceColumnDesc, SourceParser};
 
     static PROTO_FILE_DATA: &str = r#"
@@ -428,36 +454,42 @@ mod tests {
                 data_type: DataType::Int32,
                 column_id: ColumnId::from(0),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "address".to_string(),
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(1),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "city".to_string(),
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(2),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "zipcode".to_string(),
                 data_type: DataType::Int64,
                 column_id: ColumnId::from(3),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "rate".to_string(),
                 data_type: DataType::Float32,
                 column_id: ColumnId::from(4),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "date".to_string(),
                 data_type: DataType::Date,
                 column_id: ColumnId::from(5),
                 skip_parse: false,
+                fields: vec![],
             },
         ];
 
@@ -501,4 +533,43 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parse_nested_message() {
+        let field = |name: &str, data_type: DataType| SourceColumnDesc {
+            name: name.to_string(),
+            data_type,
+            column_id: ColumnId::from(0),
+            skip_parse: false,
+            fields: vec![],
+        };
+        let column = SourceColumnDesc {
+            fields: vec![
+                field("country.address", DataType::Varchar),
+                field("country.zipcode", DataType::Varchar),
+            ],
+            ..field(
+                "country",
+                DataType::Struct {
+                    fields: vec![DataType::Varchar, DataType::Varchar].into(),
+                },
+            )
+        };
+        let value = Value::Option(Some(Box::new(Value::Map(
+            [(
+                Value::String("address".to_string()),
+                Value::Option(Some(Box::new(Value::String("a".to_string())))),
+            )]
+            .into_iter()
+            .collect(),
+        ))));
+        assert_eq!(
+            protobuf_parse_value(&column, Some(value)),
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                Some(ScalarImpl::Utf8("a".to_string())),
+                None
+            ])))
+        );
+        assert_eq!(protobuf_parse_value(&column, None), None);
+    }
 }
@@ -12,12 +12,14 @@
 
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 use core::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::ast::ObjectName;
+use crate::ast::{display_comma_separated, Ident, ObjectName};
 
 /// SQL data types
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -75,6 +77,10 @@ pub enum DataType {
     Custom(ObjectName),
     /// Arrays
     Array(Box<DataType>),
+    /// Structs e.g. STRUCT<a INT, b VARCHAR>
+    Struct(Vec<StructField>),
+    /// Jsonb
+    Jsonb,
 }
 
 impl fmt::Display for DataType {
@@ -117,11 +123,27 @@ impl fmt::Display for DataType {
             DataType::String => write!(f, "STRING"),
             DataType::Bytea => write!(f, "BYTEA"),
             DataType::Array(ty) => write!(f, "{}[]", ty),
+            DataType::Struct(fields) => write!(f, "STRUCT<{}>", display_comma_separated(fields)),
+            DataType::Jsonb => write!(f, "JSONB"),
             DataType::Custom(ty) => write!(f, "{}", ty),
         }
     }
 }
 
+/// A field of a struct type, e.g. `a INT` in `STRUCT<a INT>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StructField {
+    pub name: Ident,
+    pub data_type: DataType,
+}
+
+impl fmt::Display for StructField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)
+    }
+}
+
 fn format_type_with_optional_length(
     f: &mut fmt::Formatter,
     sql_type: &'static str,
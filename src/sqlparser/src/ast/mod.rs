@@ -30,7 +30,7 @@ use itertools::Itertools;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-pub use self::data_type::DataType;
+pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
     AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
     ReferentialAction, TableConstraint,
@@ -254,6 +254,11 @@ pub enum Expr {
         // ([BOTH | LEADING | TRAILING], <expr>)
         trim_where: Option<(TrimWhereField, Box<Expr>)>,
     },
+    /// `timestamp AT TIME ZONE 'zone'`
+    AtTimeZone {
+        timestamp: Box<Expr>,
+        time_zone: String,
+    },
     /// `expr COLLATE collation`
     Collate {
         expr: Box<Expr>,
@@ -304,6 +309,10 @@ pub enum Expr {
     /// The `ARRAY` expr. Alternative syntax for `ARRAY` is by utilizing curly braces, e.g. {1, 2,
     /// 3},
     Array(Vec<Expr>),
+    /// `ANY(<expr>)` on the right of a comparison, e.g. `a = ANY(ARRAY[1, 2])`
+    AnyOp(Box<Expr>),
+    /// `ALL(<expr>)` on the right of a comparison, e.g. `a <> ALL(ARRAY[1, 2])`
+    AllOp(Box<Expr>),
 }
 
 impl fmt::Display for Expr {
@@ -375,6 +384,15 @@ impl fmt::Display for Expr {
             Expr::Cast { expr, data_type } => write!(f, "CAST({} AS {})", expr, data_type),
             Expr::TryCast { expr, data_type } => write!(f, "TRY_CAST({} AS {})", expr, data_type),
             Expr::Extract { field, expr } => write!(f, "EXTRACT({} FROM {})", field, expr),
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => write!(
+                f,
+                "{} AT TIME ZONE '{}'",
+                timestamp,
+                value::escape_single_quote_string(time_zone)
+            ),
             Expr::Collate { expr, collation } => write!(f, "{} COLLATE {}", expr, collation),
             Expr::Nested(ast) => write!(f, "({})", ast),
             Expr::Value(v) => write!(f, "{}", v),
@@ -489,6 +507,8 @@ impl fmt::Display for Expr {
                     .as_slice()
                     .join(", ")
             ),
+            Expr::AnyOp(expr) => write!(f, "ANY({})", expr),
+            Expr::AllOp(expr) => write!(f, "ALL({})", expr),
         }
     }
 }
@@ -867,6 +887,8 @@ pub enum Statement {
         analyze: bool,
         // Display additional information regarding the plan.
         verbose: bool,
+        /// Show how the plan is split into stages for distributed execution.
+        distsql: bool,
         /// A SQL query that specifies what to explain
         statement: Box<Statement>,
     },
@@ -886,6 +908,7 @@ impl fmt::Display for Statement {
                 describe_alias,
                 verbose,
                 analyze,
+                distsql,
                 statement,
             } => {
                 if *describe_alias {
@@ -894,12 +917,27 @@ impl fmt::Display for Statement {
                     write!(f, "EXPLAIN ")?;
                 }
 
-                if *analyze {
-                    write!(f, "ANALYZE ")?;
-                }
+                if *distsql {
+                    // `DISTSQL` can only be given in the parenthesized option list.
+                    let options = [
+                        (*analyze, "ANALYZE"),
+                        (*verbose, "VERBOSE"),
+                        (true, "DISTSQL"),
+                    ];
+                    let options = options
+                        .iter()
+                        .filter(|(enabled, _)| *enabled)
+                        .map(|(_, name)| *name)
+                        .collect::<Vec<_>>();
+                    write!(f, "({}) ", options.join(", "))?;
+                } else {
+                    if *analyze {
+                        write!(f, "ANALYZE ")?;
+                    }
 
-                if *verbose {
-                    write!(f, "VERBOSE ")?;
+                    if *verbose {
+                        write!(f, "VERBOSE ")?;
+                    }
                 }
 
                 write!(f, "{}", statement)
@@ -1490,6 +1528,7 @@ pub enum ObjectType {
     MaterializedView,
     Index,
     Schema,
+    Database,
     Source,
     MaterializedSource,
 }
@@ -1502,6 +1541,7 @@ impl fmt::Display for ObjectType {
             ObjectType::MaterializedView => "MATERIALIZED VIEW",
             ObjectType::Index => "INDEX",
             ObjectType::Schema => "SCHEMA",
+            ObjectType::Database => "DATABASE",
             ObjectType::Source => "SOURCE",
             ObjectType::MaterializedSource => "MATERIALIZED SOURCE",
         })
@@ -1524,9 +1564,11 @@ impl ParseTo for ObjectType {
             ObjectType::Index
         } else if parser.parse_keyword(Keyword::SCHEMA) {
             ObjectType::Schema
+        } else if parser.parse_keyword(Keyword::DATABASE) {
+            ObjectType::Database
         } else {
             return parser.expected(
-                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, or SCHEMA after DROP",
+                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, SCHEMA, or DATABASE after DROP",
                 parser.peek_token(),
             );
         };
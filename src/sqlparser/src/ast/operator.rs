@@ -76,6 +76,8 @@ pub enum BinaryOperator {
     NotLike,
     ILike,
     NotILike,
+    SimilarTo,
+    NotSimilarTo,
     BitwiseOr,
     BitwiseAnd,
     BitwiseXor,
@@ -86,6 +88,17 @@ pub enum BinaryOperator {
     PGRegexIMatch,
     PGRegexNotMatch,
     PGRegexNotIMatch,
+    /// Get a JSON object field or array element, e.g. `data -> 'a'` (PostgreSQL-specific)
+    Arrow,
+    /// Get a JSON object field or array element as text, e.g. `data ->> 'a'`
+    /// (PostgreSQL-specific)
+    LongArrow,
+    /// Get a JSON value at the specified path, e.g. `data #> ARRAY['a', 'b']`
+    /// (PostgreSQL-specific)
+    HashArrow,
+    /// Whether the left JSON value contains the right one, e.g. `data @> '{"a": 1}'`
+    /// (PostgreSQL-specific)
+    AtArrow,
 }
 
 impl fmt::Display for BinaryOperator {
@@ -111,6 +124,8 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::NotLike => "NOT LIKE",
             BinaryOperator::ILike => "ILIKE",
             BinaryOperator::NotILike => "NOT ILIKE",
+            BinaryOperator::SimilarTo => "SIMILAR TO",
+            BinaryOperator::NotSimilarTo => "NOT SIMILAR TO",
             BinaryOperator::BitwiseOr => "|",
             BinaryOperator::BitwiseAnd => "&",
             BinaryOperator::BitwiseXor => "^",
@@ -121,6 +136,10 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::PGRegexIMatch => "~*",
             BinaryOperator::PGRegexNotMatch => "!~",
             BinaryOperator::PGRegexNotIMatch => "!~*",
+            BinaryOperator::Arrow => "->",
+            BinaryOperator::LongArrow => "->>",
+            BinaryOperator::HashArrow => "#>",
+            BinaryOperator::AtArrow => "@>",
         })
     }
 }
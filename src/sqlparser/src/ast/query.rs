@@ -318,6 +318,8 @@ pub enum TableFactor {
         /// and MSSQL. Note that deprecated MSSQL `FROM foo (NOLOCK)` syntax
         /// will also be parsed as `args`.
         args: Vec<FunctionArg>,
+        /// `FOR SYSTEM_TIME AS OF <expr>`, reads the table as of a point in time.
+        as_of: Option<Expr>,
     },
     Derived {
         lateral: bool,
@@ -341,11 +343,19 @@ pub enum TableFactor {
 impl fmt::Display for TableFactor {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            TableFactor::Table { name, alias, args } => {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                as_of,
+            } => {
                 write!(f, "{}", name)?;
                 if !args.is_empty() {
                     write!(f, "({})", display_comma_separated(args))?;
                 }
+                if let Some(as_of) = as_of {
+                    write!(f, " FOR SYSTEM_TIME AS OF {}", as_of)?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {}", alias)?;
                 }
@@ -20,9 +20,7 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use super::ObjectType;
-use crate::ast::{
-    display_comma_separated, ColumnDef, Ident, ObjectName, SqlOption, TableConstraint,
-};
+use crate::ast::{display_comma_separated, ColumnDef, ObjectName, SqlOption, TableConstraint};
 use crate::keywords::Keyword;
 use crate::parser::{Parser, ParserError};
 
@@ -86,18 +84,22 @@ pub enum SourceSchema {
     Protobuf(ProtobufSchema),
     // Keyword::PROTOBUF ProtobufSchema
     Json, // Keyword::JSON
+    // Keyword::JSON Keyword::RAW
+    JsonRaw,
 }
 
 impl ParseTo for SourceSchema {
     fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
-        let schema = if p.parse_keywords(&[Keyword::JSON]) {
+        let schema = if p.parse_keywords(&[Keyword::JSON, Keyword::RAW]) {
+            SourceSchema::JsonRaw
+        } else if p.parse_keywords(&[Keyword::JSON]) {
             SourceSchema::Json
         } else if p.parse_keywords(&[Keyword::PROTOBUF]) {
             impl_parse_to!(protobuf_schema: ProtobufSchema, p);
             SourceSchema::Protobuf(protobuf_schema)
         } else {
             return Err(ParserError::ParserError(
-                "expected JSON | PROTOBUF after ROW FORMAT".to_string(),
+                "expected JSON | JSON RAW | PROTOBUF after ROW FORMAT".to_string(),
             ));
         };
         Ok(schema)
@@ -109,6 +111,7 @@ impl fmt::Display for SourceSchema {
         match self {
             SourceSchema::Protobuf(protobuf_schema) => write!(f, "PROTOBUF {}", protobuf_schema),
             SourceSchema::Json => write!(f, "JSON"),
+            SourceSchema::JsonRaw => write!(f, "JSON RAW"),
         }
     }
 }
@@ -302,7 +305,7 @@ pub struct DropStatement {
     /// An optional `IF EXISTS` clause. (Non-standard.)
     pub if_exists: bool,
     /// Object to drop.
-    pub name: Ident,
+    pub name: ObjectName,
     /// Whether `CASCADE` was specified. This will be `false` when
     /// `RESTRICT` or no drop behavior at all was specified.
     pub drop_mode: AstOption<DropMode>,
@@ -318,7 +321,7 @@ impl ParseTo for DropStatement {
     fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
         impl_parse_to!(object_type: ObjectType, p);
         impl_parse_to!(if_exists => [Keyword::IF, Keyword::EXISTS], p);
-        impl_parse_to!(name: Ident, p);
+        impl_parse_to!(name: ObjectName, p);
         impl_parse_to!(drop_mode: AstOption<DropMode>, p);
         Ok(Self {
             object_type,
@@ -178,6 +178,7 @@ define_keywords!(
     DIRECTORY,
     DISCONNECT,
     DISTINCT,
+    DISTSQL,
     DOUBLE,
     DROP,
     DYNAMIC,
@@ -251,6 +252,7 @@ define_keywords!(
     ISOLATION,
     JOIN,
     JSON,
+    JSONB,
     KEY,
     LANGUAGE,
     LARGE,
@@ -341,6 +343,7 @@ define_keywords!(
     PURGE,
     RANGE,
     RANK,
+    RAW,
     RCFILE,
     READ,
     READS,
@@ -417,6 +420,7 @@ define_keywords!(
     STDIN,
     STORED,
     STRING,
+    STRUCT,
     SUBMULTISET,
     SUBSTRING,
     SUBSTRING_REGEX,
@@ -384,6 +384,9 @@ impl Parser {
                 Keyword::TRY_CAST => self.parse_try_cast_expr(),
                 Keyword::EXISTS => self.parse_exists_expr(),
                 Keyword::EXTRACT => self.parse_extract_expr(),
+                Keyword::CURRENT_TIMESTAMP | Keyword::CURRENT_DATE => {
+                    self.parse_time_functions(ObjectName(vec![w.to_ident()]))
+                }
                 Keyword::SUBSTRING => self.parse_substring_expr(),
                 Keyword::TRIM => self.parse_trim_expr(),
                 Keyword::INTERVAL => self.parse_literal_interval(),
@@ -552,6 +555,22 @@ impl Parser {
         Ok(idents)
     }
 
+    /// Parses `CURRENT_TIMESTAMP` and `CURRENT_DATE`, which are functions called without
+    /// parentheses.
+    pub fn parse_time_functions(&mut self, name: ObjectName) -> Result<Expr, ParserError> {
+        let args = if self.consume_token(&Token::LParen) {
+            self.parse_optional_args()?
+        } else {
+            vec![]
+        };
+        Ok(Expr::Function(Function {
+            name,
+            args,
+            over: None,
+            distinct: false,
+        }))
+    }
+
     pub fn parse_function(&mut self, name: ObjectName) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
         let distinct = self.parse_all_or_distinct()?;
@@ -958,6 +977,10 @@ impl Parser {
             Token::ShiftLeft => Some(BinaryOperator::PGBitwiseShiftLeft),
             Token::ShiftRight => Some(BinaryOperator::PGBitwiseShiftRight),
             Token::Sharp => Some(BinaryOperator::PGBitwiseXor),
+            Token::Arrow => Some(BinaryOperator::Arrow),
+            Token::LongArrow => Some(BinaryOperator::LongArrow),
+            Token::HashArrow => Some(BinaryOperator::HashArrow),
+            Token::AtArrow => Some(BinaryOperator::AtArrow),
             Token::Tilde => Some(BinaryOperator::PGRegexMatch),
             Token::TildeAsterisk => Some(BinaryOperator::PGRegexIMatch),
             Token::ExclamationMarkTilde => Some(BinaryOperator::PGRegexNotMatch),
@@ -967,11 +990,17 @@ impl Parser {
                 Keyword::OR => Some(BinaryOperator::Or),
                 Keyword::LIKE => Some(BinaryOperator::Like),
                 Keyword::ILIKE => Some(BinaryOperator::ILike),
+                Keyword::SIMILAR => {
+                    self.expect_keyword(Keyword::TO)?;
+                    Some(BinaryOperator::SimilarTo)
+                }
                 Keyword::NOT => {
                     if self.parse_keyword(Keyword::LIKE) {
                         Some(BinaryOperator::NotLike)
                     } else if self.parse_keyword(Keyword::ILIKE) {
                         Some(BinaryOperator::NotILike)
+                    } else if self.parse_keywords(&[Keyword::SIMILAR, Keyword::TO]) {
+                        Some(BinaryOperator::NotSimilarTo)
                     } else {
                         None
                     }
@@ -983,10 +1012,26 @@ impl Parser {
         };
 
         if let Some(op) = regular_binary_operator {
+            let right = match (self.peek_token(), self.peek_nth_token(1)) {
+                (Token::Word(w), Token::LParen)
+                    if matches!(w.keyword, Keyword::ANY | Keyword::SOME | Keyword::ALL) =>
+                {
+                    self.next_token();
+                    self.expect_token(&Token::LParen)?;
+                    let inner = Box::new(self.parse_expr()?);
+                    self.expect_token(&Token::RParen)?;
+                    if w.keyword == Keyword::ALL {
+                        Expr::AllOp(inner)
+                    } else {
+                        Expr::AnyOp(inner)
+                    }
+                }
+                _ => self.parse_subexpr(precedence)?,
+            };
             Ok(Expr::BinaryOp {
                 left: Box::new(expr),
                 op,
-                right: Box::new(self.parse_subexpr(precedence)?),
+                right: Box::new(right),
             })
         } else if let Token::Word(w) = &tok {
             match w.keyword {
@@ -1017,6 +1062,16 @@ impl Parser {
                         )
                     }
                 }
+                Keyword::AT => {
+                    self.expect_keywords(&[Keyword::TIME, Keyword::ZONE])?;
+                    match self.next_token() {
+                        Token::SingleQuotedString(time_zone) => Ok(Expr::AtTimeZone {
+                            timestamp: Box::new(expr),
+                            time_zone,
+                        }),
+                        unexpected => self.expected("a time zone string", unexpected),
+                    }
+                }
                 Keyword::NOT | Keyword::IN | Keyword::BETWEEN => {
                     self.prev_token();
                     let negated = self.parse_keyword(Keyword::NOT);
@@ -1058,13 +1113,10 @@ impl Parser {
             debug!("Tok: {}", tok);
             key_parts.push(key);
         }
-        match expr {
-            e @ Expr::Identifier(_) | e @ Expr::CompoundIdentifier(_) => Ok(Expr::MapAccess {
-                column: Box::new(e),
-                keys: key_parts,
-            }),
-            _ => Ok(expr),
-        }
+        Ok(Expr::MapAccess {
+            column: Box::new(expr),
+            keys: key_parts,
+        })
     }
 
     /// Parses the parens following the `[ NOT ] IN` operator
@@ -1129,13 +1181,26 @@ impl Parser {
                 Token::Word(w) if w.keyword == Keyword::BETWEEN => Ok(Self::BETWEEN_PREC),
                 Token::Word(w) if w.keyword == Keyword::LIKE => Ok(Self::BETWEEN_PREC),
                 Token::Word(w) if w.keyword == Keyword::ILIKE => Ok(Self::BETWEEN_PREC),
+                Token::Word(w) if w.keyword == Keyword::SIMILAR => Ok(Self::BETWEEN_PREC),
                 _ => Ok(0),
             },
             Token::Word(w) if w.keyword == Keyword::IS => Ok(17),
+            // `AT TIME ZONE` binds tighter than arithmetic operators as in postgres.
+            Token::Word(w) if w.keyword == Keyword::AT => {
+                match (self.peek_nth_token(1), self.peek_nth_token(2)) {
+                    (Token::Word(w1), Token::Word(w2))
+                        if w1.keyword == Keyword::TIME && w2.keyword == Keyword::ZONE =>
+                    {
+                        Ok(45)
+                    }
+                    _ => Ok(0),
+                }
+            }
             Token::Word(w) if w.keyword == Keyword::IN => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::BETWEEN => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::LIKE => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::ILIKE => Ok(Self::BETWEEN_PREC),
+            Token::Word(w) if w.keyword == Keyword::SIMILAR => Ok(Self::BETWEEN_PREC),
             Token::Eq
             | Token::Lt
             | Token::LtEq
@@ -1151,11 +1216,12 @@ impl Parser {
             Token::Pipe => Ok(21),
             Token::Caret | Token::Sharp | Token::ShiftRight | Token::ShiftLeft => Ok(22),
             Token::Ampersand => Ok(23),
+            Token::Arrow | Token::LongArrow | Token::HashArrow | Token::AtArrow => Ok(25),
             Token::Plus | Token::Minus => Ok(Self::PLUS_MINUS_PREC),
             Token::Mul | Token::Div | Token::Mod | Token::StringConcat => Ok(40),
             Token::DoubleColon => Ok(50),
             Token::ExclamationMark => Ok(50),
-            Token::LBracket => Ok(10),
+            Token::LBracket => Ok(50),
             _ => Ok(0),
         }
     }
@@ -1987,6 +2053,8 @@ impl Parser {
                     }
                 }
                 Keyword::BYTEA => Ok(DataType::Bytea),
+                Keyword::JSONB => Ok(DataType::Jsonb),
+                Keyword::STRUCT => Ok(DataType::Struct(self.parse_struct_data_type()?)),
                 Keyword::NUMERIC | Keyword::DECIMAL | Keyword::DEC => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     Ok(DataType::Decimal(precision, scale))
@@ -2001,6 +2069,28 @@ impl Parser {
         }
     }
 
+    /// Parse the fields of a struct type after the `STRUCT` keyword, e.g. `<a INT, b VARCHAR>`
+    pub fn parse_struct_data_type(&mut self) -> Result<Vec<StructField>, ParserError> {
+        self.expect_token(&Token::Lt)?;
+        let fields = self.parse_comma_separated(|parser| {
+            Ok(StructField {
+                name: parser.parse_identifier()?,
+                data_type: parser.parse_data_type()?,
+            })
+        })?;
+        match self.next_token() {
+            Token::Gt => Ok(fields),
+            // `>>` also closes the enclosing struct, e.g. `STRUCT<a STRUCT<b INT>>`, so we
+            // leave a `>` for it.
+            Token::ShiftRight => {
+                self.index -= 1;
+                self.tokens[self.index] = Token::Gt;
+                Ok(fields)
+            }
+            unexpected => self.expected(">", unexpected),
+        }
+    }
+
     /// Parse `AS identifier` (or simply `identifier` if it's not a reserved keyword)
     /// Some examples with aliases: `SELECT 1 foo`, `SELECT COUNT(*) AS cnt`,
     /// `SELECT ... FROM t1 foo, t2 bar`, `SELECT ... FROM (...) AS bar`
@@ -2195,14 +2285,42 @@ impl Parser {
     }
 
     pub fn parse_explain(&mut self, describe_alias: bool) -> Result<Statement, ParserError> {
-        let analyze = self.parse_keyword(Keyword::ANALYZE);
-        let verbose = self.parse_keyword(Keyword::VERBOSE);
+        let mut analyze = false;
+        let mut verbose = false;
+        let mut distsql = false;
+
+        // `EXPLAIN (option, ...)`, to be told apart from a parenthesized query.
+        let explain_options = [Keyword::ANALYZE, Keyword::VERBOSE, Keyword::DISTSQL];
+        let has_options = self.peek_token() == Token::LParen
+            && matches!(
+                self.peek_nth_token(1),
+                Token::Word(w) if explain_options.contains(&w.keyword)
+            );
+        if has_options {
+            self.expect_token(&Token::LParen)?;
+            loop {
+                match self.parse_one_of_keywords(&explain_options) {
+                    Some(Keyword::ANALYZE) => analyze = true,
+                    Some(Keyword::VERBOSE) => verbose = true,
+                    Some(Keyword::DISTSQL) => distsql = true,
+                    _ => return self.expected("ANALYZE, VERBOSE or DISTSQL", self.peek_token()),
+                }
+                if !self.consume_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect_token(&Token::RParen)?;
+        } else {
+            analyze = self.parse_keyword(Keyword::ANALYZE);
+            verbose = self.parse_keyword(Keyword::VERBOSE);
+        }
 
         let statement = self.parse_statement()?;
         Ok(Statement::Explain {
             describe_alias,
             analyze,
             verbose,
+            distsql,
             statement: Box::new(statement),
         })
     }
@@ -2445,18 +2563,30 @@ impl Parser {
         })
     }
 
+    fn parse_set_variable_value(&mut self) -> Result<SetVariableValue, ParserError> {
+        let token = self.peek_token();
+        match (self.parse_value(), token) {
+            (Ok(value), _) => Ok(SetVariableValue::Literal(value)),
+            (Err(_), Token::Word(ident)) => Ok(SetVariableValue::Ident(ident.to_ident())),
+            (Err(_), unexpected) => self.expected("variable value", unexpected),
+        }
+    }
+
     pub fn parse_set(&mut self) -> Result<Statement, ParserError> {
         let modifier = self.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
+        // `SET TIME ZONE <value>` is an alias of `SET timezone = <value>`.
+        if self.parse_keywords(&[Keyword::TIME, Keyword::ZONE]) {
+            return Ok(Statement::SetVariable {
+                local: modifier == Some(Keyword::LOCAL),
+                variable: Ident::new("timezone"),
+                value: vec![self.parse_set_variable_value()?],
+            });
+        }
         let variable = self.parse_identifier()?;
         if self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::TO) {
             let mut values = vec![];
             loop {
-                let token = self.peek_token();
-                let value = match (self.parse_value(), token) {
-                    (Ok(value), _) => SetVariableValue::Literal(value),
-                    (Err(_), Token::Word(ident)) => SetVariableValue::Ident(ident.to_ident()),
-                    (Err(_), unexpected) => self.expected("variable value", unexpected)?,
-                };
+                let value = self.parse_set_variable_value()?;
                 values.push(value);
                 if self.consume_token(&Token::Comma) {
                     continue;
@@ -2684,8 +2814,23 @@ impl Parser {
             } else {
                 vec![]
             };
+            let as_of = if self.parse_keywords(&[
+                Keyword::FOR,
+                Keyword::SYSTEM_TIME,
+                Keyword::AS,
+                Keyword::OF,
+            ]) {
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
             let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
-            Ok(TableFactor::Table { name, alias, args })
+            Ok(TableFactor::Table {
+                name,
+                alias,
+                args,
+                as_of,
+            })
         }
     }
 
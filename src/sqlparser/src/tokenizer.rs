@@ -138,6 +138,14 @@ pub enum Token {
     PGSquareRoot,
     /// `||/` , a cube root math operator in PostgreSQL
     PGCubeRoot,
+    /// `->`, get a JSON object field or array element in PostgreSQL
+    Arrow,
+    /// `->>`, get a JSON object field or array element as text in PostgreSQL
+    LongArrow,
+    /// `#>`, get a JSON value at the specified path in PostgreSQL
+    HashArrow,
+    /// `@>`, a JSON containment operator in PostgreSQL
+    AtArrow,
 }
 
 impl fmt::Display for Token {
@@ -193,6 +201,10 @@ impl fmt::Display for Token {
             Token::ShiftRight => f.write_str(">>"),
             Token::PGSquareRoot => f.write_str("|/"),
             Token::PGCubeRoot => f.write_str("||/"),
+            Token::Arrow => f.write_str("->"),
+            Token::LongArrow => f.write_str("->>"),
+            Token::HashArrow => f.write_str("#>"),
+            Token::AtArrow => f.write_str("@>"),
         }
     }
 }
@@ -475,6 +487,13 @@ impl<'a> Tokenizer<'a> {
                                 comment,
                             })))
                         }
+                        Some('>') => {
+                            chars.next(); // consume the '>'
+                            match chars.peek() {
+                                Some('>') => self.consume_and_return(chars, Token::LongArrow),
+                                _ => Ok(Some(Token::Arrow)),
+                            }
+                        }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
                     }
@@ -576,8 +595,20 @@ impl<'a> Tokenizer<'a> {
                         _ => Ok(Some(Token::Tilde)),
                     }
                 }
-                '#' => self.consume_and_return(chars, Token::Sharp),
-                '@' => self.consume_and_return(chars, Token::AtSign),
+                '#' => {
+                    chars.next(); // consume the '#'
+                    match chars.peek() {
+                        Some('>') => self.consume_and_return(chars, Token::HashArrow),
+                        _ => Ok(Some(Token::Sharp)),
+                    }
+                }
+                '@' => {
+                    chars.next(); // consume the '@'
+                    match chars.peek() {
+                        Some('>') => self.consume_and_return(chars, Token::AtArrow),
+                        _ => Ok(Some(Token::AtSign)),
+                    }
+                }
                 other => self.consume_and_return(chars, Token::Char(other)),
             },
             None => Ok(None),
@@ -1221,6 +1252,39 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_pg_json_operators() {
+        let sql = "col->'a'->>0, col #> p, col @> j, -1";
+        let mut tokenizer = Tokenizer::new(sql);
+        let tokens = tokenizer.tokenize().unwrap();
+        let expected = vec![
+            Token::make_word("col", None),
+            Token::Arrow,
+            Token::SingleQuotedString("a".into()),
+            Token::LongArrow,
+            Token::Number("0".into(), false),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("col", None),
+            Token::Whitespace(Whitespace::Space),
+            Token::HashArrow,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("p", None),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("col", None),
+            Token::Whitespace(Whitespace::Space),
+            Token::AtArrow,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("j", None),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Minus,
+            Token::Number("1".into(), false),
+        ];
+        compare(expected, tokens);
+    }
+
     fn compare(expected: Vec<Token>, actual: Vec<Token>) {
         // println!("------------------------------");
         // println!("tokens   = {:?}", actual);
@@ -155,6 +155,7 @@ fn parse_update_with_table_alias() {
                             columns: vec![]
                         }),
                         args: vec![],
+                        as_of: None,
                     },
                     joins: vec![]
                 },
@@ -1432,6 +1433,20 @@ fn parse_drop_schema() {
     }
 }
 
+#[test]
+fn parse_drop_database() {
+    let sql = "DROP DATABASE IF EXISTS X";
+
+    match verified_stmt(sql) {
+        Statement::Drop(stmt) => {
+            assert!(stmt.if_exists);
+            assert_eq!(stmt.object_type, ObjectType::Database);
+            assert_eq!(ObjectName(vec![Ident::new("X")]), stmt.name);
+        }
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn parse_create_table_as() {
     let sql = "CREATE TABLE t AS SELECT * FROM a";
@@ -1744,6 +1759,7 @@ fn run_explain_analyze(query: &str, expected_verbose: bool, expected_analyze: bo
             describe_alias: _,
             analyze,
             verbose,
+            distsql: _,
             statement,
         } => {
             assert_eq!(verbose, expected_verbose);
@@ -1766,6 +1782,36 @@ fn parse_explain_analyze_with_simple_select() {
     );
 }
 
+#[test]
+fn parse_explain_with_options() {
+    match verified_stmt("EXPLAIN (ANALYZE, DISTSQL) SELECT sqrt(id) FROM foo") {
+        Statement::Explain {
+            analyze,
+            verbose,
+            distsql,
+            statement,
+            ..
+        } => {
+            assert!(analyze);
+            assert!(!verbose);
+            assert!(distsql);
+            assert_eq!("SELECT sqrt(id) FROM foo", statement.to_string());
+        }
+        _ => panic!("Unexpected Statement, must be Explain"),
+    }
+    one_statement_parses_to(
+        "EXPLAIN (VERBOSE, ANALYZE) SELECT sqrt(id) FROM foo",
+        "EXPLAIN ANALYZE VERBOSE SELECT sqrt(id) FROM foo",
+    );
+    one_statement_parses_to("EXPLAIN (SELECT 1)", "EXPLAIN (SELECT 1)");
+
+    let res = parse_sql_statements("EXPLAIN (ANALYZE, COSTS) SELECT 1");
+    assert_eq!(
+        ParserError::ParserError("Expected ANALYZE, VERBOSE or DISTSQL, found: COSTS".to_string()),
+        res.unwrap_err()
+    );
+}
+
 #[test]
 fn parse_named_argument_function() {
     let sql = "SELECT FUN(a => '1', b => '2') FROM foo";
@@ -2045,7 +2091,9 @@ fn parse_delimited_identifiers() {
     );
     // check FROM
     match only(select.from).relation {
-        TableFactor::Table { name, alias, args } => {
+        TableFactor::Table {
+            name, alias, args, ..
+        } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
             assert!(args.is_empty());
@@ -2083,6 +2131,26 @@ fn parse_delimited_identifiers() {
     // TODO verified_stmt(r#"UPDATE foo SET "bar" = 5"#);
 }
 
+#[test]
+fn parse_table_for_system_time_as_of() {
+    let select = verified_only_select(
+        "SELECT * FROM t FOR SYSTEM_TIME AS OF '2022-05-01 00:00:00' AS t1 JOIN t2 ON t1.a = t2.a",
+    );
+    let from = only(select.from);
+    assert_eq!(
+        from.relation,
+        TableFactor::Table {
+            name: ObjectName(vec![Ident::new("t")]),
+            alias: table_alias("t1"),
+            args: vec![],
+            as_of: Some(Expr::Value(Value::SingleQuotedString(
+                "2022-05-01 00:00:00".to_string()
+            ))),
+        }
+    );
+    assert_eq!(from.joins[0].relation, table("t2"));
+}
+
 #[test]
 fn parse_parens() {
     use self::BinaryOperator::*;
@@ -2171,6 +2239,7 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t1".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![],
             },
@@ -2179,6 +2248,7 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![],
             }
@@ -2195,12 +2265,14 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t1a".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t1b".into()]),
                         alias: None,
                         args: vec![],
+                        as_of: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2210,12 +2282,14 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t2a".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t2b".into()]),
                         alias: None,
                         args: vec![],
+                        as_of: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2235,6 +2309,7 @@ fn parse_cross_join() {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
                 args: vec![],
+                as_of: None,
             },
             join_operator: JoinOperator::CrossJoin
         },
@@ -2254,6 +2329,7 @@ fn parse_joins_on() {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
                 args: vec![],
+                as_of: None,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("c1".into())),
@@ -2306,6 +2382,7 @@ fn parse_joins_using() {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
                 args: vec![],
+                as_of: None,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
         }
@@ -2350,6 +2427,7 @@ fn parse_natural_join() {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
                 args: vec![],
+                as_of: None,
             },
             join_operator: f(JoinConstraint::Natural),
         }
@@ -2588,6 +2666,7 @@ fn parse_derived_tables() {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
             }],
@@ -2904,7 +2983,7 @@ fn parse_drop_table() {
         Statement::Drop(stmt) => {
             assert!(!stmt.if_exists);
             assert_eq!(ObjectType::Table, stmt.object_type);
-            assert_eq!(Ident::new("foo"), stmt.name);
+            assert_eq!(ObjectName(vec![Ident::new("foo")]), stmt.name);
             assert_eq!(stmt.drop_mode, AstOption::None);
         }
         _ => unreachable!(),
@@ -2915,12 +2994,23 @@ fn parse_drop_table() {
         Statement::Drop(stmt) => {
             assert!(stmt.if_exists);
             assert_eq!(ObjectType::Table, stmt.object_type);
-            assert_eq!(Ident::new("foo"), stmt.name);
+            assert_eq!(ObjectName(vec![Ident::new("foo")]), stmt.name);
             assert_eq!(stmt.drop_mode, AstOption::Some(DropMode::Cascade));
         }
         _ => unreachable!(),
     };
 
+    let sql = "DROP TABLE myschema.foo";
+    match verified_stmt(sql) {
+        Statement::Drop(stmt) => {
+            assert_eq!(
+                ObjectName(vec![Ident::new("myschema"), Ident::new("foo")]),
+                stmt.name
+            );
+        }
+        _ => unreachable!(),
+    };
+
     let sql = "DROP TABLE";
     assert_eq!(
         ParserError::ParserError("Expected identifier, found: EOF".to_string()),
@@ -2939,7 +3029,7 @@ fn parse_drop_view() {
     let sql = "DROP VIEW myview";
     match verified_stmt(sql) {
         Statement::Drop(stmt) => {
-            assert_eq!(Ident::new("myview"), stmt.name);
+            assert_eq!(ObjectName(vec![Ident::new("myview")]), stmt.name);
             assert_eq!(ObjectType::View, stmt.object_type);
         }
         _ => unreachable!(),
@@ -2951,7 +3041,7 @@ fn parse_materialized_drop_view() {
     let sql = "DROP MATERIALIZED VIEW mymview";
     match verified_stmt(sql) {
         Statement::Drop(stmt) => {
-            assert_eq!(Ident::new("mymview"), stmt.name);
+            assert_eq!(ObjectName(vec![Ident::new("mymview")]), stmt.name);
             assert_eq!(ObjectType::MaterializedView, stmt.object_type);
         }
         _ => unreachable!(),
@@ -389,6 +389,43 @@ PHP	₱ USD $
     // assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_at_time_zone() {
+    let sql = "SELECT ts AT TIME ZONE 'UTC' + INTERVAL '1' DAY";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &Expr::BinaryOp {
+            left: Box::new(Expr::AtTimeZone {
+                timestamp: Box::new(Expr::Identifier(Ident::new("ts"))),
+                time_zone: "UTC".to_string(),
+            }),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::Value(Value::Interval {
+                value: "1".to_string(),
+                leading_field: Some(DateTimeField::Day),
+                leading_precision: None,
+                last_field: None,
+                fractional_seconds_precision: None,
+            })),
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+
+    assert_eq!(
+        parse_sql_statements("SELECT ts AT TIME ZONE tz"),
+        Err(ParserError::ParserError(
+            "Expected a time zone string, found: tz".to_string()
+        )),
+    );
+}
+
+#[test]
+fn parse_current_timestamp() {
+    one_statement_parses_to("SELECT CURRENT_TIMESTAMP", "SELECT CURRENT_TIMESTAMP()");
+    one_statement_parses_to("SELECT CURRENT_DATE", "SELECT CURRENT_DATE()");
+    verified_stmt("SELECT CURRENT_TIMESTAMP()");
+}
+
 #[test]
 fn parse_set() {
     let stmt = verified_stmt("SET a = b");
@@ -445,6 +482,11 @@ fn parse_set() {
 
     one_statement_parses_to("SET a TO b", "SET a = b");
     one_statement_parses_to("SET SESSION a = b", "SET a = b");
+    one_statement_parses_to(
+        "SET TIME ZONE 'Asia/Shanghai'",
+        "SET timezone = 'Asia/Shanghai'",
+    );
+    one_statement_parses_to("SET TIME ZONE UTC", "SET timezone = UTC");
 
     assert_eq!(
         parse_sql_statements("SET"),
@@ -743,6 +785,27 @@ fn parse_map_access_expr() {
     );
 }
 
+#[test]
+fn parse_array_subscript_and_any() {
+    one_statement_parses_to(
+        "SELECT (ARRAY[1, 2])[1] = ANY(ARRAY[1, 3]), a <> ALL(b), a = SOME(b)",
+        "SELECT (ARRAY[1, 2])[1] = ANY(ARRAY[1, 3]), a <> ALL(b), a = ANY(b)",
+    );
+
+    let select = verified_only_select("SELECT a = b[1] FROM foos");
+    assert_eq!(
+        &Expr::BinaryOp {
+            left: Box::new(Identifier(Ident::new("a"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(MapAccess {
+                column: Box::new(Identifier(Ident::new("b"))),
+                keys: vec![Expr::Value(Value::Number("1".to_string(), false))]
+            }),
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+}
+
 #[test]
 fn test_transaction_statement() {
     let statement = verified_stmt("SET TRANSACTION SNAPSHOT '000003A1-1'");
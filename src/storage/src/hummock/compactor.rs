@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -34,9 +36,10 @@ use tokio::task::JoinHandle;
 use super::iterator::{BoxedHummockIterator, ConcatIterator, HummockIterator, MergeIterator};
 use super::multi_builder::CapacitySplitTableBuilder;
 use super::shared_buffer::shared_buffer_batch::SharedBufferBatch;
-use super::sstable_store::SstableStoreRef;
+use super::sstable_store::{SstableStore, SstableStoreRef, SstableWriter};
 use super::{
-    HummockError, HummockResult, HummockStorage, SSTableBuilder, SSTableIterator, Sstable,
+    CachePolicy, HummockError, HummockResult, HummockStorage, SSTableBuilder, SSTableIterator,
+    Sstable,
 };
 use crate::hummock::vacuum::Vacuum;
 use crate::monitor::StateStoreMetrics;
@@ -251,6 +254,7 @@ impl Compactor {
                         right: sst.meta.largest_key.clone(),
                         inf: false,
                     }),
+                    file_size: sst.meta.estimated_size as u64,
                 }));
         }
 
@@ -293,51 +297,75 @@ impl Compactor {
             Ok((table_id, builder))
         });
 
-        // Monitor time cost building shared buffer to SSTs.
-        let build_l0_sst_timer = if self.context.is_share_buffer_compact {
-            Some(self.context.stats.write_build_l0_sst_duration.start_timer())
-        } else {
-            None
-        };
-        Compactor::compact_and_build_sst(
-            &mut builder,
-            kr,
-            iter,
-            !self.compact_task.is_target_ultimate_and_leveling,
-            self.compact_task.watermark,
-        )
-        .await?;
-        if let Some(timer) = build_l0_sst_timer {
-            timer.observe_duration();
-        }
-
-        // Seal.
-        builder.seal_current();
-
-        let mut ssts: Vec<Sstable> = Vec::new();
-        ssts.reserve(builder.len());
-        // TODO: decide upload concurrency
-        for (table_id, data, meta) in builder.finish() {
-            let sst = Sstable { id: table_id, meta };
-            let len = self
-                .context
-                .sstable_store
-                .put(&sst, data, super::CachePolicy::Fill)
-                .await?;
-
-            if self.context.is_share_buffer_compact {
-                self.context
-                    .stats
-                    .shared_buffer_to_sstable_size
-                    .observe(len as _);
+        // Blocks are uploaded as soon as they are built, so the output SSTs are never fully
+        // buffered in memory.
+        let sstable_store = &self.context.sstable_store;
+        let mut writers = HashMap::new();
+        let result = async {
+            // Monitor time cost building shared buffer to SSTs.
+            let build_l0_sst_timer = if self.context.is_share_buffer_compact {
+                Some(self.context.stats.write_build_l0_sst_duration.start_timer())
             } else {
-                self.context.stats.compaction_upload_sst_counts.inc();
+                None
+            };
+            Compactor::compact_and_build_sst(
+                &mut builder,
+                &mut writers,
+                sstable_store,
+                kr,
+                iter,
+                !self.compact_task.is_target_ultimate_and_leveling,
+                self.compact_task.watermark,
+            )
+            .await?;
+            if let Some(timer) = build_l0_sst_timer {
+                timer.observe_duration();
             }
 
-            ssts.push(sst);
-        }
+            // Seal.
+            builder.seal_current();
+
+            let mut ssts: Vec<Sstable> = Vec::new();
+            ssts.reserve(builder.len());
+            // TODO: decide upload concurrency
+            for (table_id, data, meta) in builder.finish() {
+                let writer = match writers.remove(&table_id) {
+                    Some(writer) => writer,
+                    None => {
+                        sstable_store
+                            .create_sst_writer(table_id, CachePolicy::Fill)
+                            .await?
+                    }
+                };
+                let sst = Sstable { id: table_id, meta };
+                let len = sstable_store.finish_sst_writer(writer, &sst, data).await?;
+
+                if self.context.is_share_buffer_compact {
+                    self.context
+                        .stats
+                        .shared_buffer_to_sstable_size
+                        .observe(len as _);
+                } else {
+                    self.context.stats.compaction_upload_sst_counts.inc();
+                }
 
-        Ok((split_index, ssts))
+                ssts.push(sst);
+            }
+            Ok(ssts)
+        }
+        .await;
+
+        match result {
+            Ok(ssts) => Ok((split_index, ssts)),
+            Err(e) => {
+                for (table_id, writer) in writers {
+                    if let Err(e) = sstable_store.abort_sst_writer(writer).await {
+                        tracing::warn!("Failed to abort upload of SST {}: {}", table_id, e);
+                    }
+                }
+                Err(e)
+            }
+        }
     }
 
     /// Build the merge iterator based on the given input ssts.
@@ -506,6 +534,8 @@ impl Compactor {
 
     async fn compact_and_build_sst<B, F>(
         sst_builder: &mut CapacitySplitTableBuilder<B>,
+        sst_writers: &mut HashMap<u64, SstableWriter>,
+        sstable_store: &SstableStore,
         kr: KeyRange,
         mut iter: MergeIterator<'_>,
         has_user_key_overlap: bool,
@@ -567,6 +597,17 @@ impl Compactor {
             sst_builder
                 .add_full_key(FullKey::from_slice(iter_key), iter.value(), is_new_user_key)
                 .await?;
+            if let Some((table_id, blocks)) = sst_builder.take_built_blocks() {
+                let writer = match sst_writers.entry(table_id) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(
+                        sstable_store
+                            .create_sst_writer(table_id, CachePolicy::Fill)
+                            .await?,
+                    ),
+                };
+                sstable_store.write_blocks(writer, blocks).await?;
+            }
 
             iter.next().await?;
         }
@@ -57,6 +57,10 @@ pub struct SSTableBuilder {
     options: SSTableBuilderOptions,
     /// Write buffer.
     buf: BytesMut,
+    /// Length of data taken out of `buf` by `take_built_blocks`.
+    taken_len: usize,
+    /// Number of blocks taken out by `take_built_blocks`.
+    taken_block_count: usize,
     /// Current block builder.
     block_builder: Option<BlockBuilder>,
     /// Block metadata vec.
@@ -72,7 +76,9 @@ impl SSTableBuilder {
     pub fn new(options: SSTableBuilderOptions) -> Self {
         Self {
             options: options.clone(),
-            buf: BytesMut::with_capacity(options.capacity),
+            buf: BytesMut::with_capacity(options.block_capacity),
+            taken_len: 0,
+            taken_block_count: 0,
             block_builder: None,
             block_metas: Vec::with_capacity(options.capacity / options.block_capacity + 1),
             user_key_hashes: Vec::with_capacity(options.capacity / DEFAULT_ENTRY_SIZE + 1),
@@ -87,12 +93,12 @@ impl SSTableBuilder {
         if self.block_builder.is_none() {
             self.last_full_key.clear();
             self.block_builder = Some(BlockBuilder::new(BlockBuilderOptions {
-                capacity: self.options.capacity,
+                capacity: self.options.block_capacity,
                 restart_interval: self.options.restart_interval,
                 compression_algorithm: self.options.compression_algorithm,
             }));
             self.block_metas.push(BlockMeta {
-                offset: self.buf.len() as u32,
+                offset: self.data_len() as u32,
                 len: 0,
                 smallest_key: vec![],
             })
@@ -121,6 +127,26 @@ impl SSTableBuilder {
         self.key_count += 1;
     }
 
+    /// Takes out the blocks that have been built but not taken yet, so that they can be uploaded
+    /// before the whole sst is finished.
+    pub fn take_built_blocks(&mut self) -> Vec<Bytes> {
+        if self.buf.is_empty() {
+            return vec![];
+        }
+        let built_block_count = self.block_metas.len() - self.block_builder.is_some() as usize;
+        let data = self.buf.split().freeze();
+        let blocks = self.block_metas[self.taken_block_count..built_block_count]
+            .iter()
+            .map(|meta| {
+                let offset = meta.offset as usize - self.taken_len;
+                data.slice(offset..offset + meta.len as usize)
+            })
+            .collect();
+        self.taken_len += data.len();
+        self.taken_block_count = built_block_count;
+        blocks
+    }
+
     /// Finish building sst.
     ///
     /// Unlike most LSM-Tree implementations, sstable meta and data are encoded separately.
@@ -133,6 +159,8 @@ impl SSTableBuilder {
     /// ```plain
     /// | Block 0 | ... | Block N-1 | N (4B) |
     /// ```
+    ///
+    /// Blocks already taken by `take_built_blocks` are not included in the returned data.
     pub fn finish(mut self) -> (Bytes, SstableMeta) {
         let smallest_key = self.block_metas[0].smallest_key.clone();
         let largest_key = self.last_full_key.to_vec();
@@ -150,7 +178,7 @@ impl SSTableBuilder {
             } else {
                 vec![]
             },
-            estimated_size: self.buf.len() as u32,
+            estimated_size: self.data_len() as u32,
             key_count: self.key_count as u32,
             smallest_key,
             largest_key,
@@ -161,7 +189,12 @@ impl SSTableBuilder {
     }
 
     pub fn approximate_len(&self) -> usize {
-        self.buf.len() + 4
+        self.data_len() + 4
+    }
+
+    /// Length of data built so far, including the taken part.
+    fn data_len(&self) -> usize {
+        self.taken_len + self.buf.len()
     }
 
     fn build_block(&mut self) {
@@ -172,7 +205,7 @@ impl SSTableBuilder {
         let mut block_meta = self.block_metas.last_mut().unwrap();
         let block = self.block_builder.take().unwrap().build();
         self.buf.put_slice(&block);
-        block_meta.len = self.buf.len() as u32 - block_meta.offset;
+        block_meta.len = (self.taken_len + self.buf.len()) as u32 - block_meta.offset;
     }
 
     pub fn len(&self) -> usize {
@@ -228,6 +261,31 @@ pub(super) mod tests {
         assert_eq!(test_key_of(TEST_KEYS_COUNT - 1), meta.largest_key);
     }
 
+    #[test]
+    fn test_take_built_blocks() {
+        let build = |take: bool| {
+            let mut b = SSTableBuilder::new(default_builder_opt_for_test());
+            let mut data = BytesMut::new();
+            for i in 0..TEST_KEYS_COUNT {
+                b.add(&test_key_of(i), HummockValue::put(&test_value_of(i)));
+                if take {
+                    for block in b.take_built_blocks() {
+                        data.put_slice(&block);
+                    }
+                }
+            }
+            let (tail, meta) = b.finish();
+            data.put_slice(&tail);
+            (data.freeze(), meta)
+        };
+
+        let (data, meta) = build(false);
+        let (streamed_data, streamed_meta) = build(true);
+        assert!(meta.block_metas.len() > 1);
+        assert_eq!(data, streamed_data);
+        assert_eq!(meta, streamed_meta);
+    }
+
     async fn test_with_bloom_filter(with_blooms: bool) {
         let key_count = 1000;
 
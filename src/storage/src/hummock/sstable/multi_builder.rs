@@ -122,7 +122,23 @@ where
         }
     }
 
-    /// Finalizes all the tables to be ids, blocks and metadata.
+    /// Takes out the blocks built by the current builder since last call, together with the id of
+    /// its table. See [`SSTableBuilder::take_built_blocks`].
+    ///
+    /// Only the current builder is checked, so this should be called after each `add` to avoid
+    /// missing blocks of a builder before it's rotated.
+    pub fn take_built_blocks(&mut self) -> Option<(u64, Vec<Bytes>)> {
+        let wrapper = self.builders.last_mut()?;
+        let blocks = wrapper.builder.take_built_blocks();
+        if blocks.is_empty() {
+            None
+        } else {
+            Some((wrapper.id, blocks))
+        }
+    }
+
+    /// Finalizes all the tables to be ids, blocks and metadata. Blocks already taken by
+    /// `take_built_blocks` are not included.
     pub fn finish(self) -> Vec<(u64, Bytes, SstableMeta)> {
         self.builders
             .into_iter()
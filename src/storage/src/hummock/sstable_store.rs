@@ -21,7 +21,7 @@ use moka::future::Cache;
 use super::{Block, BlockCache, Sstable, SstableMeta};
 use crate::hummock::{HummockError, HummockResult};
 use crate::monitor::StateStoreMetrics;
-use crate::object::{BlockLocation, ObjectStoreRef};
+use crate::object::{BlockLocation, BoxedStreamingUploader, ObjectStoreRef};
 
 const DEFAULT_META_CACHE_INIT_CAPACITY: usize = 1024;
 
@@ -32,6 +32,18 @@ pub enum CachePolicy {
     NotFill,
 }
 
+/// Uploads the data of an sstable block by block while it is being built. Created by
+/// [`SstableStore::create_sst_writer`].
+pub struct SstableWriter {
+    sst_id: u64,
+    policy: CachePolicy,
+    data_uploader: BoxedStreamingUploader,
+    /// Length of data written so far.
+    written_len: usize,
+    /// Number of blocks written so far.
+    written_block_count: usize,
+}
+
 pub struct SstableStore {
     path: String,
     store: ObjectStoreRef,
@@ -108,6 +120,124 @@ impl SstableStore {
         Ok(len)
     }
 
+    /// Begins uploading the data of sstable `sst_id`.
+    pub async fn create_sst_writer(
+        &self,
+        sst_id: u64,
+        policy: CachePolicy,
+    ) -> HummockResult<SstableWriter> {
+        let data_uploader = self
+            .store
+            .streaming_upload(&self.get_sst_data_path(sst_id))
+            .await
+            .map_err(HummockError::object_io_error)?;
+        Ok(SstableWriter {
+            sst_id,
+            policy,
+            data_uploader,
+            written_len: 0,
+            written_block_count: 0,
+        })
+    }
+
+    /// Appends encoded blocks to the data of the sstable.
+    pub async fn write_blocks(
+        &self,
+        writer: &mut SstableWriter,
+        blocks: Vec<Bytes>,
+    ) -> HummockResult<()> {
+        for block in blocks {
+            writer.written_len += block.len();
+            writer
+                .data_uploader
+                .write_part(block.clone())
+                .await
+                .map_err(HummockError::object_io_error)?;
+            if let CachePolicy::Fill = writer.policy {
+                self.block_cache
+                    .insert(
+                        writer.sst_id,
+                        writer.written_block_count as u64,
+                        Arc::new(Block::decode(block)?),
+                    )
+                    .await;
+            }
+            writer.written_block_count += 1;
+        }
+        Ok(())
+    }
+
+    /// Writes the remaining `data` returned by [`SSTableBuilder::finish`], and uploads the meta of
+    /// the sstable once its data is completed. Returns the total length of data.
+    ///
+    /// [`SSTableBuilder::finish`]: super::SSTableBuilder::finish
+    pub async fn finish_sst_writer(
+        &self,
+        mut writer: SstableWriter,
+        sst: &Sstable,
+        data: Bytes,
+    ) -> HummockResult<usize> {
+        let timer = self.stats.sst_store_put_remote_duration.start_timer();
+
+        let written_len = writer.written_len;
+        let written_block_count = writer.written_block_count;
+        let len = written_len + data.len();
+        if let Err(e) = writer.data_uploader.write_part(data.clone()).await {
+            writer
+                .data_uploader
+                .abort()
+                .await
+                .map_err(HummockError::object_io_error)?;
+            return Err(HummockError::object_io_error(e));
+        }
+        writer
+            .data_uploader
+            .finish()
+            .await
+            .map_err(HummockError::object_io_error)?;
+
+        fail_point!("metadata_upload_err");
+        let meta = Bytes::from(sst.meta.encode_to_bytes());
+        let meta_path = self.get_sst_meta_path(sst.id);
+        if let Err(e) = self.store.upload(&meta_path, meta).await {
+            self.store
+                .delete(&self.get_sst_data_path(sst.id))
+                .await
+                .map_err(HummockError::object_io_error)?;
+            return Err(HummockError::object_io_error(e));
+        }
+
+        timer.observe_duration();
+
+        if let CachePolicy::Fill = writer.policy {
+            for (block_idx, meta) in sst
+                .meta
+                .block_metas
+                .iter()
+                .enumerate()
+                .skip(written_block_count)
+            {
+                let offset = meta.offset as usize - written_len;
+                let len = meta.len as usize;
+                let block = Arc::new(Block::decode(data.slice(offset..offset + len))?);
+                self.block_cache
+                    .insert(sst.id, block_idx as u64, block)
+                    .await
+            }
+        }
+
+        Ok(len)
+    }
+
+    /// Discards the data written by `writer`.
+    pub async fn abort_sst_writer(&self, writer: SstableWriter) -> HummockResult<()> {
+        writer
+            .data_uploader
+            .abort()
+            .await
+            .map_err(HummockError::object_io_error)
+    }
+
     pub async fn get(
         &self,
         sst: &Sstable,
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Bound;
+
 use bytes::{BufMut, Bytes, BytesMut};
 use risingwave_common::catalog::TableId;
 use risingwave_hummock_sdk::key::next_key;
@@ -143,6 +145,40 @@ impl<S: StateStore> Keyspace<S> {
         self.store.scan(range, limit, epoch).await
     }
 
+    /// Scans `limit` keys within the given range of the keyspace and get their values. The bounds
+    /// are not prefixed with the keyspace, and an unbounded side extends to the boundary of the
+    /// keyspace.
+    /// The returned values are based on a snapshot corresponding to the given `epoch`
+    pub async fn scan_range(
+        &self,
+        (start, end): (Bound<Vec<u8>>, Bound<Vec<u8>>),
+        limit: Option<usize>,
+        epoch: u64,
+    ) -> StorageResult<Vec<(Bytes, Bytes)>> {
+        let start = match start {
+            Bound::Included(key) => Bound::Included(self.prefixed_key(key)),
+            Bound::Excluded(key) => Bound::Excluded(self.prefixed_key(key)),
+            Bound::Unbounded => Bound::Included(self.prefix.to_owned()),
+        };
+        let end = match end {
+            Bound::Included(key) => Bound::Included(self.prefixed_key(key)),
+            Bound::Excluded(key) => Bound::Excluded(self.prefixed_key(key)),
+            Bound::Unbounded => Bound::Excluded(next_key(self.prefix.as_slice())),
+        };
+        let is_empty = match (&start, &end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            _ => false,
+        };
+        if is_empty {
+            return Ok(vec![]);
+        }
+        self.store.scan((start, end), limit, epoch).await
+    }
+
     /// Scans from the keyspace, and then strips the prefix of this keyspace.
     /// The returned values are based on a snapshot corresponding to the given `epoch`
     ///
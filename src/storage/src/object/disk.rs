@@ -0,0 +1,431 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::OsStr;
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use bytes::{Bytes, BytesMut};
+use fail::fail_point;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use super::{ObjectError, ObjectResult};
+use crate::object::{
+    BlockLocation, BoxedStreamingUploader, ObjectMetadata, ObjectStore, StreamingUploader,
+};
+
+/// Object store on local file system, useful for single-node deployments.
+///
+/// Each object is a file under `root`. An upload is written to a temporary file which is then
+/// fsync'd and renamed, so a crash never leaves a partially written object behind. Temporary files
+/// left by a crash are removed when the store is created next time.
+pub struct DiskObjectStore {
+    root: PathBuf,
+    next_tmp_id: AtomicU64,
+}
+
+#[async_trait::async_trait]
+impl ObjectStore for DiskObjectStore {
+    async fn upload(&self, path: &str, obj: Bytes) -> ObjectResult<()> {
+        fail_point!("disk_upload_err", |_| Err(ObjectError::internal(
+            "disk upload error"
+        )));
+        let path = self.object_path(path);
+        let tmp_path = self.tmp_path(&path);
+        write_object(&path, &tmp_path, &obj).await
+    }
+
+    async fn read(&self, path: &str, block_loc: Option<BlockLocation>) -> ObjectResult<Bytes> {
+        fail_point!("disk_read_err", |_| Err(ObjectError::internal(
+            "disk read error"
+        )));
+        let (mut file, total_size) = self.open(path).await?;
+        let block_loc = block_loc.unwrap_or(BlockLocation {
+            offset: 0,
+            size: total_size,
+        });
+        read_block(&mut file, total_size, block_loc)
+            .await
+            .map_err(|e| ObjectError::disk(format!("failed to read {}", path), e))
+    }
+
+    async fn readv(&self, path: &str, block_locs: Vec<BlockLocation>) -> ObjectResult<Vec<Bytes>> {
+        fail_point!("disk_read_err", |_| Err(ObjectError::internal(
+            "disk read error"
+        )));
+        let (mut file, total_size) = self.open(path).await?;
+        let mut blocks = Vec::with_capacity(block_locs.len());
+        for block_loc in block_locs {
+            blocks.push(
+                read_block(&mut file, total_size, block_loc)
+                    .await
+                    .map_err(|e| ObjectError::disk(format!("failed to read {}", path), e))?,
+            );
+        }
+        Ok(blocks)
+    }
+
+    async fn metadata(&self, path: &str) -> ObjectResult<ObjectMetadata> {
+        let metadata = fs::metadata(self.object_path(path))
+            .await
+            .map_err(|e| ObjectError::disk(format!("failed to stat {}", path), e))?;
+        Ok(ObjectMetadata {
+            total_size: metadata.len() as usize,
+        })
+    }
+
+    /// Same as S3, deleting an object that does not exist is not an error.
+    async fn delete(&self, path: &str) -> ObjectResult<()> {
+        fail_point!("disk_delete_err", |_| Err(ObjectError::internal(
+            "disk delete error"
+        )));
+        let path = self.object_path(path);
+        match fs::remove_file(&path).await {
+            Ok(()) => sync_dir(path.parent().unwrap()).await,
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(ObjectError::disk(format!("failed to delete {:?}", path), e)),
+        }
+    }
+
+    async fn streaming_upload(&self, path: &str) -> ObjectResult<BoxedStreamingUploader> {
+        let path = self.object_path(path);
+        let tmp_path = self.tmp_path(&path);
+        Ok(Box::new(DiskStreamingUploader {
+            path,
+            tmp_path,
+            buf: BytesMut::new(),
+        }))
+    }
+}
+
+/// Concatenates the parts in memory and writes the object on `finish`, the same way as
+/// [`DiskObjectStore::upload`].
+pub struct DiskStreamingUploader {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    buf: BytesMut,
+}
+
+#[async_trait::async_trait]
+impl StreamingUploader for DiskStreamingUploader {
+    async fn write_part(&mut self, part: Bytes) -> ObjectResult<()> {
+        fail_point!("disk_upload_err", |_| Err(ObjectError::internal(
+            "disk upload error"
+        )));
+        self.buf.extend_from_slice(&part);
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> ObjectResult<()> {
+        write_object(&self.path, &self.tmp_path, &self.buf).await
+    }
+
+    async fn abort(self: Box<Self>) -> ObjectResult<()> {
+        Ok(())
+    }
+}
+
+impl DiskObjectStore {
+    /// Creates an object store storing objects under directory `root`, and removes the temporary
+    /// files that have not been modified for [`TMP_FILE_EXPIRE`] under it.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        if let Some(expire_before) = SystemTime::now().checked_sub(TMP_FILE_EXPIRE) {
+            if let Err(e) = remove_tmp_files(&root, expire_before) {
+                tracing::warn!("failed to remove temporary files under {:?}: {}", root, e);
+            }
+        }
+        Self {
+            root,
+            next_tmp_id: AtomicU64::new(0),
+        }
+    }
+
+    fn object_path(&self, path: &str) -> PathBuf {
+        self.root.join(path.trim_start_matches('/'))
+    }
+
+    fn tmp_path(&self, path: &Path) -> PathBuf {
+        let mut file_name = path.file_name().unwrap().to_os_string();
+        file_name.push(format!(
+            ".{}.tmp",
+            self.next_tmp_id.fetch_add(1, Ordering::Relaxed)
+        ));
+        path.with_file_name(file_name)
+    }
+
+    async fn open(&self, path: &str) -> ObjectResult<(File, usize)> {
+        let file = File::open(self.object_path(path)).await.map_err(|e| {
+            if e.kind() == ErrorKind::NotFound {
+                ObjectError::internal(format!("no object at path '{}'", path))
+            } else {
+                ObjectError::disk(format!("failed to open {}", path), e)
+            }
+        })?;
+        let total_size = file
+            .metadata()
+            .await
+            .map_err(|e| ObjectError::disk(format!("failed to stat {}", path), e))?
+            .len() as usize;
+        Ok((file, total_size))
+    }
+}
+
+/// A temporary file is only removed after it has not been modified for this long, because the same
+/// root may be shared by several processes whose uploads are still in flight.
+const TMP_FILE_EXPIRE: Duration = Duration::from_secs(600);
+
+/// Whether `file_name` is in the form of `<object>.<id>.tmp` given by
+/// [`DiskObjectStore::tmp_path`].
+fn is_tmp_file(file_name: &OsStr) -> bool {
+    file_name
+        .to_str()
+        .and_then(|name| name.strip_suffix(".tmp"))
+        .and_then(|name| name.rsplit_once('.'))
+        .map_or(false, |(object, id)| {
+            !object.is_empty() && !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+        })
+}
+
+/// Recursively removes the temporary files under `dir` last modified before `expire_before`.
+fn remove_tmp_files(dir: &Path, expire_before: SystemTime) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            remove_tmp_files(&entry.path(), expire_before)?;
+        } else if is_tmp_file(&entry.file_name()) && metadata.modified()? < expire_before {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+async fn write_object(path: &Path, tmp_path: &Path, obj: &[u8]) -> ObjectResult<()> {
+    if obj.is_empty() {
+        return Err(ObjectError::internal("upload empty object"));
+    }
+    let dir = path.parent().unwrap();
+    fs::create_dir_all(dir)
+        .await
+        .map_err(|e| ObjectError::disk(format!("failed to create dir {:?}", dir), e))?;
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(tmp_path)
+        .await
+        .map_err(|e| ObjectError::disk(format!("failed to create {:?}", tmp_path), e))?;
+    let result = async {
+        file.write_all(obj).await?;
+        file.sync_all().await?;
+        fs::rename(tmp_path, path).await
+    }
+    .await;
+    if let Err(e) = result {
+        let _ = fs::remove_file(tmp_path).await;
+        return Err(ObjectError::disk(format!("failed to write {:?}", path), e));
+    }
+    // Persist the rename.
+    sync_dir(dir).await
+}
+
+async fn read_block(
+    file: &mut File,
+    total_size: usize,
+    block_loc: BlockLocation,
+) -> std::io::Result<Bytes> {
+    if block_loc.offset + block_loc.size > total_size {
+        return Err(std::io::Error::new(
+            ErrorKind::UnexpectedEof,
+            format!(
+                "bad block offset and size {:?}, object size {}",
+                block_loc, total_size
+            ),
+        ));
+    }
+    file.seek(SeekFrom::Start(block_loc.offset as u64)).await?;
+    let mut buf = vec![0; block_loc.size];
+    file.read_exact(&mut buf).await?;
+    Ok(buf.into())
+}
+
+async fn sync_dir(dir: &Path) -> ObjectResult<()> {
+    async { File::open(dir).await?.sync_all().await }
+        .await
+        .map_err(|e| ObjectError::disk(format!("failed to sync dir {:?}", dir), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_upload() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskObjectStore::new(dir.path());
+        store
+            .upload("/hummock/abc", Bytes::from("123456"))
+            .await
+            .unwrap();
+
+        // No such object.
+        store
+            .read("/hummock/ab", Some(BlockLocation { offset: 0, size: 3 }))
+            .await
+            .unwrap_err();
+
+        let bytes = store
+            .read("/hummock/abc", Some(BlockLocation { offset: 4, size: 2 }))
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8(bytes.to_vec()).unwrap(), "56".to_string());
+        let bytes = store.read("/hummock/abc", None).await.unwrap();
+        assert_eq!(
+            String::from_utf8(bytes.to_vec()).unwrap(),
+            "123456".to_string()
+        );
+
+        // Overflow.
+        store
+            .read("/hummock/abc", Some(BlockLocation { offset: 4, size: 4 }))
+            .await
+            .unwrap_err();
+
+        let blocks = store
+            .readv(
+                "/hummock/abc",
+                vec![
+                    BlockLocation { offset: 3, size: 3 },
+                    BlockLocation { offset: 0, size: 2 },
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(blocks, vec![Bytes::from("456"), Bytes::from("12")]);
+
+        // Overwrite.
+        store
+            .upload("/hummock/abc", Bytes::from("7890"))
+            .await
+            .unwrap();
+        assert_eq!(store.metadata("/hummock/abc").await.unwrap().total_size, 4);
+
+        store.delete("/hummock/abc").await.unwrap();
+        // Deleting a nonexistent object succeeds.
+        store.delete("/hummock/abc").await.unwrap();
+
+        // No such object.
+        store
+            .read("/hummock/abc", Some(BlockLocation { offset: 0, size: 3 }))
+            .await
+            .unwrap_err();
+        store.metadata("/hummock/abc").await.unwrap_err();
+
+        // No temporary file is left behind.
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("hummock"))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streaming_upload() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskObjectStore::new(dir.path());
+
+        let mut uploader = store.streaming_upload("/hummock/abc").await.unwrap();
+        uploader.write_part(Bytes::from("123")).await.unwrap();
+        uploader.write_part(Bytes::from("456")).await.unwrap();
+        // Not visible before finished.
+        store.metadata("/hummock/abc").await.unwrap_err();
+        uploader.finish().await.unwrap();
+        let bytes = store.read("/hummock/abc", None).await.unwrap();
+        assert_eq!(bytes, Bytes::from("123456"));
+
+        let mut uploader = store.streaming_upload("/hummock/def").await.unwrap();
+        uploader.write_part(Bytes::from("123")).await.unwrap();
+        uploader.abort().await.unwrap();
+        store.metadata("/hummock/def").await.unwrap_err();
+
+        // No temporary file is left behind.
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("hummock"))
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_tmp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskObjectStore::new(dir.path());
+        store
+            .upload("/hummock/abc", Bytes::from("123456"))
+            .await
+            .unwrap();
+        // Left by a crash between writing and renaming.
+        let tmp_path = store.tmp_path(&store.object_path("/hummock/def"));
+        std::fs::write(&tmp_path, "123").unwrap();
+        // Objects that merely look like temporary files.
+        store
+            .upload("/hummock/ghi.tmp", Bytes::from("123"))
+            .await
+            .unwrap();
+        store
+            .upload("/hummock/.1.tmp", Bytes::from("123"))
+            .await
+            .unwrap();
+
+        // Recently modified temporary files may belong to in-flight uploads.
+        DiskObjectStore::new(dir.path());
+        assert!(tmp_path.exists());
+
+        remove_tmp_files(dir.path(), SystemTime::now() + Duration::from_secs(1)).unwrap();
+        assert!(!tmp_path.exists());
+        let mut names = std::fs::read_dir(dir.path().join("hummock"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec![".1.tmp", "abc", "ghi.tmp"]);
+    }
+
+    #[tokio::test]
+    async fn test_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        DiskObjectStore::new(dir.path())
+            .upload("abc", Bytes::from("123456"))
+            .await
+            .unwrap();
+
+        let store = DiskObjectStore::new(dir.path());
+        let bytes = store
+            .read("abc", Some(BlockLocation { offset: 1, size: 2 }))
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8(bytes.to_vec()).unwrap(), "23".to_string());
+    }
+}
@@ -25,6 +25,13 @@ enum ObjectErrorInner {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("Disk error: {msg}")]
+    Disk {
+        msg: String,
+        #[source]
+        inner: std::io::Error,
+    },
 }
 
 #[derive(Error)]
@@ -56,6 +63,14 @@ impl ObjectError {
     pub fn internal(msg: impl ToString) -> Self {
         ObjectErrorInner::Internal(msg.to_string()).into()
     }
+
+    pub fn disk(msg: impl ToString, err: std::io::Error) -> Self {
+        ObjectErrorInner::Disk {
+            msg: msg.to_string(),
+            inner: err,
+        }
+        .into()
+    }
 }
 
 impl<E> From<aws_smithy_http::result::SdkError<E>> for ObjectError
@@ -13,20 +13,23 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use fail::fail_point;
 use futures::future::try_join_all;
 use itertools::Itertools;
 use tokio::sync::Mutex;
 
 use super::{ObjectError, ObjectResult};
-use crate::object::{BlockLocation, ObjectMetadata, ObjectStore};
+use crate::object::{
+    BlockLocation, BoxedStreamingUploader, ObjectMetadata, ObjectStore, StreamingUploader,
+};
 
 /// In-memory object storage, useful for testing.
 #[derive(Default)]
 pub struct InMemObjectStore {
-    objects: Mutex<HashMap<String, Bytes>>,
+    objects: Arc<Mutex<HashMap<String, Bytes>>>,
 }
 
 #[async_trait::async_trait]
@@ -74,12 +77,51 @@ impl ObjectStore for InMemObjectStore {
         self.objects.lock().await.remove(path);
         Ok(())
     }
+
+    async fn streaming_upload(&self, path: &str) -> ObjectResult<BoxedStreamingUploader> {
+        Ok(Box::new(InMemStreamingUploader {
+            path: path.to_string(),
+            buf: BytesMut::new(),
+            objects: self.objects.clone(),
+        }))
+    }
+}
+
+/// Concatenates the parts in memory and stores the object on `finish`.
+pub struct InMemStreamingUploader {
+    path: String,
+    buf: BytesMut,
+    objects: Arc<Mutex<HashMap<String, Bytes>>>,
+}
+
+#[async_trait::async_trait]
+impl StreamingUploader for InMemStreamingUploader {
+    async fn write_part(&mut self, part: Bytes) -> ObjectResult<()> {
+        fail_point!("mem_upload_err", |_| Err(ObjectError::internal(
+            "mem upload error"
+        )));
+        self.buf.extend_from_slice(&part);
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> ObjectResult<()> {
+        let Self { path, buf, objects } = *self;
+        if buf.is_empty() {
+            return Err(ObjectError::internal("upload empty object"));
+        }
+        objects.lock().await.insert(path, buf.freeze());
+        Ok(())
+    }
+
+    async fn abort(self: Box<Self>) -> ObjectResult<()> {
+        Ok(())
+    }
 }
 
 impl InMemObjectStore {
     pub fn new() -> Self {
         Self {
-            objects: Mutex::new(HashMap::new()),
+            objects: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -151,4 +193,27 @@ mod tests {
         let metadata = obj_store.metadata("/abc").await.unwrap();
         assert_eq!(metadata.total_size, 6);
     }
+
+    #[tokio::test]
+    async fn test_streaming_upload() {
+        let obj_store = InMemObjectStore::new();
+
+        let mut uploader = obj_store.streaming_upload("/abc").await.unwrap();
+        uploader.write_part(Bytes::from("123")).await.unwrap();
+        uploader.write_part(Bytes::from("456")).await.unwrap();
+        // Not visible before finished.
+        obj_store.metadata("/abc").await.unwrap_err();
+        uploader.finish().await.unwrap();
+        let bytes = obj_store.read("/abc", None).await.unwrap();
+        assert_eq!(bytes, Bytes::from("123456"));
+
+        let mut uploader = obj_store.streaming_upload("/def").await.unwrap();
+        uploader.write_part(Bytes::from("123")).await.unwrap();
+        uploader.abort().await.unwrap();
+        obj_store.metadata("/def").await.unwrap_err();
+
+        // Empty object.
+        let uploader = obj_store.streaming_upload("/ghi").await.unwrap();
+        uploader.finish().await.unwrap_err();
+    }
 }
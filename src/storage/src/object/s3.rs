@@ -12,15 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
+
+use aws_sdk_s3::model::{CompletedMultipartUpload, CompletedPart};
 use aws_sdk_s3::{Client, Endpoint, Region};
 use aws_smithy_http::body::SdkBody;
+use bytes::BytesMut;
 use fail::fail_point;
 use futures::future::try_join_all;
 use itertools::Itertools;
+use tokio::task::JoinHandle;
 
-use super::{BlockLocation, ObjectError, ObjectMetadata, ObjectResult};
+use super::{
+    BlockLocation, BoxedStreamingUploader, ObjectError, ObjectMetadata, ObjectResult,
+    StreamingUploader,
+};
 use crate::object::{Bytes, ObjectStore};
 
+/// S3 requires every part of a multipart upload except the last one to be at least 5 MiB.
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Maximum number of parts of a single object being uploaded at the same time, which also bounds
+/// the memory held by the parts in flight.
+const MAX_CONCURRENT_PART_UPLOADS: usize = 4;
+
 /// Object store with S3 backend
 pub struct S3ObjectStore {
     client: Client,
@@ -114,6 +129,221 @@ impl ObjectStore for S3ObjectStore {
             .await?;
         Ok(())
     }
+
+    async fn streaming_upload(&self, path: &str) -> ObjectResult<BoxedStreamingUploader> {
+        Ok(Box::new(S3StreamingUploader {
+            client: self.client.clone(),
+            bucket: self.bucket.clone(),
+            key: path.to_string(),
+            upload_id: None,
+            next_part_number: 1,
+            buf: BytesMut::new(),
+            part_handles: VecDeque::new(),
+            completed_parts: vec![],
+            failed: false,
+        }))
+    }
+}
+
+/// Uploads an object with S3 multipart upload. Written parts are buffered until they reach
+/// [`MIN_PART_SIZE`], and then uploaded in background, with at most
+/// [`MAX_CONCURRENT_PART_UPLOADS`] parts in flight. The multipart upload is aborted if any step
+/// fails, or if the uploader is dropped before it finishes. Once a step fails, the uploader refuses
+/// further writes and never finishes the object, whose buffered content may be incomplete.
+pub struct S3StreamingUploader {
+    client: Client,
+    bucket: String,
+    key: String,
+    /// The multipart upload is initiated on the first flushed part, so that a small object is
+    /// uploaded with a single `PutObject` instead. Reset to `None` once the upload is completed or
+    /// aborted.
+    upload_id: Option<String>,
+    next_part_number: i32,
+    buf: BytesMut,
+    part_handles: VecDeque<JoinHandle<ObjectResult<CompletedPart>>>,
+    completed_parts: Vec<CompletedPart>,
+    /// Set once any write fails.
+    failed: bool,
+}
+
+impl S3StreamingUploader {
+    async fn flush_part(&mut self) -> ObjectResult<()> {
+        let upload_id = match &self.upload_id {
+            Some(upload_id) => upload_id.clone(),
+            None => {
+                let resp = self
+                    .client
+                    .create_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(&self.key)
+                    .send()
+                    .await?;
+                let upload_id = resp
+                    .upload_id
+                    .ok_or_else(|| ObjectError::internal("s3 returned no upload id"))?;
+                self.upload_id = Some(upload_id.clone());
+                upload_id
+            }
+        };
+        // Bound the number of buffered parts being uploaded.
+        while self.part_handles.len() >= MAX_CONCURRENT_PART_UPLOADS {
+            self.wait_oldest_part().await?;
+        }
+        let part_number = self.next_part_number;
+        self.next_part_number += 1;
+        let req = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(SdkBody::from(self.buf.split().freeze()).into());
+        self.part_handles.push_back(tokio::spawn(async move {
+            let resp = req.send().await?;
+            Ok(CompletedPart::builder()
+                .set_e_tag(resp.e_tag)
+                .part_number(part_number)
+                .build())
+        }));
+        Ok(())
+    }
+
+    async fn wait_oldest_part(&mut self) -> ObjectResult<()> {
+        if let Some(handle) = self.part_handles.pop_front() {
+            self.completed_parts.push(
+                handle
+                    .await
+                    .map_err(|e| ObjectError::internal(format!("upload part failed: {}", e)))??,
+            );
+        }
+        Ok(())
+    }
+
+    async fn complete(&mut self) -> ObjectResult<()> {
+        if !self.buf.is_empty() {
+            self.flush_part().await?;
+        }
+        while !self.part_handles.is_empty() {
+            self.wait_oldest_part().await?;
+        }
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .upload_id(self.upload_id.as_ref().unwrap())
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(std::mem::take(&mut self.completed_parts)))
+                    .build(),
+            )
+            .send()
+            .await?;
+        self.upload_id = None;
+        Ok(())
+    }
+
+    fn check_not_failed(&self) -> ObjectResult<()> {
+        if self.failed {
+            return Err(ObjectError::internal(format!(
+                "streaming upload of {} has failed",
+                self.key
+            )));
+        }
+        Ok(())
+    }
+
+    async fn abort_upload(&mut self) -> ObjectResult<()> {
+        for handle in self.part_handles.drain(..) {
+            handle.abort();
+        }
+        if let Some(upload_id) = self.upload_id.take() {
+            self.client
+                .abort_multipart_upload()
+                .bucket(&self.bucket)
+                .key(&self.key)
+                .upload_id(upload_id)
+                .send()
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingUploader for S3StreamingUploader {
+    async fn write_part(&mut self, part: Bytes) -> ObjectResult<()> {
+        self.check_not_failed()?;
+        fail_point!("s3_upload_err", |_| {
+            self.failed = true;
+            Err(ObjectError::internal("s3 upload error"))
+        });
+        self.buf.extend_from_slice(&part);
+        if self.buf.len() >= MIN_PART_SIZE {
+            if let Err(e) = self.flush_part().await {
+                self.failed = true;
+                self.abort_upload().await?;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn finish(mut self: Box<Self>) -> ObjectResult<()> {
+        if let Err(e) = self.check_not_failed() {
+            self.abort_upload().await?;
+            return Err(e);
+        }
+        if self.upload_id.is_none() {
+            if self.buf.is_empty() {
+                return Err(ObjectError::internal("upload empty object"));
+            }
+            self.client
+                .put_object()
+                .bucket(&self.bucket)
+                .body(SdkBody::from(self.buf.split().freeze()).into())
+                .key(&self.key)
+                .send()
+                .await?;
+            return Ok(());
+        }
+        if let Err(e) = self.complete().await {
+            self.abort_upload().await?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn abort(mut self: Box<Self>) -> ObjectResult<()> {
+        self.abort_upload().await
+    }
+}
+
+impl Drop for S3StreamingUploader {
+    /// Aborts the unfinished multipart upload in background, so that the parts already uploaded
+    /// are not left behind in the bucket.
+    fn drop(&mut self) {
+        for handle in self.part_handles.drain(..) {
+            handle.abort();
+        }
+        let upload_id = match self.upload_id.take() {
+            Some(upload_id) => upload_id,
+            None => return,
+        };
+        let req = self
+            .client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .upload_id(upload_id);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                if let Err(e) = req.send().await {
+                    tracing::warn!("failed to abort multipart upload: {:?}", e);
+                }
+            });
+        }
+    }
 }
 
 impl S3ObjectStore {
@@ -153,3 +383,45 @@ impl S3ObjectStore {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::Credentials;
+    use aws_smithy_types::retry::RetryConfig;
+
+    use super::*;
+
+    /// Returns a store whose requests all fail, as nothing listens on the endpoint.
+    fn unreachable_store() -> S3ObjectStore {
+        let config = aws_sdk_s3::Config::builder()
+            .region(Region::new("custom"))
+            .endpoint_resolver(Endpoint::immutable(
+                "http://127.0.0.1:1".try_into().unwrap(),
+            ))
+            .credentials_provider(Credentials::from_keys("key", "secret", None))
+            .retry_config(RetryConfig::disabled())
+            .build();
+        S3ObjectStore {
+            client: Client::from_conf(config),
+            bucket: "bucket".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_upload_after_failure() {
+        let store = unreachable_store();
+        let mut uploader = store.streaming_upload("abc").await.unwrap();
+        uploader.write_part(Bytes::from("123")).await.unwrap();
+        // Flushing the first part fails.
+        uploader
+            .write_part(Bytes::from(vec![0; MIN_PART_SIZE]))
+            .await
+            .unwrap_err();
+
+        // The uploader refuses to go on with the truncated content.
+        let err = uploader.write_part(Bytes::from("456")).await.unwrap_err();
+        assert!(err.to_string().contains("has failed"), "{}", err);
+        let err = uploader.finish().await.unwrap_err();
+        assert!(err.to_string().contains("has failed"), "{}", err);
+    }
+}
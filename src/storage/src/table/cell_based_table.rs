@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Bound;
 use std::sync::Arc;
 
 use bytes::Bytes;
@@ -210,9 +211,42 @@ impl<S: StateStore> CellBasedTable<S> {
 
     // The returned iterator will iterate data from a snapshot corresponding to the given `epoch`
     pub async fn iter(&self, epoch: u64) -> StorageResult<CellBasedTableRowIter<S>> {
+        self.iter_with_pk_bounds(epoch, (Bound::Unbounded, Bound::Unbounded))
+            .await
+    }
+
+    /// Iterates the rows whose pk is within `pk_bounds`. The bounds are memcomparable-encoded pk
+    /// prefixes, and a bound covers all the pks starting with it, e.g. an included upper bound `p`
+    /// includes every pk with the prefix `p`.
+    pub async fn iter_with_pk_bounds(
+        &self,
+        epoch: u64,
+        (start, end): (Bound<Vec<u8>>, Bound<Vec<u8>>),
+    ) -> StorageResult<CellBasedTableRowIter<S>> {
+        let key_range = match (start, end) {
+            // No key is after all the keys with the prefix, e.g. the prefix is `\xff\xff`.
+            (Bound::Excluded(pk_prefix), _) if next_key(&pk_prefix).is_empty() => (
+                Bound::Included(pk_prefix.clone()),
+                Bound::Excluded(pk_prefix),
+            ),
+            (start, end) => (
+                match start {
+                    Bound::Excluded(pk_prefix) => Bound::Included(next_key(&pk_prefix)),
+                    start => start,
+                },
+                match end {
+                    Bound::Included(pk_prefix) => match next_key(&pk_prefix) {
+                        key if key.is_empty() => Bound::Unbounded,
+                        key => Bound::Excluded(key),
+                    },
+                    end => end,
+                },
+            ),
+        };
         CellBasedTableRowIter::new(
             self.keyspace.clone(),
             self.column_descs.clone(),
+            key_range,
             epoch,
             self.stats.clone(),
         )
@@ -231,6 +265,8 @@ fn generate_column_id(column_descs: &[ColumnDesc]) -> Vec<ColumnId> {
 // "RowTableIter" is reserved now
 pub struct CellBasedTableRowIter<S: StateStore> {
     keyspace: Keyspace<S>,
+    /// The range of keys to scan, which are not prefixed with the keyspace
+    key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
     /// A buffer to store prefetched kv pairs from state store
     buf: Vec<(Bytes, Bytes)>,
     /// The idx into `buf` for the next item
@@ -251,6 +287,7 @@ impl<S: StateStore> CellBasedTableRowIter<S> {
     async fn new(
         keyspace: Keyspace<S>,
         table_descs: Vec<ColumnDesc>,
+        key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         epoch: u64,
         _stats: Arc<StateStoreMetrics>,
     ) -> StorageResult<Self> {
@@ -260,6 +297,7 @@ impl<S: StateStore> CellBasedTableRowIter<S> {
 
         let iter = Self {
             keyspace,
+            key_range,
             buf: vec![],
             next_idx: 0,
             done: false,
@@ -276,17 +314,15 @@ impl<S: StateStore> CellBasedTableRowIter<S> {
         if self.buf.is_empty() {
             self.buf = self
                 .keyspace
-                .scan(Some(Self::SCAN_LIMIT), self.epoch)
+                .scan_range(self.key_range.clone(), Some(Self::SCAN_LIMIT), self.epoch)
                 .await?;
         } else {
-            let last_key = self.buf.last().unwrap().0.clone();
-            let buf = self
+            let last_key = &self.buf.last().unwrap().0[self.keyspace.key().len()..];
+            let key_range = (Bound::Excluded(last_key.to_vec()), self.key_range.1.clone());
+            self.buf = self
                 .keyspace
-                .scan_with_start_key(last_key.to_vec(), Some(Self::SCAN_LIMIT), self.epoch)
+                .scan_range(key_range, Some(Self::SCAN_LIMIT), self.epoch)
                 .await?;
-            assert!(!buf.is_empty());
-            assert_eq!(buf.first().as_ref().unwrap().0, last_key);
-            self.buf = buf[1..].to_vec();
         }
 
         self.next_idx = 0;
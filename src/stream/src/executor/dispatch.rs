@@ -16,6 +16,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use futures::channel::mpsc::Sender;
@@ -27,6 +28,7 @@ use risingwave_common::util::addr::{is_local_address, HostAddr};
 use risingwave_common::util::hash_util::CRC32FastBuilder;
 use tracing::event;
 
+use super::monitor::StreamingMetrics;
 use super::{Barrier, Executor, Message, Mutation, Result, StreamChunk, StreamConsumer};
 use crate::task::{ActorId, SharedContext};
 
@@ -119,7 +121,9 @@ pub struct DispatchExecutor {
     input: Box<dyn Executor>,
     inner: DispatcherImpl,
     actor_id: u32,
+    actor_id_string: String,
     context: Arc<SharedContext>,
+    metrics: Arc<StreamingMetrics>,
 }
 
 pub fn new_output(
@@ -153,25 +157,40 @@ impl DispatchExecutor {
         inner: DispatcherImpl,
         actor_id: u32,
         context: Arc<SharedContext>,
+        metrics: Arc<StreamingMetrics>,
     ) -> Self {
         Self {
             input,
             inner,
             actor_id,
+            actor_id_string: actor_id.to_string(),
             context,
+            metrics,
         }
     }
 
     async fn dispatch(&mut self, msg: Message) -> Result<()> {
         match msg {
             Message::Chunk(chunk) => {
+                // Sending blocks once the channels of downstream actors are full, so the time
+                // spent here shows backpressure.
+                let start = Instant::now();
                 self.inner.dispatch_data(chunk).await?;
+                self.metrics
+                    .actor_output_buffer_blocking_duration_ns
+                    .with_label_values(&[self.actor_id_string.as_str()])
+                    .inc_by(start.elapsed().as_nanos() as u64);
             }
             Message::Barrier(barrier) => {
+                let epoch = barrier.epoch.curr;
                 let mutation = barrier.mutation.clone();
                 self.pre_mutate_outputs(&mutation).await?;
                 self.inner.dispatch_barrier(barrier).await?;
                 self.post_mutate_outputs(&mutation).await?;
+                self.metrics
+                    .actor_latest_epoch
+                    .with_label_values(&[self.actor_id_string.as_str()])
+                    .set(epoch as i64);
             }
         };
         Ok(())
@@ -908,6 +927,7 @@ mod tests {
             DispatcherImpl::Simple(SimpleDispatcher::new(output)),
             actor_id,
             ctx.clone(),
+            Arc::new(StreamingMetrics::unused()),
         ));
         let mut updates1: HashMap<u32, Vec<ActorInfo>> = HashMap::new();
 
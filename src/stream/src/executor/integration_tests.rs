@@ -131,6 +131,7 @@ async fn test_merger_sum_aggr() {
         DispatcherImpl::RoundRobin(RoundRobinDataDispatcher::new(inputs)),
         0,
         ctx,
+        Arc::new(StreamingMetrics::unused()),
     );
     let context = SharedContext::for_test().into();
     let actor = Actor::new(Box::new(dispatcher), 0, context);
@@ -0,0 +1,267 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use async_trait::async_trait;
+use bytes::{Buf, BufMut, Bytes};
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::{ArrayImpl, ListValue, Op};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{
+    deserialize_datum_from, serialize_datum_into, DataType, Datum, ScalarImpl,
+};
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::managed_state::aggregation::ManagedTableState;
+use crate::executor::AggCall;
+
+/// Manages the state of `ARRAY_AGG`.
+///
+/// To support retractions, each distinct value, including null, is stored in the keyspace with
+/// the memcomparable encoding as the key and its number of occurrences as the value. The output
+/// list is ordered by value. Like [`super::string_agg::ManagedStringAggState`], the policy is
+/// all-or-nothing: all values are loaded into memory on first access.
+pub struct ManagedArrayAggState<S: StateStore> {
+    element_type: DataType,
+
+    /// All values and their counts, or `None` if they have not been loaded from the storage.
+    cache: Option<BTreeMap<Bytes, (Datum, i64)>>,
+
+    /// Keys modified since the last flush.
+    dirty_keys: BTreeSet<Bytes>,
+
+    /// The keyspace to operate on.
+    keyspace: Keyspace<S>,
+}
+
+impl<S: StateStore> ManagedArrayAggState<S> {
+    /// Create an array agg managed state based on `AggCall` and `Keyspace`.
+    pub async fn new(
+        agg_call: AggCall,
+        keyspace: Keyspace<S>,
+        row_count: Option<usize>,
+    ) -> Result<Self> {
+        let element_type = match agg_call.return_type {
+            DataType::List { datatype } => *datatype,
+            ty => {
+                return Err(ErrorCode::InternalError(format!(
+                    "array_agg should return a list, got {:?}",
+                    ty
+                ))
+                .into())
+            }
+        };
+        Ok(Self {
+            element_type,
+            // Nothing to load for a new group.
+            cache: (row_count == Some(0)).then(BTreeMap::new),
+            dirty_keys: BTreeSet::new(),
+            keyspace,
+        })
+    }
+
+    async fn cache(&mut self, epoch: u64) -> Result<&mut BTreeMap<Bytes, (Datum, i64)>> {
+        if self.cache.is_none() {
+            let mut cache = BTreeMap::new();
+            for (raw_key, raw_value) in self.keyspace.scan_strip_prefix(None, epoch).await? {
+                let mut deserializer = memcomparable::Deserializer::new(raw_key.as_ref());
+                let value = deserialize_datum_from(&self.element_type, &mut deserializer)?;
+                let mut raw_value = raw_value.as_ref();
+                if raw_value.len() != 8 {
+                    return Err(ErrorCode::InternalError(format!(
+                        "expect an array agg count, got {} bytes",
+                        raw_value.len()
+                    ))
+                    .into());
+                }
+                cache.insert(raw_key, (value, raw_value.get_i64_le()));
+            }
+            self.cache = Some(cache);
+        }
+        Ok(self.cache.as_mut().unwrap())
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> ManagedTableState<S> for ManagedArrayAggState<S> {
+    async fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+        epoch: u64,
+    ) -> Result<()> {
+        debug_assert!(super::verify_batch(ops, visibility, data));
+        let mut dirty_keys = vec![];
+        let cache = self.cache(epoch).await?;
+        for (idx, op) in ops.iter().enumerate() {
+            if let Some(visibility) = visibility && !visibility.is_set(idx)? {
+                continue;
+            }
+            let value = data[0].datum_at(idx);
+            let mut serializer = memcomparable::Serializer::new(vec![]);
+            serialize_datum_into(&value, &mut serializer)?;
+            let key = Bytes::from(serializer.into_inner());
+            let delta = match op {
+                Op::Insert | Op::UpdateInsert => 1,
+                Op::Delete | Op::UpdateDelete => -1,
+            };
+            cache.entry(key.clone()).or_insert((value, 0)).1 += delta;
+            dirty_keys.push(key);
+        }
+        self.dirty_keys.extend(dirty_keys);
+        Ok(())
+    }
+
+    async fn get_output(&mut self, epoch: u64) -> Result<Datum> {
+        let values = self
+            .cache(epoch)
+            .await?
+            .values()
+            .flat_map(|(value, count)| std::iter::repeat(value.clone()).take(*count as usize))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(ScalarImpl::List(ListValue::new(values))))
+    }
+
+    fn is_dirty(&self) -> bool {
+        !self.dirty_keys.is_empty()
+    }
+
+    fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
+        if !self.is_dirty() {
+            return Ok(());
+        }
+
+        let cache = self.cache.as_mut().unwrap();
+        let mut local = write_batch.prefixify(&self.keyspace);
+        for key in std::mem::take(&mut self.dirty_keys) {
+            let count = cache.get(&key).map(|(_, count)| *count).unwrap_or(0);
+            if count > 0 {
+                let mut bytes = Vec::with_capacity(8);
+                bytes.put_i64_le(count);
+                local.put(key, StorageValue::new_default_put(bytes));
+            } else {
+                cache.remove(&key);
+                local.delete(key);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::I32Array;
+    use risingwave_expr::expr::AggKind;
+
+    use super::*;
+    use crate::executor::test_utils::create_in_memory_keyspace;
+    use crate::executor::AggArgs;
+
+    fn create_test_agg_call() -> AggCall {
+        AggCall {
+            kind: AggKind::ArrayAgg,
+            args: AggArgs::Unary(DataType::Int32, 0),
+            return_type: DataType::List {
+                datatype: Box::new(DataType::Int32),
+            },
+        }
+    }
+
+    fn list(values: &[Option<i32>]) -> Datum {
+        Some(ScalarImpl::List(ListValue::new(
+            values.iter().map(|v| v.map(ScalarImpl::Int32)).collect(),
+        )))
+    }
+
+    async fn apply_and_flush<S: StateStore>(
+        state: &mut ManagedArrayAggState<S>,
+        keyspace: &Keyspace<S>,
+        ops: Ops<'_>,
+        data: &[Option<i32>],
+        epoch: u64,
+    ) {
+        let data = I32Array::from_slice(data).unwrap().into();
+        state.apply_batch(ops, None, &[&data], epoch).await.unwrap();
+        assert!(state.is_dirty());
+        let mut write_batch = keyspace.state_store().start_write_batch();
+        state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+        assert!(!state.is_dirty());
+    }
+
+    #[tokio::test]
+    async fn test_managed_array_agg_state() {
+        let keyspace = create_in_memory_keyspace();
+        let mut state =
+            ManagedArrayAggState::new(create_test_agg_call(), keyspace.clone(), Some(0))
+                .await
+                .unwrap();
+        assert_eq!(state.get_output(0).await.unwrap(), None);
+
+        apply_and_flush(
+            &mut state,
+            &keyspace,
+            &[Op::Insert, Op::Insert, Op::Insert, Op::Insert],
+            &[Some(3), None, Some(1), Some(3)],
+            0,
+        )
+        .await;
+        assert_eq!(
+            state.get_output(0).await.unwrap(),
+            list(&[None, Some(1), Some(3), Some(3)])
+        );
+
+        // retract values after recovery
+        let mut state = ManagedArrayAggState::new(create_test_agg_call(), keyspace.clone(), None)
+            .await
+            .unwrap();
+        apply_and_flush(
+            &mut state,
+            &keyspace,
+            &[Op::Delete, Op::Delete, Op::Insert],
+            &[Some(3), None, Some(2)],
+            1,
+        )
+        .await;
+        assert_eq!(
+            state.get_output(1).await.unwrap(),
+            list(&[Some(1), Some(2), Some(3)])
+        );
+
+        let mut state = ManagedArrayAggState::new(create_test_agg_call(), keyspace.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            state.get_output(1).await.unwrap(),
+            list(&[Some(1), Some(2), Some(3)])
+        );
+        apply_and_flush(
+            &mut state,
+            &keyspace,
+            &[Op::Delete, Op::Delete, Op::Delete],
+            &[Some(1), Some(2), Some(3)],
+            2,
+        )
+        .await;
+        assert_eq!(state.get_output(2).await.unwrap(), None);
+    }
+}
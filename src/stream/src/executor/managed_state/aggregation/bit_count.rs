@@ -0,0 +1,312 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::{Buf, BufMut};
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::{Array, ArrayImpl, Op};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_expr::expr::AggKind;
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::AggCall;
+
+/// Manages the state of `BOOL_AND`, `BOOL_OR`, `BIT_AND` and `BIT_OR`, where booleans are treated
+/// as single bits.
+///
+/// To support retractions, it counts the non-null values and, for each bit, the values having the
+/// bit set. All counts are serialized as a single value in the keyspace.
+pub struct ManagedBitCountState<S: StateStore> {
+    /// Whether the output bit is set only when all values have it set, or when any value does.
+    all: bool,
+
+    return_type: DataType,
+
+    /// Number of non-null values.
+    non_null_count: i64,
+
+    /// Number of values having each bit set.
+    bit_counts: Vec<i64>,
+
+    /// The keyspace to operate on.
+    keyspace: Keyspace<S>,
+
+    /// Indicates whether this managed state is dirty. If this state is dirty, we cannot evict the
+    /// state from memory.
+    is_dirty: bool,
+}
+
+impl<S: StateStore> ManagedBitCountState<S> {
+    /// Create a bit count managed state based on `AggCall` and `Keyspace`.
+    pub async fn new(
+        agg_call: AggCall,
+        keyspace: Keyspace<S>,
+        row_count: Option<usize>,
+    ) -> Result<Self> {
+        let all = match agg_call.kind {
+            AggKind::BoolAnd | AggKind::BitAnd => true,
+            AggKind::BoolOr | AggKind::BitOr => false,
+            kind => {
+                return Err(ErrorCode::InternalError(format!(
+                    "{} is not a bitwise aggregation",
+                    kind
+                ))
+                .into())
+            }
+        };
+        let width = match agg_call.return_type {
+            DataType::Boolean => 1,
+            DataType::Int16 => 16,
+            DataType::Int32 => 32,
+            DataType::Int64 => 64,
+            ref ty => {
+                return Err(ErrorCode::InternalError(format!(
+                    "{} does not support {:?}",
+                    agg_call.kind, ty
+                ))
+                .into())
+            }
+        };
+
+        let raw_data = if row_count != Some(0) {
+            // TODO: use the correct epoch
+            keyspace.value(u64::MAX).await?
+        } else {
+            None
+        };
+        let (non_null_count, bit_counts) = match raw_data {
+            Some(raw_data) => {
+                let mut raw_data = raw_data.as_ref();
+                if raw_data.len() != (width + 1) * 8 {
+                    return Err(ErrorCode::InternalError(format!(
+                        "expect {} bit counts, got {} bytes",
+                        width,
+                        raw_data.len()
+                    ))
+                    .into());
+                }
+                let non_null_count = raw_data.get_i64_le();
+                let bit_counts = (0..width).map(|_| raw_data.get_i64_le()).collect();
+                (non_null_count, bit_counts)
+            }
+            None => (0, vec![0; width]),
+        };
+
+        Ok(Self {
+            all,
+            return_type: agg_call.return_type,
+            non_null_count,
+            bit_counts,
+            keyspace,
+            is_dirty: false,
+        })
+    }
+
+    /// Apply a batch of data to the state.
+    pub async fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> Result<()> {
+        debug_assert!(super::verify_batch(ops, visibility, data));
+        self.is_dirty = true;
+        for (idx, op) in ops.iter().enumerate() {
+            if let Some(visibility) = visibility && !visibility.is_set(idx)? {
+                continue;
+            }
+            let bits = match data[0] {
+                ArrayImpl::Bool(array) => array.value_at(idx).map(|v| v as u64),
+                ArrayImpl::Int16(array) => array.value_at(idx).map(|v| v as u16 as u64),
+                ArrayImpl::Int32(array) => array.value_at(idx).map(|v| v as u32 as u64),
+                ArrayImpl::Int64(array) => array.value_at(idx).map(|v| v as u64),
+                array => {
+                    return Err(ErrorCode::InternalError(format!(
+                        "unsupported input of bitwise aggregation: {}",
+                        array.get_ident()
+                    ))
+                    .into())
+                }
+            };
+            let Some(bits) = bits else {
+                continue;
+            };
+            let delta = match op {
+                Op::Insert | Op::UpdateInsert => 1,
+                Op::Delete | Op::UpdateDelete => -1,
+            };
+            self.non_null_count += delta;
+            for (bit, count) in self.bit_counts.iter_mut().enumerate() {
+                if bits & (1 << bit) != 0 {
+                    *count += delta;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the output of the state.
+    pub async fn get_output(&mut self) -> Result<Datum> {
+        debug_assert!(!self.is_dirty());
+        if self.non_null_count == 0 {
+            return Ok(None);
+        }
+        let bits = self
+            .bit_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| {
+                if self.all {
+                    **count == self.non_null_count
+                } else {
+                    **count > 0
+                }
+            })
+            .fold(0u64, |bits, (bit, _)| bits | (1 << bit));
+        Ok(Some(match self.return_type {
+            DataType::Boolean => ScalarImpl::Bool(bits != 0),
+            DataType::Int16 => ScalarImpl::Int16(bits as i16),
+            DataType::Int32 => ScalarImpl::Int32(bits as i32),
+            DataType::Int64 => ScalarImpl::Int64(bits as i64),
+            _ => unreachable!(),
+        }))
+    }
+
+    /// Check if this state needs a flush.
+    pub fn is_dirty(&self) -> bool {
+        self.is_dirty
+    }
+
+    /// Flush the internal state to a write batch.
+    pub fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
+        debug_assert!(self.is_dirty());
+
+        let mut bytes = Vec::with_capacity((self.bit_counts.len() + 1) * 8);
+        bytes.put_i64_le(self.non_null_count);
+        for count in &self.bit_counts {
+            bytes.put_i64_le(*count);
+        }
+        let mut local = write_batch.prefixify(&self.keyspace);
+        local.put_single(StorageValue::new_default_put(bytes));
+        self.is_dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{BoolArray, I32Array};
+
+    use super::*;
+    use crate::executor::test_utils::create_in_memory_keyspace;
+    use crate::executor::AggArgs;
+
+    fn create_test_agg_call(kind: AggKind, data_type: DataType) -> AggCall {
+        AggCall {
+            kind,
+            args: AggArgs::Unary(data_type.clone(), 0),
+            return_type: data_type,
+        }
+    }
+
+    async fn apply_and_flush<S: StateStore>(
+        state: &mut ManagedBitCountState<S>,
+        keyspace: &Keyspace<S>,
+        ops: Ops<'_>,
+        data: ArrayImpl,
+    ) {
+        state.apply_batch(ops, None, &[&data]).await.unwrap();
+        let mut write_batch = keyspace.state_store().start_write_batch();
+        state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_managed_bool_and_state() {
+        let keyspace = create_in_memory_keyspace();
+        let agg_call = create_test_agg_call(AggKind::BoolAnd, DataType::Boolean);
+        let mut state = ManagedBitCountState::new(agg_call.clone(), keyspace.clone(), Some(0))
+            .await
+            .unwrap();
+        assert_eq!(state.get_output().await.unwrap(), None);
+
+        let data = BoolArray::from_slice(&[Some(true), Some(false), None])
+            .unwrap()
+            .into();
+        apply_and_flush(
+            &mut state,
+            &keyspace,
+            &[Op::Insert, Op::Insert, Op::Insert],
+            data,
+        )
+        .await;
+        assert_eq!(
+            state.get_output().await.unwrap(),
+            Some(ScalarImpl::Bool(false))
+        );
+
+        // retract the false value after recovery
+        let mut state = ManagedBitCountState::new(agg_call, keyspace.clone(), None)
+            .await
+            .unwrap();
+        let data = BoolArray::from_slice(&[Some(false)]).unwrap().into();
+        apply_and_flush(&mut state, &keyspace, &[Op::Delete], data).await;
+        assert_eq!(
+            state.get_output().await.unwrap(),
+            Some(ScalarImpl::Bool(true))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_managed_bit_or_state() {
+        let keyspace = create_in_memory_keyspace();
+        let agg_call = create_test_agg_call(AggKind::BitOr, DataType::Int32);
+        let mut state = ManagedBitCountState::new(agg_call, keyspace.clone(), Some(0))
+            .await
+            .unwrap();
+
+        let data = I32Array::from_slice(&[Some(0b0011), Some(-1), Some(0b0110)])
+            .unwrap()
+            .into();
+        apply_and_flush(
+            &mut state,
+            &keyspace,
+            &[Op::Insert, Op::Insert, Op::Insert],
+            data,
+        )
+        .await;
+        assert_eq!(
+            state.get_output().await.unwrap(),
+            Some(ScalarImpl::Int32(-1))
+        );
+
+        let data = I32Array::from_slice(&[Some(-1), Some(0b0011)])
+            .unwrap()
+            .into();
+        apply_and_flush(
+            &mut state,
+            &keyspace,
+            &[Op::UpdateDelete, Op::UpdateInsert],
+            data,
+        )
+        .await;
+        assert_eq!(
+            state.get_output().await.unwrap(),
+            Some(ScalarImpl::Int32(0b0111))
+        );
+    }
+}
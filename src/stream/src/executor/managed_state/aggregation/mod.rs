@@ -14,6 +14,8 @@
 
 //! Aggregators with state store support
 
+pub use array_agg::*;
+pub use bit_count::*;
 pub use extreme::*;
 use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::ArrayImpl;
@@ -23,12 +25,16 @@ use risingwave_common::types::Datum;
 use risingwave_expr::expr::AggKind;
 use risingwave_storage::write_batch::WriteBatch;
 use risingwave_storage::{Keyspace, StateStore};
+pub use sketch::*;
 pub use value::*;
 
 use super::super::{AggCall, PkDataTypes};
 
+mod array_agg;
+mod bit_count;
 mod extreme;
 mod extreme_serializer;
+mod sketch;
 mod string_agg;
 mod value;
 
@@ -56,6 +62,12 @@ pub enum ManagedStateImpl<S: StateStore> {
 
     /// States as table structure e.g. `MAX`, `STRING_AGG`
     Table(Box<dyn ManagedTableState<S>>),
+
+    /// States as serialized sketches e.g. `APPROX_COUNT_DISTINCT`
+    Sketch(ManagedSketchState<S>),
+
+    /// States as counts of set bits e.g. `BIT_AND`, `BOOL_OR`
+    BitCount(ManagedBitCountState<S>),
 }
 
 impl<S: StateStore> ManagedStateImpl<S> {
@@ -69,6 +81,8 @@ impl<S: StateStore> ManagedStateImpl<S> {
         match self {
             Self::Value(state) => state.apply_batch(ops, visibility, data).await,
             Self::Table(state) => state.apply_batch(ops, visibility, data, epoch).await,
+            Self::Sketch(state) => state.apply_batch(ops, visibility, data).await,
+            Self::BitCount(state) => state.apply_batch(ops, visibility, data).await,
         }
     }
 
@@ -77,6 +91,8 @@ impl<S: StateStore> ManagedStateImpl<S> {
         match self {
             Self::Value(state) => state.get_output().await,
             Self::Table(state) => state.get_output(epoch).await,
+            Self::Sketch(state) => state.get_output().await,
+            Self::BitCount(state) => state.get_output().await,
         }
     }
 
@@ -85,6 +101,8 @@ impl<S: StateStore> ManagedStateImpl<S> {
         match self {
             Self::Value(state) => state.is_dirty(),
             Self::Table(state) => state.is_dirty(),
+            Self::Sketch(state) => state.is_dirty(),
+            Self::BitCount(state) => state.is_dirty(),
         }
     }
 
@@ -93,6 +111,8 @@ impl<S: StateStore> ManagedStateImpl<S> {
         match self {
             Self::Value(state) => state.flush(write_batch),
             Self::Table(state) => state.flush(write_batch),
+            Self::Sketch(state) => state.flush(write_batch),
+            Self::BitCount(state) => state.flush(write_batch),
         }
     }
 
@@ -151,6 +171,40 @@ impl<S: StateStore> ManagedStateImpl<S> {
             AggKind::SingleValue => Ok(Self::Value(
                 ManagedValueState::new(agg_call, keyspace, row_count).await?,
             )),
+            AggKind::ApproxCountDistinct | AggKind::ApproxPercentile(_) => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                Ok(Self::Sketch(
+                    ManagedSketchState::new(agg_call, keyspace, row_count).await?,
+                ))
+            }
+            AggKind::BoolAnd | AggKind::BoolOr | AggKind::BitAnd | AggKind::BitOr => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                Ok(Self::BitCount(
+                    ManagedBitCountState::new(agg_call, keyspace, row_count).await?,
+                ))
+            }
+            AggKind::ArrayAgg => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                Ok(Self::Table(Box::new(
+                    ManagedArrayAggState::new(agg_call, keyspace, row_count).await?,
+                )))
+            }
+            AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp => {
+                Err(ErrorCode::InternalError(format!(
+                    "{} should have been rewritten with sum and count",
+                    agg_call.kind
+                ))
+                .into())
+            }
         }
     }
 }
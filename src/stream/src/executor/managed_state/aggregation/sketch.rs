@@ -0,0 +1,250 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::{ArrayImpl, Op};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{Datum, ScalarImpl};
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::vector_op::agg::{HyperLogLog, KllSketch};
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::AggCall;
+
+enum Sketch {
+    CountDistinct(HyperLogLog),
+    Percentile(f64, KllSketch),
+}
+
+/// Manages the state of approximate aggregations, e.g. `APPROX_COUNT_DISTINCT`. The whole sketch is
+/// serialized as a single value in the keyspace, so it survives recovery.
+///
+/// Sketches cannot forget values, so only append-only inputs are supported.
+pub struct ManagedSketchState<S: StateStore> {
+    sketch: Sketch,
+
+    /// The keyspace to operate on.
+    keyspace: Keyspace<S>,
+
+    /// Indicates whether this managed state is dirty. If this state is dirty, we cannot evict the
+    /// state from memory.
+    is_dirty: bool,
+}
+
+impl<S: StateStore> ManagedSketchState<S> {
+    /// Create a sketch managed state based on `AggCall` and `Keyspace`.
+    pub async fn new(
+        agg_call: AggCall,
+        keyspace: Keyspace<S>,
+        row_count: Option<usize>,
+    ) -> Result<Self> {
+        let raw_data = if row_count != Some(0) {
+            // TODO: use the correct epoch
+            keyspace.value(u64::MAX).await?
+        } else {
+            None
+        };
+
+        let sketch = match agg_call.kind {
+            AggKind::ApproxCountDistinct => Sketch::CountDistinct(match raw_data {
+                Some(raw_data) => HyperLogLog::from_bytes(&raw_data)?,
+                None => HyperLogLog::new(),
+            }),
+            AggKind::ApproxPercentile(percentile) => Sketch::Percentile(
+                percentile.0,
+                match raw_data {
+                    Some(raw_data) => KllSketch::from_bytes(&raw_data)?,
+                    None => KllSketch::new(),
+                },
+            ),
+            kind => {
+                return Err(ErrorCode::InternalError(format!(
+                    "{} is not an approximate aggregation",
+                    kind
+                ))
+                .into())
+            }
+        };
+
+        Ok(Self {
+            sketch,
+            keyspace,
+            is_dirty: false,
+        })
+    }
+
+    /// Apply a batch of data to the state.
+    pub async fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> Result<()> {
+        debug_assert!(super::verify_batch(ops, visibility, data));
+        self.is_dirty = true;
+        let array = data[0];
+        for (idx, op) in ops.iter().enumerate() {
+            if let Some(visibility) = visibility && !visibility.is_set(idx)? {
+                continue;
+            }
+            match op {
+                Op::Insert | Op::UpdateInsert => match &mut self.sketch {
+                    Sketch::CountDistinct(sketch) => sketch.add(array, idx)?,
+                    Sketch::Percentile(_, sketch) => sketch.add(array, idx)?,
+                },
+                Op::Delete | Op::UpdateDelete => {
+                    return Err(ErrorCode::NotImplemented(
+                        "retracting values from approximate aggregations".to_string(),
+                        None.into(),
+                    )
+                    .into())
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the output of the state.
+    pub async fn get_output(&mut self) -> Result<Datum> {
+        debug_assert!(!self.is_dirty());
+        Ok(match &self.sketch {
+            Sketch::CountDistinct(sketch) => Some(ScalarImpl::Int64(sketch.estimate())),
+            Sketch::Percentile(percentile, sketch) => sketch
+                .quantile(*percentile)
+                .map(|v| ScalarImpl::Float64(v.into())),
+        })
+    }
+
+    /// Check if this state needs a flush.
+    pub fn is_dirty(&self) -> bool {
+        self.is_dirty
+    }
+
+    /// Flush the internal state to a write batch.
+    pub fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
+        debug_assert!(self.is_dirty());
+
+        let bytes = match &self.sketch {
+            Sketch::CountDistinct(sketch) => sketch.to_bytes(),
+            Sketch::Percentile(_, sketch) => sketch.to_bytes(),
+        };
+        let mut local = write_batch.prefixify(&self.keyspace);
+        local.put_single(StorageValue::new_default_put(bytes));
+        self.is_dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::I64Array;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::create_in_memory_keyspace;
+    use crate::executor::AggArgs;
+
+    fn create_test_agg_call(kind: AggKind, return_type: DataType) -> AggCall {
+        AggCall {
+            kind,
+            args: AggArgs::Unary(DataType::Int64, 0),
+            return_type,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_managed_sketch_state() {
+        let keyspace = create_in_memory_keyspace();
+        let agg_call = create_test_agg_call(AggKind::ApproxCountDistinct, DataType::Int64);
+        let mut managed_state =
+            ManagedSketchState::new(agg_call.clone(), keyspace.clone(), Some(0))
+                .await
+                .unwrap();
+        assert!(!managed_state.is_dirty());
+
+        let data = I64Array::from_slice(&[Some(1), Some(2), Some(1), None])
+            .unwrap()
+            .into();
+        managed_state
+            .apply_batch(
+                &[Op::Insert, Op::Insert, Op::Insert, Op::Insert],
+                None,
+                &[&data],
+            )
+            .await
+            .unwrap();
+        assert!(managed_state.is_dirty());
+
+        let mut write_batch = keyspace.state_store().start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(0).await.unwrap();
+        assert_eq!(
+            managed_state.get_output().await.unwrap(),
+            Some(ScalarImpl::Int64(2))
+        );
+
+        // reload the state and check the output
+        let mut managed_state = ManagedSketchState::new(agg_call, keyspace, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            managed_state.get_output().await.unwrap(),
+            Some(ScalarImpl::Int64(2))
+        );
+
+        // retractions are rejected
+        assert!(managed_state
+            .apply_batch(
+                &[Op::Delete],
+                None,
+                &[&I64Array::from_slice(&[Some(1)]).unwrap().into()]
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_managed_percentile_state() {
+        let keyspace = create_in_memory_keyspace();
+        let agg_call =
+            create_test_agg_call(AggKind::ApproxPercentile(0.5.into()), DataType::Float64);
+        let mut managed_state =
+            ManagedSketchState::new(agg_call.clone(), keyspace.clone(), Some(0))
+                .await
+                .unwrap();
+        assert_eq!(managed_state.get_output().await.unwrap(), None);
+
+        let data = I64Array::from_slice(&[Some(3), Some(1), Some(2)])
+            .unwrap()
+            .into();
+        managed_state
+            .apply_batch(&[Op::Insert, Op::Insert, Op::Insert], None, &[&data])
+            .await
+            .unwrap();
+        let mut write_batch = keyspace.state_store().start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(0).await.unwrap();
+
+        let mut managed_state = ManagedSketchState::new(agg_call, keyspace, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            managed_state.get_output().await.unwrap(),
+            Some(ScalarImpl::Float64(2.0.into()))
+        );
+    }
+}
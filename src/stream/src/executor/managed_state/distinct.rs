@@ -0,0 +1,127 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashSet;
+
+use risingwave_common::array::Row;
+use risingwave_common::collection::evictable::EvictableHashMap;
+use risingwave_common::error::Result;
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::{Keyspace, StateStore};
+
+/// The row counts of the distinct keys, which are cached in memory and persisted in the state
+/// store under `keyspace`, keyed by the serialized keys. A key whose count drops to 0 is removed
+/// from the state store.
+pub struct ManagedDistinctState<S: StateStore> {
+    keyspace: Keyspace<S>,
+
+    /// The cached row counts. The keys not in the state store have a count of 0.
+    cache: EvictableHashMap<Row, i64>,
+
+    /// The keys whose counts are modified since the last flush. They are never evicted.
+    dirty_keys: HashSet<Row>,
+}
+
+impl<S: StateStore> ManagedDistinctState<S> {
+    pub fn new(keyspace: Keyspace<S>, cache_size: usize) -> Self {
+        Self {
+            keyspace,
+            cache: EvictableHashMap::new(cache_size),
+            dirty_keys: HashSet::new(),
+        }
+    }
+
+    async fn count_mut(&mut self, key: &Row, epoch: u64) -> Result<&mut i64> {
+        if !self.cache.contains(key) {
+            let count = match self.keyspace.get(key.serialize()?, epoch).await? {
+                Some(bytes) => i64::from_be_bytes(bytes.as_ref().try_into().unwrap()),
+                None => 0,
+            };
+            self.cache.put(key.clone(), count);
+        }
+        Ok(self.cache.get_mut(key).unwrap())
+    }
+
+    /// Get the row count of `key`.
+    pub async fn get(&mut self, key: &Row, epoch: u64) -> Result<i64> {
+        Ok(*self.count_mut(key, epoch).await?)
+    }
+
+    /// Add `delta` to the row count of `key`, and return the count before and after the change.
+    pub async fn apply(&mut self, key: Row, delta: i64, epoch: u64) -> Result<(i64, i64)> {
+        let count = self.count_mut(&key, epoch).await?;
+        let prev_count = *count;
+        *count += delta;
+        let count = *count;
+        self.dirty_keys.insert(key);
+        Ok((prev_count, count))
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty_keys.is_empty()
+    }
+
+    /// Write the modified counts to the state store, and then evict the cache to its target
+    /// capacity.
+    pub async fn flush(&mut self, epoch: u64) -> Result<()> {
+        if self.is_dirty() {
+            let mut write_batch = self.keyspace.state_store().start_write_batch();
+            let mut local = write_batch.prefixify(&self.keyspace);
+            for key in self.dirty_keys.drain() {
+                let count = *self.cache.peek(&key).unwrap();
+                if count == 0 {
+                    local.delete(key.serialize()?);
+                } else {
+                    local.put(
+                        key.serialize()?,
+                        StorageValue::new_default_put(count.to_be_bytes().to_vec()),
+                    );
+                }
+            }
+            write_batch.ingest(epoch).await?;
+        }
+        self.cache.evict_to_target_cap();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::executor::test_utils::create_in_memory_keyspace;
+
+    #[tokio::test]
+    async fn test_managed_distinct_state() {
+        let keyspace = create_in_memory_keyspace();
+        let key = Row(vec![Some(ScalarImpl::Int64(1))]);
+        let mut state = ManagedDistinctState::new(keyspace.clone(), 0);
+
+        assert_eq!(state.apply(key.clone(), 1, 0).await.unwrap(), (0, 1));
+        assert_eq!(state.apply(key.clone(), 1, 0).await.unwrap(), (1, 2));
+        state.flush(0).await.unwrap();
+        assert!(!state.is_dirty());
+
+        // The key has been evicted from the cache, and is loaded from the state store.
+        assert_eq!(state.apply(key.clone(), -1, 1).await.unwrap(), (2, 1));
+        state.flush(1).await.unwrap();
+
+        // Recover from the state store.
+        let mut state = ManagedDistinctState::new(keyspace, 0);
+        assert_eq!(state.get(&key, 2).await.unwrap(), 1);
+        assert_eq!(state.apply(key.clone(), -1, 2).await.unwrap(), (1, 0));
+        state.flush(2).await.unwrap();
+        assert_eq!(state.get(&key, 3).await.unwrap(), 0);
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod aggregation;
+pub mod distinct;
 pub mod flush_status;
 pub mod join;
 pub mod top_n;
@@ -312,6 +312,18 @@ impl<S: StateStore, const TOP_N_TYPE: usize> ManagedTopNState<S, TOP_N_TYPE> {
         Ok(())
     }
 
+    /// Loads a state whose total count is unknown, e.g. the state of a group which is first
+    /// accessed by `GroupTopNExecutor`. Unlike `fill_in_cache`, it has to scan all the elements
+    /// from the storage to count them.
+    pub async fn init(&mut self, epoch: u64) -> Result<()> {
+        debug_assert!(!self.is_dirty() && self.total_count == 0);
+        let kv_pairs = self.scan_from_storage(None, epoch).await?;
+        self.total_count = kv_pairs.len();
+        self.top_n.extend(kv_pairs);
+        self.retain_top_n();
+        Ok(())
+    }
+
     async fn flush_inner(
         &mut self,
         iterator: impl Iterator<Item = (OrderedRow, FlushStatus<Row>)>,
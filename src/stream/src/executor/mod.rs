@@ -23,13 +23,17 @@ use async_trait::async_trait;
 pub use batch_query::*;
 pub use chain::*;
 pub use debug::*;
+pub use dedup_appendonly::*;
 pub use dispatch::*;
+pub use distinct::*;
 use enum_as_inner::EnumAsInner;
 pub use filter::*;
 use futures::Stream;
 pub use global_simple_agg::*;
+pub use group_top_n::*;
 pub use hash_agg::*;
 pub use hash_join::*;
+pub use local_hash_agg::*;
 pub use local_simple_agg::*;
 pub use merge::*;
 pub use monitor::*;
@@ -66,11 +70,15 @@ mod barrier_align;
 mod batch_query;
 mod chain;
 mod debug;
+mod dedup_appendonly;
 mod dispatch;
+mod distinct;
 mod filter;
 mod global_simple_agg;
+mod group_top_n;
 mod hash_agg;
 mod hash_join;
+mod local_hash_agg;
 mod local_simple_agg;
 pub(crate) mod managed_state;
 mod merge;
@@ -502,7 +510,11 @@ pub fn create_executor(
         Node::ProjectNode => ProjectExecutorBuilder,
         Node::TopNNode => TopNExecutorBuilder,
         Node::AppendOnlyTopNNode => AppendOnlyTopNExecutorBuilder,
+        Node::GroupTopNNode => GroupTopNExecutorBuilder,
+        Node::DistinctNode => DistinctExecutorBuilder,
+        Node::AppendOnlyDedupNode => AppendOnlyDedupExecutorBuilder,
         Node::LocalSimpleAggNode => LocalSimpleAggExecutorBuilder,
+        Node::LocalHashAggNode => LocalHashAggExecutorBuilder,
         Node::GlobalSimpleAggNode => SimpleAggExecutorBuilder,
         Node::HashAggNode => HashAggExecutorBuilder,
         Node::HashJoinNode => HashJoinExecutorBuilder,
@@ -13,11 +13,16 @@
 // limitations under the License.
 
 use prometheus::core::{AtomicU64, GenericCounterVec};
-use prometheus::{register_int_counter_vec_with_registry, Registry};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntGaugeVec,
+    Registry,
+};
 
 pub struct StreamingMetrics {
     pub registry: Registry,
     pub actor_row_count: GenericCounterVec<AtomicU64>,
+    pub actor_output_buffer_blocking_duration_ns: GenericCounterVec<AtomicU64>,
+    pub actor_latest_epoch: IntGaugeVec,
 
     pub source_output_row_count: GenericCounterVec<AtomicU64>,
 }
@@ -32,6 +37,22 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let actor_output_buffer_blocking_duration_ns = register_int_counter_vec_with_registry!(
+            "stream_actor_output_buffer_blocking_duration_ns",
+            "Total time the dispatcher of each actor is blocked on sending to downstream actors",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
+        let actor_latest_epoch = register_int_gauge_vec_with_registry!(
+            "stream_actor_latest_epoch",
+            "Epoch of the latest barrier dispatched by each actor",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
         let source_output_row_count = register_int_counter_vec_with_registry!(
             "stream_source_output_rows_counts",
             "Total number of rows that have been output from source",
@@ -43,6 +64,8 @@ impl StreamingMetrics {
         Self {
             registry,
             actor_row_count,
+            actor_output_buffer_blocking_duration_ns,
+            actor_latest_epoch,
             source_output_row_count,
         }
     }
@@ -0,0 +1,226 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::ToOwnedDatum;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::error::{StreamExecutorError, TracedStreamExecutorError};
+use super::top_n_executor::generate_output;
+use super::{BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndicesRef};
+use crate::executor::managed_state::distinct::ManagedDistinctState;
+
+/// `AppendOnlyDedupExecutor` is the [`DistinctExecutor`](super::DistinctExecutor) for append-only
+/// inputs. As rows are never deleted, it only outputs the first occurrence of each distinct row,
+/// and the row counts in the state never grow beyond 1.
+pub struct AppendOnlyDedupExecutor<S: StateStore> {
+    input: BoxedExecutor,
+
+    info: ExecutorInfo,
+
+    /// Indices of the columns to deduplicate by, which are the output columns.
+    dedup_keys: Vec<usize>,
+
+    /// The distinct rows which have been seen.
+    state: ManagedDistinctState<S>,
+}
+
+impl<S: StateStore> AppendOnlyDedupExecutor<S> {
+    pub fn new(
+        input: BoxedExecutor,
+        dedup_keys: Vec<usize>,
+        keyspace: Keyspace<S>,
+        cache_size: usize,
+        executor_id: u64,
+    ) -> Self {
+        let schema = Schema::new(
+            dedup_keys
+                .iter()
+                .map(|&idx| input.schema().fields()[idx].clone())
+                .collect(),
+        );
+        let pk_indices = (0..dedup_keys.len()).collect();
+        Self {
+            input,
+            info: ExecutorInfo {
+                schema,
+                pk_indices,
+                identity: format!("AppendOnlyDedupExecutor {:X}", executor_id),
+            },
+            dedup_keys,
+            state: ManagedDistinctState::new(keyspace, cache_size),
+        }
+    }
+
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn execute_inner(self: Box<Self>) {
+        let Self {
+            input,
+            info,
+            dedup_keys,
+            mut state,
+        } = *self;
+        let mut input = input.execute();
+        let first_msg = input.next().await.unwrap()?;
+        let barrier = first_msg
+            .as_barrier()
+            .expect("the first message received by dedup executor must be a barrier");
+        let mut epoch = barrier.epoch.curr;
+        yield first_msg;
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    let mut new_rows = vec![];
+                    for row_ref in chunk.rows() {
+                        assert_eq!(row_ref.op(), Op::Insert);
+
+                        let row = Row(dedup_keys
+                            .iter()
+                            .map(|&idx| row_ref.value_at(idx).to_owned_datum())
+                            .collect());
+                        let count = state
+                            .get(&row, epoch)
+                            .await
+                            .map_err(StreamExecutorError::agg_state_error)?;
+                        if count == 0 {
+                            state
+                                .apply(row.clone(), 1, epoch)
+                                .await
+                                .map_err(StreamExecutorError::agg_state_error)?;
+                            new_rows.push(row);
+                        }
+                    }
+                    if !new_rows.is_empty() {
+                        let new_ops = vec![Op::Insert; new_rows.len()];
+                        yield Message::Chunk(generate_output(new_rows, new_ops, &info.schema)?);
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    state
+                        .flush(epoch)
+                        .await
+                        .map_err(StreamExecutorError::agg_state_error)?;
+                    epoch = barrier.epoch.curr;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for AppendOnlyDedupExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use itertools::Itertools;
+    use risingwave_common::array::{Array, I64Array};
+    use risingwave_common::catalog::Field;
+    use risingwave_common::column_nonnull;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor_v2::{Barrier, PkIndices};
+
+    #[tokio::test]
+    async fn test_append_only_dedup_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        // Rows of `(id, a)`, deduplicated by `a`.
+        let chunk1 = StreamChunk::new(
+            vec![Op::Insert; 4],
+            vec![
+                column_nonnull! { I64Array, [0, 1, 2, 3] },
+                column_nonnull! { I64Array, [1, 2, 1, 3] },
+            ],
+            None,
+        );
+        let chunk2 = StreamChunk::new(
+            vec![Op::Insert; 2],
+            vec![
+                column_nonnull! { I64Array, [4, 5] },
+                column_nonnull! { I64Array, [2, 4] },
+            ],
+            None,
+        );
+        let source = MockSource::with_messages(
+            schema,
+            PkIndices::new(),
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk1),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(chunk2),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        );
+        let mut dedup_executor = Box::new(AppendOnlyDedupExecutor::new(
+            Box::new(source),
+            vec![1],
+            create_in_memory_keyspace(),
+            0,
+            1,
+        ))
+        .execute();
+
+        // consume the init barrier
+        dedup_executor.next().await.unwrap().unwrap();
+        let res = dedup_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                res.column_at(0).array_ref().as_int64().iter().collect_vec(),
+                vec![Some(1), Some(2), Some(3)]
+            );
+        }
+        assert_matches!(
+            dedup_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        // `2` has been seen in the previous epoch, which is looked up in the state store.
+        let res = dedup_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                res.column_at(0).array_ref().as_int64().iter().collect_vec(),
+                vec![Some(4)]
+            );
+        }
+    }
+}
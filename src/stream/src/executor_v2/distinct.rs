@@ -0,0 +1,255 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::ToOwnedDatum;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::error::{StreamExecutorError, TracedStreamExecutorError};
+use super::top_n_executor::generate_output;
+use super::{BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndicesRef};
+use crate::executor::managed_state::distinct::ManagedDistinctState;
+
+/// `DistinctExecutor` outputs the distinct rows of the `distinct_keys` columns of its input, which
+/// is planned from an aggregation without aggregate calls. The row count of each distinct row is
+/// kept in the state, so that a row is inserted when its count becomes positive, and deleted when
+/// its count drops to 0.
+///
+/// For an append-only input, [`AppendOnlyDedupExecutor`](super::AppendOnlyDedupExecutor) is
+/// cheaper.
+pub struct DistinctExecutor<S: StateStore> {
+    input: BoxedExecutor,
+
+    info: ExecutorInfo,
+
+    /// Indices of the columns to deduplicate by, which are the output columns.
+    distinct_keys: Vec<usize>,
+
+    /// The row counts of the distinct rows.
+    state: ManagedDistinctState<S>,
+}
+
+impl<S: StateStore> DistinctExecutor<S> {
+    pub fn new(
+        input: BoxedExecutor,
+        distinct_keys: Vec<usize>,
+        keyspace: Keyspace<S>,
+        cache_size: usize,
+        executor_id: u64,
+    ) -> Self {
+        let schema = Schema::new(
+            distinct_keys
+                .iter()
+                .map(|&idx| input.schema().fields()[idx].clone())
+                .collect(),
+        );
+        let pk_indices = (0..distinct_keys.len()).collect();
+        Self {
+            input,
+            info: ExecutorInfo {
+                schema,
+                pk_indices,
+                identity: format!("DistinctExecutor {:X}", executor_id),
+            },
+            distinct_keys,
+            state: ManagedDistinctState::new(keyspace, cache_size),
+        }
+    }
+
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn execute_inner(self: Box<Self>) {
+        let Self {
+            input,
+            info,
+            distinct_keys,
+            mut state,
+        } = *self;
+        let mut input = input.execute();
+        let first_msg = input.next().await.unwrap()?;
+        let barrier = first_msg
+            .as_barrier()
+            .expect("the first message received by distinct executor must be a barrier");
+        let mut epoch = barrier.epoch.curr;
+        yield first_msg;
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    let mut new_ops = vec![];
+                    let mut new_rows = vec![];
+                    for row_ref in chunk.rows() {
+                        let row = Row(distinct_keys
+                            .iter()
+                            .map(|&idx| row_ref.value_at(idx).to_owned_datum())
+                            .collect());
+                        let delta = match row_ref.op() {
+                            Op::Insert | Op::UpdateInsert => 1,
+                            Op::Delete | Op::UpdateDelete => -1,
+                        };
+                        let (prev_count, count) = state
+                            .apply(row.clone(), delta, epoch)
+                            .await
+                            .map_err(StreamExecutorError::agg_state_error)?;
+                        if prev_count == 0 && count > 0 {
+                            new_ops.push(Op::Insert);
+                            new_rows.push(row);
+                        } else if prev_count > 0 && count == 0 {
+                            new_ops.push(Op::Delete);
+                            new_rows.push(row);
+                        }
+                    }
+                    if !new_rows.is_empty() {
+                        yield Message::Chunk(generate_output(new_rows, new_ops, &info.schema)?);
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    state
+                        .flush(epoch)
+                        .await
+                        .map_err(StreamExecutorError::agg_state_error)?;
+                    epoch = barrier.epoch.curr;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for DistinctExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use itertools::Itertools;
+    use risingwave_common::array::I64Array;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::column_nonnull;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor_v2::{Barrier, PkIndices};
+
+    fn chunk_rows(chunk: &StreamChunk) -> Vec<(Op, Vec<Option<i64>>)> {
+        chunk
+            .rows()
+            .map(|row| {
+                let values = (0..2)
+                    .map(|i| row.value_at(i).map(|datum| datum.into_int64()))
+                    .collect_vec();
+                (row.op(), values)
+            })
+            .collect_vec()
+    }
+
+    #[tokio::test]
+    async fn test_distinct_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        // Rows of `(a, id, b)`, deduplicated by `(a, b)`.
+        let chunk1 = StreamChunk::new(
+            vec![Op::Insert; 4],
+            vec![
+                column_nonnull! { I64Array, [1, 1, 2, 1] },
+                column_nonnull! { I64Array, [0, 1, 2, 3] },
+                column_nonnull! { I64Array, [1, 1, 2, 3] },
+            ],
+            None,
+        );
+        let chunk2 = StreamChunk::new(
+            vec![Op::Delete, Op::Delete, Op::Delete, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 1, 2] },
+                column_nonnull! { I64Array, [0, 2, 1, 4] },
+                column_nonnull! { I64Array, [1, 2, 1, 2] },
+            ],
+            None,
+        );
+        let source = MockSource::with_messages(
+            schema,
+            PkIndices::new(),
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk1),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(chunk2),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        );
+        let mut distinct_executor = Box::new(DistinctExecutor::new(
+            Box::new(source),
+            vec![0, 2],
+            create_in_memory_keyspace(),
+            0,
+            1,
+        ))
+        .execute();
+
+        // consume the init barrier
+        distinct_executor.next().await.unwrap().unwrap();
+        let res = distinct_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                chunk_rows(&res),
+                vec![
+                    (Op::Insert, vec![Some(1), Some(1)]),
+                    (Op::Insert, vec![Some(2), Some(2)]),
+                    (Op::Insert, vec![Some(1), Some(3)]),
+                ]
+            );
+        }
+        assert_matches!(
+            distinct_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        // The counts are loaded from the state store, since the cache is evicted on barriers.
+        let res = distinct_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                chunk_rows(&res),
+                vec![
+                    (Op::Delete, vec![Some(2), Some(2)]),
+                    (Op::Delete, vec![Some(1), Some(1)]),
+                    (Op::Insert, vec![Some(2), Some(2)]),
+                ]
+            );
+        }
+    }
+}
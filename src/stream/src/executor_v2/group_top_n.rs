@@ -0,0 +1,616 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use async_trait::async_trait;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema};
+use risingwave_common::collection::evictable::EvictableHashMap;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::util::ordered::{OrderedRow, OrderedRowDeserializer};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::managed_state::top_n::variants::{TOP_N_MAX, TOP_N_MIN};
+use crate::executor::managed_state::top_n::ManagedTopNState;
+use crate::executor_v2::error::{StreamExecutorError, StreamExecutorResult};
+use crate::executor_v2::top_n_executor::{generate_output, TopNExecutorBase, TopNExecutorWrapper};
+use crate::executor_v2::{BoxedMessageStream, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
+
+/// `GroupTopNExecutor` keeps the top `limit` rows of each group, which is planned from the
+/// `ROW_NUMBER() OVER (PARTITION BY ...) <= N` pattern. The rows are ordered by the primary key,
+/// and the rows of each group are kept in their own managed states under the keyspace of the group
+/// key, which are loaded from the storage when the group is first accessed. At most
+/// `group_cache_size` groups are kept in memory after each barrier.
+pub type GroupTopNExecutor<S> = TopNExecutorWrapper<InnerGroupTopNExecutor<S>>;
+
+impl<S: StateStore> GroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input: Box<dyn Executor>,
+        pk_order_types: Vec<OrderType>,
+        limit: usize,
+        pk_indices: PkIndices,
+        keyspace: Keyspace<S>,
+        cache_size: Option<usize>,
+        group_cache_size: usize,
+        executor_id: u64,
+        group_keys: Vec<usize>,
+        key_indices: Vec<usize>,
+    ) -> Result<Self> {
+        let info = input.info();
+        let schema = input.schema().clone();
+
+        Ok(TopNExecutorWrapper {
+            input,
+            inner: InnerGroupTopNExecutor::new(
+                info,
+                schema,
+                pk_order_types,
+                limit,
+                pk_indices,
+                keyspace,
+                cache_size,
+                group_cache_size,
+                executor_id,
+                group_keys,
+                key_indices,
+            ),
+        })
+    }
+}
+
+/// Key of the number of rows in `[limit, +inf)` of a group, under the keyspace of the group.
+const REST_COUNT_KEY: [u8; 1] = [b'c'];
+
+/// The rows of a group.
+struct GroupState<S: StateStore> {
+    /// Rows in the range of `[0, limit)`, which are the output of the group.
+    managed_top_state: ManagedTopNState<S, TOP_N_MAX>,
+    /// Rows in the range of `[limit, +inf)`.
+    managed_rest_state: ManagedTopNState<S, TOP_N_MIN>,
+    /// The keyspace of the group.
+    keyspace: Keyspace<S>,
+    /// The number of rows in `[limit, +inf)` in the storage, which is persisted along with the
+    /// rows so that loading the group does not need to scan all of them.
+    persisted_rest_count: usize,
+}
+
+impl<S: StateStore> GroupState<S> {
+    async fn insert(
+        &mut self,
+        ordered_pk_row: OrderedRow,
+        row: Row,
+        limit: usize,
+        epoch: u64,
+        new_ops: &mut Vec<Op>,
+        new_rows: &mut Vec<Row>,
+    ) -> StreamExecutorResult<()> {
+        if self.managed_top_state.total_count() < limit {
+            // `row` is in the range of `[0, limit)`.
+            self.managed_top_state
+                .insert(ordered_pk_row, row.clone(), epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+            new_ops.push(Op::Insert);
+            new_rows.push(row);
+            return Ok(());
+        }
+
+        if &ordered_pk_row < self.managed_top_state.top_element().unwrap().0 {
+            // The largest row in `[0, limit)` is pushed to `[limit, +inf)` by `row`.
+            let (largest_pk, largest_row) = self
+                .managed_top_state
+                .pop_top_element(epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?
+                .unwrap();
+            new_ops.push(Op::Delete);
+            new_rows.push(largest_row.clone());
+            self.managed_rest_state
+                .insert(largest_pk, largest_row, epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+            new_ops.push(Op::Insert);
+            new_rows.push(row.clone());
+            self.managed_top_state
+                .insert(ordered_pk_row, row, epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+        } else {
+            // `row` is in the range of `[limit, +inf)`.
+            self.managed_rest_state
+                .insert(ordered_pk_row, row, epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+        }
+        Ok(())
+    }
+
+    async fn delete(
+        &mut self,
+        ordered_pk_row: OrderedRow,
+        row: Row,
+        epoch: u64,
+        new_ops: &mut Vec<Op>,
+        new_rows: &mut Vec<Row>,
+    ) -> StreamExecutorResult<()> {
+        let in_top = match self.managed_top_state.top_element() {
+            Some((largest_pk, _)) => ordered_pk_row <= *largest_pk,
+            None => false,
+        };
+        if in_top {
+            self.managed_top_state
+                .delete(&ordered_pk_row, epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+            new_ops.push(Op::Delete);
+            new_rows.push(row);
+            // Bring the smallest row, if any, from `[limit, +inf)` to `[0, limit)`.
+            if let Some((smallest_pk, smallest_row)) = self
+                .managed_rest_state
+                .pop_top_element(epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?
+            {
+                new_ops.push(Op::Insert);
+                new_rows.push(smallest_row.clone());
+                self.managed_top_state
+                    .insert(smallest_pk, smallest_row, epoch)
+                    .await
+                    .map_err(StreamExecutorError::top_n_state_error)?;
+            }
+        } else {
+            self.managed_rest_state
+                .delete(&ordered_pk_row, epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        self.managed_top_state
+            .flush(epoch)
+            .await
+            .map_err(StreamExecutorError::top_n_state_error)?;
+        self.managed_rest_state
+            .flush(epoch)
+            .await
+            .map_err(StreamExecutorError::top_n_state_error)?;
+
+        let rest_count = self.managed_rest_state.total_count();
+        if rest_count != self.persisted_rest_count {
+            let mut write_batch = self.keyspace.state_store().start_write_batch();
+            let mut local = write_batch.prefixify(&self.keyspace);
+            if rest_count == 0 {
+                local.delete(REST_COUNT_KEY);
+            } else {
+                local.put(
+                    REST_COUNT_KEY,
+                    StorageValue::new_default_put((rest_count as u64).to_be_bytes().to_vec()),
+                );
+            }
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+            self.persisted_rest_count = rest_count;
+        }
+        Ok(())
+    }
+}
+
+pub struct InnerGroupTopNExecutor<S: StateStore> {
+    info: ExecutorInfo,
+
+    /// Schema of the executor.
+    schema: Schema,
+
+    /// The ordering
+    pk_order_types: Vec<OrderType>,
+    /// The number of rows to keep for each group.
+    limit: usize,
+
+    /// The primary key indices of the `GroupTopNExecutor`
+    pk_indices: PkIndices,
+
+    /// Indices of the columns to group by.
+    group_keys: Vec<usize>,
+
+    /// The states of the groups are under this keyspace, prefixed by the group keys.
+    keyspace: Keyspace<S>,
+
+    /// Number of rows to retain in memory for each state of each group.
+    cache_size: Option<usize>,
+
+    /// The groups which have been accessed. Clean groups beyond the target capacity are evicted
+    /// at barriers.
+    groups: EvictableHashMap<Row, GroupState<S>>,
+
+    row_data_types: Vec<DataType>,
+    ordered_row_deserializer: OrderedRowDeserializer,
+    cell_based_row_deserializer: CellBasedRowDeserializer,
+
+    #[allow(dead_code)]
+    /// Indices of the columns on which key distribution depends.
+    key_indices: Vec<usize>,
+}
+
+impl<S: StateStore> InnerGroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input_info: ExecutorInfo,
+        schema: Schema,
+        pk_order_types: Vec<OrderType>,
+        limit: usize,
+        pk_indices: PkIndices,
+        keyspace: Keyspace<S>,
+        cache_size: Option<usize>,
+        group_cache_size: usize,
+        executor_id: u64,
+        group_keys: Vec<usize>,
+        key_indices: Vec<usize>,
+    ) -> Self {
+        let pk_data_types = pk_indices
+            .iter()
+            .map(|idx| schema.fields[*idx].data_type())
+            .collect::<Vec<_>>();
+        let row_data_types = schema.data_types();
+        let ordered_row_deserializer =
+            OrderedRowDeserializer::new(pk_data_types, pk_order_types.clone());
+        let table_column_descs = row_data_types
+            .iter()
+            .enumerate()
+            .map(|(id, data_type)| {
+                ColumnDesc::unnamed(ColumnId::from(id as i32), data_type.clone())
+            })
+            .collect::<Vec<_>>();
+        let cell_based_row_deserializer = CellBasedRowDeserializer::new(table_column_descs);
+        Self {
+            info: ExecutorInfo {
+                schema: input_info.schema,
+                pk_indices: input_info.pk_indices,
+                identity: format!("GroupTopNExecutor {:X}", executor_id),
+            },
+            schema,
+            pk_order_types,
+            limit,
+            pk_indices,
+            group_keys,
+            keyspace,
+            cache_size,
+            groups: EvictableHashMap::new(group_cache_size),
+            row_data_types,
+            ordered_row_deserializer,
+            cell_based_row_deserializer,
+            key_indices,
+        }
+    }
+
+    /// Loads the state of a group from the storage. All rows in `[0, limit)` are loaded, while only
+    /// the first `cache_size` rows in `[limit, +inf)` are.
+    async fn load_group(&self, group_key: &Row, epoch: u64) -> StreamExecutorResult<GroupState<S>> {
+        let keyspace = self.keyspace.append(
+            group_key
+                .serialize()
+                .map_err(StreamExecutorError::top_n_state_error)?,
+        );
+        let mut managed_top_state = ManagedTopNState::<S, TOP_N_MAX>::new(
+            self.cache_size,
+            0,
+            keyspace.append_u8(b't'),
+            self.row_data_types.clone(),
+            self.ordered_row_deserializer.clone(),
+            self.cell_based_row_deserializer.clone(),
+        );
+        let rest_count = keyspace
+            .get(REST_COUNT_KEY, epoch)
+            .await
+            .map_err(StreamExecutorError::storage)?
+            .map_or(0, |bytes| {
+                u64::from_be_bytes(bytes.as_ref().try_into().unwrap()) as usize
+            });
+        let mut managed_rest_state = ManagedTopNState::<S, TOP_N_MIN>::new(
+            self.cache_size,
+            rest_count,
+            keyspace.append_u8(b'r'),
+            self.row_data_types.clone(),
+            self.ordered_row_deserializer.clone(),
+            self.cell_based_row_deserializer.clone(),
+        );
+        managed_top_state
+            .init(epoch)
+            .await
+            .map_err(StreamExecutorError::top_n_state_error)?;
+        if rest_count > 0 {
+            managed_rest_state
+                .fill_in_cache(epoch)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+        }
+        Ok(GroupState {
+            managed_top_state,
+            managed_rest_state,
+            keyspace,
+            persisted_rest_count: rest_count,
+        })
+    }
+}
+
+impl<S: StateStore> Executor for InnerGroupTopNExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        panic!("Should execute by wrapper");
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutor<S> {
+    async fn apply_chunk(
+        &mut self,
+        chunk: StreamChunk,
+        epoch: u64,
+    ) -> StreamExecutorResult<StreamChunk> {
+        let mut new_ops = vec![];
+        let mut new_rows = vec![];
+
+        for row_ref in chunk.rows() {
+            let group_key = Row(self
+                .group_keys
+                .iter()
+                .map(|&idx| row_ref.value_at(idx).to_owned_datum())
+                .collect());
+            let pk_row = Row(self
+                .pk_indices
+                .iter()
+                .map(|&idx| row_ref.value_at(idx).to_owned_datum())
+                .collect());
+            let ordered_pk_row = OrderedRow::new(pk_row, &self.pk_order_types);
+            let row = row_ref.to_owned_row();
+
+            if !self.groups.contains(&group_key) {
+                let group = self.load_group(&group_key, epoch).await?;
+                self.groups.put(group_key.clone(), group);
+            }
+            let group = self.groups.get_mut(&group_key).unwrap();
+
+            match row_ref.op() {
+                Op::Insert | Op::UpdateInsert => {
+                    group
+                        .insert(
+                            ordered_pk_row,
+                            row,
+                            self.limit,
+                            epoch,
+                            &mut new_ops,
+                            &mut new_rows,
+                        )
+                        .await?;
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    group
+                        .delete(ordered_pk_row, row, epoch, &mut new_ops, &mut new_rows)
+                        .await?;
+                }
+            }
+        }
+        generate_output(new_rows, new_ops, &self.schema)
+    }
+
+    async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        for group in self.groups.values_mut() {
+            group.flush(epoch).await?;
+        }
+        // All groups are clean after the flush.
+        self.groups.evict_to_target_cap();
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use itertools::Itertools;
+    use risingwave_common::array::{Array, I64Array};
+    use risingwave_common::catalog::Field;
+    use risingwave_common::column_nonnull;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor_v2::{Barrier, Message};
+
+    fn create_schema() -> Schema {
+        Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        }
+    }
+
+    fn create_executor(
+        messages: Vec<Message>,
+        keyspace: Keyspace<MemoryStateStore>,
+    ) -> Box<dyn Executor> {
+        let source = MockSource::with_messages(create_schema(), PkIndices::new(), messages);
+        // Keep the 2 rows with the smallest `(v, id)` of each group `g` in `(g, v, id)`. No group
+        // is kept in memory across barriers, so they are loaded from the storage in every epoch.
+        Box::new(
+            GroupTopNExecutor::new(
+                Box::new(source),
+                vec![OrderType::Ascending, OrderType::Ascending],
+                2,
+                vec![1, 2],
+                keyspace,
+                Some(1),
+                0,
+                1,
+                vec![0],
+                vec![0],
+            )
+            .unwrap(),
+        )
+    }
+
+    fn chunk_rows(chunk: &StreamChunk) -> Vec<(Op, Vec<Option<i64>>)> {
+        chunk
+            .rows()
+            .map(|row| {
+                let values = (0..3)
+                    .map(|i| row.value_at(i).map(|datum| datum.into_int64()))
+                    .collect_vec();
+                (row.op(), values)
+            })
+            .collect_vec()
+    }
+
+    #[tokio::test]
+    async fn test_group_top_n_executor() {
+        let keyspace = create_in_memory_keyspace();
+        let chunk1 = StreamChunk::new(
+            vec![Op::Insert; 6],
+            vec![
+                column_nonnull! { I64Array, [1, 1, 2, 1, 2, 1] },
+                column_nonnull! { I64Array, [5, 3, 4, 4, 6, 1] },
+                column_nonnull! { I64Array, [0, 1, 2, 3, 4, 5] },
+            ],
+            None,
+        );
+        let chunk2 = StreamChunk::new(
+            vec![Op::Delete, Op::Delete, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 2] },
+                column_nonnull! { I64Array, [1, 4, 5] },
+                column_nonnull! { I64Array, [5, 2, 6] },
+            ],
+            None,
+        );
+        let mut top_n_executor = create_executor(
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk1),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(chunk2),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+            keyspace.clone(),
+        )
+        .execute();
+
+        // consume the init barrier
+        top_n_executor.next().await.unwrap().unwrap();
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                chunk_rows(&res),
+                vec![
+                    (Op::Insert, vec![Some(1), Some(5), Some(0)]),
+                    (Op::Insert, vec![Some(1), Some(3), Some(1)]),
+                    (Op::Insert, vec![Some(2), Some(4), Some(2)]),
+                    (Op::Delete, vec![Some(1), Some(5), Some(0)]),
+                    (Op::Insert, vec![Some(1), Some(4), Some(3)]),
+                    (Op::Insert, vec![Some(2), Some(6), Some(4)]),
+                    (Op::Delete, vec![Some(1), Some(4), Some(3)]),
+                    (Op::Insert, vec![Some(1), Some(1), Some(5)]),
+                ]
+            );
+        }
+        // group 1: (1, 3) | (4, 5), group 2: (4, 6) |
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                chunk_rows(&res),
+                vec![
+                    (Op::Delete, vec![Some(1), Some(1), Some(5)]),
+                    (Op::Insert, vec![Some(1), Some(4), Some(3)]),
+                    (Op::Delete, vec![Some(2), Some(4), Some(2)]),
+                    (Op::Insert, vec![Some(2), Some(5), Some(6)]),
+                ]
+            );
+        }
+        // group 1: (3, 4) | (5), group 2: (5, 6) |
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+
+        // A new executor on the same keyspace loads the groups from the storage.
+        let chunk3 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [1, 2] },
+                column_nonnull! { I64Array, [2, 5] },
+                column_nonnull! { I64Array, [7, 6] },
+            ],
+            None,
+        );
+        let mut top_n_executor = create_executor(
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(4)),
+                Message::Chunk(chunk3),
+                Message::Barrier(Barrier::new_test_barrier(5)),
+            ],
+            keyspace,
+        )
+        .execute();
+
+        top_n_executor.next().await.unwrap().unwrap();
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_matches!(res, Message::Chunk(_));
+        if let Message::Chunk(res) = res {
+            assert_eq!(
+                chunk_rows(&res),
+                vec![
+                    (Op::Delete, vec![Some(1), Some(4), Some(3)]),
+                    (Op::Insert, vec![Some(1), Some(2), Some(7)]),
+                    (Op::Delete, vec![Some(2), Some(5), Some(6)]),
+                ]
+            );
+        }
+    }
+}
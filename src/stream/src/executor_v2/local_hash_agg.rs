@@ -0,0 +1,330 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use itertools::Itertools;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::Result;
+use risingwave_common::hash::HashKey;
+
+use super::{Executor, ExecutorInfo, StreamExecutorResult};
+use crate::executor::{
+    agg_input_array_refs, create_streaming_agg_state, AggCall, PkIndicesRef, StreamingAggStateImpl,
+};
+use crate::executor_v2::agg::{generate_agg_schema, AggExecutor, AggExecutorWrapper};
+use crate::executor_v2::error::StreamExecutorError;
+use crate::executor_v2::PkIndices;
+
+/// [`LocalHashAggExecutor`] is the first phase of a two-phase hash aggregation. It works as
+/// follows:
+///
+/// * The executor keeps the partial aggregation states of every group key seen in this epoch in
+///   memory, without touching the state store.
+/// * Upon a barrier is received, the executor emits one `Insert` row for each group, which consists
+///   of the group key and the partial results, and then clears all the states.
+///
+/// The partial results are deltas of this epoch, so they can be negative for retractions. They
+/// must be merged by a global [`crate::executor_v2::HashAggExecutor`] after shuffling by the group
+/// key, and only decomposable aggregations (e.g. `count` and `sum`) can be computed in this way.
+pub type LocalHashAggExecutor<K> = AggExecutorWrapper<AggLocalHashAggExecutor<K>>;
+
+impl<K: HashKey> LocalHashAggExecutor<K> {
+    pub fn new(
+        input: Box<dyn Executor>,
+        agg_calls: Vec<AggCall>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        key_indices: Vec<usize>,
+    ) -> Result<Self> {
+        let info = input.info();
+        let schema = generate_agg_schema(input.as_ref(), &agg_calls, Some(&key_indices));
+
+        Ok(AggExecutorWrapper {
+            input,
+            inner: AggLocalHashAggExecutor::new(
+                info,
+                agg_calls,
+                pk_indices,
+                schema,
+                executor_id,
+                key_indices,
+            ),
+        })
+    }
+}
+
+pub struct AggLocalHashAggExecutor<K: HashKey> {
+    info: ExecutorInfo,
+
+    /// Schema of the executor.
+    schema: Schema,
+
+    /// Primary key indices.
+    pk_indices: PkIndices,
+
+    /// Partial aggregation states of the groups updated in this epoch.
+    states: HashMap<K, Vec<Box<dyn StreamingAggStateImpl>>>,
+
+    /// An operator will support multiple aggregation calls.
+    agg_calls: Vec<AggCall>,
+
+    /// Indices of the columns on which key distribution depends.
+    key_indices: Vec<usize>,
+}
+
+impl<K: HashKey> AggLocalHashAggExecutor<K> {
+    pub fn new(
+        input_info: ExecutorInfo,
+        agg_calls: Vec<AggCall>,
+        pk_indices: PkIndices,
+        schema: Schema,
+        executor_id: u64,
+        key_indices: Vec<usize>,
+    ) -> Self {
+        Self {
+            info: ExecutorInfo {
+                schema: input_info.schema,
+                pk_indices: input_info.pk_indices,
+                identity: format!("LocalHashAggExecutor {:X}", executor_id),
+            },
+            schema,
+            pk_indices,
+            states: HashMap::new(),
+            agg_calls,
+            key_indices,
+        }
+    }
+}
+
+/// Create empty partial aggregation states for a new group.
+fn create_agg_states(agg_calls: &[AggCall]) -> Result<Vec<Box<dyn StreamingAggStateImpl>>> {
+    agg_calls
+        .iter()
+        .map(|agg_call| {
+            create_streaming_agg_state(
+                agg_call.args.arg_types(),
+                &agg_call.kind,
+                &agg_call.return_type,
+                None,
+            )
+        })
+        .try_collect()
+}
+
+#[async_trait]
+impl<K: HashKey> AggExecutor for AggLocalHashAggExecutor<K> {
+    async fn apply_chunk(&mut self, chunk: StreamChunk, _epoch: u64) -> StreamExecutorResult<()> {
+        let (data_chunk, ops) = chunk.into_parts();
+        let keys =
+            K::build(&self.key_indices, &data_chunk).map_err(StreamExecutorError::eval_error)?;
+        let (columns, visibility) = data_chunk.into_parts();
+        let total_num_rows = keys.len();
+
+        // Each group key corresponds to a visibility map that shadows all the rows of other keys
+        // and those already shadowed in the input.
+        let mut key_to_vis_maps = HashMap::new();
+        for (row_idx, key) in keys.into_iter().enumerate() {
+            if let Some(vis_map) = &visibility && !vis_map.is_set(row_idx).map_err(StreamExecutorError::eval_error)? {
+                continue;
+            }
+            key_to_vis_maps
+                .entry(key)
+                .or_insert_with(|| vec![false; total_num_rows])[row_idx] = true;
+        }
+
+        let all_agg_input_arrays = agg_input_array_refs(&self.agg_calls, &columns);
+        for (key, vis_map) in key_to_vis_maps {
+            let vis_map = Bitmap::try_from(vis_map).map_err(StreamExecutorError::eval_error)?;
+            let states = match self.states.entry(key) {
+                std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                std::collections::hash_map::Entry::Vacant(entry) => entry.insert(
+                    create_agg_states(&self.agg_calls)
+                        .map_err(StreamExecutorError::agg_state_error)?,
+                ),
+            };
+            states
+                .iter_mut()
+                .zip_eq(all_agg_input_arrays.iter())
+                .try_for_each(|(state, data)| state.apply_batch(&ops, Some(&vis_map), data))
+                .map_err(StreamExecutorError::agg_state_error)?;
+        }
+
+        Ok(())
+    }
+
+    async fn flush_data(&mut self, _epoch: u64) -> StreamExecutorResult<Option<StreamChunk>> {
+        if self.states.is_empty() {
+            return Ok(None);
+        }
+
+        let group_cnt = self.states.len();
+        let mut builders = self
+            .schema
+            .create_array_builders(group_cnt)
+            .map_err(StreamExecutorError::eval_error)?;
+        let (key_builders, agg_builders) = builders.split_at_mut(self.key_indices.len());
+
+        for (key, states) in self.states.drain() {
+            key.deserialize_to_builders(key_builders)
+                .map_err(StreamExecutorError::eval_error)?;
+            states
+                .iter()
+                .zip_eq(agg_builders.iter_mut())
+                .try_for_each(|(state, builder)| -> Result<_> {
+                    builder.append_datum(&state.get_output()?)
+                })
+                .map_err(StreamExecutorError::agg_state_error)?;
+        }
+
+        let columns: Vec<Column> = builders
+            .into_iter()
+            .map(|builder| -> Result<_> { Ok(Column::new(Arc::new(builder.finish()?))) })
+            .try_collect()
+            .map_err(StreamExecutorError::eval_error)?;
+        let ops = vec![Op::Insert; group_cnt];
+        Ok(Some(StreamChunk::new(ops, columns, None)))
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.info.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use itertools::Itertools;
+    use risingwave_common::array::{I64Array, Op, Row, StreamChunk};
+    use risingwave_common::catalog::schema_test_utils;
+    use risingwave_common::column_nonnull;
+    use risingwave_common::error::Result;
+    use risingwave_common::hash::Key64;
+    use risingwave_common::types::DataType;
+    use risingwave_expr::expr::AggKind;
+
+    use crate::executor::{AggArgs, AggCall, Message};
+    use crate::executor_v2::test_utils::MockSource;
+    use crate::executor_v2::{Executor, LocalHashAggExecutor};
+    use crate::row_nonnull;
+
+    #[tokio::test]
+    async fn test_local_hash_agg() -> Result<()> {
+        let chunk1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 2] },
+                column_nonnull! { I64Array, [10, 20, 30] },
+                // primary key column
+                column_nonnull! { I64Array, [1001, 1002, 1003] },
+            ],
+            None,
+        );
+        let chunk2 = StreamChunk::new(
+            vec![Op::Delete, Op::Delete, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 2] },
+                column_nonnull! { I64Array, [10, 20, 40] },
+                // primary key column
+                column_nonnull! { I64Array, [1001, 1002, 1004] },
+            ],
+            Some((vec![false, true, true]).try_into().unwrap()),
+        );
+        let schema = schema_test_utils::iii();
+
+        let mut source = MockSource::new(schema, vec![2]);
+        source.push_barrier(1, false);
+        source.push_chunks([chunk1].into_iter());
+        source.push_barrier(2, false);
+        source.push_chunks([chunk2].into_iter());
+        source.push_barrier(3, false);
+        source.push_barrier(4, false);
+
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+            },
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+            },
+        ];
+
+        let hash_agg = Box::new(LocalHashAggExecutor::<Key64>::new(
+            Box::new(source),
+            agg_calls,
+            vec![],
+            1,
+            vec![0],
+        )?);
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+
+        let mut expected_rows_per_epoch = vec![
+            vec![
+                (Op::Insert, row_nonnull![1_i64, 1_i64, 10_i64]),
+                (Op::Insert, row_nonnull![2_i64, 2_i64, 50_i64]),
+            ],
+            // Only the partial results of this epoch are emitted.
+            vec![(Op::Insert, row_nonnull![2_i64, 0_i64, 20_i64])],
+        ]
+        .into_iter();
+
+        for expected_rows in &mut expected_rows_per_epoch {
+            let msg = hash_agg.next().await.unwrap().unwrap();
+            if let Message::Chunk(chunk) = msg {
+                let (data_chunk, ops) = chunk.into_parts();
+                let rows = ops
+                    .into_iter()
+                    .zip_eq(data_chunk.rows().map(Row::from))
+                    .sorted()
+                    .collect_vec();
+                assert_eq!(rows, expected_rows);
+            } else {
+                unreachable!("unexpected message {:?}", msg);
+            }
+            assert_matches!(
+                hash_agg.next().await.unwrap().unwrap(),
+                Message::Barrier { .. }
+            );
+        }
+
+        // No chunk is emitted for an epoch without any data.
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        Ok(())
+    }
+}
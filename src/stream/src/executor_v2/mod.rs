@@ -27,10 +27,14 @@ mod agg;
 mod batch_query;
 #[allow(dead_code)]
 mod chain;
+mod dedup_appendonly;
+mod distinct;
 mod filter;
 mod global_simple_agg;
+mod group_top_n;
 mod hash_agg;
 mod hop_window;
+mod local_hash_agg;
 mod local_simple_agg;
 mod lookup;
 pub mod merge;
@@ -47,10 +51,14 @@ mod top_n_executor;
 mod v1_compat;
 
 pub use batch_query::BatchQueryExecutor;
+pub use dedup_appendonly::AppendOnlyDedupExecutor;
+pub use distinct::DistinctExecutor;
 pub use filter::FilterExecutor;
 pub use global_simple_agg::SimpleAggExecutor;
+pub use group_top_n::GroupTopNExecutor;
 pub use hash_agg::HashAggExecutor;
 pub use hop_window::HopWindowExecutor;
+pub use local_hash_agg::LocalHashAggExecutor;
 pub use local_simple_agg::LocalSimpleAggExecutor;
 pub use lookup::*;
 pub use merge::MergeExecutor;
@@ -30,12 +30,14 @@ use super::error::{StreamExecutorError, TracedStreamExecutorError};
 use super::filter::SimpleFilterExecutor;
 use super::project::SimpleProjectExecutor;
 use super::{
-    BatchQueryExecutor, BoxedExecutor, ChainExecutor, Executor, ExecutorInfo, FilterExecutor,
-    HashAggExecutor, LocalSimpleAggExecutor, MaterializeExecutor, ProjectExecutor,
+    AppendOnlyDedupExecutor, BatchQueryExecutor, BoxedExecutor, ChainExecutor, DistinctExecutor,
+    Executor, ExecutorInfo, FilterExecutor, HashAggExecutor, LocalHashAggExecutor,
+    LocalSimpleAggExecutor, MaterializeExecutor, ProjectExecutor,
 };
 pub use super::{BoxedMessageStream, ExecutorV1, Message, PkIndices, PkIndicesRef};
 use crate::executor::AggCall;
 use crate::executor_v2::global_simple_agg::SimpleAggExecutor;
+use crate::executor_v2::group_top_n::GroupTopNExecutor;
 use crate::executor_v2::top_n::TopNExecutor;
 use crate::executor_v2::top_n_appendonly::AppendOnlyTopNExecutor;
 use crate::task::FinishCreateMviewNotifier;
@@ -236,6 +238,20 @@ impl LocalSimpleAggExecutor {
     }
 }
 
+impl<K: HashKey> LocalHashAggExecutor<K> {
+    pub fn new_from_v1(
+        input: Box<dyn ExecutorV1>,
+        agg_calls: Vec<AggCall>,
+        key_indices: Vec<usize>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        _op_info: String,
+    ) -> Result<Self> {
+        let input = Box::new(ExecutorV1AsV2(input));
+        Self::new(input, agg_calls, pk_indices, executor_id, key_indices)
+    }
+}
+
 impl<S: StateStore> SimpleAggExecutor<S> {
     pub fn new_from_v1(
         input: Box<dyn ExecutorV1>,
@@ -327,6 +343,65 @@ impl<S: StateStore> TopNExecutor<S> {
     }
 }
 
+impl<S: StateStore> DistinctExecutor<S> {
+    pub fn new_from_v1(
+        input: Box<dyn ExecutorV1>,
+        distinct_keys: Vec<usize>,
+        keyspace: Keyspace<S>,
+        cache_size: usize,
+        executor_id: u64,
+        _op_info: String,
+    ) -> Self {
+        let input = Box::new(ExecutorV1AsV2(input));
+        Self::new(input, distinct_keys, keyspace, cache_size, executor_id)
+    }
+}
+
+impl<S: StateStore> AppendOnlyDedupExecutor<S> {
+    pub fn new_from_v1(
+        input: Box<dyn ExecutorV1>,
+        dedup_keys: Vec<usize>,
+        keyspace: Keyspace<S>,
+        cache_size: usize,
+        executor_id: u64,
+        _op_info: String,
+    ) -> Self {
+        let input = Box::new(ExecutorV1AsV2(input));
+        Self::new(input, dedup_keys, keyspace, cache_size, executor_id)
+    }
+}
+
+impl<S: StateStore> GroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_from_v1(
+        input: Box<dyn ExecutorV1>,
+        pk_order_types: Vec<OrderType>,
+        limit: usize,
+        pk_indices: PkIndices,
+        keyspace: Keyspace<S>,
+        cache_size: Option<usize>,
+        group_cache_size: usize,
+        executor_id: u64,
+        _op_info: String,
+        group_keys: Vec<usize>,
+        key_indices: Vec<usize>,
+    ) -> Result<Self> {
+        let input = Box::new(ExecutorV1AsV2(input));
+        Self::new(
+            input,
+            pk_order_types,
+            limit,
+            pk_indices,
+            keyspace,
+            cache_size,
+            group_cache_size,
+            executor_id,
+            group_keys,
+            key_indices,
+        )
+    }
+}
+
 impl<S: StateStore> AppendOnlyTopNExecutor<S> {
     #[allow(clippy::too_many_arguments)]
     pub fn new_from_v1(
@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
-use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -302,6 +301,16 @@ impl LocalStreamManager {
     pub fn state_store(&self) -> StateStoreImpl {
         self.core.lock().state_store.clone()
     }
+
+    /// Returns the runtime statistics of the actors running on this node, ordered by actor id.
+    pub fn get_actor_stats(&self) -> Vec<stream_service::ActorStats> {
+        let core = self.core.lock();
+        core.handles
+            .keys()
+            .sorted()
+            .map(|actor_id| core.actor_stats(*actor_id))
+            .collect()
+    }
 }
 
 pub fn build_agg_call_from_prost(agg_call_proto: &expr::AggCall) -> Result<AggCall> {
@@ -322,7 +331,7 @@ pub fn build_agg_call_from_prost(agg_call_proto: &expr::AggCall) -> Result<AggCa
         }
     };
     Ok(AggCall {
-        kind: AggKind::try_from(agg_call_proto.get_type()?)?,
+        kind: AggKind::from_protobuf(agg_call_proto)?,
         args,
         return_type: DataType::from(agg_call_proto.get_return_type()?),
     })
@@ -432,6 +441,7 @@ impl LocalStreamManagerCore {
                     )),
                     actor_id,
                     self.context.clone(),
+                    self.streaming_metrics.clone(),
                 ))
             }
             Broadcast => Box::new(DispatchExecutor::new(
@@ -439,6 +449,7 @@ impl LocalStreamManagerCore {
                 DispatcherImpl::Broadcast(BroadcastDispatcher::new(outputs)),
                 actor_id,
                 self.context.clone(),
+                self.streaming_metrics.clone(),
             )),
             Simple | NoShuffle => {
                 assert_eq!(outputs.len(), 1);
@@ -448,6 +459,7 @@ impl LocalStreamManagerCore {
                     DispatcherImpl::Simple(SimpleDispatcher::new(output)),
                     actor_id,
                     self.context.clone(),
+                    self.streaming_metrics.clone(),
                 ))
             }
             Invalid => unreachable!(),
@@ -684,6 +696,21 @@ impl LocalStreamManagerCore {
         Ok(())
     }
 
+    fn actor_stats(&self, actor_id: ActorId) -> stream_service::ActorStats {
+        let label = actor_id.to_string();
+        let labels = [label.as_str()];
+        let metrics = &self.streaming_metrics;
+        stream_service::ActorStats {
+            actor_id,
+            row_count: metrics.actor_row_count.with_label_values(&labels).get(),
+            output_blocking_duration_ns: metrics
+                .actor_output_buffer_blocking_duration_ns
+                .with_label_values(&labels)
+                .get(),
+            latest_epoch: metrics.actor_latest_epoch.with_label_values(&labels).get() as u64,
+        }
+    }
+
     pub fn take_all_handles(&mut self) -> Result<HashMap<ActorId, ActorHandle>> {
         Ok(std::mem::take(&mut self.handles))
     }
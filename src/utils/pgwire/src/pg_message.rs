@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::io::{Error, ErrorKind, IoSlice, Result, Write};
 
 use byteorder::{BigEndian, ByteOrder};
@@ -32,7 +33,11 @@ pub enum FeMessage {
     Terminate,
 }
 
-pub struct FeStartupMessage {}
+/// The startup message, which carries the parameters of the connection, e.g. `user` and
+/// `database`.
+pub struct FeStartupMessage {
+    pub config: HashMap<String, String>,
+}
 
 /// Query message contains the string sql.
 pub struct FeQueryMessage {
@@ -73,6 +78,21 @@ impl FeMessage {
 }
 
 impl FeStartupMessage {
+    /// The parameters are a list of null-terminated names and values, terminated by an extra null.
+    fn parse_config(payload: &[u8]) -> HashMap<String, String> {
+        let mut strings = payload
+            .split(|&b| b == 0)
+            .map(|s| String::from_utf8_lossy(s).into_owned());
+        let mut config = HashMap::new();
+        while let (Some(name), Some(value)) = (strings.next(), strings.next()) {
+            if name.is_empty() {
+                break;
+            }
+            config.insert(name, value);
+        }
+        config
+    }
+
     /// Read startup message from the stream.
     pub async fn read(stream: &mut (impl AsyncRead + Unpin)) -> Result<FeMessage> {
         let len = stream.read_i32().await?;
@@ -84,7 +104,9 @@ impl FeStartupMessage {
         }
         match protocol_num {
             // code from: https://www.postgresql.org/docs/current/protocol-message-formats.html
-            196608 => Ok(FeMessage::Startup(FeStartupMessage {})),
+            196608 => Ok(FeMessage::Startup(FeStartupMessage {
+                config: Self::parse_config(&payload),
+            })),
             80877103 => Ok(FeMessage::Ssl),
             // Cancel request code.
             80877102 => Ok(FeMessage::CancelQuery),
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{Error as IoError, Result};
+use std::io::Result;
 use std::sync::Arc;
 
 use bytes::BytesMut;
@@ -26,6 +26,9 @@ use crate::pg_message::{
 use crate::pg_response::PgResponse;
 use crate::pg_server::{Session, SessionManager};
 
+/// The database to connect to if the client doesn't request one in the startup message.
+const DEFAULT_DATABASE_NAME: &str = "dev";
+
 /// The state machine for each psql connection.
 /// Read pg messages from tcp stream and write results back.
 pub struct PgProtocol<S>
@@ -107,9 +110,22 @@ where
         }
     }
 
-    fn process_startup_msg(&mut self, _msg: FeStartupMessage) -> Result<()> {
-        // TODO: Replace `DEFAULT_DATABASE_NAME` with true database name in `FeStartupMessage`.
-        self.session = Some(self.session_mgr.connect("dev").map_err(IoError::other)?);
+    fn process_startup_msg(&mut self, msg: FeStartupMessage) -> Result<()> {
+        let database = msg
+            .config
+            .get("database")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_DATABASE_NAME);
+        match self.session_mgr.connect(database) {
+            Ok(session) => self.session = Some(session),
+            Err(e) => {
+                // Report the error to the client, e.g. when the database doesn't exist, and close
+                // the connection.
+                self.write_message_no_flush(&BeMessage::ErrorResponse(e))?;
+                self.is_terminate = true;
+                return Ok(());
+            }
+        }
         self.write_message_no_flush(&BeMessage::AuthenticationOk)?;
         self.write_message_no_flush(&BeMessage::ParameterStatus(
             BeParameterStatusMessage::Encoding("utf8"),
@@ -32,9 +32,15 @@ pub enum StatementType {
     CREATE_TABLE,
     CREATE_MATERIALIZED_VIEW,
     CREATE_SOURCE,
+    CREATE_INDEX,
+    CREATE_DATABASE,
+    CREATE_SCHEMA,
     DESCRIBE_TABLE,
     DROP_TABLE,
     DROP_MATERIALIZED_VIEW,
+    DROP_INDEX,
+    DROP_DATABASE,
+    DROP_SCHEMA,
     DROP_STREAM,
     // Introduce ORDER_BY statement type cuz Calcite unvalidated AST has SqlKind.ORDER_BY. Note
     // that Statement Type is not designed to be one to one mapping with SqlKind.
@@ -43,6 +49,7 @@ pub enum StatementType {
     SHOW_PARAMETERS,
     SHOW_COMMAND,
     FLUSH,
+    ANALYZE,
     OTHER,
     // EMPTY is used when query statement is empty (e.g. ";").
     EMPTY,